@@ -1,27 +1,28 @@
+use crate::bar_hwnd;
 use crate::config::get_individual_spacing;
+use crate::config::AutoHideMode;
+use crate::config::BarMessage;
 use crate::config::KomobarConfig;
 use crate::config::KomobarTheme;
-use crate::config::MonitorConfigOrIndex;
+use crate::config::MouseMessage;
 use crate::config::Position;
 use crate::config::PositionConfig;
-use crate::process_hwnd;
 use crate::render::Color32Ext;
 use crate::render::Grouping;
 use crate::render::RenderConfig;
 use crate::render::RenderExt;
+use crate::repaint;
+use crate::widgets::komorebi::FocusedContainerMode;
 use crate::widgets::komorebi::Komorebi;
 use crate::widgets::komorebi::KomorebiNotificationState;
 use crate::widgets::widget::BarWidget;
+use crate::widgets::widget::ThemedWidget;
 use crate::widgets::widget::WidgetConfig;
 use crate::KomorebiEvent;
 use crate::AUTO_SELECT_FILL_COLOUR;
 use crate::AUTO_SELECT_TEXT_COLOUR;
 use crate::BAR_HEIGHT;
 use crate::DEFAULT_PADDING;
-use crate::MAX_LABEL_WIDTH;
-use crate::MONITOR_LEFT;
-use crate::MONITOR_RIGHT;
-use crate::MONITOR_TOP;
 use crossbeam_channel::Receiver;
 use crossbeam_channel::TryRecvError;
 use eframe::egui::Align;
@@ -38,11 +39,14 @@ use eframe::egui::Frame;
 use eframe::egui::Id;
 use eframe::egui::Layout;
 use eframe::egui::Margin;
+use eframe::egui::Order;
 use eframe::egui::PointerButton;
 use eframe::egui::Rgba;
 use eframe::egui::Style;
 use eframe::egui::TextStyle;
 use eframe::egui::Vec2;
+use eframe::egui::ViewportBuilder;
+use eframe::egui::ViewportId;
 use eframe::egui::Visuals;
 use font_loader::system_fonts;
 use font_loader::system_fonts::FontPropertyBuilder;
@@ -74,8 +78,9 @@ use std::process::Stdio;
 use std::rc::Rc;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Duration;
 
-const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+pub(crate) const CREATE_NO_WINDOW: u32 = 0x0800_0000;
 
 lazy_static! {
     static ref SESSION_STDIN: Mutex<Option<ChildStdin>> = Mutex::new(None);
@@ -148,8 +153,14 @@ pub fn exec_powershell(cmd: &str) -> Result<()> {
 }
 
 pub struct Komobar {
+    /// Title of the bar window, which is used to find its handle
+    pub title: String,
+    /// Whether this is the first bar of the process, whose font family and frame rate are shared
+    /// by all of the bars
+    pub primary: bool,
     pub hwnd: Option<isize>,
     pub monitor_index: Option<usize>,
+    pub monitor_size: komorebi_client::Rect,
     pub disabled: bool,
     pub config: KomobarConfig,
     pub render_config: Rc<RefCell<RenderConfig>>,
@@ -164,9 +175,15 @@ pub struct Komobar {
     pub scale_factor: f32,
     pub size_rect: komorebi_client::Rect,
     pub work_area_offset: komorebi_client::Rect,
+    /// The style of this bar, which is swapped into the shared egui context before it is drawn
+    style: Arc<Style>,
     applied_theme_on_first_frame: bool,
     mouse_follows_focus: bool,
     input_config: InputConfig,
+    bar_actions_scroll_delta: f32,
+    auto_hide: AutoHideState,
+    /// Whether all bars have been hidden with `komorebic toggle-bar`
+    hidden: bool,
 }
 
 struct InputConfig {
@@ -179,6 +196,33 @@ struct InputConfig {
     horizontal_scroll_max_threshold: f32,
 }
 
+#[derive(Default)]
+struct AutoHideState {
+    /// Whether auto-hiding has been toggled off by a bar action
+    paused: bool,
+    /// Whether the work area offset of the bar has been released
+    released: bool,
+    /// How far the bar has been moved off the screen, from 0.0 (shown) to 1.0 (hidden)
+    progress: f32,
+}
+
+/// Executes a mouse message, acting on the bar itself for `MouseMessage::Bar`
+fn execute_mouse_message(
+    message: &MouseMessage,
+    auto_hide: &mut AutoHideState,
+    mouse_follows_focus: bool,
+) {
+    match message {
+        MouseMessage::Bar(message) => match message.bar {
+            BarMessage::ToggleAutoHide => {
+                auto_hide.paused = !auto_hide.paused;
+                tracing::info!("auto-hide paused: {}", auto_hide.paused);
+            }
+        },
+        message => message.execute(mouse_follows_focus),
+    }
+}
+
 pub fn apply_theme(
     ctx: &Context,
     theme: KomobarTheme,
@@ -347,14 +391,21 @@ impl Komobar {
         ctx: &Context,
         previous_notification_state: Option<Rc<RefCell<KomorebiNotificationState>>>,
     ) {
-        MAX_LABEL_WIDTH.store(
-            self.config.max_label_width.unwrap_or(400.0) as i32,
-            Ordering::SeqCst,
-        );
+        let mut max_label_width = self.config.max_label_width.unwrap_or(400.0);
+        if self.config.is_vertical() {
+            // Labels are truncated to fit within the width of a vertical bar
+            let padding = get_individual_spacing(DEFAULT_PADDING, &self.config.padding);
+            max_label_width = max_label_width
+                .min(self.config.height.unwrap_or(BAR_HEIGHT) - padding.left - padding.right);
+        }
+
+        if self.primary {
+            repaint::MAX_FPS.store(self.config.max_fps.unwrap_or(60), Ordering::SeqCst);
 
-        if let Some(font_family) = &self.config.font_family {
-            tracing::info!("attempting to add custom font family: {font_family}");
-            Self::add_custom_font(ctx, font_family);
+            if let Some(font_family) = &self.config.font_family {
+                tracing::info!("attempting to add custom font family: {font_family}");
+                Self::add_custom_font(ctx, font_family);
+            }
         }
 
         // Update the `size_rect` so that the bar position can be changed on the EGUI update
@@ -368,32 +419,48 @@ impl Komobar {
             Self::set_font_size(ctx, *font_size);
         }
 
-        self.render_config.replace((&self.config).new_renderconfig(
-            ctx,
-            *self.bg_color.borrow(),
-            self.config.icon_scale,
-        ));
+        let double_click_delay = self.config.double_click_delay.unwrap_or(300);
+        ctx.options_mut(|options| {
+            options.input_options.max_double_click_delay = double_click_delay as f64 / 1000.0;
+        });
+
+        let mut render_config =
+            (&self.config).new_renderconfig(ctx, *self.bg_color.borrow(), self.config.icon_scale);
+
+        render_config.max_label_width = max_label_width;
+
+        self.render_config.replace(render_config);
 
         let mut komorebi_notification_state = previous_notification_state;
         let mut komorebi_widgets = Vec::new();
 
         for (idx, widget_config) in self.config.left_widgets.iter().enumerate() {
             if let WidgetConfig::Komorebi(config) = widget_config {
-                komorebi_widgets.push((Komorebi::from(config), idx, Alignment::Left));
+                komorebi_widgets.push((Komorebi::from(config), config.theme, idx, Alignment::Left));
             }
         }
 
         if let Some(center_widgets) = &self.config.center_widgets {
             for (idx, widget_config) in center_widgets.iter().enumerate() {
                 if let WidgetConfig::Komorebi(config) = widget_config {
-                    komorebi_widgets.push((Komorebi::from(config), idx, Alignment::Center));
+                    komorebi_widgets.push((
+                        Komorebi::from(config),
+                        config.theme,
+                        idx,
+                        Alignment::Center,
+                    ));
                 }
             }
         }
 
         for (idx, widget_config) in self.config.right_widgets.iter().enumerate() {
             if let WidgetConfig::Komorebi(config) = widget_config {
-                komorebi_widgets.push((Komorebi::from(config), idx, Alignment::Right));
+                komorebi_widgets.push((
+                    Komorebi::from(config),
+                    config.theme,
+                    idx,
+                    Alignment::Right,
+                ));
             }
         }
 
@@ -425,24 +492,46 @@ impl Komobar {
         if !komorebi_widgets.is_empty() {
             komorebi_widgets
                 .into_iter()
-                .for_each(|(mut widget, idx, side)| {
+                .for_each(|(mut widget, theme, idx, side)| {
                     match komorebi_notification_state {
                         None => {
                             komorebi_notification_state =
                                 Some(widget.komorebi_notification_state.clone());
                         }
                         Some(ref previous) => {
-                            if widget.workspaces.is_some_and(|w| w.enable) {
+                            if widget.workspaces.as_ref().is_some_and(|w| w.enable) {
                                 previous.borrow_mut().update_from_config(
                                     &widget.komorebi_notification_state.borrow(),
                                 );
                             }
 
+                            if let Some(taskbar) = widget.taskbar {
+                                previous.borrow_mut().show_taskbar = taskbar.enable;
+                            }
+
+                            if let Some(focused_container) = &widget.focused_container {
+                                previous.borrow_mut().show_global_focused_container =
+                                    focused_container.enable
+                                        && focused_container.mode.unwrap_or_default()
+                                            == FocusedContainerMode::Global;
+                            }
+
+                            if let Some(notifications) = widget.notifications {
+                                previous.borrow_mut().notifications =
+                                    Some(notifications).filter(|n| n.enable);
+                            }
+
                             widget.komorebi_notification_state = previous.clone();
                         }
                     }
 
-                    let boxed: Box<dyn BarWidget> = Box::new(widget);
+                    let boxed: Box<dyn BarWidget> = match theme {
+                        Some(theme) => Box::new(ThemedWidget {
+                            widget: Box::new(widget),
+                            theme,
+                        }),
+                        None => Box::new(widget),
+                    };
                     match side {
                         Alignment::Left => left_widgets[idx] = boxed,
                         Alignment::Center => center_widgets[idx] = boxed,
@@ -457,17 +546,20 @@ impl Komobar {
         self.center_widgets = center_widgets;
         self.right_widgets = right_widgets;
 
-        let (usr_monitor_index, config_work_area_offset) = match &self.config.monitor {
-            MonitorConfigOrIndex::MonitorConfig(monitor_config) => {
-                (monitor_config.index, monitor_config.work_area_offset)
-            }
-            MonitorConfigOrIndex::Index(idx) => (*idx, None),
-        };
+        let config_work_area_offset = self
+            .config
+            .monitor
+            .as_ref()
+            .and_then(|monitor| monitor.work_area_offset());
 
         let mapped_state = self.komorebi_notification_state.as_ref().map(|state| {
             let state = state.borrow();
             (
-                state.monitor_usr_idx_map.get(&usr_monitor_index).copied(),
+                match &self.config.monitor {
+                    Some(monitor) => state.monitor_usr_idx_map.get(&monitor.index()).copied(),
+                    // A detected monitor index is only updated from komorebi notifications
+                    None => self.monitor_index,
+                },
                 state.mouse_follows_focus,
             )
         });
@@ -477,14 +569,21 @@ impl Komobar {
             self.mouse_follows_focus = state.1;
         }
 
+        if let Some(monitor_index) = self.monitor_index {
+            self.render_config.borrow_mut().monitor_idx = monitor_index;
+        }
+
         if let Some(monitor_index) = self.monitor_index {
             if let (prev_rect, Some(new_rect)) = (&self.work_area_offset, &config_work_area_offset)
             {
                 if new_rect != prev_rect {
                     self.work_area_offset = *new_rect;
-                    if let Err(error) = komorebi_client::send_message(
-                        &SocketMessage::MonitorWorkAreaOffset(monitor_index, *new_rect),
-                    ) {
+                    if let Err(error) =
+                        komorebi_client::send_message(&SocketMessage::MonitorWorkAreaOffset(
+                            monitor_index,
+                            self.applied_work_area_offset(),
+                        ))
+                    {
                         tracing::error!(
                             "error applying work area offset to monitor '{}': {}",
                             monitor_index,
@@ -494,6 +593,50 @@ impl Komobar {
                         tracing::info!("work area offset applied to monitor: {}", monitor_index);
                     }
                 }
+            } else if self.config.is_vertical() {
+                let width = self.config.height.unwrap_or(BAR_HEIGHT) as i32;
+                let margin = get_individual_spacing(0.0, &self.config.margin);
+                let monitor_left = self.monitor_size.left;
+                let monitor_width = self.monitor_size.right;
+
+                // A bar in the right half of the monitor reserves work area on the right edge
+                let new_rect = if self.size_rect.left + width / 2 > monitor_left + monitor_width / 2
+                {
+                    komorebi_client::Rect {
+                        left: 0,
+                        top: 0,
+                        right: monitor_left + monitor_width - self.size_rect.left
+                            + margin.left as i32,
+                        bottom: 0,
+                    }
+                } else {
+                    // We only add the `right_margin` to the work_area_offset since the left margin
+                    // is already considered on the `size_rect.left`
+                    let offset = width + (self.size_rect.left - monitor_left) + margin.right as i32;
+
+                    komorebi_client::Rect {
+                        left: offset,
+                        top: 0,
+                        right: offset,
+                        bottom: 0,
+                    }
+                };
+
+                if new_rect != self.work_area_offset {
+                    self.work_area_offset = new_rect;
+                    if let Err(error) =
+                        komorebi_client::send_message(&SocketMessage::MonitorWorkAreaOffset(
+                            monitor_index,
+                            self.applied_work_area_offset(),
+                        ))
+                    {
+                        tracing::error!(
+                            "error applying work area offset to monitor '{monitor_index}': {error}"
+                        );
+                    } else {
+                        tracing::info!("work area offset applied to monitor: {monitor_index}",);
+                    }
+                }
             } else if let Some(height) = self.config.height.or(Some(BAR_HEIGHT)) {
                 // We only add the `bottom_margin` to the work_area_offset since the top margin is
                 // already considered on the `size_rect.top`
@@ -505,19 +648,22 @@ impl Komobar {
                 let new_rect = komorebi_client::Rect {
                     left: 0,
                     top: (height as i32)
-                        + (self.size_rect.top - MONITOR_TOP.load(Ordering::SeqCst))
+                        + (self.size_rect.top - self.monitor_size.top)
                         + bottom_margin,
                     right: 0,
                     bottom: (height as i32)
-                        + (self.size_rect.top - MONITOR_TOP.load(Ordering::SeqCst))
+                        + (self.size_rect.top - self.monitor_size.top)
                         + bottom_margin,
                 };
 
                 if new_rect != self.work_area_offset {
                     self.work_area_offset = new_rect;
-                    if let Err(error) = komorebi_client::send_message(
-                        &SocketMessage::MonitorWorkAreaOffset(monitor_index, new_rect),
-                    ) {
+                    if let Err(error) =
+                        komorebi_client::send_message(&SocketMessage::MonitorWorkAreaOffset(
+                            monitor_index,
+                            self.applied_work_area_offset(),
+                        ))
+                    {
                         tracing::error!(
                             "error applying work area offset to monitor '{monitor_index}': {error}"
                         );
@@ -571,38 +717,57 @@ impl Komobar {
 
     /// Updates the `size_rect` field. Returns a bool indicating if the field was changed or not
     fn update_size_rect(&mut self) {
+        let vertical = self.config.is_vertical();
+
+        let default_end = if vertical {
+            Position {
+                x: BAR_HEIGHT,
+                y: self.monitor_size.bottom as f32,
+            }
+        } else {
+            Position {
+                x: self.monitor_size.right as f32,
+                y: BAR_HEIGHT,
+            }
+        };
+
         let position = self.config.position.clone().unwrap_or(PositionConfig {
             start: Some(Position {
-                x: MONITOR_LEFT.load(Ordering::SeqCst) as f32,
-                y: MONITOR_TOP.load(Ordering::SeqCst) as f32,
-            }),
-            end: Some(Position {
-                x: MONITOR_RIGHT.load(Ordering::SeqCst) as f32,
-                y: BAR_HEIGHT,
+                x: self.monitor_size.left as f32,
+                y: self.monitor_size.top as f32,
             }),
+            end: Some(default_end),
         });
 
         let mut start = position.start.unwrap_or(Position {
-            x: MONITOR_LEFT.load(Ordering::SeqCst) as f32,
-            y: MONITOR_TOP.load(Ordering::SeqCst) as f32,
+            x: self.monitor_size.left as f32,
+            y: self.monitor_size.top as f32,
         });
 
-        let mut end = position.end.unwrap_or(Position {
-            x: MONITOR_RIGHT.load(Ordering::SeqCst) as f32,
-            y: BAR_HEIGHT,
-        });
+        let mut end = position.end.unwrap_or(default_end);
 
         if let Some(height) = self.config.height {
-            end.y = height;
+            if vertical {
+                end.x = height;
+            } else {
+                end.y = height;
+            }
         }
 
         let margin = get_individual_spacing(0.0, &self.config.margin);
 
         start.y += margin.top;
         start.x += margin.left;
-        end.x -= margin.left + margin.right;
 
-        if end.y == 0.0 {
+        if vertical {
+            end.y -= margin.top + margin.bottom;
+        } else {
+            end.x -= margin.left + margin.right;
+        }
+
+        if vertical && end.x == 0.0 {
+            tracing::warn!("position.end.x is set to 0.0 which will make your vertical bar invisible on a config reload - this is usually set to 50.0 by default")
+        } else if !vertical && end.y == 0.0 {
             tracing::warn!("position.end.y is set to 0.0 which will make your bar invisible on a config reload - this is usually set to 50.0 by default")
         }
 
@@ -713,15 +878,24 @@ impl Komobar {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         cc: &eframe::CreationContext<'_>,
+        title: String,
+        primary: bool,
         rx_gui: Receiver<KomorebiEvent>,
         rx_config: Receiver<KomobarConfig>,
         config: KomobarConfig,
+        monitor_index: usize,
+        monitor_size: komorebi_client::Rect,
     ) -> Self {
         let mut komobar = Self {
-            hwnd: process_hwnd(),
-            monitor_index: None,
+            hwnd: bar_hwnd(&title),
+            title,
+            primary,
+            // Without a configured monitor, start with the monitor detected on startup
+            monitor_index: config.monitor.is_none().then_some(monitor_index),
+            monitor_size,
             disabled: false,
             config,
             render_config: Rc::new(RefCell::new(RenderConfig::new())),
@@ -736,6 +910,7 @@ impl Komobar {
             scale_factor: cc.egui_ctx.native_pixels_per_point().unwrap_or(1.0),
             size_rect: komorebi_client::Rect::default(),
             work_area_offset: komorebi_client::Rect::default(),
+            style: cc.egui_ctx.style(),
             applied_theme_on_first_frame: false,
             mouse_follows_focus: false,
             input_config: InputConfig {
@@ -747,15 +922,34 @@ impl Komobar {
                 vertical_scroll_max_threshold: 0.0,
                 horizontal_scroll_max_threshold: 0.0,
             },
+            bar_actions_scroll_delta: 0.0,
+            auto_hide: AutoHideState::default(),
+            hidden: false,
         };
 
         komobar.apply_config(&cc.egui_ctx, None);
         // needs a double apply the first time for some reason
         komobar.apply_config(&cc.egui_ctx, None);
 
+        if let Some(state) = &komobar.komorebi_notification_state {
+            state.borrow_mut().monitor_index = monitor_index;
+        }
+
+        komobar.style = cc.egui_ctx.style();
+
         komobar
     }
 
+    /// The viewport of an additional bar, which is positioned by the bar itself once it has been
+    /// created
+    fn viewport_builder(&self) -> ViewportBuilder {
+        ViewportBuilder::default()
+            .with_title(&self.title)
+            .with_decorations(false)
+            .with_transparent(true)
+            .with_taskbar(false)
+    }
+
     fn set_font_size(ctx: &Context, font_size: f32) {
         ctx.style_mut(|style| {
             style.text_styles = [
@@ -841,11 +1035,190 @@ impl Komobar {
         }
     }
 
+    /// The work area offset which should currently be applied to the monitor of this bar
+    fn applied_work_area_offset(&self) -> komorebi_client::Rect {
+        if self.auto_hide.released {
+            komorebi_client::Rect::default()
+        } else {
+            self.work_area_offset
+        }
+    }
+
+    /// The displacement which moves the bar entirely off the monitor edge it is attached to
+    fn auto_hide_offset(&self) -> (i32, i32) {
+        let monitor_left = self.monitor_size.left;
+        let monitor_top = self.monitor_size.top;
+        let monitor_width = self.monitor_size.right;
+        let monitor_height = self.monitor_size.bottom;
+        let rect = &self.size_rect;
+
+        if self.config.is_vertical() {
+            if rect.left + rect.right / 2 > monitor_left + monitor_width / 2 {
+                (monitor_left + monitor_width - rect.left, 0)
+            } else {
+                (monitor_left - rect.left - rect.right, 0)
+            }
+        } else if rect.top + rect.bottom / 2 > monitor_top + monitor_height / 2 {
+            (0, monitor_top + monitor_height - rect.top)
+        } else {
+            (0, monitor_top - rect.top - rect.bottom)
+        }
+    }
+
+    /// Checks if the foreground window covers the entire monitor of this bar
+    fn has_fullscreen_window(&self) -> bool {
+        let Ok(foreground) = komorebi_client::WindowsApi::foreground_window() else {
+            return false;
+        };
+
+        if Some(foreground) == self.hwnd {
+            return false;
+        }
+
+        // The desktop covers the entire monitor when it has focus
+        if matches!(
+            komorebi_client::Window::from(foreground).class().as_deref(),
+            Ok("Progman" | "WorkerW")
+        ) {
+            return false;
+        }
+
+        komorebi_client::WindowsApi::window_rect(foreground)
+            .is_ok_and(|rect| rect == self.monitor_size)
+    }
+
+    /// Hides the bar or reveals it depending on the auto-hide configuration, the foreground window
+    /// and the position of the mouse, moving the bar off and on the screen edge step by step
+    fn update_auto_hide(&mut self, ctx: &Context) {
+        let (Some(hwnd), Some(monitor_index)) = (self.hwnd, self.monitor_index) else {
+            return;
+        };
+
+        // A bar hidden with `komorebic toggle-bar` is left alone until it is toggled back
+        if self.hidden {
+            return;
+        }
+
+        let auto_hide = self.config.auto_hide.filter(|_| !self.auto_hide.paused);
+
+        let engaged = auto_hide.is_some_and(|auto_hide| match auto_hide.mode {
+            AutoHideMode::Always => true,
+            AutoHideMode::Fullscreen => self.has_fullscreen_window(),
+        });
+
+        if engaged != self.auto_hide.released {
+            self.auto_hide.released = engaged;
+            if let Err(error) =
+                komorebi_client::send_message(&SocketMessage::MonitorWorkAreaOffset(
+                    monitor_index,
+                    self.applied_work_area_offset(),
+                ))
+            {
+                tracing::error!(
+                    "error applying work area offset to monitor '{monitor_index}': {error}"
+                );
+            }
+        }
+
+        let (offset_x, offset_y) = self.auto_hide_offset();
+
+        let revealed = komorebi_client::WindowsApi::cursor_pos().is_ok_and(|cursor| {
+            let reveal_distance = auto_hide
+                .and_then(|auto_hide| auto_hide.reveal_distance)
+                .unwrap_or(2);
+            let monitor_left = self.monitor_size.left;
+            let monitor_top = self.monitor_size.top;
+            let monitor_width = self.monitor_size.right;
+            let monitor_height = self.monitor_size.bottom;
+
+            let on_monitor = cursor.x >= monitor_left
+                && cursor.x < monitor_left + monitor_width
+                && cursor.y >= monitor_top
+                && cursor.y < monitor_top + monitor_height;
+
+            let at_edge = match (offset_x.signum(), offset_y.signum()) {
+                (-1, _) => cursor.x < monitor_left + reveal_distance,
+                (1, _) => cursor.x >= monitor_left + monitor_width - reveal_distance,
+                (_, -1) => cursor.y < monitor_top + reveal_distance,
+                _ => cursor.y >= monitor_top + monitor_height - reveal_distance,
+            };
+
+            // Once revealed, the bar stays visible for as long as the mouse is over it
+            let over_bar = self.auto_hide.progress < 1.0
+                && self.size_rect.contains_point((cursor.x, cursor.y));
+
+            on_monitor && (at_edge || over_bar)
+        });
+
+        let target = if engaged && !revealed { 1.0 } else { 0.0 };
+
+        if self.auto_hide.progress == target {
+            if auto_hide.is_some() {
+                // Keep polling the foreground window and the mouse position
+                ctx.request_repaint_after(Duration::from_millis(100));
+            }
+
+            return;
+        }
+
+        if self.auto_hide.progress == 1.0 {
+            komorebi_client::WindowsApi::restore_window(hwnd);
+        }
+
+        let duration = auto_hide
+            .and_then(|auto_hide| auto_hide.animation_duration)
+            .unwrap_or(200);
+        let step = if duration == 0 {
+            1.0
+        } else {
+            ctx.input(|i| i.stable_dt).min(0.1) * 1000.0 / duration as f32
+        };
+
+        self.auto_hide.progress = if target > self.auto_hide.progress {
+            (self.auto_hide.progress + step).min(target)
+        } else {
+            (self.auto_hide.progress - step).max(target)
+        };
+
+        let rect = komorebi_client::Rect {
+            left: self.size_rect.left + (offset_x as f32 * self.auto_hide.progress) as i32,
+            top: self.size_rect.top + (offset_y as f32 * self.auto_hide.progress) as i32,
+            ..self.size_rect
+        };
+
+        if let Err(error) = komorebi_client::Window::from(hwnd).set_position(&rect, false) {
+            tracing::error!("{error}");
+        }
+
+        if self.auto_hide.progress == 1.0 {
+            komorebi_client::WindowsApi::hide_window(hwnd);
+        }
+
+        ctx.request_repaint();
+    }
+
+    /// Resolves the komorebi monitor index of this bar from the configured monitor index, or from
+    /// the monitor the bar is displayed on if no monitor has been configured
+    fn resolve_monitor_index(&self, state: &komorebi_client::State) -> Option<usize> {
+        match &self.config.monitor {
+            Some(monitor) => state.monitor_usr_idx_map.get(&monitor.index()).copied(),
+            None => self
+                .hwnd
+                .and_then(|hwnd| {
+                    let hmonitor = komorebi_client::WindowsApi::monitor_from_window(hwnd);
+                    state
+                        .monitors
+                        .elements()
+                        .iter()
+                        .position(|monitor| monitor.id() == hmonitor)
+                })
+                .or(self.monitor_index),
+        }
+    }
+
     fn update_monitor_coordinates(&mut self, monitor_size: &komorebi_client::Rect) {
         // Store the new monitor coordinates
-        MONITOR_TOP.store(monitor_size.top, Ordering::SeqCst);
-        MONITOR_LEFT.store(monitor_size.left, Ordering::SeqCst);
-        MONITOR_RIGHT.store(monitor_size.right, Ordering::SeqCst);
+        self.monitor_size = *monitor_size;
 
         // Since the `config.position` is changed on `main.rs` we need to update it here.
         // If the user had set up some `start` position, that will be overriden here
@@ -858,15 +1231,53 @@ impl Komobar {
         }
     }
 }
-impl eframe::App for Komobar {
+
+/// All of the bars run by this process. The first bar is drawn in the root viewport and each
+/// additional bar is drawn in an immediate viewport of its own, so that the bars share one event
+/// loop and one egui context
+pub struct Komobars {
+    pub bars: Vec<Komobar>,
+}
+
+impl eframe::App for Komobars {
     // Needed for transparency
     fn clear_color(&self, _visuals: &Visuals) -> [f32; 4] {
         Rgba::TRANSPARENT.to_array()
     }
 
     fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
+        let Some((primary, additional)) = self.bars.split_first_mut() else {
+            return;
+        };
+
+        // The style of an egui context is shared by all of its viewports, so every bar draws
+        // with its own style and keeps whatever changes were made to it while drawing
+        if additional.is_empty() {
+            primary.update(ctx);
+        } else {
+            ctx.set_style(primary.style.clone());
+            primary.update(ctx);
+            primary.style = ctx.style();
+        }
+
+        for bar in additional {
+            ctx.show_viewport_immediate(
+                ViewportId::from_hash_of(&bar.title),
+                bar.viewport_builder(),
+                |ctx, _class| {
+                    ctx.set_style(bar.style.clone());
+                    bar.update(ctx);
+                    bar.style = ctx.style();
+                },
+            );
+        }
+    }
+}
+
+impl Komobar {
+    fn update(&mut self, ctx: &Context) {
         if self.hwnd.is_none() {
-            self.hwnd = process_hwnd();
+            self.hwnd = bar_hwnd(&self.title);
         }
 
         if self.scale_factor != ctx.native_pixels_per_point().unwrap_or(1.0) {
@@ -874,12 +1285,28 @@ impl eframe::App for Komobar {
             self.apply_config(ctx, self.komorebi_notification_state.clone());
         }
 
-        if let Ok(updated_config) = self.rx_config.try_recv() {
+        if let Ok(mut updated_config) = self.rx_config.try_recv() {
+            if let Some(monitor_index) = self.monitor_index {
+                let monitor_usr_idx_map = self
+                    .komorebi_notification_state
+                    .as_ref()
+                    .map(|state| state.borrow().monitor_usr_idx_map.clone())
+                    .unwrap_or_default();
+
+                updated_config.apply_monitor_overrides(monitor_index, &monitor_usr_idx_map);
+            }
+
             self.config = updated_config;
             self.apply_config(ctx, self.komorebi_notification_state.clone());
         }
 
-        match self.rx_gui.try_recv() {
+        let event = self.rx_gui.try_recv();
+        if event.is_ok() {
+            // keep drawing frames until all of the queued events have been handled
+            repaint::mark_dirty();
+        }
+
+        match event {
             Err(error) => match error {
                 TryRecvError::Empty => {}
                 TryRecvError::Disconnected => {
@@ -890,12 +1317,8 @@ impl eframe::App for Komobar {
             },
             Ok(KomorebiEvent::Notification(notification)) => {
                 let state = &notification.state;
-                let usr_monitor_index = match &self.config.monitor {
-                    MonitorConfigOrIndex::MonitorConfig(monitor_config) => monitor_config.index,
-                    MonitorConfigOrIndex::Index(idx) => *idx,
-                };
-                let monitor_index = state.monitor_usr_idx_map.get(&usr_monitor_index).copied();
-                self.monitor_index = monitor_index;
+                let previous_monitor_index = self.monitor_index;
+                self.monitor_index = self.resolve_monitor_index(state);
                 let mut should_apply_config = false;
 
                 match notification.event {
@@ -905,7 +1328,10 @@ impl eframe::App for Komobar {
                         tracing::debug!(
                             "back on komorebi's associated virtual desktop - restoring bar"
                         );
-                        if let Some(hwnd) = self.hwnd {
+                        if let Some(hwnd) = self
+                            .hwnd
+                            .filter(|_| self.auto_hide.progress < 1.0 && !self.hidden)
+                        {
                             komorebi_client::WindowsApi::restore_window(hwnd);
                         }
                     }
@@ -922,6 +1348,21 @@ impl eframe::App for Komobar {
                     _ => {}
                 }
 
+                // komorebi releases the work area offsets of all bars itself while they are
+                // hidden, so there is nothing to send back here
+                if state.bars_hidden != self.hidden {
+                    self.hidden = state.bars_hidden;
+                    if let Some(hwnd) = self.hwnd {
+                        if self.hidden {
+                            tracing::info!("hiding bar");
+                            komorebi_client::WindowsApi::hide_window(hwnd);
+                        } else {
+                            tracing::info!("showing bar");
+                            komorebi_client::WindowsApi::restore_window(hwnd);
+                        }
+                    }
+                }
+
                 if self.monitor_index.is_none()
                     || self
                         .monitor_index
@@ -960,10 +1401,17 @@ impl eframe::App for Komobar {
                     self.disabled = false;
                 }
 
-                if matches!(
-                    notification.event,
-                    NotificationEvent::Monitor(MonitorNotification::DisplayConnectionChange)
-                ) {
+                // A bar without a configured monitor has been moved to a different monitor
+                let moved_monitor = self.config.monitor.is_none()
+                    && previous_monitor_index.is_some()
+                    && previous_monitor_index != self.monitor_index;
+
+                if moved_monitor
+                    || matches!(
+                        notification.event,
+                        NotificationEvent::Monitor(MonitorNotification::DisplayConnectionChange)
+                    )
+                {
                     let monitor_index = self.monitor_index.expect("should have a monitor index");
 
                     let monitor_size = state.monitors.elements()[monitor_index].size();
@@ -980,16 +1428,7 @@ impl eframe::App for Komobar {
                 // Check if monitor coordinates/size has changed
                 if let Some(monitor_index) = self.monitor_index {
                     let monitor_size = state.monitors.elements()[monitor_index].size();
-                    let top = MONITOR_TOP.load(Ordering::SeqCst);
-                    let left = MONITOR_LEFT.load(Ordering::SeqCst);
-                    let right = MONITOR_RIGHT.load(Ordering::SeqCst);
-                    let rect = komorebi_client::Rect {
-                        top,
-                        left,
-                        bottom: monitor_size.bottom,
-                        right,
-                    };
-                    if *monitor_size != rect {
+                    if *monitor_size != self.monitor_size {
                         tracing::info!(
                             "Monitor coordinates/size has changed, storing new coordinates: {:#?}",
                             monitor_size
@@ -1007,7 +1446,7 @@ impl eframe::App for Komobar {
                         .handle_notification(
                             ctx,
                             self.monitor_index,
-                            notification,
+                            &notification,
                             self.bg_color.clone(),
                             self.bg_color_with_alpha.clone(),
                             self.config.transparency_alpha,
@@ -1024,11 +1463,23 @@ impl eframe::App for Komobar {
                     self.position_bar();
                 }
             }
+            Ok(KomorebiEvent::Disconnect) => {
+                if let Some(state) = &self.komorebi_notification_state {
+                    state.borrow_mut().disconnected = true;
+                }
+            }
             Ok(KomorebiEvent::Reconnect) => {
+                if let Some(state) = &self.komorebi_notification_state {
+                    state.borrow_mut().disconnected = false;
+                }
+
                 if let Some(monitor_index) = self.monitor_index {
-                    if let Err(error) = komorebi_client::send_message(
-                        &SocketMessage::MonitorWorkAreaOffset(monitor_index, self.work_area_offset),
-                    ) {
+                    if let Err(error) =
+                        komorebi_client::send_message(&SocketMessage::MonitorWorkAreaOffset(
+                            monitor_index,
+                            self.applied_work_area_offset(),
+                        ))
+                    {
                         tracing::error!(
                             "error applying work area offset to monitor '{}': {}",
                             monitor_index,
@@ -1062,11 +1513,14 @@ impl eframe::App for Komobar {
                 bottom: ((current_rect.max.y - current_rect.min.y) * self.scale_factor) as i32,
             };
 
-            if self.size_rect != current_rect {
+            // The bar is moved away from its `size_rect` while it is being auto-hidden
+            if self.size_rect != current_rect && self.auto_hide.progress == 0.0 {
                 self.position_bar();
             }
         }
 
+        self.update_auto_hide(ctx);
+
         let frame = match &self.config.padding {
             None => {
                 if let Some(frame) = &self.config.frame {
@@ -1201,7 +1655,61 @@ impl eframe::App for Komobar {
                 };
 
                 if let Some(command) = command {
-                    command.execute(self.mouse_follows_focus);
+                    execute_mouse_message(command, &mut self.auto_hide, self.mouse_follows_focus);
+                }
+            }
+
+            if let Some(bar_actions) = &self.config.bar_actions {
+                // Widgets are drawn in their own areas on top of the central panel, so the pointer
+                // is only over the empty bar background when no other layer is hit at its position
+                let over_background = ctx.pointer_hover_pos().is_some_and(|pos| {
+                    ctx.layer_id_at(pos)
+                        .is_none_or(|layer| layer.order == Order::Background)
+                });
+
+                let action = if !over_background {
+                    self.bar_actions_scroll_delta = 0.0;
+                    &None
+                } else if ui.input(|i| i.pointer.button_double_clicked(PointerButton::Primary)) {
+                    tracing::debug!("Bar action: primary button double clicked");
+                    &bar_actions.on_primary_double_click
+                } else if ui.input(|i| i.pointer.button_clicked(PointerButton::Secondary)) {
+                    tracing::debug!("Bar action: secondary button clicked");
+                    &bar_actions.on_secondary_click
+                } else if ui.input(|i| i.pointer.button_clicked(PointerButton::Middle)) {
+                    tracing::debug!("Bar action: middle button clicked");
+                    &bar_actions.on_middle_click
+                } else {
+                    let threshold = bar_actions
+                        .scroll_threshold
+                        .unwrap_or(30.0)
+                        .clamp(10.0, 300.0);
+
+                    // Do not store more than three ticks worth of scrolling
+                    self.bar_actions_scroll_delta = (self.bar_actions_scroll_delta
+                        + ui.input(|i| i.smooth_scroll_delta.y))
+                    .clamp(-threshold * 3.0, threshold * 3.0);
+
+                    if self.bar_actions_scroll_delta.abs() >= threshold {
+                        let direction_action = if self.bar_actions_scroll_delta > 0.0 {
+                            &bar_actions.on_scroll_up
+                        } else {
+                            &bar_actions.on_scroll_down
+                        };
+
+                        self.bar_actions_scroll_delta -=
+                            threshold * self.bar_actions_scroll_delta.signum();
+
+                        tracing::debug!("Bar action: scroll ticked");
+
+                        direction_action
+                    } else {
+                        &None
+                    }
+                };
+
+                if let Some(action) = action {
+                    execute_mouse_message(action, &mut self.auto_hide, self.mouse_follows_focus);
                 }
             }
 
@@ -1216,15 +1724,34 @@ impl eframe::App for Komobar {
                     .outer_margin(Margin::same(0))
             };
 
-            let available_height = ui.max_rect().max.y;
-            ctx.style_mut(|style| {
-                style.spacing.interact_size.y = available_height;
-            });
+            let vertical = self.config.is_vertical();
+
+            // Widgets on a vertical bar keep their natural height instead of filling the bar
+            if !vertical {
+                let available_height = ui.max_rect().max.y;
+                ctx.style_mut(|style| {
+                    style.spacing.interact_size.y = available_height;
+                });
+            }
+
+            // Lays out the widgets of a panel along the bar
+            let panel_layout = if vertical {
+                Layout::top_down(Align::Center)
+            } else {
+                Layout::left_to_right(Align::Center)
+            };
 
             if !self.left_widgets.is_empty() {
-                // Left-aligned widgets layout
+                // Left-aligned widgets layout, or top-aligned on a vertical bar
                 Area::new(Id::new("left_panel"))
-                    .anchor(Align2::LEFT_CENTER, [0.0, 0.0]) // Align in the left center of the window
+                    .anchor(
+                        if vertical {
+                            Align2::CENTER_TOP
+                        } else {
+                            Align2::LEFT_CENTER
+                        },
+                        [0.0, 0.0],
+                    )
                     .show(ctx, |ui| {
                         let mut left_area_frame = area_frame;
                         if let Some(padding) = self
@@ -1236,14 +1763,20 @@ impl eframe::App for Komobar {
                             left_area_frame.inner_margin.left = padding.left as i8;
                             left_area_frame.inner_margin.top = padding.top as i8;
                             left_area_frame.inner_margin.bottom = padding.bottom as i8;
+                            if vertical {
+                                left_area_frame.inner_margin.right = padding.right as i8;
+                            }
                         } else if let Some(frame) = &self.config.frame {
                             left_area_frame.inner_margin.left = frame.inner_margin.x as i8;
                             left_area_frame.inner_margin.top = frame.inner_margin.y as i8;
                             left_area_frame.inner_margin.bottom = frame.inner_margin.y as i8;
+                            if vertical {
+                                left_area_frame.inner_margin.right = frame.inner_margin.x as i8;
+                            }
                         }
 
                         left_area_frame.show(ui, |ui| {
-                            ui.horizontal(|ui| {
+                            ui.with_layout(panel_layout, |ui| {
                                 let mut render_conf = render_config.clone();
                                 render_conf.alignment = Some(Alignment::Left);
 
@@ -1258,9 +1791,16 @@ impl eframe::App for Komobar {
             }
 
             if !self.right_widgets.is_empty() {
-                // Right-aligned widgets layout
+                // Right-aligned widgets layout, or bottom-aligned on a vertical bar
                 Area::new(Id::new("right_panel"))
-                    .anchor(Align2::RIGHT_CENTER, [0.0, 0.0]) // Align in the right center of the window
+                    .anchor(
+                        if vertical {
+                            Align2::CENTER_BOTTOM
+                        } else {
+                            Align2::RIGHT_CENTER
+                        },
+                        [0.0, 0.0],
+                    )
                     .show(ctx, |ui| {
                         let mut right_area_frame = area_frame;
                         if let Some(padding) = self
@@ -1272,31 +1812,46 @@ impl eframe::App for Komobar {
                             right_area_frame.inner_margin.right = padding.right as i8;
                             right_area_frame.inner_margin.top = padding.top as i8;
                             right_area_frame.inner_margin.bottom = padding.bottom as i8;
+                            if vertical {
+                                right_area_frame.inner_margin.left = padding.left as i8;
+                            }
                         } else if let Some(frame) = &self.config.frame {
                             right_area_frame.inner_margin.right = frame.inner_margin.x as i8;
                             right_area_frame.inner_margin.top = frame.inner_margin.y as i8;
                             right_area_frame.inner_margin.bottom = frame.inner_margin.y as i8;
+                            if vertical {
+                                right_area_frame.inner_margin.left = frame.inner_margin.x as i8;
+                            }
                         }
 
                         right_area_frame.show(ui, |ui| {
-                            let initial_size = Vec2 {
-                                x: ui.available_size_before_wrap().x,
-                                y: ui.spacing().interact_size.y,
+                            let (initial_size, layout) = if vertical {
+                                (
+                                    Vec2 {
+                                        x: ui.spacing().interact_size.x,
+                                        y: ui.available_size_before_wrap().y,
+                                    },
+                                    Layout::bottom_up(Align::Center),
+                                )
+                            } else {
+                                (
+                                    Vec2 {
+                                        x: ui.available_size_before_wrap().x,
+                                        y: ui.spacing().interact_size.y,
+                                    },
+                                    Layout::right_to_left(Align::Center),
+                                )
                             };
-                            ui.allocate_ui_with_layout(
-                                initial_size,
-                                Layout::right_to_left(Align::Center),
-                                |ui| {
-                                    let mut render_conf = render_config.clone();
-                                    render_conf.alignment = Some(Alignment::Right);
-
-                                    render_config.apply_on_alignment(ui, |ui| {
-                                        for w in &mut self.right_widgets {
-                                            w.render(ctx, ui, &mut render_conf);
-                                        }
-                                    });
-                                },
-                            );
+                            ui.allocate_ui_with_layout(initial_size, layout, |ui| {
+                                let mut render_conf = render_config.clone();
+                                render_conf.alignment = Some(Alignment::Right);
+
+                                render_config.apply_on_alignment(ui, |ui| {
+                                    for w in &mut self.right_widgets {
+                                        w.render(ctx, ui, &mut render_conf);
+                                    }
+                                });
+                            });
                         });
                     });
             }
@@ -1313,15 +1868,25 @@ impl eframe::App for Komobar {
                             .as_ref()
                             .map(|s| s.to_individual(DEFAULT_PADDING))
                         {
-                            center_area_frame.inner_margin.top = padding.top as i8;
-                            center_area_frame.inner_margin.bottom = padding.bottom as i8;
+                            if vertical {
+                                center_area_frame.inner_margin.left = padding.left as i8;
+                                center_area_frame.inner_margin.right = padding.right as i8;
+                            } else {
+                                center_area_frame.inner_margin.top = padding.top as i8;
+                                center_area_frame.inner_margin.bottom = padding.bottom as i8;
+                            }
                         } else if let Some(frame) = &self.config.frame {
-                            center_area_frame.inner_margin.top = frame.inner_margin.y as i8;
-                            center_area_frame.inner_margin.bottom = frame.inner_margin.y as i8;
+                            if vertical {
+                                center_area_frame.inner_margin.left = frame.inner_margin.x as i8;
+                                center_area_frame.inner_margin.right = frame.inner_margin.x as i8;
+                            } else {
+                                center_area_frame.inner_margin.top = frame.inner_margin.y as i8;
+                                center_area_frame.inner_margin.bottom = frame.inner_margin.y as i8;
+                            }
                         }
 
                         center_area_frame.show(ui, |ui| {
-                            ui.horizontal(|ui| {
+                            ui.with_layout(panel_layout, |ui| {
                                 let mut render_conf = render_config.clone();
                                 render_conf.alignment = Some(Alignment::Center);
 
@@ -1335,6 +1900,19 @@ impl eframe::App for Komobar {
                     });
             }
         });
+
+        let next_widget_refresh = repaint::next_widget_refresh(
+            self.left_widgets
+                .iter()
+                .chain(&self.center_widgets)
+                .chain(&self.right_widgets),
+        );
+        let idle_interval = match self.config.idle_repaint_interval.unwrap_or(10) {
+            0 => None,
+            seconds => Some(Duration::from_secs(seconds)),
+        };
+
+        repaint::schedule_next_frame(ctx, next_widget_refresh, idle_interval);
     }
 }
 
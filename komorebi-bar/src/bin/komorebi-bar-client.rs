@@ -0,0 +1,60 @@
+// the listening half of this module is only used by komorebi-bar
+#[allow(dead_code)]
+#[path = "../ipc.rs"]
+mod ipc;
+
+use crate::ipc::BarMessage;
+use crate::ipc::CustomOutput;
+use clap::Parser;
+use std::io::Read;
+
+#[derive(Parser)]
+#[clap(author, about, version)]
+/// Push content to the widgets of running komorebi-bar instances
+enum SubCommand {
+    /// Replace the content of the custom widgets with the given id
+    Custom(Custom),
+}
+
+#[derive(Parser)]
+struct Custom {
+    /// Id of the custom widgets to update, as set by their `id` option
+    id: String,
+    /// Plain text, or a JSON object with the optional fields `text`, `icon`, `color`, `tooltip`
+    /// and `on_click`. Use "-" to read the content from stdin
+    output: String,
+}
+
+fn main() -> color_eyre::Result<()> {
+    color_eyre::install()?;
+
+    // bars which could not be reached are reported on stderr
+    tracing::subscriber::set_global_default(
+        tracing_subscriber::fmt::Subscriber::builder()
+            .with_writer(std::io::stderr)
+            .finish(),
+    )?;
+
+    match SubCommand::parse() {
+        SubCommand::Custom(arg) => {
+            let output = if arg.output == "-" {
+                let mut buffer = String::new();
+                std::io::stdin().read_to_string(&mut buffer)?;
+                buffer
+            } else {
+                arg.output
+            };
+
+            let delivered = ipc::send(&BarMessage::UpdateCustom {
+                id: arg.id,
+                output: CustomOutput::parse(&output),
+            })?;
+
+            if delivered == 0 {
+                color_eyre::eyre::bail!("there are no running bars to push content to");
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -2,6 +2,9 @@ use crate::bar::exec_powershell;
 use crate::render::Grouping;
 use crate::widgets::widget::WidgetConfig;
 use crate::DEFAULT_PADDING;
+use chrono::Datelike;
+use chrono::NaiveTime;
+use chrono::Timelike;
 use eframe::egui::Pos2;
 use eframe::egui::TextBuffer;
 use eframe::egui::Vec2;
@@ -18,7 +21,7 @@ use std::path::PathBuf;
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 /// The `komorebi.bar.json` configuration file reference for `v0.1.38`
 pub struct KomobarConfig {
-    /// Bar height (default: 50)
+    /// Bar height, or bar width when the orientation is vertical (default: 50)
     pub height: Option<f32>,
     /// Bar padding. Use one value for all sides or use a grouped padding for horizontal and/or
     /// vertical definition which can each take a single value for a symmetric padding or two
@@ -73,10 +76,25 @@ pub struct KomobarConfig {
     /// Bar positioning options
     #[serde(alias = "viewport")]
     pub position: Option<PositionConfig>,
+    /// Bar orientation. A vertical bar stacks its widgets top-to-bottom and reserves work area on
+    /// the left or right edge of the monitor, depending on its position (default: Horizontal)
+    pub orientation: Option<Orientation>,
+    /// Automatically hide the bar and release its work area offset, revealing it again when the
+    /// mouse touches the edge of the screen the bar is attached to
+    pub auto_hide: Option<AutoHideConfig>,
     /// Frame options (see: https://docs.rs/egui/latest/egui/containers/frame/struct.Frame.html)
     pub frame: Option<FrameConfig>,
-    /// The monitor index or the full monitor options
-    pub monitor: MonitorConfigOrIndex,
+    /// The monitor index or the full monitor options. If this is not set, the bar will detect
+    /// the monitor it is displayed on
+    pub monitor: Option<MonitorConfigOrIndex>,
+    /// Per-monitor overrides, applied to this configuration when the bar is displayed on the
+    /// monitor with the matching index. This allows a single configuration file to be shared by
+    /// the bars on monitors with different resolutions or scaling factors
+    pub monitors: Option<Vec<MonitorOverrideConfig>>,
+    /// Additional bars to run in the same process as this bar, such as a bottom bar alongside a
+    /// top bar or one bar per monitor. Each entry is a complete bar configuration. All of the bars
+    /// share the connection to komorebi, the icon cache and the font family of this bar
+    pub bars: Option<Vec<KomobarConfig>>,
     /// Font family
     pub font_family: Option<String>,
     /// Font size (default: 12.5)
@@ -95,11 +113,23 @@ pub struct KomobarConfig {
     pub grouping: Option<Grouping>,
     /// Options for mouse interaction on the bar
     pub mouse: Option<MouseConfig>,
-    /// Left side widgets (ordered left-to-right)
+    /// Actions to perform when clicking or scrolling on the empty bar background
+    pub bar_actions: Option<BarActionsConfig>,
+    /// Maximum time in milliseconds between two clicks for them to count as a double click, on
+    /// both widgets and the bar background. Single clicks are always acted on immediately
+    /// (default: 300)
+    pub double_click_delay: Option<u64>,
+    /// Maximum number of frames drawn per second, 0 for uncapped (default: 60)
+    pub max_fps: Option<u32>,
+    /// Interval in seconds at which the bar is repainted when nothing has changed, on top of the
+    /// repaints requested by komorebi notifications, polled widgets and the mouse hovering over
+    /// the bar. Set to 0 to never repaint an idle bar (default: 10)
+    pub idle_repaint_interval: Option<u64>,
+    /// Left side widgets (ordered left-to-right), or top widgets on a vertical bar
     pub left_widgets: Vec<WidgetConfig>,
     /// Center widgets (ordered left-to-right)
     pub center_widgets: Option<Vec<WidgetConfig>>,
-    /// Right side widgets (ordered left-to-right)
+    /// Right side widgets (ordered left-to-right), or bottom widgets on a vertical bar
     pub right_widgets: Vec<WidgetConfig>,
 }
 
@@ -131,16 +161,50 @@ impl KomobarConfig {
         }
     }
 
+    pub fn is_vertical(&self) -> bool {
+        matches!(self.orientation, Some(Orientation::Vertical))
+    }
+
     pub fn show_all_icons_on_komorebi_workspace(widgets: &[WidgetConfig]) -> bool {
         widgets
             .iter()
-            .any(|w| matches!(w, WidgetConfig::Komorebi(config) if config.workspaces.is_some_and(|w| w.enable && w.display.is_some_and(|s| matches!(s,
+            .any(|w| matches!(w, WidgetConfig::Komorebi(config) if config.workspaces.as_ref().is_some_and(|w| w.enable && w.display.is_some_and(|s| matches!(s,
             WorkspacesDisplayFormat::AllIcons
             | WorkspacesDisplayFormat::AllIconsAndText
             | WorkspacesDisplayFormat::AllIconsAndTextOnSelected)))))
     }
 }
 
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum Orientation {
+    /// The bar spans the width of the monitor
+    #[default]
+    Horizontal,
+    /// The bar spans the height of the monitor
+    Vertical,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct AutoHideConfig {
+    /// When the bar should be hidden
+    pub mode: AutoHideMode,
+    /// Duration in milliseconds of the animation when hiding or revealing the bar (default: 200)
+    pub animation_duration: Option<u64>,
+    /// Distance in pixels from the screen edge at which the mouse reveals the bar (default: 2)
+    pub reveal_distance: Option<i32>,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum AutoHideMode {
+    /// Hide the bar while a window on its monitor is fullscreen
+    Fullscreen,
+    /// Always hide the bar
+    Always,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PositionConfig {
@@ -178,6 +242,41 @@ pub struct MonitorConfig {
     pub work_area_offset: Option<Rect>,
 }
 
+impl MonitorConfigOrIndex {
+    pub fn index(&self) -> usize {
+        match self {
+            MonitorConfigOrIndex::MonitorConfig(monitor_config) => monitor_config.index,
+            MonitorConfigOrIndex::Index(idx) => *idx,
+        }
+    }
+
+    pub fn work_area_offset(&self) -> Option<Rect> {
+        match self {
+            MonitorConfigOrIndex::MonitorConfig(monitor_config) => monitor_config.work_area_offset,
+            MonitorConfigOrIndex::Index(_) => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct MonitorOverrideConfig {
+    /// Monitor index to which these overrides apply, as used by the `monitor` option
+    pub index: usize,
+    /// Bar height, or bar width when the orientation is vertical
+    pub height: Option<f32>,
+    /// Font size
+    pub font_size: Option<f32>,
+    /// Theme
+    pub theme: Option<KomobarTheme>,
+    /// Left side widgets (ordered left-to-right), or top widgets on a vertical bar
+    pub left_widgets: Option<Vec<WidgetConfig>>,
+    /// Center widgets (ordered left-to-right)
+    pub center_widgets: Option<Vec<WidgetConfig>>,
+    /// Right side widgets (ordered left-to-right), or bottom widgets on a vertical bar
+    pub right_widgets: Option<Vec<WidgetConfig>>,
+}
+
 pub type Padding = SpacingKind;
 pub type Margin = SpacingKind;
 
@@ -369,12 +468,35 @@ pub enum MouseMessage {
     /// }
     /// ```
     Komorebi(KomorebiMouseMessage),
+    /// Perform an action on the bar itself.
+    ///
+    /// Example:
+    /// ```json
+    /// "on_primary_double_click": {
+    ///   "bar": "ToggleAutoHide"
+    /// }
+    /// ```
+    Bar(BarMouseMessage),
     /// Execute a custom command.
     /// CMD (%variable%), Bash ($variable) and PowerShell ($Env:variable) variables will be resolved.
     /// Example: `komorebic toggle-pause`
     Command(String),
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct BarMouseMessage {
+    /// The action to perform on the bar
+    pub bar: BarMessage,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum BarMessage {
+    /// Toggle the auto-hiding of the bar, if it has been configured
+    ToggleAutoHide,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct KomorebiMouseMessage {
@@ -433,6 +555,23 @@ impl MouseConfig {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct BarActionsConfig {
+    /// Action to perform on primary/left double button click on the bar background
+    pub on_primary_double_click: Option<MouseMessage>,
+    /// Action to perform on secondary/right button click on the bar background
+    pub on_secondary_click: Option<MouseMessage>,
+    /// Action to perform on middle button click on the bar background
+    pub on_middle_click: Option<MouseMessage>,
+    /// Action to perform on scrolling up on the bar background (every tick)
+    pub on_scroll_up: Option<MouseMessage>,
+    /// Action to perform on scrolling down on the bar background (every tick)
+    pub on_scroll_down: Option<MouseMessage>,
+    /// Defines how many points a user needs to scroll to make a "tick" on a mouse/touchpad/touchscreen (default: 30)
+    pub scroll_threshold: Option<f32>,
+}
+
 impl MouseMessage {
     pub fn execute(&self, mouse_follows_focus: bool) {
         match self {
@@ -457,6 +596,8 @@ impl MouseMessage {
                     tracing::error!("could not send commands");
                 }
             }
+            // Handled by the bar itself
+            MouseMessage::Bar(_) => {}
             MouseMessage::Command(cmd) => {
                 tracing::debug!("Executing command: {}", cmd);
 
@@ -478,16 +619,85 @@ impl KomobarConfig {
             _ => panic!("unsupported format"),
         };
 
-        if value.frame.is_none() {
-            value.frame = Some(FrameConfig {
+        value.apply_default_frame();
+
+        if let Some(bars) = &mut value.bars {
+            bars.iter_mut().for_each(Self::apply_default_frame);
+        }
+
+        Ok(value)
+    }
+
+    fn apply_default_frame(&mut self) {
+        if self.frame.is_none() {
+            self.frame = Some(FrameConfig {
                 inner_margin: Position {
                     x: DEFAULT_PADDING,
                     y: DEFAULT_PADDING,
                 },
             });
         }
+    }
 
-        Ok(value)
+    /// Splits this configuration into the configurations of all of the bars it defines, starting
+    /// with this bar. Additional bars cannot define further bars of their own
+    pub fn into_bars(mut self) -> Vec<Self> {
+        let additional_bars = self.bars.take().unwrap_or_default();
+
+        std::iter::once(self)
+            .chain(additional_bars.into_iter().map(|mut bar| {
+                if bar.bars.take().is_some() {
+                    tracing::warn!("ignoring the bars defined by an additional bar");
+                }
+
+                bar
+            }))
+            .collect()
+    }
+
+    /// Applies the entry of `monitors` matching the komorebi `monitor_index` of the bar, if any.
+    /// Override indices are resolved through `monitor_usr_idx_map` like the `monitor` option.
+    pub fn apply_monitor_overrides(
+        &mut self,
+        monitor_index: usize,
+        monitor_usr_idx_map: &HashMap<usize, usize>,
+    ) {
+        let Some(monitor_override) = self.monitors.as_ref().and_then(|monitors| {
+            monitors
+                .iter()
+                .find(|m| {
+                    monitor_usr_idx_map.get(&m.index).map_or(m.index, |i| *i) == monitor_index
+                })
+                .cloned()
+        }) else {
+            return;
+        };
+
+        tracing::info!("applying configuration overrides for monitor: {monitor_index}");
+
+        if monitor_override.height.is_some() {
+            self.height = monitor_override.height;
+        }
+
+        if monitor_override.font_size.is_some() {
+            self.font_size = monitor_override.font_size;
+        }
+
+        if monitor_override.theme.is_some() {
+            self.theme = monitor_override.theme;
+        }
+
+        if let Some(left_widgets) = monitor_override.left_widgets {
+            self.left_widgets = left_widgets;
+        }
+
+        if monitor_override.center_widgets.is_some() {
+            self.center_widgets = monitor_override.center_widgets;
+        }
+
+        if let Some(right_widgets) = monitor_override.right_widgets {
+            self.right_widgets = right_widgets;
+        }
     }
 }
 
@@ -594,6 +804,89 @@ pub enum LabelPrefix {
     IconAndText,
 }
 
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct FormatCondition {
+    /// Only match on these days of the week
+    pub weekdays: Option<Vec<Weekday>>,
+    /// Only match on these days of the month (1-31)
+    pub days_of_month: Option<Vec<u32>>,
+    /// Only match from this time of day onwards (format: HH:MM)
+    pub start_time: Option<String>,
+    /// Only match before this time of day (format: HH:MM)
+    pub end_time: Option<String>,
+}
+
+impl FormatCondition {
+    /// Returns true if every criterion of this condition that has been set matches `now`
+    pub fn matches(&self, now: &(impl Datelike + Timelike)) -> bool {
+        if let Some(weekdays) = &self.weekdays {
+            if !weekdays
+                .iter()
+                .any(|weekday| chrono::Weekday::from(*weekday) == now.weekday())
+            {
+                return false;
+            }
+        }
+
+        if let Some(days_of_month) = &self.days_of_month {
+            if !days_of_month.contains(&now.day()) {
+                return false;
+            }
+        }
+
+        let time =
+            NaiveTime::from_hms_opt(now.hour(), now.minute(), now.second()).unwrap_or_default();
+        let parse = |time: &Option<String>| {
+            time.as_ref()
+                .and_then(|time| NaiveTime::parse_from_str(time, "%H:%M").ok())
+        };
+
+        match (parse(&self.start_time), parse(&self.end_time)) {
+            // a range such as 22:00-06:00 wraps around midnight
+            (Some(start), Some(end)) if start > end => time >= start || time < end,
+            (start, end) => {
+                start.is_none_or(|start| time >= start) && end.is_none_or(|end| time < end)
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl From<Weekday> for chrono::Weekday {
+    fn from(value: Weekday) -> Self {
+        match value {
+            Weekday::Monday => chrono::Weekday::Mon,
+            Weekday::Tuesday => chrono::Weekday::Tue,
+            Weekday::Wednesday => chrono::Weekday::Wed,
+            Weekday::Thursday => chrono::Weekday::Thu,
+            Weekday::Friday => chrono::Weekday::Fri,
+            Weekday::Saturday => chrono::Weekday::Sat,
+            Weekday::Sunday => chrono::Weekday::Sun,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum DateTimeLocale {
+    /// Use the locale selected in the Windows region settings
+    System,
+    /// Use a named locale, e.g. "de_DE" or "ja-JP"
+    Named(String),
+}
+
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum DisplayFormat {
@@ -695,4 +988,89 @@ mod tests {
 
         assert!(serde_json::from_str::<ExampleConfig>(&raw).is_err())
     }
+
+    #[test]
+    pub fn format_condition_matches() {
+        use super::FormatCondition;
+        use super::Weekday;
+        use chrono::NaiveDate;
+
+        // 2024-09-09 was a Monday
+        let monday_morning = NaiveDate::from_ymd_opt(2024, 9, 9)
+            .and_then(|date| date.and_hms_opt(9, 30, 0))
+            .unwrap();
+        let monday_night = NaiveDate::from_ymd_opt(2024, 9, 9)
+            .and_then(|date| date.and_hms_opt(23, 0, 0))
+            .unwrap();
+
+        assert!(FormatCondition::default().matches(&monday_morning));
+
+        let mondays = FormatCondition {
+            weekdays: Some(vec![Weekday::Monday]),
+            ..Default::default()
+        };
+        assert!(mondays.matches(&monday_morning));
+
+        let weekends = FormatCondition {
+            weekdays: Some(vec![Weekday::Saturday, Weekday::Sunday]),
+            ..Default::default()
+        };
+        assert!(!weekends.matches(&monday_morning));
+
+        let ninth = FormatCondition {
+            days_of_month: Some(vec![9]),
+            ..Default::default()
+        };
+        assert!(ninth.matches(&monday_morning));
+
+        let working_hours = FormatCondition {
+            start_time: Some(String::from("09:00")),
+            end_time: Some(String::from("17:00")),
+            ..Default::default()
+        };
+        assert!(working_hours.matches(&monday_morning));
+        assert!(!working_hours.matches(&monday_night));
+
+        let overnight = FormatCondition {
+            start_time: Some(String::from("22:00")),
+            end_time: Some(String::from("06:00")),
+            ..Default::default()
+        };
+        assert!(!overnight.matches(&monday_morning));
+        assert!(overnight.matches(&monday_night));
+    }
+
+    #[test]
+    pub fn split_additional_bars() {
+        use super::KomobarConfig;
+
+        let raw = json!({
+            "left_widgets": [],
+            "right_widgets": [],
+            "bars": [
+                {
+                    "height": 30.0,
+                    "left_widgets": [],
+                    "right_widgets": [],
+                    "bars": [
+                        {
+                            "left_widgets": [],
+                            "right_widgets": [],
+                        }
+                    ]
+                }
+            ]
+        })
+        .to_string();
+
+        let bars = serde_json::from_str::<KomobarConfig>(&raw)
+            .unwrap()
+            .into_bars();
+
+        assert_eq!(bars.len(), 2);
+        assert!(bars[0].bars.is_none());
+        assert_eq!(bars[1].height, Some(30.0));
+        // additional bars cannot define further bars
+        assert!(bars[1].bars.is_none());
+    }
 }
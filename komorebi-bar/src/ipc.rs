@@ -0,0 +1,194 @@
+//! Messages which external programs can push to running bars. This module is shared with the
+//! `komorebi-bar-client` binary, so it must not depend on the rest of the bar
+
+use komorebi_client::UnixListener;
+use komorebi_client::UnixStream;
+use komorebi_client::DATA_DIR;
+use serde::Deserialize;
+use serde::Serialize;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const SOCKET_PREFIX: &str = "komorebi-bar-ipc-";
+const SOCKET_EXTENSION: &str = "sock";
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", content = "content")]
+pub enum BarMessage {
+    /// Replaces the content of the custom widgets with a matching id
+    UpdateCustom { id: String, output: CustomOutput },
+}
+
+/// The content of a custom widget, either parsed from JSON or with plain text as `text`
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct CustomOutput {
+    #[serde(default)]
+    pub text: String,
+    pub icon: Option<String>,
+    pub color: Option<String>,
+    pub tooltip: Option<String>,
+    pub on_click: Option<String>,
+}
+
+impl CustomOutput {
+    pub fn parse(stdout: &str) -> Self {
+        let stdout = stdout.trim();
+
+        if stdout.starts_with('{') {
+            if let Ok(output) = serde_json::from_str::<CustomOutput>(stdout) {
+                return output;
+            }
+        }
+
+        Self {
+            text: stdout.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Every running bar listens on its own socket, named after its process id
+fn socket_path(pid: u32) -> PathBuf {
+    DATA_DIR.join(format!("{SOCKET_PREFIX}{pid}.{SOCKET_EXTENSION}"))
+}
+
+/// Listens for messages pushed to this bar, handing each of them to `on_message`
+pub fn listen(on_message: impl Fn(BarMessage) + Send + 'static) -> std::io::Result<()> {
+    let socket = socket_path(std::process::id());
+
+    match std::fs::remove_file(&socket) {
+        Ok(()) => {}
+        Err(error) => match error.kind() {
+            ErrorKind::NotFound => {}
+            _ => {
+                return Err(error);
+            }
+        },
+    };
+
+    let listener = UnixListener::bind(&socket)?;
+
+    std::thread::spawn(move || {
+        for client in listener.incoming() {
+            let mut stream = match client {
+                Ok(stream) => stream,
+                Err(error) => {
+                    tracing::error!("{error}");
+                    continue;
+                }
+            };
+
+            if let Err(error) = stream.set_read_timeout(Some(Duration::from_secs(1))) {
+                tracing::error!("{error}");
+            }
+
+            let mut buffer = String::new();
+            if let Err(error) = stream.read_to_string(&mut buffer) {
+                tracing::error!("could not read bar message: {error}");
+                continue;
+            }
+
+            match serde_json::from_str::<BarMessage>(&buffer) {
+                Ok(message) => on_message(message),
+                Err(error) => tracing::error!("could not deserialize bar message: {error}"),
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Sends a message to every running bar, returning the number of bars which received it
+pub fn send(message: &BarMessage) -> std::io::Result<usize> {
+    let message = serde_json::to_string(message)?;
+    let mut delivered = 0;
+
+    for entry in std::fs::read_dir(&*DATA_DIR)?.flatten() {
+        let path = entry.path();
+        let is_bar_socket = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with(SOCKET_PREFIX))
+            && path
+                .extension()
+                .is_some_and(|extension| extension == SOCKET_EXTENSION);
+
+        if !is_bar_socket {
+            continue;
+        }
+
+        // a bar which can't be reached doesn't stop the message from reaching the others
+        match UnixStream::connect(&path) {
+            Ok(mut stream) => {
+                let written = stream
+                    .set_write_timeout(Some(Duration::from_secs(1)))
+                    .and_then(|()| stream.write_all(message.as_bytes()));
+
+                match written {
+                    Ok(()) => delivered += 1,
+                    Err(error) => {
+                        tracing::error!("could not send message to {}: {error}", path.display());
+                    }
+                }
+            }
+            // the socket was left behind by a bar which is no longer running
+            Err(error) if error.kind() == ErrorKind::ConnectionRefused => {
+                let _ = std::fs::remove_file(&path);
+            }
+            Err(error) => {
+                tracing::error!("could not connect to {}: {error}", path.display());
+            }
+        }
+    }
+
+    Ok(delivered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_custom_output() {
+        assert_eq!(
+            CustomOutput::parse("hello world\r\n"),
+            CustomOutput {
+                text: String::from("hello world"),
+                ..Default::default()
+            }
+        );
+
+        assert_eq!(
+            CustomOutput::parse(r##"{"text": "42%", "color": "#ff0000", "tooltip": "volume"}"##),
+            CustomOutput {
+                text: String::from("42%"),
+                color: Some(String::from("#ff0000")),
+                tooltip: Some(String::from("volume")),
+                ..Default::default()
+            }
+        );
+
+        // malformed JSON is displayed as it is
+        assert_eq!(
+            CustomOutput::parse("{not json"),
+            CustomOutput {
+                text: String::from("{not json"),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_bar_message_round_trip() {
+        let message = BarMessage::UpdateCustom {
+            id: String::from("volume"),
+            output: CustomOutput::parse("42%"),
+        };
+
+        let json = serde_json::to_string(&message).unwrap();
+        assert_eq!(serde_json::from_str::<BarMessage>(&json).unwrap(), message);
+    }
+}
@@ -0,0 +1,50 @@
+use crate::config::DateTimeLocale;
+use chrono::DateTime;
+use chrono::Locale;
+use chrono::TimeZone;
+use std::fmt::Display;
+use windows::Win32::Globalization::GetUserDefaultLocaleName;
+use windows::Win32::Globalization::LOCALE_NAME_MAX_LENGTH;
+
+/// Name of the locale selected in the Windows region settings, e.g. "en-GB"
+fn system_locale_name() -> Option<String> {
+    let mut buffer = [0; LOCALE_NAME_MAX_LENGTH as usize];
+    let char_count = unsafe { GetUserDefaultLocaleName(&mut buffer) };
+
+    match char_count {
+        0 => None,
+        // the count includes the null terminator
+        _ => String::from_utf16(&buffer[..char_count as usize - 1]).ok(),
+    }
+}
+
+/// Parses a locale name in either the Windows ("de-DE") or POSIX ("de_DE") notation
+fn parse(name: &str) -> Option<Locale> {
+    Locale::try_from(name.replace('-', "_").as_str()).ok()
+}
+
+/// Resolves the configured locale, returning `None` if the locale is unknown so that callers
+/// can fall back to chrono's unlocalized formatting
+pub fn resolve(locale: &DateTimeLocale) -> Option<Locale> {
+    let resolved = match locale {
+        DateTimeLocale::System => system_locale_name().as_deref().and_then(parse),
+        DateTimeLocale::Named(name) => parse(name),
+    };
+
+    if resolved.is_none() {
+        tracing::warn!("unknown locale {locale:?}, falling back to the default formatting");
+    }
+
+    resolved
+}
+
+/// Formats `dt` with month and day names in `locale`, or in English if there is no locale
+pub fn format<Tz: TimeZone>(dt: &DateTime<Tz>, fmt: &str, locale: Option<Locale>) -> String
+where
+    Tz::Offset: Display,
+{
+    match locale {
+        Some(locale) => dt.format_localized(fmt, locale).to_string(),
+        None => dt.format(fmt).to_string(),
+    }
+}
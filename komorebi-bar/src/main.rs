@@ -1,16 +1,23 @@
 mod bar;
 mod config;
+// the sending half of this module is only used by komorebi-bar-client
+#[allow(dead_code)]
+mod ipc;
+mod locale;
 mod render;
+mod repaint;
 mod selected_frame;
 mod ui;
 mod widgets;
 
 use crate::bar::Komobar;
+use crate::bar::Komobars;
 use crate::config::KomobarConfig;
 use crate::config::Position;
 use crate::config::PositionConfig;
 use clap::Parser;
-use config::MonitorConfigOrIndex;
+use crossbeam_channel::Receiver;
+use crossbeam_channel::Sender;
 use eframe::egui::ViewportBuilder;
 use font_loader::system_fonts;
 use hotwatch::EventKind;
@@ -22,33 +29,40 @@ use komorebi_client::SubscribeOptions;
 use std::io::BufReader;
 use std::io::Read;
 use std::path::PathBuf;
-use std::sync::atomic::AtomicI32;
 use std::sync::atomic::AtomicU32;
-use std::sync::atomic::AtomicUsize;
-use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::LazyLock;
 use std::time::Duration;
 use tracing_subscriber::EnvFilter;
 use windows::Win32::Foundation::HWND;
 use windows::Win32::Foundation::LPARAM;
-use windows::Win32::System::Threading::GetCurrentProcessId;
 use windows::Win32::System::Threading::GetCurrentThreadId;
 use windows::Win32::UI::HiDpi::SetProcessDpiAwarenessContext;
 use windows::Win32::UI::HiDpi::DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2;
 use windows::Win32::UI::WindowsAndMessaging::EnumThreadWindows;
-use windows::Win32::UI::WindowsAndMessaging::GetWindowThreadProcessId;
 use windows_core::BOOL;
 
-pub static MAX_LABEL_WIDTH: AtomicI32 = AtomicI32::new(400);
-pub static MONITOR_LEFT: AtomicI32 = AtomicI32::new(0);
-pub static MONITOR_TOP: AtomicI32 = AtomicI32::new(0);
-pub static MONITOR_RIGHT: AtomicI32 = AtomicI32::new(0);
-pub static MONITOR_INDEX: AtomicUsize = AtomicUsize::new(0);
 pub static BAR_HEIGHT: f32 = 50.0;
 pub static DEFAULT_PADDING: f32 = 10.0;
 
 pub static AUTO_SELECT_FILL_COLOUR: AtomicU32 = AtomicU32::new(0);
 pub static AUTO_SELECT_TEXT_COLOUR: AtomicU32 = AtomicU32::new(0);
 
+/// Shortest and longest time between two attempts to reconnect to komorebi, backing off from one
+/// to the other for as long as it can't be reached
+const RECONNECT_INTERVAL_MIN: Duration = Duration::from_secs(1);
+const RECONNECT_INTERVAL_MAX: Duration = Duration::from_secs(5);
+
+/// Wakes up the reconnect loop of the subscriber thread to try again straight away
+static RECONNECT_REQUESTS: LazyLock<(Sender<()>, Receiver<()>)> =
+    LazyLock::new(|| crossbeam_channel::bounded(1));
+
+/// Asks the subscriber thread to try to reconnect to komorebi now, instead of waiting until its
+/// next attempt
+pub fn request_reconnect() {
+    let _ = RECONNECT_REQUESTS.0.try_send(());
+}
+
 #[derive(Parser)]
 #[clap(author, about, version)]
 struct Opts {
@@ -73,40 +87,131 @@ struct Opts {
 
 extern "system" fn enum_window(hwnd: HWND, lparam: LPARAM) -> BOOL {
     unsafe {
-        let mut process_id = 0;
-        GetWindowThreadProcessId(hwnd, Some(&mut process_id));
-
-        if process_id == GetCurrentProcessId() {
-            *(lparam.0 as *mut HWND) = hwnd;
-            BOOL::from(false) // Stop enumeration
-        } else {
-            BOOL::from(true) // Continue enumeration
-        }
+        (*(lparam.0 as *mut Vec<isize>)).push(hwnd.0 as isize);
     }
+
+    BOOL::from(true) // Continue enumeration
 }
 
-fn process_hwnd() -> Option<isize> {
+/// Finds the window with the given title among the windows of the gui thread, which is where the
+/// windows of all of the bars of this process are created
+fn bar_hwnd(title: &str) -> Option<isize> {
+    let mut hwnds: Vec<isize> = vec![];
+
     unsafe {
-        let mut hwnd = HWND::default();
         let _ = EnumThreadWindows(
             GetCurrentThreadId(),
             Some(enum_window),
-            LPARAM(&mut hwnd as *mut HWND as isize),
+            LPARAM(&mut hwnds as *mut Vec<isize> as isize),
         );
+    }
 
-        if hwnd.0 as isize == 0 {
-            None
-        } else {
-            Some(hwnd.0 as isize)
-        }
+    hwnds.into_iter().find(|hwnd| {
+        komorebi_client::Window::from(*hwnd)
+            .title()
+            .is_ok_and(|window_title| window_title == title)
+    })
+}
+
+/// The title of the window of the bar at `idx` in the configuration file
+fn bar_title(idx: usize) -> String {
+    match idx {
+        0 => String::from("komorebi-bar"),
+        idx => format!("komorebi-bar-{}", idx + 1),
     }
 }
 
 pub enum KomorebiEvent {
-    Notification(komorebi_client::Notification),
+    /// Notifications are shared by all of the bars of this process
+    Notification(Arc<komorebi_client::Notification>),
+    /// The notification socket to komorebi has dropped, so the state shown is stale
+    Disconnect,
     Reconnect,
 }
 
+/// Resolves the komorebi monitor index of a bar on startup, applies the configuration overrides
+/// of that monitor, fills in the default position of the bar and applies its configured work area
+/// offset
+fn prepare_bar(
+    config: &mut KomobarConfig,
+    state: &komorebi_client::State,
+) -> color_eyre::Result<usize> {
+    let work_area_offset = config
+        .monitor
+        .as_ref()
+        .and_then(|monitor| monitor.work_area_offset());
+
+    let monitor_index = match &config.monitor {
+        Some(monitor) => {
+            let usr_monitor_index = monitor.index();
+            state
+                .monitor_usr_idx_map
+                .get(&usr_monitor_index)
+                .map_or(usr_monitor_index, |i| *i)
+        }
+        // Without a configured monitor, start on the monitor containing the configured start
+        // position, or the focused monitor; the bar re-detects its monitor once it is displayed
+        None => config
+            .position
+            .as_ref()
+            .and_then(|position| position.start)
+            .and_then(|start| {
+                state
+                    .monitors
+                    .elements()
+                    .iter()
+                    .position(|m| m.size().contains_point((start.x as i32, start.y as i32)))
+            })
+            .unwrap_or_else(|| state.monitors.focused_idx()),
+    };
+
+    let monitor_size = *state.monitors.elements()[monitor_index].size();
+
+    config.apply_monitor_overrides(monitor_index, &state.monitor_usr_idx_map);
+
+    let default_start = Position {
+        x: monitor_size.left as f32,
+        y: monitor_size.top as f32,
+    };
+
+    let default_end = if config.is_vertical() {
+        Position {
+            x: 50.0,
+            y: monitor_size.bottom as f32,
+        }
+    } else {
+        Position {
+            x: monitor_size.right as f32,
+            y: 50.0,
+        }
+    };
+
+    match config.position {
+        None => {
+            config.position = Some(PositionConfig {
+                start: Some(default_start),
+                end: Some(default_end),
+            })
+        }
+        Some(ref mut position) => {
+            if position.start.is_none() {
+                position.start = Some(default_start);
+            }
+
+            if position.end.is_none() {
+                position.end = Some(default_end);
+            }
+        }
+    }
+
+    if let Some(rect) = &work_area_offset {
+        komorebi_client::send_message(&SocketMessage::MonitorWorkAreaOffset(monitor_index, *rect))?;
+        tracing::info!("work area offset applied to monitor: {}", monitor_index);
+    }
+
+    Ok(monitor_index)
+}
+
 fn main() -> color_eyre::Result<()> {
     unsafe { SetProcessDpiAwarenessContext(DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2) }?;
 
@@ -186,7 +291,7 @@ fn main() -> color_eyre::Result<()> {
             .then_some(default_config_path.clone())
     });
 
-    let mut config = match config_path {
+    let config = match config_path {
         None => {
             let komorebi_bar_json =
                 include_str!("../../docs/komorebi.bar.example.json").to_string();
@@ -215,69 +320,30 @@ fn main() -> color_eyre::Result<()> {
         std::process::exit(0);
     }
 
-    let state = serde_json::from_str::<komorebi_client::State>(&komorebi_client::send_query(
-        &SocketMessage::State,
-    )?)?;
-
-    let (usr_monitor_index, work_area_offset) = match &config.monitor {
-        MonitorConfigOrIndex::MonitorConfig(monitor_config) => {
-            (monitor_config.index, monitor_config.work_area_offset)
+    // the bar is often launched at the same time as komorebi, so wait for it to start instead of
+    // exiting; any other error, such as a version mismatch, is surfaced straight away
+    let mut retry_interval = RECONNECT_INTERVAL_MIN;
+    let state = loop {
+        match komorebi_client::query_state_with_timeout(Duration::from_secs(5)) {
+            Ok(state) => break state,
+            Err(error) if error.is_transient() => {
+                tracing::info!("{error}, retrying in {}s", retry_interval.as_secs());
+                std::thread::sleep(retry_interval);
+                retry_interval = (retry_interval * 2).min(RECONNECT_INTERVAL_MAX);
+            }
+            Err(error) => return Err(error.into()),
         }
-        MonitorConfigOrIndex::Index(idx) => (*idx, None),
     };
-    let monitor_index = state
-        .monitor_usr_idx_map
-        .get(&usr_monitor_index)
-        .map_or(usr_monitor_index, |i| *i);
-
-    MONITOR_RIGHT.store(
-        state.monitors.elements()[monitor_index].size().right,
-        Ordering::SeqCst,
-    );
-
-    MONITOR_TOP.store(
-        state.monitors.elements()[monitor_index].size().top,
-        Ordering::SeqCst,
-    );
 
-    MONITOR_LEFT.store(
-        state.monitors.elements()[monitor_index].size().left,
-        Ordering::SeqCst,
-    );
-
-    MONITOR_INDEX.store(monitor_index, Ordering::SeqCst);
-
-    match config.position {
-        None => {
-            config.position = Some(PositionConfig {
-                start: Some(Position {
-                    x: state.monitors.elements()[monitor_index].size().left as f32,
-                    y: state.monitors.elements()[monitor_index].size().top as f32,
-                }),
-                end: Some(Position {
-                    x: state.monitors.elements()[monitor_index].size().right as f32,
-                    y: 50.0,
-                }),
-            })
-        }
-        Some(ref mut position) => {
-            if position.start.is_none() {
-                position.start = Some(Position {
-                    x: state.monitors.elements()[monitor_index].size().left as f32,
-                    y: state.monitors.elements()[monitor_index].size().top as f32,
-                });
-            }
-
-            if position.end.is_none() {
-                position.end = Some(Position {
-                    x: state.monitors.elements()[monitor_index].size().right as f32,
-                    y: 50.0,
-                })
-            }
-        }
+    let mut bars = vec![];
+    for (idx, mut bar_config) in config.into_bars().into_iter().enumerate() {
+        let monitor_index = prepare_bar(&mut bar_config, &state)?;
+        let monitor_size = *state.monitors.elements()[monitor_index].size();
+        bars.push((bar_title(idx), bar_config, monitor_index, monitor_size));
     }
 
     let viewport_builder = ViewportBuilder::default()
+        .with_title(&bars[0].0)
         .with_decorations(false)
         .with_transparent(true)
         .with_taskbar(false);
@@ -287,13 +353,11 @@ fn main() -> color_eyre::Result<()> {
         ..Default::default()
     };
 
-    if let Some(rect) = &work_area_offset {
-        komorebi_client::send_message(&SocketMessage::MonitorWorkAreaOffset(monitor_index, *rect))?;
-        tracing::info!("work area offset applied to monitor: {}", monitor_index);
-    }
-
-    let (tx_gui, rx_gui) = crossbeam_channel::unbounded();
-    let (tx_config, rx_config) = crossbeam_channel::unbounded();
+    // Every bar receives its own copy of the komorebi notifications and configuration updates
+    let (gui_senders, gui_receivers): (Vec<_>, Vec<_>) =
+        bars.iter().map(|_| crossbeam_channel::unbounded()).unzip();
+    let (config_senders, config_receivers): (Vec<_>, Vec<_>) =
+        bars.iter().map(|_| crossbeam_channel::unbounded()).unzip();
 
     let mut hotwatch = Hotwatch::new()?;
     let config_path_cl = config_path.clone();
@@ -303,9 +367,18 @@ fn main() -> color_eyre::Result<()> {
             Ok(updated) => {
                 tracing::info!("configuration file updated: {}", config_path_cl.display());
 
-                if let Err(error) = tx_config.send(updated) {
-                    tracing::error!("could not send configuration update to gui: {error}")
+                let updated = updated.into_bars();
+                if updated.len() != config_senders.len() {
+                    tracing::warn!("the number of bars has changed, komorebi-bar must be restarted to add or remove bars");
                 }
+
+                for (tx_config, updated) in config_senders.iter().zip(updated) {
+                    if let Err(error) = tx_config.send(updated) {
+                        tracing::error!("could not send configuration update to gui: {error}")
+                    }
+                }
+
+                repaint::mark_dirty();
             }
             Err(error) => {
                 tracing::error!("{error}");
@@ -316,22 +389,27 @@ fn main() -> color_eyre::Result<()> {
 
     tracing::info!("watching configuration file for changes");
 
+    if let Err(error) = ipc::listen(|message| match message {
+        ipc::BarMessage::UpdateCustom { id, output } => {
+            widgets::custom::push_output(id, output);
+            repaint::mark_dirty();
+        }
+    }) {
+        tracing::error!("could not listen for pushed widget content: {error}");
+    }
+
     eframe::run_native(
         "komorebi-bar",
         native_options,
         Box::new(|cc| {
-            let ctx_repainter = cc.egui_ctx.clone();
-            std::thread::spawn(move || loop {
-                std::thread::sleep(Duration::from_secs(1));
-                ctx_repainter.request_repaint();
-            });
+            repaint::set_context(cc.egui_ctx.clone());
 
-            let ctx_komorebi = cc.egui_ctx.clone();
             std::thread::spawn(move || {
                 let subscriber_name = format!("komorebi-bar-{}", random_word::get(random_word::Lang::En));
 
                 let listener = komorebi_client::subscribe_with_options(&subscriber_name, SubscribeOptions {
                     filter_state_changes: true,
+                    events: None,
                 })
                     .expect("could not subscribe to komorebi notifications");
 
@@ -351,22 +429,43 @@ fn main() -> color_eyre::Result<()> {
                             if matches!(reader.read_to_end(&mut buffer), Ok(0)) {
                                 tracing::info!("disconnected from komorebi");
 
-                                // keep trying to reconnect to komorebi
+                                for tx_gui in &gui_senders {
+                                    if let Err(error) = tx_gui.send(KomorebiEvent::Disconnect) {
+                                        tracing::error!("could not send komorebi disconnect event to gui thread: {error}")
+                                    }
+                                }
+
+                                repaint::mark_dirty();
+
+                                // requests made while connected have nothing left to do
+                                while RECONNECT_REQUESTS.1.try_recv().is_ok() {}
+
+                                // keep trying to reconnect to komorebi, backing off until a
+                                // reconnect is requested from the bar
+                                let mut retry_interval = RECONNECT_INTERVAL_MIN;
                                 while komorebi_client::send_message(
                                     &SocketMessage::AddSubscriberSocket(subscriber_name.clone()),
                                 )
                                 .is_err()
                                 {
-                                    std::thread::sleep(Duration::from_secs(1));
+                                    retry_interval =
+                                        match RECONNECT_REQUESTS.1.recv_timeout(retry_interval) {
+                                            Ok(()) => RECONNECT_INTERVAL_MIN,
+                                            Err(_) => {
+                                                (retry_interval * 2).min(RECONNECT_INTERVAL_MAX)
+                                            }
+                                        };
                                 }
 
                                 tracing::info!("reconnected to komorebi");
 
-                                if let Err(error) = tx_gui.send(KomorebiEvent::Reconnect) {
-                                    tracing::error!("could not send komorebi reconnect event to gui thread: {error}")
+                                for tx_gui in &gui_senders {
+                                    if let Err(error) = tx_gui.send(KomorebiEvent::Reconnect) {
+                                        tracing::error!("could not send komorebi reconnect event to gui thread: {error}")
+                                    }
                                 }
 
-                                ctx_komorebi.request_repaint();
+                                repaint::mark_dirty();
                                 continue;
                             }
 
@@ -378,11 +477,14 @@ fn main() -> color_eyre::Result<()> {
                                         Ok(notification) => {
                                             tracing::debug!("received notification from komorebi");
 
-                                            if let Err(error) = tx_gui.send(KomorebiEvent::Notification(notification)) {
-                                                tracing::error!("could not send komorebi notification update to gui thread: {error}")
+                                            let notification = Arc::new(notification);
+                                            for tx_gui in &gui_senders {
+                                                if let Err(error) = tx_gui.send(KomorebiEvent::Notification(notification.clone())) {
+                                                    tracing::error!("could not send komorebi notification update to gui thread: {error}")
+                                                }
                                             }
 
-                                            ctx_komorebi.request_repaint();
+                                            repaint::mark_dirty();
                                         }
                                         Err(error) => {
                                             tracing::error!("could not deserialize komorebi notification: {error}");
@@ -403,7 +505,27 @@ fn main() -> color_eyre::Result<()> {
                 }
             });
 
-            Ok(Box::new(Komobar::new(cc, rx_gui, rx_config, config)))
+            let bars = bars
+                .into_iter()
+                .zip(gui_receivers.into_iter().zip(config_receivers))
+                .enumerate()
+                .map(
+                    |(idx, ((title, config, monitor_index, monitor_size), (rx_gui, rx_config)))| {
+                        Komobar::new(
+                            cc,
+                            title,
+                            idx == 0,
+                            rx_gui,
+                            rx_config,
+                            config,
+                            monitor_index,
+                            monitor_size,
+                        )
+                    },
+                )
+                .collect();
+
+            Ok(Box::new(Komobars { bars }))
         }),
     )
     .map_err(|error| color_eyre::eyre::Error::msg(error.to_string()))
@@ -1,6 +1,5 @@
 use crate::bar::Alignment;
 use crate::config::KomobarConfig;
-use crate::config::MonitorConfigOrIndex;
 use crate::AUTO_SELECT_FILL_COLOUR;
 use crate::AUTO_SELECT_TEXT_COLOUR;
 use eframe::egui::Color32;
@@ -11,6 +10,7 @@ use eframe::egui::Frame;
 use eframe::egui::InnerResponse;
 use eframe::egui::Margin;
 use eframe::egui::Shadow;
+use eframe::egui::Style;
 use eframe::egui::TextStyle;
 use eframe::egui::Ui;
 use komorebi_client::Colour;
@@ -23,6 +23,7 @@ use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 static SHOW_KOMOREBI_LAYOUT_OPTIONS: AtomicUsize = AtomicUsize::new(0);
+static SHOW_KOMOREBI_CONTROL_MENU: AtomicUsize = AtomicUsize::new(0);
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
@@ -50,6 +51,8 @@ pub struct RenderConfig {
     pub background_color: Color32,
     /// Alignment of the widgets
     pub alignment: Option<Alignment>,
+    /// Widgets are stacked top-to-bottom on a vertical bar
+    pub vertical: bool,
     /// Add more inner margin when adding a widget group
     pub more_inner_margin: bool,
     /// Set to true after the first time the apply_on_widget was called on an alignment
@@ -60,10 +63,51 @@ pub struct RenderConfig {
     pub icon_font_id: FontId,
     /// Show all icons on the workspace section of the Komorebi widget
     pub show_all_icons: bool,
+    /// Max label width before text truncation
+    pub max_label_width: f32,
     /// Background color of the selected frame
     pub auto_select_fill: Option<Color32>,
     /// Text color of the selected frame
     pub auto_select_text: Option<Color32>,
+    /// Theme overrides of the widget currently being rendered
+    pub widget_theme: Option<WidgetTheme>,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WidgetTheme {
+    /// Text colour of the widget
+    pub text_color: Option<Colour>,
+    /// Accent colour of the widget, used for icons and selections
+    pub accent_color: Option<Colour>,
+    /// Background colour of the widget
+    pub background_color: Option<Colour>,
+    /// Rounding values for the 4 corners of the widget background. Can be a single or 4 values.
+    pub corner_radius: Option<RoundingConfig>,
+}
+
+impl WidgetTheme {
+    /// Applies the colour and rounding overrides of this theme to `style`
+    pub fn apply_on_style(&self, style: &mut Style) {
+        if let Some(text_color) = self.text_color {
+            style.visuals.override_text_color = Some(text_color.into());
+        }
+
+        if let Some(accent_color) = self.accent_color {
+            let accent_color = Color32::from(accent_color);
+            style.visuals.selection.stroke.color = accent_color;
+            style.visuals.selection.bg_fill = accent_color.linear_multiply(0.3);
+        }
+
+        if let Some(corner_radius) = self.corner_radius {
+            let corner_radius = CornerRadius::from(corner_radius);
+            style.visuals.widgets.noninteractive.corner_radius = corner_radius;
+            style.visuals.widgets.inactive.corner_radius = corner_radius;
+            style.visuals.widgets.hovered.corner_radius = corner_radius;
+            style.visuals.widgets.active.corner_radius = corner_radius;
+            style.visuals.widgets.open.corner_radius = corner_radius;
+        }
+    }
 }
 
 pub trait RenderExt {
@@ -92,11 +136,6 @@ impl RenderExt for &KomobarConfig {
         let mut icon_font_id = text_font_id.clone();
         icon_font_id.size *= icon_scale.unwrap_or(1.4).clamp(1.0, 2.0);
 
-        let monitor_idx = match &self.monitor {
-            MonitorConfigOrIndex::MonitorConfig(monitor_config) => monitor_config.index,
-            MonitorConfigOrIndex::Index(idx) => *idx,
-        };
-
         // check if any of the alignments have a komorebi widget with the workspace set to show all icons
         let show_all_icons =
             KomobarConfig::show_all_icons_on_komorebi_workspace(&self.left_widgets)
@@ -107,20 +146,25 @@ impl RenderExt for &KomobarConfig {
                 || KomobarConfig::show_all_icons_on_komorebi_workspace(&self.right_widgets);
 
         RenderConfig {
-            monitor_idx,
+            // the configured monitor index is user-facing, so the bar sets this once it has
+            // resolved its komorebi monitor index
+            monitor_idx: 0,
             spacing: self.widget_spacing.unwrap_or(10.0),
             grouping: self.grouping.unwrap_or(Grouping::None),
             background_color,
             alignment: None,
+            vertical: self.is_vertical(),
             more_inner_margin: false,
             applied_on_widget: false,
             text_font_id,
             icon_font_id,
             show_all_icons,
+            max_label_width: self.max_label_width.unwrap_or(400.0),
             auto_select_fill: NonZeroU32::new(AUTO_SELECT_FILL_COLOUR.load(Ordering::SeqCst))
                 .map(|c| Colour::Rgb(Rgb::from(c.get())).into()),
             auto_select_text: NonZeroU32::new(AUTO_SELECT_TEXT_COLOUR.load(Ordering::SeqCst))
                 .map(|c| Colour::Rgb(Rgb::from(c.get())).into()),
+            widget_theme: None,
         }
     }
 }
@@ -134,6 +178,14 @@ impl RenderConfig {
         SHOW_KOMOREBI_LAYOUT_OPTIONS.store(show as usize, Ordering::SeqCst);
     }
 
+    pub fn load_show_komorebi_control_menu() -> bool {
+        SHOW_KOMOREBI_CONTROL_MENU.load(Ordering::SeqCst) != 0
+    }
+
+    pub fn store_show_komorebi_control_menu(show: bool) {
+        SHOW_KOMOREBI_CONTROL_MENU.store(show as usize, Ordering::SeqCst);
+    }
+
     pub fn new() -> Self {
         Self {
             monitor_idx: 0,
@@ -141,13 +193,16 @@ impl RenderConfig {
             grouping: Grouping::None,
             background_color: Color32::BLACK,
             alignment: None,
+            vertical: false,
             more_inner_margin: false,
             applied_on_widget: false,
             text_font_id: FontId::default(),
             icon_font_id: FontId::default(),
             show_all_icons: false,
+            max_label_width: 400.0,
             auto_select_fill: None,
             auto_select_text: None,
+            widget_theme: None,
         }
     }
 
@@ -218,13 +273,21 @@ impl RenderConfig {
         ui: &mut Ui,
         add_contents: impl FnOnce(&mut Ui) -> R,
     ) -> InnerResponse<R> {
-        Frame::NONE
+        let mut frame = Frame::NONE
             .outer_margin(outer_margin.unwrap_or(Margin::ZERO))
             .inner_margin(match self.more_inner_margin {
                 true => Margin::symmetric(5, 0),
                 false => Margin::same(0),
-            })
-            .show(ui, add_contents)
+            });
+
+        if let Some(background_color) = self.widget_theme.and_then(|theme| theme.background_color) {
+            frame = frame
+                .fill(background_color.into())
+                .corner_radius(ui.style().visuals.widgets.noninteractive.corner_radius)
+                .inner_margin(Margin::symmetric(5, 0));
+        }
+
+        frame.show(ui, add_contents)
     }
 
     fn define_group<R>(
@@ -251,12 +314,20 @@ impl RenderConfig {
                 false => Margin::symmetric(1, 1),
             })
             .stroke(ui_style.visuals.widgets.noninteractive.bg_stroke)
-            .corner_radius(match config.rounding {
-                Some(rounding) => rounding.into(),
-                None => ui_style.visuals.widgets.noninteractive.corner_radius,
-            })
+            .corner_radius(
+                match self
+                    .widget_theme
+                    .and_then(|theme| theme.corner_radius)
+                    .or(config.rounding)
+                {
+                    Some(rounding) => rounding.into(),
+                    None => ui_style.visuals.widgets.noninteractive.corner_radius,
+                },
+            )
             .fill(
-                self.background_color
+                self.widget_theme
+                    .and_then(|theme| theme.background_color)
+                    .map_or(self.background_color, Color32::from)
                     .try_apply_alpha(config.transparency_alpha),
             )
             .shadow(match config.style {
@@ -322,7 +393,13 @@ impl RenderConfig {
     fn widget_outer_margin(&mut self, ui: &mut Ui) -> Margin {
         let spacing = if self.applied_on_widget {
             // Remove the default item spacing from the margin
-            (self.spacing - ui.spacing().item_spacing.x) as i8
+            let item_spacing = if self.vertical {
+                ui.spacing().item_spacing.y
+            } else {
+                ui.spacing().item_spacing.x
+            };
+
+            (self.spacing - item_spacing) as i8
         } else {
             0
         };
@@ -331,25 +408,38 @@ impl RenderConfig {
             self.applied_on_widget = true;
         }
 
-        Margin {
-            left: match self.alignment {
-                Some(align) => match align {
-                    Alignment::Left => spacing,
-                    Alignment::Center => spacing,
-                    Alignment::Right => 0,
-                },
-                None => 0,
+        let leading = match self.alignment {
+            Some(align) => match align {
+                Alignment::Left => spacing,
+                Alignment::Center => spacing,
+                Alignment::Right => 0,
             },
-            right: match self.alignment {
-                Some(align) => match align {
-                    Alignment::Left => 0,
-                    Alignment::Center => 0,
-                    Alignment::Right => spacing,
-                },
-                None => 0,
+            None => 0,
+        };
+
+        let trailing = match self.alignment {
+            Some(align) => match align {
+                Alignment::Left => 0,
+                Alignment::Center => 0,
+                Alignment::Right => spacing,
             },
-            top: 0,
-            bottom: 0,
+            None => 0,
+        };
+
+        if self.vertical {
+            Margin {
+                left: 0,
+                right: 0,
+                top: leading,
+                bottom: trailing,
+            }
+        } else {
+            Margin {
+                left: leading,
+                right: trailing,
+                top: 0,
+                bottom: 0,
+            }
         }
     }
 }
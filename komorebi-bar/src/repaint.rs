@@ -0,0 +1,73 @@
+use crate::widgets::widget::BarWidget;
+use eframe::egui::Context;
+use std::sync::atomic::AtomicU32;
+use std::sync::atomic::Ordering;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::time::Instant;
+
+/// The context of the bar, which is repainted when something happens outside of the gui thread
+static CONTEXT: OnceLock<Context> = OnceLock::new();
+/// Maximum number of frames drawn per second, 0 means uncapped
+pub static MAX_FPS: AtomicU32 = AtomicU32::new(60);
+
+/// Stores the context to repaint when the bar is marked dirty
+pub fn set_context(ctx: Context) {
+    let _ = CONTEXT.set(ctx);
+}
+
+/// Marks the bar as needing a repaint, which will happen straight away. Nothing happens before
+/// the context has been stored, as the first frame has yet to be drawn
+pub fn mark_dirty() {
+    if let Some(ctx) = CONTEXT.get() {
+        ctx.request_repaint();
+    }
+}
+
+/// Minimum time between two frames, as derived from the `max_fps` configuration
+pub fn frame_interval() -> Duration {
+    match MAX_FPS.load(Ordering::SeqCst) {
+        0 => Duration::ZERO,
+        max_fps => Duration::from_secs(1) / max_fps,
+    }
+}
+
+/// Time left until a widget which last refreshed its data at `last_updated` should refresh again
+pub fn refresh_due_in(last_updated: Instant, interval: Duration) -> Duration {
+    interval.saturating_sub(last_updated.elapsed())
+}
+
+/// Returns the soonest refresh that any of the widgets is waiting for
+pub fn next_widget_refresh<'a>(
+    widgets: impl IntoIterator<Item = &'a Box<dyn BarWidget>>,
+) -> Option<Duration> {
+    widgets
+        .into_iter()
+        .filter_map(|widget| widget.next_refresh())
+        .min()
+}
+
+/// Schedules the next frame once the current one has been drawn. Nothing is scheduled when the
+/// bar is idle, unless an idle repaint interval has been configured
+pub fn schedule_next_frame(
+    ctx: &Context,
+    next_widget_refresh: Option<Duration>,
+    idle_interval: Option<Duration>,
+) {
+    // egui animations and hover effects need a steady frame rate while the mouse is on the bar
+    let hovered = ctx.input(|i| i.pointer.has_pointer());
+    let frame_interval = frame_interval();
+
+    let next = [
+        hovered.then_some(frame_interval),
+        next_widget_refresh,
+        idle_interval,
+    ]
+    .into_iter()
+    .flatten()
+    .min();
+
+    if let Some(next) = next {
+        ctx.request_repaint_after(next.max(frame_interval));
+    }
+}
@@ -11,6 +11,7 @@ use eframe::egui::Ui;
 pub struct SelectableFrame {
     selected: bool,
     selected_fill: Option<Color32>,
+    sense: Sense,
 }
 
 impl SelectableFrame {
@@ -18,6 +19,7 @@ impl SelectableFrame {
         Self {
             selected,
             selected_fill: None,
+            sense: Sense::click(),
         }
     }
 
@@ -25,18 +27,26 @@ impl SelectableFrame {
         Self {
             selected,
             selected_fill,
+            sense: Sense::click(),
         }
     }
 
+    /// Sets how the frame responds to interaction (default: clicks only)
+    pub fn sense(mut self, sense: Sense) -> Self {
+        self.sense = sense;
+        self
+    }
+
     pub fn show<R>(self, ui: &mut Ui, add_contents: impl FnOnce(&mut Ui) -> R) -> Response {
         let Self {
             selected,
             selected_fill,
+            sense,
         } = self;
 
         Frame::NONE
             .show(ui, |ui| {
-                let response = ui.interact(ui.max_rect(), ui.unique_id(), Sense::click());
+                let response = ui.interact(ui.max_rect(), ui.unique_id(), sense);
 
                 if ui.is_rect_visible(response.rect) {
                     // take into account the stroke width
@@ -1,5 +1,6 @@
 use super::ImageIcon;
 use crate::render::RenderConfig;
+use crate::render::WidgetTheme;
 use crate::selected_frame::SelectableFrame;
 use crate::widgets::widget::BarWidget;
 use eframe::egui::vec2;
@@ -30,13 +31,15 @@ use tracing;
 use which::which;
 
 /// Minimum interval between consecutive application launches to prevent accidental spamming.
-const MIN_LAUNCH_INTERVAL: Duration = Duration::from_millis(800);
+pub const MIN_LAUNCH_INTERVAL: Duration = Duration::from_millis(800);
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ApplicationsConfig {
     /// Enables or disables the applications widget.
     pub enable: bool,
+    /// Theme overrides applied to this widget only
+    pub theme: Option<WidgetTheme>,
     /// Whether to show the launch command on hover (optional).
     /// Could be overridden per application. Defaults to `false` if not set.
     pub show_command_on_hover: Option<bool>,
@@ -1,5 +1,7 @@
 use crate::config::LabelPrefix;
 use crate::render::RenderConfig;
+use crate::render::WidgetTheme;
+use crate::repaint::refresh_due_in;
 use crate::selected_frame::SelectableFrame;
 use crate::widgets::widget::BarWidget;
 use eframe::egui::text::LayoutJob;
@@ -22,6 +24,8 @@ use std::time::Instant;
 pub struct BatteryConfig {
     /// Enable the Battery widget
     pub enable: bool,
+    /// Theme overrides applied to this widget only
+    pub theme: Option<WidgetTheme>,
     /// Hide the widget if the battery is at full charge
     pub hide_on_full_charge: Option<bool>,
     /// Data refresh interval (default: 10 seconds)
@@ -188,4 +192,11 @@ impl BarWidget for Battery {
             }
         }
     }
+
+    fn next_refresh(&self) -> Option<Duration> {
+        Some(refresh_due_in(
+            self.last_updated,
+            Duration::from_secs(self.data_refresh_interval),
+        ))
+    }
 }
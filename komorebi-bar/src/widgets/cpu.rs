@@ -1,5 +1,7 @@
 use crate::config::LabelPrefix;
 use crate::render::RenderConfig;
+use crate::render::WidgetTheme;
+use crate::repaint::refresh_due_in;
 use crate::selected_frame::SelectableFrame;
 use crate::widgets::widget::BarWidget;
 use eframe::egui::text::LayoutJob;
@@ -21,6 +23,8 @@ use sysinfo::System;
 pub struct CpuConfig {
     /// Enable the Cpu widget
     pub enable: bool,
+    /// Theme overrides applied to this widget only
+    pub theme: Option<WidgetTheme>,
     /// Data refresh interval (default: 10 seconds)
     pub data_refresh_interval: Option<u64>,
     /// Display label prefix
@@ -131,4 +135,11 @@ impl BarWidget for Cpu {
             }
         }
     }
+
+    fn next_refresh(&self) -> Option<Duration> {
+        Some(refresh_due_in(
+            self.last_updated,
+            Duration::from_secs(self.data_refresh_interval),
+        ))
+    }
 }
@@ -0,0 +1,241 @@
+use crate::bar::CREATE_NO_WINDOW;
+use crate::ipc::CustomOutput;
+use crate::render::RenderConfig;
+use crate::render::WidgetTheme;
+use crate::repaint::refresh_due_in;
+use crate::selected_frame::SelectableFrame;
+use crate::widgets::applications::UserCommand;
+use crate::widgets::widget::BarWidget;
+use eframe::egui::text::LayoutJob;
+use eframe::egui::Align;
+use eframe::egui::Color32;
+use eframe::egui::Context;
+use eframe::egui::Label;
+use eframe::egui::TextFormat;
+use eframe::egui::Ui;
+use komorebi_client::Colour;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::os::windows::process::CommandExt;
+use std::process::Command;
+use std::sync::mpsc;
+use std::sync::mpsc::Receiver;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+/// Content pushed by external programs by widget id, along with the time it was pushed
+static PUSHED_OUTPUT: LazyLock<Mutex<HashMap<String, (Instant, CustomOutput)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Stores content pushed to the custom widgets with the given id, which they pick up on their
+/// next render
+pub fn push_output(id: String, output: CustomOutput) {
+    if let Ok(mut pushed) = PUSHED_OUTPUT.lock() {
+        pushed.insert(id, (Instant::now(), output));
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CustomConfig {
+    /// Enable the Custom widget
+    pub enable: bool,
+    /// Theme overrides applied to this widget only
+    pub theme: Option<WidgetTheme>,
+    /// Command to run, the output of which is displayed as the label
+    ///
+    /// The output can either be plain text, or a JSON object with the optional fields
+    /// `text`, `icon`, `color` (hex, e.g. "#ff0000"), `tooltip` and `on_click`
+    pub command: Option<String>,
+    /// Id under which external programs can push content to this widget instead of it being
+    /// polled, e.g. `komorebi-bar-client custom <id> <output>`. Pushed content uses the same
+    /// format as the command output
+    pub id: Option<String>,
+    /// Data refresh interval (default: 10 seconds)
+    pub data_refresh_interval: Option<u64>,
+    /// Icon glyph to display when the command output doesn't provide one, e.g. a Phosphor icon
+    pub icon: Option<String>,
+    /// Command to run when the widget is clicked, unless the command output provides one
+    pub on_click: Option<String>,
+}
+
+impl From<CustomConfig> for Custom {
+    fn from(value: CustomConfig) -> Self {
+        let data_refresh_interval = value.data_refresh_interval.unwrap_or(10);
+
+        Self {
+            enable: value.enable,
+            command: value.command,
+            id: value.id,
+            data_refresh_interval,
+            icon: value.icon,
+            on_click: value.on_click.as_deref().map(UserCommand::new),
+            output_on_click: None,
+            last_state: CustomOutput::default(),
+            pending: None,
+            last_pushed: None,
+            last_updated: Instant::now()
+                .checked_sub(Duration::from_secs(data_refresh_interval))
+                .unwrap(),
+        }
+    }
+}
+
+pub struct Custom {
+    pub enable: bool,
+    command: Option<String>,
+    id: Option<String>,
+    data_refresh_interval: u64,
+    icon: Option<String>,
+    on_click: Option<UserCommand>,
+    output_on_click: Option<UserCommand>,
+    last_state: CustomOutput,
+    pending: Option<Receiver<CustomOutput>>,
+    last_pushed: Option<Instant>,
+    last_updated: Instant,
+}
+
+impl Custom {
+    fn set_state(&mut self, output: CustomOutput) {
+        let on_click_changed =
+            output.on_click.as_deref() != self.output_on_click.as_ref().map(|c| c.as_ref());
+
+        if on_click_changed {
+            self.output_on_click = output.on_click.as_deref().map(UserCommand::new);
+        }
+
+        self.last_state = output;
+    }
+
+    fn output(&mut self, ctx: &Context) -> CustomOutput {
+        if let Some(pending) = &self.pending {
+            match pending.try_recv() {
+                Ok(output) => {
+                    self.set_state(output);
+                    self.pending = None;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => self.pending = None,
+                Err(mpsc::TryRecvError::Empty) => {}
+            }
+        }
+
+        // the latest content wins, whether it was pushed or came from the command
+        let pushed = self.id.as_ref().and_then(|id| {
+            PUSHED_OUTPUT
+                .lock()
+                .ok()
+                .and_then(|pushed| pushed.get(id).cloned())
+        });
+
+        if let Some((pushed_at, output)) = pushed {
+            if self.last_pushed != Some(pushed_at) {
+                self.last_pushed = Some(pushed_at);
+                self.set_state(output);
+            }
+        }
+
+        let Some(command) = &self.command else {
+            return self.last_state.clone();
+        };
+
+        let now = Instant::now();
+        if self.pending.is_none()
+            && now.duration_since(self.last_updated)
+                > Duration::from_secs(self.data_refresh_interval)
+        {
+            let (tx, rx) = mpsc::channel();
+            let command = command.clone();
+            let ctx = ctx.clone();
+
+            // the command may take a while to run, so it shouldn't block the bar from rendering
+            std::thread::spawn(move || {
+                match Command::new("cmd")
+                    .args(["/C", &command])
+                    .creation_flags(CREATE_NO_WINDOW)
+                    .output()
+                {
+                    Ok(output) => {
+                        let stdout = String::from_utf8_lossy(&output.stdout);
+                        if tx.send(CustomOutput::parse(&stdout)).is_ok() {
+                            ctx.request_repaint();
+                        }
+                    }
+                    Err(error) => {
+                        tracing::error!("failed to run custom command '{command}': {error}")
+                    }
+                }
+            });
+
+            self.pending = Some(rx);
+            self.last_updated = now;
+        }
+
+        self.last_state.clone()
+    }
+}
+
+impl BarWidget for Custom {
+    fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
+        if self.enable {
+            let output = self.output(ctx);
+            if !output.text.is_empty() {
+                let color = output
+                    .color
+                    .as_deref()
+                    .and_then(|color| color.parse::<Colour>().ok())
+                    .map(Color32::from);
+
+                let icon = output.icon.as_ref().or(self.icon.as_ref());
+
+                let mut layout_job = LayoutJob::simple(
+                    icon.cloned().unwrap_or_default(),
+                    config.icon_font_id.clone(),
+                    color.unwrap_or(ctx.style().visuals.selection.stroke.color),
+                    100.0,
+                );
+
+                layout_job.append(
+                    &output.text,
+                    if icon.is_some() { 10.0 } else { 0.0 },
+                    TextFormat {
+                        font_id: config.text_font_id.clone(),
+                        color: color.unwrap_or(ctx.style().visuals.text_color()),
+                        valign: Align::Center,
+                        ..Default::default()
+                    },
+                );
+
+                config.apply_on_widget(false, ui, |ui| {
+                    let mut response = SelectableFrame::new(false)
+                        .show(ui, |ui| ui.add(Label::new(layout_job).selectable(false)));
+
+                    if let Some(tooltip) = &output.tooltip {
+                        response = response.on_hover_text(tooltip);
+                    }
+
+                    if response.clicked() {
+                        if let Some(command) =
+                            self.output_on_click.as_mut().or(self.on_click.as_mut())
+                        {
+                            command.launch_if_ready();
+                        }
+                    }
+                });
+            }
+        }
+    }
+
+    fn next_refresh(&self) -> Option<Duration> {
+        // a pending command requests a repaint by itself once it has finished, and pushed content
+        // marks the bar as dirty when it arrives
+        (self.command.is_some() && self.pending.is_none()).then(|| {
+            refresh_due_in(
+                self.last_updated,
+                Duration::from_secs(self.data_refresh_interval),
+            )
+        })
+    }
+}
@@ -1,8 +1,17 @@
+use crate::config::DateTimeLocale;
+use crate::config::FormatCondition;
 use crate::config::LabelPrefix;
+use crate::config::Weekday;
+use crate::locale;
 use crate::render::RenderConfig;
+use crate::render::WidgetTheme;
+use crate::repaint::refresh_due_in;
 use crate::selected_frame::SelectableFrame;
 use crate::widgets::widget::BarWidget;
+use chrono::Datelike;
 use chrono::Local;
+use chrono::Locale;
+use chrono::Timelike;
 use chrono_tz::Tz;
 use eframe::egui::text::LayoutJob;
 use eframe::egui::Align;
@@ -64,6 +73,8 @@ impl CustomModifiers {
 pub struct DateConfig {
     /// Enable the Date widget
     pub enable: bool,
+    /// Theme overrides applied to this widget only
+    pub theme: Option<WidgetTheme>,
     /// Set the Date format
     pub format: DateFormat,
     /// Display label prefix
@@ -81,17 +92,72 @@ pub struct DateConfig {
     ///}
     /// ```
     pub timezone: Option<String>,
+    /// Additional formats to cycle through on click, instead of the built-in format order
+    pub alternate_formats: Option<Vec<DateFormat>>,
+    /// Formats which take precedence over the selected format whenever their condition matches,
+    /// the first matching entry is used
+    ///
+    /// Show the week number on Mondays, i.e.:
+    /// ```json
+    /// {
+    ///     "Date": {
+    ///         "enable": true,
+    ///         "format": "DayDateMonthYear",
+    ///         "scheduled_formats": [
+    ///             {
+    ///                 "condition": { "weekdays": ["Monday"] },
+    ///                 "format": { "Custom": "%A %e %B %Y (week %V)" }
+    ///             }
+    ///         ]
+    ///      }
+    ///}
+    /// ```
+    pub scheduled_formats: Option<Vec<ScheduledDateFormat>>,
+    /// Locale used for the names of months and days, and by the `Localized` format for the order
+    /// of the date (default: none, English names)
+    ///
+    /// Use the locale selected in the Windows region settings, i.e.:
+    /// ```json
+    /// {
+    ///     "Date": {
+    ///         "enable": true,
+    ///         "format": "Localized",
+    ///         "locale": "System"
+    ///      }
+    ///}
+    /// ```
+    pub locale: Option<DateTimeLocale>,
+    /// First day of the week, for the calendar flyout (default: from the Windows region settings)
+    pub first_day_of_week: Option<Weekday>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ScheduledDateFormat {
+    /// Condition which must match for this format to be used
+    pub condition: FormatCondition,
+    /// Date format to use while the condition matches
+    pub format: DateFormat,
 }
 
 impl From<DateConfig> for Date {
     fn from(value: DateConfig) -> Self {
         let data_refresh_interval = 1;
 
+        let mut alternate_formats = value.alternate_formats.unwrap_or_default();
+        if !alternate_formats.is_empty() {
+            alternate_formats.insert(0, value.format.clone());
+        }
+
         Self {
             enable: value.enable,
             format: value.format,
+            alternate_formats,
+            alternate_format_idx: 0,
+            scheduled_formats: value.scheduled_formats.unwrap_or_default(),
             label_prefix: value.label_prefix.unwrap_or(LabelPrefix::Icon),
             timezone: value.timezone,
+            locale: value.locale.as_ref().and_then(locale::resolve),
             data_refresh_interval,
             last_state: String::new(),
             last_updated: Instant::now()
@@ -112,6 +178,8 @@ pub enum DateFormat {
     DateMonthYear,
     /// Day Date Month Year format (8 September 2024)
     DayDateMonthYear,
+    /// Preferred date format of the configured locale (09/08/2024 for en_GB, 08.09.2024 for de_DE)
+    Localized,
     /// Custom format (https://docs.rs/chrono/latest/chrono/format/strftime/index.html)
     Custom(String),
     /// Custom format with modifiers
@@ -135,6 +203,7 @@ impl DateFormat {
             DateFormat::YearMonthDate => String::from("%F"),
             DateFormat::DateMonthYear => String::from("%v"),
             DateFormat::DayDateMonthYear => String::from("%A %e %B %Y"),
+            DateFormat::Localized => String::from("%x"),
             DateFormat::Custom(custom) => custom.to_string(),
             DateFormat::CustomModifiers(custom) => custom.format.clone(),
         }
@@ -145,39 +214,70 @@ impl DateFormat {
 pub struct Date {
     pub enable: bool,
     pub format: DateFormat,
+    alternate_formats: Vec<DateFormat>,
+    alternate_format_idx: usize,
+    scheduled_formats: Vec<ScheduledDateFormat>,
     label_prefix: LabelPrefix,
     timezone: Option<String>,
+    locale: Option<Locale>,
     data_refresh_interval: u64,
     last_state: String,
     last_updated: Instant,
 }
 
 impl Date {
+    /// Returns the first scheduled format matching `now`, falling back to the selected format
+    fn active_format(&self, now: &(impl Datelike + Timelike)) -> &DateFormat {
+        self.scheduled_formats
+            .iter()
+            .find(|scheduled| scheduled.condition.matches(now))
+            .map_or(&self.format, |scheduled| &scheduled.format)
+    }
+
+    fn next_format(&mut self) {
+        if self.alternate_formats.is_empty() {
+            self.format.next();
+        } else {
+            self.alternate_format_idx =
+                (self.alternate_format_idx + 1) % self.alternate_formats.len();
+            self.format = self.alternate_formats[self.alternate_format_idx].clone();
+        }
+    }
+
     fn output(&mut self) -> String {
         let mut output = self.last_state.clone();
         let now = Instant::now();
 
         if now.duration_since(self.last_updated) > Duration::from_secs(self.data_refresh_interval) {
-            let formatted = match &self.timezone {
+            let (formatted, format) = match &self.timezone {
                 Some(timezone) => match timezone.parse::<Tz>() {
-                    Ok(tz) => Local::now()
-                        .with_timezone(&tz)
-                        .format(&self.format.fmt_string())
-                        .to_string()
-                        .trim()
-                        .to_string(),
-                    Err(_) => format!("Invalid timezone: {}", timezone),
+                    Ok(tz) => {
+                        let dt = Local::now().with_timezone(&tz);
+                        let format = self.active_format(&dt);
+                        (
+                            locale::format(&dt, &format.fmt_string(), self.locale)
+                                .trim()
+                                .to_string(),
+                            Some(format),
+                        )
+                    }
+                    Err(_) => (format!("Invalid timezone: {}", timezone), None),
                 },
-                None => Local::now()
-                    .format(&self.format.fmt_string())
-                    .to_string()
-                    .trim()
-                    .to_string(),
+                None => {
+                    let dt = Local::now();
+                    let format = self.active_format(&dt);
+                    (
+                        locale::format(&dt, &format.fmt_string(), self.locale)
+                            .trim()
+                            .to_string(),
+                        Some(format),
+                    )
+                }
             };
 
             // if custom modifiers are used, apply them
-            output = match &self.format {
-                DateFormat::CustomModifiers(custom) => custom.apply(&formatted),
+            output = match format {
+                Some(DateFormat::CustomModifiers(custom)) => custom.apply(&formatted),
                 _ => formatted,
             };
 
@@ -231,10 +331,17 @@ impl BarWidget for Date {
                         })
                         .clicked()
                     {
-                        self.format.next()
+                        self.next_format()
                     }
                 });
             }
         }
     }
+
+    fn next_refresh(&self) -> Option<Duration> {
+        Some(refresh_due_in(
+            self.last_updated,
+            Duration::from_secs(self.data_refresh_interval),
+        ))
+    }
 }
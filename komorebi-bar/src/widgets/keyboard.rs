@@ -1,5 +1,7 @@
 use crate::config::LabelPrefix;
 use crate::render::RenderConfig;
+use crate::render::WidgetTheme;
+use crate::repaint::refresh_due_in;
 use crate::widgets::widget::BarWidget;
 use eframe::egui::text::LayoutJob;
 use eframe::egui::Align;
@@ -27,6 +29,8 @@ const ERROR_TEXT: &str = "Error";
 pub struct KeyboardConfig {
     /// Enable the Input widget
     pub enable: bool,
+    /// Theme overrides applied to this widget only
+    pub theme: Option<WidgetTheme>,
     /// Data refresh interval (default: 1 second)
     pub data_refresh_interval: Option<u64>,
     /// Display label prefix
@@ -174,4 +178,11 @@ impl BarWidget for Keyboard {
             }
         }
     }
+
+    fn next_refresh(&self) -> Option<Duration> {
+        Some(refresh_due_in(
+            self.last_updated,
+            Duration::from_secs(self.data_refresh_interval),
+        ))
+    }
 }
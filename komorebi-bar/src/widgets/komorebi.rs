@@ -5,30 +5,34 @@ use crate::config::KomobarTheme;
 use crate::config::WorkspacesDisplayFormat;
 use crate::render::Grouping;
 use crate::render::RenderConfig;
+use crate::render::WidgetTheme;
 use crate::selected_frame::SelectableFrame;
 use crate::ui::CustomUi;
 use crate::widgets::komorebi_layout::KomorebiLayout;
+use crate::widgets::komorebi_layout::KomorebiLayoutOverrides;
 use crate::widgets::widget::BarWidget;
-use crate::MAX_LABEL_WIDTH;
-use crate::MONITOR_INDEX;
+use eframe::egui::pos2;
 use eframe::egui::text::LayoutJob;
 use eframe::egui::vec2;
 use eframe::egui::Align;
 use eframe::egui::Color32;
 use eframe::egui::Context;
 use eframe::egui::CornerRadius;
+use eframe::egui::CursorIcon;
 use eframe::egui::Frame;
 use eframe::egui::Image;
 use eframe::egui::Label;
 use eframe::egui::Margin;
 use eframe::egui::RichText;
 use eframe::egui::Sense;
+use eframe::egui::Shape;
 use eframe::egui::Stroke;
 use eframe::egui::StrokeKind;
 use eframe::egui::TextFormat;
 use eframe::egui::Ui;
 use eframe::egui::Vec2;
 use komorebi_client::Container;
+use komorebi_client::CustomLayout;
 use komorebi_client::NotificationEvent;
 use komorebi_client::PathExt;
 use komorebi_client::Rect;
@@ -36,14 +40,20 @@ use komorebi_client::SocketMessage;
 use komorebi_client::Window;
 use komorebi_client::Workspace;
 use komorebi_client::WorkspaceLayer;
+use regex::Regex;
 use serde::Deserialize;
 use serde::Serialize;
 use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
 use std::path::PathBuf;
+use std::process::Command;
 use std::rc::Rc;
-use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
@@ -61,9 +71,19 @@ pub struct KomorebiConfig {
     pub locked_container: Option<KomorebiLockedContainerConfig>,
     /// Configure the Configuration Switcher widget
     pub configuration_switcher: Option<KomorebiConfigurationSwitcherConfig>,
+    /// Configure the Taskbar widget
+    pub taskbar: Option<KomorebiTaskbarConfig>,
+    /// Configure the Notifications widget
+    pub notifications: Option<KomorebiNotificationsConfig>,
+    /// Configure the Control Menu widget
+    pub control_menu: Option<KomorebiControlMenuConfig>,
+    /// Configure the Pause widget
+    pub pause: Option<KomorebiPauseConfig>,
+    /// Theme overrides applied to the Komorebi widgets only
+    pub theme: Option<WidgetTheme>,
 }
 
-#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct KomorebiWorkspacesConfig {
     /// Enable the Komorebi Workspaces widget
@@ -72,6 +92,47 @@ pub struct KomorebiWorkspacesConfig {
     pub hide_empty_workspaces: bool,
     /// Display format of the workspace
     pub display: Option<WorkspacesDisplayFormat>,
+    /// Show the workspaces of all monitors, grouped by monitor with a monitor label (default: false)
+    pub show_all_monitors: Option<bool>,
+    /// Show the titles and icons of the windows on a workspace when hovering it (default: false)
+    pub hover_preview: Option<bool>,
+    /// Cycle through the workspaces when scrolling over the widget (default: false)
+    pub scroll_to_cycle: Option<bool>,
+    /// Defines how many points a user needs to scroll to cycle to the next workspace (default: 30)
+    pub scroll_threshold: Option<f32>,
+    /// Reorder the workspaces of a monitor by dragging them onto each other (default: false)
+    pub drag_to_reorder: Option<bool>,
+    /// Animate the selection highlight when the focused workspace changes (default: None)
+    pub selection_animation: Option<SelectionAnimation>,
+    /// Duration of the selection animation in milliseconds (default: 200)
+    pub selection_animation_duration: Option<u64>,
+    /// Action to perform when a workspace is double clicked. The first click still focuses the
+    /// workspace straight away (default: None)
+    pub on_double_click: Option<WorkspaceDoubleClickAction>,
+    /// Prefix the label of each workspace with its index, e.g. "2: web" (default: false)
+    pub show_index: Option<bool>,
+    /// Names of the workspaces to display first, in the order given here, followed by any other
+    /// workspaces in komorebi's order. Dragging to reorder is disabled when this is set
+    pub order: Option<Vec<String>>,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum WorkspaceDoubleClickAction {
+    /// Move the container which was focused before the first click to the workspace, following it
+    MoveContainer,
+    /// Send the container which was focused before the first click to the workspace, staying on
+    /// the workspace it was sent from
+    SendContainer,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum SelectionAnimation {
+    /// Slide the selection highlight from the previous workspace to the new one
+    Slide,
+    /// Fade the selection highlight out on the previous workspace and in on the new one
+    Fade,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -83,6 +144,20 @@ pub struct KomorebiLayoutConfig {
     pub options: Option<Vec<KomorebiLayout>>,
     /// Display format of the current layout
     pub display: Option<DisplayFormat>,
+    /// Custom icons and labels for layouts, keyed by layout name (e.g. "BSP", "Monocle", "Custom")
+    pub layouts: Option<BTreeMap<String, KomorebiLayoutOverrideConfig>>,
+    /// Custom icons and labels for custom layouts, keyed by the path to the custom layout file
+    pub custom_layouts: Option<BTreeMap<String, KomorebiLayoutOverrideConfig>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct KomorebiLayoutOverrideConfig {
+    /// Optional icon: a path to an image or a text-based glyph (e.g., from Nerd Fonts).
+    /// Note: glyphs require a compatible `font_family`.
+    pub icon: Option<String>,
+    /// Label to display instead of the layout name
+    pub label: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize)]
@@ -96,7 +171,7 @@ pub struct KomorebiWorkspaceLayerConfig {
     pub show_when_tiling: Option<bool>,
 }
 
-#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct KomorebiFocusedContainerConfig {
     /// Enable the Komorebi Focused Container widget
@@ -105,6 +180,143 @@ pub struct KomorebiFocusedContainerConfig {
     pub show_icon: Option<bool>,
     /// Display format of the currently focused container
     pub display: Option<DisplayFormat>,
+    /// Which focused container to show (default: Monitor)
+    pub mode: Option<FocusedContainerMode>,
+    /// Rules used to rewrite window titles before they are displayed, applied in order
+    pub title_rewrite_rules: Option<Vec<TitleRewriteRule>>,
+    /// How the windows of a stacked container are shown (default: Labels)
+    pub stack_style: Option<StackStyle>,
+    /// Action buttons shown next to each window of the focused container
+    pub buttons: Option<Vec<FocusedContainerButton>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TitleRewriteRule {
+    /// Only apply this rule to windows belonging to this executable (e.g. "firefox.exe")
+    pub exe: Option<String>,
+    /// Regular expression to match against the window title (default: the whole title)
+    pub pattern: Option<String>,
+    /// Replacement for the matched text, capture groups can be referenced with $1, $2 etc.
+    pub replacement: String,
+}
+
+/// Compiled title rewrite rules of a focused container widget
+#[derive(Clone, Debug, Default)]
+pub struct TitleRewriteRules(Vec<(Option<String>, Regex, String)>);
+
+impl From<&[TitleRewriteRule]> for TitleRewriteRules {
+    fn from(rules: &[TitleRewriteRule]) -> Self {
+        Self(
+            rules
+                .iter()
+                .filter_map(|rule| {
+                    let pattern = rule.pattern.as_deref().unwrap_or("^.*$");
+                    match Regex::new(pattern) {
+                        Ok(regex) => Some((rule.exe.clone(), regex, rule.replacement.clone())),
+                        Err(error) => {
+                            tracing::error!("invalid title rewrite pattern '{pattern}': {error}");
+                            None
+                        }
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+impl TitleRewriteRules {
+    /// Returns `title` of the window `hwnd` after applying all matching title rewrite rules
+    fn rewrite(&self, hwnd: isize, title: &str) -> String {
+        let mut title = title.to_string();
+
+        if self.0.is_empty() {
+            return title;
+        }
+
+        let exe = Window::from(hwnd).exe().unwrap_or_default();
+        for (rule_exe, regex, replacement) in &self.0 {
+            if rule_exe
+                .as_ref()
+                .is_some_and(|rule_exe| !rule_exe.eq_ignore_ascii_case(&exe))
+            {
+                continue;
+            }
+
+            title = regex.replace_all(&title, replacement.as_str()).into_owned();
+        }
+
+        title
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum FocusedContainerMode {
+    /// Show the focused container of the monitor the bar is on
+    #[default]
+    Monitor,
+    /// Show the globally focused container, whichever monitor it is on, annotated with its
+    /// monitor and workspace
+    Global,
+}
+
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum StackStyle {
+    /// Show each window of a stack as a plain label
+    #[default]
+    Labels,
+    /// Show each window of a stack as a tab with a close button, like the komorebi stackbar
+    Tabs,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum FocusedContainerButton {
+    /// Close the window
+    Close,
+    /// Minimize the window
+    Minimize,
+    /// Toggle the floating state of the window
+    Float,
+}
+
+impl FocusedContainerButton {
+    fn icon(self) -> &'static str {
+        match self {
+            FocusedContainerButton::Close => egui_phosphor::regular::X,
+            FocusedContainerButton::Minimize => egui_phosphor::regular::MINUS,
+            FocusedContainerButton::Float => egui_phosphor::regular::PICTURE_IN_PICTURE,
+        }
+    }
+
+    fn tooltip(self) -> &'static str {
+        match self {
+            FocusedContainerButton::Close => "Close",
+            FocusedContainerButton::Minimize => "Minimize",
+            FocusedContainerButton::Float => "Toggle float",
+        }
+    }
+
+    /// The messages which apply this action to the window `hwnd`
+    fn messages(self, hwnd: isize, mouse_follows_focus: bool) -> Vec<SocketMessage> {
+        match self {
+            FocusedContainerButton::Close => vec![SocketMessage::CloseWindow(hwnd)],
+            FocusedContainerButton::Minimize => vec![SocketMessage::MinimizeWindow(hwnd)],
+            // floating can only be toggled on the focused window
+            FocusedContainerButton::Float if mouse_follows_focus => vec![
+                SocketMessage::MouseFollowsFocus(false),
+                SocketMessage::FocusHwnd(hwnd),
+                SocketMessage::ToggleFloat,
+                SocketMessage::MouseFollowsFocus(true),
+            ],
+            FocusedContainerButton::Float => {
+                vec![SocketMessage::FocusHwnd(hwnd), SocketMessage::ToggleFloat]
+            }
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize)]
@@ -118,6 +330,54 @@ pub struct KomorebiLockedContainerConfig {
     pub show_when_unlocked: Option<bool>,
 }
 
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct KomorebiTaskbarConfig {
+    /// Enable the Komorebi Taskbar widget
+    pub enable: bool,
+    /// Display format of the windows on the focused workspace (default: Icon)
+    pub display: Option<DisplayFormat>,
+    /// Action to perform on a window when it is double clicked. The first click still focuses the
+    /// window straight away (default: None)
+    pub on_double_click: Option<TaskbarDoubleClickAction>,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum TaskbarDoubleClickAction {
+    /// Toggle monocle mode for the container of the window
+    ToggleMonocle,
+    /// Toggle the maximization of the window
+    ToggleMaximize,
+    /// Toggle between tiling and floating the window
+    ToggleFloat,
+}
+
+impl TaskbarDoubleClickAction {
+    fn message(self) -> SocketMessage {
+        match self {
+            Self::ToggleMonocle => SocketMessage::ToggleMonocle,
+            Self::ToggleMaximize => SocketMessage::ToggleMaximize,
+            Self::ToggleFloat => SocketMessage::ToggleFloat,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct KomorebiNotificationsConfig {
+    /// Enable the Komorebi Notifications widget
+    pub enable: bool,
+    /// How long a notification is displayed for, in seconds (default: 3)
+    pub duration: Option<u64>,
+    /// Show a notification when the focused workspace changes (default: true)
+    pub workspace_change: Option<bool>,
+    /// Show a notification when the layout of the focused workspace changes (default: true)
+    pub layout_change: Option<bool>,
+    /// Show a notification when a monitor is connected or disconnected (default: true)
+    pub monitor_connection: Option<bool>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct KomorebiConfigurationSwitcherConfig {
@@ -127,6 +387,129 @@ pub struct KomorebiConfigurationSwitcherConfig {
     pub configurations: BTreeMap<String, String>,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct KomorebiControlMenuConfig {
+    /// Enable the Komorebi Control Menu widget
+    pub enable: bool,
+    /// Display format of the menu button (default: Icon)
+    pub display: Option<DisplayFormat>,
+    /// Actions to show in the menu, in order (default: all)
+    pub actions: Option<Vec<KomorebiControlAction>>,
+    /// Command used to restart komorebi (default: "komorebic stop && komorebic start")
+    pub restart_command: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct KomorebiPauseConfig {
+    /// Enable the Komorebi Pause widget
+    pub enable: bool,
+    /// Display format of the pause state (default: Icon)
+    pub display: Option<DisplayFormat>,
+    /// Show the widget even if tiling is not paused (default: true)
+    pub show_when_running: Option<bool>,
+    /// Flash the widget for a moment when tiling is paused or resumed (default: true)
+    pub flash_on_change: Option<bool>,
+}
+
+/// How long the Pause widget flashes for after the pause state changes
+const PAUSE_FLASH_DURATION: Duration = Duration::from_secs(2);
+/// How long each on and off phase of the Pause widget flash lasts
+const PAUSE_FLASH_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum KomorebiControlAction {
+    /// Pause or resume komorebi
+    TogglePause,
+    /// Retile the focused workspace
+    Retile,
+    /// Reload the komorebi.json configuration file
+    ReloadConfiguration,
+    /// Restart komorebi
+    Restart,
+    /// Stop komorebi
+    Stop,
+}
+
+impl KomorebiControlAction {
+    const ALL: [KomorebiControlAction; 5] = [
+        KomorebiControlAction::TogglePause,
+        KomorebiControlAction::Retile,
+        KomorebiControlAction::ReloadConfiguration,
+        KomorebiControlAction::Restart,
+        KomorebiControlAction::Stop,
+    ];
+
+    fn icon(&self, is_paused: bool) -> &'static str {
+        match self {
+            KomorebiControlAction::TogglePause if is_paused => egui_phosphor::regular::PLAY,
+            KomorebiControlAction::TogglePause => egui_phosphor::regular::PAUSE,
+            KomorebiControlAction::Retile => egui_phosphor::regular::SQUARES_FOUR,
+            KomorebiControlAction::ReloadConfiguration => egui_phosphor::regular::FILE_ARROW_UP,
+            KomorebiControlAction::Restart => egui_phosphor::regular::ARROW_CLOCKWISE,
+            KomorebiControlAction::Stop => egui_phosphor::regular::STOP,
+        }
+    }
+
+    fn label(&self, is_paused: bool) -> &'static str {
+        match self {
+            KomorebiControlAction::TogglePause if is_paused => "Resume",
+            KomorebiControlAction::TogglePause => "Pause",
+            KomorebiControlAction::Retile => "Retile",
+            KomorebiControlAction::ReloadConfiguration => "Reload configuration",
+            KomorebiControlAction::Restart => "Restart komorebi",
+            KomorebiControlAction::Stop => "Stop komorebi",
+        }
+    }
+
+    /// Runs the action, reloading the configuration file komorebi was started with, which is
+    /// `configuration_path` when komorebi has reported it
+    fn execute(&self, restart_command: Option<&str>, configuration_path: Option<&Path>) {
+        let messages = match self {
+            KomorebiControlAction::TogglePause => vec![SocketMessage::TogglePause],
+            KomorebiControlAction::Retile => {
+                vec![SocketMessage::FocusMonitorAtCursor, SocketMessage::Retile]
+            }
+            KomorebiControlAction::ReloadConfiguration => {
+                let configuration_path = match configuration_path {
+                    Some(path) => path.to_path_buf(),
+                    None => {
+                        let home_dir = std::env::var("KOMOREBI_CONFIG_HOME")
+                            .map(|home_path| home_path.replace_env())
+                            .ok()
+                            .or_else(dirs::home_dir);
+
+                        let Some(home_dir) = home_dir else {
+                            tracing::error!("could not find the komorebi configuration directory");
+                            return;
+                        };
+
+                        home_dir.join("komorebi.json")
+                    }
+                };
+
+                vec![SocketMessage::ReloadStaticConfiguration(configuration_path)]
+            }
+            KomorebiControlAction::Restart => {
+                let command = restart_command.unwrap_or("komorebic stop && komorebic start");
+
+                if let Err(error) = Command::new("cmd").args(["/C", command]).spawn() {
+                    tracing::error!("failed to restart komorebi with '{}': {}", command, error);
+                }
+
+                return;
+            }
+            KomorebiControlAction::Stop => vec![SocketMessage::Stop],
+        };
+
+        if komorebi_client::send_batch(messages).is_err() {
+            tracing::error!("could not send message to komorebi: {:?}", self);
+        }
+    }
+}
+
 impl From<&KomorebiConfig> for Komorebi {
     fn from(value: &KomorebiConfig) -> Self {
         let configuration_switcher =
@@ -149,24 +532,75 @@ impl From<&KomorebiConfig> for Komorebi {
                 workspaces: vec![],
                 hide_empty_workspaces: value
                     .workspaces
+                    .as_ref()
                     .map(|w| w.hide_empty_workspaces)
                     .unwrap_or_default(),
+                show_all_monitors: value
+                    .workspaces
+                    .as_ref()
+                    .and_then(|w| w.show_all_monitors)
+                    .unwrap_or_default(),
+                all_monitor_workspaces: vec![],
+                hover_preview: value
+                    .workspaces
+                    .as_ref()
+                    .and_then(|w| w.hover_preview)
+                    .unwrap_or_default(),
+                workspace_previews: HashMap::new(),
                 mouse_follows_focus: true,
+                focused_monitor_workspace: (0, 0),
+                always_on_top_focused: false,
                 work_area_offset: None,
                 focused_container_information: (
                     false,
                     KomorebiNotificationStateContainerInformation::EMPTY,
                 ),
                 stack_accent: None,
-                monitor_index: MONITOR_INDEX.load(Ordering::SeqCst),
+                monitor_index: 0,
                 monitor_usr_idx_map: HashMap::new(),
+                custom_layout: None,
+                taskbar: vec![],
+                show_taskbar: value.taskbar.is_some_and(|t| t.enable),
+                show_global_focused_container: value.focused_container.as_ref().is_some_and(|f| {
+                    f.enable && f.mode.unwrap_or_default() == FocusedContainerMode::Global
+                }),
+                global_focused_container_information: None,
+                notifications: value.notifications.filter(|n| n.enable),
+                notification: None,
+                monitor_count: 0,
+                is_paused: false,
+                pause_changed_at: None,
+                configuration_path: None,
+                disconnected: false,
+                privacy_mode: false,
+                privacy_sensitive_windows: HashSet::new(),
+                title_rewrite_rules: value
+                    .focused_container
+                    .as_ref()
+                    .and_then(|f| f.title_rewrite_rules.as_deref())
+                    .map(TitleRewriteRules::from)
+                    .unwrap_or_default(),
             })),
-            workspaces: value.workspaces,
+            workspaces: value.workspaces.clone(),
             layout: value.layout.clone(),
-            focused_container: value.focused_container,
+            layout_overrides: value
+                .layout
+                .as_ref()
+                .map(KomorebiLayoutOverrides::from)
+                .unwrap_or_default(),
+            focused_container: value.focused_container.clone(),
             workspace_layer: value.workspace_layer,
             locked_container: value.locked_container,
             configuration_switcher,
+            configuration_errors: HashMap::new(),
+            configuration_entries: Vec::new(),
+            configuration_checked_at: None,
+            taskbar: value.taskbar,
+            notifications: value.notifications,
+            control_menu: value.control_menu.clone(),
+            pause: value.pause,
+            workspace_click_origin: None,
+            workspace_scroll_delta: 0.0,
         }
     }
 }
@@ -176,10 +610,65 @@ pub struct Komorebi {
     pub komorebi_notification_state: Rc<RefCell<KomorebiNotificationState>>,
     pub workspaces: Option<KomorebiWorkspacesConfig>,
     pub layout: Option<KomorebiLayoutConfig>,
+    pub layout_overrides: KomorebiLayoutOverrides,
     pub focused_container: Option<KomorebiFocusedContainerConfig>,
     pub workspace_layer: Option<KomorebiWorkspaceLayerConfig>,
     pub locked_container: Option<KomorebiLockedContainerConfig>,
     pub configuration_switcher: Option<KomorebiConfigurationSwitcherConfig>,
+    /// Validation errors of the configuration switcher entries, keyed by path along with the
+    /// modification time of the file they were checked at
+    pub configuration_errors: HashMap<PathBuf, (Option<SystemTime>, Option<String>)>,
+    /// The configuration switcher entries which exist on disk as of the last check
+    pub configuration_entries: Vec<ConfigurationEntry>,
+    /// When the configuration switcher entries were last checked on disk
+    pub configuration_checked_at: Option<Instant>,
+    pub taskbar: Option<KomorebiTaskbarConfig>,
+    pub notifications: Option<KomorebiNotificationsConfig>,
+    pub control_menu: Option<KomorebiControlMenuConfig>,
+    pub pause: Option<KomorebiPauseConfig>,
+    /// The focused monitor and workspace indices when a workspace was last clicked, which is
+    /// where the container acted on by a double click comes from
+    pub workspace_click_origin: Option<(usize, usize)>,
+    /// Scrolling over the workspaces which hasn't yet added up to cycling a workspace
+    pub workspace_scroll_delta: f32,
+}
+
+/// How often the files of the configuration switcher entries are checked again, rather than on
+/// every frame
+const CONFIGURATION_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A configuration switcher entry which exists on disk, resolved to its canonical path
+#[derive(Clone, Debug)]
+pub struct ConfigurationEntry {
+    pub name: String,
+    pub path: PathBuf,
+    /// The reason the configuration can't be loaded, if any
+    pub error: Option<String>,
+}
+
+/// Returns the reason the configuration file at `path` can't be loaded, if any, only reading
+/// the file again when it has been modified since it was last validated
+fn validate_configuration(
+    errors: &mut HashMap<PathBuf, (Option<SystemTime>, Option<String>)>,
+    path: &PathBuf,
+) -> Option<String> {
+    let modified = std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok();
+
+    if let Some((validated_at, error)) = errors.get(path) {
+        if *validated_at == modified {
+            return error.clone();
+        }
+    }
+
+    let error = komorebi_client::StaticConfig::read(path)
+        .err()
+        .map(|error| error.to_string());
+
+    errors.insert(path.clone(), (modified, error.clone()));
+
+    error
 }
 
 impl BarWidget for Komorebi {
@@ -188,23 +677,260 @@ impl BarWidget for Komorebi {
         let icon_size = Vec2::splat(config.icon_font_id.size);
         let text_size = Vec2::splat(config.text_font_id.size);
 
-        if let Some(workspaces) = self.workspaces {
+        // none of the state can be trusted until komorebi can be reached again
+        if komorebi_notification_state.disconnected {
+            let mut layout_job = LayoutJob::simple(
+                egui_phosphor::regular::LINK_BREAK.to_string(),
+                config.icon_font_id.clone(),
+                ctx.style().visuals.warn_fg_color,
+                100.0,
+            );
+
+            layout_job.append(
+                "Disconnected",
+                10.0,
+                TextFormat {
+                    font_id: config.text_font_id.clone(),
+                    color: ctx.style().visuals.warn_fg_color,
+                    valign: Align::Center,
+                    ..Default::default()
+                },
+            );
+
+            config.apply_on_widget(false, ui, |ui| {
+                if SelectableFrame::new(false)
+                    .show(ui, |ui| ui.add(Label::new(layout_job).selectable(false)))
+                    .on_hover_text("Lost the connection to komorebi, click to reconnect")
+                    .clicked()
+                {
+                    crate::request_reconnect();
+                }
+            });
+
+            return;
+        }
+
+        if let Some(control_menu) = &self.control_menu {
+            if control_menu.enable {
+                let mut show_menu = RenderConfig::load_show_komorebi_control_menu();
+                let is_paused = komorebi_notification_state.is_paused;
+                let configuration_path = komorebi_notification_state.configuration_path.clone();
+                let format = control_menu.display.unwrap_or(DisplayFormat::Icon);
+                let icon_font_id = config.icon_font_id.clone();
+
+                let mut layout_job = LayoutJob::simple(
+                    if format != DisplayFormat::Text {
+                        egui_phosphor::regular::TREE.to_string()
+                    } else {
+                        String::new()
+                    },
+                    config.icon_font_id.clone(),
+                    ctx.style().visuals.selection.stroke.color,
+                    100.0,
+                );
+
+                if format != DisplayFormat::Icon {
+                    layout_job.append(
+                        "komorebi",
+                        10.0,
+                        TextFormat {
+                            font_id: config.text_font_id.clone(),
+                            color: ctx.style().visuals.text_color(),
+                            valign: Align::Center,
+                            ..Default::default()
+                        },
+                    );
+                }
+
+                config.apply_on_widget(false, ui, |ui| {
+                    if SelectableFrame::new(show_menu)
+                        .show(ui, |ui| ui.add(Label::new(layout_job).selectable(false)))
+                        .clicked()
+                    {
+                        show_menu = !show_menu;
+                    }
+
+                    if show_menu {
+                        let actions = control_menu
+                            .actions
+                            .as_deref()
+                            .unwrap_or(&KomorebiControlAction::ALL);
+
+                        for action in actions {
+                            if SelectableFrame::new(false)
+                                .show(ui, |ui| {
+                                    ui.add(
+                                        Label::new(
+                                            RichText::new(action.icon(is_paused))
+                                                .font(icon_font_id.clone()),
+                                        )
+                                        .selectable(false),
+                                    )
+                                })
+                                .on_hover_text(action.label(is_paused))
+                                .clicked()
+                            {
+                                action.execute(
+                                    control_menu.restart_command.as_deref(),
+                                    configuration_path.as_deref(),
+                                );
+                                show_menu = false;
+                            }
+                        }
+                    }
+                });
+
+                RenderConfig::store_show_komorebi_control_menu(show_menu);
+            }
+        }
+
+        if let Some(pause) = self.pause {
+            let is_paused = komorebi_notification_state.is_paused;
+
+            if pause.enable && (is_paused || pause.show_when_running.unwrap_or(true)) {
+                let format = pause.display.unwrap_or(DisplayFormat::Icon);
+
+                // flash the widget after the state changes so that accidental pausing is noticed
+                let flash_elapsed = komorebi_notification_state
+                    .pause_changed_at
+                    .filter(|_| pause.flash_on_change.unwrap_or(true))
+                    .map(|changed_at| changed_at.elapsed())
+                    .filter(|elapsed| *elapsed < PAUSE_FLASH_DURATION);
+
+                let flashing = flash_elapsed.is_some_and(|elapsed| {
+                    (elapsed.as_millis() / PAUSE_FLASH_INTERVAL.as_millis()) % 2 == 0
+                });
+
+                let auto_text_color = config.auto_select_text.filter(|_| flashing);
+
+                let mut layout_job = LayoutJob::simple(
+                    if format != DisplayFormat::Text {
+                        if is_paused {
+                            egui_phosphor::regular::PAUSE_CIRCLE.to_string()
+                        } else {
+                            egui_phosphor::regular::PLAY_CIRCLE.to_string()
+                        }
+                    } else {
+                        String::new()
+                    },
+                    config.icon_font_id.clone(),
+                    auto_text_color.unwrap_or(ctx.style().visuals.selection.stroke.color),
+                    100.0,
+                );
+
+                if format != DisplayFormat::Icon {
+                    layout_job.append(
+                        if is_paused { "Paused" } else { "Tiling" },
+                        10.0,
+                        TextFormat {
+                            font_id: config.text_font_id.clone(),
+                            color: auto_text_color.unwrap_or(ctx.style().visuals.text_color()),
+                            valign: Align::Center,
+                            ..Default::default()
+                        },
+                    );
+                }
+
+                let auto_focus_fill = config.auto_select_fill;
+
+                config.apply_on_widget(false, ui, |ui| {
+                    if SelectableFrame::new_auto(flashing, auto_focus_fill)
+                        .show(ui, |ui| ui.add(Label::new(layout_job).selectable(false)))
+                        .on_hover_text(if is_paused {
+                            "Resume tiling"
+                        } else {
+                            "Pause tiling"
+                        })
+                        .clicked()
+                        && komorebi_client::send_message(&SocketMessage::TogglePause).is_err()
+                    {
+                        tracing::error!("could not send message to komorebi: TogglePause");
+                    }
+                });
+
+                if let Some(elapsed) = flash_elapsed {
+                    ctx.request_repaint_after(
+                        PAUSE_FLASH_INTERVAL
+                            - Duration::from_millis(
+                                (elapsed.as_millis() % PAUSE_FLASH_INTERVAL.as_millis()) as u64,
+                            ),
+                    );
+                }
+            }
+        }
+
+        if let Some(workspaces) = &self.workspaces {
             if workspaces.enable {
                 let mut update = None;
 
                 if !komorebi_notification_state.workspaces.is_empty() {
                     let format = workspaces.display.unwrap_or(DisplayFormat::Text.into());
+                    let scroll_to_cycle = workspaces.scroll_to_cycle.unwrap_or_default();
+                    let scroll_threshold = workspaces
+                        .scroll_threshold
+                        .unwrap_or(30.0)
+                        .clamp(10.0, 300.0);
+                    let workspace_order = workspaces.order.as_deref();
+                    let show_index = workspaces.show_index.unwrap_or_default();
+                    // the displayed order doesn't change when the workspaces are swapped
+                    let drag_to_reorder =
+                        workspaces.drag_to_reorder.unwrap_or_default() && workspace_order.is_none();
+                    let on_double_click = workspaces.on_double_click;
+                    let selection_animation = workspaces.selection_animation;
+                    let selection_animation_duration =
+                        workspaces.selection_animation_duration.unwrap_or(200) as f32 / 1000.0;
 
                     config.apply_on_widget(false, ui, |ui| {
-                        for (i, (ws, containers, _, should_show)) in
-                            komorebi_notification_state.workspaces.iter().enumerate()
-                        {
+                        let show_all_monitors = komorebi_notification_state.show_all_monitors;
+                        let monitor_groups = if show_all_monitors {
+                            komorebi_notification_state
+                                .all_monitor_workspaces
+                                .iter()
+                                .map(|(monitor_idx, selected, workspaces)| (*monitor_idx, selected, workspaces))
+                                .collect::<Vec<_>>()
+                        } else {
+                            vec![(
+                                komorebi_notification_state.monitor_index,
+                                &komorebi_notification_state.selected_workspace,
+                                &komorebi_notification_state.workspaces,
+                            )]
+                        };
+
+                        let mut over_workspaces = false;
+
+                        for (monitor_idx, selected_workspace, workspaces) in monitor_groups {
+                        if show_all_monitors {
+                            ui.add(
+                                Label::new(RichText::new(format!("M{}", monitor_idx + 1)).small().weak())
+                                    .selectable(false),
+                            );
+                        }
+
+                        let mut button_rects = vec![];
+                        let mut dropped = None;
+                        // shapes are reserved before the buttons so that the animated highlight
+                        // is painted behind them
+                        let slide_highlight = ui.painter().add(Shape::Noop);
+                        let mut fade_highlights = vec![];
+                        let mut selected_rect = None;
+
+                        // `i` is always the index of the workspace in komorebi, whatever order it
+                        // is displayed in
+                        for i in display_order(workspaces, workspace_order) {
+                            let (ws, containers, _, should_show) = &workspaces[i];
                             if *should_show {
-                            let is_selected = komorebi_notification_state.selected_workspace.eq(ws);
+                            let is_selected = selected_workspace.eq(ws);
+                            let label = if show_index {
+                                format!("{}: {ws}", i + 1)
+                            } else {
+                                ws.to_string()
+                            };
+                            let fade_highlight = ui.painter().add(Shape::Noop);
 
-                            if SelectableFrame::new(
-                                is_selected,
+                            let mut response = SelectableFrame::new(
+                                is_selected && selection_animation.is_none(),
                             )
+                            .sense(if drag_to_reorder { Sense::click_and_drag() } else { Sense::click() })
                             .show(ui, |ui| {
                                 let mut has_icon = false;
 
@@ -256,69 +982,210 @@ impl BarWidget for Komorebi {
                                     painter.rect_stroke(rect, rounding, stroke, StrokeKind::Outside);
                                     painter.line_segment([c - vec2(r, r), c + vec2(r, r)], stroke);
 
-                                    response.on_hover_text(ws.to_string())
+                                    response.on_hover_text(label.as_str())
                                 // add hover text when there are only icons
                                 } else if match format {
                                     WorkspacesDisplayFormat::AllIcons | WorkspacesDisplayFormat::Existing(DisplayFormat::Icon) => has_icon,
                                     _ => false,
                                 } {
-                                    ui.response().on_hover_text(ws.to_string())
+                                    ui.response().on_hover_text(label.as_str())
                                 // add label only
                                 } else if (format != WorkspacesDisplayFormat::AllIconsAndTextOnSelected && format != DisplayFormat::IconAndTextOnSelected.into())
                                     || (is_selected && matches!(format, WorkspacesDisplayFormat::AllIconsAndTextOnSelected | WorkspacesDisplayFormat::Existing(DisplayFormat::IconAndTextOnSelected)))
                                 {
                                      if is_selected {
-                                        ui.add(Label::new(RichText::new(ws.to_string()).color(ctx.style().visuals.selection.stroke.color)).selectable(false))
+                                        ui.add(Label::new(RichText::new(&label).color(ctx.style().visuals.selection.stroke.color)).selectable(false))
                                     }
                                     else {
-                                        ui.add(Label::new(ws.to_string()).selectable(false))
+                                        ui.add(Label::new(label.as_str()).selectable(false))
                                     }
                                 } else {
                                     ui.response()
                                 }
-                            })
-                            .clicked()
+                            });
+
+                            if let Some(preview) = komorebi_notification_state
+                                .workspace_previews
+                                .get(&(monitor_idx, i))
                             {
-                                update = Some(ws.to_string());
+                                response = response.on_hover_ui(|ui| {
+                                    workspace_preview_ui(ctx, ui, ws, preview, text_size);
+                                });
+                            }
 
-                                if komorebi_notification_state.mouse_follows_focus {
-                                    if komorebi_client::send_batch([
-                                        SocketMessage::MouseFollowsFocus(false),
-                                        SocketMessage::FocusMonitorWorkspaceNumber(
-                                            komorebi_notification_state.monitor_index,
-                                            i,
-                                        ),
-                                        SocketMessage::MouseFollowsFocus(true),
-                                    ])
-                                        .is_err()
-                                    {
-                                        tracing::error!(
-                                            "could not send the following batch of messages to komorebi:\n
-                                            MouseFollowsFocus(false)\n
-                                            FocusMonitorWorkspaceNumber({}, {})\n
-                                            MouseFollowsFocus(true)\n",
-                                            komorebi_notification_state.monitor_index,
-                                            i,
+                            button_rects.push((i, response.rect));
+                            fade_highlights.push((i, is_selected, response.rect, fade_highlight));
+
+                            if is_selected {
+                                selected_rect = Some(response.rect);
+                            }
+
+                            if response.dragged() {
+                                ui.ctx().set_cursor_icon(CursorIcon::Grabbing);
+                            }
+
+                            if response.drag_stopped() {
+                                dropped = ui.input(|i| i.pointer.interact_pos()).map(|pos| (i, pos));
+                            }
+
+                            // the first click of a double click has already focused the
+                            // workspace, so the second one only performs the double click action
+                            if response.double_clicked() {
+                                if let (Some(action), Some(origin)) =
+                                    (on_double_click, self.workspace_click_origin)
+                                {
+                                    double_click_workspace(
+                                        action,
+                                        origin,
+                                        (monitor_idx, i),
+                                        komorebi_notification_state.mouse_follows_focus,
+                                    );
+                                }
+                            } else if response.clicked() {
+                                self.workspace_click_origin =
+                                    Some(komorebi_notification_state.focused_monitor_workspace);
+
+                                if monitor_idx == komorebi_notification_state.monitor_index {
+                                    update = Some(ws.to_string());
+                                }
+
+                                focus_monitor_workspace(
+                                    monitor_idx,
+                                    i,
+                                    komorebi_notification_state.mouse_follows_focus,
+                                );
+                            }
+                            }
+                        }
+
+                        let highlight_fill = ui.style().visuals.selection.bg_fill;
+                        let highlight_corner_radius =
+                            ui.style().visuals.widgets.inactive.corner_radius;
+                        let animation_id = ui.id().with(("workspace_selection", monitor_idx));
+
+                        match selection_animation {
+                            Some(SelectionAnimation::Slide) => {
+                                if let Some(rect) = selected_rect {
+                                    let animate = |name: &str, value: f32| {
+                                        ctx.animate_value_with_time(
+                                            animation_id.with(name),
+                                            value,
+                                            selection_animation_duration,
+                                        )
+                                    };
+
+                                    let rect = eframe::egui::Rect::from_min_max(
+                                        pos2(animate("min_x", rect.min.x), animate("min_y", rect.min.y)),
+                                        pos2(animate("max_x", rect.max.x), animate("max_y", rect.max.y)),
+                                    );
+
+                                    ui.painter().set(
+                                        slide_highlight,
+                                        Shape::rect_filled(rect, highlight_corner_radius, highlight_fill),
+                                    );
+                                }
+                            }
+                            Some(SelectionAnimation::Fade) => {
+                                for (i, is_selected, rect, shape) in fade_highlights {
+                                    let opacity = ctx.animate_bool_with_time(
+                                        animation_id.with(i),
+                                        is_selected,
+                                        selection_animation_duration,
+                                    );
+
+                                    if opacity > 0.0 {
+                                        ui.painter().set(
+                                            shape,
+                                            Shape::rect_filled(
+                                                rect,
+                                                highlight_corner_radius,
+                                                highlight_fill.gamma_multiply(opacity),
+                                            ),
                                         );
                                     }
-                                } else if komorebi_client::send_batch([
-                                    SocketMessage::FocusMonitorWorkspaceNumber(
-                                        komorebi_notification_state.monitor_index,
-                                        i,
-                                    ),
-                                ])
+                                }
+                            }
+                            None => {}
+                        }
+
+                        // swap the dragged workspace with the one it was dropped on
+                        if let Some((source, pos)) = dropped {
+                            if let Some((target, _)) =
+                                button_rects.iter().find(|(_, rect)| rect.contains(pos))
+                            {
+                                if *target != source
+                                    && komorebi_client::send_message(&SocketMessage::SwapWorkspaces(
+                                        monitor_idx,
+                                        source,
+                                        *target,
+                                    ))
                                     .is_err()
                                 {
                                     tracing::error!(
-                                        "could not send the following batch of messages to komorebi:\n
-                                        FocusMonitorWorkspaceNumber({}, {})\n",
-                                        komorebi_notification_state.monitor_index,
-                                        i,
+                                        "could not send message to komorebi: SwapWorkspaces({}, {}, {})",
+                                        monitor_idx,
+                                        source,
+                                        target,
                                     );
                                 }
                             }
+                        }
+
+                        let group_rect = button_rects
+                            .iter()
+                            .fold(eframe::egui::Rect::NOTHING, |group, (_, rect)| group.union(*rect));
+
+                        let mut scroll = 0.0;
+
+                        if scroll_to_cycle && ui.rect_contains_pointer(group_rect) {
+                            over_workspaces = true;
+
+                            // Do not store more than three ticks worth of scrolling
+                            self.workspace_scroll_delta = (self.workspace_scroll_delta
+                                + ui.input(|i| i.smooth_scroll_delta.y))
+                            .clamp(-scroll_threshold * 3.0, scroll_threshold * 3.0);
+
+                            // When the accumulated scroll passes the threshold, trigger a tick
+                            if self.workspace_scroll_delta.abs() >= scroll_threshold {
+                                scroll = self.workspace_scroll_delta;
+                                self.workspace_scroll_delta -=
+                                    scroll_threshold * self.workspace_scroll_delta.signum();
+                            }
+                        }
+
+                        if scroll != 0.0 {
+                            let visible = display_order(workspaces, workspace_order)
+                                .into_iter()
+                                .filter(|i| workspaces[*i].3)
+                                .collect::<Vec<_>>();
+
+                            if let Some(position) = visible
+                                .iter()
+                                .position(|i| selected_workspace.eq(&workspaces[*i].0))
+                            {
+                                // scrolling up moves to the previous workspace
+                                let next = if scroll > 0.0 {
+                                    visible[(position + visible.len() - 1) % visible.len()]
+                                } else {
+                                    visible[(position + 1) % visible.len()]
+                                };
+
+                                if monitor_idx == komorebi_notification_state.monitor_index {
+                                    update = Some(workspaces[next].0.clone());
+                                }
+
+                                focus_monitor_workspace(
+                                    monitor_idx,
+                                    next,
+                                    komorebi_notification_state.mouse_follows_focus,
+                                );
                             }
                         }
+                        }
+
+                        if !over_workspaces {
+                            self.workspace_scroll_delta = 0.0;
+                        }
                     });
                 }
 
@@ -337,9 +1204,17 @@ impl BarWidget for Komorebi {
                     .map(|(_, _, layer, _)| layer);
 
                 if let Some(layer) = layer {
+                    let always_on_top = komorebi_notification_state.always_on_top_focused;
+                    let layer_name = if always_on_top {
+                        String::from("Always On Top")
+                    } else {
+                        layer.to_string()
+                    };
+
                     if (layer_config.show_when_tiling.unwrap_or_default()
                         && matches!(layer, WorkspaceLayer::Tiling))
                         || matches!(layer, WorkspaceLayer::Floating)
+                        || always_on_top
                     {
                         let display_format = layer_config.display.unwrap_or(DisplayFormat::Text);
                         let size = Vec2::splat(config.icon_font_id.size);
@@ -348,7 +1223,39 @@ impl BarWidget for Komorebi {
                             let layer_frame = SelectableFrame::new(false)
                                 .show(ui, |ui| {
                                     if display_format != DisplayFormat::Text {
-                                        if matches!(layer, WorkspaceLayer::Tiling) {
+                                        if always_on_top {
+                                            let (response, painter) =
+                                                ui.allocate_painter(size, Sense::hover());
+                                            let color = ctx.style().visuals.selection.stroke.color;
+                                            let stroke = Stroke::new(1.0, color);
+                                            let mut rect = response.rect;
+                                            let corner =
+                                                CornerRadius::same((rect.width() * 0.1) as u8);
+                                            rect = rect.shrink(stroke.width);
+
+                                            // always on top
+                                            let mut rect_below = response.rect;
+                                            rect_below.set_width(rect.width() * 0.98);
+                                            rect_below.set_height(rect.height() * 0.98);
+                                            let mut rect_above = rect_below;
+                                            rect_above.set_width(rect.width() * 0.5);
+                                            rect_above.set_height(rect.height() * 0.5);
+                                            rect_below = rect_below.translate(Vec2::new(
+                                                rect.width() * 0.01 + stroke.width,
+                                                rect.width() * 0.01 + stroke.width,
+                                            ));
+                                            rect_above = rect_above.translate(Vec2::new(
+                                                rect.width() * 0.25 + stroke.width,
+                                                rect.width() * 0.25 + stroke.width,
+                                            ));
+                                            painter.rect_stroke(
+                                                rect_below,
+                                                corner,
+                                                stroke,
+                                                StrokeKind::Outside,
+                                            );
+                                            painter.rect_filled(rect_above, corner, color);
+                                        } else if matches!(layer, WorkspaceLayer::Tiling) {
                                             let (response, painter) =
                                                 ui.allocate_painter(size, Sense::hover());
                                             let color = ctx.style().visuals.selection.stroke.color;
@@ -412,10 +1319,10 @@ impl BarWidget for Komorebi {
                                     }
 
                                     if display_format != DisplayFormat::Icon {
-                                        ui.add(Label::new(layer.to_string()).selectable(false));
+                                        ui.add(Label::new(&layer_name).selectable(false));
                                     }
                                 })
-                                .on_hover_text(layer.to_string());
+                                .on_hover_text(&layer_name);
 
                             if layer_frame.clicked()
                                 && komorebi_client::send_batch([
@@ -449,11 +1356,14 @@ impl BarWidget for Komorebi {
                     .iter()
                     .position(|o| komorebi_notification_state.selected_workspace.eq(&o.0));
 
-                komorebi_notification_state.layout.show(
+                let state = &mut *komorebi_notification_state;
+                state.layout.show(
                     ctx,
                     ui,
                     config,
                     layout_config,
+                    &self.layout_overrides,
+                    state.custom_layout.as_ref(),
                     workspace_idx,
                 );
             }
@@ -461,19 +1371,55 @@ impl BarWidget for Komorebi {
 
         if let Some(configuration_switcher) = &self.configuration_switcher {
             if configuration_switcher.enable {
-                for (name, location) in configuration_switcher.configurations.iter() {
-                    let path = PathBuf::from(location);
-                    if path.is_file() {
-                        config.apply_on_widget(false, ui, |ui| {
-                            if SelectableFrame::new(false)
-                                .show(ui, |ui| ui.add(Label::new(name).selectable(false)))
-                                .clicked()
-                            {
-                                let canonicalized =
-                                    dunce::canonicalize(path.clone()).unwrap_or(path);
+                if self
+                    .configuration_checked_at
+                    .is_none_or(|checked_at| checked_at.elapsed() >= CONFIGURATION_CHECK_INTERVAL)
+                {
+                    self.configuration_entries = configuration_switcher
+                        .configurations
+                        .iter()
+                        .filter_map(|(name, location)| {
+                            let path = PathBuf::from(location);
+                            if !path.is_file() {
+                                return None;
+                            }
+
+                            let path = dunce::canonicalize(path.clone()).unwrap_or(path);
+                            let error =
+                                validate_configuration(&mut self.configuration_errors, &path);
+
+                            Some(ConfigurationEntry {
+                                name: name.clone(),
+                                path,
+                                error,
+                            })
+                        })
+                        .collect();
+                    self.configuration_checked_at = Some(Instant::now());
+                }
 
+                for ConfigurationEntry { name, path, error } in &self.configuration_entries {
+                    let is_active = komorebi_notification_state
+                        .configuration_path
+                        .as_ref()
+                        .is_some_and(|active| active == path);
+
+                    config.apply_on_widget(false, ui, |ui| {
+                        ui.add_enabled_ui(error.is_none(), |ui| {
+                            let response = SelectableFrame::new(is_active)
+                                .show(ui, |ui| ui.add(Label::new(name).selectable(false)));
+
+                            let response = match error {
+                                Some(error) => response.on_disabled_hover_text(error),
+                                None if is_active => {
+                                    response.on_hover_text(format!("{} (active)", path.display()))
+                                }
+                                None => response.on_hover_text(path.display().to_string()),
+                            };
+
+                            if response.clicked() && !is_active {
                                 if komorebi_client::send_message(
-                                    &SocketMessage::ReplaceConfiguration(canonicalized),
+                                    &SocketMessage::ReplaceConfiguration(path.clone()),
                                 )
                                 .is_err()
                                 {
@@ -483,7 +1429,7 @@ impl BarWidget for Komorebi {
                                 }
                             }
                         });
-                    }
+                    });
                 }
             }
         }
@@ -553,29 +1499,78 @@ impl BarWidget for Komorebi {
             }
         }
 
-        if let Some(focused_container_config) = self.focused_container {
+        if let Some(focused_container_config) = &self.focused_container {
             if focused_container_config.enable {
-                let titles = &komorebi_notification_state
-                    .focused_container_information
-                    .1
-                    .titles;
+                let (annotation, container_information) =
+                    match &komorebi_notification_state.global_focused_container_information {
+                        Some((annotation, information))
+                            if focused_container_config.mode.unwrap_or_default()
+                                == FocusedContainerMode::Global =>
+                        {
+                            (Some(annotation), information)
+                        }
+                        _ => (
+                            None,
+                            &komorebi_notification_state.focused_container_information.1,
+                        ),
+                    };
+
+                let titles = &container_information.titles;
+                let icon_font_id = config.icon_font_id.clone();
+                let max_label_width = config.max_label_width;
 
                 if !titles.is_empty() {
                     config.apply_on_widget(false, ui, |ui| {
-                        let icons = &komorebi_notification_state
-                            .focused_container_information.1
-                            .icons;
-                        let focused_window_idx = komorebi_notification_state
-                            .focused_container_information.1
-                            .focused_window_idx;
+                        if let Some(annotation) = annotation {
+                            ui.add(
+                                Label::new(RichText::new(annotation).small().weak())
+                                    .selectable(false),
+                            );
+                        }
+
+                        let icons = &container_information.icons;
+                        let focused_window_idx = container_information.focused_window_idx;
 
                         let iter = titles.iter().zip(icons.iter());
                         let len = iter.len();
+                        let tabs = len > 1
+                            && focused_container_config.stack_style.unwrap_or_default()
+                                == StackStyle::Tabs;
+
+                        // tabs always get a close button
+                        let mut buttons =
+                            focused_container_config.buttons.clone().unwrap_or_default();
+                        if tabs && !buttons.contains(&FocusedContainerButton::Close) {
+                            buttons.push(FocusedContainerButton::Close);
+                        }
 
                         for (i, (title, icon)) in iter.enumerate() {
                             let selected = i == focused_window_idx && len != 1;
                             let text_color = if selected { ctx.style().visuals.selection.stroke.color } else { ui.style().visuals.text_color() };
 
+                            let tab_frame = if tabs {
+                                let visuals = &ui.style().visuals;
+                                let rounding = visuals.widgets.inactive.corner_radius.nw;
+
+                                Frame::NONE
+                                    .stroke(visuals.widgets.noninteractive.bg_stroke)
+                                    .corner_radius(CornerRadius {
+                                        nw: rounding,
+                                        ne: rounding,
+                                        sw: 0,
+                                        se: 0,
+                                    })
+                                    .fill(if selected {
+                                        visuals.selection.bg_fill
+                                    } else {
+                                        visuals.faint_bg_color
+                                    })
+                                    .inner_margin(Margin::symmetric(2, 0))
+                            } else {
+                                Frame::NONE
+                            };
+
+                            tab_frame.show(ui, |ui| {
                             if SelectableFrame::new(selected)
                                 .show(ui, |ui| {
                                     // handle legacy setting
@@ -623,7 +1618,7 @@ impl BarWidget for Komorebi {
 
                                         custom_ui.add_sized_left_to_right(
                                             Vec2::new(
-                                                MAX_LABEL_WIDTH.load(Ordering::SeqCst) as f32,
+                                                max_label_width,
                                                 available_height,
                                             ),
                                             Label::new(RichText::new( title).color(text_color)).selectable(false).truncate(),
@@ -631,11 +1626,8 @@ impl BarWidget for Komorebi {
                                     }
                                 })
                                 .clicked()
+                                && !selected
                             {
-                                if selected {
-                                    return;
-                                }
-
                                 if komorebi_notification_state.mouse_follows_focus {
                                     if komorebi_client::send_batch([
                                         SocketMessage::MouseFollowsFocus(false),
@@ -658,37 +1650,457 @@ impl BarWidget for Komorebi {
                                     );
                                 }
                             }
+
+                            if let Some(hwnd) = container_information.hwnds.get(i) {
+                                for button in &buttons {
+                                    if ui
+                                        .add(
+                                            Label::new(
+                                                RichText::new(button.icon())
+                                                    .font(icon_font_id.clone())
+                                                    .color(text_color),
+                                            )
+                                            .selectable(false)
+                                            .sense(Sense::click()),
+                                        )
+                                        .on_hover_text(button.tooltip())
+                                        .clicked()
+                                    {
+                                        let messages = button.messages(
+                                            *hwnd,
+                                            komorebi_notification_state.mouse_follows_focus,
+                                        );
+
+                                        if komorebi_client::send_batch(messages.clone()).is_err() {
+                                            tracing::error!(
+                                                "could not send the following batch of messages to komorebi: {:?}",
+                                                messages,
+                                            );
+                                        }
+                                    }
+                                }
+                            }
+                            });
                         }
                     });
                 }
             }
         }
+
+        if self.notifications.is_some_and(|n| n.enable) {
+            let duration =
+                Duration::from_secs(self.notifications.and_then(|n| n.duration).unwrap_or(3));
+
+            let expired = komorebi_notification_state
+                .notification
+                .as_ref()
+                .is_some_and(|(_, shown_at)| shown_at.elapsed() >= duration);
+
+            if expired {
+                komorebi_notification_state.notification = None;
+            }
+
+            if let Some((message, shown_at)) = &komorebi_notification_state.notification {
+                let icon_font_id = config.icon_font_id.clone();
+                let text_font_id = config.text_font_id.clone();
+
+                config.apply_on_widget(false, ui, |ui| {
+                    let mut layout_job = LayoutJob::simple(
+                        egui_phosphor::regular::BELL_SIMPLE.to_string(),
+                        icon_font_id,
+                        ctx.style().visuals.selection.stroke.color,
+                        100.0,
+                    );
+
+                    layout_job.append(
+                        message,
+                        10.0,
+                        TextFormat {
+                            font_id: text_font_id,
+                            color: ctx.style().visuals.text_color(),
+                            valign: Align::Center,
+                            ..Default::default()
+                        },
+                    );
+
+                    ui.add(Label::new(layout_job).selectable(false));
+                });
+
+                // Make sure that the notification is removed once it expires, even if nothing
+                // else triggers a repaint
+                ctx.request_repaint_after(duration.saturating_sub(shown_at.elapsed()));
+            }
+        }
+
+        if let Some(taskbar_config) = self.taskbar {
+            if taskbar_config.enable && !komorebi_notification_state.taskbar.is_empty() {
+                let format = taskbar_config.display.unwrap_or(DisplayFormat::Icon);
+                let mouse_follows_focus = komorebi_notification_state.mouse_follows_focus;
+                let on_double_click = taskbar_config.on_double_click;
+                let max_label_width = config.max_label_width;
+
+                config.apply_on_widget(false, ui, |ui| {
+                    for (container_idx, (is_focused, container)) in
+                        komorebi_notification_state.taskbar.iter().enumerate()
+                    {
+                        if container_idx != 0 {
+                            ui.separator();
+                        }
+
+                        let windows = container
+                            .hwnds
+                            .iter()
+                            .zip(container.titles.iter())
+                            .zip(container.icons.iter());
+
+                        for (i, ((hwnd, title), icon)) in windows.enumerate() {
+                            let selected = *is_focused && i == container.focused_window_idx;
+                            let text_color = if selected {
+                                ctx.style().visuals.selection.stroke.color
+                            } else {
+                                ui.style().visuals.text_color()
+                            };
+
+                            let response = SelectableFrame::new(selected).show(ui, |ui| {
+                                if format == DisplayFormat::Icon
+                                    || format == DisplayFormat::IconAndText
+                                    || format == DisplayFormat::IconAndTextOnSelected
+                                    || (format == DisplayFormat::TextAndIconOnSelected && selected)
+                                {
+                                    if let Some(img) = icon {
+                                        Frame::NONE
+                                            .inner_margin(Margin::same(
+                                                ui.style().spacing.button_padding.y as i8,
+                                            ))
+                                            .show(ui, |ui| {
+                                                let response = ui.add(
+                                                    Image::from(&img.texture(ctx))
+                                                        .maintain_aspect_ratio(true)
+                                                        .fit_to_exact_size(icon_size),
+                                                );
+
+                                                if let DisplayFormat::Icon = format {
+                                                    response.on_hover_text(title);
+                                                }
+                                            });
+                                    }
+                                }
+
+                                if format == DisplayFormat::Text
+                                    || format == DisplayFormat::IconAndText
+                                    || format == DisplayFormat::TextAndIconOnSelected
+                                    || (format == DisplayFormat::IconAndTextOnSelected && selected)
+                                {
+                                    let available_height = ui.available_height();
+                                    let mut custom_ui = CustomUi(ui);
+
+                                    custom_ui.add_sized_left_to_right(
+                                        Vec2::new(max_label_width, available_height),
+                                        Label::new(RichText::new(title).color(text_color))
+                                            .selectable(false)
+                                            .truncate(),
+                                    );
+                                }
+                            });
+
+                            if response.double_clicked() {
+                                if let Some(action) = on_double_click {
+                                    if komorebi_client::send_message(&action.message()).is_err() {
+                                        tracing::error!(
+                                            "could not send message to komorebi: {:?}",
+                                            action.message()
+                                        );
+                                    }
+                                }
+                            } else if response.clicked() && !selected {
+                                let result = if mouse_follows_focus {
+                                    komorebi_client::send_batch([
+                                        SocketMessage::MouseFollowsFocus(false),
+                                        SocketMessage::FocusHwnd(*hwnd),
+                                        SocketMessage::MouseFollowsFocus(true),
+                                    ])
+                                } else {
+                                    komorebi_client::send_message(&SocketMessage::FocusHwnd(*hwnd))
+                                };
+
+                                if result.is_err() {
+                                    tracing::error!(
+                                        "could not send message to komorebi: FocusHwnd"
+                                    );
+                                }
+                            }
+
+                            if response.middle_clicked() {
+                                if let Err(error) = Window::from(*hwnd).close() {
+                                    tracing::error!("could not close window {hwnd}: {error}");
+                                }
+                            }
+                        }
+                    }
+                });
+            }
+        }
+    }
+}
+
+/// The indices of `workspaces` in the order they are displayed in: the workspaces named in
+/// `order` first, in that order, followed by the rest in komorebi's order
+fn display_order(
+    workspaces: &[KomorebiNotificationStateWorkspace],
+    order: Option<&[String]>,
+) -> Vec<usize> {
+    let mut indices = order
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|name| workspaces.iter().position(|(ws, _, _, _)| ws == name))
+        .collect::<Vec<_>>();
+
+    // a workspace listed twice is only displayed once, at its first position
+    let mut seen = HashSet::new();
+    indices.retain(|i| seen.insert(*i));
+
+    indices.extend((0..workspaces.len()).filter(|i| !seen.contains(i)));
+    indices
+}
+
+/// Focuses the workspace at `workspace_idx` on the monitor at `monitor_idx` without moving the
+/// mouse cursor
+fn focus_monitor_workspace(monitor_idx: usize, workspace_idx: usize, mouse_follows_focus: bool) {
+    if mouse_follows_focus {
+        if komorebi_client::send_batch([
+            SocketMessage::MouseFollowsFocus(false),
+            SocketMessage::FocusMonitorWorkspaceNumber(monitor_idx, workspace_idx),
+            SocketMessage::MouseFollowsFocus(true),
+        ])
+        .is_err()
+        {
+            tracing::error!(
+                "could not send the following batch of messages to komorebi:\n
+                MouseFollowsFocus(false)\n
+                FocusMonitorWorkspaceNumber({}, {})\n
+                MouseFollowsFocus(true)\n",
+                monitor_idx,
+                workspace_idx,
+            );
+        }
+    } else if komorebi_client::send_batch([SocketMessage::FocusMonitorWorkspaceNumber(
+        monitor_idx,
+        workspace_idx,
+    )])
+    .is_err()
+    {
+        tracing::error!(
+            "could not send the following batch of messages to komorebi:\n
+            FocusMonitorWorkspaceNumber({}, {})\n",
+            monitor_idx,
+            workspace_idx,
+        );
+    }
+}
+
+/// Moves or sends the container focused on the workspace at `origin` to the workspace at
+/// `target`, each given as a pair of monitor and workspace indices
+fn double_click_workspace(
+    action: WorkspaceDoubleClickAction,
+    origin: (usize, usize),
+    target: (usize, usize),
+    mouse_follows_focus: bool,
+) {
+    if origin == target {
+        return;
+    }
+
+    let (origin_monitor_idx, origin_workspace_idx) = origin;
+    let (target_monitor_idx, target_workspace_idx) = target;
+
+    let message = match action {
+        WorkspaceDoubleClickAction::MoveContainer => {
+            SocketMessage::MoveContainerToMonitorWorkspaceNumber(
+                target_monitor_idx,
+                target_workspace_idx,
+            )
+        }
+        WorkspaceDoubleClickAction::SendContainer => {
+            SocketMessage::SendContainerToMonitorWorkspaceNumber(
+                target_monitor_idx,
+                target_workspace_idx,
+            )
+        }
+    };
+
+    // the first click has already focused the target workspace, so the origin is focused again
+    // to act on the container which was focused before it
+    let mut messages = vec![
+        SocketMessage::FocusMonitorWorkspaceNumber(origin_monitor_idx, origin_workspace_idx),
+        message,
+    ];
+
+    if mouse_follows_focus {
+        messages.insert(0, SocketMessage::MouseFollowsFocus(false));
+        messages.push(SocketMessage::MouseFollowsFocus(true));
+    }
+
+    if komorebi_client::send_batch(messages.clone()).is_err() {
+        tracing::error!(
+            "could not send the following batch of messages to komorebi:\n{:#?}",
+            messages
+        );
+    }
+}
+
+/// Lists the titles and icons of the windows in `containers` under the workspace `name`
+fn workspace_preview_ui(
+    ctx: &Context,
+    ui: &mut Ui,
+    name: &str,
+    containers: &[KomorebiNotificationStateContainerInformation],
+    icon_size: Vec2,
+) {
+    ui.label(RichText::new(name).strong());
+
+    if containers
+        .iter()
+        .all(|container| container.titles.is_empty())
+    {
+        ui.label(RichText::new("No windows").weak());
+        return;
+    }
+
+    for container in containers {
+        for (title, icon) in container.titles.iter().zip(&container.icons) {
+            ui.horizontal(|ui| {
+                if let Some(icon) = icon {
+                    ui.add(
+                        Image::from(&icon.texture(ctx))
+                            .maintain_aspect_ratio(true)
+                            .fit_to_exact_size(icon_size),
+                    );
+                }
+
+                ui.add(Label::new(title).selectable(false).truncate());
+            });
+        }
     }
 }
 
-#[allow(clippy::type_complexity)]
+/// The name, containers, layer and visibility of a workspace
+pub type KomorebiNotificationStateWorkspace = (
+    String,
+    Vec<(bool, KomorebiNotificationStateContainerInformation)>,
+    WorkspaceLayer,
+    bool,
+);
+
 #[derive(Clone, Debug)]
 pub struct KomorebiNotificationState {
-    pub workspaces: Vec<(
-        String,
-        Vec<(bool, KomorebiNotificationStateContainerInformation)>,
-        WorkspaceLayer,
-        bool,
-    )>,
+    pub workspaces: Vec<KomorebiNotificationStateWorkspace>,
     pub selected_workspace: String,
     pub focused_container_information: (bool, KomorebiNotificationStateContainerInformation),
+    pub show_global_focused_container: bool,
+    /// The monitor and workspace annotation and the information of the globally focused
+    /// container, only populated when `show_global_focused_container` is enabled
+    pub global_focused_container_information:
+        Option<(String, KomorebiNotificationStateContainerInformation)>,
     pub layout: KomorebiLayout,
     pub hide_empty_workspaces: bool,
+    pub show_all_monitors: bool,
+    /// The monitor index, selected workspace and workspaces of every monitor, only populated
+    /// when `show_all_monitors` is enabled
+    pub all_monitor_workspaces: Vec<(usize, String, Vec<KomorebiNotificationStateWorkspace>)>,
+    pub hover_preview: bool,
+    /// The information of every container of each workspace keyed by monitor and workspace
+    /// index, only populated when `hover_preview` is enabled
+    pub workspace_previews:
+        HashMap<(usize, usize), Vec<KomorebiNotificationStateContainerInformation>>,
     pub mouse_follows_focus: bool,
+    /// The indices of the globally focused monitor and its focused workspace
+    pub focused_monitor_workspace: (usize, usize),
+    /// Whether the focused window is pinned above the tiling layer of the monitor
+    pub always_on_top_focused: bool,
     pub work_area_offset: Option<Rect>,
     pub stack_accent: Option<Color32>,
     pub monitor_index: usize,
     pub monitor_usr_idx_map: HashMap<usize, usize>,
+    pub custom_layout: Option<CustomLayout>,
+    pub taskbar: Vec<(bool, KomorebiNotificationStateContainerInformation)>,
+    pub show_taskbar: bool,
+    pub notifications: Option<KomorebiNotificationsConfig>,
+    pub notification: Option<(String, Instant)>,
+    pub monitor_count: usize,
+    pub is_paused: bool,
+    /// When tiling was last paused or resumed, used to flash the Pause widget
+    pub pause_changed_at: Option<Instant>,
+    /// The canonicalized path of the configuration file komorebi was last loaded with
+    pub configuration_path: Option<PathBuf>,
+    /// Set while the notification socket to komorebi is down and the state shown is stale
+    pub disconnected: bool,
+    /// Set while privacy mode has been turned on with `komorebic privacy-mode`
+    pub privacy_mode: bool,
+    /// Windows of privacy sensitive applications to leave out while privacy mode is on
+    pub privacy_sensitive_windows: HashSet<isize>,
+    /// Rewrite rules for the titles shown by the focused container widget
+    pub title_rewrite_rules: TitleRewriteRules,
 }
 
 impl KomorebiNotificationState {
     pub fn update_from_config(&mut self, config: &Self) {
         self.hide_empty_workspaces = config.hide_empty_workspaces;
+        self.show_all_monitors = config.show_all_monitors;
+        self.hover_preview = config.hover_preview;
+        self.title_rewrite_rules = config.title_rewrite_rules.clone();
+    }
+
+    fn workspaces_of(
+        &self,
+        monitor: &komorebi_client::Monitor,
+        show_all_icons: bool,
+    ) -> Vec<KomorebiNotificationStateWorkspace> {
+        let focused_workspace_idx = monitor.focused_workspace_idx();
+        let mut workspaces = vec![];
+
+        for (i, ws) in monitor.workspaces().iter().enumerate() {
+            let should_show = if self.hide_empty_workspaces {
+                focused_workspace_idx == i || !ws.is_empty()
+            } else {
+                true
+            };
+
+            workspaces.push((
+                ws.name().to_owned().unwrap_or_else(|| format!("{}", i + 1)),
+                self.redacted(if show_all_icons {
+                    KomorebiNotificationStateContainerInformation::from_all_containers(ws)
+                } else {
+                    vec![(true, ws.into())]
+                }),
+                ws.layer().to_owned(),
+                should_show,
+            ));
+        }
+
+        workspaces
+    }
+
+    /// Leaves out the windows of privacy sensitive applications and blanks the titles of all
+    /// other windows while privacy mode is on
+    fn redacted(
+        &self,
+        containers: Vec<(bool, KomorebiNotificationStateContainerInformation)>,
+    ) -> Vec<(bool, KomorebiNotificationStateContainerInformation)> {
+        if !self.privacy_mode {
+            return containers;
+        }
+
+        containers
+            .into_iter()
+            .map(|(focused, container)| {
+                (focused, container.redacted(&self.privacy_sensitive_windows))
+            })
+            .filter(|(_, container)| !container.hwnds.is_empty())
+            .collect()
+    }
+
+    fn notify(&mut self, message: String) {
+        self.notification = Some((message, Instant::now()));
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -696,7 +2108,7 @@ impl KomorebiNotificationState {
         &mut self,
         ctx: &Context,
         monitor_index: Option<usize>,
-        notification: komorebi_client::Notification,
+        notification: &komorebi_client::Notification,
         bg_color: Rc<RefCell<Color32>>,
         bg_color_with_alpha: Rc<RefCell<Color32>>,
         transparency_alpha: Option<u8>,
@@ -706,13 +2118,15 @@ impl KomorebiNotificationState {
     ) {
         let show_all_icons = render_config.borrow().show_all_icons;
 
-        match notification.event {
+        match &notification.event {
             NotificationEvent::VirtualDesktop(_) => {}
             NotificationEvent::WindowManager(_) => {}
             NotificationEvent::Monitor(_) => {}
+            NotificationEvent::WindowEviction(_) => {}
+            NotificationEvent::WorkspaceSchedule(_) => {}
             NotificationEvent::Socket(message) => match message {
                 SocketMessage::ReloadStaticConfiguration(path) => {
-                    if let Ok(config) = komorebi_client::StaticConfig::read(&path) {
+                    if let Ok(config) = komorebi_client::StaticConfig::read(path) {
                         if let Some(theme) = config.theme {
                             apply_theme(
                                 ctx,
@@ -743,7 +2157,7 @@ impl KomorebiNotificationState {
                 SocketMessage::Theme(theme) => {
                     apply_theme(
                         ctx,
-                        KomobarTheme::from(*theme),
+                        KomobarTheme::from((**theme).clone()),
                         bg_color,
                         bg_color_with_alpha.clone(),
                         transparency_alpha,
@@ -757,6 +2171,28 @@ impl KomorebiNotificationState {
         }
 
         self.monitor_usr_idx_map = notification.state.monitor_usr_idx_map.clone();
+        self.disconnected = false;
+
+        self.configuration_path = notification
+            .state
+            .configuration_path
+            .as_ref()
+            .map(|path| dunce::canonicalize(path).unwrap_or_else(|_| path.clone()));
+
+        let previous_workspace = self.selected_workspace.clone();
+        let previous_layout = self.layout;
+        let previous_monitor_count = self.monitor_count;
+        self.monitor_count = notification.state.monitors.elements().len();
+
+        if let Some(notifications) = self.notifications {
+            if notifications.monitor_connection.unwrap_or(true) && previous_monitor_count != 0 {
+                if self.monitor_count > previous_monitor_count {
+                    self.notify(String::from("Monitor connected"));
+                } else if self.monitor_count < previous_monitor_count {
+                    self.notify(String::from("Monitor disconnected"));
+                }
+            }
+        }
 
         if monitor_index.is_none()
             || monitor_index.is_some_and(|idx| idx >= notification.state.monitors.elements().len())
@@ -769,65 +2205,97 @@ impl KomorebiNotificationState {
         self.monitor_index = monitor_index;
 
         self.mouse_follows_focus = notification.state.mouse_follows_focus;
+        self.privacy_mode = notification.state.privacy_mode;
+        self.privacy_sensitive_windows = notification
+            .state
+            .privacy_sensitive_windows
+            .iter()
+            .copied()
+            .collect();
+        let focused_monitor_idx = notification.state.monitors.focused_idx();
+        self.focused_monitor_workspace = (
+            focused_monitor_idx,
+            notification
+                .state
+                .monitors
+                .elements()
+                .get(focused_monitor_idx)
+                .map_or(0, |monitor| monitor.focused_workspace_idx()),
+        );
+        if self.is_paused != notification.state.is_paused {
+            self.pause_changed_at = Some(Instant::now());
+        }
+        self.is_paused = notification.state.is_paused;
 
         let monitor = &notification.state.monitors.elements()[monitor_index];
+        self.always_on_top_focused = monitor.always_on_top_focused();
         self.work_area_offset =
             notification.state.monitors.elements()[monitor_index].work_area_offset();
 
         let focused_workspace_idx = monitor.focused_workspace_idx();
 
-        let mut workspaces = vec![];
-
         self.selected_workspace = monitor.workspaces()[focused_workspace_idx]
             .name()
             .to_owned()
             .unwrap_or_else(|| format!("{}", focused_workspace_idx + 1));
 
-        for (i, ws) in monitor.workspaces().iter().enumerate() {
-            let should_show = if self.hide_empty_workspaces {
-                focused_workspace_idx == i || !ws.is_empty()
-            } else {
-                true
-            };
-
-            workspaces.push((
-                ws.name().to_owned().unwrap_or_else(|| format!("{}", i + 1)),
-                if show_all_icons {
-                    let mut containers = vec![];
-                    let mut has_monocle = false;
-
-                    // add monocle container
-                    if let Some(container) = ws.monocle_container() {
-                        containers.push((true, container.into()));
-                        has_monocle = true;
-                    }
+        self.workspaces = self.workspaces_of(monitor, show_all_icons);
 
-                    // add all tiled windows
-                    for (i, container) in ws.containers().iter().enumerate() {
-                        containers.push((
-                            !has_monocle && i == ws.focused_container_idx(),
-                            container.into(),
-                        ));
-                    }
-
-                    // add all floating windows
-                    for floating_window in ws.floating_windows() {
-                        containers.push((
-                            !has_monocle && floating_window.is_focused(),
-                            floating_window.into(),
-                        ));
-                    }
+        self.all_monitor_workspaces = if self.show_all_monitors {
+            notification
+                .state
+                .monitors
+                .elements()
+                .iter()
+                .enumerate()
+                .map(|(idx, m)| {
+                    let focused_idx = m.focused_workspace_idx();
+                    let selected = m
+                        .workspaces()
+                        .get(focused_idx)
+                        .and_then(|ws| ws.name().to_owned())
+                        .unwrap_or_else(|| format!("{}", focused_idx + 1));
+
+                    (idx, selected, self.workspaces_of(m, show_all_icons))
+                })
+                .collect()
+        } else {
+            vec![]
+        };
 
-                    containers
-                } else {
-                    vec![(true, ws.into())]
-                },
-                ws.layer().to_owned(),
-                should_show,
-            ));
-        }
+        self.workspace_previews = if self.hover_preview {
+            // Shared by the per-monitor closures below for redacting while privacy mode is on
+            let komorebi_notification_state = &*self;
 
-        self.workspaces = workspaces;
+            notification
+                .state
+                .monitors
+                .elements()
+                .iter()
+                .enumerate()
+                .filter(|(idx, _)| self.show_all_monitors || *idx == monitor_index)
+                .flat_map(|(monitor_idx, m)| {
+                    m.workspaces()
+                        .iter()
+                        .enumerate()
+                        .map(move |(workspace_idx, ws)| {
+                            (
+                                (monitor_idx, workspace_idx),
+                                komorebi_notification_state.redacted(
+                                    KomorebiNotificationStateContainerInformation::from_all_containers(
+                                        ws,
+                                    ),
+                                )
+                                .into_iter()
+                                .map(|(_, container)| container)
+                                .collect(),
+                            )
+                        })
+                })
+                .collect()
+        } else {
+            HashMap::new()
+        };
 
         if monitor.workspaces()[focused_workspace_idx]
             .monocle_container()
@@ -845,18 +2313,92 @@ impl KomorebiNotificationState {
             };
         }
 
+        self.custom_layout = match monitor.workspaces()[focused_workspace_idx].layout() {
+            komorebi_client::Layout::Custom(layout) => {
+                let mut layout = layout.clone();
+                layout.name = monitor.workspaces()[focused_workspace_idx]
+                    .custom_layout_name()
+                    .clone();
+                Some(layout)
+            }
+            komorebi_client::Layout::Default(_) => None,
+        };
+
         let focused_workspace = &monitor.workspaces()[focused_workspace_idx];
         let is_locked = match focused_workspace.focused_container() {
             Some(container) => container.locked(),
             None => false,
         };
 
-        self.focused_container_information = (is_locked, focused_workspace.into());
+        self.taskbar = if self.show_taskbar {
+            self.redacted(
+                KomorebiNotificationStateContainerInformation::from_all_containers(
+                    focused_workspace,
+                ),
+            )
+        } else {
+            vec![]
+        };
+
+        // Don't notify about the initial state received when the bar starts up
+        if let (Some(notifications), false) = (self.notifications, previous_workspace.is_empty()) {
+            if notifications.workspace_change.unwrap_or(true)
+                && previous_workspace != self.selected_workspace
+            {
+                self.notify(format!("Workspace {}", self.selected_workspace));
+            } else if notifications.layout_change.unwrap_or(true) && previous_layout != self.layout
+            {
+                self.notify(format!("Layout: {}", self.layout));
+            }
+        }
+
+        // The focused container is always shown, but without its titles while privacy mode is on
+        let no_windows = HashSet::new();
+        let focused_container_information =
+            KomorebiNotificationStateContainerInformation::from(focused_workspace)
+                .rewritten(&self.title_rewrite_rules);
+        self.focused_container_information = if self.privacy_mode {
+            (
+                is_locked,
+                focused_container_information.redacted(&no_windows),
+            )
+        } else {
+            (is_locked, focused_container_information)
+        };
+
+        self.global_focused_container_information = if self.show_global_focused_container {
+            let focused_monitor_idx = notification.state.monitors.focused_idx();
+            notification.state.monitors.focused().and_then(|monitor| {
+                let workspace_idx = monitor.focused_workspace_idx();
+                monitor.workspaces().get(workspace_idx).map(|workspace| {
+                    let workspace_name = workspace
+                        .name()
+                        .to_owned()
+                        .unwrap_or_else(|| format!("{}", workspace_idx + 1));
+
+                    let container_information =
+                        KomorebiNotificationStateContainerInformation::from(workspace)
+                            .rewritten(&self.title_rewrite_rules);
+
+                    (
+                        format!("M{} {}", focused_monitor_idx + 1, workspace_name),
+                        if self.privacy_mode {
+                            container_information.redacted(&no_windows)
+                        } else {
+                            container_information
+                        },
+                    )
+                })
+            })
+        } else {
+            None
+        };
     }
 }
 
 #[derive(Clone, Debug)]
 pub struct KomorebiNotificationStateContainerInformation {
+    pub hwnds: Vec<isize>,
     pub titles: Vec<String>,
     pub icons: Vec<Option<ImageIcon>>,
     pub focused_window_idx: usize,
@@ -898,6 +2440,7 @@ impl From<&Container> for KomorebiNotificationStateContainerInformation {
             .collect::<Vec<_>>();
 
         Self {
+            hwnds: value.windows().iter().map(|w| w.hwnd).collect::<Vec<_>>(),
             titles: value
                 .windows()
                 .iter()
@@ -917,6 +2460,7 @@ impl From<&Window> for KomorebiNotificationStateContainerInformation {
         });
 
         Self {
+            hwnds: vec![value.hwnd],
             titles: vec![value.title().unwrap_or_default()],
             icons: vec![icons],
             focused_window_idx: 0,
@@ -926,8 +2470,75 @@ impl From<&Window> for KomorebiNotificationStateContainerInformation {
 
 impl KomorebiNotificationStateContainerInformation {
     pub const EMPTY: Self = Self {
+        hwnds: vec![],
         titles: vec![],
         icons: vec![],
         focused_window_idx: 0,
     };
+
+    /// Returns this information with the titles rewritten by `rules`
+    pub fn rewritten(self, rules: &TitleRewriteRules) -> Self {
+        Self {
+            titles: self
+                .hwnds
+                .iter()
+                .zip(&self.titles)
+                .map(|(hwnd, title)| rules.rewrite(*hwnd, title))
+                .collect(),
+            ..self
+        }
+    }
+
+    /// Returns this information without the windows in `hidden_hwnds` and with the titles of all
+    /// remaining windows blanked
+    pub fn redacted(self, hidden_hwnds: &HashSet<isize>) -> Self {
+        let focused_hwnd = self.hwnds.get(self.focused_window_idx).copied();
+
+        let (hwnds, icons): (Vec<_>, Vec<_>) = self
+            .hwnds
+            .into_iter()
+            .zip(self.icons)
+            .filter(|(hwnd, _)| !hidden_hwnds.contains(hwnd))
+            .unzip();
+
+        Self {
+            titles: vec![String::new(); hwnds.len()],
+            focused_window_idx: focused_hwnd
+                .and_then(|focused| hwnds.iter().position(|hwnd| *hwnd == focused))
+                .unwrap_or(0),
+            hwnds,
+            icons,
+        }
+    }
+
+    /// Returns the information of the monocle container, all tiled containers and all floating
+    /// windows of `workspace`, each paired with whether it is focused
+    pub fn from_all_containers(workspace: &Workspace) -> Vec<(bool, Self)> {
+        let mut containers = vec![];
+        let mut has_monocle = false;
+
+        // add monocle container
+        if let Some(container) = workspace.monocle_container() {
+            containers.push((true, container.into()));
+            has_monocle = true;
+        }
+
+        // add all tiled windows
+        for (i, container) in workspace.containers().iter().enumerate() {
+            containers.push((
+                !has_monocle && i == workspace.focused_container_idx(),
+                container.into(),
+            ));
+        }
+
+        // add all floating windows
+        for floating_window in workspace.floating_windows() {
+            containers.push((
+                !has_monocle && floating_window.is_focused(),
+                floating_window.into(),
+            ));
+        }
+
+        containers
+    }
 }
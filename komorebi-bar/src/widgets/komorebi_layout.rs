@@ -1,7 +1,10 @@
 use crate::config::DisplayFormat;
 use crate::render::RenderConfig;
 use crate::selected_frame::SelectableFrame;
+use crate::widgets::applications::Icon;
+use crate::widgets::applications::IconConfig;
 use crate::widgets::komorebi::KomorebiLayoutConfig;
+use crate::widgets::komorebi::KomorebiLayoutOverrideConfig;
 use eframe::egui::vec2;
 use eframe::egui::Context;
 use eframe::egui::CornerRadius;
@@ -13,12 +16,17 @@ use eframe::egui::Stroke;
 use eframe::egui::StrokeKind;
 use eframe::egui::Ui;
 use eframe::egui::Vec2;
+use komorebi_client::Axis;
+use komorebi_client::CustomLayout;
+use komorebi_client::CycleDirection;
+use komorebi_client::PathExt;
 use komorebi_client::SocketMessage;
 use serde::de::Error;
 use serde::Deserialize;
 use serde::Deserializer;
 use serde::Serialize;
 use serde_json::from_str;
+use std::collections::HashMap;
 use std::fmt::Display;
 use std::fmt::Formatter;
 
@@ -70,6 +78,145 @@ impl Display for KomorebiLayout {
     }
 }
 
+/// Layout maintenance actions offered in the right-click menu of the layout widget
+#[derive(Copy, Clone, Debug)]
+enum KomorebiLayoutQuickAction {
+    Retile,
+    FlipHorizontal,
+    FlipVertical,
+    ToggleTiling,
+    CycleLayoutPrevious,
+}
+
+impl KomorebiLayoutQuickAction {
+    const ALL: [KomorebiLayoutQuickAction; 5] = [
+        KomorebiLayoutQuickAction::Retile,
+        KomorebiLayoutQuickAction::FlipHorizontal,
+        KomorebiLayoutQuickAction::FlipVertical,
+        KomorebiLayoutQuickAction::ToggleTiling,
+        KomorebiLayoutQuickAction::CycleLayoutPrevious,
+    ];
+
+    fn icon(&self) -> &'static str {
+        match self {
+            KomorebiLayoutQuickAction::Retile => egui_phosphor::regular::ARROWS_CLOCKWISE,
+            KomorebiLayoutQuickAction::FlipHorizontal => egui_phosphor::regular::FLIP_HORIZONTAL,
+            KomorebiLayoutQuickAction::FlipVertical => egui_phosphor::regular::FLIP_VERTICAL,
+            KomorebiLayoutQuickAction::ToggleTiling => egui_phosphor::regular::SQUARES_FOUR,
+            KomorebiLayoutQuickAction::CycleLayoutPrevious => {
+                egui_phosphor::regular::ARROW_COUNTER_CLOCKWISE
+            }
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            KomorebiLayoutQuickAction::Retile => "Retile",
+            KomorebiLayoutQuickAction::FlipHorizontal => "Flip horizontally",
+            KomorebiLayoutQuickAction::FlipVertical => "Flip vertically",
+            KomorebiLayoutQuickAction::ToggleTiling => "Toggle tiling",
+            KomorebiLayoutQuickAction::CycleLayoutPrevious => "Previous layout",
+        }
+    }
+
+    fn message(&self) -> SocketMessage {
+        match self {
+            KomorebiLayoutQuickAction::Retile => SocketMessage::Retile,
+            KomorebiLayoutQuickAction::FlipHorizontal => {
+                SocketMessage::FlipLayout(Axis::Horizontal)
+            }
+            KomorebiLayoutQuickAction::FlipVertical => SocketMessage::FlipLayout(Axis::Vertical),
+            KomorebiLayoutQuickAction::ToggleTiling => SocketMessage::ToggleTiling,
+            KomorebiLayoutQuickAction::CycleLayoutPrevious => {
+                SocketMessage::CycleLayout(CycleDirection::Previous)
+            }
+        }
+    }
+
+    fn execute(&self) {
+        if komorebi_client::send_batch([SocketMessage::FocusMonitorAtCursor, self.message()])
+            .is_err()
+        {
+            tracing::error!("could not send message to komorebi: {:?}", self.message());
+        }
+    }
+}
+
+/// Icon and label overrides for a layout, resolved from a [`KomorebiLayoutOverrideConfig`]
+#[derive(Clone, Debug)]
+pub struct KomorebiLayoutOverride {
+    pub icon: Option<Icon>,
+    pub label: Option<String>,
+}
+
+impl From<&KomorebiLayoutOverrideConfig> for KomorebiLayoutOverride {
+    fn from(value: &KomorebiLayoutOverrideConfig) -> Self {
+        Self {
+            icon: Icon::try_from_path(value.icon.as_deref()),
+            label: value.label.clone(),
+        }
+    }
+}
+
+/// All of the layout overrides configured for the layout widget
+#[derive(Clone, Debug, Default)]
+pub struct KomorebiLayoutOverrides {
+    by_name: HashMap<String, KomorebiLayoutOverride>,
+    custom_layouts: Vec<(CustomLayout, KomorebiLayoutOverride)>,
+}
+
+impl From<&KomorebiLayoutConfig> for KomorebiLayoutOverrides {
+    fn from(value: &KomorebiLayoutConfig) -> Self {
+        let by_name = value
+            .layouts
+            .iter()
+            .flatten()
+            .map(|(name, config)| (name.clone(), config.into()))
+            .collect();
+
+        let mut custom_layouts = vec![];
+        for (path, config) in value.custom_layouts.iter().flatten() {
+            match CustomLayout::from_path(path.replace_env()) {
+                Ok(layout) => custom_layouts.push((layout, config.into())),
+                Err(error) => {
+                    tracing::error!("could not load custom layout from {}: {}", path, error)
+                }
+            }
+        }
+
+        Self {
+            by_name,
+            custom_layouts,
+        }
+    }
+}
+
+impl KomorebiLayoutOverrides {
+    /// Returns the override for `layout`, matching custom layouts against the configured custom
+    /// layout files (by name where available, otherwise by content) before falling back to the
+    /// layout name
+    pub fn get(
+        &self,
+        layout: &KomorebiLayout,
+        custom_layout: Option<&CustomLayout>,
+    ) -> Option<&KomorebiLayoutOverride> {
+        if let (KomorebiLayout::Custom, Some(custom_layout)) = (layout, custom_layout) {
+            if let Some((_, layout_override)) =
+                self.custom_layouts
+                    .iter()
+                    .find(|(configured, _)| match &custom_layout.name {
+                        Some(name) => configured.name.as_ref() == Some(name),
+                        None => configured.columns == custom_layout.columns,
+                    })
+            {
+                return Some(layout_override);
+            }
+        }
+
+        self.by_name.get(&layout.to_string())
+    }
+}
+
 impl KomorebiLayout {
     fn is_default(&mut self) -> bool {
         matches!(self, KomorebiLayout::Default(_))
@@ -133,15 +280,37 @@ impl KomorebiLayout {
         }
     }
 
-    fn show_icon(&mut self, is_selected: bool, font_id: FontId, ctx: &Context, ui: &mut Ui) {
-        // paint custom icons for the layout
-        let size = Vec2::splat(font_id.size);
-        let (response, painter) = ui.allocate_painter(size, Sense::hover());
+    fn show_icon(
+        &mut self,
+        is_selected: bool,
+        font_id: FontId,
+        layout_override: Option<&KomorebiLayoutOverride>,
+        ctx: &Context,
+        ui: &mut Ui,
+    ) {
         let color = if is_selected {
             ctx.style().visuals.selection.stroke.color
         } else {
             ui.style().visuals.text_color()
         };
+
+        // use the user-provided icon for the layout if there is one
+        if let Some(icon) = layout_override.and_then(|o| o.icon.as_ref()) {
+            icon.draw(
+                ctx,
+                ui,
+                &IconConfig {
+                    size: font_id.size,
+                    font_id,
+                    color,
+                },
+            );
+            return;
+        }
+
+        // paint custom icons for the layout
+        let size = Vec2::splat(font_id.size);
+        let (response, painter) = ui.allocate_painter(size, Sense::hover());
         let stroke = Stroke::new(1.0, color);
         let mut rect = response.rect;
         let rounding = CornerRadius::same((rect.width() * 0.1) as u8);
@@ -194,6 +363,24 @@ impl KomorebiLayout {
                     painter.line_segment([c - vec2(0.0, r), c + vec2(0.0, r)], stroke);
                     painter.line_segment([c - vec2(-r / 2.0, r), c + vec2(r / 2.0, r)], stroke);
                 }
+                komorebi_client::DefaultLayout::CenteredMaster => {
+                    painter.line_segment([c - vec2(r / 2.0, r), c + vec2(-r / 2.0, r)], stroke);
+                    painter.line_segment([c - vec2(-r / 2.0, r), c + vec2(r / 2.0, r)], stroke);
+                    painter.line_segment([c - vec2(r, 0.0), c - vec2(r / 2.0, 0.0)], stroke);
+                    painter.line_segment([c + vec2(r / 2.0, 0.0), c + vec2(r, 0.0)], stroke);
+                }
+                komorebi_client::DefaultLayout::Manual => {
+                    painter.line_segment([c - vec2(0.0, r), c + vec2(0.0, r)], stroke);
+                    painter.line_segment([c, c + vec2(r, 0.0)], stroke);
+                    painter.line_segment([c + vec2(r / 2.0, 0.0), c + vec2(r / 2.0, r)], stroke);
+                }
+                komorebi_client::DefaultLayout::Tabbed => {
+                    painter.line_segment([c - vec2(r, r / 2.0), c + vec2(r, -r / 2.0)], stroke);
+                    painter
+                        .line_segment([c - vec2(r / 3.0, r), c - vec2(r / 3.0, r / 2.0)], stroke);
+                    painter
+                        .line_segment([c + vec2(r / 3.0, -r), c + vec2(r / 3.0, -r / 2.0)], stroke);
+                }
             },
             KomorebiLayout::Monocle => {}
             KomorebiLayout::Floating => {
@@ -242,12 +429,22 @@ impl KomorebiLayout {
         ui: &mut Ui,
         render_config: &mut RenderConfig,
         layout_config: &KomorebiLayoutConfig,
+        layout_overrides: &KomorebiLayoutOverrides,
+        custom_layout: Option<&CustomLayout>,
         workspace_idx: Option<usize>,
     ) {
         let monitor_idx = render_config.monitor_idx;
         let font_id = render_config.icon_font_id.clone();
         let mut show_options = RenderConfig::load_show_komorebi_layout_options();
         let format = layout_config.display.unwrap_or(DisplayFormat::IconAndText);
+        let layout_override = layout_overrides.get(self, custom_layout);
+        let label = layout_override
+            .and_then(|o| o.label.clone())
+            .or_else(|| match self {
+                KomorebiLayout::Custom => custom_layout.and_then(|c| c.name.clone()),
+                _ => None,
+            })
+            .unwrap_or_else(|| self.to_string());
 
         if !self.is_default() {
             show_options = false;
@@ -257,19 +454,31 @@ impl KomorebiLayout {
             let layout_frame = SelectableFrame::new(false)
                 .show(ui, |ui| {
                     if let DisplayFormat::Icon | DisplayFormat::IconAndText = format {
-                        self.show_icon(true, font_id.clone(), ctx, ui);
+                        self.show_icon(true, font_id.clone(), layout_override, ctx, ui);
                     }
 
                     if let DisplayFormat::Text | DisplayFormat::IconAndText = format {
-                        ui.add(Label::new(self.to_string()).selectable(false));
+                        ui.add(Label::new(label.clone()).selectable(false));
                     }
                 })
-                .on_hover_text(self.to_string());
+                .on_hover_text(label.clone());
 
             if layout_frame.clicked() {
                 show_options = self.on_click(&show_options, monitor_idx, workspace_idx);
             }
 
+            layout_frame.context_menu(|ui| {
+                for action in KomorebiLayoutQuickAction::ALL {
+                    if ui
+                        .button(format!("{} {}", action.icon(), action.label()))
+                        .clicked()
+                    {
+                        action.execute();
+                        ui.close_menu();
+                    }
+                }
+            });
+
             if show_options {
                 if let Some(workspace_idx) = workspace_idx {
                     Frame::NONE.show(ui, |ui| {
@@ -293,6 +502,9 @@ impl KomorebiLayout {
                                 komorebi_client::DefaultLayout::UltrawideVerticalStack,
                             ),
                             KomorebiLayout::Default(komorebi_client::DefaultLayout::Grid),
+                            KomorebiLayout::Default(komorebi_client::DefaultLayout::CenteredMaster),
+                            KomorebiLayout::Default(komorebi_client::DefaultLayout::Manual),
+                            KomorebiLayout::Default(komorebi_client::DefaultLayout::Tabbed),
                             //KomorebiLayout::Custom,
                             KomorebiLayout::Monocle,
                             KomorebiLayout::Floating,
@@ -301,18 +513,29 @@ impl KomorebiLayout {
 
                         for layout_option in &mut layout_options {
                             let is_selected = self == layout_option;
-
-                            if SelectableFrame::new(is_selected)
-                                .show(ui, |ui| {
-                                    layout_option.show_icon(is_selected, font_id.clone(), ctx, ui)
-                                })
-                                .on_hover_text(match layout_option {
+                            let option_override = layout_overrides.get(layout_option, None);
+                            let hover_text = match option_override.and_then(|o| o.label.clone()) {
+                                Some(label) => label,
+                                None => match layout_option {
                                     KomorebiLayout::Default(layout) => layout.to_string(),
                                     KomorebiLayout::Monocle => "Toggle monocle".to_string(),
                                     KomorebiLayout::Floating => "Toggle tiling".to_string(),
                                     KomorebiLayout::Paused => "Toggle pause".to_string(),
                                     KomorebiLayout::Custom => "Custom".to_string(),
+                                },
+                            };
+
+                            if SelectableFrame::new(is_selected)
+                                .show(ui, |ui| {
+                                    layout_option.show_icon(
+                                        is_selected,
+                                        font_id.clone(),
+                                        option_override,
+                                        ctx,
+                                        ui,
+                                    )
                                 })
+                                .on_hover_text(hover_text)
                                 .clicked()
                             {
                                 layout_option.on_click_option(monitor_idx, Some(workspace_idx));
@@ -1,8 +1,8 @@
 use crate::render::RenderConfig;
+use crate::render::WidgetTheme;
 use crate::selected_frame::SelectableFrame;
 use crate::ui::CustomUi;
 use crate::widgets::widget::BarWidget;
-use crate::MAX_LABEL_WIDTH;
 use eframe::egui::text::LayoutJob;
 use eframe::egui::Align;
 use eframe::egui::Context;
@@ -12,14 +12,19 @@ use eframe::egui::Ui;
 use eframe::egui::Vec2;
 use serde::Deserialize;
 use serde::Serialize;
-use std::sync::atomic::Ordering;
+use std::time::Duration;
 use windows::Media::Control::GlobalSystemMediaTransportControlsSessionManager;
 
+/// How often the bar is repainted to pick up track changes
+const MEDIA_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Copy, Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct MediaConfig {
     /// Enable the Media widget
     pub enable: bool,
+    /// Theme overrides applied to this widget only
+    pub theme: Option<WidgetTheme>,
 }
 
 impl From<MediaConfig> for Media {
@@ -99,6 +104,8 @@ impl BarWidget for Media {
                     },
                 );
 
+                let max_label_width = config.max_label_width;
+
                 config.apply_on_widget(false, ui, |ui| {
                     if SelectableFrame::new(false)
                         .show(ui, |ui| {
@@ -106,10 +113,7 @@ impl BarWidget for Media {
                             let mut custom_ui = CustomUi(ui);
 
                             custom_ui.add_sized_left_to_right(
-                                Vec2::new(
-                                    MAX_LABEL_WIDTH.load(Ordering::SeqCst) as f32,
-                                    available_height,
-                                ),
+                                Vec2::new(max_label_width, available_height),
                                 Label::new(layout_job).selectable(false).truncate(),
                             )
                         })
@@ -121,4 +125,9 @@ impl BarWidget for Media {
             }
         }
     }
+
+    fn next_refresh(&self) -> Option<Duration> {
+        // the media session is read on every frame, so the current track is kept up to date
+        Some(MEDIA_REFRESH_INTERVAL)
+    }
 }
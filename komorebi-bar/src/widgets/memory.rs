@@ -1,5 +1,7 @@
 use crate::config::LabelPrefix;
 use crate::render::RenderConfig;
+use crate::render::WidgetTheme;
+use crate::repaint::refresh_due_in;
 use crate::selected_frame::SelectableFrame;
 use crate::widgets::widget::BarWidget;
 use eframe::egui::text::LayoutJob;
@@ -10,23 +12,36 @@ use eframe::egui::TextFormat;
 use eframe::egui::Ui;
 use serde::Deserialize;
 use serde::Serialize;
+use std::collections::HashMap;
 use std::process::Command;
 use std::time::Duration;
 use std::time::Instant;
+use sysinfo::ProcessRefreshKind;
+use sysinfo::ProcessesToUpdate;
 use sysinfo::RefreshKind;
 use sysinfo::System;
+use windows::Win32::System::SystemInformation::GlobalMemoryStatusEx;
+use windows::Win32::System::SystemInformation::MEMORYSTATUSEX;
+
+const TOP_CONSUMERS_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct MemoryConfig {
     /// Enable the Memory widget
     pub enable: bool,
+    /// Theme overrides applied to this widget only
+    pub theme: Option<WidgetTheme>,
     /// Data refresh interval (default: 10 seconds)
     pub data_refresh_interval: Option<u64>,
     /// Display label prefix
     pub label_prefix: Option<LabelPrefix>,
     /// Select when the current percentage is over this value [[1-100]]
     pub auto_select_over: Option<u8>,
+    /// Show the commit charge as a percentage of the commit limit (default: false)
+    pub show_commit_charge: Option<bool>,
+    /// Show this many of the top memory consumers in a tooltip when hovering over the widget
+    pub top_consumers: Option<usize>,
 }
 
 impl From<MemoryConfig> for Memory {
@@ -41,6 +56,11 @@ impl From<MemoryConfig> for Memory {
             data_refresh_interval,
             label_prefix: value.label_prefix.unwrap_or(LabelPrefix::IconAndText),
             auto_select_over: value.auto_select_over.map(|o| o.clamp(1, 100)),
+            show_commit_charge: value.show_commit_charge.unwrap_or_default(),
+            top_consumers: value.top_consumers.unwrap_or_default(),
+            processes: System::new(),
+            last_state_top_consumers: String::new(),
+            last_updated_top_consumers: None,
             last_updated: Instant::now()
                 .checked_sub(Duration::from_secs(data_refresh_interval))
                 .unwrap(),
@@ -60,6 +80,11 @@ pub struct Memory {
     data_refresh_interval: u64,
     label_prefix: LabelPrefix,
     auto_select_over: Option<u8>,
+    show_commit_charge: bool,
+    top_consumers: usize,
+    processes: System,
+    last_state_top_consumers: String,
+    last_updated_top_consumers: Option<Instant>,
     last_updated: Instant,
 }
 
@@ -76,18 +101,82 @@ impl Memory {
         let usage = ((used * 100) / total) as u8;
         let selected = self.auto_select_over.is_some_and(|o| usage >= o);
 
-        MemoryOutput {
-            label: match self.label_prefix {
-                LabelPrefix::Text | LabelPrefix::IconAndText => {
-                    format!("RAM: {}%", usage)
+        let mut label = match self.label_prefix {
+            LabelPrefix::Text | LabelPrefix::IconAndText => {
+                format!("RAM: {}%", usage)
+            }
+            LabelPrefix::None | LabelPrefix::Icon => format!("{}%", usage),
+        };
+
+        if self.show_commit_charge {
+            if let Some(commit_charge) = commit_charge() {
+                match self.label_prefix {
+                    LabelPrefix::Text | LabelPrefix::IconAndText => {
+                        label.push_str(&format!(" COMMIT: {}%", commit_charge))
+                    }
+                    LabelPrefix::None | LabelPrefix::Icon => {
+                        label.push_str(&format!(" ({}%)", commit_charge))
+                    }
                 }
-                LabelPrefix::None | LabelPrefix::Icon => format!("{}%", usage),
-            },
-            selected,
+            }
         }
+
+        MemoryOutput { label, selected }
+    }
+
+    /// Returns the top memory consumers, grouped by process name. Processes are only refreshed
+    /// while this is being called, i.e. while the tooltip is shown.
+    fn top_consumers(&mut self) -> String {
+        let now = Instant::now();
+        if self.last_updated_top_consumers.is_some_and(|last_updated| {
+            now.duration_since(last_updated) < TOP_CONSUMERS_REFRESH_INTERVAL
+        }) {
+            return self.last_state_top_consumers.clone();
+        }
+
+        self.processes.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing().with_memory(),
+        );
+
+        let mut consumers: HashMap<String, u64> = HashMap::new();
+        for process in self.processes.processes().values() {
+            *consumers
+                .entry(process.name().to_string_lossy().to_string())
+                .or_default() += process.memory();
+        }
+
+        let mut consumers = consumers.into_iter().collect::<Vec<_>>();
+        consumers.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+        self.last_state_top_consumers = consumers
+            .iter()
+            .take(self.top_consumers)
+            .map(|(name, memory)| format!("{name}: {} MB", memory / 1024 / 1024))
+            .collect::<Vec<_>>()
+            .join("\n");
+        self.last_updated_top_consumers = Some(now);
+
+        self.last_state_top_consumers.clone()
     }
 }
 
+/// Returns the commit charge as a percentage of the commit limit
+fn commit_charge() -> Option<u8> {
+    let mut status = MEMORYSTATUSEX {
+        dwLength: std::mem::size_of::<MEMORYSTATUSEX>() as u32,
+        ..Default::default()
+    };
+
+    unsafe { GlobalMemoryStatusEx(&mut status) }.ok()?;
+
+    let limit = status.ullTotalPageFile;
+    let used = limit.checked_sub(status.ullAvailPageFile)?;
+
+    (limit > 0).then(|| ((used * 100) / limit) as u8)
+}
+
 impl BarWidget for Memory {
     fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
         if self.enable {
@@ -121,10 +210,14 @@ impl BarWidget for Memory {
                 let auto_focus_fill = config.auto_select_fill;
 
                 config.apply_on_widget(false, ui, |ui| {
-                    if SelectableFrame::new_auto(output.selected, auto_focus_fill)
-                        .show(ui, |ui| ui.add(Label::new(layout_job).selectable(false)))
-                        .clicked()
-                    {
+                    let mut response = SelectableFrame::new_auto(output.selected, auto_focus_fill)
+                        .show(ui, |ui| ui.add(Label::new(layout_job).selectable(false)));
+
+                    if self.top_consumers > 0 && response.hovered() {
+                        response = response.on_hover_text(self.top_consumers());
+                    }
+
+                    if response.clicked() {
                         if let Err(error) =
                             Command::new("cmd.exe").args(["/C", "taskmgr.exe"]).spawn()
                         {
@@ -135,4 +228,11 @@ impl BarWidget for Memory {
             }
         }
     }
+
+    fn next_refresh(&self) -> Option<Duration> {
+        Some(refresh_due_in(
+            self.last_updated,
+            Duration::from_secs(self.data_refresh_interval),
+        ))
+    }
 }
@@ -12,6 +12,7 @@ use std::sync::RwLock;
 pub mod applications;
 pub mod battery;
 pub mod cpu;
+pub mod custom;
 pub mod date;
 pub mod keyboard;
 pub mod komorebi;
@@ -19,9 +20,13 @@ mod komorebi_layout;
 pub mod media;
 pub mod memory;
 pub mod network;
+pub mod session;
+pub mod shortcuts;
 pub mod storage;
 pub mod time;
 pub mod update;
+pub mod uptime;
+pub mod weather;
 pub mod widget;
 
 /// Global cache for icon images and their associated GPU textures.
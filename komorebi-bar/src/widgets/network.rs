@@ -1,6 +1,9 @@
 use crate::bar::Alignment;
 use crate::config::LabelPrefix;
 use crate::render::RenderConfig;
+use crate::render::WidgetTheme;
+use crate::repaint;
+use crate::repaint::refresh_due_in;
 use crate::selected_frame::SelectableFrame;
 use crate::widgets::widget::BarWidget;
 use eframe::egui::text::LayoutJob;
@@ -10,20 +13,43 @@ use eframe::egui::Context;
 use eframe::egui::Label;
 use eframe::egui::TextFormat;
 use eframe::egui::Ui;
+use netdev::interface::InterfaceType;
 use num_derive::FromPrimitive;
 use serde::Deserialize;
 use serde::Serialize;
+use std::collections::HashMap;
+use std::ffi::c_void;
 use std::fmt;
 use std::process::Command;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Once;
 use std::time::Duration;
 use std::time::Instant;
 use sysinfo::Networks;
-
-#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+use windows::Win32::Foundation::ERROR_SUCCESS;
+use windows::Win32::Foundation::HANDLE;
+use windows::Win32::NetworkManagement::IpHelper::NotifyIpInterfaceChange;
+use windows::Win32::NetworkManagement::IpHelper::MIB_IPINTERFACE_ROW;
+use windows::Win32::NetworkManagement::IpHelper::MIB_NOTIFICATION_TYPE;
+use windows::Win32::NetworkManagement::WiFi::wlan_interface_state_connected;
+use windows::Win32::NetworkManagement::WiFi::wlan_intf_opcode_current_connection;
+use windows::Win32::NetworkManagement::WiFi::WlanCloseHandle;
+use windows::Win32::NetworkManagement::WiFi::WlanEnumInterfaces;
+use windows::Win32::NetworkManagement::WiFi::WlanFreeMemory;
+use windows::Win32::NetworkManagement::WiFi::WlanOpenHandle;
+use windows::Win32::NetworkManagement::WiFi::WlanQueryInterface;
+use windows::Win32::NetworkManagement::WiFi::WLAN_CONNECTION_ATTRIBUTES;
+use windows::Win32::NetworkManagement::WiFi::WLAN_INTERFACE_INFO_LIST;
+use windows::Win32::Networking::WinSock::AF_UNSPEC;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct NetworkConfig {
     /// Enable the Network widget
     pub enable: bool,
+    /// Theme overrides applied to this widget only
+    pub theme: Option<WidgetTheme>,
     /// Show total received and transmitted activity
     #[serde(alias = "show_total_data_transmitted")]
     pub show_total_activity: bool,
@@ -32,6 +58,11 @@ pub struct NetworkConfig {
     pub show_activity: bool,
     /// Show default interface
     pub show_default_interface: Option<bool>,
+    /// Interfaces to show instead of the default interface, matched against the adapter's name
+    /// as shown in the Network Connections control panel (e.g. "Wi-Fi", "Ethernet")
+    pub interfaces: Option<Vec<String>>,
+    /// Show the connected SSID and signal strength of wireless interfaces (default: false)
+    pub show_wireless_details: Option<bool>,
     /// Characters to reserve for received and transmitted activity
     #[serde(alias = "network_activity_fill_characters")]
     pub activity_left_padding: Option<usize>,
@@ -60,13 +91,21 @@ impl From<NetworkConfig> for Network {
     fn from(value: NetworkConfig) -> Self {
         let data_refresh_interval = value.data_refresh_interval.unwrap_or(10);
 
+        register_link_state_notifications();
+
         Self {
             enable: value.enable,
             show_total_activity: value.show_total_activity,
             show_activity: value.show_activity,
             show_default_interface: value.show_default_interface.unwrap_or(true),
+            show_wireless_details: value.show_wireless_details.unwrap_or_default(),
             networks_network_activity: Networks::new_with_refreshed_list(),
-            default_interface: String::new(),
+            interface_names: value.interfaces.unwrap_or_default(),
+            interfaces: vec![],
+            link_state_generation: LINK_STATE_GENERATION.load(Ordering::SeqCst),
+            last_updated_interfaces: Instant::now()
+                .checked_sub(Duration::from_secs(data_refresh_interval))
+                .unwrap(),
             data_refresh_interval,
             label_prefix: value.label_prefix.unwrap_or(LabelPrefix::Icon),
             auto_select: value.auto_select,
@@ -85,11 +124,15 @@ pub struct Network {
     pub show_total_activity: bool,
     pub show_activity: bool,
     pub show_default_interface: bool,
+    show_wireless_details: bool,
     networks_network_activity: Networks,
     data_refresh_interval: u64,
     label_prefix: LabelPrefix,
     auto_select: Option<NetworkSelectConfig>,
-    default_interface: String,
+    interface_names: Vec<String>,
+    interfaces: Vec<InterfaceInformation>,
+    link_state_generation: u64,
+    last_updated_interfaces: Instant,
     last_state_total_activity: Vec<NetworkReading>,
     last_state_activity: Vec<NetworkReading>,
     last_updated_network_activity: Instant,
@@ -97,11 +140,85 @@ pub struct Network {
 }
 
 impl Network {
-    fn default_interface(&mut self) {
-        if let Ok(interface) = netdev::get_default_interface() {
-            if let Some(friendly_name) = &interface.friendly_name {
-                self.default_interface.clone_from(friendly_name);
-            }
+    /// Refreshes the selected interfaces, either when the refresh interval has elapsed or
+    /// immediately when the link state of any interface has changed
+    fn refresh_interfaces(&mut self) {
+        let now = Instant::now();
+        let link_state_generation = LINK_STATE_GENERATION.load(Ordering::SeqCst);
+
+        if link_state_generation == self.link_state_generation
+            && now.duration_since(self.last_updated_interfaces)
+                < Duration::from_secs(self.data_refresh_interval)
+        {
+            return;
+        }
+
+        let interfaces = if self.interface_names.is_empty() {
+            netdev::get_default_interface()
+                .into_iter()
+                .collect::<Vec<_>>()
+        } else {
+            let all_interfaces = netdev::get_interfaces();
+            self.interface_names
+                .iter()
+                .filter_map(|name| {
+                    all_interfaces
+                        .iter()
+                        .find(|interface| interface.friendly_name.as_ref() == Some(name))
+                        .cloned()
+                })
+                .collect()
+        };
+
+        let wireless_connections = if self.show_wireless_details
+            && interfaces
+                .iter()
+                .any(|interface| interface.if_type == InterfaceType::Wireless80211)
+        {
+            wireless_connections()
+        } else {
+            HashMap::new()
+        };
+
+        self.interfaces = interfaces
+            .iter()
+            .filter_map(|interface| {
+                let name = interface.friendly_name.clone()?;
+                let guid = interface
+                    .name
+                    .trim_matches(|c| c == '{' || c == '}')
+                    .to_uppercase();
+
+                Some(InterfaceInformation {
+                    name,
+                    is_up: interface.is_up(),
+                    is_wireless: interface.if_type == InterfaceType::Wireless80211,
+                    wireless_connection: wireless_connections.get(&guid).cloned(),
+                })
+            })
+            .collect();
+
+        self.link_state_generation = link_state_generation;
+        self.last_updated_interfaces = now;
+    }
+
+    fn interface_icon(&self, interface: &InterfaceInformation) -> &'static str {
+        if !interface.is_up {
+            return egui_phosphor::regular::WIFI_SLASH;
+        }
+
+        if !self.show_wireless_details || !interface.is_wireless {
+            return egui_phosphor::regular::WIFI_HIGH;
+        }
+
+        match &interface.wireless_connection {
+            None => egui_phosphor::regular::WIFI_SLASH,
+            Some(connection) => match connection.signal_quality {
+                0..25 => egui_phosphor::regular::WIFI_NONE,
+                25..50 => egui_phosphor::regular::WIFI_LOW,
+                50..75 => egui_phosphor::regular::WIFI_MEDIUM,
+                _ => egui_phosphor::regular::WIFI_HIGH,
+            },
         }
     }
 
@@ -116,44 +233,36 @@ impl Network {
             activity.clear();
             total_activity.clear();
 
-            if let Ok(interface) = netdev::get_default_interface() {
-                if let Some(friendly_name) = &interface.friendly_name {
-                    self.default_interface.clone_from(friendly_name);
-
-                    self.networks_network_activity.refresh(true);
-
-                    for (interface_name, data) in &self.networks_network_activity {
-                        if friendly_name.eq(interface_name) {
-                            if self.show_activity {
-                                let received = Self::to_pretty_bytes(
-                                    data.received(),
-                                    self.data_refresh_interval,
-                                );
-                                let transmitted = Self::to_pretty_bytes(
-                                    data.transmitted(),
-                                    self.data_refresh_interval,
-                                );
+            self.networks_network_activity.refresh(true);
+
+            for (interface_name, data) in &self.networks_network_activity {
+                if self
+                    .interfaces
+                    .iter()
+                    .any(|interface| interface.name.eq(interface_name))
+                {
+                    if self.show_activity {
+                        let received =
+                            Self::to_pretty_bytes(data.received(), self.data_refresh_interval);
+                        let transmitted =
+                            Self::to_pretty_bytes(data.transmitted(), self.data_refresh_interval);
+
+                        activity.push(NetworkReading::new(
+                            NetworkReadingFormat::Speed,
+                            ReadingValue::from(received),
+                            ReadingValue::from(transmitted),
+                        ));
+                    }
 
-                                activity.push(NetworkReading::new(
-                                    NetworkReadingFormat::Speed,
-                                    ReadingValue::from(received),
-                                    ReadingValue::from(transmitted),
-                                ));
-                            }
+                    if self.show_total_activity {
+                        let total_received = Self::to_pretty_bytes(data.total_received(), 1);
+                        let total_transmitted = Self::to_pretty_bytes(data.total_transmitted(), 1);
 
-                            if self.show_total_activity {
-                                let total_received =
-                                    Self::to_pretty_bytes(data.total_received(), 1);
-                                let total_transmitted =
-                                    Self::to_pretty_bytes(data.total_transmitted(), 1);
-
-                                total_activity.push(NetworkReading::new(
-                                    NetworkReadingFormat::Total,
-                                    ReadingValue::from(total_received),
-                                    ReadingValue::from(total_transmitted),
-                                ))
-                            }
-                        }
+                        total_activity.push(NetworkReading::new(
+                            NetworkReadingFormat::Total,
+                            ReadingValue::from(total_received),
+                            ReadingValue::from(total_transmitted),
+                        ))
                     }
                 }
             }
@@ -328,6 +437,8 @@ impl BarWidget for Network {
             // widget spacing: make sure to use the same config to call the apply_on_widget function
             let mut render_config = config.clone();
 
+            self.refresh_interfaces();
+
             if self.show_total_activity || self.show_activity {
                 let (activity, total_activity) = self.network_activity();
 
@@ -434,13 +545,11 @@ impl BarWidget for Network {
             }
 
             if self.show_default_interface {
-                self.default_interface();
-
-                if !self.default_interface.is_empty() {
+                for interface in &self.interfaces {
                     let mut layout_job = LayoutJob::simple(
                         match self.label_prefix {
                             LabelPrefix::Icon | LabelPrefix::IconAndText => {
-                                egui_phosphor::regular::WIFI_HIGH.to_string()
+                                self.interface_icon(interface).to_string()
                             }
                             LabelPrefix::None | LabelPrefix::Text => String::new(),
                         },
@@ -449,12 +558,19 @@ impl BarWidget for Network {
                         100.0,
                     );
 
+                    let mut text = match &interface.wireless_connection {
+                        Some(connection) if self.show_wireless_details => {
+                            format!("{} ({})", interface.name, connection.ssid)
+                        }
+                        _ => interface.name.clone(),
+                    };
+
                     if let LabelPrefix::Text | LabelPrefix::IconAndText = self.label_prefix {
-                        self.default_interface.insert_str(0, "NET: ");
+                        text.insert_str(0, "NET: ");
                     }
 
                     layout_job.append(
-                        &self.default_interface,
+                        &text,
                         10.0,
                         TextFormat {
                             font_id: config.text_font_id.clone(),
@@ -476,6 +592,125 @@ impl BarWidget for Network {
             *config = render_config.clone();
         }
     }
+
+    fn next_refresh(&self) -> Option<Duration> {
+        let interval = Duration::from_secs(self.data_refresh_interval);
+
+        Some(
+            refresh_due_in(self.last_updated_interfaces, interval)
+                .min(refresh_due_in(self.last_updated_network_activity, interval)),
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+struct InterfaceInformation {
+    name: String,
+    is_up: bool,
+    is_wireless: bool,
+    wireless_connection: Option<WirelessConnection>,
+}
+
+#[derive(Clone, Debug)]
+struct WirelessConnection {
+    ssid: String,
+    /// Signal quality as a percentage
+    signal_quality: u32,
+}
+
+/// Returns the current connections of all connected wireless interfaces, keyed by interface GUID
+fn wireless_connections() -> HashMap<String, WirelessConnection> {
+    let mut connections = HashMap::new();
+
+    unsafe {
+        let mut negotiated_version = 0;
+        let mut handle = HANDLE::default();
+        if WlanOpenHandle(2, None, &mut negotiated_version, &mut handle) != ERROR_SUCCESS.0 {
+            return connections;
+        }
+
+        let mut interface_list: *mut WLAN_INTERFACE_INFO_LIST = std::ptr::null_mut();
+        if WlanEnumInterfaces(handle, None, &mut interface_list) == ERROR_SUCCESS.0 {
+            let interfaces = std::slice::from_raw_parts(
+                (*interface_list).InterfaceInfo.as_ptr(),
+                (*interface_list).dwNumberOfItems as usize,
+            );
+
+            for interface in interfaces {
+                if interface.isState != wlan_interface_state_connected {
+                    continue;
+                }
+
+                let mut data_size = 0;
+                let mut data = std::ptr::null_mut();
+                if WlanQueryInterface(
+                    handle,
+                    &interface.InterfaceGuid,
+                    wlan_intf_opcode_current_connection,
+                    None,
+                    &mut data_size,
+                    &mut data,
+                    None,
+                ) == ERROR_SUCCESS.0
+                {
+                    let attributes = &*(data as *const WLAN_CONNECTION_ATTRIBUTES);
+                    let association = &attributes.wlanAssociationAttributes;
+                    let ssid = &association.dot11Ssid;
+                    let ssid_length = (ssid.uSSIDLength as usize).min(ssid.ucSSID.len());
+
+                    connections.insert(
+                        format!("{:?}", interface.InterfaceGuid),
+                        WirelessConnection {
+                            ssid: String::from_utf8_lossy(&ssid.ucSSID[..ssid_length]).to_string(),
+                            signal_quality: association.wlanSignalQuality,
+                        },
+                    );
+
+                    WlanFreeMemory(data);
+                }
+            }
+
+            WlanFreeMemory(interface_list as *const c_void);
+        }
+
+        WlanCloseHandle(handle, None);
+    }
+
+    connections
+}
+
+/// Incremented whenever the operational state of any network interface changes
+static LINK_STATE_GENERATION: AtomicU64 = AtomicU64::new(0);
+static LINK_STATE_NOTIFICATIONS: Once = Once::new();
+
+unsafe extern "system" fn on_ip_interface_change(
+    _caller_context: *const c_void,
+    _row: *const MIB_IPINTERFACE_ROW,
+    _notification_type: MIB_NOTIFICATION_TYPE,
+) {
+    LINK_STATE_GENERATION.fetch_add(1, Ordering::SeqCst);
+    repaint::mark_dirty();
+}
+
+/// Registers for interface change notifications so that link state changes are reflected
+/// without waiting for the next refresh interval
+fn register_link_state_notifications() {
+    LINK_STATE_NOTIFICATIONS.call_once(|| {
+        let mut handle = HANDLE::default();
+        let result = unsafe {
+            NotifyIpInterfaceChange(
+                AF_UNSPEC,
+                Some(on_ip_interface_change),
+                None,
+                false,
+                &mut handle,
+            )
+        };
+
+        if result != ERROR_SUCCESS {
+            tracing::error!("could not register for network interface change notifications");
+        }
+    });
 }
 
 #[derive(Clone)]
@@ -0,0 +1,204 @@
+use crate::config::LabelPrefix;
+use crate::render::RenderConfig;
+use crate::render::WidgetTheme;
+use crate::selected_frame::SelectableFrame;
+use crate::widgets::widget::BarWidget;
+use eframe::egui::text::LayoutJob;
+use eframe::egui::Align;
+use eframe::egui::Context;
+use eframe::egui::Label;
+use eframe::egui::TextFormat;
+use eframe::egui::Ui;
+use serde::Deserialize;
+use serde::Serialize;
+use std::process::Command;
+use windows::Win32::System::Power::SetSuspendState;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct SessionConfig {
+    /// Enable the Session widget
+    pub enable: bool,
+    /// Theme overrides applied to this widget only
+    pub theme: Option<WidgetTheme>,
+    /// Display label prefix
+    pub label_prefix: Option<LabelPrefix>,
+    /// Show the hostname next to the user name (default: true)
+    pub show_hostname: Option<bool>,
+    /// Session actions to show when the widget is clicked (default: all)
+    pub actions: Option<Vec<SessionAction>>,
+    /// Require a second click to confirm a session action (default: true)
+    pub confirm_actions: Option<bool>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum SessionAction {
+    /// Lock the workstation
+    Lock,
+    /// Sign out of the current session
+    SignOut,
+    /// Put the computer to sleep
+    Sleep,
+    /// Restart the computer
+    Restart,
+    /// Shut down the computer
+    Shutdown,
+}
+
+impl SessionAction {
+    const ALL: [SessionAction; 5] = [
+        SessionAction::Lock,
+        SessionAction::SignOut,
+        SessionAction::Sleep,
+        SessionAction::Restart,
+        SessionAction::Shutdown,
+    ];
+
+    fn icon(&self) -> &'static str {
+        match self {
+            SessionAction::Lock => egui_phosphor::regular::LOCK,
+            SessionAction::SignOut => egui_phosphor::regular::SIGN_OUT,
+            SessionAction::Sleep => egui_phosphor::regular::MOON,
+            SessionAction::Restart => egui_phosphor::regular::ARROW_CLOCKWISE,
+            SessionAction::Shutdown => egui_phosphor::regular::POWER,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            SessionAction::Lock => "Lock",
+            SessionAction::SignOut => "Sign out",
+            SessionAction::Sleep => "Sleep",
+            SessionAction::Restart => "Restart",
+            SessionAction::Shutdown => "Shut down",
+        }
+    }
+
+    fn execute(&self) {
+        let args: &[&str] = match self {
+            SessionAction::Lock => &["/C", "rundll32.exe user32.dll,LockWorkStation"],
+            SessionAction::SignOut => &["/C", "shutdown.exe /l"],
+            SessionAction::Restart => &["/C", "shutdown.exe /r /t 0"],
+            SessionAction::Shutdown => &["/C", "shutdown.exe /s /t 0"],
+            SessionAction::Sleep => {
+                // rundll32 would hibernate instead of sleeping when hibernation is enabled
+                if !unsafe { SetSuspendState(false, false, false) } {
+                    tracing::error!("failed to put the computer to sleep");
+                }
+
+                return;
+            }
+        };
+
+        if let Err(error) = Command::new("cmd.exe").args(args).spawn() {
+            tracing::error!("failed to execute session action {:?}: {}", self, error);
+        }
+    }
+}
+
+impl From<SessionConfig> for Session {
+    fn from(value: SessionConfig) -> Self {
+        let user = std::env::var("USERNAME").unwrap_or_default();
+        let label = match std::env::var("COMPUTERNAME") {
+            Ok(hostname) if value.show_hostname.unwrap_or(true) => format!("{user}@{hostname}"),
+            _ => user,
+        };
+
+        Self {
+            enable: value.enable,
+            label,
+            label_prefix: value.label_prefix.unwrap_or(LabelPrefix::Icon),
+            actions: value.actions.unwrap_or_else(|| SessionAction::ALL.to_vec()),
+            confirm_actions: value.confirm_actions.unwrap_or(true),
+            show_actions: false,
+            pending_action: None,
+        }
+    }
+}
+
+pub struct Session {
+    pub enable: bool,
+    label: String,
+    label_prefix: LabelPrefix,
+    actions: Vec<SessionAction>,
+    confirm_actions: bool,
+    show_actions: bool,
+    pending_action: Option<SessionAction>,
+}
+
+impl BarWidget for Session {
+    fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
+        if self.enable {
+            let mut layout_job = LayoutJob::simple(
+                match self.label_prefix {
+                    LabelPrefix::Icon | LabelPrefix::IconAndText => {
+                        egui_phosphor::regular::USER.to_string()
+                    }
+                    LabelPrefix::None | LabelPrefix::Text => String::new(),
+                },
+                config.icon_font_id.clone(),
+                ctx.style().visuals.selection.stroke.color,
+                100.0,
+            );
+
+            layout_job.append(
+                &self.label,
+                10.0,
+                TextFormat {
+                    font_id: config.text_font_id.clone(),
+                    color: ctx.style().visuals.text_color(),
+                    valign: Align::Center,
+                    ..Default::default()
+                },
+            );
+
+            let icon_font_id = config.icon_font_id.clone();
+
+            config.apply_on_widget(false, ui, |ui| {
+                if SelectableFrame::new(self.show_actions)
+                    .show(ui, |ui| ui.add(Label::new(layout_job).selectable(false)))
+                    .clicked()
+                {
+                    self.show_actions = !self.show_actions;
+                    self.pending_action = None;
+                }
+
+                if self.show_actions {
+                    for action in &self.actions {
+                        let is_pending = self.pending_action == Some(*action);
+
+                        let hover_text = if is_pending {
+                            format!("Click again to {}", action.label().to_lowercase())
+                        } else {
+                            action.label().to_string()
+                        };
+
+                        if SelectableFrame::new(is_pending)
+                            .show(ui, |ui| {
+                                ui.add(
+                                    Label::new(LayoutJob::simple_singleline(
+                                        action.icon().to_string(),
+                                        icon_font_id.clone(),
+                                        ctx.style().visuals.selection.stroke.color,
+                                    ))
+                                    .selectable(false),
+                                )
+                            })
+                            .on_hover_text(hover_text)
+                            .clicked()
+                        {
+                            if !self.confirm_actions || is_pending {
+                                self.show_actions = false;
+                                self.pending_action = None;
+                                action.execute();
+                            } else {
+                                self.pending_action = Some(*action);
+                            }
+                        }
+                    }
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use crate::render::RenderConfig;
+use crate::render::WidgetTheme;
+use crate::selected_frame::SelectableFrame;
+use crate::widgets::applications::MIN_LAUNCH_INTERVAL;
+use crate::widgets::widget::BarWidget;
+use eframe::egui::text::LayoutJob;
+use eframe::egui::Align;
+use eframe::egui::Context;
+use eframe::egui::Label;
+use eframe::egui::TextFormat;
+use eframe::egui::Ui;
+use komorebi_client::PathExt;
+use serde::Deserialize;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Instant;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ShortcutsConfig {
+    /// Enable the Shortcuts widget
+    pub enable: bool,
+    /// Theme overrides applied to this widget only
+    pub theme: Option<WidgetTheme>,
+    /// Shortcuts to display
+    pub items: Vec<ShortcutConfig>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ShortcutConfig {
+    /// Text to display for the shortcut (default: the name of the executable if there is no icon)
+    pub label: Option<String>,
+    /// Icon glyph to display for the shortcut, e.g. a Phosphor icon
+    pub icon: Option<String>,
+    /// Executable to launch, either as a path or as a name which can be found on the PATH
+    pub exe: String,
+    /// Arguments to pass to the executable
+    pub args: Option<Vec<String>>,
+    /// Working directory to launch the executable in
+    pub working_directory: Option<String>,
+}
+
+impl From<ShortcutsConfig> for Shortcuts {
+    fn from(value: ShortcutsConfig) -> Self {
+        Self {
+            enable: value.enable,
+            items: value.items.into_iter().map(Shortcut::from).collect(),
+        }
+    }
+}
+
+impl From<ShortcutConfig> for Shortcut {
+    fn from(value: ShortcutConfig) -> Self {
+        let exe = value.exe.replace_env();
+        let label = value.label.or_else(|| {
+            value.icon.is_none().then(|| {
+                exe.file_stem()
+                    .map(|stem| stem.to_string_lossy().to_string())
+                    .unwrap_or_else(|| value.exe.clone())
+            })
+        });
+
+        Self {
+            label,
+            icon: value.icon,
+            exe,
+            args: value.args.unwrap_or_default(),
+            working_directory: value.working_directory.map(|dir| dir.replace_env()),
+            // allow an immediate launch
+            last_launch: Instant::now() - 2 * MIN_LAUNCH_INTERVAL,
+        }
+    }
+}
+
+pub struct Shortcuts {
+    pub enable: bool,
+    items: Vec<Shortcut>,
+}
+
+struct Shortcut {
+    label: Option<String>,
+    icon: Option<String>,
+    exe: PathBuf,
+    args: Vec<String>,
+    working_directory: Option<PathBuf>,
+    last_launch: Instant,
+}
+
+impl Shortcut {
+    /// The full command line of the shortcut, shown on hover
+    fn command_line(&self) -> String {
+        std::iter::once(self.exe.to_string_lossy().to_string())
+            .chain(self.args.iter().cloned())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn launch_if_ready(&mut self) {
+        let now = Instant::now();
+        if now.duration_since(self.last_launch) < MIN_LAUNCH_INTERVAL {
+            return;
+        }
+
+        self.last_launch = now;
+
+        let mut command = Command::new(&self.exe);
+        command.args(&self.args);
+        if let Some(working_directory) = &self.working_directory {
+            command.current_dir(working_directory);
+        }
+
+        if let Err(error) = command.spawn() {
+            tracing::error!(
+                "failed to launch shortcut '{}': {}",
+                self.command_line(),
+                error
+            );
+        }
+    }
+}
+
+impl BarWidget for Shortcuts {
+    fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
+        if self.enable {
+            let icon_font_id = config.icon_font_id.clone();
+            let text_font_id = config.text_font_id.clone();
+
+            config.apply_on_widget(false, ui, |ui| {
+                for shortcut in &mut self.items {
+                    let mut layout_job = LayoutJob::simple(
+                        shortcut.icon.clone().unwrap_or_default(),
+                        icon_font_id.clone(),
+                        ctx.style().visuals.selection.stroke.color,
+                        100.0,
+                    );
+
+                    if let Some(label) = &shortcut.label {
+                        layout_job.append(
+                            label,
+                            if shortcut.icon.is_some() { 10.0 } else { 0.0 },
+                            TextFormat {
+                                font_id: text_font_id.clone(),
+                                color: ctx.style().visuals.text_color(),
+                                valign: Align::Center,
+                                ..Default::default()
+                            },
+                        );
+                    }
+
+                    if SelectableFrame::new(false)
+                        .show(ui, |ui| ui.add(Label::new(layout_job).selectable(false)))
+                        .on_hover_text(shortcut.command_line())
+                        .clicked()
+                    {
+                        shortcut.launch_if_ready();
+                    }
+                }
+            });
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use crate::bar::Alignment;
 use crate::config::LabelPrefix;
 use crate::render::RenderConfig;
+use crate::render::WidgetTheme;
+use crate::repaint::refresh_due_in;
 use crate::selected_frame::SelectableFrame;
 use crate::widgets::widget::BarWidget;
 use eframe::egui::text::LayoutJob;
@@ -14,13 +16,16 @@ use serde::Serialize;
 use std::process::Command;
 use std::time::Duration;
 use std::time::Instant;
+use sysinfo::Disk;
 use sysinfo::Disks;
 
-#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct StorageConfig {
     /// Enable the Storage widget
     pub enable: bool,
+    /// Theme overrides applied to this widget only
+    pub theme: Option<WidgetTheme>,
     /// Data refresh interval (default: 10 seconds)
     pub data_refresh_interval: Option<u64>,
     /// Display label prefix
@@ -29,6 +34,31 @@ pub struct StorageConfig {
     pub auto_select_over: Option<u8>,
     /// Hide when the current percentage is under this value [[1-100]]
     pub auto_hide_under: Option<u8>,
+    /// Only show these volumes, in this order (default: all volumes)
+    pub volumes: Option<Vec<StorageVolumeConfig>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct StorageVolumeConfig {
+    /// Drive letter (e.g. "C:") or label of the volume
+    pub volume: String,
+    /// Name to display instead of the mount point
+    pub name: Option<String>,
+    /// Select when the current percentage of this volume is over this value [[1-100]]
+    pub auto_select_over: Option<u8>,
+    /// Hide when the current percentage of this volume is under this value [[1-100]]
+    pub auto_hide_under: Option<u8>,
+}
+
+impl StorageVolumeConfig {
+    fn matches(&self, disk: &Disk) -> bool {
+        let normalize = |volume: &str| volume.trim_end_matches(['\\', '/', ':']).to_uppercase();
+
+        let volume = normalize(&self.volume);
+        volume == normalize(&disk.mount_point().to_string_lossy())
+            || volume == normalize(&disk.name().to_string_lossy())
+    }
 }
 
 impl From<StorageConfig> for Storage {
@@ -40,6 +70,7 @@ impl From<StorageConfig> for Storage {
             label_prefix: value.label_prefix.unwrap_or(LabelPrefix::IconAndText),
             auto_select_over: value.auto_select_over.map(|o| o.clamp(1, 100)),
             auto_hide_under: value.auto_hide_under.map(|o| o.clamp(1, 100)),
+            volumes: value.volumes,
             last_updated: Instant::now(),
         }
     }
@@ -47,6 +78,7 @@ impl From<StorageConfig> for Storage {
 
 struct StorageDisk {
     label: String,
+    mount_point: String,
     selected: bool,
 }
 
@@ -57,6 +89,7 @@ pub struct Storage {
     label_prefix: LabelPrefix,
     auto_select_over: Option<u8>,
     auto_hide_under: Option<u8>,
+    volumes: Option<Vec<StorageVolumeConfig>>,
     last_updated: Instant,
 }
 
@@ -68,34 +101,62 @@ impl Storage {
             self.last_updated = now;
         }
 
+        let selected_disks = match &self.volumes {
+            Some(volumes) => volumes
+                .iter()
+                .filter_map(|volume| {
+                    self.disks
+                        .iter()
+                        .find(|disk| volume.matches(disk))
+                        .map(|disk| (disk, Some(volume)))
+                })
+                .collect::<Vec<_>>(),
+            None => {
+                let mut disks = self
+                    .disks
+                    .iter()
+                    .map(|disk| (disk, None))
+                    .collect::<Vec<_>>();
+                disks.sort_by(|(a, _), (b, _)| a.mount_point().cmp(b.mount_point()));
+                disks
+            }
+        };
+
         let mut disks = vec![];
 
-        for disk in &self.disks {
-            let mount = disk.mount_point();
+        for (disk, volume) in selected_disks {
+            let mount = disk.mount_point().to_string_lossy().to_string();
             let total = disk.total_space();
             let available = disk.available_space();
             let used = total - available;
             let percentage = ((used * 100) / total) as u8;
 
-            let hide = self.auto_hide_under.is_some_and(|u| percentage <= u);
+            let auto_hide_under = volume
+                .and_then(|v| v.auto_hide_under.map(|u| u.clamp(1, 100)))
+                .or(self.auto_hide_under);
+            let hide = auto_hide_under.is_some_and(|u| percentage <= u);
 
             if !hide {
-                let selected = self.auto_select_over.is_some_and(|o| percentage >= o);
+                let auto_select_over = volume
+                    .and_then(|v| v.auto_select_over.map(|o| o.clamp(1, 100)))
+                    .or(self.auto_select_over);
+                let selected = auto_select_over.is_some_and(|o| percentage >= o);
+
+                let name = volume.and_then(|v| v.name.as_deref()).unwrap_or(&mount);
 
                 disks.push(StorageDisk {
                     label: match self.label_prefix {
                         LabelPrefix::Text | LabelPrefix::IconAndText => {
-                            format!("{} {}%", mount.to_string_lossy(), percentage)
+                            format!("{} {}%", name, percentage)
                         }
                         LabelPrefix::None | LabelPrefix::Icon => format!("{}%", percentage),
                     },
+                    mount_point: mount.clone(),
                     selected,
                 })
             }
         }
 
-        disks.sort_by(|a, b| a.label.cmp(&b.label));
-
         disks
     }
 }
@@ -144,11 +205,7 @@ impl BarWidget for Storage {
                         .clicked()
                     {
                         if let Err(error) = Command::new("cmd.exe")
-                            .args([
-                                "/C",
-                                "explorer.exe",
-                                output.label.split(' ').collect::<Vec<&str>>()[0],
-                            ])
+                            .args(["/C", "explorer.exe", &output.mount_point])
                             .spawn()
                         {
                             eprintln!("{}", error)
@@ -158,4 +215,11 @@ impl BarWidget for Storage {
             }
         }
     }
+
+    fn next_refresh(&self) -> Option<Duration> {
+        Some(refresh_due_in(
+            self.last_updated,
+            Duration::from_secs(self.data_refresh_interval),
+        ))
+    }
 }
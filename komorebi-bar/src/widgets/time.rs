@@ -1,10 +1,18 @@
 use crate::bar::Alignment;
+use crate::config::DateTimeLocale;
+use crate::config::FormatCondition;
 use crate::config::LabelPrefix;
+use crate::locale;
 use crate::render::RenderConfig;
+use crate::render::WidgetTheme;
+use crate::repaint::refresh_due_in;
 use crate::selected_frame::SelectableFrame;
 use crate::widgets::widget::BarWidget;
+use chrono::Datelike;
 use chrono::Local;
+use chrono::Locale;
 use chrono::NaiveTime;
+use chrono::Timelike;
 use chrono_tz::Tz;
 use eframe::egui::text::LayoutJob;
 use eframe::egui::Align;
@@ -75,6 +83,8 @@ lazy_static! {
 pub struct TimeConfig {
     /// Enable the Time widget
     pub enable: bool,
+    /// Theme overrides applied to this widget only
+    pub theme: Option<WidgetTheme>,
     /// Set the Time format
     pub format: TimeFormat,
     /// Display label prefix
@@ -94,6 +104,23 @@ pub struct TimeConfig {
     pub timezone: Option<String>,
     /// Change the icon depending on the time. The default icon is used between 8:30 and 12:00. (default: false)
     pub changing_icon: Option<bool>,
+    /// Additional formats to cycle through on click, instead of the built-in format order
+    pub alternate_formats: Option<Vec<TimeFormat>>,
+    /// Formats which take precedence over the selected format whenever their condition matches,
+    /// the first matching entry is used
+    pub scheduled_formats: Option<Vec<ScheduledTimeFormat>>,
+    /// Locale used for the AM/PM designators and by the `Localized` format for the order of the
+    /// time (default: none, English designators)
+    pub locale: Option<DateTimeLocale>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ScheduledTimeFormat {
+    /// Condition which must match for this format to be used
+    pub condition: FormatCondition,
+    /// Time format to use while the condition matches
+    pub format: TimeFormat,
 }
 
 impl From<TimeConfig> for Time {
@@ -102,11 +129,20 @@ impl From<TimeConfig> for Time {
         // This is still better than getting an update every frame
         let data_refresh_interval = 500;
 
+        let mut alternate_formats = value.alternate_formats.unwrap_or_default();
+        if !alternate_formats.is_empty() {
+            alternate_formats.insert(0, value.format.clone());
+        }
+
         Self {
             enable: value.enable,
             format: value.format,
+            alternate_formats,
+            alternate_format_idx: 0,
+            scheduled_formats: value.scheduled_formats.unwrap_or_default(),
             label_prefix: value.label_prefix.unwrap_or(LabelPrefix::Icon),
             timezone: value.timezone,
+            locale: value.locale.as_ref().and_then(locale::resolve),
             changing_icon: value.changing_icon.unwrap_or_default(),
             data_refresh_interval_millis: data_refresh_interval,
             last_state: TimeOutput::new(),
@@ -132,6 +168,8 @@ pub enum TimeFormat {
     BinaryCircle,
     /// Twenty-four-hour format displayed as a binary clock with rectangles (with seconds) (https://en.wikipedia.org/wiki/Binary_clock)
     BinaryRectangle,
+    /// Preferred time format of the configured locale
+    Localized,
     /// Custom format (https://docs.rs/chrono/latest/chrono/format/strftime/index.html)
     Custom(String),
 }
@@ -157,6 +195,7 @@ impl TimeFormat {
             TimeFormat::TwentyFourHourWithoutSeconds => String::from("%H:%M"),
             TimeFormat::BinaryCircle => String::from("c%T"),
             TimeFormat::BinaryRectangle => String::from("r%T"),
+            TimeFormat::Localized => String::from("%X"),
             TimeFormat::Custom(format) => format.to_string(),
         }
     }
@@ -181,8 +220,12 @@ impl TimeOutput {
 pub struct Time {
     pub enable: bool,
     pub format: TimeFormat,
+    alternate_formats: Vec<TimeFormat>,
+    alternate_format_idx: usize,
+    scheduled_formats: Vec<ScheduledTimeFormat>,
     label_prefix: LabelPrefix,
     timezone: Option<String>,
+    locale: Option<Locale>,
     changing_icon: bool,
     data_refresh_interval_millis: u64,
     last_state: TimeOutput,
@@ -190,6 +233,24 @@ pub struct Time {
 }
 
 impl Time {
+    /// Returns the first scheduled format matching `now`, falling back to the selected format
+    fn active_format(&self, now: &(impl Datelike + Timelike)) -> &TimeFormat {
+        self.scheduled_formats
+            .iter()
+            .find(|scheduled| scheduled.condition.matches(now))
+            .map_or(&self.format, |scheduled| &scheduled.format)
+    }
+
+    fn next_format(&mut self) {
+        if self.alternate_formats.is_empty() {
+            self.format.toggle();
+        } else {
+            self.alternate_format_idx =
+                (self.alternate_format_idx + 1) % self.alternate_formats.len();
+            self.format = self.alternate_formats[self.alternate_format_idx].clone();
+        }
+    }
+
     fn output(&mut self) -> TimeOutput {
         let mut output = self.last_state.clone();
         let now = Instant::now();
@@ -202,8 +263,7 @@ impl Time {
                     Ok(tz) => {
                         let dt = Local::now().with_timezone(&tz);
                         (
-                            dt.format(&self.format.fmt_string())
-                                .to_string()
+                            locale::format(&dt, &self.active_format(&dt).fmt_string(), self.locale)
                                 .trim()
                                 .to_string(),
                             Some(dt.time()),
@@ -214,8 +274,7 @@ impl Time {
                 None => {
                     let dt = Local::now();
                     (
-                        dt.format(&self.format.fmt_string())
-                            .to_string()
+                        locale::format(&dt, &self.active_format(&dt).fmt_string(), self.locale)
                             .trim()
                             .to_string(),
                         Some(dt.time()),
@@ -529,10 +588,17 @@ impl BarWidget for Time {
                         })
                         .clicked()
                     {
-                        self.format.toggle()
+                        self.next_format()
                     }
                 });
             }
         }
     }
+
+    fn next_refresh(&self) -> Option<Duration> {
+        Some(refresh_due_in(
+            self.last_updated,
+            Duration::from_millis(self.data_refresh_interval_millis),
+        ))
+    }
 }
@@ -0,0 +1,202 @@
+use crate::config::LabelPrefix;
+use crate::render::RenderConfig;
+use crate::render::WidgetTheme;
+use crate::repaint::refresh_due_in;
+use crate::selected_frame::SelectableFrame;
+use crate::widgets::applications::UserCommand;
+use crate::widgets::widget::BarWidget;
+use chrono::Local;
+use chrono::TimeZone;
+use eframe::egui::text::LayoutJob;
+use eframe::egui::Align;
+use eframe::egui::Context;
+use eframe::egui::Label;
+use eframe::egui::TextFormat;
+use eframe::egui::Ui;
+use serde::Deserialize;
+use serde::Serialize;
+use std::time::Duration;
+use std::time::Instant;
+use sysinfo::System;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct UptimeConfig {
+    /// Enable the Uptime widget
+    pub enable: bool,
+    /// Theme overrides applied to this widget only
+    pub theme: Option<WidgetTheme>,
+    /// Data refresh interval (default: 60 seconds)
+    pub data_refresh_interval: Option<u64>,
+    /// Display label prefix
+    pub label_prefix: Option<LabelPrefix>,
+    /// Show the time of the last boot next to the uptime (default: false)
+    pub show_boot_time: Option<bool>,
+    /// Format of the last boot time (default: "%Y-%m-%d %H:%M")
+    pub boot_time_format: Option<String>,
+    /// Select when the uptime is over this many hours
+    pub auto_select_over: Option<u64>,
+    /// Command to run when the widget is clicked
+    pub on_click: Option<String>,
+}
+
+impl From<UptimeConfig> for Uptime {
+    fn from(value: UptimeConfig) -> Self {
+        let data_refresh_interval = value.data_refresh_interval.unwrap_or(60);
+
+        Self {
+            enable: value.enable,
+            data_refresh_interval,
+            label_prefix: value.label_prefix.unwrap_or(LabelPrefix::IconAndText),
+            show_boot_time: value.show_boot_time.unwrap_or_default(),
+            boot_time_format: value
+                .boot_time_format
+                .unwrap_or_else(|| String::from("%Y-%m-%d %H:%M")),
+            auto_select_over: value.auto_select_over,
+            on_click: value.on_click.as_deref().map(UserCommand::new),
+            last_state: UptimeOutput {
+                label: String::new(),
+                selected: false,
+            },
+            last_updated: Instant::now()
+                .checked_sub(Duration::from_secs(data_refresh_interval))
+                .unwrap(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct UptimeOutput {
+    label: String,
+    selected: bool,
+}
+
+pub struct Uptime {
+    pub enable: bool,
+    data_refresh_interval: u64,
+    label_prefix: LabelPrefix,
+    show_boot_time: bool,
+    boot_time_format: String,
+    auto_select_over: Option<u64>,
+    on_click: Option<UserCommand>,
+    last_state: UptimeOutput,
+    last_updated: Instant,
+}
+
+impl Uptime {
+    fn output(&mut self) -> UptimeOutput {
+        let now = Instant::now();
+        if now.duration_since(self.last_updated) > Duration::from_secs(self.data_refresh_interval) {
+            let uptime = System::uptime();
+
+            let mut label = match self.label_prefix {
+                LabelPrefix::Text | LabelPrefix::IconAndText => {
+                    format!("UPTIME: {}", format_uptime(uptime))
+                }
+                LabelPrefix::None | LabelPrefix::Icon => format_uptime(uptime),
+            };
+
+            if self.show_boot_time {
+                if let Some(boot_time) = i64::try_from(System::boot_time())
+                    .ok()
+                    .and_then(|boot_time| Local.timestamp_opt(boot_time, 0).single())
+                {
+                    label.push_str(&format!(
+                        " (since {})",
+                        boot_time.format(&self.boot_time_format)
+                    ));
+                }
+            }
+
+            self.last_state = UptimeOutput {
+                label,
+                selected: self
+                    .auto_select_over
+                    .is_some_and(|hours| uptime >= hours * 60 * 60),
+            };
+            self.last_updated = now;
+        }
+
+        self.last_state.clone()
+    }
+}
+
+/// Formats an uptime in seconds as days, hours and minutes, omitting leading zero units
+fn format_uptime(seconds: u64) -> String {
+    let days = seconds / 86400;
+    let hours = (seconds % 86400) / 3600;
+    let minutes = (seconds % 3600) / 60;
+
+    match (days, hours) {
+        (0, 0) => format!("{minutes}m"),
+        (0, _) => format!("{hours}h {minutes}m"),
+        _ => format!("{days}d {hours}h {minutes}m"),
+    }
+}
+
+impl BarWidget for Uptime {
+    fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
+        if self.enable {
+            let output = self.output();
+            if !output.label.is_empty() {
+                let auto_text_color = config.auto_select_text.filter(|_| output.selected);
+
+                let mut layout_job = LayoutJob::simple(
+                    match self.label_prefix {
+                        LabelPrefix::Icon | LabelPrefix::IconAndText => {
+                            egui_phosphor::regular::CLOCK_COUNTER_CLOCKWISE.to_string()
+                        }
+                        LabelPrefix::None | LabelPrefix::Text => String::new(),
+                    },
+                    config.icon_font_id.clone(),
+                    auto_text_color.unwrap_or(ctx.style().visuals.selection.stroke.color),
+                    100.0,
+                );
+
+                layout_job.append(
+                    &output.label,
+                    10.0,
+                    TextFormat {
+                        font_id: config.text_font_id.clone(),
+                        color: auto_text_color.unwrap_or(ctx.style().visuals.text_color()),
+                        valign: Align::Center,
+                        ..Default::default()
+                    },
+                );
+
+                let auto_focus_fill = config.auto_select_fill;
+
+                config.apply_on_widget(false, ui, |ui| {
+                    if SelectableFrame::new_auto(output.selected, auto_focus_fill)
+                        .show(ui, |ui| ui.add(Label::new(layout_job).selectable(false)))
+                        .clicked()
+                    {
+                        if let Some(command) = &mut self.on_click {
+                            command.launch_if_ready();
+                        }
+                    }
+                });
+            }
+        }
+    }
+
+    fn next_refresh(&self) -> Option<Duration> {
+        Some(refresh_due_in(
+            self.last_updated,
+            Duration::from_secs(self.data_refresh_interval),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_uptime() {
+        assert_eq!(format_uptime(59), "0m");
+        assert_eq!(format_uptime(45 * 60), "45m");
+        assert_eq!(format_uptime(3 * 3600 + 5 * 60), "3h 5m");
+        assert_eq!(format_uptime(2 * 86400 + 30 * 60), "2d 0h 30m");
+    }
+}
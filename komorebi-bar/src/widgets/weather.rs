@@ -0,0 +1,428 @@
+use crate::config::LabelPrefix;
+use crate::render::RenderConfig;
+use crate::render::WidgetTheme;
+use crate::repaint::refresh_due_in;
+use crate::selected_frame::SelectableFrame;
+use crate::widgets::widget::BarWidget;
+use eframe::egui::text::LayoutJob;
+use eframe::egui::Align;
+use eframe::egui::Context;
+use eframe::egui::Label;
+use eframe::egui::TextFormat;
+use eframe::egui::Ui;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::mpsc;
+use std::sync::mpsc::Receiver;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+/// Cache of the latest weather reports, shared by all Weather widgets so that reloading the
+/// configuration doesn't trigger a new request to the provider
+static WEATHER_CACHE: LazyLock<Mutex<HashMap<String, (Instant, WeatherReport)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WeatherConfig {
+    /// Enable the Weather widget
+    pub enable: bool,
+    /// Theme overrides applied to this widget only
+    pub theme: Option<WidgetTheme>,
+    /// Location of the weather conditions, either a city name or coordinates
+    pub location: WeatherLocation,
+    /// Temperature units (default: Celsius)
+    pub units: Option<TemperatureUnits>,
+    /// Weather data provider (default: OpenMeteo)
+    pub provider: Option<WeatherProviderKind>,
+    /// Data refresh interval (default: 30 minutes)
+    pub data_refresh_interval: Option<u64>,
+    /// Display label prefix
+    pub label_prefix: Option<LabelPrefix>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum WeatherLocation {
+    /// City name, resolved to coordinates by the provider
+    City(String),
+    /// Geographic coordinates
+    Coordinates {
+        /// Latitude in degrees
+        latitude: f64,
+        /// Longitude in degrees
+        longitude: f64,
+    },
+}
+
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum TemperatureUnits {
+    /// Degrees Celsius
+    #[default]
+    Celsius,
+    /// Degrees Fahrenheit
+    Fahrenheit,
+}
+
+impl TemperatureUnits {
+    fn symbol(self) -> &'static str {
+        match self {
+            TemperatureUnits::Celsius => "°C",
+            TemperatureUnits::Fahrenheit => "°F",
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum WeatherProviderKind {
+    /// Open-Meteo (https://open-meteo.com), which doesn't require an API key
+    #[default]
+    OpenMeteo,
+}
+
+impl WeatherProviderKind {
+    fn provider(self) -> Box<dyn WeatherProvider + Send> {
+        match self {
+            WeatherProviderKind::OpenMeteo => Box::new(OpenMeteo),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum WeatherCondition {
+    Clear,
+    PartlyCloudy,
+    Cloudy,
+    Fog,
+    Drizzle,
+    Rain,
+    Snow,
+    Thunderstorm,
+}
+
+impl WeatherCondition {
+    /// Maps a WMO weather interpretation code to a condition
+    pub fn from_wmo_code(code: u32) -> Self {
+        match code {
+            0 => WeatherCondition::Clear,
+            1 | 2 => WeatherCondition::PartlyCloudy,
+            45 | 48 => WeatherCondition::Fog,
+            51..=57 => WeatherCondition::Drizzle,
+            61..=67 | 80..=82 => WeatherCondition::Rain,
+            71..=77 | 85 | 86 => WeatherCondition::Snow,
+            95..=99 => WeatherCondition::Thunderstorm,
+            _ => WeatherCondition::Cloudy,
+        }
+    }
+
+    fn icon(self, is_day: bool) -> &'static str {
+        match self {
+            WeatherCondition::Clear if is_day => egui_phosphor::regular::SUN,
+            WeatherCondition::Clear => egui_phosphor::regular::MOON,
+            WeatherCondition::PartlyCloudy if is_day => egui_phosphor::regular::CLOUD_SUN,
+            WeatherCondition::PartlyCloudy => egui_phosphor::regular::CLOUD_MOON,
+            WeatherCondition::Cloudy => egui_phosphor::regular::CLOUD,
+            WeatherCondition::Fog => egui_phosphor::regular::CLOUD_FOG,
+            WeatherCondition::Drizzle | WeatherCondition::Rain => {
+                egui_phosphor::regular::CLOUD_RAIN
+            }
+            WeatherCondition::Snow => egui_phosphor::regular::CLOUD_SNOW,
+            WeatherCondition::Thunderstorm => egui_phosphor::regular::CLOUD_LIGHTNING,
+        }
+    }
+}
+
+/// Current weather conditions at a location
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct WeatherReport {
+    pub temperature: f64,
+    pub condition: WeatherCondition,
+    pub is_day: bool,
+}
+
+/// A source of current weather conditions
+pub trait WeatherProvider {
+    fn current(
+        &self,
+        location: &WeatherLocation,
+        units: TemperatureUnits,
+    ) -> color_eyre::Result<WeatherReport>;
+}
+
+pub struct OpenMeteo;
+
+impl OpenMeteo {
+    fn coordinates(
+        client: &reqwest::blocking::Client,
+        location: &WeatherLocation,
+    ) -> color_eyre::Result<(f64, f64)> {
+        #[derive(Deserialize)]
+        struct GeocodingResult {
+            latitude: f64,
+            longitude: f64,
+        }
+
+        #[derive(Deserialize)]
+        struct Geocoding {
+            #[serde(default)]
+            results: Vec<GeocodingResult>,
+        }
+
+        match location {
+            WeatherLocation::Coordinates {
+                latitude,
+                longitude,
+            } => Ok((*latitude, *longitude)),
+            WeatherLocation::City(city) => {
+                let response = client
+                    .get("https://geocoding-api.open-meteo.com/v1/search")
+                    .query(&[("name", city.as_str()), ("count", "1")])
+                    .send()?
+                    .error_for_status()?;
+                let geocoding = serde_json::from_str::<Geocoding>(&response.text()?)?;
+
+                geocoding
+                    .results
+                    .first()
+                    .map(|result| (result.latitude, result.longitude))
+                    .ok_or_else(|| color_eyre::eyre::eyre!("could not find the city '{city}'"))
+            }
+        }
+    }
+}
+
+impl WeatherProvider for OpenMeteo {
+    fn current(
+        &self,
+        location: &WeatherLocation,
+        units: TemperatureUnits,
+    ) -> color_eyre::Result<WeatherReport> {
+        #[derive(Deserialize)]
+        struct Current {
+            temperature_2m: f64,
+            weather_code: u32,
+            is_day: u8,
+        }
+
+        #[derive(Deserialize)]
+        struct Forecast {
+            current: Current,
+        }
+
+        let client = reqwest::blocking::Client::new();
+        let (latitude, longitude) = Self::coordinates(&client, location)?;
+
+        let response = client
+            .get("https://api.open-meteo.com/v1/forecast")
+            .query(&[
+                ("latitude", latitude.to_string()),
+                ("longitude", longitude.to_string()),
+                ("current", "temperature_2m,weather_code,is_day".to_string()),
+                (
+                    "temperature_unit",
+                    match units {
+                        TemperatureUnits::Celsius => "celsius",
+                        TemperatureUnits::Fahrenheit => "fahrenheit",
+                    }
+                    .to_string(),
+                ),
+            ])
+            .send()?
+            .error_for_status()?;
+        let forecast = serde_json::from_str::<Forecast>(&response.text()?)?;
+
+        Ok(WeatherReport {
+            temperature: forecast.current.temperature_2m,
+            condition: WeatherCondition::from_wmo_code(forecast.current.weather_code),
+            is_day: forecast.current.is_day == 1,
+        })
+    }
+}
+
+impl From<&WeatherConfig> for Weather {
+    fn from(value: &WeatherConfig) -> Self {
+        let data_refresh_interval = value.data_refresh_interval.unwrap_or(30);
+        let units = value.units.unwrap_or_default();
+        let provider = value.provider.unwrap_or_default();
+
+        Self {
+            enable: value.enable,
+            cache_key: format!("{provider:?}|{:?}|{units:?}", value.location),
+            location: value.location.clone(),
+            units,
+            provider,
+            data_refresh_interval,
+            label_prefix: value.label_prefix.unwrap_or(LabelPrefix::Icon),
+            pending: None,
+            last_updated: None,
+        }
+    }
+}
+
+pub struct Weather {
+    pub enable: bool,
+    location: WeatherLocation,
+    units: TemperatureUnits,
+    provider: WeatherProviderKind,
+    data_refresh_interval: u64,
+    label_prefix: LabelPrefix,
+    cache_key: String,
+    pending: Option<Receiver<WeatherReport>>,
+    last_updated: Option<Instant>,
+}
+
+impl Weather {
+    fn output(&mut self, ctx: &Context) -> Option<WeatherReport> {
+        let refresh_interval = Duration::from_secs(self.data_refresh_interval * 60);
+
+        if let Some(pending) = &self.pending {
+            match pending.try_recv() {
+                Ok(report) => {
+                    if let Ok(mut cache) = WEATHER_CACHE.lock() {
+                        cache.insert(self.cache_key.clone(), (Instant::now(), report));
+                    }
+                    self.pending = None;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => self.pending = None,
+                Err(mpsc::TryRecvError::Empty) => {}
+            }
+        }
+
+        let cached = WEATHER_CACHE
+            .lock()
+            .ok()
+            .and_then(|cache| cache.get(&self.cache_key).copied());
+
+        let now = Instant::now();
+        let is_stale =
+            cached.is_none_or(|(fetched, _)| now.duration_since(fetched) > refresh_interval);
+
+        // Failed requests are only retried after the refresh interval
+        if is_stale
+            && self.pending.is_none()
+            && self
+                .last_updated
+                .is_none_or(|last_updated| now.duration_since(last_updated) > refresh_interval)
+        {
+            let (tx, rx) = mpsc::channel();
+            let provider = self.provider.provider();
+            let location = self.location.clone();
+            let units = self.units;
+            let ctx = ctx.clone();
+
+            std::thread::spawn(move || match provider.current(&location, units) {
+                Ok(report) => {
+                    if tx.send(report).is_ok() {
+                        ctx.request_repaint();
+                    }
+                }
+                Err(error) => tracing::error!("could not fetch the weather: {error}"),
+            });
+
+            self.pending = Some(rx);
+            self.last_updated = Some(now);
+        }
+
+        cached.map(|(_, report)| report)
+    }
+}
+
+impl BarWidget for Weather {
+    fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
+        if self.enable {
+            if let Some(report) = self.output(ctx) {
+                let mut layout_job = LayoutJob::simple(
+                    match self.label_prefix {
+                        LabelPrefix::Icon | LabelPrefix::IconAndText => {
+                            report.condition.icon(report.is_day).to_string()
+                        }
+                        LabelPrefix::None | LabelPrefix::Text => String::new(),
+                    },
+                    config.icon_font_id.clone(),
+                    ctx.style().visuals.selection.stroke.color,
+                    100.0,
+                );
+
+                let temperature = format!("{:.0}{}", report.temperature, self.units.symbol());
+
+                layout_job.append(
+                    &match self.label_prefix {
+                        LabelPrefix::Text | LabelPrefix::IconAndText => {
+                            format!("WEATHER: {temperature}")
+                        }
+                        LabelPrefix::None | LabelPrefix::Icon => temperature,
+                    },
+                    10.0,
+                    TextFormat {
+                        font_id: config.text_font_id.clone(),
+                        color: ctx.style().visuals.text_color(),
+                        valign: Align::Center,
+                        ..Default::default()
+                    },
+                );
+
+                config.apply_on_widget(false, ui, |ui| {
+                    SelectableFrame::new(false)
+                        .show(ui, |ui| ui.add(Label::new(layout_job).selectable(false)));
+                });
+            }
+        }
+    }
+
+    fn next_refresh(&self) -> Option<Duration> {
+        // a pending request asks for a repaint by itself once it has finished
+        self.pending.is_none().then(|| {
+            self.last_updated.map_or(Duration::ZERO, |last_updated| {
+                refresh_due_in(
+                    last_updated,
+                    Duration::from_secs(self.data_refresh_interval * 60),
+                )
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wmo_code_conditions() {
+        assert_eq!(WeatherCondition::from_wmo_code(0), WeatherCondition::Clear);
+        assert_eq!(
+            WeatherCondition::from_wmo_code(2),
+            WeatherCondition::PartlyCloudy
+        );
+        assert_eq!(WeatherCondition::from_wmo_code(3), WeatherCondition::Cloudy);
+        assert_eq!(WeatherCondition::from_wmo_code(48), WeatherCondition::Fog);
+        assert_eq!(WeatherCondition::from_wmo_code(81), WeatherCondition::Rain);
+        assert_eq!(WeatherCondition::from_wmo_code(86), WeatherCondition::Snow);
+        assert_eq!(
+            WeatherCondition::from_wmo_code(95),
+            WeatherCondition::Thunderstorm
+        );
+    }
+
+    #[test]
+    fn test_deserialize_location() {
+        let location = serde_json::from_str::<WeatherLocation>(r#""Berlin""#).unwrap();
+        assert_eq!(location, WeatherLocation::City(String::from("Berlin")));
+
+        let location =
+            serde_json::from_str::<WeatherLocation>(r#"{"latitude": 52.5, "longitude": 13.4}"#)
+                .unwrap();
+        assert_eq!(
+            location,
+            WeatherLocation::Coordinates {
+                latitude: 52.5,
+                longitude: 13.4
+            }
+        );
+    }
+}
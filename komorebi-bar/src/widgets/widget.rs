@@ -1,10 +1,13 @@
 use crate::render::RenderConfig;
+use crate::render::WidgetTheme;
 use crate::widgets::applications::Applications;
 use crate::widgets::applications::ApplicationsConfig;
 use crate::widgets::battery::Battery;
 use crate::widgets::battery::BatteryConfig;
 use crate::widgets::cpu::Cpu;
 use crate::widgets::cpu::CpuConfig;
+use crate::widgets::custom::Custom;
+use crate::widgets::custom::CustomConfig;
 use crate::widgets::date::Date;
 use crate::widgets::date::DateConfig;
 use crate::widgets::keyboard::Keyboard;
@@ -17,19 +20,33 @@ use crate::widgets::memory::Memory;
 use crate::widgets::memory::MemoryConfig;
 use crate::widgets::network::Network;
 use crate::widgets::network::NetworkConfig;
+use crate::widgets::session::Session;
+use crate::widgets::session::SessionConfig;
+use crate::widgets::shortcuts::Shortcuts;
+use crate::widgets::shortcuts::ShortcutsConfig;
 use crate::widgets::storage::Storage;
 use crate::widgets::storage::StorageConfig;
 use crate::widgets::time::Time;
 use crate::widgets::time::TimeConfig;
 use crate::widgets::update::Update;
 use crate::widgets::update::UpdateConfig;
+use crate::widgets::uptime::Uptime;
+use crate::widgets::uptime::UptimeConfig;
+use crate::widgets::weather::Weather;
+use crate::widgets::weather::WeatherConfig;
 use eframe::egui::Context;
 use eframe::egui::Ui;
 use serde::Deserialize;
 use serde::Serialize;
+use std::time::Duration;
 
 pub trait BarWidget {
     fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig);
+
+    /// Time left until the widget should refresh its data, for widgets polling on an interval
+    fn next_refresh(&self) -> Option<Duration> {
+        None
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -38,32 +55,103 @@ pub enum WidgetConfig {
     Applications(ApplicationsConfig),
     Battery(BatteryConfig),
     Cpu(CpuConfig),
+    Custom(CustomConfig),
     Date(DateConfig),
     Keyboard(KeyboardConfig),
     Komorebi(KomorebiConfig),
     Media(MediaConfig),
     Memory(MemoryConfig),
     Network(NetworkConfig),
+    Session(SessionConfig),
+    Shortcuts(ShortcutsConfig),
     Storage(StorageConfig),
     Time(TimeConfig),
     Update(UpdateConfig),
+    Uptime(UptimeConfig),
+    Weather(WeatherConfig),
+}
+
+/// Renders a widget with its theme overrides applied on top of the bar theme
+pub struct ThemedWidget {
+    pub widget: Box<dyn BarWidget>,
+    pub theme: WidgetTheme,
+}
+
+impl BarWidget for ThemedWidget {
+    fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
+        // widgets read colours from both the context and the ui style, so the context style is
+        // swapped for the duration of this widget and restored straight after
+        let original = ctx.style();
+        let mut style = (*original).clone();
+        self.theme.apply_on_style(&mut style);
+        ctx.set_style(style.clone());
+
+        ui.scope(|ui| {
+            ui.set_style(style);
+            config.widget_theme = Some(self.theme);
+            self.widget.render(ctx, ui, config);
+            config.widget_theme = None;
+        });
+
+        ctx.set_style(original);
+    }
+
+    fn next_refresh(&self) -> Option<Duration> {
+        self.widget.next_refresh()
+    }
 }
 
 impl WidgetConfig {
     pub fn as_boxed_bar_widget(&self) -> Box<dyn BarWidget> {
+        let widget = self.as_unthemed_bar_widget();
+
+        match self.theme() {
+            Some(theme) => Box::new(ThemedWidget { widget, theme }),
+            None => widget,
+        }
+    }
+
+    fn as_unthemed_bar_widget(&self) -> Box<dyn BarWidget> {
         match self {
             WidgetConfig::Applications(config) => Box::new(Applications::from(config)),
             WidgetConfig::Battery(config) => Box::new(Battery::from(*config)),
             WidgetConfig::Cpu(config) => Box::new(Cpu::from(*config)),
+            WidgetConfig::Custom(config) => Box::new(Custom::from(config.clone())),
             WidgetConfig::Date(config) => Box::new(Date::from(config.clone())),
             WidgetConfig::Keyboard(config) => Box::new(Keyboard::from(*config)),
             WidgetConfig::Komorebi(config) => Box::new(Komorebi::from(config)),
             WidgetConfig::Media(config) => Box::new(Media::from(*config)),
             WidgetConfig::Memory(config) => Box::new(Memory::from(*config)),
-            WidgetConfig::Network(config) => Box::new(Network::from(*config)),
-            WidgetConfig::Storage(config) => Box::new(Storage::from(*config)),
+            WidgetConfig::Network(config) => Box::new(Network::from(config.clone())),
+            WidgetConfig::Session(config) => Box::new(Session::from(config.clone())),
+            WidgetConfig::Shortcuts(config) => Box::new(Shortcuts::from(config.clone())),
+            WidgetConfig::Storage(config) => Box::new(Storage::from(config.clone())),
             WidgetConfig::Time(config) => Box::new(Time::from(config.clone())),
             WidgetConfig::Update(config) => Box::new(Update::from(*config)),
+            WidgetConfig::Uptime(config) => Box::new(Uptime::from(config.clone())),
+            WidgetConfig::Weather(config) => Box::new(Weather::from(config)),
+        }
+    }
+
+    pub fn theme(&self) -> Option<WidgetTheme> {
+        match self {
+            WidgetConfig::Applications(config) => config.theme,
+            WidgetConfig::Battery(config) => config.theme,
+            WidgetConfig::Cpu(config) => config.theme,
+            WidgetConfig::Custom(config) => config.theme,
+            WidgetConfig::Date(config) => config.theme,
+            WidgetConfig::Keyboard(config) => config.theme,
+            WidgetConfig::Komorebi(config) => config.theme,
+            WidgetConfig::Media(config) => config.theme,
+            WidgetConfig::Memory(config) => config.theme,
+            WidgetConfig::Network(config) => config.theme,
+            WidgetConfig::Session(config) => config.theme,
+            WidgetConfig::Shortcuts(config) => config.theme,
+            WidgetConfig::Storage(config) => config.theme,
+            WidgetConfig::Time(config) => config.theme,
+            WidgetConfig::Update(config) => config.theme,
+            WidgetConfig::Uptime(config) => config.theme,
+            WidgetConfig::Weather(config) => config.theme,
         }
     }
 
@@ -72,6 +160,7 @@ impl WidgetConfig {
             WidgetConfig::Applications(config) => config.enable,
             WidgetConfig::Battery(config) => config.enable,
             WidgetConfig::Cpu(config) => config.enable,
+            WidgetConfig::Custom(config) => config.enable,
             WidgetConfig::Date(config) => config.enable,
             WidgetConfig::Keyboard(config) => config.enable,
             WidgetConfig::Komorebi(config) => {
@@ -82,13 +171,19 @@ impl WidgetConfig {
                         .configuration_switcher
                         .as_ref()
                         .is_some_and(|w| w.enable)
+                    || config.control_menu.as_ref().is_some_and(|w| w.enable)
+                    || config.pause.as_ref().is_some_and(|w| w.enable)
             }
             WidgetConfig::Media(config) => config.enable,
             WidgetConfig::Memory(config) => config.enable,
             WidgetConfig::Network(config) => config.enable,
+            WidgetConfig::Session(config) => config.enable,
+            WidgetConfig::Shortcuts(config) => config.enable,
             WidgetConfig::Storage(config) => config.enable,
             WidgetConfig::Time(config) => config.enable,
             WidgetConfig::Update(config) => config.enable,
+            WidgetConfig::Uptime(config) => config.enable,
+            WidgetConfig::Weather(config) => config.enable,
         }
     }
 }
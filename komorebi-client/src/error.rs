@@ -0,0 +1,51 @@
+use std::io::ErrorKind;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// komorebi has not been started yet, or has exited and left its socket behind
+    #[error("komorebi is not running")]
+    NotRunning,
+    /// komorebi responded with something this client could not understand because they are
+    /// different versions
+    #[error("komorebi {komorebi} is not compatible with komorebi-client {client}")]
+    VersionMismatch { komorebi: String, client: String },
+    /// A message could not be serialized or a response could not be deserialized
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+    /// komorebi did not respond in time, such as when it is busy applying a large layout
+    #[error("timed out waiting for komorebi")]
+    Timeout,
+    #[error(transparent)]
+    Io(std::io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        match error.kind() {
+            // a socket read or write timeout is reported as either of these depending on the
+            // platform
+            ErrorKind::TimedOut | ErrorKind::WouldBlock => Self::Timeout,
+            _ => Self::Io(error),
+        }
+    }
+}
+
+impl Error {
+    /// Maps an error connecting to the komorebi socket, which does not exist or is not listened
+    /// on when komorebi is not running
+    pub(crate) fn from_connect(error: std::io::Error) -> Self {
+        match error.kind() {
+            ErrorKind::NotFound | ErrorKind::ConnectionRefused => Self::NotRunning,
+            _ => error.into(),
+        }
+    }
+
+    /// Whether the same request may succeed if it is retried later, once komorebi has started
+    /// or is no longer busy
+    #[must_use]
+    pub const fn is_transient(&self) -> bool {
+        matches!(self, Self::NotRunning | Self::Timeout)
+    }
+}
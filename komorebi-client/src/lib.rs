@@ -1,6 +1,14 @@
 #![warn(clippy::all)]
 #![allow(clippy::missing_errors_doc)]
 
+mod error;
+mod multiplexer;
+
+pub use error::Error;
+pub use error::Result;
+pub use multiplexer::MultiplexedEvent;
+pub use multiplexer::SubscriptionMultiplexer;
+
 pub use komorebi::animation::prefix::AnimationPrefix;
 pub use komorebi::animation::PerAnimationPrefixConfig;
 pub use komorebi::asc::ApplicationSpecificConfiguration;
@@ -10,6 +18,9 @@ pub use komorebi::config_generation::IdWithIdentifier;
 pub use komorebi::config_generation::IdWithIdentifierAndComment;
 pub use komorebi::config_generation::MatchingRule;
 pub use komorebi::config_generation::MatchingStrategy;
+pub use komorebi::config_generation::RuleCombinator;
+pub use komorebi::config_generation::WorkspaceRule;
+pub use komorebi::config_generation::WorkspaceRuleWithOptions;
 pub use komorebi::container::Container;
 pub use komorebi::core::config_generation::ApplicationConfigurationGenerator;
 pub use komorebi::core::replace_env_in_path;
@@ -17,6 +28,7 @@ pub use komorebi::core::AnimationStyle;
 pub use komorebi::core::ApplicationIdentifier;
 pub use komorebi::core::Arrangement;
 pub use komorebi::core::Axis;
+pub use komorebi::core::BorderAnimation;
 pub use komorebi::core::BorderImplementation;
 pub use komorebi::core::BorderStyle;
 pub use komorebi::core::Column;
@@ -30,6 +42,7 @@ pub use komorebi::core::Direction;
 pub use komorebi::core::FloatingLayerBehaviour;
 pub use komorebi::core::FocusFollowsMouseImplementation;
 pub use komorebi::core::HidingBehaviour;
+pub use komorebi::core::KeyModifier;
 pub use komorebi::core::Layout;
 pub use komorebi::core::MoveBehaviour;
 pub use komorebi::core::OperationBehaviour;
@@ -38,13 +51,20 @@ pub use komorebi::core::PathExt;
 pub use komorebi::core::Rect;
 pub use komorebi::core::Sizing;
 pub use komorebi::core::SocketMessage;
+pub use komorebi::core::SplitDirection;
 pub use komorebi::core::StackbarLabel;
 pub use komorebi::core::StackbarMode;
 pub use komorebi::core::StateQuery;
 pub use komorebi::core::WindowKind;
+pub use komorebi::core::WorkspaceSwitchAnimation;
+pub use komorebi::eviction_manager::EvictionBehaviour;
+pub use komorebi::eviction_manager::WindowEviction;
 pub use komorebi::monitor::Monitor;
 pub use komorebi::monitor_reconciliator::MonitorNotification;
 pub use komorebi::ring::Ring;
+pub use komorebi::schedule_manager::ScheduleDay;
+pub use komorebi::schedule_manager::WorkspaceSchedule;
+pub use komorebi::schedule_manager::WorkspaceScheduleNotification;
 pub use komorebi::win32_display_data;
 pub use komorebi::window::Window;
 pub use komorebi::window_manager_event::WindowManagerEvent;
@@ -57,11 +77,14 @@ pub use komorebi::AspectRatio;
 pub use komorebi::BorderColours;
 pub use komorebi::Colour;
 pub use komorebi::CrossBoundaryBehaviour;
+pub use komorebi::CrossMonitorFocusBehaviour;
 pub use komorebi::GlobalState;
 pub use komorebi::KomorebiTheme;
 pub use komorebi::MonitorConfig;
+pub use komorebi::NewWindowFocusBehaviour;
 pub use komorebi::Notification;
 pub use komorebi::NotificationEvent;
+pub use komorebi::Pong;
 pub use komorebi::PredefinedAspectRatio;
 pub use komorebi::Rgb;
 pub use komorebi::RuleDebug;
@@ -69,34 +92,38 @@ pub use komorebi::StackbarConfig;
 pub use komorebi::State;
 pub use komorebi::StaticConfig;
 pub use komorebi::SubscribeOptions;
+pub use komorebi::SubscriptionEvent;
 pub use komorebi::TabsConfig;
 pub use komorebi::VirtualDesktopNotification;
 pub use komorebi::WindowContainerBehaviour;
 pub use komorebi::WindowsApi;
 pub use komorebi::WorkspaceConfig;
-
-use komorebi::DATA_DIR;
+pub use komorebi::DATA_DIR;
 
 use std::io::BufReader;
+use std::io::ErrorKind;
 use std::io::Read;
 use std::io::Write;
 use std::net::Shutdown;
 use std::time::Duration;
+use std::time::Instant;
 pub use uds_windows::UnixListener;
-use uds_windows::UnixStream;
+pub use uds_windows::UnixStream;
 
 const KOMOREBI: &str = "komorebi.sock";
 
-pub fn send_message(message: &SocketMessage) -> std::io::Result<()> {
-    let socket = DATA_DIR.join(KOMOREBI);
-    let mut stream = UnixStream::connect(socket)?;
+fn connect() -> Result<UnixStream> {
+    UnixStream::connect(DATA_DIR.join(KOMOREBI)).map_err(Error::from_connect)
+}
+
+pub fn send_message(message: &SocketMessage) -> Result<()> {
+    let mut stream = connect()?;
     stream.set_write_timeout(Some(Duration::from_secs(1)))?;
-    stream.write_all(serde_json::to_string(message)?.as_bytes())
+    Ok(stream.write_all(serde_json::to_string(message)?.as_bytes())?)
 }
 
-pub fn send_batch(messages: impl IntoIterator<Item = SocketMessage>) -> std::io::Result<()> {
-    let socket = DATA_DIR.join(KOMOREBI);
-    let mut stream = UnixStream::connect(socket)?;
+pub fn send_batch(messages: impl IntoIterator<Item = SocketMessage>) -> Result<()> {
+    let mut stream = connect()?;
     stream.set_write_timeout(Some(Duration::from_secs(1)))?;
     let msgs = messages.into_iter().fold(String::new(), |mut s, m| {
         if let Ok(m_str) = serde_json::to_string(&m) {
@@ -105,13 +132,11 @@ pub fn send_batch(messages: impl IntoIterator<Item = SocketMessage>) -> std::io:
         }
         s
     });
-    stream.write_all(msgs.as_bytes())
+    Ok(stream.write_all(msgs.as_bytes())?)
 }
 
-pub fn send_query(message: &SocketMessage) -> std::io::Result<String> {
-    let socket = DATA_DIR.join(KOMOREBI);
-
-    let mut stream = UnixStream::connect(socket)?;
+pub fn send_query(message: &SocketMessage) -> Result<String> {
+    let mut stream = connect()?;
     stream.set_read_timeout(Some(Duration::from_secs(1)))?;
     stream.set_write_timeout(Some(Duration::from_secs(1)))?;
     stream.write_all(serde_json::to_string(message)?.as_bytes())?;
@@ -124,7 +149,120 @@ pub fn send_query(message: &SocketMessage) -> std::io::Result<String> {
     Ok(response)
 }
 
-pub fn subscribe(name: &str) -> std::io::Result<UnixListener> {
+/// Sends a query and reads the whole response, failing with `Error::Timeout` if it has not been
+/// received within `timeout`, such as when komorebi is busy applying a large layout
+pub fn send_query_with_timeout(message: &SocketMessage, timeout: Duration) -> Result<String> {
+    let deadline = Instant::now() + timeout;
+
+    let mut stream = connect()?;
+    stream.set_write_timeout(Some(remaining(deadline)?))?;
+    stream.write_all(serde_json::to_string(message)?.as_bytes())?;
+    stream.shutdown(Shutdown::Write)?;
+
+    let mut response = vec![];
+    let mut buffer = [0; 4096];
+
+    loop {
+        // the read timeout only applies to a single read, so it is shortened before every read to
+        // keep the whole response within the deadline
+        stream.set_read_timeout(Some(remaining(deadline)?))?;
+
+        match stream.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => response.extend_from_slice(&buffer[..read]),
+            Err(error) if error.kind() == ErrorKind::Interrupted => {}
+            Err(error) => return Err(error.into()),
+        }
+    }
+
+    String::from_utf8(response)
+        .map_err(|error| std::io::Error::new(ErrorKind::InvalidData, error).into())
+}
+
+/// Sends every query at the same time, returning their responses in the same order once they
+/// have all been received or `timeout` has passed
+pub fn send_queries_with_timeout(
+    messages: impl IntoIterator<Item = SocketMessage>,
+    timeout: Duration,
+) -> Vec<Result<String>> {
+    let handles = messages
+        .into_iter()
+        .map(|message| std::thread::spawn(move || send_query_with_timeout(&message, timeout)))
+        .collect::<Vec<_>>();
+
+    handles
+        .into_iter()
+        .map(|handle| {
+            handle
+                .join()
+                .unwrap_or_else(|_| Err(std::io::Error::other("query thread panicked").into()))
+        })
+        .collect()
+}
+
+pub fn query_state_with_timeout(timeout: Duration) -> Result<State> {
+    let response = send_query_with_timeout(&SocketMessage::State, timeout)?;
+    serde_json::from_str(&response).map_err(version_mismatch)
+}
+
+pub fn query_global_state_with_timeout(timeout: Duration) -> Result<GlobalState> {
+    let response = send_query_with_timeout(&SocketMessage::GlobalState, timeout)?;
+    serde_json::from_str(&response).map_err(version_mismatch)
+}
+
+/// Returns the window focused on the focused workspace, which is the focused floating window
+/// when the workspace is on the floating layer
+pub fn query_focused_window(timeout: Duration) -> Result<Option<Window>> {
+    let state = query_state_with_timeout(timeout)?;
+
+    let Some(workspace) = state
+        .monitors
+        .focused()
+        .and_then(|monitor| monitor.focused_workspace())
+    else {
+        return Ok(None);
+    };
+
+    let window = if workspace.layer() == &WorkspaceLayer::Floating {
+        workspace.focused_floating_window()
+    } else if let Some(window) = workspace.maximized_window() {
+        Option::from(window)
+    } else if let Some(monocle) = workspace.monocle_container() {
+        monocle.focused_window()
+    } else {
+        workspace
+            .focused_container()
+            .and_then(|container| container.focused_window())
+    };
+
+    Ok(window.copied())
+}
+
+/// Checks whether a response could not be deserialized because the running komorebi is a
+/// different version to this client
+fn version_mismatch(error: serde_json::Error) -> Error {
+    let client = env!("CARGO_PKG_VERSION");
+
+    match send_query(&SocketMessage::Ping)
+        .ok()
+        .and_then(|response| serde_json::from_str::<Pong>(&response).ok())
+    {
+        Some(pong) if pong.version != client => Error::VersionMismatch {
+            komorebi: pong.version,
+            client: client.to_string(),
+        },
+        _ => Error::Serde(error),
+    }
+}
+
+fn remaining(deadline: Instant) -> Result<Duration> {
+    deadline
+        .checked_duration_since(Instant::now())
+        .filter(|remaining| !remaining.is_zero())
+        .ok_or(Error::Timeout)
+}
+
+pub fn subscribe(name: &str) -> Result<UnixListener> {
     let socket = DATA_DIR.join(name);
 
     match std::fs::remove_file(&socket) {
@@ -132,7 +270,7 @@ pub fn subscribe(name: &str) -> std::io::Result<UnixListener> {
         Err(error) => match error.kind() {
             std::io::ErrorKind::NotFound => {}
             _ => {
-                return Err(error);
+                return Err(error.into());
             }
         },
     };
@@ -144,10 +282,27 @@ pub fn subscribe(name: &str) -> std::io::Result<UnixListener> {
     Ok(listener)
 }
 
-pub fn subscribe_with_options(
-    name: &str,
-    options: SubscribeOptions,
-) -> std::io::Result<UnixListener> {
+pub fn subscribe_diff(name: &str) -> Result<UnixListener> {
+    let socket = DATA_DIR.join(name);
+
+    match std::fs::remove_file(&socket) {
+        Ok(()) => {}
+        Err(error) => match error.kind() {
+            std::io::ErrorKind::NotFound => {}
+            _ => {
+                return Err(error.into());
+            }
+        },
+    };
+
+    let listener = UnixListener::bind(&socket)?;
+
+    send_message(&SocketMessage::SubscribeSocketDiff(name.to_string()))?;
+
+    Ok(listener)
+}
+
+pub fn subscribe_with_options(name: &str, options: SubscribeOptions) -> Result<UnixListener> {
     let socket = DATA_DIR.join(name);
 
     match std::fs::remove_file(&socket) {
@@ -155,7 +310,7 @@ pub fn subscribe_with_options(
         Err(error) => match error.kind() {
             std::io::ErrorKind::NotFound => {}
             _ => {
-                return Err(error);
+                return Err(error.into());
             }
         },
     };
@@ -0,0 +1,155 @@
+use crate::send_message;
+use crate::subscribe_with_options;
+use crate::Notification;
+use crate::Result;
+use crate::SocketMessage;
+use crate::SubscribeOptions;
+use crate::SubscriptionEvent;
+use std::io::Read;
+use std::sync::mpsc::Receiver;
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::time::Duration;
+
+const RECONNECT_INTERVAL_MIN: Duration = Duration::from_secs(1);
+const RECONNECT_INTERVAL_MAX: Duration = Duration::from_secs(5);
+
+type Filter = Box<dyn Fn(&Notification) -> bool + Send>;
+
+/// An event received by a consumer of a [`SubscriptionMultiplexer`]
+#[derive(Debug, Clone)]
+pub enum MultiplexedEvent {
+    /// A notification accepted by the consumer's filter
+    Notification(Arc<Notification>),
+    /// komorebi has shut down; the multiplexer keeps trying to subscribe again
+    Disconnect,
+    /// The multiplexer has subscribed to komorebi again after a disconnect
+    Reconnect,
+}
+
+struct Consumer {
+    filter: Filter,
+    sender: Sender<MultiplexedEvent>,
+}
+
+fn broadcast(consumers: &Mutex<Vec<Consumer>>, event: &MultiplexedEvent) {
+    consumers
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .retain(|consumer| {
+            let accepted = match event {
+                MultiplexedEvent::Notification(notification) => (consumer.filter)(notification),
+                MultiplexedEvent::Disconnect | MultiplexedEvent::Reconnect => true,
+            };
+
+            !accepted || consumer.sender.send(event.clone()).is_ok()
+        });
+}
+
+/// A single subscription to komorebi which is shared by any number of consumers in the same
+/// process, each of which only receives the notifications it is interested in
+pub struct SubscriptionMultiplexer {
+    name: String,
+    consumers: Arc<Mutex<Vec<Consumer>>>,
+}
+
+impl SubscriptionMultiplexer {
+    /// Subscribes to komorebi on the socket `name`, and hands every notification received on it
+    /// to the consumers whose filters accept it. Consumers are dropped once their receiver is.
+    ///
+    /// When komorebi shuts down every consumer receives [`MultiplexedEvent::Disconnect`], and the
+    /// multiplexer keeps trying to subscribe again until it succeeds, at which point every
+    /// consumer receives [`MultiplexedEvent::Reconnect`].
+    pub fn subscribe(name: &str, options: SubscribeOptions) -> Result<Self> {
+        let listener = subscribe_with_options(name, options.clone())?;
+        let consumers: Arc<Mutex<Vec<Consumer>>> = Arc::default();
+
+        let thread_name = name.to_string();
+        let thread_consumers = consumers.clone();
+        std::thread::spawn(move || {
+            for client in listener.incoming() {
+                let Ok(mut subscription) = client else {
+                    continue;
+                };
+
+                if subscription
+                    .set_read_timeout(Some(Duration::from_secs(1)))
+                    .is_err()
+                {
+                    continue;
+                }
+
+                let mut buffer = String::new();
+                if subscription.read_to_string(&mut buffer).is_err() {
+                    continue;
+                }
+
+                // komorebi connects without sending anything when it shuts down
+                if buffer.is_empty() {
+                    broadcast(&thread_consumers, &MultiplexedEvent::Disconnect);
+
+                    let mut retry_interval = RECONNECT_INTERVAL_MIN;
+                    while send_message(&SocketMessage::AddSubscriberSocketWithOptions(
+                        thread_name.clone(),
+                        options.clone(),
+                    ))
+                    .is_err()
+                    {
+                        std::thread::sleep(retry_interval);
+                        retry_interval = (retry_interval * 2).min(RECONNECT_INTERVAL_MAX);
+                    }
+
+                    broadcast(&thread_consumers, &MultiplexedEvent::Reconnect);
+                    continue;
+                }
+
+                let Ok(notification) = serde_json::from_str::<Notification>(&buffer) else {
+                    continue;
+                };
+
+                broadcast(
+                    &thread_consumers,
+                    &MultiplexedEvent::Notification(Arc::new(notification)),
+                );
+            }
+        });
+
+        Ok(Self {
+            name: name.to_string(),
+            consumers,
+        })
+    }
+
+    /// The name of the socket the notifications are received on
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Adds a consumer which receives every notification `filter` returns true for, along with
+    /// every disconnect and reconnect
+    pub fn add_consumer(
+        &self,
+        filter: impl Fn(&Notification) -> bool + Send + 'static,
+    ) -> Receiver<MultiplexedEvent> {
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        self.consumers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(Consumer {
+                filter: Box::new(filter),
+                sender,
+            });
+
+        receiver
+    }
+
+    /// Adds a consumer which receives the notifications of any of `events`
+    pub fn add_event_consumer(&self, events: Vec<SubscriptionEvent>) -> Receiver<MultiplexedEvent> {
+        self.add_consumer(move |notification| {
+            events.contains(&SubscriptionEvent::from(&notification.event))
+        })
+    }
+}
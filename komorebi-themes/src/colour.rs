@@ -8,12 +8,30 @@ use schemars::schema::Schema;
 #[cfg(feature = "schemars")]
 use schemars::schema::SchemaObject;
 
+use std::str::FromStr;
+
+use crate::Base16;
+use crate::Base16Value;
+use crate::Base16Wrapper;
+use crate::Catppuccin;
+use crate::CatppuccinValue;
 use crate::Color32;
+use serde::de::Error;
+use serde::de::IntoDeserializer;
 use serde::Deserialize;
+use serde::Deserializer;
 use serde::Serialize;
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq)]
-#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+/// A colour, which can be written in a configuration file as
+///
+/// - an object with `r`, `g` and `b` components
+/// - an integer in the `0x00BBGGRR` format used by Windows
+/// - a hex string, e.g. `"#42a5f5"`
+/// - a functional string, e.g. `"rgb(66, 165, 245)"` or `"rgba(66, 165, 245, 0.5)"`
+/// - a colour of a named theme palette, e.g. `"catppuccin:Mocha:Blue"` or `"base16:Ashes:Base0D"`
+///
+/// Colours are always opaque, so any alpha component is ignored.
+#[derive(Debug, Copy, Clone, Serialize, PartialEq)]
 #[serde(untagged)]
 pub enum Colour {
     /// Colour represented as RGB
@@ -22,6 +40,119 @@ pub enum Colour {
     Hex(Hex),
 }
 
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ColourRepr {
+    Rgb(Rgb),
+    Integer(u32),
+    String(String),
+}
+
+impl<'de> Deserialize<'de> for Colour {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match ColourRepr::deserialize(deserializer)? {
+            ColourRepr::Rgb(rgb) => Ok(Self::Rgb(rgb)),
+            ColourRepr::Integer(value) => Ok(Self::from(value)),
+            ColourRepr::String(value) => value.parse().map_err(D::Error::custom),
+        }
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Colour {
+    fn schema_name() -> String {
+        String::from("Colour")
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        SchemaObject {
+            subschemas: Some(Box::new(schemars::schema::SubschemaValidation {
+                any_of: Some(vec![
+                    gen.subschema_for::<Rgb>(),
+                    gen.subschema_for::<u32>(),
+                    SchemaObject {
+                        instance_type: Some(InstanceType::String.into()),
+                        ..Default::default()
+                    }
+                    .into(),
+                ]),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+/// Resolves a reference to a colour of a named theme palette, written as
+/// `"catppuccin:<theme>:<colour>"` (e.g. `"catppuccin:Mocha:Blue"`) or
+/// `"base16:<theme>:<colour>"` (e.g. `"base16:Ashes:Base0D"`)
+fn palette_colour(value: &str) -> Result<Colour, String> {
+    fn variant<'de, T: Deserialize<'de>>(name: &'de str, value: &str) -> Result<T, String> {
+        T::deserialize(IntoDeserializer::<serde::de::value::Error>::into_deserializer(name))
+            .map_err(|_| format!("unknown palette name '{name}' in '{value}'"))
+    }
+
+    let colour = match value.split(':').collect::<Vec<_>>().as_slice() {
+        ["catppuccin", theme, colour] => variant::<CatppuccinValue>(colour, value)?
+            .color32(variant::<Catppuccin>(theme, value)?.as_theme()),
+        ["base16", theme, colour] => variant::<Base16Value>(colour, value)?
+            .color32(Base16Wrapper::Base16(variant::<Base16>(theme, value)?)),
+        _ => return Err(format!("unknown colour '{value}'")),
+    };
+
+    Ok(Colour::from(colour))
+}
+
+impl FromStr for Colour {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim();
+        let lowercase = value.to_ascii_lowercase();
+
+        if value.starts_with('#') {
+            return HexColor::parse(value)
+                .map(|hex| Self::Hex(Hex(hex)))
+                .map_err(|error| format!("invalid hex colour '{value}': {error}"));
+        }
+
+        let arguments = lowercase
+            .strip_prefix("rgba(")
+            .or_else(|| lowercase.strip_prefix("rgb("))
+            .and_then(|rest| rest.strip_suffix(')'));
+
+        if let Some(arguments) = arguments {
+            let components = arguments.split(',').map(str::trim).collect::<Vec<_>>();
+
+            if !(3..=4).contains(&components.len()) {
+                return Err(format!("invalid functional colour '{value}'"));
+            }
+
+            let mut rgb = [0; 3];
+            for (component, channel) in components.iter().zip(rgb.iter_mut()) {
+                *channel = component
+                    .parse::<u8>()
+                    .map_err(|_| format!("invalid colour component '{component}' in '{value}'"))?
+                    as u32;
+            }
+
+            if let Some(alpha) = components.get(3) {
+                alpha
+                    .parse::<f32>()
+                    .map_err(|_| format!("invalid alpha component '{alpha}' in '{value}'"))?;
+            }
+
+            return Ok(Self::Rgb(Rgb::new(rgb[0], rgb[1], rgb[2])));
+        }
+
+        palette_colour(value)
+    }
+}
+
 impl From<Rgb> for Colour {
     fn from(value: Rgb) -> Self {
         Self::Rgb(value)
@@ -132,3 +263,42 @@ impl From<u32> for Rgb {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_colour_formats() {
+        let expected = Rgb::new(66, 165, 245);
+
+        for json in [
+            r#"{"r": 66, "g": 165, "b": 245}"#,
+            "16098626",
+            r##""#42a5f5""##,
+            r#""rgb(66, 165, 245)""#,
+            r#""RGBA(66,165,245,0.5)""#,
+        ] {
+            let colour: Colour = serde_json::from_str(json).unwrap();
+            assert_eq!(u32::from(colour), u32::from(expected), "{json}");
+        }
+
+        let catppuccin: Colour = serde_json::from_str(r#""catppuccin:Mocha:Blue""#).unwrap();
+        assert_eq!(
+            catppuccin,
+            Colour::from(CatppuccinValue::Blue.color32(Catppuccin::Mocha.as_theme()))
+        );
+
+        let base16: Colour = serde_json::from_str(r#""base16:Ashes:Base0D""#).unwrap();
+        assert_eq!(
+            base16,
+            Colour::from(Base16Value::Base0D.color32(Base16Wrapper::Base16(Base16::Ashes)))
+        );
+
+        assert!(serde_json::from_str::<Colour>(r#""catppuccin:Mocha:Orange""#).is_err());
+        assert!(serde_json::from_str::<Colour>(r#""orange""#).is_err());
+
+        assert!(serde_json::from_str::<Colour>(r#""rgb(300, 0, 0)""#).is_err());
+        assert!(serde_json::from_str::<Colour>(r#""not-a-colour""#).is_err());
+    }
+}
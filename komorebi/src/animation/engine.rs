@@ -10,6 +10,7 @@ use super::RenderDispatcher;
 use super::ANIMATION_DURATION_GLOBAL;
 use super::ANIMATION_FPS;
 use super::ANIMATION_MANAGER;
+use super::ANIMATION_MAX_CONCURRENT;
 
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
@@ -43,7 +44,8 @@ impl AnimationEngine {
                 ANIMATION_MANAGER.lock().end(animation_key);
             }
 
-            std::thread::sleep(Duration::from_millis(250 / 2));
+            // the cancelled animation checks for cancellation once per frame
+            std::thread::sleep(Self::target_frame_time());
         }
 
         let latest_cancel_idx = ANIMATION_MANAGER.lock().latest_cancel_idx(animation_key);
@@ -53,9 +55,13 @@ impl AnimationEngine {
         latest_cancel_idx == cancel_idx
     }
 
+    fn target_frame_time() -> Duration {
+        Duration::from_millis(1000 / ANIMATION_FPS.load(Ordering::Relaxed).max(1))
+    }
+
     #[allow(clippy::cast_precision_loss)]
     pub fn animate(
-        render_dispatcher: (impl RenderDispatcher + Send + 'static),
+        mut render_dispatcher: (impl RenderDispatcher + Send + 'static),
         duration: Duration,
     ) -> Result<()> {
         std::thread::spawn(move || {
@@ -66,14 +72,31 @@ impl AnimationEngine {
                 if !should_animate {
                     return Ok(());
                 }
+
+                // continue from wherever the cancelled animation stopped instead of jumping back
+                render_dispatcher.retarget()?;
             }
 
             render_dispatcher.pre_render()?;
 
-            ANIMATION_MANAGER.lock().start(animation_key.as_str());
+            let over_limit = {
+                let mut animation_manager = ANIMATION_MANAGER.lock();
+                let over_limit =
+                    animation_manager.count() >= ANIMATION_MAX_CONCURRENT.load(Ordering::Relaxed);
+
+                if !over_limit {
+                    animation_manager.start(animation_key.as_str());
+                }
+
+                over_limit
+            };
+
+            if over_limit {
+                render_dispatcher.render(1.0).ok();
+                return render_dispatcher.post_render();
+            }
 
-            let target_frame_time =
-                Duration::from_millis(1000 / ANIMATION_FPS.load(Ordering::Relaxed));
+            let target_frame_time = Self::target_frame_time();
             let mut progress = 0.0;
             let animation_start = Instant::now();
 
@@ -1,11 +1,14 @@
 use crate::animation::animation_manager::AnimationManager;
 use crate::core::animation::AnimationStyle;
+use crate::core::animation::WorkspaceSwitchAnimation;
 
+use crossbeam_utils::atomic::AtomicCell;
 use lazy_static::lazy_static;
 use prefix::AnimationPrefix;
 use std::collections::HashMap;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
 
 use parking_lot::Mutex;
@@ -35,6 +38,10 @@ pub const DEFAULT_ANIMATION_STYLE: AnimationStyle = AnimationStyle::Linear;
 pub const DEFAULT_ANIMATION_DURATION: u64 = 250;
 pub const DEFAULT_ANIMATION_FPS: u64 = 60;
 
+/// Workspace switches with more windows than this are not animated, as moving that many windows at
+/// once is too choppy to be worth it
+pub const WORKSPACE_SWITCH_ANIMATION_WINDOW_LIMIT: usize = 8;
+
 lazy_static! {
     pub static ref ANIMATION_MANAGER: Arc<Mutex<AnimationManager>> =
         Arc::new(Mutex::new(AnimationManager::new()));
@@ -53,3 +60,8 @@ lazy_static! {
 }
 
 pub static ANIMATION_FPS: AtomicU64 = AtomicU64::new(DEFAULT_ANIMATION_FPS);
+/// Animations started while this many are already running jump straight to their final frame
+pub static ANIMATION_MAX_CONCURRENT: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+pub static WORKSPACE_SWITCH_ANIMATION: AtomicCell<WorkspaceSwitchAnimation> =
+    AtomicCell::new(WorkspaceSwitchAnimation::Slide);
@@ -3,6 +3,11 @@ use color_eyre::Result;
 pub trait RenderDispatcher {
     fn get_animation_key(&self) -> String;
     fn pre_render(&self) -> Result<()>;
+    /// Called when this animation replaces a cancelled in-flight animation with the same key, so
+    /// that it can pick up from wherever the previous animation left off
+    fn retarget(&mut self) -> Result<()> {
+        Ok(())
+    }
     fn render(&self, delta: f64) -> Result<()>;
     fn post_render(&self) -> Result<()>;
 }
@@ -1,9 +1,11 @@
 use crate::border_manager::window_kind_colour;
 use crate::border_manager::RenderTarget;
 use crate::border_manager::WindowKind;
+use crate::border_manager::ANIMATION;
 use crate::border_manager::BORDER_OFFSET;
 use crate::border_manager::BORDER_WIDTH;
 use crate::border_manager::STYLE;
+use crate::core::BorderAnimation;
 use crate::core::BorderStyle;
 use crate::core::Rect;
 use crate::windows_api;
@@ -14,6 +16,7 @@ use std::ops::Deref;
 use std::sync::atomic::Ordering;
 use std::sync::mpsc;
 use std::sync::LazyLock;
+use std::time::Instant;
 use windows::Win32::Foundation::FALSE;
 use windows::Win32::Foundation::HWND;
 use windows::Win32::Foundation::LPARAM;
@@ -22,16 +25,22 @@ use windows::Win32::Foundation::TRUE;
 use windows::Win32::Foundation::WPARAM;
 use windows::Win32::Graphics::Direct2D::Common::D2D1_ALPHA_MODE_PREMULTIPLIED;
 use windows::Win32::Graphics::Direct2D::Common::D2D1_COLOR_F;
+use windows::Win32::Graphics::Direct2D::Common::D2D1_GRADIENT_STOP;
 use windows::Win32::Graphics::Direct2D::Common::D2D1_PIXEL_FORMAT;
 use windows::Win32::Graphics::Direct2D::Common::D2D_RECT_F;
 use windows::Win32::Graphics::Direct2D::Common::D2D_SIZE_U;
 use windows::Win32::Graphics::Direct2D::D2D1CreateFactory;
+use windows::Win32::Graphics::Direct2D::ID2D1Brush;
 use windows::Win32::Graphics::Direct2D::ID2D1Factory;
+use windows::Win32::Graphics::Direct2D::ID2D1LinearGradientBrush;
 use windows::Win32::Graphics::Direct2D::ID2D1SolidColorBrush;
 use windows::Win32::Graphics::Direct2D::D2D1_ANTIALIAS_MODE_PER_PRIMITIVE;
 use windows::Win32::Graphics::Direct2D::D2D1_BRUSH_PROPERTIES;
+use windows::Win32::Graphics::Direct2D::D2D1_EXTEND_MODE_CLAMP;
 use windows::Win32::Graphics::Direct2D::D2D1_FACTORY_TYPE_MULTI_THREADED;
+use windows::Win32::Graphics::Direct2D::D2D1_GAMMA_2_2;
 use windows::Win32::Graphics::Direct2D::D2D1_HWND_RENDER_TARGET_PROPERTIES;
+use windows::Win32::Graphics::Direct2D::D2D1_LINEAR_GRADIENT_BRUSH_PROPERTIES;
 use windows::Win32::Graphics::Direct2D::D2D1_PRESENT_OPTIONS_IMMEDIATELY;
 use windows::Win32::Graphics::Direct2D::D2D1_RENDER_TARGET_PROPERTIES;
 use windows::Win32::Graphics::Direct2D::D2D1_RENDER_TARGET_TYPE_DEFAULT;
@@ -49,9 +58,11 @@ use windows::Win32::UI::WindowsAndMessaging::DispatchMessageW;
 use windows::Win32::UI::WindowsAndMessaging::GetMessageW;
 use windows::Win32::UI::WindowsAndMessaging::GetSystemMetrics;
 use windows::Win32::UI::WindowsAndMessaging::GetWindowLongPtrW;
+use windows::Win32::UI::WindowsAndMessaging::KillTimer;
 use windows::Win32::UI::WindowsAndMessaging::LoadCursorW;
 use windows::Win32::UI::WindowsAndMessaging::PostQuitMessage;
 use windows::Win32::UI::WindowsAndMessaging::SetCursor;
+use windows::Win32::UI::WindowsAndMessaging::SetTimer;
 use windows::Win32::UI::WindowsAndMessaging::SetWindowLongPtrW;
 use windows::Win32::UI::WindowsAndMessaging::TranslateMessage;
 use windows::Win32::UI::WindowsAndMessaging::CREATESTRUCTW;
@@ -65,10 +76,12 @@ use windows::Win32::UI::WindowsAndMessaging::WM_CREATE;
 use windows::Win32::UI::WindowsAndMessaging::WM_DESTROY;
 use windows::Win32::UI::WindowsAndMessaging::WM_PAINT;
 use windows::Win32::UI::WindowsAndMessaging::WM_SETCURSOR;
+use windows::Win32::UI::WindowsAndMessaging::WM_TIMER;
 use windows::Win32::UI::WindowsAndMessaging::WNDCLASSW;
 use windows_core::BOOL;
 use windows_core::PCWSTR;
 use windows_numerics::Matrix3x2;
+use windows_numerics::Vector2;
 
 pub struct RenderFactory(ID2D1Factory);
 unsafe impl Sync for RenderFactory {}
@@ -92,6 +105,16 @@ static RENDER_FACTORY: LazyLock<RenderFactory> = unsafe {
     })
 };
 
+/// Id of the timer which redraws the border of the focused window while it is animated
+const ANIMATION_TIMER_ID: usize = 1;
+/// Roughly 30 frames per second, which is plenty for the slow border animations
+const ANIMATION_FRAME_MS: u32 = 33;
+const PULSE_PERIOD_SECS: f32 = 2.0;
+const GRADIENT_PERIOD_SECS: f32 = 4.0;
+
+/// Shared by every border so that the animations of different borders stay in step
+static ANIMATION_EPOCH: LazyLock<Instant> = LazyLock::new(Instant::now);
+
 static BRUSH_PROPERTIES: LazyLock<D2D1_BRUSH_PROPERTIES> =
     LazyLock::new(|| D2D1_BRUSH_PROPERTIES {
         opacity: 1.0,
@@ -126,6 +149,7 @@ pub struct Border {
     pub brush_properties: D2D1_BRUSH_PROPERTIES,
     pub rounded_rect: D2D1_ROUNDED_RECT,
     pub brushes: HashMap<WindowKind, ID2D1SolidColorBrush>,
+    pub gradient_brushes: HashMap<WindowKind, ID2D1LinearGradientBrush>,
 }
 
 impl From<isize> for Border {
@@ -144,6 +168,7 @@ impl From<isize> for Border {
             brush_properties: D2D1_BRUSH_PROPERTIES::default(),
             rounded_rect: D2D1_ROUNDED_RECT::default(),
             brushes: HashMap::new(),
+            gradient_brushes: HashMap::new(),
         }
     }
 }
@@ -192,6 +217,7 @@ impl Border {
                 brush_properties: Default::default(),
                 rounded_rect: Default::default(),
                 brushes: HashMap::new(),
+                gradient_brushes: HashMap::new(),
             };
 
             let border_pointer = &raw mut border;
@@ -291,6 +317,37 @@ impl Border {
                     {
                         self.brushes.insert(window_kind, brush);
                     }
+
+                    // only the border of the focused window is animated
+                    if !matches!(
+                        window_kind,
+                        WindowKind::Unfocused | WindowKind::UnfocusedLocked
+                    ) {
+                        let stops = [
+                            D2D1_GRADIENT_STOP {
+                                position: 0.0,
+                                color,
+                            },
+                            D2D1_GRADIENT_STOP {
+                                position: 1.0,
+                                color: D2D1_COLOR_F { a: 0.25, ..color },
+                            },
+                        ];
+
+                        if let Ok(stop_collection) = render_target.CreateGradientStopCollection(
+                            &stops,
+                            D2D1_GAMMA_2_2,
+                            D2D1_EXTEND_MODE_CLAMP,
+                        ) {
+                            if let Ok(brush) = render_target.CreateLinearGradientBrush(
+                                &D2D1_LINEAR_GRADIENT_BRUSH_PROPERTIES::default(),
+                                Some(&self.brush_properties),
+                                &stop_collection,
+                            ) {
+                                self.gradient_brushes.insert(window_kind, brush);
+                            }
+                        }
+                    }
                 }
 
                 render_target.SetAntialiasMode(D2D1_ANTIALIAS_MODE_PER_PRIMITIVE);
@@ -326,6 +383,126 @@ impl Border {
         Ok(())
     }
 
+    /// The animation to draw the border with, if it is the visible border of the focused window
+    /// and battery saver is not on. A hidden border is painted again when it is shown, which
+    /// restarts the animation.
+    fn animation(&self) -> Option<BorderAnimation> {
+        if matches!(
+            self.window_kind,
+            WindowKind::Unfocused | WindowKind::UnfocusedLocked
+        ) || !WindowsApi::is_window_visible(self.hwnd)
+        {
+            return None;
+        }
+
+        ANIMATION
+            .load()
+            .filter(|_| !WindowsApi::battery_saver_enabled())
+    }
+
+    /// Draws the border in the colour of its window kind, or the current frame of its animation,
+    /// and (re)starts or stops the timer which draws the next frames
+    fn draw(&self) {
+        let Some(render_target) = self.render_target.as_ref() else {
+            return;
+        };
+
+        let Some(solid_brush) = self.brushes.get(&self.window_kind) else {
+            return;
+        };
+
+        // Calculate border radius based on style
+        let style = match self.style {
+            BorderStyle::System => {
+                if *WINDOWS_11 {
+                    BorderStyle::Rounded
+                } else {
+                    BorderStyle::Square
+                }
+            }
+            BorderStyle::Rounded => BorderStyle::Rounded,
+            BorderStyle::Square => BorderStyle::Square,
+        };
+
+        let animation = self.animation();
+        let elapsed = ANIMATION_EPOCH.elapsed().as_secs_f32();
+
+        let brush: &ID2D1Brush = match animation {
+            Some(BorderAnimation::Pulse) => {
+                let phase = (elapsed / PULSE_PERIOD_SECS) * std::f32::consts::TAU;
+                unsafe { solid_brush.SetOpacity(0.7 + 0.3 * phase.cos()) };
+                solid_brush
+            }
+            Some(BorderAnimation::Gradient) => match self.gradient_brushes.get(&self.window_kind) {
+                Some(gradient_brush) => {
+                    let rect = self.rounded_rect.rect;
+                    let center_x = (rect.left + rect.right) / 2.0;
+                    let center_y = (rect.top + rect.bottom) / 2.0;
+                    let radius = (rect.right - rect.left).max(rect.bottom - rect.top) / 2.0;
+
+                    let angle = (elapsed / GRADIENT_PERIOD_SECS) * std::f32::consts::TAU;
+                    let (sin, cos) = angle.sin_cos();
+
+                    unsafe {
+                        gradient_brush.SetStartPoint(Vector2 {
+                            X: center_x - radius * cos,
+                            Y: center_y - radius * sin,
+                        });
+                        gradient_brush.SetEndPoint(Vector2 {
+                            X: center_x + radius * cos,
+                            Y: center_y + radius * sin,
+                        });
+                    }
+
+                    gradient_brush
+                }
+                None => solid_brush,
+            },
+            None => solid_brush,
+        };
+
+        unsafe {
+            render_target.BeginDraw();
+            render_target.Clear(None);
+
+            match style {
+                BorderStyle::Rounded => {
+                    render_target.DrawRoundedRectangle(
+                        &self.rounded_rect,
+                        brush,
+                        self.width as f32,
+                        None,
+                    );
+                }
+                BorderStyle::Square => {
+                    render_target.DrawRectangle(
+                        &self.rounded_rect.rect,
+                        brush,
+                        self.width as f32,
+                        None,
+                    );
+                }
+                _ => {}
+            }
+
+            let _ = render_target.EndDraw(None, None);
+
+            // reset so that the border is opaque again once it is no longer animated
+            solid_brush.SetOpacity(1.0);
+
+            if animation.is_some() {
+                SetTimer(
+                    Option::from(self.hwnd()),
+                    ANIMATION_TIMER_ID,
+                    ANIMATION_FRAME_MS,
+                    None,
+                );
+            } else {
+                let _ = KillTimer(Option::from(self.hwnd()), ANIMATION_TIMER_ID);
+            }
+        }
+    }
+
     // this triggers WM_PAINT in the callback below
     pub fn invalidate(&self) {
         let _ = unsafe { InvalidateRect(Option::from(self.hwnd()), None, false) };
@@ -409,46 +586,7 @@ impl Border {
                                 height: rect.bottom as u32,
                             });
 
-                            let window_kind = (*border_pointer).window_kind;
-                            if let Some(brush) = (*border_pointer).brushes.get(&window_kind) {
-                                render_target.BeginDraw();
-                                render_target.Clear(None);
-
-                                // Calculate border radius based on style
-                                let style = match (*border_pointer).style {
-                                    BorderStyle::System => {
-                                        if *WINDOWS_11 {
-                                            BorderStyle::Rounded
-                                        } else {
-                                            BorderStyle::Square
-                                        }
-                                    }
-                                    BorderStyle::Rounded => BorderStyle::Rounded,
-                                    BorderStyle::Square => BorderStyle::Square,
-                                };
-
-                                match style {
-                                    BorderStyle::Rounded => {
-                                        render_target.DrawRoundedRectangle(
-                                            &(*border_pointer).rounded_rect,
-                                            brush,
-                                            border_width as f32,
-                                            None,
-                                        );
-                                    }
-                                    BorderStyle::Square => {
-                                        render_target.DrawRectangle(
-                                            &(*border_pointer).rounded_rect.rect,
-                                            brush,
-                                            border_width as f32,
-                                            None,
-                                        );
-                                    }
-                                    _ => {}
-                                }
-
-                                let _ = render_target.EndDraw(None, None);
-                            }
+                            (*border_pointer).draw();
                         }
                     }
 
@@ -494,55 +632,25 @@ impl Border {
                                 height: rect.bottom as u32,
                             });
 
-                            // Get window kind and color
-                            let window_kind = (*border_pointer).window_kind;
-                            if let Some(brush) = (*border_pointer).brushes.get(&window_kind) {
-                                render_target.BeginDraw();
-                                render_target.Clear(None);
-
-                                (*border_pointer).style = STYLE.load();
-
-                                // Calculate border radius based on style
-                                let style = match (*border_pointer).style {
-                                    BorderStyle::System => {
-                                        if *WINDOWS_11 {
-                                            BorderStyle::Rounded
-                                        } else {
-                                            BorderStyle::Square
-                                        }
-                                    }
-                                    BorderStyle::Rounded => BorderStyle::Rounded,
-                                    BorderStyle::Square => BorderStyle::Square,
-                                };
-
-                                match style {
-                                    BorderStyle::Rounded => {
-                                        render_target.DrawRoundedRectangle(
-                                            &(*border_pointer).rounded_rect,
-                                            brush,
-                                            border_width as f32,
-                                            None,
-                                        );
-                                    }
-                                    BorderStyle::Square => {
-                                        render_target.DrawRectangle(
-                                            &(*border_pointer).rounded_rect.rect,
-                                            brush,
-                                            border_width as f32,
-                                            None,
-                                        );
-                                    }
-                                    _ => {}
-                                }
-
-                                let _ = render_target.EndDraw(None, None);
-                            }
+                            (*border_pointer).style = STYLE.load();
+                            (*border_pointer).draw();
                         }
                     }
                     let _ = ValidateRect(Option::from(window), None);
                     LRESULT(0)
                 }
+                WM_TIMER if wparam.0 == ANIMATION_TIMER_ID => {
+                    let border_pointer: *mut Border = GetWindowLongPtrW(window, GWLP_USERDATA) as _;
+
+                    if border_pointer.is_null() {
+                        return LRESULT(0);
+                    }
+
+                    (*border_pointer).draw();
+                    LRESULT(0)
+                }
                 WM_DESTROY => {
+                    let _ = KillTimer(Option::from(window), ANIMATION_TIMER_ID);
                     SetWindowLongPtrW(window, GWLP_USERDATA, 0);
                     PostQuitMessage(0);
                     LRESULT(0)
@@ -1,6 +1,7 @@
 #![deny(clippy::unwrap_used, clippy::expect_used)]
 
 mod border;
+use crate::core::BorderAnimation;
 use crate::core::BorderImplementation;
 use crate::core::BorderStyle;
 use crate::core::WindowKind;
@@ -42,6 +43,7 @@ pub static BORDER_ENABLED: AtomicBool = AtomicBool::new(true);
 
 lazy_static! {
     pub static ref STYLE: AtomicCell<BorderStyle> = AtomicCell::new(BorderStyle::System);
+    pub static ref ANIMATION: AtomicCell<Option<BorderAnimation>> = AtomicCell::new(None);
     pub static ref IMPLEMENTATION: AtomicCell<BorderImplementation> =
         AtomicCell::new(BorderImplementation::Komorebi);
     pub static ref FOCUSED: AtomicU32 =
@@ -528,6 +530,10 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                             container_and_floating_window_ids.push(w.hwnd.to_string());
                         }
 
+                        for tile in ws.reserved_tiles() {
+                            container_and_floating_window_ids.push(tile.id.clone());
+                        }
+
                         // Remove any borders not associated with the focused workspace
                         remove_borders(
                             &mut borders,
@@ -628,6 +634,8 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                             windows_borders.insert(focused_window_hwnd, id);
                         }
 
+                        handle_reserved_tile_borders(&mut borders, ws, monitor_idx, forced_update)?;
+
                         handle_floating_borders(
                             &mut borders,
                             &mut windows_borders,
@@ -714,6 +722,46 @@ fn handle_floating_borders(
     Ok(())
 }
 
+/// Draws a placeholder outline for each of the reserved tiles of the workspace. These borders
+/// don't track any window, their position is taken from the layout of the workspace.
+fn handle_reserved_tile_borders(
+    borders: &mut HashMap<String, Box<Border>>,
+    ws: &Workspace,
+    monitor_idx: usize,
+    forced_update: bool,
+) -> color_eyre::Result<()> {
+    for tile in ws.reserved_tiles() {
+        let mut new_border = false;
+        let border = match borders.entry(tile.id.clone()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                if let Ok(border) = Border::create(&tile.id, 0, monitor_idx) {
+                    new_border = true;
+                    entry.insert(border)
+                } else {
+                    return Ok(());
+                }
+            }
+        };
+
+        border.window_kind = WindowKind::Unfocused;
+        border.monitor_idx = Some(monitor_idx);
+
+        let should_invalidate = new_border || border.window_rect != tile.rect || forced_update;
+        border.window_rect = tile.rect;
+
+        if should_invalidate {
+            if forced_update && !new_border {
+                border.update_brushes()?;
+            }
+            border.set_position(&tile.rect, 0)?;
+            border.invalidate();
+        }
+    }
+
+    Ok(())
+}
+
 /// Removes all borders from monitor with index `monitor_idx` filtered by
 /// `condition`. This condition is a function that will take a reference to
 /// the container id and the border and returns a bool, if true that border
@@ -140,6 +140,14 @@ impl Container {
         self.remove_window_by_idx(focused_idx)
     }
 
+    /// Replaces the window with `hwnd` in place, keeping its position in the stack, and returns the
+    /// window that was replaced.
+    pub fn replace_window(&mut self, hwnd: isize, window: Window) -> Option<Window> {
+        let idx = self.idx_for_window(hwnd)?;
+        let existing = self.windows_mut().get_mut(idx)?;
+        Option::from(std::mem::replace(existing, window))
+    }
+
     pub fn add_window(&mut self, window: Window) {
         self.windows_mut().push_back(window);
         self.focus_window(self.windows().len().saturating_sub(1));
@@ -157,6 +165,20 @@ impl Container {
         tracing::info!("focusing window");
         self.windows.focus(idx);
     }
+
+    /// Moves the window at `from` to `to` in the stack, keeping the same window focused
+    pub fn move_window(&mut self, from: usize, to: usize) {
+        let focused_hwnd = self.focused_window().map(|window| window.hwnd);
+
+        if let Some(window) = self.windows_mut().remove(from) {
+            let to = to.min(self.windows().len());
+            self.windows_mut().insert(to, window);
+        }
+
+        if let Some(idx) = focused_hwnd.and_then(|hwnd| self.idx_for_window(hwnd)) {
+            self.focus_window(idx);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -181,6 +203,28 @@ mod tests {
         assert_eq!(container.idx_for_window(4), None);
     }
 
+    #[test]
+    fn test_replace_window() {
+        let mut container = Container::default();
+
+        for i in 0..3 {
+            container.add_window(Window::from(i));
+        }
+
+        // Replace window 1 with window 4
+        let replaced = container.replace_window(1, Window::from(4));
+        assert_eq!(replaced, Some(Window::from(1)));
+
+        // Window 4 should take the position previously held by window 1
+        assert_eq!(container.idx_for_window(4), Some(1));
+        assert!(!container.contains_window(1));
+        assert_eq!(container.windows().len(), 3);
+
+        // Replacing a window that doesn't exist should do nothing
+        assert_eq!(container.replace_window(5, Window::from(6)), None);
+        assert!(!container.contains_window(6));
+    }
+
     #[test]
     fn test_remove_window_by_idx() {
         let mut container = Container::default();
@@ -231,6 +275,41 @@ mod tests {
         assert!(container.contains_window(1));
     }
 
+    #[test]
+    fn test_move_window() {
+        let mut container = Container::default();
+
+        for i in 0..4 {
+            container.add_window(Window::from(i));
+        }
+
+        container.focus_window(1);
+
+        // Move window 1 to the end of the stack
+        container.move_window(1, 3);
+
+        let hwnds = container
+            .windows()
+            .iter()
+            .map(|w| w.hwnd)
+            .collect::<Vec<_>>();
+        assert_eq!(hwnds, vec![0, 2, 3, 1]);
+
+        // The moved window should still be focused
+        assert_eq!(container.focused_window_idx(), 3);
+
+        // Move window 3 to the start of the stack
+        container.move_window(2, 0);
+
+        let hwnds = container
+            .windows()
+            .iter()
+            .map(|w| w.hwnd)
+            .collect::<Vec<_>>();
+        assert_eq!(hwnds, vec![3, 0, 2, 1]);
+        assert_eq!(container.focused_window_idx(), 3);
+    }
+
     #[test]
     fn test_focus_window() {
         let mut container = Container::default();
@@ -6,6 +6,18 @@ use serde::Serialize;
 use strum::Display;
 use strum::EnumString;
 
+#[derive(
+    Copy, Clone, Debug, Default, Serialize, Deserialize, Display, EnumString, ValueEnum, PartialEq,
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum WorkspaceSwitchAnimation {
+    /// Slide the windows of the newly focused workspace in from the direction of the switch
+    #[default]
+    Slide,
+    /// Fade the windows of the newly focused workspace in
+    Fade,
+}
+
 #[derive(Copy, Clone, Debug, Display, EnumString, ValueEnum, PartialEq)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum AnimationStyle {
@@ -12,6 +12,7 @@ use super::custom_layout::ColumnSplitWithCapacity;
 use super::CustomLayout;
 use super::DefaultLayout;
 use super::Rect;
+use crate::default_layout::GridFill;
 use crate::default_layout::LayoutOptions;
 
 pub trait Arrangement {
@@ -23,6 +24,7 @@ pub trait Arrangement {
         container_padding: Option<i32>,
         layout_flip: Option<Axis>,
         resize_dimensions: &[Option<Rect>],
+        split_ratios: &[Option<f32>],
         focused_idx: usize,
         layout_options: Option<LayoutOptions>,
         latest_layout: &[Rect],
@@ -38,6 +40,7 @@ impl Arrangement for DefaultLayout {
         container_padding: Option<i32>,
         layout_flip: Option<Axis>,
         resize_dimensions: &[Option<Rect>],
+        split_ratios: &[Option<f32>],
         focused_idx: usize,
         layout_options: Option<LayoutOptions>,
         latest_layout: &[Rect],
@@ -142,12 +145,18 @@ impl Arrangement for DefaultLayout {
 
                 layouts
             }
-            Self::BSP => recursive_fibonacci(
+            // the workspace stacks every window into a single container, so any others only exist
+            // until the next update and can be placed underneath it
+            Self::Tabbed => vec![*area; len],
+            // the split tree of the Manual layout is held by the workspace, which calculates
+            // the layout itself, so this is only a fallback
+            Self::BSP | Self::Manual => recursive_fibonacci(
                 0,
                 len,
                 area,
                 layout_flip,
                 calculate_resize_adjustments(resize_dimensions),
+                split_ratios,
             ),
             Self::Columns => {
                 let mut layouts = columns(area, len);
@@ -527,49 +536,152 @@ impl Arrangement for DefaultLayout {
                 clippy::cast_possible_wrap
             )]
             Self::Grid => {
-                // Shamelessly lifted from LeftWM
+                // Originally lifted from LeftWM
                 // https://github.com/leftwm/leftwm/blob/18675067b8450e520ef75db2ebbb0d973aa1199e/leftwm-core/src/layouts/grid_horizontal.rs
-                let mut layouts: Vec<Rect> = vec![];
-                layouts.resize(len, Rect::default());
+                let options = layout_options.and_then(|o| o.grid).unwrap_or_default();
+                let fill = options.fill.unwrap_or_default();
+                let lines = options.lines(len);
+                let num_lines = lines.len() as i32;
 
-                let len = len as i32;
+                let mut layouts: Vec<Rect> = Vec::with_capacity(len);
 
-                let num_cols = (len as f32).sqrt().ceil() as i32;
-                let mut iter = layouts.iter_mut().enumerate().peekable();
+                for (line, &num_in_line) in lines.iter().enumerate() {
+                    let line = line as i32;
+                    let num_in_line = num_in_line as i32;
 
-                for col in 0..num_cols {
-                    let iter_peek = iter.peek().map(|x| x.0).unwrap_or_default() as i32;
-                    let remaining_windows = len - iter_peek;
-                    let remaining_columns = num_cols - col;
-                    let num_rows_in_this_col = remaining_windows / remaining_columns;
+                    for position in 0..num_in_line {
+                        let (col, row, num_cols, num_rows) = match fill {
+                            GridFill::ColumnMajor => (line, position, num_lines, num_in_line),
+                            GridFill::RowMajor => (position, line, num_in_line, num_lines),
+                        };
 
-                    let win_height = area.bottom / num_rows_in_this_col;
-                    let win_width = area.right / num_cols;
+                        let win_height = area.bottom / num_rows;
+                        let win_width = area.right / num_cols;
 
-                    for row in 0..num_rows_in_this_col {
-                        if let Some((_idx, win)) = iter.next() {
-                            let mut left = area.left + win_width * col;
-                            let mut top = area.top + win_height * row;
+                        let mut left = area.left + win_width * col;
+                        let mut top = area.top + win_height * row;
 
-                            match layout_flip {
-                                Some(Axis::Horizontal) => {
-                                    left = area.right - win_width * (col + 1) + area.left;
-                                }
-                                Some(Axis::Vertical) => {
-                                    top = area.bottom - win_height * (row + 1) + area.top;
-                                }
-                                Some(Axis::HorizontalAndVertical) => {
-                                    left = area.right - win_width * (col + 1) + area.left;
-                                    top = area.bottom - win_height * (row + 1) + area.top;
-                                }
-                                None => {} // No flip
+                        match layout_flip {
+                            Some(Axis::Horizontal) => {
+                                left = area.right - win_width * (col + 1) + area.left;
                             }
-
-                            win.bottom = win_height;
-                            win.right = win_width;
-                            win.left = left;
-                            win.top = top;
+                            Some(Axis::Vertical) => {
+                                top = area.bottom - win_height * (row + 1) + area.top;
+                            }
+                            Some(Axis::HorizontalAndVertical) => {
+                                left = area.right - win_width * (col + 1) + area.left;
+                                top = area.bottom - win_height * (row + 1) + area.top;
+                            }
+                            None => {} // No flip
                         }
+
+                        layouts.push(Rect {
+                            left,
+                            top,
+                            right: win_width,
+                            bottom: win_height,
+                        });
+                    }
+                }
+
+                layouts
+            }
+            Self::CenteredMaster => {
+                let width_percentage = layout_options
+                    .and_then(|o| o.centered_master.map(|c| c.width_percentage))
+                    .unwrap_or(50)
+                    .clamp(1, 100);
+
+                #[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation)]
+                let side_width = (area.right - area.right * width_percentage as i32 / 100) / 2;
+
+                // Odd indices are stacked to the right of the primary container and even indices
+                // are stacked to the left of it
+                let right_count = len / 2;
+                let left_count = (len - 1) / 2;
+
+                // The primary container takes up the space of any side without containers
+                let (primary_left, primary_width) = match (left_count, right_count) {
+                    (0, 0) => (area.left, area.right),
+                    (0, _) => (area.left, area.right - side_width),
+                    _ => (area.left + side_width, area.right - side_width * 2),
+                };
+
+                let right_rows = match right_count {
+                    0 => vec![],
+                    n => rows(
+                        &Rect {
+                            left: area.left + area.right - side_width,
+                            top: area.top,
+                            right: side_width,
+                            bottom: area.bottom,
+                        },
+                        n,
+                    ),
+                };
+
+                let left_rows = match left_count {
+                    0 => vec![],
+                    n => rows(
+                        &Rect {
+                            left: area.left,
+                            top: area.top,
+                            right: side_width,
+                            bottom: area.bottom,
+                        },
+                        n,
+                    ),
+                };
+
+                let mut layouts = Vec::with_capacity(len);
+                layouts.push(Rect {
+                    left: primary_left,
+                    top: area.top,
+                    right: primary_width,
+                    bottom: area.bottom,
+                });
+
+                let mut right_rows = right_rows.into_iter();
+                let mut left_rows = left_rows.into_iter();
+                for i in 1..len {
+                    let next = if i % 2 == 1 {
+                        right_rows.next()
+                    } else {
+                        left_rows.next()
+                    };
+
+                    if let Some(rect) = next {
+                        layouts.push(rect);
+                    }
+                }
+
+                let adjustment = calculate_centered_master_adjustment(resize_dimensions);
+                layouts
+                    .iter_mut()
+                    .zip(adjustment.iter())
+                    .for_each(|(layout, adjustment)| {
+                        layout.top += adjustment.top;
+                        layout.bottom += adjustment.bottom;
+                        layout.left += adjustment.left;
+                        layout.right += adjustment.right;
+                    });
+
+                // Flipping mirrors the whole layout, stacks and resize adjustments included
+                if matches!(
+                    layout_flip,
+                    Some(Axis::Horizontal | Axis::HorizontalAndVertical)
+                ) {
+                    for rect in &mut layouts {
+                        rect.left = area.left * 2 + area.right - rect.left - rect.right;
+                    }
+                }
+
+                if matches!(
+                    layout_flip,
+                    Some(Axis::Vertical | Axis::HorizontalAndVertical)
+                ) {
+                    for rect in &mut layouts {
+                        rect.top = area.top * 2 + area.bottom - rect.top - rect.bottom;
                     }
                 }
 
@@ -593,6 +705,7 @@ impl Arrangement for CustomLayout {
         container_padding: Option<i32>,
         _layout_flip: Option<Axis>,
         _resize_dimensions: &[Option<Rect>],
+        _split_ratios: &[Option<f32>],
         _focused_idx: usize,
         _layout_options: Option<LayoutOptions>,
         _latest_layout: &[Rect],
@@ -867,6 +980,7 @@ fn recursive_fibonacci(
     area: &Rect,
     layout_flip: Option<Axis>,
     resize_adjustments: Vec<Option<Rect>>,
+    split_ratios: &[Option<f32>],
 ) -> Vec<Rect> {
     let mut a = *area;
 
@@ -880,33 +994,47 @@ fn recursive_fibonacci(
         *area
     };
 
-    let half_width = area.right / 2;
-    let half_height = area.bottom / 2;
-    let half_resized_width = resized.right / 2;
-    let half_resized_height = resized.bottom / 2;
+    // the share of the area given to the container at this split, which is an even split unless
+    // the split has been adjusted with a ResizeSplit message
+    let ratio = split_ratios.get(idx).copied().flatten();
+    let split = |length: i32| match ratio {
+        Some(ratio) => (length as f32 * ratio) as i32,
+        None => length / 2,
+    };
+
+    let half_width = split(area.right);
+    let half_height = split(area.bottom);
+    let half_resized_width = split(resized.right);
+    let half_resized_height = split(resized.bottom);
+
+    // the offset of the main container from the start of the area when the split is flipped
+    let (flipped_width, flipped_height) = match ratio {
+        Some(_) => (area.right - half_width, area.bottom - half_height),
+        None => (half_width, half_height),
+    };
 
     let (main_x, alt_x, alt_y, main_y);
 
     if let Some(flip) = layout_flip {
         match flip {
             Axis::Horizontal => {
-                main_x = resized.left + half_width + (half_width - half_resized_width);
+                main_x = resized.left + flipped_width + (half_width - half_resized_width);
                 alt_x = resized.left;
 
                 alt_y = resized.top + half_resized_height;
                 main_y = resized.top;
             }
             Axis::Vertical => {
-                main_y = resized.top + half_height + (half_height - half_resized_height);
+                main_y = resized.top + flipped_height + (half_height - half_resized_height);
                 alt_y = resized.top;
 
                 main_x = resized.left;
                 alt_x = resized.left + half_resized_width;
             }
             Axis::HorizontalAndVertical => {
-                main_x = resized.left + half_width + (half_width - half_resized_width);
+                main_x = resized.left + flipped_width + (half_width - half_resized_width);
                 alt_x = resized.left;
-                main_y = resized.top + half_height + (half_height - half_resized_height);
+                main_y = resized.top + flipped_height + (half_height - half_resized_height);
                 alt_y = resized.top;
             }
         }
@@ -945,6 +1073,7 @@ fn recursive_fibonacci(
             },
             layout_flip,
             resize_adjustments,
+            split_ratios,
         ));
         res
     } else {
@@ -965,6 +1094,7 @@ fn recursive_fibonacci(
             },
             layout_flip,
             resize_adjustments,
+            split_ratios,
         ));
         res
     }
@@ -1224,6 +1354,77 @@ fn calculate_ultrawide_adjustment(resize_dimensions: &[Option<Rect>]) -> Vec<Rec
     result
 }
 
+fn calculate_centered_master_adjustment(resize_dimensions: &[Option<Rect>]) -> Vec<Rect> {
+    let len = resize_dimensions.len();
+    let mut result = vec![Rect::default(); len];
+
+    // One container can't be resized
+    if len < 2 {
+        return result;
+    }
+
+    // Container 0 is in the center, odd containers are stacked on the right of it and even
+    // containers are stacked on the left of it
+    let right = (1..len).step_by(2).collect::<Vec<_>>();
+    let left = (2..len).step_by(2).collect::<Vec<_>>();
+
+    if let Some(resize_primary) = resize_dimensions[0] {
+        if !left.is_empty() {
+            resize_left(&mut result[0], resize_primary.left);
+            for idx in &left {
+                resize_right(&mut result[*idx], resize_primary.left);
+            }
+        }
+
+        resize_right(&mut result[0], resize_primary.right);
+        for idx in &right {
+            resize_left(&mut result[*idx], resize_primary.right);
+        }
+    }
+
+    // Handle stack on the right
+    for (i, idx) in right.iter().enumerate() {
+        if let Some(rect) = resize_dimensions[*idx] {
+            resize_right(&mut result[0], rect.left);
+            for idx in &right {
+                resize_left(&mut result[*idx], rect.left);
+            }
+
+            resize_stack_neighbours(&mut result, &right, i, &rect);
+        }
+    }
+
+    // Handle stack on the left
+    for (i, idx) in left.iter().enumerate() {
+        if let Some(rect) = resize_dimensions[*idx] {
+            resize_left(&mut result[0], rect.right);
+            for idx in &left {
+                resize_right(&mut result[*idx], rect.right);
+            }
+
+            resize_stack_neighbours(&mut result, &left, i, &rect);
+        }
+    }
+
+    result
+}
+
+/// Moves the edges shared by the container at position `i` of `stack` and the containers above
+/// and below it
+fn resize_stack_neighbours(result: &mut [Rect], stack: &[usize], i: usize, rect: &Rect) {
+    // Containers in stack except first can be resized up displacing container above them
+    if i != 0 {
+        resize_bottom(&mut result[stack[i - 1]], rect.top);
+        resize_top(&mut result[stack[i]], rect.top);
+    }
+
+    // Containers in stack except last can be resized down displacing container below them
+    if i != stack.len() - 1 {
+        resize_bottom(&mut result[stack[i]], rect.bottom);
+        resize_top(&mut result[stack[i + 1]], rect.bottom);
+    }
+}
+
 fn calculate_scrolling_adjustment(resize_dimensions: &[Option<Rect>]) -> Vec<Rect> {
     let len = resize_dimensions.len();
     let mut result = vec![Rect::default(); len];
@@ -1272,3 +1473,163 @@ fn resize_top(rect: &mut Rect, resize: i32) {
 fn resize_bottom(rect: &mut Rect, resize: i32) {
     rect.bottom += resize / 2;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::OperationDirection;
+
+    const AREA: Rect = Rect {
+        left: 0,
+        top: 0,
+        right: 1000,
+        bottom: 800,
+    };
+
+    fn centered_master(len: usize, flip: Option<Axis>, resize: &[Option<Rect>]) -> Vec<Rect> {
+        DefaultLayout::CenteredMaster.calculate(
+            &AREA,
+            NonZeroUsize::new(len).unwrap(),
+            None,
+            flip,
+            resize,
+            &[],
+            0,
+            None,
+            &[],
+        )
+    }
+
+    #[test]
+    fn test_centered_master_single_container_fills_area() {
+        assert_eq!(centered_master(1, None, &[None]), vec![AREA]);
+
+        // Without a stack on the left, the primary container takes up its space
+        assert_eq!(
+            centered_master(2, None, &[None, None]),
+            vec![
+                Rect {
+                    left: 0,
+                    top: 0,
+                    right: 750,
+                    bottom: 800,
+                },
+                Rect {
+                    left: 750,
+                    top: 0,
+                    right: 250,
+                    bottom: 800,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_centered_master_applies_resize_dimensions() {
+        let resize = [
+            Some(Rect {
+                left: 0,
+                top: 0,
+                right: 100,
+                bottom: 0,
+            }),
+            None,
+            None,
+        ];
+
+        let expected = vec![
+            Rect {
+                left: 250,
+                top: 0,
+                right: 550,
+                bottom: 800,
+            },
+            Rect {
+                left: 800,
+                top: 0,
+                right: 200,
+                bottom: 800,
+            },
+            Rect {
+                left: 0,
+                top: 0,
+                right: 250,
+                bottom: 800,
+            },
+        ];
+
+        assert_eq!(centered_master(3, None, &resize), expected);
+
+        // Flipping mirrors the resized layout
+        let flipped = centered_master(3, Some(Axis::Horizontal), &resize);
+        assert_eq!(flipped[0].left, 200);
+        assert_eq!(flipped[1].left, 0);
+        assert_eq!(flipped[2].left, 750);
+    }
+
+    #[test]
+    fn test_centered_master_directions_respect_layout_flip() {
+        let len = 5;
+
+        for flip in [
+            None,
+            Some(Axis::Horizontal),
+            Some(Axis::Vertical),
+            Some(Axis::HorizontalAndVertical),
+        ] {
+            let layouts = centered_master(len, flip, &vec![None; len]);
+
+            for (idx, from) in layouts.iter().enumerate() {
+                for direction in [
+                    OperationDirection::Left,
+                    OperationDirection::Right,
+                    OperationDirection::Up,
+                    OperationDirection::Down,
+                ] {
+                    let Some(target) = direction.destination(
+                        &DefaultLayout::CenteredMaster,
+                        flip,
+                        idx,
+                        NonZeroUsize::new(len).unwrap(),
+                        None,
+                    ) else {
+                        continue;
+                    };
+
+                    let to = layouts[target];
+                    let in_direction = match direction {
+                        OperationDirection::Left => to.left + to.right <= from.left,
+                        OperationDirection::Right => to.left >= from.left + from.right,
+                        OperationDirection::Up => to.top + to.bottom <= from.top,
+                        OperationDirection::Down => to.top >= from.top + from.bottom,
+                    };
+
+                    assert!(
+                        in_direction,
+                        "{target} is not {direction} of {idx} with flip {flip:?}"
+                    );
+                }
+            }
+        }
+
+        let destination = |direction: OperationDirection, flip, idx| {
+            direction.destination(
+                &DefaultLayout::CenteredMaster,
+                flip,
+                idx,
+                NonZeroUsize::new(len).unwrap(),
+                None,
+            )
+        };
+
+        assert_eq!(destination(OperationDirection::Right, None, 0), Some(1));
+        assert_eq!(
+            destination(OperationDirection::Left, Some(Axis::Horizontal), 0),
+            Some(1)
+        );
+        assert_eq!(
+            destination(OperationDirection::Up, Some(Axis::Vertical), 1),
+            Some(3)
+        );
+    }
+}
@@ -6,6 +6,9 @@ use strum::Display;
 use strum::EnumString;
 
 use super::ApplicationIdentifier;
+use super::HidingBehaviour;
+use super::NewWindowFocusBehaviour;
+use super::Rect;
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, Display, EnumString, ValueEnum)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
@@ -56,6 +59,19 @@ impl ApplicationOptions {
 pub enum MatchingRule {
     Simple(IdWithIdentifier),
     Composite(Vec<IdWithIdentifier>),
+    Combinator(RuleCombinator),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum RuleCombinator {
+    /// Matches when all of the nested rules match
+    All(Vec<MatchingRule>),
+    /// Matches when any of the nested rules match
+    Any(Vec<MatchingRule>),
+    /// Matches when the nested rule does not match
+    Not(Box<MatchingRule>),
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -65,6 +81,142 @@ pub struct WorkspaceMatchingRule {
     pub workspace_index: usize,
     pub matching_rule: MatchingRule,
     pub initial_only: bool,
+    #[serde(default)]
+    pub follow: bool,
+}
+
+/// A rule moving matching windows to a workspace, either as a plain matching rule or along with
+/// options for how the move is made
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum WorkspaceRule {
+    WithOptions(WorkspaceRuleWithOptions),
+    Plain(MatchingRule),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WorkspaceRuleWithOptions {
+    /// The rule identifying the applications to move to the workspace
+    pub matching_rule: MatchingRule,
+    /// Focus the workspace when a matching window is moved to it (default: false)
+    #[serde(default)]
+    pub follow: bool,
+}
+
+impl WorkspaceRule {
+    #[must_use]
+    pub const fn matching_rule(&self) -> &MatchingRule {
+        match self {
+            Self::WithOptions(rule) => &rule.matching_rule,
+            Self::Plain(matching_rule) => matching_rule,
+        }
+    }
+
+    #[must_use]
+    pub const fn follow(&self) -> bool {
+        match self {
+            Self::WithOptions(rule) => rule.follow,
+            Self::Plain(_) => false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct MinimumTileSizeRule {
+    /// The rule identifying the applications this minimum tile size applies to
+    pub matching_rule: MatchingRule,
+    /// Minimum width of a tile containing a matching window, in pixels
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<i32>,
+    /// Minimum height of a tile containing a matching window, in pixels
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<i32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct NewWindowFocusRule {
+    /// The rule identifying the applications this focus behaviour applies to
+    pub matching_rule: MatchingRule,
+    /// Whether new windows of matching applications receive focus
+    pub behaviour: NewWindowFocusBehaviour,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct HidingBehaviourRule {
+    /// The rule identifying the applications this hiding behaviour applies to
+    pub matching_rule: MatchingRule,
+    /// Which Windows signal to use when hiding matching windows
+    pub behaviour: HidingBehaviour,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum FloatingDimension {
+    /// An absolute number of pixels
+    Pixels(i32),
+    /// A percentage of the width or height of the work area
+    Percentage(f32),
+}
+
+impl FloatingDimension {
+    /// Resolves this dimension against `length`, the width or height of the work area
+    #[must_use]
+    pub fn resolve(self, length: i32) -> i32 {
+        match self {
+            Self::Pixels(pixels) => pixels,
+            Self::Percentage(percentage) => (length as f32 * percentage / 100.0).round() as i32,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct FloatingGeometryRule {
+    /// The rule identifying the applications this floating geometry applies to
+    pub matching_rule: MatchingRule,
+    /// Offset of matching windows from the left of the work area (default: centered)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub left: Option<FloatingDimension>,
+    /// Offset of matching windows from the top of the work area (default: centered)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top: Option<FloatingDimension>,
+    /// Width of matching windows (default: their current width)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<FloatingDimension>,
+    /// Height of matching windows (default: their current height)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<FloatingDimension>,
+}
+
+impl FloatingGeometryRule {
+    /// The position and size in `work_area` of a matching window which is currently `current`
+    #[must_use]
+    pub fn rect(&self, work_area: &Rect, current: &Rect) -> Rect {
+        let width = self
+            .width
+            .map_or(current.right, |width| width.resolve(work_area.right));
+        let height = self
+            .height
+            .map_or(current.bottom, |height| height.resolve(work_area.bottom));
+        let left = self.left.map_or((work_area.right - width) / 2, |left| {
+            left.resolve(work_area.right)
+        });
+        let top = self.top.map_or((work_area.bottom - height) / 2, |top| {
+            top.resolve(work_area.bottom)
+        });
+
+        Rect {
+            left: work_area.left + left,
+            top: work_area.top + top,
+            right: width,
+            bottom: height,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -286,3 +438,61 @@ impl ApplicationConfigurationGenerator {
         Ok(lines)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_floating_geometry_rule_rect() {
+        let work_area = Rect {
+            left: 100,
+            top: 40,
+            right: 2000,
+            bottom: 1000,
+        };
+
+        let current = Rect {
+            left: 0,
+            top: 0,
+            right: 640,
+            bottom: 360,
+        };
+
+        let mut rule = FloatingGeometryRule {
+            matching_rule: MatchingRule::Simple(IdWithIdentifier {
+                kind: ApplicationIdentifier::Exe,
+                id: String::from("mpv.exe"),
+                matching_strategy: Option::from(MatchingStrategy::Equals),
+            }),
+            left: None,
+            top: None,
+            width: Option::from(FloatingDimension::Percentage(25.0)),
+            height: None,
+        };
+
+        // unset positions center the window and unset sizes are kept
+        assert_eq!(
+            rule.rect(&work_area, &current),
+            Rect {
+                left: 850,
+                top: 360,
+                right: 500,
+                bottom: 360,
+            }
+        );
+
+        rule.left = Option::from(FloatingDimension::Percentage(75.0));
+        rule.top = Option::from(FloatingDimension::Pixels(20));
+
+        assert_eq!(
+            rule.rect(&work_area, &current),
+            Rect {
+                left: 1600,
+                top: 60,
+                right: 500,
+                bottom: 360,
+            }
+        );
+    }
+}
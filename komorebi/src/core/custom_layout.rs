@@ -13,28 +13,61 @@ use serde::Serialize;
 
 use super::Rect;
 
+/// Custom layouts are serialized as the plain list of columns found in custom layout files, the
+/// name of the active custom layout is surfaced separately on the workspace in the window manager
+/// state
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
-#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
-pub struct CustomLayout(Vec<Column>);
+#[serde(from = "Vec<Column>", into = "Vec<Column>")]
+pub struct CustomLayout {
+    /// Name of the custom layout, taken from the file stem of the file it was loaded from
+    pub name: Option<String>,
+    pub columns: Vec<Column>,
+}
+
+impl From<Vec<Column>> for CustomLayout {
+    fn from(columns: Vec<Column>) -> Self {
+        Self {
+            name: None,
+            columns,
+        }
+    }
+}
+
+impl From<CustomLayout> for Vec<Column> {
+    fn from(value: CustomLayout) -> Self {
+        value.columns
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for CustomLayout {
+    fn schema_name() -> String {
+        String::from("CustomLayout")
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        <Vec<Column>>::json_schema(gen)
+    }
+}
 
 impl Deref for CustomLayout {
     type Target = Vec<Column>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.columns
     }
 }
 
 impl DerefMut for CustomLayout {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        &mut self.columns
     }
 }
 
 impl CustomLayout {
     pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
         let path = path.as_ref();
-        let layout: Self = match path.extension() {
+        let mut layout: Self = match path.extension() {
             Some(extension) if extension == "yaml" || extension == "yml" => {
                 serde_json::from_reader(BufReader::new(File::open(path)?))?
             }
@@ -48,6 +81,10 @@ impl CustomLayout {
             bail!("the layout file provided was invalid");
         }
 
+        layout.name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string());
+
         Ok(layout)
     }
 
@@ -22,6 +22,12 @@ pub enum DefaultLayout {
     Grid,
     RightMainVerticalStack,
     Scrolling,
+    CenteredMaster,
+    /// Containers are placed in a tree of splits, the direction of which is chosen with the Split
+    /// message before a new window is added
+    Manual,
+    /// Every window on the workspace is stacked in a single container, with the stackbar as tabs
+    Tabbed,
     // NOTE: If any new layout is added, please make sure to register the same in `DefaultLayout::cycle`
 }
 
@@ -30,6 +36,12 @@ pub enum DefaultLayout {
 pub struct LayoutOptions {
     /// Options related to the Scrolling layout
     pub scrolling: Option<ScrollingLayoutOptions>,
+    /// Options related to the CenteredMaster layout
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub centered_master: Option<CenteredMasterLayoutOptions>,
+    /// Options related to the Grid layout
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grid: Option<GridLayoutOptions>,
 }
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, Eq, PartialEq)]
@@ -39,6 +51,81 @@ pub struct ScrollingLayoutOptions {
     pub columns: usize,
 }
 
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Eq, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CenteredMasterLayoutOptions {
+    /// Width of the centered master container as a percentage of the work area (default: 50)
+    pub width_percentage: usize,
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, Eq, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct GridLayoutOptions {
+    /// Order in which containers fill the grid (default: ColumnMajor)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fill: Option<GridFill>,
+    /// Maximum number of columns in the grid (default: unlimited)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_columns: Option<usize>,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+    Eq,
+    PartialEq,
+    Display,
+    EnumString,
+    ValueEnum,
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum GridFill {
+    /// Fill each column from top to bottom before moving on to the next column
+    #[default]
+    ColumnMajor,
+    /// Fill each row from left to right before moving on to the next row
+    RowMajor,
+}
+
+impl GridLayoutOptions {
+    /// Returns the number of containers in each line of a grid of `len` containers, where a line
+    /// is a column when filling column-major and a row when filling row-major
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    pub fn lines(&self, len: usize) -> Vec<usize> {
+        if len == 0 {
+            return vec![];
+        }
+
+        let num_cols = ((len as f32).sqrt().ceil() as usize)
+            .min(self.max_columns.unwrap_or(usize::MAX))
+            .max(1);
+
+        let num_lines = match self.fill.unwrap_or_default() {
+            GridFill::ColumnMajor => num_cols,
+            GridFill::RowMajor => len.div_ceil(num_cols),
+        };
+
+        // Earlier lines get fewer containers when they can't be evenly distributed
+        let mut lines = Vec::with_capacity(num_lines);
+        let mut remaining = len;
+        for line in 0..num_lines {
+            let count = remaining / (num_lines - line);
+            lines.push(count);
+            remaining -= count;
+        }
+
+        lines
+    }
+}
+
 impl DefaultLayout {
     pub fn leftmost_index(&self, len: usize) -> usize {
         match self {
@@ -47,12 +134,18 @@ impl DefaultLayout {
                 _ => 0,
             },
             Self::Scrolling => 0,
+            Self::CenteredMaster => match len {
+                n if n > 2 => 2,
+                _ => 0,
+            },
             DefaultLayout::BSP
             | DefaultLayout::Columns
             | DefaultLayout::Rows
             | DefaultLayout::VerticalStack
             | DefaultLayout::HorizontalStack
-            | DefaultLayout::Grid => 0,
+            | DefaultLayout::Grid
+            | DefaultLayout::Manual
+            | DefaultLayout::Tabbed => 0,
         }
     }
 
@@ -63,13 +156,19 @@ impl DefaultLayout {
             | DefaultLayout::Rows
             | DefaultLayout::VerticalStack
             | DefaultLayout::HorizontalStack
-            | DefaultLayout::Grid => len.saturating_sub(1),
+            | DefaultLayout::Grid
+            | DefaultLayout::Manual => len.saturating_sub(1),
+            DefaultLayout::Tabbed => 0,
             DefaultLayout::UltrawideVerticalStack => match len {
                 2 => 0,
                 _ => len.saturating_sub(1),
             },
             DefaultLayout::RightMainVerticalStack => 0,
             DefaultLayout::Scrolling => len.saturating_sub(1),
+            DefaultLayout::CenteredMaster => match len {
+                n if n > 1 => 1,
+                _ => 0,
+            },
         }
     }
 
@@ -93,6 +192,7 @@ impl DefaultLayout {
                 | Self::HorizontalStack
                 | Self::UltrawideVerticalStack
                 | Self::Scrolling
+                | Self::CenteredMaster
         ) {
             return None;
         };
@@ -188,13 +288,19 @@ impl DefaultLayout {
             Self::UltrawideVerticalStack => Self::Grid,
             Self::Grid => Self::RightMainVerticalStack,
             Self::RightMainVerticalStack => Self::Scrolling,
-            Self::Scrolling => Self::BSP,
+            Self::Scrolling => Self::CenteredMaster,
+            Self::CenteredMaster => Self::Manual,
+            Self::Manual => Self::Tabbed,
+            Self::Tabbed => Self::BSP,
         }
     }
 
     #[must_use]
     pub const fn cycle_previous(self) -> Self {
         match self {
+            Self::Tabbed => Self::Manual,
+            Self::Manual => Self::CenteredMaster,
+            Self::CenteredMaster => Self::Scrolling,
             Self::Scrolling => Self::RightMainVerticalStack,
             Self::RightMainVerticalStack => Self::Grid,
             Self::Grid => Self::UltrawideVerticalStack,
@@ -203,7 +309,7 @@ impl DefaultLayout {
             Self::VerticalStack => Self::Rows,
             Self::Rows => Self::Columns,
             Self::Columns => Self::BSP,
-            Self::BSP => Self::RightMainVerticalStack,
+            Self::BSP => Self::Tabbed,
         }
     }
 }
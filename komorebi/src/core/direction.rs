@@ -4,6 +4,8 @@ use super::custom_layout::ColumnSplitWithCapacity;
 use super::custom_layout::CustomLayout;
 use super::DefaultLayout;
 use super::OperationDirection;
+use crate::default_layout::GridFill;
+use crate::default_layout::LayoutOptions;
 
 pub trait Direction {
     fn index_in_direction(
@@ -11,6 +13,7 @@ pub trait Direction {
         op_direction: OperationDirection,
         idx: usize,
         count: usize,
+        layout_options: Option<LayoutOptions>,
     ) -> Option<usize>;
 
     fn is_valid_direction(
@@ -18,30 +21,35 @@ pub trait Direction {
         op_direction: OperationDirection,
         idx: usize,
         count: usize,
+        layout_options: Option<LayoutOptions>,
     ) -> bool;
     fn up_index(
         &self,
         op_direction: Option<OperationDirection>,
         idx: usize,
         count: Option<usize>,
+        layout_options: Option<LayoutOptions>,
     ) -> usize;
     fn down_index(
         &self,
         op_direction: Option<OperationDirection>,
         idx: usize,
         count: Option<usize>,
+        layout_options: Option<LayoutOptions>,
     ) -> usize;
     fn left_index(
         &self,
         op_direction: Option<OperationDirection>,
         idx: usize,
         count: Option<usize>,
+        layout_options: Option<LayoutOptions>,
     ) -> usize;
     fn right_index(
         &self,
         op_direction: Option<OperationDirection>,
         idx: usize,
         count: Option<usize>,
+        layout_options: Option<LayoutOptions>,
     ) -> usize;
 }
 
@@ -51,32 +59,53 @@ impl Direction for DefaultLayout {
         op_direction: OperationDirection,
         idx: usize,
         count: usize,
+        layout_options: Option<LayoutOptions>,
     ) -> Option<usize> {
         match op_direction {
             OperationDirection::Left => {
-                if self.is_valid_direction(op_direction, idx, count) {
-                    Option::from(self.left_index(Some(op_direction), idx, Some(count)))
+                if self.is_valid_direction(op_direction, idx, count, layout_options) {
+                    Option::from(self.left_index(
+                        Some(op_direction),
+                        idx,
+                        Some(count),
+                        layout_options,
+                    ))
                 } else {
                     None
                 }
             }
             OperationDirection::Right => {
-                if self.is_valid_direction(op_direction, idx, count) {
-                    Option::from(self.right_index(Some(op_direction), idx, Some(count)))
+                if self.is_valid_direction(op_direction, idx, count, layout_options) {
+                    Option::from(self.right_index(
+                        Some(op_direction),
+                        idx,
+                        Some(count),
+                        layout_options,
+                    ))
                 } else {
                     None
                 }
             }
             OperationDirection::Up => {
-                if self.is_valid_direction(op_direction, idx, count) {
-                    Option::from(self.up_index(Some(op_direction), idx, Some(count)))
+                if self.is_valid_direction(op_direction, idx, count, layout_options) {
+                    Option::from(self.up_index(
+                        Some(op_direction),
+                        idx,
+                        Some(count),
+                        layout_options,
+                    ))
                 } else {
                     None
                 }
             }
             OperationDirection::Down => {
-                if self.is_valid_direction(op_direction, idx, count) {
-                    Option::from(self.down_index(Some(op_direction), idx, Some(count)))
+                if self.is_valid_direction(op_direction, idx, count, layout_options) {
+                    Option::from(self.down_index(
+                        Some(op_direction),
+                        idx,
+                        Some(count),
+                        layout_options,
+                    ))
                 } else {
                     None
                 }
@@ -89,6 +118,7 @@ impl Direction for DefaultLayout {
         op_direction: OperationDirection,
         idx: usize,
         count: usize,
+        layout_options: Option<LayoutOptions>,
     ) -> bool {
         if count < 2 {
             return false;
@@ -101,8 +131,13 @@ impl Direction for DefaultLayout {
                 Self::Rows | Self::HorizontalStack => idx != 0,
                 Self::VerticalStack | Self::RightMainVerticalStack => idx != 0 && idx != 1,
                 Self::UltrawideVerticalStack => idx > 2,
-                Self::Grid => !is_grid_edge(op_direction, idx, count),
+                Self::Grid => grid_neighbor(op_direction, idx, count, layout_options).is_some(),
                 Self::Scrolling => false,
+                // the Manual layout is navigated using its calculated layout by the workspace
+                Self::Manual => false,
+                // tabs are navigated with the stack commands
+                Self::Tabbed => false,
+                Self::CenteredMaster => idx > 2,
             },
             OperationDirection::Down => match self {
                 Self::BSP => idx != count - 1 && idx % 2 != 0,
@@ -111,8 +146,11 @@ impl Direction for DefaultLayout {
                 Self::VerticalStack | Self::RightMainVerticalStack => idx != 0 && idx != count - 1,
                 Self::HorizontalStack => idx == 0,
                 Self::UltrawideVerticalStack => idx > 1 && idx != count - 1,
-                Self::Grid => !is_grid_edge(op_direction, idx, count),
+                Self::Grid => grid_neighbor(op_direction, idx, count, layout_options).is_some(),
                 Self::Scrolling => false,
+                Self::Manual => false,
+                Self::Tabbed => false,
+                Self::CenteredMaster => idx != 0 && idx + 2 < count,
             },
             OperationDirection::Left => match self {
                 Self::BSP => idx != 0,
@@ -121,8 +159,14 @@ impl Direction for DefaultLayout {
                 Self::Rows => false,
                 Self::HorizontalStack => idx != 0 && idx != 1,
                 Self::UltrawideVerticalStack => idx != 1,
-                Self::Grid => !is_grid_edge(op_direction, idx, count),
+                Self::Grid => grid_neighbor(op_direction, idx, count, layout_options).is_some(),
                 Self::Scrolling => idx != 0,
+                Self::Manual => false,
+                Self::Tabbed => false,
+                Self::CenteredMaster => match idx {
+                    0 => count > 2,
+                    n => n % 2 == 1,
+                },
             },
             OperationDirection::Right => match self {
                 Self::BSP => idx % 2 == 0 && idx != count - 1,
@@ -135,8 +179,14 @@ impl Direction for DefaultLayout {
                     2 => idx != 0,
                     _ => idx < 2,
                 },
-                Self::Grid => !is_grid_edge(op_direction, idx, count),
+                Self::Grid => grid_neighbor(op_direction, idx, count, layout_options).is_some(),
                 Self::Scrolling => idx != count - 1,
+                Self::Manual => false,
+                Self::Tabbed => false,
+                Self::CenteredMaster => match idx {
+                    0 => count > 1,
+                    n => n % 2 == 0,
+                },
             },
         }
     }
@@ -146,6 +196,7 @@ impl Direction for DefaultLayout {
         op_direction: Option<OperationDirection>,
         idx: usize,
         count: Option<usize>,
+        layout_options: Option<LayoutOptions>,
     ) -> usize {
         match self {
             Self::BSP => {
@@ -161,8 +212,10 @@ impl Direction for DefaultLayout {
             | Self::UltrawideVerticalStack
             | Self::RightMainVerticalStack => idx - 1,
             Self::HorizontalStack => 0,
-            Self::Grid => grid_neighbor(op_direction, idx, count),
+            Self::Grid => grid_neighbor_index(op_direction, idx, count, layout_options),
             Self::Scrolling => unreachable!(),
+            Self::Manual | Self::Tabbed => unreachable!(),
+            Self::CenteredMaster => idx - 2,
         }
     }
 
@@ -171,6 +224,7 @@ impl Direction for DefaultLayout {
         op_direction: Option<OperationDirection>,
         idx: usize,
         count: Option<usize>,
+        layout_options: Option<LayoutOptions>,
     ) -> usize {
         match self {
             Self::BSP
@@ -180,8 +234,10 @@ impl Direction for DefaultLayout {
             | Self::RightMainVerticalStack => idx + 1,
             Self::Columns => unreachable!(),
             Self::HorizontalStack => 1,
-            Self::Grid => grid_neighbor(op_direction, idx, count),
+            Self::Grid => grid_neighbor_index(op_direction, idx, count, layout_options),
             Self::Scrolling => unreachable!(),
+            Self::Manual | Self::Tabbed => unreachable!(),
+            Self::CenteredMaster => idx + 2,
         }
     }
 
@@ -190,6 +246,7 @@ impl Direction for DefaultLayout {
         op_direction: Option<OperationDirection>,
         idx: usize,
         count: Option<usize>,
+        layout_options: Option<LayoutOptions>,
     ) -> usize {
         match self {
             Self::BSP => {
@@ -208,8 +265,13 @@ impl Direction for DefaultLayout {
                 1 => unreachable!(),
                 _ => 0,
             },
-            Self::Grid => grid_neighbor(op_direction, idx, count),
+            Self::Grid => grid_neighbor_index(op_direction, idx, count, layout_options),
             Self::Scrolling => idx - 1,
+            Self::Manual | Self::Tabbed => unreachable!(),
+            Self::CenteredMaster => match idx {
+                0 => 2,
+                _ => 0,
+            },
         }
     }
 
@@ -218,6 +280,7 @@ impl Direction for DefaultLayout {
         op_direction: Option<OperationDirection>,
         idx: usize,
         count: Option<usize>,
+        layout_options: Option<LayoutOptions>,
     ) -> usize {
         match self {
             Self::BSP | Self::Columns | Self::HorizontalStack => idx + 1,
@@ -229,125 +292,83 @@ impl Direction for DefaultLayout {
                 0 => 2,
                 _ => unreachable!(),
             },
-            Self::Grid => grid_neighbor(op_direction, idx, count),
+            Self::Grid => grid_neighbor_index(op_direction, idx, count, layout_options),
             Self::Scrolling => idx + 1,
+            Self::Manual | Self::Tabbed => unreachable!(),
+            Self::CenteredMaster => match idx {
+                0 => 1,
+                _ => 0,
+            },
         }
     }
 }
 
-struct GridItem {
-    state: GridItemState,
-    row: usize,
-    num_rows: usize,
-    touching_edges: GridTouchingEdges,
+/// Returns the index of the container at `position` in `line` of a grid, clamping the position
+/// to the number of containers in that line
+fn grid_index(lines: &[usize], line: usize, position: usize) -> usize {
+    lines[..line].iter().sum::<usize>() + position.min(lines[line].saturating_sub(1))
 }
 
-enum GridItemState {
-    Valid,
-    Invalid,
-}
-
-#[allow(clippy::struct_excessive_bools)]
-struct GridTouchingEdges {
-    left: bool,
-    right: bool,
-    up: bool,
-    down: bool,
-}
-
-#[allow(
-    clippy::cast_possible_truncation,
-    clippy::cast_precision_loss,
-    clippy::cast_sign_loss
-)]
-fn get_grid_item(idx: usize, count: usize) -> GridItem {
-    let num_cols = (count as f32).sqrt().ceil() as usize;
-    let mut iter = 0;
+/// Returns the line of a grid that the container at `idx` is in, and its position in that line
+fn grid_position(lines: &[usize], idx: usize) -> Option<(usize, usize)> {
+    let mut start = 0;
 
-    for col in 0..num_cols {
-        let remaining_windows = count - iter;
-        let remaining_columns = num_cols - col;
-        let num_rows_in_this_col = remaining_windows / remaining_columns;
-
-        for row in 0..num_rows_in_this_col {
-            if iter == idx {
-                return GridItem {
-                    state: GridItemState::Valid,
-                    row: row + 1,
-                    num_rows: num_rows_in_this_col,
-                    touching_edges: GridTouchingEdges {
-                        left: col == 0,
-                        right: col == num_cols - 1,
-                        up: row == 0,
-                        down: row == num_rows_in_this_col - 1,
-                    },
-                };
-            }
-
-            iter += 1;
+    for (line, &len) in lines.iter().enumerate() {
+        if idx < start + len {
+            return Option::from((line, idx - start));
         }
-    }
 
-    GridItem {
-        state: GridItemState::Invalid,
-        row: 0,
-        num_rows: 0,
-        touching_edges: GridTouchingEdges {
-            left: true,
-            right: true,
-            up: true,
-            down: true,
-        },
+        start += len;
     }
+
+    None
 }
 
-fn is_grid_edge(op_direction: OperationDirection, idx: usize, count: usize) -> bool {
-    let item = get_grid_item(idx, count);
+fn grid_neighbor(
+    op_direction: OperationDirection,
+    idx: usize,
+    count: usize,
+    layout_options: Option<LayoutOptions>,
+) -> Option<usize> {
+    let options = layout_options.and_then(|o| o.grid).unwrap_or_default();
+    let lines = options.lines(count);
+    let (line, position) = grid_position(&lines, idx)?;
+
+    // Moving across lines changes the column when filling column-major and the row when filling
+    // row-major, while moving along a line does the opposite
+    let across = match (options.fill.unwrap_or_default(), op_direction) {
+        (GridFill::ColumnMajor, OperationDirection::Left | OperationDirection::Right)
+        | (GridFill::RowMajor, OperationDirection::Up | OperationDirection::Down) => true,
+        (GridFill::ColumnMajor, OperationDirection::Up | OperationDirection::Down)
+        | (GridFill::RowMajor, OperationDirection::Left | OperationDirection::Right) => false,
+    };
 
-    match item.state {
-        GridItemState::Invalid => false,
-        GridItemState::Valid => match op_direction {
-            OperationDirection::Left => item.touching_edges.left,
-            OperationDirection::Right => item.touching_edges.right,
-            OperationDirection::Up => item.touching_edges.up,
-            OperationDirection::Down => item.touching_edges.down,
-        },
+    let forward = matches!(
+        op_direction,
+        OperationDirection::Right | OperationDirection::Down
+    );
+
+    match (across, forward) {
+        (true, false) => line
+            .checked_sub(1)
+            .map(|line| grid_index(&lines, line, position)),
+        (true, true) => (line + 1 < lines.len()).then(|| grid_index(&lines, line + 1, position)),
+        (false, false) => position.checked_sub(1).map(|_| idx - 1),
+        (false, true) => (position + 1 < lines[line]).then_some(idx + 1),
     }
 }
 
-fn grid_neighbor(
+fn grid_neighbor_index(
     op_direction: Option<OperationDirection>,
     idx: usize,
     count: Option<usize>,
+    layout_options: Option<LayoutOptions>,
 ) -> usize {
-    let Some(op_direction) = op_direction else {
-        return 0;
-    };
-
-    let Some(count) = count else {
+    let (Some(op_direction), Some(count)) = (op_direction, count) else {
         return 0;
     };
 
-    let item = get_grid_item(idx, count);
-
-    match op_direction {
-        OperationDirection::Left => {
-            let item_from_prev_col = get_grid_item(idx - item.row, count);
-
-            if item.touching_edges.up && item.num_rows != item_from_prev_col.num_rows {
-                return idx - (item.num_rows - 1);
-            }
-
-            if item.num_rows != item_from_prev_col.num_rows && !item.touching_edges.down {
-                return idx - (item.num_rows - 1);
-            }
-
-            idx - item.num_rows
-        }
-        OperationDirection::Right => idx + item.num_rows,
-        OperationDirection::Up => idx - 1,
-        OperationDirection::Down => idx + 1,
-    }
+    grid_neighbor(op_direction, idx, count, layout_options).unwrap_or_default()
 }
 
 impl Direction for CustomLayout {
@@ -356,36 +377,42 @@ impl Direction for CustomLayout {
         op_direction: OperationDirection,
         idx: usize,
         count: usize,
+        layout_options: Option<LayoutOptions>,
     ) -> Option<usize> {
         if count <= self.len() {
-            return DefaultLayout::Columns.index_in_direction(op_direction, idx, count);
+            return DefaultLayout::Columns.index_in_direction(
+                op_direction,
+                idx,
+                count,
+                layout_options,
+            );
         }
 
         match op_direction {
             OperationDirection::Left => {
-                if self.is_valid_direction(op_direction, idx, count) {
-                    Option::from(self.left_index(None, idx, None))
+                if self.is_valid_direction(op_direction, idx, count, layout_options) {
+                    Option::from(self.left_index(None, idx, None, layout_options))
                 } else {
                     None
                 }
             }
             OperationDirection::Right => {
-                if self.is_valid_direction(op_direction, idx, count) {
-                    Option::from(self.right_index(None, idx, None))
+                if self.is_valid_direction(op_direction, idx, count, layout_options) {
+                    Option::from(self.right_index(None, idx, None, layout_options))
                 } else {
                     None
                 }
             }
             OperationDirection::Up => {
-                if self.is_valid_direction(op_direction, idx, count) {
-                    Option::from(self.up_index(None, idx, None))
+                if self.is_valid_direction(op_direction, idx, count, layout_options) {
+                    Option::from(self.up_index(None, idx, None, layout_options))
                 } else {
                     None
                 }
             }
             OperationDirection::Down => {
-                if self.is_valid_direction(op_direction, idx, count) {
-                    Option::from(self.down_index(None, idx, None))
+                if self.is_valid_direction(op_direction, idx, count, layout_options) {
+                    Option::from(self.down_index(None, idx, None, layout_options))
                 } else {
                     None
                 }
@@ -398,9 +425,15 @@ impl Direction for CustomLayout {
         op_direction: OperationDirection,
         idx: usize,
         count: usize,
+        layout_options: Option<LayoutOptions>,
     ) -> bool {
         if count <= self.len() {
-            return DefaultLayout::Columns.is_valid_direction(op_direction, idx, count);
+            return DefaultLayout::Columns.is_valid_direction(
+                op_direction,
+                idx,
+                count,
+                layout_options,
+            );
         }
 
         match op_direction {
@@ -444,6 +477,7 @@ impl Direction for CustomLayout {
         _op_direction: Option<OperationDirection>,
         idx: usize,
         _count: Option<usize>,
+        _layout_options: Option<LayoutOptions>,
     ) -> usize {
         idx - 1
     }
@@ -453,6 +487,7 @@ impl Direction for CustomLayout {
         _op_direction: Option<OperationDirection>,
         idx: usize,
         _count: Option<usize>,
+        _layout_options: Option<LayoutOptions>,
     ) -> usize {
         idx + 1
     }
@@ -462,6 +497,7 @@ impl Direction for CustomLayout {
         _op_direction: Option<OperationDirection>,
         idx: usize,
         _count: Option<usize>,
+        _layout_options: Option<LayoutOptions>,
     ) -> usize {
         let column_idx = self.column_for_container_idx(idx);
         if column_idx - 1 == 0 {
@@ -476,6 +512,7 @@ impl Direction for CustomLayout {
         _op_direction: Option<OperationDirection>,
         idx: usize,
         _count: Option<usize>,
+        _layout_options: Option<LayoutOptions>,
     ) -> usize {
         let column_idx = self.column_for_container_idx(idx);
         self.first_container_idx(column_idx + 1)
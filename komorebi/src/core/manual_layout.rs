@@ -0,0 +1,421 @@
+use clap::ValueEnum;
+use serde::Deserialize;
+use serde::Serialize;
+use strum::Display;
+use strum::EnumString;
+
+use super::OperationDirection;
+use super::Rect;
+
+#[derive(
+    Clone, Copy, Debug, Serialize, Deserialize, Eq, PartialEq, Display, EnumString, ValueEnum,
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum SplitDirection {
+    /// Place the next window to the right of the focused window
+    Horizontal,
+    /// Place the next window below the focused window
+    Vertical,
+}
+
+/// A node in the tree of a workspace using the Manual layout
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum ManualNode {
+    /// A container or reserved tile, identified by its id
+    Leaf(String),
+    /// An area divided evenly between its children in the given direction
+    Split {
+        direction: SplitDirection,
+        children: Vec<ManualNode>,
+    },
+}
+
+/// The explicit tree of splits that containers are placed in with the Manual layout
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Eq, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ManualLayout {
+    pub root: Option<ManualNode>,
+    /// Direction to split the focused container in when the next window is added, chosen with
+    /// the Split message
+    pub next_split: Option<SplitDirection>,
+}
+
+impl ManualNode {
+    fn contains(&self, id: &str) -> bool {
+        match self {
+            Self::Leaf(leaf) => leaf == id,
+            Self::Split { children, .. } => children.iter().any(|child| child.contains(id)),
+        }
+    }
+
+    /// Removes every leaf for which `keep` returns false, collapsing splits left with a single
+    /// child into that child. Returns false if nothing is left of this node.
+    fn retain(&mut self, keep: &impl Fn(&str) -> bool) -> bool {
+        match self {
+            Self::Leaf(id) => keep(id),
+            Self::Split { children, .. } => {
+                children.retain_mut(|child| child.retain(keep));
+
+                match children.len() {
+                    0 => false,
+                    1 => {
+                        *self = children.remove(0);
+                        true
+                    }
+                    _ => true,
+                }
+            }
+        }
+    }
+
+    /// Places a new leaf `id` after the leaf `target`, either next to it in its parent split if
+    /// that is in the same direction, or in a new split replacing it. Returns false if `target`
+    /// is not in this node.
+    fn insert_after(
+        &mut self,
+        target: &str,
+        id: &str,
+        direction: Option<SplitDirection>,
+        parent_direction: Option<SplitDirection>,
+    ) -> bool {
+        match self {
+            Self::Leaf(leaf) if leaf == target => {
+                *self = Self::Split {
+                    direction: direction
+                        .or(parent_direction)
+                        .unwrap_or(SplitDirection::Horizontal),
+                    children: vec![Self::Leaf(target.to_string()), Self::Leaf(id.to_string())],
+                };
+
+                true
+            }
+            Self::Leaf(_) => false,
+            Self::Split {
+                direction: split_direction,
+                children,
+            } => {
+                let position = children
+                    .iter()
+                    .position(|child| matches!(child, Self::Leaf(leaf) if leaf == target));
+                let same_direction =
+                    direction.is_none_or(|direction| direction == *split_direction);
+
+                match position {
+                    Some(idx) if same_direction => {
+                        children.insert(idx + 1, Self::Leaf(id.to_string()));
+                        true
+                    }
+                    _ => {
+                        let split_direction = *split_direction;
+                        children.iter_mut().any(|child| {
+                            child.insert_after(target, id, direction, Some(split_direction))
+                        })
+                    }
+                }
+            }
+        }
+    }
+
+    fn swap(&mut self, a: &str, b: &str) {
+        match self {
+            Self::Leaf(id) => {
+                if id == a {
+                    *id = b.to_string();
+                } else if id == b {
+                    *id = a.to_string();
+                }
+            }
+            Self::Split { children, .. } => {
+                for child in children {
+                    child.swap(a, b);
+                }
+            }
+        }
+    }
+
+    fn calculate(&self, area: &Rect, rects: &mut Vec<(String, Rect)>) {
+        match self {
+            Self::Leaf(id) => rects.push((id.clone(), *area)),
+            Self::Split {
+                direction,
+                children,
+            } => {
+                let count = children.len() as i32;
+                let mut offset = 0;
+
+                for (i, child) in children.iter().enumerate() {
+                    let i = i as i32;
+                    // the last child takes whatever is left over after rounding
+                    let child_area = match direction {
+                        SplitDirection::Horizontal => {
+                            let width = if i == count - 1 {
+                                area.right - offset
+                            } else {
+                                area.right / count
+                            };
+
+                            let rect = Rect {
+                                left: area.left + offset,
+                                top: area.top,
+                                right: width,
+                                bottom: area.bottom,
+                            };
+
+                            offset += width;
+                            rect
+                        }
+                        SplitDirection::Vertical => {
+                            let height = if i == count - 1 {
+                                area.bottom - offset
+                            } else {
+                                area.bottom / count
+                            };
+
+                            let rect = Rect {
+                                left: area.left,
+                                top: area.top + offset,
+                                right: area.right,
+                                bottom: height,
+                            };
+
+                            offset += height;
+                            rect
+                        }
+                    };
+
+                    child.calculate(&child_area, rects);
+                }
+            }
+        }
+    }
+}
+
+impl ManualLayout {
+    /// Brings the tree in line with `ids`, the ids of the containers and reserved tiles of a
+    /// workspace in order, dropping leaves which are gone and placing each new id after the id
+    /// before it
+    pub fn sync(&mut self, ids: &[String]) {
+        if let Some(root) = &mut self.root {
+            if !root.retain(&|id| ids.iter().any(|existing| existing == id)) {
+                self.root = None;
+            }
+        }
+
+        for (i, id) in ids.iter().enumerate() {
+            let Some(root) = &mut self.root else {
+                self.root = Option::from(ManualNode::Leaf(id.clone()));
+                continue;
+            };
+
+            if root.contains(id) {
+                continue;
+            }
+
+            let direction = self.next_split.take();
+
+            let inserted = ids[..i]
+                .iter()
+                .rev()
+                .any(|target| root.insert_after(target, id, direction, None));
+
+            // nothing before it is in the tree, so it becomes the first leaf
+            if !inserted {
+                let previous = std::mem::replace(root, ManualNode::Leaf(id.clone()));
+                *root = ManualNode::Split {
+                    direction: direction.unwrap_or(SplitDirection::Horizontal),
+                    children: vec![ManualNode::Leaf(id.clone()), previous],
+                };
+            }
+        }
+    }
+
+    /// Swaps the positions of the leaves `a` and `b` in the tree
+    pub fn swap(&mut self, a: &str, b: &str) {
+        if let Some(root) = &mut self.root {
+            root.swap(a, b);
+        }
+    }
+
+    /// Returns the area of each of `ids` within `area`, in the same order
+    pub fn calculate(&self, area: &Rect, ids: &[String], container_padding: i32) -> Vec<Rect> {
+        let mut rects = vec![];
+        if let Some(root) = &self.root {
+            root.calculate(area, &mut rects);
+        }
+
+        ids.iter()
+            .map(|id| {
+                let mut rect = rects
+                    .iter()
+                    .find_map(|(leaf, rect)| (leaf == id).then_some(*rect))
+                    .unwrap_or(*area);
+
+                rect.add_padding(container_padding);
+                rect
+            })
+            .collect()
+    }
+}
+
+/// Returns the index of the nearest of `layouts` in `direction` of the one at `idx` which it
+/// overlaps with along the other axis
+pub fn neighbour_in_direction(
+    layouts: &[Rect],
+    idx: usize,
+    direction: OperationDirection,
+) -> Option<usize> {
+    let current = layouts.get(idx)?;
+
+    let overlap = |start_a: i32, length_a: i32, start_b: i32, length_b: i32| {
+        (start_a + length_a).min(start_b + length_b) - start_a.max(start_b)
+    };
+
+    layouts
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != idx)
+        .filter_map(|(i, rect)| {
+            let (distance, overlap) = match direction {
+                OperationDirection::Left => (
+                    current.left - (rect.left + rect.right),
+                    overlap(current.top, current.bottom, rect.top, rect.bottom),
+                ),
+                OperationDirection::Right => (
+                    rect.left - (current.left + current.right),
+                    overlap(current.top, current.bottom, rect.top, rect.bottom),
+                ),
+                OperationDirection::Up => (
+                    current.top - (rect.top + rect.bottom),
+                    overlap(current.left, current.right, rect.left, rect.right),
+                ),
+                OperationDirection::Down => (
+                    rect.top - (current.top + current.bottom),
+                    overlap(current.left, current.right, rect.left, rect.right),
+                ),
+            };
+
+            (distance >= 0 && overlap > 0).then_some((i, distance, overlap))
+        })
+        .min_by_key(|(_, distance, overlap)| (*distance, -overlap))
+        .map(|(i, _, _)| i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AREA: Rect = Rect {
+        left: 0,
+        top: 0,
+        right: 1000,
+        bottom: 800,
+    };
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_sync_follows_chosen_split_directions() {
+        let mut layout = ManualLayout::default();
+
+        layout.sync(&ids(&["a", "b"]));
+        layout.next_split = Option::from(SplitDirection::Vertical);
+        layout.sync(&ids(&["a", "b", "c"]));
+
+        // b was split vertically, and the choice only applies to the next window
+        assert_eq!(layout.next_split, None);
+        assert_eq!(
+            layout.calculate(&AREA, &ids(&["a", "b", "c"]), 0),
+            vec![
+                Rect {
+                    left: 0,
+                    top: 0,
+                    right: 500,
+                    bottom: 800,
+                },
+                Rect {
+                    left: 500,
+                    top: 0,
+                    right: 500,
+                    bottom: 400,
+                },
+                Rect {
+                    left: 500,
+                    top: 400,
+                    right: 500,
+                    bottom: 400,
+                },
+            ]
+        );
+
+        // c continues the vertical split it was placed in
+        layout.sync(&ids(&["a", "b", "c", "d"]));
+        assert_eq!(
+            layout.root,
+            Some(ManualNode::Split {
+                direction: SplitDirection::Horizontal,
+                children: vec![
+                    ManualNode::Leaf("a".to_string()),
+                    ManualNode::Split {
+                        direction: SplitDirection::Vertical,
+                        children: vec![
+                            ManualNode::Leaf("b".to_string()),
+                            ManualNode::Leaf("c".to_string()),
+                            ManualNode::Leaf("d".to_string()),
+                        ],
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn test_sync_collapses_splits_of_removed_containers() {
+        let mut layout = ManualLayout::default();
+
+        layout.sync(&ids(&["a", "b"]));
+        layout.next_split = Option::from(SplitDirection::Vertical);
+        layout.sync(&ids(&["a", "b", "c"]));
+        layout.sync(&ids(&["a", "c"]));
+
+        assert_eq!(
+            layout.root,
+            Some(ManualNode::Split {
+                direction: SplitDirection::Horizontal,
+                children: vec![
+                    ManualNode::Leaf("a".to_string()),
+                    ManualNode::Leaf("c".to_string()),
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn test_neighbour_in_direction() {
+        let mut layout = ManualLayout::default();
+        layout.sync(&ids(&["a", "b"]));
+        layout.next_split = Option::from(SplitDirection::Vertical);
+        layout.sync(&ids(&["a", "b", "c"]));
+
+        let layouts = layout.calculate(&AREA, &ids(&["a", "b", "c"]), 10);
+
+        assert_eq!(
+            neighbour_in_direction(&layouts, 2, OperationDirection::Up),
+            Some(1)
+        );
+        assert_eq!(
+            neighbour_in_direction(&layouts, 2, OperationDirection::Left),
+            Some(0)
+        );
+        assert_eq!(
+            neighbour_in_direction(&layouts, 0, OperationDirection::Right),
+            Some(1)
+        );
+        assert_eq!(
+            neighbour_in_direction(&layouts, 0, OperationDirection::Down),
+            None
+        );
+    }
+}
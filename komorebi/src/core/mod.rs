@@ -15,6 +15,7 @@ use strum::EnumString;
 use crate::animation::prefix::AnimationPrefix;
 use crate::KomorebiTheme;
 pub use animation::AnimationStyle;
+pub use animation::WorkspaceSwitchAnimation;
 pub use arrangement::Arrangement;
 pub use arrangement::Axis;
 pub use custom_layout::Column;
@@ -26,6 +27,8 @@ pub use cycle_direction::CycleDirection;
 pub use default_layout::DefaultLayout;
 pub use direction::Direction;
 pub use layout::Layout;
+pub use manual_layout::ManualLayout;
+pub use manual_layout::SplitDirection;
 pub use operation_direction::OperationDirection;
 pub use pathext::replace_env_in_path;
 pub use pathext::resolve_option_hashmap_usize_path;
@@ -42,6 +45,7 @@ pub mod cycle_direction;
 pub mod default_layout;
 pub mod direction;
 pub mod layout;
+pub mod manual_layout;
 pub mod operation_direction;
 pub mod pathext;
 pub mod rect;
@@ -62,10 +66,18 @@ pub enum SocketMessage {
     CycleStack(CycleDirection),
     CycleStackIndex(CycleDirection),
     FocusStackWindow(usize),
+    MoveStackWindow(isize, usize),
+    FocusHwnd(isize),
+    Mark(String),
+    Unmark(String),
+    JumpToMark(String),
     StackAll,
     UnstackAll,
     ResizeWindowEdge(OperationDirection, Sizing),
     ResizeWindowAxis(Axis, Sizing),
+    ResizeWindowPercent(Axis, i32),
+    ResizeSplit(OperationDirection, i32),
+    Split(SplitDirection),
     MoveContainerToLastWorkspace,
     SendContainerToLastWorkspace,
     MoveContainerToMonitorNumber(usize),
@@ -83,9 +95,14 @@ pub enum SocketMessage {
     CycleMoveWorkspaceToMonitor(CycleDirection),
     MoveWorkspaceToMonitorNumber(usize),
     SwapWorkspacesToMonitorNumber(usize),
+    CloneWorkspaceToMonitorNumber(usize),
+    SwapWorkspaces(usize, usize, usize),
     ForceFocus,
     Close,
     Minimize,
+    CloseWindow(isize),
+    MinimizeWindow(isize),
+    RestoreHidden,
     Promote,
     PromoteFocus,
     PromoteWindow(OperationDirection),
@@ -94,7 +111,9 @@ pub enum SocketMessage {
     UnlockMonitorWorkspaceContainer(usize, usize, usize),
     ToggleLock,
     ToggleFloat,
+    ToggleAlwaysOnTop,
     ToggleMonocle,
+    ToggleZoom,
     ToggleMaximize,
     ToggleWindowContainerBehaviour,
     ToggleFloatOverride,
@@ -114,28 +133,38 @@ pub enum SocketMessage {
     FlipLayout(Axis),
     ToggleWorkspaceWindowContainerBehaviour,
     ToggleWorkspaceFloatOverride,
+    ToggleSmartGaps,
     // Monitor and Workspace Commands
     MonitorIndexPreference(usize, i32, i32, i32, i32),
     DisplayIndexPreference(usize, String),
+    PersistMonitorIndices,
     EnsureWorkspaces(usize, usize),
     EnsureNamedWorkspaces(usize, Vec<String>),
     NewWorkspace,
+    ReserveTile(String, bool),
+    ClearReservedTiles,
     ToggleTiling,
     Stop,
     StopIgnoreRestore,
     TogglePause,
     Retile,
     RetileWithResizeDimensions,
+    RetileMonitor(usize),
+    RetileWorkspace(usize, usize),
+    RetileNamedWorkspace(String),
     QuickSave,
     QuickLoad,
     Save(#[serde_as(as = "ResolvedPathBuf")] PathBuf),
     Load(#[serde_as(as = "ResolvedPathBuf")] PathBuf),
+    SaveSession(#[serde_as(as = "ResolvedPathBuf")] PathBuf),
+    LoadSession(#[serde_as(as = "ResolvedPathBuf")] PathBuf),
     CycleFocusMonitor(CycleDirection),
     CycleFocusWorkspace(CycleDirection),
     CycleFocusEmptyWorkspace(CycleDirection),
     FocusMonitorNumber(usize),
     FocusMonitorAtCursor,
     FocusLastWorkspace,
+    FocusLastWindow,
     CloseWorkspace,
     FocusWorkspaceNumber(usize),
     FocusWorkspaceNumbers(usize),
@@ -177,6 +206,7 @@ pub enum SocketMessage {
     Animation(bool, Option<AnimationPrefix>),
     AnimationDuration(u64, Option<AnimationPrefix>),
     AnimationFps(u64),
+    AnimationMaxConcurrent(usize),
     AnimationStyle(AnimationStyle, Option<AnimationPrefix>),
     #[serde(alias = "ActiveWindowBorder")]
     Border(bool),
@@ -203,6 +233,8 @@ pub enum SocketMessage {
     WorkAreaOffset(Rect),
     MonitorWorkAreaOffset(usize, Rect),
     ToggleWindowBasedWorkAreaOffset,
+    ToggleBar,
+    PrivacyMode(bool),
     ResizeDelta(i32),
     InitialWorkspaceRule(ApplicationIdentifier, String, usize, usize),
     InitialNamedWorkspaceRule(ApplicationIdentifier, String, String),
@@ -224,6 +256,7 @@ pub enum SocketMessage {
     IdentifyBorderOverflowApplication(ApplicationIdentifier, String),
     State,
     GlobalState,
+    Ping,
     VisibleWindows,
     MonitorInformation,
     Query(StateQuery),
@@ -231,12 +264,16 @@ pub enum SocketMessage {
     ToggleFocusFollowsMouse(FocusFollowsMouseImplementation),
     MouseFollowsFocus(bool),
     ToggleMouseFollowsFocus,
+    /// Process the wrapped message without moving the mouse cursor to the newly focused window
+    NoMouseFollowsFocus(Box<SocketMessage>),
     RemoveTitleBar(ApplicationIdentifier, String),
     ToggleTitleBars,
     AddSubscriberSocket(String),
     AddSubscriberSocketWithOptions(String, SubscribeOptions),
     RemoveSubscriberSocket(String),
+    SubscribeSocketDiff(String),
     AddSubscriberPipe(String),
+    AddSubscriberPipeWithOptions(String, SubscribeOptions),
     RemoveSubscriberPipe(String),
     ApplicationSpecificConfigurationSchema,
     NotificationSchema,
@@ -260,11 +297,36 @@ impl FromStr for SocketMessage {
     }
 }
 
-#[derive(Default, Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Default, Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SubscribeOptions {
     /// Only emit notifications when the window manager state has changed
     pub filter_state_changes: bool,
+    /// Only emit notifications for these kinds of events (default: all events)
+    pub events: Option<Vec<SubscriptionEvent>>,
+}
+
+#[derive(
+    Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Display, EnumString, ValueEnum,
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum SubscriptionEvent {
+    /// A window received focus
+    FocusChange,
+    /// A workspace was focused or had its properties changed
+    WorkspaceChange,
+    /// A monitor was focused, connected, disconnected or reconfigured
+    MonitorChange,
+    /// A window was shown, hidden, moved, resized or had its title changed
+    WindowChange,
+    /// A container was stacked, moved, resized, locked or floated
+    ContainerChange,
+    /// A workspace layout was changed
+    LayoutChange,
+    /// The configuration was reloaded or a global setting was changed
+    ConfigurationChange,
+    /// Any other event
+    Other,
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Display, Serialize, Deserialize, ValueEnum)]
@@ -297,6 +359,15 @@ pub enum BorderStyle {
     Square,
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Display, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum BorderAnimation {
+    /// Slowly pulse the opacity of the focused window border
+    Pulse,
+    /// Rotate a gradient around the focused window border
+    Gradient,
+}
+
 #[derive(
     Default, Copy, Clone, Debug, Eq, PartialEq, Display, Serialize, Deserialize, ValueEnum,
 )]
@@ -344,6 +415,9 @@ pub enum StateQuery {
     FocusedWorkspaceName,
     FocusedWorkspaceLayout,
     FocusedContainerKind,
+    FocusedWindowHidingBehaviour,
+    WindowHidingBehaviour,
+    Marks,
     Version,
 }
 
@@ -362,6 +436,16 @@ pub enum ApplicationIdentifier {
     Path,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum KeyModifier {
+    Alt,
+    Ctrl,
+    Shift,
+    /// Either of the Windows keys
+    Win,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize, Display, EnumString, ValueEnum)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum FocusFollowsMouseImplementation {
@@ -486,6 +570,18 @@ pub enum CrossBoundaryBehaviour {
     Monitor,
 }
 
+#[derive(
+    Clone, Copy, Debug, Default, Serialize, Deserialize, Display, EnumString, ValueEnum, PartialEq,
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum CrossMonitorFocusBehaviour {
+    /// Focus the container at the nearest edge of the layout on the monitor directly adjacent to the focused monitor
+    #[default]
+    Layout,
+    /// Focus the container on any monitor which is closest on screen to the focused window in the given direction
+    Geometry,
+}
+
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, Display, EnumString, ValueEnum, PartialEq)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum HidingBehaviour {
@@ -495,6 +591,9 @@ pub enum HidingBehaviour {
     Minimize,
     /// Use the undocumented SetCloak Win32 function to hide windows when switching workspaces
     Cloak,
+    /// Move windows outside of the visible desktop when switching workspaces (for apps which stop
+    /// audio or timers when cloaked)
+    OffScreen,
 }
 
 #[derive(
@@ -532,6 +631,32 @@ impl Sizing {
     }
 }
 
+#[derive(
+    Clone, Copy, Debug, Default, Serialize, Deserialize, Display, EnumString, ValueEnum, PartialEq,
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum NewWindowFocusBehaviour {
+    /// New windows always receive focus
+    #[default]
+    Always,
+    /// New windows only receive focus if they open on the focused workspace
+    FocusedWorkspace,
+    /// New windows never receive focus and are marked as urgent instead
+    Never,
+}
+
+#[derive(
+    Clone, Copy, Debug, Default, Serialize, Deserialize, Display, EnumString, ValueEnum, PartialEq,
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum MinimumTileSizeBehaviour {
+    /// Take space from neighbouring tiles to give the window its minimum size
+    #[default]
+    Reflow,
+    /// Float the window when its tile would be smaller than its minimum size
+    Float,
+}
+
 #[derive(
     Clone, Copy, Debug, Default, Serialize, Deserialize, Display, EnumString, ValueEnum, PartialEq,
 )]
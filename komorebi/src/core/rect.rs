@@ -2,6 +2,8 @@ use serde::Deserialize;
 use serde::Serialize;
 use windows::Win32::Foundation::RECT;
 
+use super::OperationDirection;
+
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Rect {
@@ -95,6 +97,79 @@ impl Rect {
         }
     }
 
+    /// Returns how far `target` is from self when travelling in `direction`, as a tuple of the
+    /// gap on the perpendicular axis, the gap on the axis of travel and the offset between the
+    /// centres on the perpendicular axis, or `None` if `target` is not in that direction
+    #[must_use]
+    pub fn distance_in_direction(
+        &self,
+        target: &Rect,
+        direction: OperationDirection,
+    ) -> Option<(i32, i32, i32)> {
+        let (origin_start, origin_end, target_start, target_end) = match direction {
+            OperationDirection::Left | OperationDirection::Right => (
+                self.left,
+                self.left + self.right,
+                target.left,
+                target.left + target.right,
+            ),
+            OperationDirection::Up | OperationDirection::Down => (
+                self.top,
+                self.top + self.bottom,
+                target.top,
+                target.top + target.bottom,
+            ),
+        };
+
+        let (origin_cross_start, origin_cross_end, target_cross_start, target_cross_end) =
+            match direction {
+                OperationDirection::Left | OperationDirection::Right => (
+                    self.top,
+                    self.top + self.bottom,
+                    target.top,
+                    target.top + target.bottom,
+                ),
+                OperationDirection::Up | OperationDirection::Down => (
+                    self.left,
+                    self.left + self.right,
+                    target.left,
+                    target.left + target.right,
+                ),
+            };
+
+        let target_centre = (target_start + target_end) / 2;
+        let gap = match direction {
+            OperationDirection::Left | OperationDirection::Up => {
+                if target_centre >= origin_start {
+                    return None;
+                }
+
+                origin_start - target_end
+            }
+            OperationDirection::Right | OperationDirection::Down => {
+                if target_centre <= origin_end {
+                    return None;
+                }
+
+                target_start - origin_end
+            }
+        };
+
+        let cross_gap = if target_cross_end < origin_cross_start {
+            origin_cross_start - target_cross_end
+        } else if target_cross_start > origin_cross_end {
+            target_cross_start - origin_cross_end
+        } else {
+            0
+        };
+
+        let cross_offset = ((origin_cross_start + origin_cross_end) / 2
+            - (target_cross_start + target_cross_end) / 2)
+            .abs();
+
+        Option::from((cross_gap, gap.max(0), cross_offset))
+    }
+
     #[must_use]
     pub const fn rect(&self) -> RECT {
         RECT {
@@ -105,3 +180,163 @@ impl Rect {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORIGIN: Rect = Rect {
+        left: 0,
+        top: 0,
+        right: 1920,
+        bottom: 1080,
+    };
+
+    #[test]
+    fn test_distance_in_each_direction() {
+        let left = Rect {
+            left: -1920,
+            top: 0,
+            right: 1920,
+            bottom: 1080,
+        };
+        let right = Rect {
+            left: 1920,
+            top: 0,
+            right: 1920,
+            bottom: 1080,
+        };
+        let up = Rect {
+            left: 0,
+            top: -1080,
+            right: 1920,
+            bottom: 1080,
+        };
+        let down = Rect {
+            left: 0,
+            top: 1080,
+            right: 1920,
+            bottom: 1080,
+        };
+
+        for (target, direction) in [
+            (left, OperationDirection::Left),
+            (right, OperationDirection::Right),
+            (up, OperationDirection::Up),
+            (down, OperationDirection::Down),
+        ] {
+            assert_eq!(
+                ORIGIN.distance_in_direction(&target, direction),
+                Some((0, 0, 0))
+            );
+        }
+    }
+
+    #[test]
+    fn test_distance_to_offset_monitors() {
+        // A taller monitor to the right, shifted down
+        let right = Rect {
+            left: 1920,
+            top: 300,
+            right: 2560,
+            bottom: 1440,
+        };
+        assert_eq!(
+            ORIGIN.distance_in_direction(&right, OperationDirection::Right),
+            Some((0, 0, 480))
+        );
+
+        // A monitor above, shifted left
+        let up = Rect {
+            left: -500,
+            top: -1080,
+            right: 1920,
+            bottom: 1080,
+        };
+        assert_eq!(
+            ORIGIN.distance_in_direction(&up, OperationDirection::Up),
+            Some((0, 0, 500))
+        );
+
+        // A monitor to the left with a gap between the two
+        let left = Rect {
+            left: -2000,
+            top: 100,
+            right: 1900,
+            bottom: 800,
+        };
+        assert_eq!(
+            ORIGIN.distance_in_direction(&left, OperationDirection::Left),
+            Some((0, 100, 40))
+        );
+
+        // A monitor diagonally below and to the right, with gaps on both axes
+        let down = Rect {
+            left: 2000,
+            top: 1200,
+            right: 1920,
+            bottom: 1080,
+        };
+        assert_eq!(
+            ORIGIN.distance_in_direction(&down, OperationDirection::Down),
+            Some((80, 120, 2000))
+        );
+    }
+
+    #[test]
+    fn test_distance_to_overlapping_monitor() {
+        // The centre of the overlapping monitor is past the right edge of the origin
+        let overlapping = Rect {
+            left: 1000,
+            top: 0,
+            right: 1920,
+            bottom: 1080,
+        };
+
+        assert_eq!(
+            ORIGIN.distance_in_direction(&overlapping, OperationDirection::Right),
+            Some((0, 0, 0))
+        );
+        assert_eq!(
+            ORIGIN.distance_in_direction(&overlapping, OperationDirection::Left),
+            None
+        );
+    }
+
+    #[test]
+    fn test_no_distance_in_opposite_direction() {
+        let right = Rect {
+            left: 1920,
+            top: 0,
+            right: 1920,
+            bottom: 1080,
+        };
+        let down = Rect {
+            left: 0,
+            top: 1080,
+            right: 1920,
+            bottom: 1080,
+        };
+
+        assert_eq!(
+            ORIGIN.distance_in_direction(&right, OperationDirection::Left),
+            None
+        );
+        assert_eq!(
+            ORIGIN.distance_in_direction(&down, OperationDirection::Up),
+            None
+        );
+
+        // A monitor whose centre lines up with the edge of the origin is not beyond it
+        let centred_on_edge = Rect {
+            left: 960,
+            top: 0,
+            right: 1920,
+            bottom: 1080,
+        };
+        assert_eq!(
+            ORIGIN.distance_in_direction(&centred_on_edge, OperationDirection::Right),
+            None
+        );
+    }
+}
@@ -0,0 +1,346 @@
+#![deny(clippy::unwrap_used, clippy::expect_used)]
+
+use crate::animation::prefix::new_animation_key;
+use crate::animation::prefix::AnimationPrefix;
+use crate::animation::ANIMATION_MANAGER;
+use crate::border_manager;
+use crate::core::config_generation::IdWithIdentifier;
+use crate::core::config_generation::MatchingRule;
+use crate::core::config_generation::MatchingStrategy;
+use crate::core::ApplicationIdentifier;
+use crate::core::Rect;
+use crate::notify_subscribers;
+use crate::winevent_listener;
+use crate::Notification;
+use crate::NotificationEvent;
+use crate::Window;
+use crate::WindowManager;
+use crate::WindowManagerEvent;
+use crate::WindowsApi;
+use crate::FLOATING_APPLICATIONS;
+use crate::IGNORE_IDENTIFIERS;
+
+use crossbeam_channel::Receiver;
+use crossbeam_channel::Sender;
+use crossbeam_utils::atomic::AtomicCell;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::time::Instant;
+
+/// Number of rejected placements within `EVICTION_WINDOW_SECONDS` after which a window is
+/// evicted, 0 disables eviction
+pub static EVICTION_THRESHOLD: AtomicUsize = AtomicUsize::new(0);
+pub static EVICTION_WINDOW_SECONDS: AtomicU64 = AtomicU64::new(10);
+pub static EVICTION_BEHAVIOUR: AtomicCell<EvictionBehaviour> =
+    AtomicCell::new(EvictionBehaviour::Float);
+
+lazy_static! {
+    /// Where each tiled window was left after it was last placed and when it rejected its placements
+    static ref PLACEMENTS: Arc<Mutex<HashMap<isize, Placement>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+}
+
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, Eq, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum EvictionBehaviour {
+    /// Float the window, leaving it wherever it puts itself
+    #[default]
+    Float,
+    /// Stop managing the window
+    Ignore,
+}
+
+/// A window which was evicted for repeatedly rejecting the position komorebi placed it in
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WindowEviction {
+    pub window: Window,
+    pub exe: String,
+    pub behaviour: EvictionBehaviour,
+    /// A rule which can be added to `floating_applications` or `ignore_rules` to handle the
+    /// application from the start
+    pub suggested_rule: MatchingRule,
+}
+
+/// How far in pixels a window can be from where it was left before it has moved itself
+const PLACEMENT_TOLERANCE: i32 = 10;
+
+struct Placement {
+    placed: Option<Rect>,
+    rejections: VecDeque<Instant>,
+}
+
+/// Whether a window which was left at `placed` has moved itself to `current`
+fn has_moved_itself(placed: &Rect, current: &Rect) -> bool {
+    (placed.left - current.left).abs() > PLACEMENT_TOLERANCE
+        || (placed.top - current.top).abs() > PLACEMENT_TOLERANCE
+        || (placed.right - current.right).abs() > PLACEMENT_TOLERANCE
+        || (placed.bottom - current.bottom).abs() > PLACEMENT_TOLERANCE
+}
+
+impl Placement {
+    /// Records a rejection at `now`, returning true once there have been at least `threshold`
+    /// rejections within `window`
+    fn reject(&mut self, now: Instant, window: Duration, threshold: usize) -> bool {
+        self.rejections.push_back(now);
+
+        while self
+            .rejections
+            .front()
+            .is_some_and(|rejection| now.duration_since(*rejection) > window)
+        {
+            self.rejections.pop_front();
+        }
+
+        self.rejections.len() >= threshold
+    }
+}
+
+pub struct EvictionNotification(isize);
+
+static CHANNEL: OnceLock<(Sender<EvictionNotification>, Receiver<EvictionNotification>)> =
+    OnceLock::new();
+
+pub fn channel() -> &'static (Sender<EvictionNotification>, Receiver<EvictionNotification>) {
+    CHANNEL.get_or_init(|| crossbeam_channel::bounded(20))
+}
+
+fn event_tx() -> Sender<EvictionNotification> {
+    channel().0.clone()
+}
+
+fn event_rx() -> Receiver<EvictionNotification> {
+    channel().1.clone()
+}
+
+pub fn send_notification(hwnd: isize) {
+    if event_tx().try_send(EvictionNotification(hwnd)).is_err() {
+        tracing::warn!("channel is full; dropping notification")
+    }
+}
+
+/// Called before a tiled window with `hwnd` currently at `current` is positioned again. If it has
+/// moved itself away from where it was left after it was last positioned, it has rejected that
+/// placement, and is evicted once it has done so too many times.
+pub fn check_placement(hwnd: isize, current: &Rect) {
+    let threshold = EVICTION_THRESHOLD.load(Ordering::SeqCst);
+    if threshold == 0 {
+        return;
+    }
+
+    let mut placements = PLACEMENTS.lock();
+    let Some(placement) = placements.get_mut(&hwnd) else {
+        return;
+    };
+
+    // the window was still on its way to its position when it was last placed
+    let Some(placed) = placement.placed.take() else {
+        return;
+    };
+
+    if !has_moved_itself(&placed, current) {
+        return;
+    }
+
+    let window = Duration::from_secs(EVICTION_WINDOW_SECONDS.load(Ordering::SeqCst));
+    if placement.reject(Instant::now(), window, threshold) {
+        placements.remove(&hwnd);
+        send_notification(hwnd);
+    }
+}
+
+/// Called after a tiled window with `hwnd` has been positioned, remembering where it was left so
+/// that it can be told apart from where it puts itself
+pub fn record_placement(hwnd: isize) {
+    if EVICTION_THRESHOLD.load(Ordering::SeqCst) == 0 {
+        return;
+    }
+
+    // the window is still on its way to the requested position
+    let animation_key = new_animation_key(AnimationPrefix::Movement, hwnd.to_string());
+    let placed = if ANIMATION_MANAGER.lock().in_progress(&animation_key) {
+        None
+    } else {
+        WindowsApi::window_rect(hwnd).ok()
+    };
+
+    PLACEMENTS
+        .lock()
+        .entry(hwnd)
+        .or_insert_with(|| Placement {
+            placed: None,
+            rejections: VecDeque::new(),
+        })
+        .placed = placed;
+}
+
+pub fn listen_for_notifications(wm: Arc<Mutex<WindowManager>>) {
+    std::thread::spawn(move || loop {
+        match handle_notifications(wm.clone()) {
+            Ok(()) => {
+                tracing::warn!("restarting finished thread");
+            }
+            Err(error) => {
+                tracing::warn!("restarting failed thread: {}", error);
+            }
+        }
+    });
+}
+
+fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result<()> {
+    tracing::info!("listening");
+
+    let receiver = event_rx();
+
+    for notification in receiver {
+        let window = Window::from(notification.0);
+        if !window.is_window() {
+            PLACEMENTS.lock().remove(&window.hwnd);
+            continue;
+        }
+
+        wm.lock().evict_window(window)?;
+    }
+
+    Ok(())
+}
+
+impl WindowManager {
+    /// Floats or stops managing `window` according to `EVICTION_BEHAVIOUR`, adding a rule for
+    /// its executable for the rest of the session so that it isn't tiled again
+    #[tracing::instrument(skip(self))]
+    pub fn evict_window(&mut self, window: Window) -> color_eyre::Result<()> {
+        let Some((monitor_idx, workspace_idx)) = self.known_hwnds.get(&window.hwnd).copied() else {
+            return Ok(());
+        };
+
+        let exe = window.exe()?;
+        let behaviour = EVICTION_BEHAVIOUR.load();
+        let suggested_rule = MatchingRule::Simple(IdWithIdentifier {
+            kind: ApplicationIdentifier::Exe,
+            id: exe.clone(),
+            matching_strategy: Option::from(MatchingStrategy::Equals),
+        });
+
+        tracing::warn!(
+            "evicting {exe} ({}) after it repeatedly rejected its placement, add {} to {} to handle it from the start",
+            window.hwnd,
+            serde_json::to_string(&suggested_rule)?,
+            match behaviour {
+                EvictionBehaviour::Float => "floating_applications",
+                EvictionBehaviour::Ignore => "ignore_rules",
+            }
+        );
+
+        match behaviour {
+            EvictionBehaviour::Float => {
+                FLOATING_APPLICATIONS.lock().push(suggested_rule.clone());
+
+                let focused_workspace_idx = self.focused_workspace_idx()?;
+                let focused_monitor_idx = self.focused_monitor_idx();
+
+                let Some(workspace) = self
+                    .monitors_mut()
+                    .get_mut(monitor_idx)
+                    .and_then(|monitor| monitor.workspaces_mut().get_mut(workspace_idx))
+                else {
+                    return Ok(());
+                };
+
+                if workspace
+                    .floating_windows()
+                    .iter()
+                    .any(|w| w.hwnd == window.hwnd)
+                {
+                    return Ok(());
+                }
+
+                workspace.remove_window(window.hwnd)?;
+                workspace.floating_windows_mut().push_back(window);
+
+                if monitor_idx == focused_monitor_idx && workspace_idx == focused_workspace_idx {
+                    self.update_focused_workspace(false, false)?;
+                    border_manager::send_notification(Some(window.hwnd));
+                }
+            }
+            EvictionBehaviour::Ignore => {
+                IGNORE_IDENTIFIERS.lock().push(suggested_rule.clone());
+                winevent_listener::event_tx().send(WindowManagerEvent::Unmanage(window))?;
+            }
+        }
+
+        notify_subscribers(
+            Notification {
+                event: NotificationEvent::WindowEviction(WindowEviction {
+                    window,
+                    exe,
+                    behaviour,
+                    suggested_rule,
+                }),
+                state: self.as_ref().into(),
+            },
+            true,
+        )?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejections_outside_window_are_forgotten() {
+        let mut placement = Placement {
+            placed: None,
+            rejections: VecDeque::new(),
+        };
+
+        let start = Instant::now();
+        let window = Duration::from_secs(10);
+
+        assert!(!placement.reject(start, window, 3));
+        assert!(!placement.reject(start + Duration::from_secs(5), window, 3));
+        // the first rejection is more than 10 seconds ago by now
+        assert!(!placement.reject(start + Duration::from_secs(11), window, 3));
+        assert!(placement.reject(start + Duration::from_secs(12), window, 3));
+    }
+
+    #[test]
+    fn test_small_moves_are_not_rejections() {
+        let placed = Rect {
+            left: 0,
+            top: 0,
+            right: 960,
+            bottom: 1080,
+        };
+
+        // a terminal snapping its size to its character grid
+        let snapped = Rect {
+            right: 955,
+            bottom: 1072,
+            ..placed
+        };
+
+        let moved = Rect {
+            left: 200,
+            top: 100,
+            ..placed
+        };
+
+        assert!(!has_moved_itself(&placed, &placed));
+        assert!(!has_moved_itself(&placed, &snapped));
+        assert!(has_moved_itself(&placed, &moved));
+    }
+}
@@ -0,0 +1,685 @@
+#![deny(clippy::unwrap_used, clippy::expect_used)]
+
+use crate::container::Container;
+use crate::monitor::Monitor;
+use crate::window_manager::WindowManager;
+use crate::workspace::Workspace;
+use crate::CycleDirection;
+use crate::NotificationEvent;
+use crate::OperationDirection;
+use crate::Rect;
+use crate::SocketMessage;
+use crate::State;
+use crate::SubscriptionEvent;
+use crate::Window;
+use crate::WindowManagerEvent;
+use crate::WindowsApi;
+use crate::DATA_DIR;
+use crate::PRIVACY_MODE;
+
+use color_eyre::Result;
+use crossbeam_channel::Receiver;
+use crossbeam_channel::Sender;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use serde_json::json;
+use serde_json::Value;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::io::Write;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::OnceLock;
+use std::time::Duration;
+use uds_windows::UnixListener;
+use uds_windows::UnixStream;
+
+/// Name of the socket in the data directory which i3 clients can be pointed to with `I3SOCK`
+pub const SOCKET_NAME: &str = "i3-ipc.sock";
+
+const MAGIC: &[u8; 6] = b"i3-ipc";
+const HEADER_LENGTH: usize = MAGIC.len() + 8;
+
+const RUN_COMMAND: u32 = 0;
+const GET_WORKSPACES: u32 = 1;
+const SUBSCRIBE: u32 = 2;
+const GET_OUTPUTS: u32 = 3;
+const GET_TREE: u32 = 4;
+const GET_VERSION: u32 = 7;
+
+// The highest bit of the message type is set for events
+const WORKSPACE_EVENT: u32 = 1 << 31;
+const WINDOW_EVENT: u32 = (1 << 31) | 3;
+
+struct Subscriber {
+    stream: UnixStream,
+    workspace: bool,
+    window: bool,
+}
+
+/// An event for subscribed clients, resolved from the window manager state but with the
+/// window queries needed to describe it left to the writer thread
+enum Event {
+    Window {
+        change: &'static str,
+        window: Window,
+        focused: bool,
+    },
+    Workspace(Value),
+}
+
+lazy_static! {
+    static ref SUBSCRIBERS: Mutex<Vec<Subscriber>> = Mutex::new(vec![]);
+}
+
+static CHANNEL: OnceLock<(Sender<Event>, Receiver<Event>)> = OnceLock::new();
+
+fn channel() -> &'static (Sender<Event>, Receiver<Event>) {
+    CHANNEL.get_or_init(|| crossbeam_channel::bounded(50))
+}
+
+fn event_tx() -> Sender<Event> {
+    channel().0.clone()
+}
+
+fn event_rx() -> Receiver<Event> {
+    channel().1.clone()
+}
+
+pub fn listen_for_clients(wm: Arc<Mutex<WindowManager>>) -> Result<()> {
+    let socket = DATA_DIR.join(SOCKET_NAME);
+
+    match std::fs::remove_file(&socket) {
+        Ok(()) => {}
+        Err(error) if error.kind() == ErrorKind::NotFound => {}
+        Err(error) => return Err(error.into()),
+    }
+
+    let listener = UnixListener::bind(&socket)?;
+    tracing::info!("listening for i3 ipc clients on {}", socket.display());
+
+    std::thread::spawn(|| loop {
+        match write_events() {
+            Ok(()) => {
+                tracing::warn!("restarting finished thread");
+            }
+            Err(error) => {
+                tracing::warn!("restarting failed thread: {}", error);
+            }
+        }
+    });
+
+    std::thread::spawn(move || {
+        for client in listener.incoming() {
+            match client {
+                Ok(stream) => {
+                    let wm = wm.clone();
+                    std::thread::spawn(move || {
+                        if let Err(error) = handle_client(&wm, stream) {
+                            tracing::debug!("i3 ipc client disconnected: {error}");
+                        }
+                    });
+                }
+                Err(error) => {
+                    tracing::error!("{error}");
+                    break;
+                }
+            }
+        }
+    });
+
+    Ok(())
+}
+
+fn handle_client(wm: &Arc<Mutex<WindowManager>>, mut stream: UnixStream) -> Result<()> {
+    loop {
+        let (message_type, payload) = read_message(&mut stream)?;
+        let payload = String::from_utf8_lossy(&payload);
+
+        let reply = match message_type {
+            RUN_COMMAND => Value::Array(run_commands(wm, &payload)),
+            GET_WORKSPACES => Value::Array(workspaces(&State::from(&*wm.lock()))),
+            GET_OUTPUTS => Value::Array(outputs(&State::from(&*wm.lock()))),
+            GET_TREE => tree(&State::from(&*wm.lock())),
+            GET_VERSION => json!({
+                "major": 4,
+                "minor": 0,
+                "patch": 0,
+                "human_readable": format!("komorebi {}", env!("CARGO_PKG_VERSION")),
+                "loaded_config_file_name": "",
+            }),
+            SUBSCRIBE => match serde_json::from_str::<Vec<String>>(&payload) {
+                Ok(events) => {
+                    // The reply has to be received before any of the events
+                    write_message(&mut stream, SUBSCRIBE, br#"{"success":true}"#)?;
+
+                    // A client which stops reading is dropped instead of holding up the others
+                    let subscriber = stream.try_clone()?;
+                    subscriber.set_write_timeout(Some(Duration::from_secs(1)))?;
+
+                    SUBSCRIBERS.lock().push(Subscriber {
+                        stream: subscriber,
+                        workspace: events.iter().any(|event| event == "workspace"),
+                        window: events.iter().any(|event| event == "window"),
+                    });
+
+                    continue;
+                }
+                Err(error) => json!({ "success": false, "error": error.to_string() }),
+            },
+            _ => json!({ "success": false, "error": "unsupported message type" }),
+        };
+
+        write_message(&mut stream, message_type, reply.to_string().as_bytes())?;
+    }
+}
+
+fn read_message(stream: &mut impl Read) -> std::io::Result<(u32, Vec<u8>)> {
+    let mut header = [0; HEADER_LENGTH];
+    stream.read_exact(&mut header)?;
+
+    if &header[..MAGIC.len()] != MAGIC {
+        return Err(std::io::Error::new(
+            ErrorKind::InvalidData,
+            "message does not start with the i3-ipc magic string",
+        ));
+    }
+
+    let length = u32::from_le_bytes([header[6], header[7], header[8], header[9]]);
+    let message_type = u32::from_le_bytes([header[10], header[11], header[12], header[13]]);
+
+    let mut payload = vec![0; length as usize];
+    stream.read_exact(&mut payload)?;
+
+    Ok((message_type, payload))
+}
+
+fn write_message(
+    stream: &mut impl Write,
+    message_type: u32,
+    payload: &[u8],
+) -> std::io::Result<()> {
+    let mut message = Vec::with_capacity(HEADER_LENGTH + payload.len());
+    message.extend_from_slice(MAGIC);
+    message.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    message.extend_from_slice(&message_type.to_le_bytes());
+    message.extend_from_slice(payload);
+
+    stream.write_all(&message)
+}
+
+/// Queues the i3 events corresponding to a komorebi notification for the subscribed clients
+pub fn push_events(event: &NotificationEvent, state: &State) -> Result<()> {
+    if SUBSCRIBERS.lock().is_empty() {
+        return Ok(());
+    }
+
+    let window_event = |change, window: Window| Event::Window {
+        change,
+        window,
+        focused: focused_window(state).is_some_and(|focused| focused.hwnd == window.hwnd),
+    };
+
+    let event = match event {
+        NotificationEvent::WindowManager(WindowManagerEvent::Manage(window)) => {
+            window_event("new", *window)
+        }
+        NotificationEvent::WindowManager(WindowManagerEvent::Destroy(_, window)) => {
+            window_event("close", *window)
+        }
+        NotificationEvent::WindowManager(WindowManagerEvent::TitleUpdate(_, window)) => {
+            window_event("title", *window)
+        }
+        event => match SubscriptionEvent::from(event) {
+            SubscriptionEvent::FocusChange => match focused_window(state) {
+                Some(window) => window_event("focus", window),
+                None => return Ok(()),
+            },
+            SubscriptionEvent::WorkspaceChange => {
+                let Some((monitor_idx, monitor)) = state
+                    .monitors
+                    .focused()
+                    .map(|monitor| (state.monitors.focused_idx(), monitor))
+                else {
+                    return Ok(());
+                };
+
+                let workspace_idx = monitor.focused_workspace_idx();
+                let Some(workspace) = monitor.focused_workspace() else {
+                    return Ok(());
+                };
+
+                Event::Workspace(json!({
+                    "change": "focus",
+                    "current": workspace_node(
+                        monitor_idx, monitor, workspace_idx, workspace, true
+                    ),
+                    "old": null,
+                }))
+            }
+            _ => return Ok(()),
+        },
+    };
+
+    if event_tx().try_send(event).is_err() {
+        tracing::warn!("channel is full; dropping i3 event")
+    }
+
+    Ok(())
+}
+
+/// Writes queued events to the subscribed clients, away from the window manager lock
+fn write_events() -> Result<()> {
+    tracing::info!("listening");
+
+    for event in event_rx() {
+        let (event_type, payload) = match event {
+            Event::Window {
+                change,
+                window,
+                focused,
+            } => (
+                WINDOW_EVENT,
+                json!({
+                    "change": change,
+                    "container": window_node(window, focused),
+                }),
+            ),
+            Event::Workspace(payload) => (WORKSPACE_EVENT, payload),
+        };
+
+        let payload = payload.to_string();
+
+        // Clients which have disconnected or stopped reading are dropped
+        SUBSCRIBERS.lock().retain_mut(|subscriber| {
+            let subscribed = match event_type {
+                WORKSPACE_EVENT => subscriber.workspace,
+                _ => subscriber.window,
+            };
+
+            !subscribed
+                || write_message(&mut subscriber.stream, event_type, payload.as_bytes()).is_ok()
+        });
+    }
+
+    Ok(())
+}
+
+/// Translates a single i3 command into the equivalent komorebi command
+fn translate_command(command: &str) -> std::result::Result<SocketMessage, String> {
+    let words = command
+        .split_whitespace()
+        .map(|word| word.trim_matches('"'))
+        .collect::<Vec<_>>();
+
+    let direction = |word: &str| match word {
+        "left" => Ok(OperationDirection::Left),
+        "right" => Ok(OperationDirection::Right),
+        "up" => Ok(OperationDirection::Up),
+        "down" => Ok(OperationDirection::Down),
+        _ => Err(format!("unknown direction: {word}")),
+    };
+
+    // i3 workspace numbers start at 1
+    let workspace_number = |number: &str| match number.parse::<usize>() {
+        Ok(number) if number > 0 => Ok(number - 1),
+        _ => Err(format!("invalid workspace number: {number}")),
+    };
+
+    match words.as_slice() {
+        ["workspace", "next" | "next_on_output"] => {
+            Ok(SocketMessage::CycleFocusWorkspace(CycleDirection::Next))
+        }
+        ["workspace", "prev" | "prev_on_output"] => {
+            Ok(SocketMessage::CycleFocusWorkspace(CycleDirection::Previous))
+        }
+        ["workspace", "back_and_forth"] => Ok(SocketMessage::FocusLastWorkspace),
+        ["workspace", "number", number] => Ok(SocketMessage::FocusWorkspaceNumber(
+            workspace_number(number)?,
+        )),
+        ["workspace", name @ ..] if !name.is_empty() => match workspace_number(name[0]) {
+            Ok(number) if name.len() == 1 => Ok(SocketMessage::FocusWorkspaceNumber(number)),
+            _ => Ok(SocketMessage::FocusNamedWorkspace(name.join(" "))),
+        },
+        ["focus", direction_word] => Ok(SocketMessage::FocusWindow(direction(direction_word)?)),
+        ["move", "container" | "window", "to", "workspace", "number", number]
+        | ["move", "to", "workspace", "number", number] => Ok(
+            SocketMessage::MoveContainerToWorkspaceNumber(workspace_number(number)?),
+        ),
+        ["move", "container" | "window", "to", "workspace", name @ ..]
+        | ["move", "to", "workspace", name @ ..]
+            if !name.is_empty() =>
+        {
+            match workspace_number(name[0]) {
+                Ok(number) if name.len() == 1 => {
+                    Ok(SocketMessage::MoveContainerToWorkspaceNumber(number))
+                }
+                _ => Ok(SocketMessage::MoveContainerToNamedWorkspace(name.join(" "))),
+            }
+        }
+        ["move", direction_word] | ["move", "container" | "window", direction_word] => {
+            Ok(SocketMessage::MoveWindow(direction(direction_word)?))
+        }
+        ["fullscreen"] | ["fullscreen", "toggle"] => Ok(SocketMessage::ToggleMonocle),
+        ["floating", "toggle"] => Ok(SocketMessage::ToggleFloat),
+        ["kill"] => Ok(SocketMessage::Close),
+        ["reload"] => Ok(SocketMessage::ReloadConfiguration),
+        _ => Err(format!("unsupported command: {command}")),
+    }
+}
+
+fn run_commands(wm: &Arc<Mutex<WindowManager>>, payload: &str) -> Vec<Value> {
+    payload
+        .split([';', ','])
+        .map(str::trim)
+        .filter(|command| !command.is_empty())
+        .map(|command| {
+            let result = translate_command(command).and_then(|message| {
+                wm.lock()
+                    .process_command(message, std::io::sink())
+                    .map_err(|error| error.to_string())
+            });
+
+            match result {
+                Ok(()) => json!({ "success": true }),
+                Err(error) => json!({ "success": false, "error": error }),
+            }
+        })
+        .collect()
+}
+
+fn rect(rect: &Rect) -> Value {
+    json!({
+        "x": rect.left,
+        "y": rect.top,
+        "width": rect.right,
+        "height": rect.bottom,
+    })
+}
+
+// i3 node ids are unique integers, so komorebi's indices and ids are mapped into separate ranges
+fn output_id(monitor_idx: usize) -> usize {
+    (monitor_idx + 1) << 16
+}
+
+fn workspace_id(monitor_idx: usize, workspace_idx: usize) -> usize {
+    output_id(monitor_idx) | (workspace_idx + 1)
+}
+
+fn container_id(container: &Container) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    container.id().hash(&mut hasher);
+    // Kept clear of the range of window handles
+    hasher.finish() | (1 << 62)
+}
+
+fn focused_window(state: &State) -> Option<Window> {
+    state
+        .monitors
+        .focused()?
+        .focused_workspace()?
+        .focused_container()?
+        .focused_window()
+        .copied()
+}
+
+fn window_node(window: Window, focused: bool) -> Value {
+    // Titles are blanked while privacy mode is on, as they are in komorebi's own notifications
+    let title = if PRIVACY_MODE.load(Ordering::SeqCst) {
+        String::new()
+    } else {
+        window.title().unwrap_or_default()
+    };
+
+    json!({
+        "id": window.hwnd,
+        "type": "con",
+        "name": title,
+        "window": window.hwnd,
+        "window_properties": {
+            "class": window.class().unwrap_or_default(),
+            "instance": window.exe().unwrap_or_default(),
+            "title": title,
+        },
+        "focused": focused,
+        "urgent": false,
+        "layout": "splith",
+        "rect": rect(&WindowsApi::window_rect(window.hwnd).unwrap_or_default()),
+        "nodes": [],
+        "floating_nodes": [],
+    })
+}
+
+fn container_node(container: &Container, focused_hwnd: Option<isize>) -> Value {
+    let nodes = container
+        .windows()
+        .iter()
+        .map(|window| window_node(*window, focused_hwnd == Some(window.hwnd)))
+        .collect::<Vec<_>>();
+
+    // A single window is shown in place of its container, as i3 does
+    match nodes.as_slice() {
+        [node] => node.clone(),
+        _ => json!({
+            "id": container_id(container),
+            "type": "con",
+            "name": null,
+            "focused": false,
+            "urgent": false,
+            "layout": "stacked",
+            "nodes": nodes,
+            "floating_nodes": [],
+        }),
+    }
+}
+
+fn workspace_node(
+    monitor_idx: usize,
+    monitor: &Monitor,
+    workspace_idx: usize,
+    workspace: &Workspace,
+    focused: bool,
+) -> Value {
+    json!({
+        "id": workspace_id(monitor_idx, workspace_idx),
+        "type": "workspace",
+        "num": workspace_idx + 1,
+        "name": workspace
+            .name()
+            .clone()
+            .unwrap_or_else(|| (workspace_idx + 1).to_string()),
+        "visible": monitor.focused_workspace_idx() == workspace_idx,
+        "focused": focused,
+        "urgent": false,
+        "rect": rect(monitor.work_area_size()),
+        "output": monitor.name(),
+    })
+}
+
+fn workspaces(state: &State) -> Vec<Value> {
+    let focused_monitor_idx = state.monitors.focused_idx();
+    let mut workspaces = vec![];
+
+    for (monitor_idx, monitor) in state.monitors.elements().iter().enumerate() {
+        for (workspace_idx, workspace) in monitor.workspaces().iter().enumerate() {
+            let focused = monitor_idx == focused_monitor_idx
+                && monitor.focused_workspace_idx() == workspace_idx;
+
+            workspaces.push(workspace_node(
+                monitor_idx,
+                monitor,
+                workspace_idx,
+                workspace,
+                focused,
+            ));
+        }
+    }
+
+    workspaces
+}
+
+fn outputs(state: &State) -> Vec<Value> {
+    state
+        .monitors
+        .elements()
+        .iter()
+        .enumerate()
+        .map(|(monitor_idx, monitor)| {
+            let current_workspace = monitor.focused_workspace().map(|workspace| {
+                workspace
+                    .name()
+                    .clone()
+                    .unwrap_or_else(|| (monitor.focused_workspace_idx() + 1).to_string())
+            });
+
+            json!({
+                "id": output_id(monitor_idx),
+                "name": monitor.name(),
+                "active": true,
+                "primary": false,
+                "current_workspace": current_workspace,
+                "rect": rect(monitor.size()),
+            })
+        })
+        .collect()
+}
+
+fn tree(state: &State) -> Value {
+    let focused_monitor_idx = state.monitors.focused_idx();
+    let focused_hwnd = focused_window(state).map(|window| window.hwnd);
+
+    let outputs = state
+        .monitors
+        .elements()
+        .iter()
+        .enumerate()
+        .map(|(monitor_idx, monitor)| {
+            let workspaces = monitor
+                .workspaces()
+                .iter()
+                .enumerate()
+                .map(|(workspace_idx, workspace)| {
+                    let focused = monitor_idx == focused_monitor_idx
+                        && monitor.focused_workspace_idx() == workspace_idx;
+
+                    let mut node =
+                        workspace_node(monitor_idx, monitor, workspace_idx, workspace, focused);
+
+                    let nodes = workspace
+                        .monocle_container()
+                        .iter()
+                        .chain(workspace.containers())
+                        .map(|container| container_node(container, focused_hwnd))
+                        .collect::<Vec<_>>();
+
+                    let floating_nodes = workspace
+                        .floating_windows()
+                        .iter()
+                        .map(|window| window_node(*window, focused_hwnd == Some(window.hwnd)))
+                        .collect::<Vec<_>>();
+
+                    node["layout"] = json!("splith");
+                    node["nodes"] = Value::Array(nodes);
+                    node["floating_nodes"] = Value::Array(floating_nodes);
+                    node
+                })
+                .collect::<Vec<_>>();
+
+            json!({
+                "id": output_id(monitor_idx),
+                "type": "output",
+                "name": monitor.name(),
+                "rect": rect(monitor.size()),
+                "nodes": workspaces,
+                "floating_nodes": [],
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "id": 1,
+        "type": "root",
+        "name": "root",
+        "nodes": outputs,
+        "floating_nodes": [],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_round_trip() {
+        let mut buffer = vec![];
+        let Ok(()) = write_message(&mut buffer, GET_TREE, b"[]") else {
+            panic!("could not write message");
+        };
+
+        assert_eq!(&buffer[..6], b"i3-ipc");
+
+        let Ok((message_type, payload)) = read_message(&mut buffer.as_slice()) else {
+            panic!("could not read message");
+        };
+
+        assert_eq!(message_type, GET_TREE);
+        assert_eq!(payload, b"[]");
+    }
+
+    #[test]
+    fn test_read_message_rejects_invalid_magic() {
+        let message = b"sway-ipc\x00\x00\x00\x00\x00\x00";
+        assert!(read_message(&mut message.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_translate_command() {
+        assert!(matches!(
+            translate_command("workspace number 2"),
+            Ok(SocketMessage::FocusWorkspaceNumber(1))
+        ));
+
+        assert!(matches!(
+            translate_command("workspace 3"),
+            Ok(SocketMessage::FocusWorkspaceNumber(2))
+        ));
+
+        assert!(matches!(
+            translate_command(r#"workspace "web stuff""#),
+            Ok(SocketMessage::FocusNamedWorkspace(name)) if name == "web stuff"
+        ));
+
+        assert!(matches!(
+            translate_command("focus left"),
+            Ok(SocketMessage::FocusWindow(OperationDirection::Left))
+        ));
+
+        assert!(matches!(
+            translate_command("move container to workspace number 4"),
+            Ok(SocketMessage::MoveContainerToWorkspaceNumber(3))
+        ));
+
+        assert!(matches!(
+            translate_command("move to workspace code"),
+            Ok(SocketMessage::MoveContainerToNamedWorkspace(name)) if name == "code"
+        ));
+
+        assert!(matches!(
+            translate_command("move down"),
+            Ok(SocketMessage::MoveWindow(OperationDirection::Down))
+        ));
+
+        assert!(matches!(
+            translate_command("kill"),
+            Ok(SocketMessage::Close)
+        ));
+
+        // i3 workspace numbers start at 1
+        assert!(translate_command("workspace number 0").is_err());
+        assert!(translate_command("focus sideways").is_err());
+        assert!(translate_command("exec notepad.exe").is_err());
+    }
+}
@@ -7,16 +7,25 @@ pub mod com;
 pub mod ring;
 pub mod container;
 pub mod core;
+pub mod eviction_manager;
 pub mod focus_manager;
+pub mod i3_ipc;
 pub mod lockable_sequence;
 pub mod monitor;
 pub mod monitor_reconciliator;
+pub mod mouse_hook;
+pub mod notification_manager;
 pub mod process_command;
 pub mod process_event;
 pub mod process_movement;
 pub mod reaper;
+pub mod resize_adjustments;
+pub mod rule_engine;
+pub mod schedule_manager;
+pub mod session;
 pub mod set_window_position;
 pub mod stackbar_manager;
+pub mod state_diff;
 pub mod static_config;
 pub mod styles;
 pub mod theme_manager;
@@ -33,6 +42,7 @@ pub mod workspace;
 use lazy_static::lazy_static;
 use monitor_reconciliator::MonitorNotification;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::fs::File;
 use std::io::Write;
@@ -45,6 +55,7 @@ use std::sync::atomic::AtomicU32;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Instant;
 
 pub use core::*;
 pub use komorebi_themes::colour::*;
@@ -58,10 +69,17 @@ pub use window_manager_event::*;
 pub use windows_api::WindowsApi;
 pub use windows_api::*;
 
+use crate::core::config_generation::FloatingGeometryRule;
+use crate::core::config_generation::HidingBehaviourRule;
 use crate::core::config_generation::IdWithIdentifier;
 use crate::core::config_generation::MatchingRule;
 use crate::core::config_generation::MatchingStrategy;
+use crate::core::config_generation::MinimumTileSizeRule;
+use crate::core::config_generation::NewWindowFocusRule;
 use crate::core::config_generation::WorkspaceMatchingRule;
+use crate::eviction_manager::WindowEviction;
+use crate::schedule_manager::WorkspaceScheduleNotification;
+use crate::state_diff::DiffSubscriber;
 use color_eyre::Result;
 use crossbeam_utils::atomic::AtomicCell;
 use os_info::Version;
@@ -131,12 +149,21 @@ lazy_static! {
     ]));
     static ref OBJECT_NAME_CHANGE_TITLE_IGNORE_LIST: Arc<Mutex<Vec<Regex>>> = Arc::new(Mutex::new(Vec::new()));
     static ref TRANSPARENCY_BLACKLIST: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(Vec::new()));
+    static ref ANIMATION_BLACKLIST: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(Vec::new()));
+    static ref MINIMIZE_TO_TRAY_RULES: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(Vec::new()));
     static ref MONITOR_INDEX_PREFERENCES: Arc<Mutex<HashMap<usize, Rect>>> =
         Arc::new(Mutex::new(HashMap::new()));
     static ref DISPLAY_INDEX_PREFERENCES: Arc<RwLock<HashMap<usize, String>>> =
         Arc::new(RwLock::new(HashMap::new()));
     static ref WORKSPACE_MATCHING_RULES: Arc<Mutex<Vec<WorkspaceMatchingRule>>> =
         Arc::new(Mutex::new(Vec::new()));
+    static ref NEW_WINDOW_FOCUS_RULES: Arc<Mutex<Vec<NewWindowFocusRule>>> =
+        Arc::new(Mutex::new(Vec::new()));
+    static ref MINIMUM_TILE_SIZE_RULES: Arc<Mutex<Vec<MinimumTileSizeRule>>> =
+        Arc::new(Mutex::new(Vec::new()));
+    // The minimum tile width and height resolved for each window from the minimum tile size rules
+    static ref MINIMUM_TILE_SIZES: Arc<Mutex<HashMap<isize, Option<(Option<i32>, Option<i32>)>>>> =
+        Arc::new(Mutex::new(HashMap::new()));
     static ref REGEX_IDENTIFIERS: Arc<Mutex<HashMap<String, Regex>>> =
         Arc::new(Mutex::new(HashMap::new()));
     static ref MANAGE_IDENTIFIERS: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(vec![]));
@@ -159,6 +186,15 @@ lazy_static! {
             matching_strategy: Option::from(MatchingStrategy::Equals),
         })
     ]));
+    static ref SWALLOWING_RULES: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(Vec::new()));
+    static ref MAXIMIZE_MONOCLE_RULES: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(Vec::new()));
+    // Windows matching a maximize monocle rule which are in monocle because they were maximized
+    static ref MAXIMIZED_MONOCLE_HWNDS: Arc<Mutex<HashSet<isize>>> = Arc::new(Mutex::new(HashSet::new()));
+    // Whether each window matches a maximize monocle rule
+    static ref MAXIMIZE_MONOCLE_MATCHES: Arc<Mutex<HashMap<isize, bool>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+    static ref TRANSIENT_DIALOG_RULES: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(Vec::new()));
+    static ref PRIVACY_SENSITIVE_APPLICATIONS: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(Vec::new()));
     static ref SESSION_FLOATING_APPLICATIONS: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(Vec::new()));
     static ref FLOATING_APPLICATIONS: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(vec![
         MatchingRule::Simple(IdWithIdentifier {
@@ -186,14 +222,30 @@ lazy_static! {
         Arc::new(RwLock::new(Vec::new()));
     static ref SUBSCRIPTION_PIPES: Arc<Mutex<HashMap<String, File>>> =
         Arc::new(Mutex::new(HashMap::new()));
+    pub static ref SUBSCRIPTION_PIPE_OPTIONS: Arc<Mutex<HashMap<String, SubscribeOptions>>> =
+        Arc::new(Mutex::new(HashMap::new()));
     pub static ref SUBSCRIPTION_SOCKETS: Arc<Mutex<HashMap<String, PathBuf>>> =
         Arc::new(Mutex::new(HashMap::new()));
+    pub static ref SUBSCRIPTION_DIFF_SOCKETS: Arc<Mutex<HashMap<String, DiffSubscriber>>> =
+        Arc::new(Mutex::new(HashMap::new()));
     pub static ref SUBSCRIPTION_SOCKET_OPTIONS: Arc<Mutex<HashMap<String, SubscribeOptions>>> =
         Arc::new(Mutex::new(HashMap::new()));
     static ref TCP_CONNECTIONS: Arc<Mutex<HashMap<String, TcpStream>>> =
         Arc::new(Mutex::new(HashMap::new()));
     static ref HIDING_BEHAVIOUR: Arc<Mutex<HidingBehaviour>> =
         Arc::new(Mutex::new(HidingBehaviour::Cloak));
+    static ref HIDING_BEHAVIOUR_RULES: Arc<Mutex<Vec<HidingBehaviourRule>>> =
+        Arc::new(Mutex::new(Vec::new()));
+    // The behaviour of the hiding behaviour rule matched by each window, if any
+    static ref HIDING_BEHAVIOURS: Arc<Mutex<HashMap<isize, Option<HidingBehaviour>>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+    static ref FLOATING_GEOMETRY_RULES: Arc<Mutex<Vec<FloatingGeometryRule>>> =
+        Arc::new(Mutex::new(Vec::new()));
+    static ref FOCUS_FOLLOWS_MOUSE_IGNORE_RULES: Arc<Mutex<Vec<MatchingRule>>> =
+        Arc::new(Mutex::new(Vec::new()));
+    // Positions of windows hidden with HidingBehaviour::OffScreen, to move them back on restore
+    static ref OFF_SCREEN_RECTS: Arc<Mutex<HashMap<isize, Rect>>> =
+        Arc::new(Mutex::new(HashMap::new()));
     pub static ref HOME_DIR: PathBuf = {
         std::env::var("KOMOREBI_CONFIG_HOME").map_or_else(|_| dirs::home_dir().expect("there is no home directory"), |home_path| {
             let home = home_path.replace_env();
@@ -237,6 +289,14 @@ lazy_static! {
     static ref FLOATING_WINDOW_TOGGLE_ASPECT_RATIO: Arc<Mutex<AspectRatio>> = Arc::new(Mutex::new(AspectRatio::Predefined(PredefinedAspectRatio::Widescreen)));
 
     static ref CURRENT_VIRTUAL_DESKTOP: Arc<Mutex<Option<Vec<u8>>>> = Arc::new(Mutex::new(None));
+
+    // The static configuration file which was most recently loaded, and when it was loaded as a
+    // Unix timestamp in seconds
+    pub static ref CONFIGURATION_PATH: Arc<Mutex<Option<PathBuf>>> = Arc::new(Mutex::new(None));
+    pub static ref CONFIGURATION_LOADED_AT: Arc<Mutex<Option<u64>>> = Arc::new(Mutex::new(None));
+
+    // When komorebi was started, used to report its uptime in response to a Ping
+    pub static ref STARTED_AT: Instant = Instant::now();
 }
 
 pub static DEFAULT_WORKSPACE_PADDING: AtomicI32 = AtomicI32::new(10);
@@ -247,12 +307,27 @@ pub static CUSTOM_FFM: AtomicBool = AtomicBool::new(false);
 pub static SESSION_ID: AtomicU32 = AtomicU32::new(0);
 
 pub static REMOVE_TITLEBARS: AtomicBool = AtomicBool::new(false);
+pub static DYNAMIC_WORKSPACES: AtomicBool = AtomicBool::new(false);
+// The work area offsets of the monitors, which are set by the bars, are not applied while the
+// bars have been hidden with `komorebic toggle-bar`
+pub static BARS_HIDDEN: AtomicBool = AtomicBool::new(false);
+
+// Window titles are blanked in notifications while privacy mode has been turned on with
+// `komorebic privacy-mode`
+pub static PRIVACY_MODE: AtomicBool = AtomicBool::new(false);
 
 pub static SLOW_APPLICATION_COMPENSATION_TIME: AtomicU64 = AtomicU64::new(20);
+pub static NOTIFICATION_COALESCING_WINDOW: AtomicU64 = AtomicU64::new(0);
 
 pub static WINDOW_HANDLING_BEHAVIOUR: AtomicCell<WindowHandlingBehaviour> =
     AtomicCell::new(WindowHandlingBehaviour::Sync);
 
+pub static MINIMUM_TILE_SIZE_BEHAVIOUR: AtomicCell<MinimumTileSizeBehaviour> =
+    AtomicCell::new(MinimumTileSizeBehaviour::Reflow);
+
+pub static NEW_WINDOW_FOCUS_BEHAVIOUR: AtomicCell<NewWindowFocusBehaviour> =
+    AtomicCell::new(NewWindowFocusBehaviour::Always);
+
 shadow_rs::shadow!(build);
 
 /// A trait for types that can be marked as locked or unlocked.
@@ -315,6 +390,255 @@ pub enum NotificationEvent {
     Socket(SocketMessage),
     Monitor(MonitorNotification),
     VirtualDesktop(VirtualDesktopNotification),
+    WindowEviction(WindowEviction),
+    WorkspaceSchedule(WorkspaceScheduleNotification),
+}
+
+impl From<&SocketMessage> for SubscriptionEvent {
+    fn from(value: &SocketMessage) -> Self {
+        match value {
+            SocketMessage::FocusWindow(_)
+            | SocketMessage::CycleFocusWindow(_)
+            | SocketMessage::FocusStackWindow(_)
+            | SocketMessage::FocusHwnd(_)
+            | SocketMessage::FocusLastWindow
+            | SocketMessage::JumpToMark(_)
+            | SocketMessage::CycleStack(_)
+            | SocketMessage::ForceFocus
+            | SocketMessage::EagerFocus(_) => Self::FocusChange,
+            SocketMessage::CycleFocusWorkspace(_)
+            | SocketMessage::CycleFocusEmptyWorkspace(_)
+            | SocketMessage::FocusLastWorkspace
+            | SocketMessage::FocusWorkspaceNumber(_)
+            | SocketMessage::FocusWorkspaceNumbers(_)
+            | SocketMessage::FocusMonitorWorkspaceNumber(_, _)
+            | SocketMessage::FocusNamedWorkspace(_)
+            | SocketMessage::CloseWorkspace
+            | SocketMessage::NewWorkspace
+            | SocketMessage::EnsureWorkspaces(_, _)
+            | SocketMessage::EnsureNamedWorkspaces(_, _)
+            | SocketMessage::WorkspaceName(_, _, _)
+            | SocketMessage::ToggleWorkspaceLayer
+            | SocketMessage::CycleMoveWorkspaceToMonitor(_)
+            | SocketMessage::MoveWorkspaceToMonitorNumber(_)
+            | SocketMessage::SwapWorkspacesToMonitorNumber(_)
+            | SocketMessage::CloneWorkspaceToMonitorNumber(_)
+            | SocketMessage::SwapWorkspaces(_, _, _)
+            | SocketMessage::AdjustContainerPadding(_, _)
+            | SocketMessage::AdjustWorkspacePadding(_, _)
+            | SocketMessage::ToggleWorkspaceWindowContainerBehaviour
+            | SocketMessage::ToggleWorkspaceFloatOverride
+            | SocketMessage::ToggleSmartGaps
+            | SocketMessage::ToggleWindowBasedWorkAreaOffset
+            | SocketMessage::ReserveTile(_, _)
+            | SocketMessage::ClearReservedTiles
+            | SocketMessage::ContainerPadding(_, _, _)
+            | SocketMessage::NamedWorkspaceContainerPadding(_, _)
+            | SocketMessage::FocusedWorkspaceContainerPadding(_)
+            | SocketMessage::WorkspacePadding(_, _, _)
+            | SocketMessage::NamedWorkspacePadding(_, _)
+            | SocketMessage::FocusedWorkspacePadding(_)
+            | SocketMessage::LoadSession(_) => Self::WorkspaceChange,
+            SocketMessage::CycleFocusMonitor(_)
+            | SocketMessage::FocusMonitorNumber(_)
+            | SocketMessage::FocusMonitorAtCursor
+            | SocketMessage::MonitorWorkAreaOffset(_, _)
+            | SocketMessage::WorkAreaOffset(_)
+            | SocketMessage::MonitorIndexPreference(_, _, _, _, _)
+            | SocketMessage::DisplayIndexPreference(_, _)
+            | SocketMessage::PersistMonitorIndices => Self::MonitorChange,
+            SocketMessage::ChangeLayout(_)
+            | SocketMessage::CycleLayout(_)
+            | SocketMessage::ChangeLayoutCustom(_)
+            | SocketMessage::FlipLayout(_)
+            | SocketMessage::ScrollingLayoutColumns(_)
+            | SocketMessage::WorkspaceLayout(_, _, _)
+            | SocketMessage::NamedWorkspaceLayout(_, _)
+            | SocketMessage::WorkspaceLayoutCustom(_, _, _)
+            | SocketMessage::NamedWorkspaceLayoutCustom(_, _)
+            | SocketMessage::ToggleTiling
+            | SocketMessage::WorkspaceTiling(_, _, _)
+            | SocketMessage::NamedWorkspaceTiling(_, _)
+            | SocketMessage::Retile
+            | SocketMessage::RetileWithResizeDimensions
+            | SocketMessage::ResizeSplit(_, _)
+            | SocketMessage::Split(_)
+            | SocketMessage::WorkspaceLayoutRule(_, _, _, _)
+            | SocketMessage::NamedWorkspaceLayoutRule(_, _, _)
+            | SocketMessage::WorkspaceLayoutCustomRule(_, _, _, _)
+            | SocketMessage::NamedWorkspaceLayoutCustomRule(_, _, _)
+            | SocketMessage::ClearWorkspaceLayoutRules(_, _)
+            | SocketMessage::ClearNamedWorkspaceLayoutRules(_)
+            | SocketMessage::RetileMonitor(_)
+            | SocketMessage::RetileWorkspace(_, _)
+            | SocketMessage::RetileNamedWorkspace(_)
+            | SocketMessage::QuickLoad
+            | SocketMessage::Load(_) => Self::LayoutChange,
+            SocketMessage::MoveWindow(_)
+            | SocketMessage::CycleMoveWindow(_)
+            | SocketMessage::StackWindow(_)
+            | SocketMessage::UnstackWindow
+            | SocketMessage::CycleStackIndex(_)
+            | SocketMessage::MoveStackWindow(_, _)
+            | SocketMessage::StackAll
+            | SocketMessage::UnstackAll
+            | SocketMessage::ResizeWindowEdge(_, _)
+            | SocketMessage::ResizeWindowAxis(_, _)
+            | SocketMessage::MoveContainerToLastWorkspace
+            | SocketMessage::SendContainerToLastWorkspace
+            | SocketMessage::MoveContainerToMonitorNumber(_)
+            | SocketMessage::CycleMoveContainerToMonitor(_)
+            | SocketMessage::MoveContainerToWorkspaceNumber(_)
+            | SocketMessage::MoveContainerToNamedWorkspace(_)
+            | SocketMessage::CycleMoveContainerToWorkspace(_)
+            | SocketMessage::SendContainerToMonitorNumber(_)
+            | SocketMessage::CycleSendContainerToMonitor(_)
+            | SocketMessage::SendContainerToWorkspaceNumber(_)
+            | SocketMessage::CycleSendContainerToWorkspace(_)
+            | SocketMessage::SendContainerToMonitorWorkspaceNumber(_, _)
+            | SocketMessage::MoveContainerToMonitorWorkspaceNumber(_, _)
+            | SocketMessage::SendContainerToNamedWorkspace(_)
+            | SocketMessage::Close
+            | SocketMessage::Minimize
+            | SocketMessage::CloseWindow(_)
+            | SocketMessage::MinimizeWindow(_)
+            | SocketMessage::RestoreHidden
+            | SocketMessage::Promote
+            | SocketMessage::PromoteFocus
+            | SocketMessage::PromoteWindow(_)
+            | SocketMessage::LockMonitorWorkspaceContainer(_, _, _)
+            | SocketMessage::UnlockMonitorWorkspaceContainer(_, _, _)
+            | SocketMessage::ToggleLock
+            | SocketMessage::ToggleFloat
+            | SocketMessage::ToggleAlwaysOnTop
+            | SocketMessage::ToggleMonocle
+            | SocketMessage::ToggleMaximize
+            | SocketMessage::ToggleZoom
+            | SocketMessage::ManageFocusedWindow
+            | SocketMessage::UnmanageFocusedWindow
+            | SocketMessage::Mark(_)
+            | SocketMessage::Unmark(_)
+            | SocketMessage::ResizeWindowPercent(_, _)
+            | SocketMessage::EnforceWorkspaceRules
+            | SocketMessage::SessionFloatRule => Self::ContainerChange,
+            SocketMessage::ReloadConfiguration
+            | SocketMessage::ReplaceConfiguration(_)
+            | SocketMessage::ReloadStaticConfiguration(_)
+            | SocketMessage::CompleteConfiguration
+            | SocketMessage::Theme(_)
+            | SocketMessage::PrivacyMode(_)
+            | SocketMessage::ToggleWindowContainerBehaviour
+            | SocketMessage::ToggleFloatOverride
+            | SocketMessage::WindowHidingBehaviour(_)
+            | SocketMessage::ToggleCrossMonitorMoveBehaviour
+            | SocketMessage::CrossMonitorMoveBehaviour(_)
+            | SocketMessage::UnmanagedWindowOperationBehaviour(_)
+            | SocketMessage::WatchConfiguration(_)
+            | SocketMessage::AltFocusHack(_)
+            | SocketMessage::Animation(_, _)
+            | SocketMessage::AnimationDuration(_, _)
+            | SocketMessage::AnimationFps(_)
+            | SocketMessage::AnimationMaxConcurrent(_)
+            | SocketMessage::AnimationStyle(_, _)
+            | SocketMessage::Border(_)
+            | SocketMessage::BorderColour(_, _, _, _)
+            | SocketMessage::BorderStyle(_)
+            | SocketMessage::BorderWidth(_)
+            | SocketMessage::BorderOffset(_)
+            | SocketMessage::BorderImplementation(_)
+            | SocketMessage::Transparency(_)
+            | SocketMessage::ToggleTransparency
+            | SocketMessage::TransparencyAlpha(_)
+            | SocketMessage::InvisibleBorders(_)
+            | SocketMessage::StackbarMode(_)
+            | SocketMessage::StackbarLabel(_)
+            | SocketMessage::StackbarFocusedTextColour(_, _, _)
+            | SocketMessage::StackbarUnfocusedTextColour(_, _, _)
+            | SocketMessage::StackbarBackgroundColour(_, _, _)
+            | SocketMessage::StackbarHeight(_)
+            | SocketMessage::StackbarTabWidth(_)
+            | SocketMessage::StackbarFontSize(_)
+            | SocketMessage::StackbarFontFamily(_)
+            | SocketMessage::ToggleBar
+            | SocketMessage::ResizeDelta(_)
+            | SocketMessage::InitialWorkspaceRule(_, _, _, _)
+            | SocketMessage::InitialNamedWorkspaceRule(_, _, _)
+            | SocketMessage::WorkspaceRule(_, _, _, _)
+            | SocketMessage::NamedWorkspaceRule(_, _, _)
+            | SocketMessage::ClearWorkspaceRules(_, _)
+            | SocketMessage::ClearNamedWorkspaceRules(_)
+            | SocketMessage::ClearAllWorkspaceRules
+            | SocketMessage::ClearSessionFloatRules
+            | SocketMessage::IgnoreRule(_, _)
+            | SocketMessage::ManageRule(_, _)
+            | SocketMessage::IdentifyObjectNameChangeApplication(_, _)
+            | SocketMessage::IdentifyTrayApplication(_, _)
+            | SocketMessage::IdentifyLayeredApplication(_, _)
+            | SocketMessage::IdentifyBorderOverflowApplication(_, _)
+            | SocketMessage::FocusFollowsMouse(_, _)
+            | SocketMessage::ToggleFocusFollowsMouse(_)
+            | SocketMessage::MouseFollowsFocus(_)
+            | SocketMessage::ToggleMouseFollowsFocus
+            | SocketMessage::RemoveTitleBar(_, _)
+            | SocketMessage::ToggleTitleBars => Self::ConfigurationChange,
+            SocketMessage::NoMouseFollowsFocus(message) => Self::from(message.as_ref()),
+            SocketMessage::Stop
+            | SocketMessage::StopIgnoreRestore
+            | SocketMessage::TogglePause
+            | SocketMessage::QuickSave
+            | SocketMessage::Save(_)
+            | SocketMessage::SaveSession(_)
+            | SocketMessage::SessionFloatRules
+            | SocketMessage::State
+            | SocketMessage::GlobalState
+            | SocketMessage::Ping
+            | SocketMessage::VisibleWindows
+            | SocketMessage::MonitorInformation
+            | SocketMessage::Query(_)
+            | SocketMessage::AddSubscriberSocket(_)
+            | SocketMessage::AddSubscriberSocketWithOptions(_, _)
+            | SocketMessage::RemoveSubscriberSocket(_)
+            | SocketMessage::SubscribeSocketDiff(_)
+            | SocketMessage::AddSubscriberPipe(_)
+            | SocketMessage::AddSubscriberPipeWithOptions(_, _)
+            | SocketMessage::RemoveSubscriberPipe(_)
+            | SocketMessage::ApplicationSpecificConfigurationSchema
+            | SocketMessage::NotificationSchema
+            | SocketMessage::SocketSchema
+            | SocketMessage::StaticConfigSchema
+            | SocketMessage::GenerateStaticConfig
+            | SocketMessage::DebugWindow(_) => Self::Other,
+        }
+    }
+}
+
+impl From<&NotificationEvent> for SubscriptionEvent {
+    fn from(value: &NotificationEvent) -> Self {
+        match value {
+            NotificationEvent::WindowManager(event) => match event {
+                WindowManagerEvent::FocusChange(_, _) => Self::FocusChange,
+                WindowManagerEvent::Destroy(_, _)
+                | WindowManagerEvent::Hide(_, _)
+                | WindowManagerEvent::Cloak(_, _)
+                | WindowManagerEvent::Minimize(_, _)
+                | WindowManagerEvent::Show(_, _)
+                | WindowManagerEvent::Uncloak(_, _)
+                | WindowManagerEvent::MoveResizeStart(_, _)
+                | WindowManagerEvent::MoveResizeEnd(_, _)
+                | WindowManagerEvent::MaximizeChange(_, _)
+                | WindowManagerEvent::MouseCapture(_, _)
+                | WindowManagerEvent::Manage(_)
+                | WindowManagerEvent::Unmanage(_)
+                | WindowManagerEvent::Raise(_)
+                | WindowManagerEvent::TitleUpdate(_, _) => Self::WindowChange,
+            },
+            NotificationEvent::Socket(message) => Self::from(message),
+            NotificationEvent::Monitor(_) => Self::MonitorChange,
+            NotificationEvent::VirtualDesktop(_) => Self::Other,
+            NotificationEvent::WindowEviction(_) => Self::WindowChange,
+            NotificationEvent::WorkspaceSchedule(_) => Self::WorkspaceChange,
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq)]
@@ -332,30 +656,96 @@ pub struct Notification {
 }
 
 pub fn notify_subscribers(notification: Notification, state_has_been_modified: bool) -> Result<()> {
-    let is_override_event = matches!(
+    state_diff::push_diff_notifications(&notification.event, &notification.state)?;
+    i3_ipc::push_events(&notification.event, &notification.state)?;
+
+    let is_subscription_event = matches!(
         notification.event,
         NotificationEvent::Socket(SocketMessage::AddSubscriberSocket(_))
             | NotificationEvent::Socket(SocketMessage::AddSubscriberSocketWithOptions(_, _))
+            | NotificationEvent::Socket(SocketMessage::AddSubscriberPipeWithOptions(_, _))
             | NotificationEvent::Socket(SocketMessage::Theme(_))
             | NotificationEvent::Socket(SocketMessage::ReloadStaticConfiguration(_))
-            | NotificationEvent::WindowManager(WindowManagerEvent::TitleUpdate(_, _))
-            | NotificationEvent::WindowManager(WindowManagerEvent::Show(_, _))
+    );
+
+    // New subscribers must receive an initial state regardless of their event filters
+    let event_kind = match notification.event {
+        NotificationEvent::Socket(SocketMessage::AddSubscriberSocket(_))
+        | NotificationEvent::Socket(SocketMessage::AddSubscriberSocketWithOptions(_, _))
+        | NotificationEvent::Socket(SocketMessage::AddSubscriberPipeWithOptions(_, _)) => None,
+        ref event => Some(SubscriptionEvent::from(event)),
+    };
+
+    let is_override_event = is_subscription_event
+        || matches!(
+            notification.event,
+            NotificationEvent::WindowManager(WindowManagerEvent::TitleUpdate(_, _))
+                | NotificationEvent::WindowManager(WindowManagerEvent::Show(_, _))
+                | NotificationEvent::WindowManager(WindowManagerEvent::Uncloak(_, _))
+        );
+
+    let is_cloak_event = matches!(
+        notification.event,
+        NotificationEvent::WindowManager(WindowManagerEvent::Cloak(_, _))
             | NotificationEvent::WindowManager(WindowManagerEvent::Uncloak(_, _))
     );
 
-    let notification = &serde_json::to_string(&notification)?;
+    // The state is only serialized once, both to compare it against the last pushed state and
+    // to build the notification, which serializes to the same fields as `Notification`
+    let state = serde_json::to_string(&notification.state)?;
+    let event = serde_json::to_string(&notification.event)?;
+
+    notification_manager::send_notification(notification_manager::Notification {
+        sequence: notification_manager::next_sequence(),
+        notification: format!(r#"{{"event":{event},"state":{state}}}"#),
+        state,
+        event_kind,
+        state_has_been_modified,
+        is_subscription_event,
+        is_override_event,
+        is_cloak_event,
+    });
+
+    Ok(())
+}
+
+/// Whether a subscriber with `options` should be sent a notification for an event of
+/// `event_kind`, where `None` is an event which every subscriber is sent
+fn wants_notification(
+    options: Option<&SubscribeOptions>,
+    event_kind: Option<SubscriptionEvent>,
+    state_has_been_modified: bool,
+    is_override_event: bool,
+) -> bool {
+    options.is_none_or(|options| {
+        let wants_event = options
+            .events
+            .as_ref()
+            .zip(event_kind)
+            .is_none_or(|(events, event_kind)| events.contains(&event_kind));
+
+        wants_event
+            && (!options.filter_state_changes || state_has_been_modified || is_override_event)
+    })
+}
+
+pub(crate) fn push_notification(
+    notification: &str,
+    event_kind: Option<SubscriptionEvent>,
+    state_has_been_modified: bool,
+    is_override_event: bool,
+) -> Result<()> {
     let mut stale_sockets = vec![];
     let mut sockets = SUBSCRIPTION_SOCKETS.lock();
     let options = SUBSCRIPTION_SOCKET_OPTIONS.lock();
 
     for (socket, path) in &mut *sockets {
-        let apply_state_filter = (*options)
-            .get(socket)
-            .copied()
-            .unwrap_or_default()
-            .filter_state_changes;
-
-        if !apply_state_filter || state_has_been_modified || is_override_event {
+        if wants_notification(
+            options.get(socket),
+            event_kind,
+            state_has_been_modified,
+            is_override_event,
+        ) {
             match UnixStream::connect(path) {
                 Ok(mut stream) => {
                     tracing::debug!("pushed notification to subscriber: {socket}");
@@ -382,7 +772,17 @@ pub fn notify_subscribers(notification: Notification, state_has_been_modified: b
 
     let mut stale_pipes = vec![];
     let mut pipes = SUBSCRIPTION_PIPES.lock();
+    let mut pipe_options = SUBSCRIPTION_PIPE_OPTIONS.lock();
     for (subscriber, pipe) in &mut *pipes {
+        if !wants_notification(
+            pipe_options.get(subscriber),
+            event_kind,
+            state_has_been_modified,
+            is_override_event,
+        ) {
+            continue;
+        }
+
         match writeln!(pipe, "{notification}") {
             Ok(()) => {
                 tracing::debug!("pushed notification to subscriber: {subscriber}");
@@ -407,6 +807,7 @@ pub fn notify_subscribers(notification: Notification, state_has_been_modified: b
     for subscriber in stale_pipes {
         tracing::warn!("removing stale subscription: {}", subscriber);
         pipes.remove(&subscriber);
+        pipe_options.remove(&subscriber);
     }
 
     Ok(())
@@ -36,14 +36,22 @@ use tracing_subscriber::EnvFilter;
 use uds_windows::UnixStream;
 
 use komorebi::border_manager;
+use komorebi::eviction_manager;
 use komorebi::focus_manager;
+use komorebi::i3_ipc;
 use komorebi::load_configuration;
 use komorebi::monitor_reconciliator;
+use komorebi::mouse_hook;
+use komorebi::notification_manager;
 use komorebi::process_command::listen_for_commands;
 use komorebi::process_command::listen_for_commands_tcp;
 use komorebi::process_event::listen_for_events;
 use komorebi::process_movement::listen_for_movements;
 use komorebi::reaper;
+use komorebi::resize_adjustments;
+use komorebi::resize_adjustments::ResizeAdjustments;
+use komorebi::schedule_manager;
+use komorebi::session::Session;
 use komorebi::stackbar_manager;
 use komorebi::static_config::StaticConfig;
 use komorebi::theme_manager;
@@ -57,6 +65,7 @@ use komorebi::DATA_DIR;
 use komorebi::HOME_DIR;
 use komorebi::INITIAL_CONFIGURATION_LOADED;
 use komorebi::SESSION_ID;
+use komorebi::STARTED_AT;
 
 fn setup(log_level: LogLevel) -> Result<(WorkerGuard, WorkerGuard)> {
     if std::env::var("RUST_LIB_BACKTRACE").is_err() {
@@ -176,6 +185,9 @@ struct Opts {
     /// Start a TCP server on the given port to allow the direct sending of SocketMessages
     #[clap(short, long)]
     tcp_port: Option<usize>,
+    /// Start a server which speaks the i3 IPC protocol to allow the use of i3 and sway clients
+    #[clap(long)]
+    i3_ipc: bool,
     /// Path to a static configuration JSON file
     #[clap(short, long)]
     #[clap(value_parser = replace_env_in_path)]
@@ -191,6 +203,8 @@ struct Opts {
 #[tracing::instrument]
 #[allow(clippy::cognitive_complexity)]
 fn main() -> Result<()> {
+    lazy_static::initialize(&STARTED_AT);
+
     let opts: Opts = Opts::parse();
     CUSTOM_FFM.store(opts.focus_follows_mouse, Ordering::SeqCst);
 
@@ -318,15 +332,57 @@ fn main() -> Result<()> {
         }
     }
 
+    let session_file = Session::default_path();
+
+    if !opts.clean_state && session_file.is_file() {
+        match Session::load(&session_file) {
+            Ok(session) => {
+                if let Err(error) = wm.lock().restore_session(&session) {
+                    tracing::warn!("could not restore session: {error}");
+                }
+            }
+            Err(error) => {
+                tracing::warn!(
+                    "cannot restore session from {}: {error}",
+                    session_file.display()
+                );
+            }
+        }
+    }
+
+    let resize_adjustments_file = ResizeAdjustments::default_path();
+
+    if !opts.clean_state && resize_adjustments_file.is_file() {
+        match ResizeAdjustments::load(&resize_adjustments_file) {
+            Ok(adjustments) => {
+                if let Err(error) = wm.lock().restore_resize_adjustments(adjustments) {
+                    tracing::warn!("could not restore resize adjustments: {error}");
+                }
+            }
+            Err(error) => {
+                tracing::warn!(
+                    "cannot restore resize adjustments from {}: {error}",
+                    resize_adjustments_file.display()
+                );
+            }
+        }
+    }
+
     wm.lock().retile_all(false)?;
 
     border_manager::listen_for_notifications(wm.clone());
     stackbar_manager::listen_for_notifications(wm.clone());
     transparency_manager::listen_for_notifications(wm.clone());
     monitor_reconciliator::listen_for_notifications(wm.clone())?;
+    monitor_reconciliator::taskbar::listen_for_changes();
+    resize_adjustments::listen_for_changes();
     reaper::listen_for_notifications(wm.clone(), wm.lock().known_hwnds.clone());
     focus_manager::listen_for_notifications(wm.clone());
+    eviction_manager::listen_for_notifications(wm.clone());
     theme_manager::listen_for_notifications();
+    notification_manager::listen_for_notifications();
+    schedule_manager::listen_for_schedules(wm.clone());
+    mouse_hook::listen_for_drags(wm.clone());
 
     listen_for_commands(wm.clone());
 
@@ -334,6 +390,10 @@ fn main() -> Result<()> {
         listen_for_commands_tcp(wm.clone(), port);
     }
 
+    if opts.i3_ipc {
+        i3_ipc::listen_for_clients(wm.clone())?;
+    }
+
     listen_for_events(wm.clone());
 
     if CUSTOM_FFM.load(Ordering::SeqCst) {
@@ -372,6 +432,13 @@ fn main() -> Result<()> {
         }
     }
 
+    let diff_sockets = komorebi::SUBSCRIPTION_DIFF_SOCKETS.lock();
+    for subscriber in (*diff_sockets).values() {
+        if let Ok(stream) = UnixStream::connect(subscriber.path()) {
+            stream.shutdown(Shutdown::Both)?;
+        }
+    }
+
     let socket = DATA_DIR.join("komorebi.sock");
     let _ = std::fs::remove_file(socket);
 
@@ -27,9 +27,12 @@ use crate::FloatingLayerBehaviour;
 use crate::Layout;
 use crate::OperationDirection;
 use crate::Wallpaper;
+use crate::Window;
 use crate::WindowsApi;
+use crate::BARS_HIDDEN;
 use crate::DEFAULT_CONTAINER_PADDING;
 use crate::DEFAULT_WORKSPACE_PADDING;
+use crate::DYNAMIC_WORKSPACES;
 
 #[derive(
     Debug, Clone, Serialize, Deserialize, Getters, CopyGetters, MutGetters, Setters, PartialEq,
@@ -56,6 +59,10 @@ pub struct Monitor {
     pub window_based_work_area_offset: Option<Rect>,
     #[getset(get_copy = "pub", set = "pub")]
     pub window_based_work_area_offset_limit: isize,
+    /// Space taken up by a shown auto-hiding taskbar, on top of the work area offset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[getset(get_copy = "pub", set = "pub")]
+    pub taskbar_work_area_offset: Option<Rect>,
     pub workspaces: Ring<Workspace>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[getset(get_copy = "pub", set = "pub")]
@@ -70,6 +77,14 @@ pub struct Monitor {
     pub wallpaper: Option<Wallpaper>,
     #[getset(get_copy = "pub", set = "pub")]
     pub floating_layer_behaviour: Option<FloatingLayerBehaviour>,
+    /// Windows pinned above the tiling layer of every workspace on this monitor
+    #[serde(default, skip_serializing_if = "VecDeque::is_empty")]
+    #[getset(get = "pub", get_mut = "pub")]
+    pub always_on_top: VecDeque<Window>,
+    /// Whether the focused window is one of the `always_on_top` windows
+    #[serde(default)]
+    #[getset(get_copy = "pub", set = "pub")]
+    pub always_on_top_focused: bool,
 }
 
 impl_ring_elements!(Monitor, Workspace);
@@ -120,6 +135,7 @@ pub fn new(
         work_area_offset: None,
         window_based_work_area_offset: None,
         window_based_work_area_offset_limit: 1,
+        taskbar_work_area_offset: None,
         workspaces,
         last_focused_workspace: None,
         workspace_names: HashMap::default(),
@@ -127,6 +143,8 @@ pub fn new(
         workspace_padding: None,
         wallpaper: None,
         floating_layer_behaviour: None,
+        always_on_top: VecDeque::new(),
+        always_on_top_focused: false,
     }
 }
 
@@ -163,6 +181,7 @@ impl Monitor {
             work_area_offset: None,
             window_based_work_area_offset: None,
             window_based_work_area_offset_limit: 0,
+            taskbar_work_area_offset: None,
             workspaces: Default::default(),
             last_focused_workspace: None,
             workspace_names: Default::default(),
@@ -170,6 +189,8 @@ impl Monitor {
             workspace_padding: None,
             wallpaper: None,
             floating_layer_behaviour: None,
+            always_on_top: VecDeque::new(),
+            always_on_top_focused: false,
         }
     }
 
@@ -201,9 +222,38 @@ impl Monitor {
             }
         }
 
+        // Every change of the focused workspace ends up here, so this is where workspaces which
+        // have been left empty at the end of the list are cleaned up
+        if DYNAMIC_WORKSPACES.load(Ordering::SeqCst) {
+            self.remove_trailing_empty_workspaces();
+        }
+
         Ok(())
     }
 
+    /// The work area offset of this monitor, unless the bars which usually set it are hidden
+    fn applied_work_area_offset(&self) -> Option<Rect> {
+        self.work_area_offset
+            .filter(|_| !BARS_HIDDEN.load(Ordering::SeqCst))
+    }
+
+    /// The work area offset of this monitor, falling back to `offset`, with the space taken up by
+    /// a shown auto-hiding taskbar added on top
+    fn combined_work_area_offset(&self, offset: Option<Rect>) -> Option<Rect> {
+        match (
+            self.applied_work_area_offset().or(offset),
+            self.taskbar_work_area_offset,
+        ) {
+            (Some(offset), Some(taskbar)) => Option::from(Rect {
+                left: offset.left + taskbar.left,
+                top: offset.top + taskbar.top,
+                right: offset.right + taskbar.right,
+                bottom: offset.bottom + taskbar.bottom,
+            }),
+            (offset, taskbar) => offset.or(taskbar),
+        }
+    }
+
     /// Updates the `globals` field of all workspaces
     pub fn update_workspaces_globals(&mut self, offset: Option<Rect>) {
         let container_padding = self
@@ -223,7 +273,7 @@ impl Monitor {
             }
         };
         let work_area = *self.work_area_size();
-        let work_area_offset = self.work_area_offset.or(offset);
+        let work_area_offset = self.combined_work_area_offset(offset);
         let window_based_work_area_offset = self.window_based_work_area_offset();
         let window_based_work_area_offset_limit = self.window_based_work_area_offset_limit();
         let floating_layer_behaviour = self.floating_layer_behaviour();
@@ -262,7 +312,7 @@ impl Monitor {
             }
         };
         let work_area = *self.work_area_size();
-        let work_area_offset = self.work_area_offset.or(offset);
+        let work_area_offset = self.combined_work_area_offset(offset);
         let window_based_work_area_offset = self.window_based_work_area_offset();
         let window_based_work_area_offset_limit = self.window_based_work_area_offset_limit();
         let floating_layer_behaviour = self.floating_layer_behaviour();
@@ -381,6 +431,47 @@ impl Monitor {
         Ok(())
     }
 
+    /// Swaps the workspaces at `first` and `second`, keeping the same workspace focused
+    pub fn swap_workspaces(&mut self, first: usize, second: usize) -> Result<()> {
+        let len = self.workspaces().len();
+        if first >= len || second >= len {
+            bail!("there is no workspace at this index");
+        }
+
+        if first == second {
+            return Ok(());
+        }
+
+        self.workspaces_mut().swap(first, second);
+
+        let swapped = |idx: usize| {
+            if idx == first {
+                second
+            } else if idx == second {
+                first
+            } else {
+                idx
+            }
+        };
+
+        let focused_workspace_idx = self.focused_workspace_idx();
+        self.workspaces.focus(swapped(focused_workspace_idx));
+        self.last_focused_workspace = self.last_focused_workspace.map(swapped);
+
+        let first_name = self.workspace_names.remove(&first);
+        let second_name = self.workspace_names.remove(&second);
+
+        if let Some(name) = first_name {
+            self.workspace_names.insert(second, name);
+        }
+
+        if let Some(name) = second_name {
+            self.workspace_names.insert(first, name);
+        }
+
+        Ok(())
+    }
+
     pub fn remove_workspace_by_idx(&mut self, idx: usize) -> Option<Workspace> {
         if idx < self.workspaces().len() {
             return self.workspaces_mut().remove(idx);
@@ -402,6 +493,34 @@ impl Monitor {
         }
     }
 
+    /// Removes empty, unnamed and unconfigured workspaces from the end of the workspace list. The
+    /// focused workspace and any workspaces before it are never removed, so that the indices of
+    /// the remaining workspaces don't change.
+    pub fn remove_trailing_empty_workspaces(&mut self) {
+        let focused_idx = self.focused_workspace_idx();
+
+        while self.workspaces().len() > focused_idx + 1 {
+            let removable = self.workspaces().back().is_some_and(|workspace| {
+                workspace.is_empty()
+                    && workspace.name().is_none()
+                    && workspace.workspace_config().is_none()
+            });
+
+            if !removable {
+                break;
+            }
+
+            self.workspaces_mut().pop_back();
+        }
+
+        if self
+            .last_focused_workspace()
+            .is_some_and(|idx| idx >= self.workspaces().len())
+        {
+            self.set_last_focused_workspace(None);
+        }
+    }
+
     pub fn remove_workspaces(&mut self) -> VecDeque<Workspace> {
         self.workspaces_mut().drain(..).collect()
     }
@@ -523,11 +642,7 @@ impl Monitor {
     }
 
     pub fn update_focused_workspace(&mut self, offset: Option<Rect>) -> Result<()> {
-        let offset = if self.work_area_offset().is_some() {
-            self.work_area_offset()
-        } else {
-            offset
-        };
+        let offset = self.applied_work_area_offset().or(offset);
 
         let focused_workspace_idx = self.focused_workspace_idx();
         self.update_workspace_globals(focused_workspace_idx, offset);
@@ -597,6 +712,45 @@ mod tests {
         assert_eq!(m.workspaces().len(), 2);
     }
 
+    #[test]
+    fn test_remove_trailing_empty_workspaces() {
+        let mut m = Monitor::new(
+            0,
+            Rect::default(),
+            Rect::default(),
+            "TestMonitor".to_string(),
+            "TestDevice".to_string(),
+            "TestDeviceID".to_string(),
+            Some("TestMonitorID".to_string()),
+        );
+
+        // Create workspaces 2, 3, 4 and 5
+        m.focus_workspace(4).unwrap();
+        assert_eq!(m.workspaces().len(), 5);
+
+        // Add a window to workspace 3 and name workspace 2
+        m.workspaces_mut()[2].add_container_to_back(Container::default());
+        m.workspaces_mut()[1].set_name(Some("named".to_string()));
+
+        // Nothing should be removed while the last workspace is focused
+        m.remove_trailing_empty_workspaces();
+        assert_eq!(m.workspaces().len(), 5);
+
+        // Workspaces 4 and 5 are empty and after the focused workspace
+        m.focus_workspace(0).unwrap();
+        m.set_last_focused_workspace(Some(4));
+        m.remove_trailing_empty_workspaces();
+        assert_eq!(m.workspaces().len(), 3);
+        assert_eq!(m.last_focused_workspace(), None);
+
+        // Workspace 3 is not empty so it should be kept
+        m.workspaces_mut()[2].remove_container_by_idx(0);
+        m.remove_trailing_empty_workspaces();
+
+        // Workspace 2 is named so it should be kept
+        assert_eq!(m.workspaces().len(), 2);
+    }
+
     #[test]
     fn test_remove_workspaces() {
         let mut m = Monitor::new(
@@ -675,6 +829,36 @@ mod tests {
         assert_eq!(m.focused_workspace_idx(), 1);
     }
 
+    #[test]
+    fn test_swap_workspaces() {
+        let mut m = Monitor::new(
+            0,
+            Rect::default(),
+            Rect::default(),
+            "TestMonitor".to_string(),
+            "TestDevice".to_string(),
+            "TestDeviceID".to_string(),
+            Some("TestMonitorID".to_string()),
+        );
+
+        m.focus_workspace(2).unwrap();
+        m.workspaces_mut()[0].set_name(Some("first".to_string()));
+        m.workspace_names_mut().insert(0, "first".to_string());
+
+        m.swap_workspaces(0, 2).unwrap();
+
+        // The named workspace and its name should have moved to index 2
+        assert_eq!(m.workspaces()[2].name(), &Some("first".to_string()));
+        assert_eq!(m.workspace_names_mut().get(&2), Some(&"first".to_string()));
+        assert!(!m.workspace_names_mut().contains_key(&0));
+
+        // The focused workspace should have moved to index 0 and still be focused
+        assert_eq!(m.focused_workspace_idx(), 0);
+
+        // Swapping with a workspace that doesn't exist should fail
+        assert!(m.swap_workspaces(0, 3).is_err());
+    }
+
     #[test]
     fn test_new_workspace_idx() {
         let m = Monitor::new(
@@ -12,6 +12,7 @@ use crate::NotificationEvent;
 use crate::State;
 use crate::WindowManager;
 use crate::WindowsApi;
+use crate::DATA_DIR;
 use crate::DISPLAY_INDEX_PREFERENCES;
 use crate::DUPLICATE_MONITOR_SERIAL_IDS;
 use crate::WORKSPACE_MATCHING_RULES;
@@ -22,12 +23,14 @@ use parking_lot::Mutex;
 use serde::Deserialize;
 use serde::Serialize;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::OnceLock;
 
 pub mod hidden;
+pub mod taskbar;
 
 #[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
@@ -40,6 +43,7 @@ pub enum MonitorNotification {
     ResumingFromSuspendedState,
     SessionLocked,
     SessionUnlocked,
+    TaskbarVisibilityChanged,
 }
 
 static ACTIVE: AtomicBool = AtomicBool::new(true);
@@ -84,6 +88,98 @@ pub fn insert_in_monitor_cache(serial_or_device_id: &str, monitor: Monitor) {
     monitor_cache.insert(preferred_id, monitor);
 }
 
+/// The file holding the monitor index assignments pinned with `komorebic monitors --persist`
+pub fn persisted_display_index_preferences_path() -> PathBuf {
+    DATA_DIR.join("komorebi.display_index_preferences.json")
+}
+
+/// Merges the persisted monitor index assignments into the display index preferences. Indices
+/// and displays which already have a preference, such as those given in the static
+/// configuration file, are left untouched.
+pub fn load_persisted_display_index_preferences() -> color_eyre::Result<()> {
+    let path = persisted_display_index_preferences_path();
+    if !path.is_file() {
+        return Ok(());
+    }
+
+    let persisted: HashMap<usize, String> = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+
+    let mut dip = DISPLAY_INDEX_PREFERENCES.write();
+    for (index, id) in persisted {
+        if !dip.contains_key(&index) && !dip.values().any(|existing| *existing == id) {
+            tracing::info!("applying persisted display index preference {index}: {id}");
+            dip.insert(index, id);
+        }
+    }
+
+    Ok(())
+}
+
+/// Pins the current user-facing index of every connected monitor to its serial number id, or
+/// its device id if the serial number id is not unique, so that the indices stay stable across
+/// reboots and cable swaps
+pub fn persist_display_index_preferences(wm: &WindowManager) -> color_eyre::Result<()> {
+    let mut persisted = HashMap::new();
+
+    {
+        let dupes = DUPLICATE_MONITOR_SERIAL_IDS.read();
+        for (usr_idx, m_idx) in &wm.monitor_usr_idx_map {
+            if let Some(monitor) = wm.monitors().get(*m_idx) {
+                let id = monitor
+                    .serial_number_id()
+                    .clone()
+                    .filter(|id| !dupes.contains(id))
+                    .unwrap_or_else(|| monitor.device_id().clone());
+
+                persisted.insert(*usr_idx, id);
+            }
+        }
+    }
+
+    std::fs::write(
+        persisted_display_index_preferences_path(),
+        serde_json::to_string_pretty(&persisted)?,
+    )?;
+
+    let mut dip = DISPLAY_INDEX_PREFERENCES.write();
+    for (index, id) in persisted {
+        if !dip.contains_key(&index) && !dip.values().any(|existing| *existing == id) {
+            dip.insert(index, id);
+        }
+    }
+
+    Ok(())
+}
+
+/// The hwnds of every window in the workspaces of `monitor`, whether tiled, in a monocle
+/// container, maximized or floating
+fn workspace_hwnds(monitor: &Monitor) -> Vec<isize> {
+    let mut hwnds = Vec::new();
+
+    for workspace in monitor.workspaces() {
+        for container in workspace.containers() {
+            hwnds.extend(container.windows().iter().map(|window| window.hwnd));
+        }
+
+        if let Some(container) = workspace.monocle_container() {
+            hwnds.extend(container.windows().iter().map(|window| window.hwnd));
+        }
+
+        if let Some(window) = workspace.maximized_window() {
+            hwnds.push(window.hwnd);
+        }
+
+        hwnds.extend(
+            workspace
+                .floating_windows()
+                .iter()
+                .map(|window| window.hwnd),
+        );
+    }
+
+    hwnds
+}
+
 pub fn attached_display_devices<F, I>(display_provider: F) -> color_eyre::Result<Vec<Monitor>>
 where
     F: Fn() -> I + Copy,
@@ -244,6 +340,23 @@ where
                     }
                 }
             }
+            MonitorNotification::TaskbarVisibilityChanged => {
+                tracing::debug!("handling taskbar visibility changed notification");
+                let offset = wm.work_area_offset;
+                for monitor in wm.monitors_mut() {
+                    let taskbar_offset = taskbar::offset(monitor.id());
+                    if taskbar_offset != monitor.taskbar_work_area_offset() {
+                        tracing::info!(
+                            "updated taskbar work area offset for {}",
+                            monitor.device_id()
+                        );
+
+                        monitor.set_taskbar_work_area_offset(taskbar_offset);
+                        monitor.update_focused_workspace(offset)?;
+                        border_manager::send_notification(None);
+                    }
+                }
+            }
             MonitorNotification::ResolutionScalingChanged => {
                 tracing::debug!("handling resolution/scaling changed notification");
                 let offset = wm.work_area_offset;
@@ -505,6 +618,43 @@ where
                         "monitor count mismatch ({post_removal_monitor_count} vs {post_addition_monitor_count}), adding connected monitors",
                     );
 
+                    // While a monitor was disconnected its windows were moved onto and managed
+                    // by the remaining monitors; take back the ones which are still in the
+                    // cached workspaces of a reconnecting monitor so that they can be restored
+                    // to their original workspaces below
+                    let mut reclaimed_hwnds = Vec::new();
+                    for m in wm.monitors() {
+                        if post_removal_device_ids.contains(m.device_id()) {
+                            continue;
+                        }
+
+                        if let Some(cached) = monitor_cache.get(m.device_id()).or(m
+                            .serial_number_id()
+                            .as_ref()
+                            .and_then(|sn| monitor_cache.get(sn)))
+                        {
+                            reclaimed_hwnds.extend(workspace_hwnds(cached));
+                        }
+                    }
+
+                    for hwnd in reclaimed_hwnds {
+                        if let Some((m_idx, w_idx)) = wm.known_hwnds.remove(&hwnd) {
+                            if let Some(workspace) = wm
+                                .monitors_mut()
+                                .get_mut(m_idx)
+                                .and_then(|m| m.workspaces_mut().get_mut(w_idx))
+                            {
+                                tracing::info!(
+                                    "moving window {hwnd} back to its workspace on the reconnected monitor"
+                                );
+
+                                if let Err(error) = workspace.remove_window(hwnd) {
+                                    tracing::warn!("could not reclaim window {hwnd}: {error}");
+                                }
+                            }
+                        }
+                    }
+
                     let known_hwnds = wm.known_hwnds.clone();
                     let offset = wm.work_area_offset;
                     let mouse_follows_focus = wm.mouse_follows_focus;
@@ -531,7 +681,7 @@ where
 
                                 // If it does, update the cached monitor info with the new one and
                                 // load the cached monitor removing any window that has since been
-                                // closed or moved to another workspace
+                                // closed
                                 *m = Monitor {
                                     // Data that should be the one just read from `win32-display-data`
                                     id: m.id,
@@ -541,6 +691,7 @@ where
                                     serial_number_id: m.serial_number_id.clone(),
                                     size: m.size,
                                     work_area_size: m.work_area_size,
+                                    taskbar_work_area_offset: taskbar::offset(m.id),
 
                                     // The rest should come from the cached monitor
                                     work_area_offset: cached.work_area_offset,
@@ -555,6 +706,8 @@ where
                                     workspace_padding: cached.workspace_padding,
                                     wallpaper: cached.wallpaper.clone(),
                                     floating_layer_behaviour: cached.floating_layer_behaviour,
+                                    always_on_top: cached.always_on_top.clone(),
+                                    always_on_top_focused: cached.always_on_top_focused,
                                 };
 
                                 let focused_workspace_idx = m.focused_workspace_idx();
@@ -665,8 +818,9 @@ where
                                             workspace_matching_rules.push(WorkspaceMatchingRule {
                                                 monitor_index: i,
                                                 workspace_index: j,
-                                                matching_rule: r.clone(),
+                                                matching_rule: r.matching_rule().clone(),
                                                 initial_only: false,
+                                                follow: r.follow(),
                                             });
                                         }
                                     }
@@ -680,8 +834,9 @@ where
                                             workspace_matching_rules.push(WorkspaceMatchingRule {
                                                 monitor_index: i,
                                                 workspace_index: j,
-                                                matching_rule: r.clone(),
+                                                matching_rule: r.matching_rule().clone(),
                                                 initial_only: true,
+                                                follow: r.follow(),
                                             });
                                         }
                                     }
@@ -704,6 +859,9 @@ where
                     // steal the focus away.
                     wm.focus_monitor(focused_monitor_idx)?;
                     wm.focus_workspace(focused_workspace_idx)?;
+
+                    // Reclaimed windows are now on the reconnected monitor
+                    wm.update_known_hwnds();
                 }
 
                 let final_count = wm.monitors().len();
@@ -733,7 +891,9 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::container::Container;
     use crate::window_manager_event::WindowManagerEvent;
+    use crate::Window;
     use crossbeam_channel::bounded;
     use crossbeam_channel::Sender;
     use std::path::PathBuf;
@@ -938,6 +1098,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_workspace_hwnds() {
+        let mut m = monitor::new(
+            0,
+            Rect::default(),
+            Rect::default(),
+            "Test Monitor".to_string(),
+            "Test Device".to_string(),
+            "Test Device ID".to_string(),
+            Some("TestMonitorID".to_string()),
+        );
+
+        let Some(workspace) = m.focused_workspace_mut() else {
+            panic!("monitor should have a workspace");
+        };
+
+        let mut container = Container::default();
+        container.add_window(Window::from(1));
+        container.add_window(Window::from(2));
+        workspace.add_container_to_back(container);
+        workspace.floating_windows_mut().push_back(Window::from(3));
+
+        let mut monocle = Container::default();
+        monocle.add_window(Window::from(4));
+        workspace.set_monocle_container(Some(monocle));
+
+        let mut hwnds = workspace_hwnds(&m);
+        hwnds.sort();
+
+        // Every window in the cached workspaces is reclaimed, not just the tiled ones
+        assert_eq!(hwnds, vec![1, 2, 3, 4]);
+    }
+
     #[test]
     fn test_listen_for_notifications() {
         // Create a WindowManager instance for testing
@@ -0,0 +1,275 @@
+#![deny(clippy::unwrap_used, clippy::expect_used)]
+
+use crate::core::Rect;
+use crate::monitor_reconciliator;
+use crate::monitor_reconciliator::MonitorNotification;
+use crate::winevent::WinEvent;
+use crate::WindowsApi;
+use crossbeam_channel::Receiver;
+use crossbeam_channel::Sender;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::OnceLock;
+
+/// An auto-hidden taskbar leaves a strip this thick on screen to be revealed by the mouse
+const HIDDEN_THICKNESS: i32 = 4;
+
+pub static TASKBAR_AUTO_HIDE_OFFSET: AtomicBool = AtomicBool::new(false);
+
+lazy_static! {
+    /// Work area offsets of monitors with a shown auto-hiding taskbar, keyed by monitor id
+    static ref TASKBAR_OFFSETS: Arc<Mutex<HashMap<isize, Rect>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+    /// Handles of the taskbar windows, so that their events can be recognised without looking up
+    /// their class, which is no longer possible once they have been destroyed
+    static ref TASKBARS: Arc<Mutex<HashSet<isize>>> = Arc::new(Mutex::new(HashSet::new()));
+}
+
+pub struct Notification;
+
+static CHANNEL: OnceLock<(Sender<Notification>, Receiver<Notification>)> = OnceLock::new();
+
+fn channel() -> &'static (Sender<Notification>, Receiver<Notification>) {
+    CHANNEL.get_or_init(|| crossbeam_channel::bounded(1))
+}
+
+fn event_tx() -> Sender<Notification> {
+    channel().0.clone()
+}
+
+fn event_rx() -> Receiver<Notification> {
+    channel().1.clone()
+}
+
+/// Requests the taskbar offsets to be checked again; a full channel already has a pending check
+pub fn send_notification() {
+    let _ = event_tx().try_send(Notification);
+}
+
+/// The work area offset needed to keep windows clear of the taskbar on the monitor with id
+/// `monitor_id`, if it has a shown auto-hiding taskbar
+pub fn offset(monitor_id: isize) -> Option<Rect> {
+    TASKBAR_OFFSETS.lock().get(&monitor_id).copied()
+}
+
+/// Called from the WinEvent hook, checks the taskbar offsets again when a taskbar is shown,
+/// hidden, destroyed or slides in and out of view
+pub fn handle_win_event(winevent: WinEvent, hwnd: isize) {
+    if !TASKBAR_AUTO_HIDE_OFFSET.load(Ordering::SeqCst) {
+        return;
+    }
+
+    let is_taskbar = match winevent {
+        WinEvent::ObjectShow => {
+            let is_taskbar = WindowsApi::real_window_class_w(hwnd)
+                .is_ok_and(|class| class == "Shell_TrayWnd" || class == "Shell_SecondaryTrayWnd");
+
+            if is_taskbar {
+                TASKBARS.lock().insert(hwnd);
+            }
+
+            is_taskbar
+        }
+        WinEvent::ObjectDestroy => TASKBARS.lock().remove(&hwnd),
+        WinEvent::ObjectHide | WinEvent::ObjectLocationChange => TASKBARS.lock().contains(&hwnd),
+        _ => false,
+    };
+
+    if is_taskbar {
+        send_notification();
+    }
+}
+
+pub fn listen_for_changes() {
+    std::thread::spawn(move || loop {
+        match handle_changes() {
+            Ok(()) => {
+                tracing::warn!("restarting finished thread");
+            }
+            Err(error) => {
+                if cfg!(debug_assertions) {
+                    tracing::error!("restarting failed thread: {:?}", error)
+                } else {
+                    tracing::error!("restarting failed thread: {}", error)
+                }
+            }
+        }
+    });
+}
+
+fn handle_changes() -> color_eyre::Result<()> {
+    tracing::info!("listening");
+
+    *TASKBARS.lock() = WindowsApi::taskbars()?.into_iter().collect();
+    send_notification();
+
+    let receiver = event_rx();
+
+    for _ in receiver {
+        let offsets =
+            if TASKBAR_AUTO_HIDE_OFFSET.load(Ordering::SeqCst) && WindowsApi::taskbar_auto_hide() {
+                current_offsets()?
+            } else {
+                HashMap::new()
+            };
+
+        let mut taskbar_offsets = TASKBAR_OFFSETS.lock();
+        if *taskbar_offsets != offsets {
+            tracing::info!("taskbar visibility changed, updating work area offsets");
+            *taskbar_offsets = offsets;
+            drop(taskbar_offsets);
+
+            monitor_reconciliator::send_notification(MonitorNotification::TaskbarVisibilityChanged);
+        }
+    }
+
+    Ok(())
+}
+
+fn current_offsets() -> color_eyre::Result<HashMap<isize, Rect>> {
+    let mut offsets = HashMap::new();
+
+    let taskbars = TASKBARS.lock().clone();
+    for hwnd in taskbars {
+        let monitor_id = WindowsApi::monitor_from_window(hwnd);
+        let monitor = WindowsApi::monitor_rect(monitor_id)?;
+        let taskbar = WindowsApi::window_rect(hwnd)?;
+
+        if let Some(offset) = shown_taskbar_offset(&monitor, &taskbar) {
+            offsets.insert(monitor_id, offset);
+        }
+    }
+
+    Ok(offsets)
+}
+
+/// Returns the work area offset for the edge of `monitor` that `taskbar` is docked to, or None
+/// if no more than the strip left by an auto-hidden taskbar is on screen
+pub fn shown_taskbar_offset(monitor: &Rect, taskbar: &Rect) -> Option<Rect> {
+    let visible_width = (monitor.left + monitor.right).min(taskbar.left + taskbar.right)
+        - monitor.left.max(taskbar.left);
+    let visible_height = (monitor.top + monitor.bottom).min(taskbar.top + taskbar.bottom)
+        - monitor.top.max(taskbar.top);
+
+    if visible_width <= 0 || visible_height <= 0 {
+        return None;
+    }
+
+    let horizontal = taskbar.right >= taskbar.bottom;
+
+    if horizontal {
+        if visible_height <= HIDDEN_THICKNESS {
+            return None;
+        }
+
+        let docked_top = taskbar.top + taskbar.bottom / 2 < monitor.top + monitor.bottom / 2;
+
+        Option::from(if docked_top {
+            Rect {
+                top: taskbar.bottom,
+                ..Default::default()
+            }
+        } else {
+            Rect {
+                bottom: taskbar.bottom,
+                ..Default::default()
+            }
+        })
+    } else {
+        if visible_width <= HIDDEN_THICKNESS {
+            return None;
+        }
+
+        let docked_left = taskbar.left + taskbar.right / 2 < monitor.left + monitor.right / 2;
+
+        Option::from(if docked_left {
+            Rect {
+                left: taskbar.right,
+                ..Default::default()
+            }
+        } else {
+            Rect {
+                right: taskbar.right,
+                ..Default::default()
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MONITOR: Rect = Rect {
+        left: 0,
+        top: 0,
+        right: 1920,
+        bottom: 1080,
+    };
+
+    #[test]
+    fn test_hidden_taskbar_has_no_offset() {
+        let taskbar = Rect {
+            left: 0,
+            top: 1078,
+            right: 1920,
+            bottom: 48,
+        };
+
+        assert_eq!(shown_taskbar_offset(&MONITOR, &taskbar), None);
+    }
+
+    #[test]
+    fn test_shown_taskbar_offsets_docked_edge() {
+        let bottom = Rect {
+            left: 0,
+            top: 1032,
+            right: 1920,
+            bottom: 48,
+        };
+
+        assert_eq!(
+            shown_taskbar_offset(&MONITOR, &bottom),
+            Some(Rect {
+                left: 0,
+                top: 0,
+                right: 0,
+                bottom: 48,
+            })
+        );
+
+        let left = Rect {
+            left: 0,
+            top: 0,
+            right: 62,
+            bottom: 1080,
+        };
+
+        assert_eq!(
+            shown_taskbar_offset(&MONITOR, &left),
+            Some(Rect {
+                left: 62,
+                top: 0,
+                right: 0,
+                bottom: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn test_taskbar_on_another_monitor_has_no_offset() {
+        let taskbar = Rect {
+            left: 1920,
+            top: 1032,
+            right: 1920,
+            bottom: 48,
+        };
+
+        assert_eq!(shown_taskbar_offset(&MONITOR, &taskbar), None);
+    }
+}
@@ -0,0 +1,972 @@
+#![deny(clippy::unwrap_used, clippy::expect_used)]
+
+use crate::border_manager;
+use crate::core::KeyModifier;
+use crate::core::Rect;
+use crate::window::Window;
+use crate::window_manager::WindowManager;
+use crate::window_manager_event::WindowManagerEvent;
+use crate::windows_api;
+use crate::windows_api::WindowsApi;
+use crate::windows_callbacks;
+use crate::winevent::WinEvent;
+use crate::winevent_listener;
+
+use crossbeam_channel::Receiver;
+use crossbeam_channel::Sender;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use std::sync::atomic::AtomicI32;
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::OnceLock;
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::HINSTANCE;
+use windows::Win32::Foundation::HWND;
+use windows::Win32::Foundation::LPARAM;
+use windows::Win32::Foundation::LRESULT;
+use windows::Win32::Foundation::POINT;
+use windows::Win32::Foundation::WPARAM;
+use windows::Win32::UI::Input::KeyboardAndMouse::MOUSEEVENTF_LEFTDOWN;
+use windows::Win32::UI::Input::KeyboardAndMouse::MOUSEEVENTF_LEFTUP;
+use windows::Win32::UI::Input::KeyboardAndMouse::MOUSEEVENTF_RIGHTDOWN;
+use windows::Win32::UI::Input::KeyboardAndMouse::MOUSEEVENTF_RIGHTUP;
+use windows::Win32::UI::Input::KeyboardAndMouse::MOUSE_EVENT_FLAGS;
+use windows::Win32::UI::Input::KeyboardAndMouse::VIRTUAL_KEY;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_CONTROL;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_LWIN;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_MENU;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_RWIN;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_SHIFT;
+use windows::Win32::UI::WindowsAndMessaging::CreateWindowExW;
+use windows::Win32::UI::WindowsAndMessaging::DefWindowProcW;
+use windows::Win32::UI::WindowsAndMessaging::DispatchMessageW;
+use windows::Win32::UI::WindowsAndMessaging::GetMessageW;
+use windows::Win32::UI::WindowsAndMessaging::TranslateMessage;
+use windows::Win32::UI::WindowsAndMessaging::MSG;
+use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNOACTIVATE;
+use windows::Win32::UI::WindowsAndMessaging::WM_LBUTTONDOWN;
+use windows::Win32::UI::WindowsAndMessaging::WM_LBUTTONUP;
+use windows::Win32::UI::WindowsAndMessaging::WM_MOUSEMOVE;
+use windows::Win32::UI::WindowsAndMessaging::WM_RBUTTONDOWN;
+use windows::Win32::UI::WindowsAndMessaging::WM_RBUTTONUP;
+use windows::Win32::UI::WindowsAndMessaging::WNDCLASSW;
+use windows::Win32::UI::WindowsAndMessaging::WS_EX_LAYERED;
+use windows::Win32::UI::WindowsAndMessaging::WS_EX_NOACTIVATE;
+use windows::Win32::UI::WindowsAndMessaging::WS_EX_TOOLWINDOW;
+use windows::Win32::UI::WindowsAndMessaging::WS_EX_TOPMOST;
+use windows::Win32::UI::WindowsAndMessaging::WS_EX_TRANSPARENT;
+use windows::Win32::UI::WindowsAndMessaging::WS_POPUP;
+
+/// Window classes of the desktop and the taskbar, which can never be dragged
+const IGNORED_CLASSES: [&str; 4] = [
+    "Progman",
+    "WorkerW",
+    "Shell_TrayWnd",
+    "Shell_SecondaryTrayWnd",
+];
+
+/// Distance the mouse has to travel before a press turns into a drag, so that a modifier+click
+/// still focuses a window without nudging it
+const DRAG_THRESHOLD: i32 = 4;
+
+/// Smallest width and height a window can be resized to with a drag
+const MINIMUM_SIZE: i32 = 100;
+
+/// Thickness of the guide lines shown along snapped edges
+const GUIDE_THICKNESS: i32 = 2;
+
+/// Opacity of the guide lines shown along snapped edges
+const GUIDE_ALPHA: u8 = 200;
+
+/// Distance within which the edges of a dragged floating window snap to the edges of the monitor
+/// work area and of other floating windows, 0 disables snapping
+pub static SNAP_DISTANCE: AtomicI32 = AtomicI32::new(10);
+
+lazy_static! {
+    pub static ref MOUSE_DRAG_MODIFIERS: Arc<Mutex<Vec<KeyModifier>>> =
+        Arc::new(Mutex::new(Vec::new()));
+    /// The window being moved or resized by its title bar or borders and where it started from
+    static ref MOVE_RESIZE_START: Arc<Mutex<Option<(isize, Rect)>>> = Arc::new(Mutex::new(None));
+}
+
+/// The mouse drag modifiers as a bitmask, which the hook can read without taking a lock
+static DRAG_MODIFIERS: AtomicU8 = AtomicU8::new(0);
+
+/// The drag operation of the mouse button which is held down, or `NOT_HELD`
+static HELD_OPERATION: AtomicU8 = AtomicU8::new(NOT_HELD);
+
+const NOT_HELD: u8 = 0;
+
+/// An unassigned virtual key, pressed so that releasing Alt or Win after a swallowed click
+/// doesn't open the menu bar or the start menu
+const DUMMY_KEY: VIRTUAL_KEY = VIRTUAL_KEY(0xE8);
+
+static CHANNEL: OnceLock<(Sender<DragEvent>, Receiver<DragEvent>)> = OnceLock::new();
+
+static HOOK_PUMP: OnceLock<std::thread::JoinHandle<()>> = OnceLock::new();
+
+/// The vertical and horizontal guide lines, owned by the hook thread
+static GUIDES: OnceLock<[isize; 2]> = OnceLock::new();
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+enum DragOperation {
+    Move = 1,
+    Resize = 2,
+}
+
+impl DragOperation {
+    const fn button_flags(self) -> (MOUSE_EVENT_FLAGS, MOUSE_EVENT_FLAGS) {
+        match self {
+            Self::Move => (MOUSEEVENTF_LEFTDOWN, MOUSEEVENTF_LEFTUP),
+            Self::Resize => (MOUSEEVENTF_RIGHTDOWN, MOUSEEVENTF_RIGHTUP),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+enum DragEvent {
+    Press(DragOperation, POINT),
+    Motion(POINT),
+    Release,
+}
+
+struct Drag {
+    window: Window,
+    operation: DragOperation,
+    origin: POINT,
+    initial: Rect,
+    /// Whether the mouse has moved far enough for the press to be treated as a drag
+    started: bool,
+    /// Resizes move the edges closest to where the window was grabbed
+    resize_left: bool,
+    resize_top: bool,
+    /// Monitor work areas and other floating windows that a floating window snaps to, this is
+    /// empty for windows managed by a layout
+    snap_targets: Vec<Rect>,
+}
+
+/// An adjustment along one axis which aligns an edge of the dragged window with an edge of one of
+/// the snap targets
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct Snap {
+    offset: i32,
+    position: i32,
+    target: usize,
+}
+
+/// Finds the smallest adjustment within `distance` which moves one of the `edges` onto one of the
+/// edges of the `targets`
+fn nearest_snap(edges: &[i32], targets: &[(usize, [i32; 2])], distance: i32) -> Option<Snap> {
+    let mut nearest: Option<Snap> = None;
+
+    for (target, target_edges) in targets {
+        for edge in edges {
+            for position in target_edges {
+                let offset = position - edge;
+                if offset.abs() <= distance
+                    && nearest.is_none_or(|nearest| offset.abs() < nearest.offset.abs())
+                {
+                    nearest = Some(Snap {
+                        offset,
+                        position: *position,
+                        target: *target,
+                    });
+                }
+            }
+        }
+    }
+
+    nearest
+}
+
+/// Whether the ranges `start..start + length` of two rects on the same axis overlap
+const fn overlaps(start: i32, length: i32, other_start: i32, other_length: i32) -> bool {
+    start <= other_start + other_length && other_start <= start + length
+}
+
+impl Drag {
+    fn new(window: Window, operation: DragOperation, origin: POINT, initial: Rect) -> Self {
+        Self {
+            window,
+            operation,
+            origin,
+            initial,
+            started: false,
+            resize_left: origin.x < initial.left + initial.right / 2,
+            resize_top: origin.y < initial.top + initial.bottom / 2,
+            snap_targets: vec![],
+        }
+    }
+
+    fn rect(&self, point: POINT) -> Rect {
+        let dx = point.x - self.origin.x;
+        let dy = point.y - self.origin.y;
+        let mut rect = self.initial;
+
+        match self.operation {
+            DragOperation::Move => {
+                rect.left += dx;
+                rect.top += dy;
+            }
+            DragOperation::Resize => {
+                if self.resize_left {
+                    rect.right = (self.initial.right - dx).max(MINIMUM_SIZE);
+                    rect.left = self.initial.left + self.initial.right - rect.right;
+                } else {
+                    rect.right = (self.initial.right + dx).max(MINIMUM_SIZE);
+                }
+
+                if self.resize_top {
+                    rect.bottom = (self.initial.bottom - dy).max(MINIMUM_SIZE);
+                    rect.top = self.initial.top + self.initial.bottom - rect.bottom;
+                } else {
+                    rect.bottom = (self.initial.bottom + dy).max(MINIMUM_SIZE);
+                }
+            }
+        }
+
+        rect
+    }
+
+    /// Snaps the moving edges of `rect` to the nearest edges of the snap targets within
+    /// `distance`, returning the snapped rect along with the vertical and horizontal guide lines
+    /// to show for the edges which were snapped
+    fn snap(&self, mut rect: Rect, distance: i32) -> (Rect, [Option<Rect>; 2]) {
+        if distance <= 0 || self.snap_targets.is_empty() {
+            return (rect, [None, None]);
+        }
+
+        let (moving_x, moving_y) = match self.operation {
+            DragOperation::Move => (
+                vec![rect.left, rect.left + rect.right],
+                vec![rect.top, rect.top + rect.bottom],
+            ),
+            DragOperation::Resize => (
+                vec![if self.resize_left {
+                    rect.left
+                } else {
+                    rect.left + rect.right
+                }],
+                vec![if self.resize_top {
+                    rect.top
+                } else {
+                    rect.top + rect.bottom
+                }],
+            ),
+        };
+
+        // edges only snap to targets which are level with the window on the other axis
+        let targets_x = self
+            .snap_targets
+            .iter()
+            .enumerate()
+            .filter(|(_, target)| overlaps(rect.top, rect.bottom, target.top, target.bottom))
+            .map(|(idx, target)| (idx, [target.left, target.left + target.right]))
+            .collect::<Vec<_>>();
+
+        let targets_y = self
+            .snap_targets
+            .iter()
+            .enumerate()
+            .filter(|(_, target)| overlaps(rect.left, rect.right, target.left, target.right))
+            .map(|(idx, target)| (idx, [target.top, target.top + target.bottom]))
+            .collect::<Vec<_>>();
+
+        let mut snap_x = nearest_snap(&moving_x, &targets_x, distance);
+        let mut snap_y = nearest_snap(&moving_y, &targets_y, distance);
+
+        match self.operation {
+            DragOperation::Move => {
+                if let Some(snap) = snap_x {
+                    rect.left += snap.offset;
+                }
+
+                if let Some(snap) = snap_y {
+                    rect.top += snap.offset;
+                }
+            }
+            DragOperation::Resize => {
+                snap_x = snap_x.filter(|snap| {
+                    let offset = if self.resize_left {
+                        -snap.offset
+                    } else {
+                        snap.offset
+                    };
+
+                    rect.right + offset >= MINIMUM_SIZE
+                });
+
+                snap_y = snap_y.filter(|snap| {
+                    let offset = if self.resize_top {
+                        -snap.offset
+                    } else {
+                        snap.offset
+                    };
+
+                    rect.bottom + offset >= MINIMUM_SIZE
+                });
+
+                if let Some(snap) = snap_x {
+                    if self.resize_left {
+                        rect.left += snap.offset;
+                        rect.right -= snap.offset;
+                    } else {
+                        rect.right += snap.offset;
+                    }
+                }
+
+                if let Some(snap) = snap_y {
+                    if self.resize_top {
+                        rect.top += snap.offset;
+                        rect.bottom -= snap.offset;
+                    } else {
+                        rect.bottom += snap.offset;
+                    }
+                }
+            }
+        }
+
+        // guide lines span both the window and the target they were snapped to
+        let vertical = snap_x.map(|snap| {
+            let target = self.snap_targets[snap.target];
+            let top = rect.top.min(target.top);
+            let bottom = (rect.top + rect.bottom).max(target.top + target.bottom);
+
+            Rect {
+                left: snap.position - GUIDE_THICKNESS / 2,
+                top,
+                right: GUIDE_THICKNESS,
+                bottom: bottom - top,
+            }
+        });
+
+        let horizontal = snap_y.map(|snap| {
+            let target = self.snap_targets[snap.target];
+            let left = rect.left.min(target.left);
+            let right = (rect.left + rect.right).max(target.left + target.right);
+
+            Rect {
+                left,
+                top: snap.position - GUIDE_THICKNESS / 2,
+                right: right - left,
+                bottom: GUIDE_THICKNESS,
+            }
+        });
+
+        (rect, [vertical, horizontal])
+    }
+}
+
+/// Collects the rects that a dragged window snaps to, which are the work areas of all monitors and
+/// the other floating windows on their focused workspaces. Windows managed by a layout never snap
+fn snap_targets(wm: &WindowManager, hwnd: isize) -> Vec<Rect> {
+    let mut targets = vec![];
+
+    for monitor in wm.monitors() {
+        let Some(workspace) = monitor.focused_workspace() else {
+            continue;
+        };
+
+        if workspace.contains_managed_window(hwnd) {
+            return vec![];
+        }
+
+        targets.push(*monitor.work_area_size());
+
+        for window in workspace.floating_windows() {
+            if window.hwnd != hwnd {
+                if let Ok(rect) = WindowsApi::window_rect(window.hwnd) {
+                    targets.push(rect);
+                }
+            }
+        }
+    }
+
+    targets
+}
+
+/// Records where a window was when a move or resize was started, so that the edges which were
+/// dragged can be snapped once it ends
+pub fn start_move_resize(hwnd: isize) {
+    if let Ok(rect) = WindowsApi::window_rect(hwnd) {
+        *MOVE_RESIZE_START.lock() = Some((hwnd, rect));
+    }
+}
+
+/// Snaps the dragged edges of a floating window which has been moved or resized by its title bar
+/// or borders, rather than with a modifier drag which snaps while the window is being dragged
+pub fn snap_after_move_resize(wm: &WindowManager, hwnd: isize) -> color_eyre::Result<()> {
+    let Some((start_hwnd, initial)) = MOVE_RESIZE_START.lock().take() else {
+        return Ok(());
+    };
+
+    let distance = SNAP_DISTANCE.load(Ordering::SeqCst);
+    let is_floating = wm
+        .monitors()
+        .iter()
+        .filter_map(|monitor| monitor.focused_workspace())
+        .any(|workspace| {
+            workspace
+                .floating_windows()
+                .iter()
+                .any(|window| window.hwnd == hwnd)
+        });
+
+    if start_hwnd != hwnd || distance <= 0 || !is_floating {
+        return Ok(());
+    }
+
+    let rect = WindowsApi::window_rect(hwnd)?;
+    let operation = if rect.right == initial.right && rect.bottom == initial.bottom {
+        DragOperation::Move
+    } else {
+        DragOperation::Resize
+    };
+
+    let drag = Drag {
+        window: Window::from(hwnd),
+        operation,
+        origin: POINT::default(),
+        initial,
+        started: true,
+        resize_left: rect.left != initial.left,
+        resize_top: rect.top != initial.top,
+        snap_targets: snap_targets(wm, hwnd),
+    };
+
+    let (snapped, _) = drag.snap(rect, distance);
+    if snapped != rect {
+        WindowsApi::position_window(hwnd, &snapped, false, true)?;
+    }
+
+    Ok(())
+}
+
+fn show_guides(guides: [Option<Rect>; 2]) {
+    let Some(hwnds) = GUIDES.get() else {
+        return;
+    };
+
+    for (hwnd, guide) in hwnds.iter().zip(guides) {
+        match guide {
+            Some(rect) => {
+                if let Err(error) = WindowsApi::position_window(*hwnd, &rect, true, true) {
+                    tracing::warn!("could not position snap guide: {error}");
+                }
+
+                WindowsApi::show_window(*hwnd, SW_SHOWNOACTIVATE);
+            }
+            None => WindowsApi::hide_window(*hwnd),
+        }
+    }
+}
+
+unsafe extern "system" fn guide_callback(
+    hwnd: HWND,
+    message: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    DefWindowProcW(hwnd, message, wparam, lparam)
+}
+
+/// Creates the guide lines on the current thread, which has to pump their messages. They are
+/// click-through and never take focus, so they can stay topmost while hidden
+fn create_guides() -> color_eyre::Result<[isize; 2]> {
+    let name: Vec<u16> = "komorebi-snap-guide\0".encode_utf16().collect();
+    let class_name = PCWSTR(name.as_ptr());
+    let h_module = WindowsApi::module_handle_w()?;
+
+    let window_class = WNDCLASSW {
+        lpfnWndProc: Some(guide_callback),
+        hInstance: h_module.into(),
+        lpszClassName: class_name,
+        hbrBackground: WindowsApi::create_solid_brush(
+            border_manager::FOCUSED.load(Ordering::SeqCst),
+        ),
+        ..Default::default()
+    };
+
+    let _ = WindowsApi::register_class_w(&window_class);
+
+    let instance = h_module.0 as isize;
+    let mut hwnds = [0; 2];
+
+    for hwnd in &mut hwnds {
+        *hwnd = unsafe {
+            CreateWindowExW(
+                WS_EX_TOOLWINDOW
+                    | WS_EX_LAYERED
+                    | WS_EX_TOPMOST
+                    | WS_EX_NOACTIVATE
+                    | WS_EX_TRANSPARENT,
+                class_name,
+                class_name,
+                WS_POPUP,
+                0,
+                0,
+                0,
+                0,
+                None,
+                None,
+                Option::from(HINSTANCE(windows_api::as_ptr!(instance))),
+                None,
+            )?
+        }
+        .0 as isize;
+
+        WindowsApi::set_transparent(*hwnd, GUIDE_ALPHA)?;
+    }
+
+    Ok(hwnds)
+}
+
+fn channel() -> &'static (Sender<DragEvent>, Receiver<DragEvent>) {
+    CHANNEL.get_or_init(crossbeam_channel::unbounded)
+}
+
+fn is_pressed(modifier: KeyModifier) -> bool {
+    match modifier {
+        KeyModifier::Alt => WindowsApi::key_is_pressed(VK_MENU),
+        KeyModifier::Ctrl => WindowsApi::key_is_pressed(VK_CONTROL),
+        KeyModifier::Shift => WindowsApi::key_is_pressed(VK_SHIFT),
+        KeyModifier::Win => {
+            WindowsApi::key_is_pressed(VK_LWIN) || WindowsApi::key_is_pressed(VK_RWIN)
+        }
+    }
+}
+
+const fn modifier_bit(modifier: KeyModifier) -> u8 {
+    match modifier {
+        KeyModifier::Alt => 1,
+        KeyModifier::Ctrl => 1 << 1,
+        KeyModifier::Shift => 1 << 2,
+        KeyModifier::Win => 1 << 3,
+    }
+}
+
+fn has_modifier(modifier: KeyModifier) -> bool {
+    DRAG_MODIFIERS.load(Ordering::SeqCst) & modifier_bit(modifier) != 0
+}
+
+/// Sets the modifier keys which turn clicks into drags
+pub fn set_mouse_drag_modifiers(modifiers: Vec<KeyModifier>) {
+    let bits = modifiers
+        .iter()
+        .fold(0, |bits, modifier| bits | modifier_bit(*modifier));
+
+    *MOUSE_DRAG_MODIFIERS.lock() = modifiers;
+    DRAG_MODIFIERS.store(bits, Ordering::SeqCst);
+}
+
+fn modifiers_pressed() -> bool {
+    DRAG_MODIFIERS.load(Ordering::SeqCst) != 0
+        && [
+            KeyModifier::Alt,
+            KeyModifier::Ctrl,
+            KeyModifier::Shift,
+            KeyModifier::Win,
+        ]
+        .into_iter()
+        .filter(|modifier| has_modifier(*modifier))
+        .all(is_pressed)
+}
+
+fn draggable_window(point: POINT) -> Option<isize> {
+    let hwnd = WindowsApi::window_from_point(point)
+        .and_then(WindowsApi::root_window)
+        .ok()?;
+    let class = WindowsApi::real_window_class_w(hwnd).ok()?;
+
+    (!IGNORED_CLASSES.contains(&class.as_str()) && !WindowsApi::is_zoomed(hwnd)).then_some(hwnd)
+}
+
+/// Atomically replaces the held drag operation `current` with `new`
+fn swap_held(current: u8, new: u8) -> bool {
+    HELD_OPERATION
+        .compare_exchange(current, new, Ordering::SeqCst, Ordering::SeqCst)
+        .is_ok()
+}
+
+/// Handles a mouse event from the low-level mouse hook, returning true if the event should be
+/// swallowed instead of being passed on to the window under the cursor. This is called for every
+/// mouse event on the system, so it only reads atomics and key states, and looking up the window
+/// under the cursor is left to the drag thread
+pub fn handle_mouse_event(message: u32, point: POINT, injected: bool) -> bool {
+    // clicks replayed by the drag thread when there was no window to drag are passed on
+    if injected {
+        return false;
+    }
+
+    let event = match message {
+        WM_LBUTTONDOWN | WM_RBUTTONDOWN if modifiers_pressed() => {
+            let operation = if message == WM_LBUTTONDOWN {
+                DragOperation::Move
+            } else {
+                DragOperation::Resize
+            };
+
+            if !swap_held(NOT_HELD, operation as u8) {
+                return false;
+            }
+
+            DragEvent::Press(operation, point)
+        }
+        WM_MOUSEMOVE if HELD_OPERATION.load(Ordering::SeqCst) != NOT_HELD => {
+            // the cursor still has to move, so mouse moves are never swallowed
+            let _ = channel().0.send(DragEvent::Motion(point));
+            return false;
+        }
+        WM_LBUTTONUP if swap_held(DragOperation::Move as u8, NOT_HELD) => DragEvent::Release,
+        WM_RBUTTONUP if swap_held(DragOperation::Resize as u8, NOT_HELD) => DragEvent::Release,
+        _ => return false,
+    };
+
+    let _ = channel().0.send(event);
+    true
+}
+
+/// Installs the low-level mouse hook, this only needs to happen once mouse drag modifiers have
+/// been configured
+pub fn start() {
+    HOOK_PUMP.get_or_init(|| {
+        std::thread::spawn(move || {
+            match create_guides() {
+                Ok(hwnds) => {
+                    let _ = GUIDES.set(hwnds);
+                }
+                Err(error) => tracing::warn!("could not create snap guides: {error}"),
+            }
+
+            if let Err(error) =
+                WindowsApi::set_low_level_mouse_hook(Some(windows_callbacks::low_level_mouse_hook))
+            {
+                tracing::error!("could not install low-level mouse hook: {error}");
+                return;
+            }
+
+            let mut msg: MSG = MSG::default();
+
+            // low-level hooks are called on the thread that installed them, which has to keep
+            // pumping messages without delay for the mouse to stay responsive
+            loop {
+                unsafe {
+                    if !GetMessageW(&mut msg, None, 0, 0).as_bool() {
+                        tracing::debug!("low-level mouse hook thread shutdown");
+                        break;
+                    };
+                    let _ = TranslateMessage(&msg);
+                    DispatchMessageW(&msg);
+                }
+            }
+        })
+    });
+}
+
+pub fn listen_for_drags(wm: Arc<Mutex<WindowManager>>) {
+    std::thread::spawn(move || loop {
+        match handle_drags(wm.clone()) {
+            Ok(()) => {
+                tracing::warn!("restarting finished thread");
+            }
+            Err(error) => {
+                tracing::warn!("restarting failed thread: {}", error);
+            }
+        }
+    });
+}
+
+fn handle_drags(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result<()> {
+    tracing::info!("listening");
+
+    let receiver = channel().1.clone();
+    let event_tx = winevent_listener::event_tx();
+    let mut drag: Option<Drag> = None;
+    // a swallowed press which wasn't on a draggable window, whose release has to be replayed too
+    let mut replayed: Option<DragOperation> = None;
+
+    for event in receiver {
+        match event {
+            DragEvent::Press(operation, origin) => {
+                if has_modifier(KeyModifier::Alt) || has_modifier(KeyModifier::Win) {
+                    WindowsApi::send_key(DUMMY_KEY);
+                }
+
+                let Some(hwnd) = draggable_window(origin) else {
+                    WindowsApi::send_mouse_button(operation.button_flags().0);
+                    replayed = Some(operation);
+                    continue;
+                };
+
+                let window = Window::from(hwnd);
+                let initial = WindowsApi::window_rect(hwnd)?;
+
+                // the click which would have focused the window has been swallowed
+                window.focus(false)?;
+
+                let mut new_drag = Drag::new(window, operation, origin, initial);
+                new_drag.snap_targets = snap_targets(&wm.lock(), hwnd);
+                drag = Some(new_drag);
+            }
+            DragEvent::Motion(point) => {
+                let Some(drag) = drag.as_mut() else {
+                    continue;
+                };
+
+                if !drag.started {
+                    if (point.x - drag.origin.x).abs() < DRAG_THRESHOLD
+                        && (point.y - drag.origin.y).abs() < DRAG_THRESHOLD
+                    {
+                        continue;
+                    }
+
+                    drag.started = true;
+
+                    // the window manager handles the drag just like a native move or resize
+                    event_tx.send(WindowManagerEvent::MoveResizeStart(
+                        WinEvent::SystemMoveSizeStart,
+                        drag.window,
+                    ))?;
+                }
+
+                let (rect, guides) =
+                    drag.snap(drag.rect(point), SNAP_DISTANCE.load(Ordering::SeqCst));
+
+                WindowsApi::position_window(drag.window.hwnd, &rect, false, true)?;
+                show_guides(guides);
+            }
+            DragEvent::Release => {
+                show_guides([None, None]);
+
+                if let Some(operation) = replayed.take() {
+                    WindowsApi::send_mouse_button(operation.button_flags().1);
+                    continue;
+                }
+
+                if let Some(drag) = drag.take().filter(|drag| drag.started) {
+                    event_tx.send(WindowManagerEvent::MoveResizeEnd(
+                        WinEvent::SystemMoveSizeEnd,
+                        drag.window,
+                    ))?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_drag(operation: DragOperation, x: i32, y: i32) -> Drag {
+        Drag::new(
+            Window::from(0),
+            operation,
+            POINT { x, y },
+            Rect {
+                left: 100,
+                top: 100,
+                right: 400,
+                bottom: 300,
+            },
+        )
+    }
+
+    #[test]
+    fn test_drag_move() {
+        let drag = new_drag(DragOperation::Move, 200, 200);
+
+        assert_eq!(
+            drag.rect(POINT { x: 250, y: 180 }),
+            Rect {
+                left: 150,
+                top: 80,
+                right: 400,
+                bottom: 300,
+            }
+        );
+    }
+
+    #[test]
+    fn test_drag_resize_from_nearest_corner() {
+        // grabbed near the bottom right corner
+        let drag = new_drag(DragOperation::Resize, 450, 350);
+        assert_eq!(
+            drag.rect(POINT { x: 500, y: 330 }),
+            Rect {
+                left: 100,
+                top: 100,
+                right: 450,
+                bottom: 280,
+            }
+        );
+
+        // grabbed near the top left corner, the opposite edges stay in place
+        let drag = new_drag(DragOperation::Resize, 110, 110);
+        assert_eq!(
+            drag.rect(POINT { x: 150, y: 80 }),
+            Rect {
+                left: 140,
+                top: 70,
+                right: 360,
+                bottom: 330,
+            }
+        );
+    }
+
+    #[test]
+    fn test_drag_resize_minimum_size() {
+        let drag = new_drag(DragOperation::Resize, 110, 110);
+
+        assert_eq!(
+            drag.rect(POINT { x: 1000, y: 1000 }),
+            Rect {
+                left: 400,
+                top: 300,
+                right: MINIMUM_SIZE,
+                bottom: MINIMUM_SIZE,
+            }
+        );
+    }
+
+    #[test]
+    fn test_snap_move_to_work_area_and_window() {
+        let mut drag = new_drag(DragOperation::Move, 200, 200);
+        drag.snap_targets = vec![
+            Rect {
+                left: 0,
+                top: 0,
+                right: 1920,
+                bottom: 1080,
+            },
+            Rect {
+                left: 500,
+                top: 150,
+                right: 300,
+                bottom: 300,
+            },
+        ];
+
+        // the left edge snaps to the work area and the right edge to the other window
+        let (rect, [vertical, horizontal]) = drag.snap(
+            Rect {
+                left: 6,
+                top: 40,
+                right: 400,
+                bottom: 300,
+            },
+            10,
+        );
+
+        assert_eq!(
+            rect,
+            Rect {
+                left: 0,
+                top: 40,
+                right: 400,
+                bottom: 300,
+            }
+        );
+        assert_eq!(
+            vertical,
+            Some(Rect {
+                left: -GUIDE_THICKNESS / 2,
+                top: 0,
+                right: GUIDE_THICKNESS,
+                bottom: 1080,
+            })
+        );
+        assert_eq!(horizontal, None);
+
+        let (rect, _) = drag.snap(
+            Rect {
+                left: 95,
+                top: 40,
+                right: 400,
+                bottom: 300,
+            },
+            10,
+        );
+
+        assert_eq!(rect.left, 100);
+    }
+
+    #[test]
+    fn test_snap_outside_distance() {
+        let mut drag = new_drag(DragOperation::Move, 200, 200);
+        drag.snap_targets = vec![Rect {
+            left: 0,
+            top: 0,
+            right: 1920,
+            bottom: 1080,
+        }];
+
+        let rect = Rect {
+            left: 20,
+            top: 20,
+            right: 400,
+            bottom: 300,
+        };
+
+        assert_eq!(drag.snap(rect, 10), (rect, [None, None]));
+        assert_eq!(drag.snap(rect, 0), (rect, [None, None]));
+    }
+
+    #[test]
+    fn test_snap_ignores_targets_which_are_not_level() {
+        let mut drag = new_drag(DragOperation::Move, 200, 200);
+        drag.snap_targets = vec![Rect {
+            left: 505,
+            top: 800,
+            right: 300,
+            bottom: 200,
+        }];
+
+        let rect = Rect {
+            left: 100,
+            top: 100,
+            right: 400,
+            bottom: 300,
+        };
+
+        assert_eq!(drag.snap(rect, 10), (rect, [None, None]));
+    }
+
+    #[test]
+    fn test_snap_resize_only_moves_grabbed_edges() {
+        // grabbed near the bottom right corner
+        let mut drag = new_drag(DragOperation::Resize, 450, 350);
+        drag.snap_targets = vec![Rect {
+            left: 0,
+            top: 0,
+            right: 1920,
+            bottom: 1080,
+        }];
+
+        let (rect, _) = drag.snap(
+            Rect {
+                left: 5,
+                top: 100,
+                right: 1910,
+                bottom: 975,
+            },
+            10,
+        );
+
+        // the left edge is within the distance but stays in place
+        assert_eq!(
+            rect,
+            Rect {
+                left: 5,
+                top: 100,
+                right: 1915,
+                bottom: 980,
+            }
+        );
+    }
+}
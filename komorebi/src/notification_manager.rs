@@ -0,0 +1,161 @@
+#![deny(clippy::unwrap_used, clippy::expect_used)]
+
+use crate::push_notification;
+use crate::SubscriptionEvent;
+use crate::NOTIFICATION_COALESCING_WINDOW;
+
+use crossbeam_channel::Receiver;
+use crossbeam_channel::RecvTimeoutError;
+use crossbeam_channel::Sender;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::time::Instant;
+
+/// A serialized notification waiting to be pushed to subscribers
+pub struct Notification {
+    pub sequence: u64,
+    pub notification: String,
+    pub state: String,
+    pub event_kind: Option<SubscriptionEvent>,
+    pub state_has_been_modified: bool,
+    pub is_subscription_event: bool,
+    pub is_override_event: bool,
+    pub is_cloak_event: bool,
+}
+
+struct LastNotification {
+    sequence: u64,
+    state: String,
+    is_cloak_event: bool,
+    pushed_at: Instant,
+}
+
+static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+static CHANNEL: OnceLock<(Sender<Notification>, Receiver<Notification>)> = OnceLock::new();
+
+pub fn channel() -> &'static (Sender<Notification>, Receiver<Notification>) {
+    CHANNEL.get_or_init(crossbeam_channel::unbounded)
+}
+
+fn event_tx() -> Sender<Notification> {
+    channel().0.clone()
+}
+
+fn event_rx() -> Receiver<Notification> {
+    channel().1.clone()
+}
+
+/// The sequence number to tag the next notification with; notifications are created while the
+/// window manager is locked, so these follow the order in which states were produced
+pub fn next_sequence() -> u64 {
+    SEQUENCE.fetch_add(1, Ordering::SeqCst)
+}
+
+pub fn send_notification(notification: Notification) {
+    if event_tx().send(notification).is_err() {
+        tracing::warn!("channel is closed; dropping notification")
+    }
+}
+
+pub fn listen_for_notifications() {
+    std::thread::spawn(move || loop {
+        match handle_notifications() {
+            Ok(()) => {
+                tracing::warn!("restarting finished thread");
+            }
+            Err(error) => {
+                tracing::warn!("restarting failed thread: {}", error);
+            }
+        }
+    });
+}
+
+pub fn handle_notifications() -> color_eyre::Result<()> {
+    tracing::info!("listening");
+
+    let receiver = event_rx();
+    let mut last: Option<LastNotification> = None;
+    let mut pending: Option<(Notification, Instant)> = None;
+
+    loop {
+        let notification = match &pending {
+            Some((_, deadline)) => match receiver.recv_deadline(*deadline) {
+                Ok(notification) => notification,
+                Err(RecvTimeoutError::Timeout) => {
+                    // Nothing newer arrived within the coalescing window, so the latest
+                    // coalesced notification can be pushed
+                    if let Some((notification, _)) = pending.take() {
+                        push(notification, &mut last);
+                    }
+
+                    continue;
+                }
+                Err(RecvTimeoutError::Disconnected) => return Ok(()),
+            },
+            None => receiver.recv()?,
+        };
+
+        if last
+            .as_ref()
+            .is_some_and(|last| notification.sequence <= last.sequence)
+        {
+            tracing::trace!("dropping notification older than the last one pushed");
+            continue;
+        }
+
+        let coalescing_window = NOTIFICATION_COALESCING_WINDOW.load(Ordering::SeqCst);
+
+        if coalescing_window != 0 && !notification.is_subscription_event {
+            if let Some(last) = &last {
+                if last.pushed_at.elapsed() < Duration::from_millis(coalescing_window) {
+                    // Identical consecutive states don't tell subscribers anything new, and any
+                    // coalesced notification would now be older than what they already have
+                    if last.state == notification.state
+                        && (!notification.is_override_event || notification.is_cloak_event)
+                    {
+                        tracing::trace!("suppressing notification with an unchanged state");
+                        pending = None;
+                        continue;
+                    }
+
+                    // Cloak/uncloak chatter is coalesced and only the latest notification is
+                    // pushed once the coalescing window has elapsed
+                    if notification.is_cloak_event && last.is_cloak_event {
+                        let deadline = pending.as_ref().map_or_else(
+                            || Instant::now() + Duration::from_millis(coalescing_window),
+                            |(_, deadline)| *deadline,
+                        );
+
+                        pending = Some((notification, deadline));
+                        continue;
+                    }
+                }
+            }
+        }
+
+        // Any coalesced notification has now been superseded by this one
+        pending = None;
+        push(notification, &mut last);
+    }
+}
+
+fn push(notification: Notification, last: &mut Option<LastNotification>) {
+    if let Err(error) = push_notification(
+        &notification.notification,
+        notification.event_kind,
+        notification.state_has_been_modified,
+        notification.is_override_event,
+    ) {
+        tracing::error!("could not push notification: {error}");
+    }
+
+    *last = Some(LastNotification {
+        sequence: notification.sequence,
+        state: notification.state,
+        is_cloak_event: notification.is_cloak_event,
+        pushed_at: Instant::now(),
+    });
+}
@@ -5,6 +5,7 @@ use komorebi_themes::colour::Rgb;
 use miow::pipe::connect;
 use net2::TcpStreamExt;
 use parking_lot::Mutex;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::fs::File;
 use std::fs::OpenOptions;
@@ -25,6 +26,7 @@ use crate::animation::ANIMATION_DURATION_PER_ANIMATION;
 use crate::animation::ANIMATION_ENABLED_GLOBAL;
 use crate::animation::ANIMATION_ENABLED_PER_ANIMATION;
 use crate::animation::ANIMATION_FPS;
+use crate::animation::ANIMATION_MAX_CONCURRENT;
 use crate::animation::ANIMATION_STYLE_GLOBAL;
 use crate::animation::ANIMATION_STYLE_PER_ANIMATION;
 use crate::border_manager;
@@ -36,8 +38,8 @@ use crate::core::config_generation::IdWithIdentifier;
 use crate::core::config_generation::MatchingRule;
 use crate::core::config_generation::MatchingStrategy;
 use crate::core::ApplicationIdentifier;
-use crate::core::Axis;
 use crate::core::BorderImplementation;
+use crate::core::DefaultLayout;
 use crate::core::FocusFollowsMouseImplementation;
 use crate::core::Layout;
 use crate::core::MoveBehaviour;
@@ -52,10 +54,15 @@ use crate::current_virtual_desktop;
 use crate::default_layout::LayoutOptions;
 use crate::default_layout::ScrollingLayoutOptions;
 use crate::monitor::MonitorInformation;
+use crate::monitor_reconciliator;
 use crate::notify_subscribers;
+use crate::process_movement;
+use crate::resize_adjustments;
+use crate::session::Session;
 use crate::stackbar_manager;
 use crate::stackbar_manager::STACKBAR_FONT_FAMILY;
 use crate::stackbar_manager::STACKBAR_FONT_SIZE;
+use crate::state_diff::DiffSubscriber;
 use crate::static_config::StaticConfig;
 use crate::theme_manager;
 use crate::transparency_manager;
@@ -70,7 +77,9 @@ use crate::workspace::WorkspaceWindowLocation;
 use crate::GlobalState;
 use crate::Notification;
 use crate::NotificationEvent;
+use crate::Pong;
 use crate::State;
+use crate::BARS_HIDDEN;
 use crate::CUSTOM_FFM;
 use crate::DATA_DIR;
 use crate::DISPLAY_INDEX_PREFERENCES;
@@ -83,9 +92,12 @@ use crate::MANAGE_IDENTIFIERS;
 use crate::MONITOR_INDEX_PREFERENCES;
 use crate::NO_TITLEBAR;
 use crate::OBJECT_NAME_CHANGE_ON_LAUNCH;
+use crate::PRIVACY_MODE;
 use crate::REMOVE_TITLEBARS;
 use crate::SESSION_FLOATING_APPLICATIONS;
+use crate::SUBSCRIPTION_DIFF_SOCKETS;
 use crate::SUBSCRIPTION_PIPES;
+use crate::SUBSCRIPTION_PIPE_OPTIONS;
 use crate::SUBSCRIPTION_SOCKETS;
 use crate::SUBSCRIPTION_SOCKET_OPTIONS;
 use crate::TCP_CONNECTIONS;
@@ -193,6 +205,17 @@ impl WindowManager {
         message: SocketMessage,
         mut reply: impl std::io::Write,
     ) -> Result<()> {
+        // The wrapped message is processed on its own so that it is the one subscribers are
+        // notified about, and mouse follows focus is restored even if it fails
+        if let SocketMessage::NoMouseFollowsFocus(message) = message {
+            let mouse_follows_focus = self.mouse_follows_focus;
+            self.mouse_follows_focus = false;
+            let result = self.process_command(*message, reply);
+            self.mouse_follows_focus = mouse_follows_focus;
+
+            return result;
+        }
+
         if let Some(virtual_desktop_id) = &self.virtual_desktop_id {
             if let Some(id) = current_virtual_desktop() {
                 if id != *virtual_desktop_id {
@@ -366,18 +389,80 @@ impl WindowManager {
                 }
                 self.focus_container_window(idx)?;
             }
+            SocketMessage::FocusHwnd(hwnd) => {
+                self.focus_hwnd(hwnd)?;
+            }
+            SocketMessage::MoveStackWindow(hwnd, idx) => {
+                self.move_stack_window(hwnd, idx)?;
+            }
+            SocketMessage::Mark(ref mark) => {
+                let hwnd = self.focused_window()?.hwnd;
+                self.marks.insert(mark.clone(), hwnd);
+            }
+            SocketMessage::Unmark(ref mark) => {
+                self.marks.remove(mark);
+            }
+            SocketMessage::JumpToMark(ref mark) => {
+                let hwnd = *self
+                    .marks
+                    .get(mark)
+                    .ok_or_else(|| anyhow!("there is no window marked as {mark}"))?;
+
+                if let Err(error) = self.focus_hwnd(hwnd) {
+                    // the marked window has been closed or is no longer managed
+                    self.marks.remove(mark);
+                    return Err(error);
+                }
+            }
+            SocketMessage::FocusLastWindow => {
+                let current = WindowsApi::foreground_window().unwrap_or_default();
+                let workspace = self.focused_workspace_mut()?;
+                let hwnd = workspace.last_focused_window(current).ok_or_else(|| {
+                    anyhow!("there is no previously focused window on this workspace")
+                })?;
+
+                if workspace.container_idx_for_window(hwnd).is_some() {
+                    workspace.focus_container_by_window(hwnd)?;
+                    workspace.set_layer(WorkspaceLayer::Tiling);
+                } else if workspace.floating_windows().iter().any(|w| w.hwnd == hwnd) {
+                    workspace.set_layer(WorkspaceLayer::Floating);
+                }
+
+                self.update_focused_workspace(self.mouse_follows_focus, false)?;
+                Window::from(hwnd).focus(self.mouse_follows_focus)?;
+            }
             SocketMessage::ForceFocus => {
                 let focused_window = self.focused_window()?;
                 let focused_window_rect = WindowsApi::window_rect(focused_window.hwnd)?;
                 WindowsApi::center_cursor_in_rect(&focused_window_rect)?;
                 WindowsApi::left_click();
             }
-            SocketMessage::Close => {
-                Window::from(WindowsApi::foreground_window()?).close()?;
+            SocketMessage::Close | SocketMessage::Minimize => {
+                let window = Window::from(WindowsApi::foreground_window()?);
+                if window.is_minimize_to_tray() {
+                    self.hide_to_tray(window)?;
+                } else if matches!(message, SocketMessage::Close) {
+                    window.close()?;
+                } else {
+                    window.minimize();
+                }
             }
-            SocketMessage::Minimize => {
-                Window::from(WindowsApi::foreground_window()?).minimize();
+            SocketMessage::CloseWindow(hwnd) | SocketMessage::MinimizeWindow(hwnd) => {
+                // only act on windows managed by komorebi, not on any window a client names
+                if !self.known_hwnds.contains_key(&hwnd) {
+                    return Err(anyhow!("there is no managed window with hwnd {hwnd}"));
+                }
+
+                let window = Window::from(hwnd);
+                if window.is_minimize_to_tray() && self.focused_workspace()?.contains_window(hwnd) {
+                    self.hide_to_tray(window)?;
+                } else if matches!(message, SocketMessage::CloseWindow(_)) {
+                    window.close()?;
+                } else {
+                    window.minimize();
+                }
             }
+            SocketMessage::RestoreHidden => self.restore_hidden_windows()?,
             SocketMessage::LockMonitorWorkspaceContainer(
                 monitor_idx,
                 workspace_idx,
@@ -418,8 +503,13 @@ impl WindowManager {
             }
             SocketMessage::ToggleLock => self.toggle_lock()?,
             SocketMessage::ToggleFloat => self.toggle_float(false)?,
+            SocketMessage::ToggleAlwaysOnTop => self.toggle_always_on_top()?,
             SocketMessage::ToggleMonocle => self.toggle_monocle()?,
             SocketMessage::ToggleMaximize => self.toggle_maximize()?,
+            SocketMessage::ToggleZoom => {
+                self.focused_workspace_mut()?.toggle_zoom()?;
+                self.update_focused_workspace(self.mouse_follows_focus, false)?;
+            }
             SocketMessage::ContainerPadding(monitor_idx, workspace_idx, size) => {
                 self.set_container_padding(monitor_idx, workspace_idx, size)?;
             }
@@ -451,6 +541,7 @@ impl WindowManager {
                         matching_strategy: Some(MatchingStrategy::Legacy),
                     }),
                     initial_only: true,
+                    follow: false,
                 };
 
                 if !workspace_rules.contains(&workspace_matching_rule) {
@@ -471,6 +562,7 @@ impl WindowManager {
                             matching_strategy: Some(MatchingStrategy::Legacy),
                         }),
                         initial_only: true,
+                        follow: false,
                     };
 
                     if !workspace_rules.contains(&workspace_matching_rule) {
@@ -489,6 +581,7 @@ impl WindowManager {
                         matching_strategy: Some(MatchingStrategy::Legacy),
                     }),
                     initial_only: false,
+                    follow: false,
                 };
 
                 if !workspace_rules.contains(&workspace_matching_rule) {
@@ -509,6 +602,7 @@ impl WindowManager {
                             matching_strategy: Some(MatchingStrategy::Legacy),
                         }),
                         initial_only: false,
+                        follow: false,
                     };
 
                     if !workspace_rules.contains(&workspace_matching_rule) {
@@ -796,6 +890,9 @@ impl WindowManager {
             SocketMessage::SwapWorkspacesToMonitorNumber(monitor_idx) => {
                 self.swap_focused_monitor(monitor_idx)?;
             }
+            SocketMessage::SwapWorkspaces(monitor_idx, first, second) => {
+                self.swap_workspaces(monitor_idx, first, second)?;
+            }
             SocketMessage::CycleMoveContainerToMonitor(direction) => {
                 let monitor_idx = direction.next_idx(
                     self.focused_monitor_idx(),
@@ -887,6 +984,9 @@ impl WindowManager {
             SocketMessage::MoveWorkspaceToMonitorNumber(monitor_idx) => {
                 self.move_workspace_to_monitor(monitor_idx)?;
             }
+            SocketMessage::CloneWorkspaceToMonitorNumber(monitor_idx) => {
+                self.clone_workspace_to_monitor(monitor_idx)?;
+            }
             SocketMessage::CycleMoveWorkspaceToMonitor(direction) => {
                 let monitor_idx = direction.next_idx(
                     self.focused_monitor_idx(),
@@ -909,6 +1009,14 @@ impl WindowManager {
             SocketMessage::ToggleTiling => {
                 self.toggle_tiling()?;
             }
+            SocketMessage::ReserveTile(ref exe, here) => {
+                self.focused_workspace_mut()?.reserve_tile(exe, here);
+                self.update_focused_workspace(false, false)?;
+            }
+            SocketMessage::ClearReservedTiles => {
+                self.focused_workspace_mut()?.reserved_tiles_mut().clear();
+                self.update_focused_workspace(false, false)?;
+            }
             SocketMessage::CycleFocusMonitor(direction) => {
                 let monitor_idx = direction.next_idx(
                     self.focused_monitor_idx(),
@@ -938,6 +1046,28 @@ impl WindowManager {
                 force_update_borders = true;
                 self.retile_all(true)?
             }
+            SocketMessage::RetileMonitor(monitor_idx) => {
+                let workspace_idx = self
+                    .monitors()
+                    .get(monitor_idx)
+                    .ok_or_else(|| anyhow!("there is no monitor at index {monitor_idx}"))?
+                    .focused_workspace_idx();
+
+                force_update_borders = true;
+                self.retile_workspace(monitor_idx, workspace_idx, false)?
+            }
+            SocketMessage::RetileWorkspace(monitor_idx, workspace_idx) => {
+                force_update_borders = true;
+                self.retile_workspace(monitor_idx, workspace_idx, false)?
+            }
+            SocketMessage::RetileNamedWorkspace(ref workspace) => {
+                let (monitor_idx, workspace_idx) = self
+                    .monitor_workspace_index_by_name(workspace)
+                    .ok_or_else(|| anyhow!("there is no workspace named {workspace}"))?;
+
+                force_update_borders = true;
+                self.retile_workspace(monitor_idx, workspace_idx, false)?
+            }
             SocketMessage::FlipLayout(layout_flip) => self.flip_layout(layout_flip)?,
             SocketMessage::ScrollingLayoutColumns(count) => {
                 let focused_workspace = self.focused_workspace_mut()?;
@@ -954,6 +1084,8 @@ impl WindowManager {
                         scrolling: Some(ScrollingLayoutOptions {
                             columns: count.into(),
                         }),
+                        centered_master: None,
+                        grid: None,
                     },
                 };
 
@@ -1399,6 +1531,9 @@ impl WindowManager {
                 let mut display_index_preferences = DISPLAY_INDEX_PREFERENCES.write();
                 display_index_preferences.insert(index_preference, display.clone());
             }
+            SocketMessage::PersistMonitorIndices => {
+                monitor_reconciliator::persist_display_index_preferences(self)?;
+            }
             SocketMessage::EnsureWorkspaces(monitor_idx, workspace_count) => {
                 self.ensure_workspaces_for_monitor(monitor_idx, workspace_count)?;
             }
@@ -1436,6 +1571,9 @@ impl WindowManager {
 
                 tracing::info!("replying to global state done");
             }
+            SocketMessage::Ping => {
+                reply_to_ping(&mut reply)?;
+            }
             SocketMessage::VisibleWindows => {
                 let mut monitor_visible_windows = HashMap::new();
 
@@ -1489,6 +1627,10 @@ impl WindowManager {
                             .unwrap_or_else(|| focused_monitor.focused_workspace_idx().to_string())
                     }
                     StateQuery::Version => build::RUST_VERSION.to_string(),
+                    StateQuery::Marks => {
+                        let marks = self.marks.iter().collect::<BTreeMap<_, _>>();
+                        serde_json::to_string_pretty(&marks)?
+                    }
                     StateQuery::FocusedWorkspaceLayout => {
                         let focused_monitor = self
                             .focused_monitor()
@@ -1498,10 +1640,16 @@ impl WindowManager {
                             || "None".to_string(),
                             |layout| match layout {
                                 Layout::Default(default_layout) => default_layout.to_string(),
-                                Layout::Custom(_) => "Custom".to_string(),
+                                Layout::Custom(custom) => {
+                                    custom.name.clone().unwrap_or_else(|| "Custom".to_string())
+                                }
                             },
                         )
                     }
+                    StateQuery::FocusedWindowHidingBehaviour => {
+                        self.focused_window()?.hiding_behaviour().to_string()
+                    }
+                    StateQuery::WindowHidingBehaviour => HIDING_BEHAVIOUR.lock().to_string(),
                     StateQuery::FocusedContainerKind => {
                         match self.focused_workspace()?.focused_container() {
                             None => "None".to_string(),
@@ -1521,110 +1669,44 @@ impl WindowManager {
             SocketMessage::ResizeWindowEdge(direction, sizing) => {
                 self.resize_window(direction, sizing, self.resize_delta, true)?;
             }
-            SocketMessage::ResizeWindowAxis(axis, sizing) => {
-                // If the user has a custom layout, allow for the resizing of the primary column
-                // with this signal
+            SocketMessage::ResizeSplit(direction, delta) => {
+                self.focused_workspace_mut()?
+                    .resize_split(direction, delta)?;
+                self.update_focused_workspace(self.mouse_follows_focus, false)?;
+            }
+            SocketMessage::Split(direction) => {
                 let workspace = self.focused_workspace_mut()?;
-                let container_len = workspace.containers().len();
-                let no_layout_rules = workspace.layout_rules().is_empty();
-
-                if let Layout::Custom(ref mut custom) = workspace.layout_mut() {
-                    if matches!(axis, Axis::Horizontal) {
-                        #[allow(clippy::cast_precision_loss)]
-                        let percentage = custom
-                            .primary_width_percentage()
-                            .unwrap_or(100.0 / (custom.len() as f32));
-
-                        if no_layout_rules {
-                            match sizing {
-                                Sizing::Increase => {
-                                    custom.set_primary_width_percentage(percentage + 5.0);
-                                }
-                                Sizing::Decrease => {
-                                    custom.set_primary_width_percentage(percentage - 5.0);
-                                }
-                            }
-                        } else {
-                            for rule in workspace.layout_rules_mut() {
-                                if container_len >= rule.0 {
-                                    if let Layout::Custom(ref mut custom) = rule.1 {
-                                        match sizing {
-                                            Sizing::Increase => {
-                                                custom
-                                                    .set_primary_width_percentage(percentage + 5.0);
-                                            }
-                                            Sizing::Decrease => {
-                                                custom
-                                                    .set_primary_width_percentage(percentage - 5.0);
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    // Otherwise proceed with the resizing logic for individual window containers in the
-                    // assumed BSP layout
-                } else {
-                    match axis {
-                        Axis::Horizontal => {
-                            self.resize_window(
-                                OperationDirection::Left,
-                                sizing,
-                                self.resize_delta,
-                                false,
-                            )?;
-                            self.resize_window(
-                                OperationDirection::Right,
-                                sizing,
-                                self.resize_delta,
-                                false,
-                            )?;
-                        }
-                        Axis::Vertical => {
-                            self.resize_window(
-                                OperationDirection::Up,
-                                sizing,
-                                self.resize_delta,
-                                false,
-                            )?;
-                            self.resize_window(
-                                OperationDirection::Down,
-                                sizing,
-                                self.resize_delta,
-                                false,
-                            )?;
-                        }
-                        Axis::HorizontalAndVertical => {
-                            self.resize_window(
-                                OperationDirection::Left,
-                                sizing,
-                                self.resize_delta,
-                                false,
-                            )?;
-                            self.resize_window(
-                                OperationDirection::Right,
-                                sizing,
-                                self.resize_delta,
-                                false,
-                            )?;
-                            self.resize_window(
-                                OperationDirection::Up,
-                                sizing,
-                                self.resize_delta,
-                                false,
-                            )?;
-                            self.resize_window(
-                                OperationDirection::Down,
-                                sizing,
-                                self.resize_delta,
-                                false,
-                            )?;
-                        }
-                    }
+                if !matches!(workspace.layout(), Layout::Default(DefaultLayout::Manual)) {
+                    return Err(anyhow!(
+                        "the split direction can only be chosen on workspaces using the Manual layout"
+                    ));
                 }
 
-                self.update_focused_workspace(false, false)?;
+                workspace.manual_layout_mut().next_split = Option::from(direction);
+            }
+            SocketMessage::ResizeWindowAxis(axis, sizing) => {
+                self.resize_window_axis(axis, sizing, self.resize_delta, self.resize_delta, 5.0)?;
+            }
+            SocketMessage::ResizeWindowPercent(axis, percent) => {
+                let sizing = if percent < 0 {
+                    Sizing::Decrease
+                } else {
+                    Sizing::Increase
+                };
+
+                let work_area = self.focused_monitor_work_area()?;
+                let horizontal_delta = work_area.right * percent.abs() / 100;
+                let vertical_delta = work_area.bottom * percent.abs() / 100;
+                #[allow(clippy::cast_precision_loss)]
+                let primary_width_delta = percent.abs() as f32;
+
+                self.resize_window_axis(
+                    axis,
+                    sizing,
+                    horizontal_delta,
+                    vertical_delta,
+                    primary_width_delta,
+                )?;
             }
             SocketMessage::FocusFollowsMouse(mut implementation, enable) => {
                 if !CUSTOM_FFM.load(Ordering::SeqCst) {
@@ -1913,6 +1995,22 @@ if (!(Get-Process komorebi-bar -ErrorAction SilentlyContinue))
 
                 self.retile_all(true)?;
             }
+            SocketMessage::ToggleBar => {
+                let hidden = !BARS_HIDDEN.fetch_xor(true, Ordering::SeqCst);
+                tracing::info!("bars hidden: {hidden}");
+
+                // The bars hide or show themselves when they are notified of the new state, while
+                // the work area offsets they have set on each monitor are all released or
+                // re-applied here in a single retile
+                self.retile_all(false)?;
+            }
+            SocketMessage::PrivacyMode(enable) => {
+                PRIVACY_MODE.store(enable, Ordering::SeqCst);
+                tracing::info!("privacy mode: {enable}");
+
+                // The stackbars are the only place where komorebi itself draws window titles
+                stackbar_manager::send_notification();
+            }
             SocketMessage::QuickSave => {
                 let workspace = self.focused_workspace()?;
                 let resize = workspace.resize_dimensions();
@@ -1963,22 +2061,37 @@ if (!(Get-Process komorebi-bar -ErrorAction SilentlyContinue))
                 workspace.set_resize_dimensions(resize);
                 self.update_focused_workspace(false, false)?;
             }
+            SocketMessage::SaveSession(ref path) => {
+                Session::from(&*self).save(path)?;
+            }
+            SocketMessage::LoadSession(ref path) => {
+                let session = Session::load(path)?;
+                self.restore_session(&session)?;
+            }
             SocketMessage::AddSubscriberSocket(ref socket) => {
                 let mut sockets = SUBSCRIPTION_SOCKETS.lock();
                 let socket_path = DATA_DIR.join(socket);
                 sockets.insert(socket.clone(), socket_path);
             }
-            SocketMessage::AddSubscriberSocketWithOptions(ref socket, options) => {
+            SocketMessage::AddSubscriberSocketWithOptions(ref socket, ref options) => {
                 let mut sockets = SUBSCRIPTION_SOCKETS.lock();
                 let socket_path = DATA_DIR.join(socket);
                 sockets.insert(socket.clone(), socket_path);
 
                 let mut socket_options = SUBSCRIPTION_SOCKET_OPTIONS.lock();
-                socket_options.insert(socket.clone(), options);
+                socket_options.insert(socket.clone(), options.clone());
             }
             SocketMessage::RemoveSubscriberSocket(ref socket) => {
                 let mut sockets = SUBSCRIPTION_SOCKETS.lock();
                 sockets.remove(socket);
+
+                let mut diff_sockets = SUBSCRIPTION_DIFF_SOCKETS.lock();
+                diff_sockets.remove(socket);
+            }
+            SocketMessage::SubscribeSocketDiff(ref socket) => {
+                let mut diff_sockets = SUBSCRIPTION_DIFF_SOCKETS.lock();
+                let socket_path = DATA_DIR.join(socket);
+                diff_sockets.insert(socket.clone(), DiffSubscriber::new(socket_path));
             }
             SocketMessage::AddSubscriberPipe(ref subscriber) => {
                 let mut pipes = SUBSCRIPTION_PIPES.lock();
@@ -1989,9 +2102,24 @@ if (!(Get-Process komorebi-bar -ErrorAction SilentlyContinue))
 
                 pipes.insert(subscriber.clone(), pipe);
             }
+            SocketMessage::AddSubscriberPipeWithOptions(ref subscriber, ref options) => {
+                let mut pipes = SUBSCRIPTION_PIPES.lock();
+                let pipe_path = format!(r"\\.\pipe\{subscriber}");
+                let pipe = connect(&pipe_path).map_err(|_| {
+                    anyhow!("the named pipe '{}' has not yet been created; please create it before running this command", pipe_path)
+                })?;
+
+                pipes.insert(subscriber.clone(), pipe);
+
+                let mut pipe_options = SUBSCRIPTION_PIPE_OPTIONS.lock();
+                pipe_options.insert(subscriber.clone(), options.clone());
+            }
             SocketMessage::RemoveSubscriberPipe(ref subscriber) => {
                 let mut pipes = SUBSCRIPTION_PIPES.lock();
                 pipes.remove(subscriber);
+
+                let mut pipe_options = SUBSCRIPTION_PIPE_OPTIONS.lock();
+                pipe_options.remove(subscriber);
             }
             SocketMessage::MouseFollowsFocus(enable) => {
                 self.mouse_follows_focus = enable;
@@ -2049,6 +2177,11 @@ if (!(Get-Process komorebi-bar -ErrorAction SilentlyContinue))
                         .set_float_override(Some(!current_global_override));
                 };
             }
+            SocketMessage::ToggleSmartGaps => {
+                let workspace = self.focused_workspace_mut()?;
+                workspace.set_smart_gaps(!workspace.smart_gaps());
+                self.update_focused_workspace(false, false)?;
+            }
             SocketMessage::WindowHidingBehaviour(behaviour) => {
                 let mut hiding_behaviour = HIDING_BEHAVIOUR.lock();
                 *hiding_behaviour = behaviour;
@@ -2164,6 +2297,9 @@ if (!(Get-Process komorebi-bar -ErrorAction SilentlyContinue))
             SocketMessage::AnimationFps(fps) => {
                 ANIMATION_FPS.store(fps, Ordering::SeqCst);
             }
+            SocketMessage::AnimationMaxConcurrent(max) => {
+                ANIMATION_MAX_CONCURRENT.store(max, Ordering::SeqCst);
+            }
             SocketMessage::AnimationStyle(style, prefix) => match prefix {
                 Some(prefix) => {
                     ANIMATION_STYLE_PER_ANIMATION.lock().insert(prefix, style);
@@ -2302,6 +2438,9 @@ if (!(Get-Process komorebi-bar -ErrorAction SilentlyContinue))
             SocketMessage::Theme(ref theme) => {
                 theme_manager::send_notification(*theme.clone());
             }
+            SocketMessage::NoMouseFollowsFocus(_) => {
+                unreachable!("wrapped messages are unwrapped before being matched")
+            }
             // Deprecated commands
             SocketMessage::AltFocusHack(_)
             | SocketMessage::IdentifyBorderOverflowApplication(_, _) => {}
@@ -2310,6 +2449,10 @@ if (!(Get-Process komorebi-bar -ErrorAction SilentlyContinue))
         // Update list of known_hwnds and their monitor/workspace index pair
         self.update_known_hwnds();
 
+        process_movement::set_focus_follows_mouse(self.focus_follows_mouse);
+
+        resize_adjustments::persist(self);
+
         notify_subscribers(
             Notification {
                 event: NotificationEvent::Socket(message.clone()),
@@ -2331,6 +2474,15 @@ if (!(Get-Process komorebi-bar -ErrorAction SilentlyContinue))
     }
 }
 
+/// Pings are answered without the window manager lock so that clients can check that komorebi
+/// is alive even while it is busy processing events
+fn reply_to_ping(mut reply: impl std::io::Write) -> Result<()> {
+    let pong = serde_json::to_string_pretty(&Pong::now()).unwrap_or_else(|error| error.to_string());
+    reply.write_all(pong.as_bytes())?;
+
+    Ok(())
+}
+
 pub fn read_commands_uds(wm: &Arc<Mutex<WindowManager>>, mut stream: UnixStream) -> Result<()> {
     let reader = BufReader::new(stream.try_clone()?);
     // TODO(raggi): while this processes more than one command, if there are
@@ -2340,6 +2492,11 @@ pub fn read_commands_uds(wm: &Arc<Mutex<WindowManager>>, mut stream: UnixStream)
     for line in reader.lines() {
         let message = SocketMessage::from_str(&line?)?;
 
+        if matches!(message, SocketMessage::Ping) {
+            reply_to_ping(&mut stream)?;
+            continue;
+        }
+
         match wm.try_lock_for(Duration::from_secs(1)) {
             None => {
                 tracing::warn!(
@@ -2393,6 +2550,11 @@ pub fn read_commands_tcp(
                     break;
                 };
 
+                if matches!(message, SocketMessage::Ping) {
+                    reply_to_ping(&mut *stream)?;
+                    continue;
+                }
+
                 let mut wm = wm.lock();
 
                 if wm.is_paused {
@@ -15,7 +15,9 @@ use crate::border_manager;
 use crate::border_manager::BORDER_OFFSET;
 use crate::border_manager::BORDER_WIDTH;
 use crate::current_virtual_desktop;
+use crate::mouse_hook;
 use crate::notify_subscribers;
+use crate::resize_adjustments;
 use crate::stackbar_manager;
 use crate::transparency_manager;
 use crate::window::should_act;
@@ -27,6 +29,7 @@ use crate::winevent::WinEvent;
 use crate::workspace::WorkspaceLayer;
 use crate::DefaultLayout;
 use crate::Layout;
+use crate::NewWindowFocusBehaviour;
 use crate::Notification;
 use crate::NotificationEvent;
 use crate::State;
@@ -35,6 +38,11 @@ use crate::Window;
 use crate::CURRENT_VIRTUAL_DESKTOP;
 use crate::FLOATING_APPLICATIONS;
 use crate::HIDDEN_HWNDS;
+use crate::HIDING_BEHAVIOURS;
+use crate::MAXIMIZED_MONOCLE_HWNDS;
+use crate::MAXIMIZE_MONOCLE_MATCHES;
+use crate::MINIMUM_TILE_SIZES;
+use crate::OFF_SCREEN_RECTS;
 use crate::REGEX_IDENTIFIERS;
 use crate::TRAY_AND_MULTI_WINDOW_IDENTIFIERS;
 
@@ -225,18 +233,115 @@ impl WindowManager {
         }
 
         match event {
+            // Windows which are always on top are kept out of the workspaces, so all that needs
+            // to be tracked for them is whether one of them is focused
+            _ if self.is_always_on_top(event.hwnd())
+                && !matches!(
+                    event,
+                    WindowManagerEvent::Destroy(..) | WindowManagerEvent::Unmanage(_)
+                ) =>
+            {
+                if let WindowManagerEvent::FocusChange(_, window) = event {
+                    for monitor in self.monitors_mut() {
+                        let focused = monitor
+                            .always_on_top()
+                            .iter()
+                            .any(|w| w.hwnd == window.hwnd);
+                        monitor.set_always_on_top_focused(focused);
+                    }
+                }
+            }
             WindowManagerEvent::Raise(window) => {
                 window.focus(false)?;
                 self.has_pending_raise_op = false;
             }
             WindowManagerEvent::Destroy(_, window) | WindowManagerEvent::Unmanage(window) => {
+                MINIMUM_TILE_SIZES.lock().remove(&window.hwnd);
+
+                // The handle may be reused by a new window once this one is gone
+                if matches!(event, WindowManagerEvent::Destroy(..)) {
+                    HIDING_BEHAVIOURS.lock().remove(&window.hwnd);
+                    OFF_SCREEN_RECTS.lock().remove(&window.hwnd);
+                    MAXIMIZE_MONOCLE_MATCHES.lock().remove(&window.hwnd);
+                    MAXIMIZED_MONOCLE_HWNDS.lock().remove(&window.hwnd);
+                }
+
+                // If a swallowed window is destroyed, there is nothing left to restore
+                self.swallowed_windows.retain(|_, w| w.hwnd != window.hwnd);
+                let swallowed_window = self.swallowed_windows.remove(&window.hwnd);
+
+                for monitor in self.monitors_mut() {
+                    // An always on top window which goes away no longer needs to be kept on top
+                    if let Some(idx) = monitor
+                        .always_on_top()
+                        .iter()
+                        .position(|w| w.hwnd == window.hwnd)
+                    {
+                        monitor.always_on_top_mut().remove(idx);
+                        monitor.set_always_on_top_focused(false);
+
+                        if matches!(event, WindowManagerEvent::Unmanage(_)) {
+                            WindowsApi::set_topmost(window.hwnd, false)?;
+                        }
+                    }
+
+                    // A window hidden by a minimize to tray rule has nothing left to restore either
+                    for workspace in monitor.workspaces_mut() {
+                        workspace
+                            .hidden_windows_mut()
+                            .retain(|w| w.hwnd != window.hwnd);
+                    }
+                }
+
                 if self.focused_workspace()?.contains_window(window.hwnd) {
-                    self.focused_workspace_mut()?.remove_window(window.hwnd)?;
+                    match swallowed_window {
+                        Some(swallowed) if swallowed.is_window() => {
+                            tracing::info!("restoring swallowed window {}", swallowed.hwnd);
+                            self.focused_workspace_mut()?
+                                .replace_window(window.hwnd, swallowed)?;
+                            swallowed.restore();
+                        }
+                        _ => {
+                            self.focused_workspace_mut()?.remove_window(window.hwnd)?;
+                        }
+                    }
+
                     self.update_focused_workspace(false, false)?;
+                    self.return_focus_to_owner(window.hwnd)?;
 
                     let mut already_moved_window_handles = self.already_moved_window_handles.lock();
 
                     already_moved_window_handles.remove(&window.hwnd);
+                } else {
+                    // The swallowing window may have been closed on a workspace which isn't
+                    // focused, in which case the swallowed window is restored in its place there
+                    if let (Some(swallowed), Some((m_idx, ws_idx))) = (
+                        swallowed_window.filter(|w| w.is_window()),
+                        self.known_hwnds.get(&window.hwnd).copied(),
+                    ) {
+                        let mut visible = false;
+
+                        if let Some(monitor) = self.monitors_mut().get_mut(m_idx) {
+                            let focused_workspace_idx = monitor.focused_workspace_idx();
+
+                            if let Some(workspace) = monitor.workspaces_mut().get_mut(ws_idx) {
+                                if workspace.contains_window(window.hwnd) {
+                                    tracing::info!("restoring swallowed window {}", swallowed.hwnd);
+                                    workspace.replace_window(window.hwnd, swallowed)?;
+                                    visible = focused_workspace_idx == ws_idx;
+                                }
+                            }
+                        }
+
+                        // Windows on workspaces which aren't visible are shown again when their
+                        // workspace is next focused
+                        if visible {
+                            swallowed.restore();
+                            self.update_focused_workspace_by_monitor_idx(m_idx)?;
+                        }
+                    }
+
+                    self.window_owners.remove(&window.hwnd);
                 }
             }
             WindowManagerEvent::Minimize(_, window) => {
@@ -249,7 +354,9 @@ impl WindowManager {
                     }
                 }
 
-                if hide {
+                if hide && window.is_minimize_to_tray() {
+                    self.hide_to_tray(window)?;
+                } else if hide {
                     self.focused_workspace_mut()?.remove_window(window.hwnd)?;
                     self.update_focused_workspace(false, false)?;
                 }
@@ -293,6 +400,7 @@ impl WindowManager {
                 if hide {
                     self.focused_workspace_mut()?.remove_window(window.hwnd)?;
                     self.update_focused_workspace(false, false)?;
+                    self.return_focus_to_owner(window.hwnd)?;
                 }
 
                 let mut already_moved_window_handles = self.already_moved_window_handles.lock();
@@ -300,6 +408,20 @@ impl WindowManager {
                 already_moved_window_handles.remove(&window.hwnd);
             }
             WindowManagerEvent::FocusChange(_, window) => {
+                for monitor in self.monitors_mut() {
+                    monitor.set_always_on_top_focused(false);
+                }
+
+                // focusing any other tiled window on the workspace ends a zoom
+                let workspace = self.focused_workspace_mut()?;
+                if workspace
+                    .zoomed_window()
+                    .is_some_and(|hwnd| hwnd != window.hwnd)
+                    && workspace.container_idx_for_window(window.hwnd).is_some()
+                {
+                    workspace.unzoom();
+                }
+
                 // don't want to trigger the full workspace updates when there are no managed
                 // containers - this makes floating windows on empty workspaces go into very
                 // annoying focus change loops which prevents users from interacting with them
@@ -312,6 +434,10 @@ impl WindowManager {
                 }
 
                 let workspace = self.focused_workspace_mut()?;
+                if workspace.contains_window(window.hwnd) {
+                    workspace.record_focus(window.hwnd);
+                }
+
                 let floating_window_idx = workspace
                     .floating_windows()
                     .iter()
@@ -359,6 +485,22 @@ impl WindowManager {
                     tracing::info!("ignoring uncloak after monocle move by mouse across monitors");
                     self.uncloack_to_ignore = self.uncloack_to_ignore.saturating_sub(1);
                 } else {
+                    // A window hidden to the tray which has been shown again by its application,
+                    // eg. from its tray icon, is managed like any other window from now on
+                    let was_hidden_to_tray = matches!(
+                        event,
+                        WindowManagerEvent::Show(_, _) | WindowManagerEvent::Manage(_)
+                    ) && self.forget_hidden_window(window.hwnd);
+
+                    // Only windows appearing for the first time can be refused focus, not ones
+                    // being restored or managed again
+                    let is_new_window = matches!(
+                        event,
+                        WindowManagerEvent::Show(winevent, _)
+                            if winevent != WinEvent::SystemMinimizeEnd
+                    ) && !was_hidden_to_tray
+                        && !self.known_hwnds.contains_key(&window.hwnd);
+
                     let focused_monitor_idx = self.focused_monitor_idx();
                     let focused_workspace_idx =
                         self.focused_workspace_idx_for_monitor_idx(focused_monitor_idx)?;
@@ -413,10 +555,39 @@ impl WindowManager {
                             focused_monitor_idx,
                             focused_workspace_idx,
                         );
+                        let swallowing_parent = if needs_reconciliation.is_none()
+                            && !self.focused_workspace()?.contains_window(window.hwnd)
+                        {
+                            self.swallowing_parent(window)?
+                        } else {
+                            None
+                        };
+                        let dialog_owner = if needs_reconciliation.is_none()
+                            && !self.focused_workspace()?.contains_window(window.hwnd)
+                        {
+                            self.transient_dialog_owner(window)?
+                        } else {
+                            None
+                        };
+                        if needs_reconciliation.is_none()
+                            && !self.focused_workspace()?.contains_window(window.hwnd)
+                        {
+                            self.track_window_owner(window)?;
+                        }
                         let workspace = self.focused_workspace_mut()?;
                         let workspace_contains_window = workspace.contains_window(window.hwnd);
                         let monocle_container = workspace.monocle_container().clone();
 
+                        // The window which keeps focus if this window shouldn't receive it
+                        let previously_focused = if workspace.layer() == &WorkspaceLayer::Floating {
+                            workspace.focused_floating_window().copied()
+                        } else {
+                            workspace
+                                .focused_container()
+                                .and_then(|c| c.focused_window())
+                                .copied()
+                        };
+
                         if !workspace_contains_window && needs_reconciliation.is_none() {
                             let floating_applications = FLOATING_APPLICATIONS.lock();
                             let mut should_float = false;
@@ -459,14 +630,38 @@ impl WindowManager {
                                     placement.should_center() && workspace.tile;
                                 workspace.floating_windows_mut().push_back(window);
                                 workspace.set_layer(WorkspaceLayer::Floating);
-                                if center_spawned_floats {
-                                    let mut floating_window = window;
+                                let mut floating_window = window;
+                                if !floating_window
+                                    .apply_floating_geometry(&workspace.globals().work_area)?
+                                    && center_spawned_floats
+                                {
                                     floating_window.center(
                                         &workspace.globals().work_area,
                                         placement.should_resize(),
                                     )?;
                                 }
                                 self.update_focused_workspace(false, false)?;
+                            } else if let Some(parent) = swallowing_parent {
+                                tracing::info!("swallowing window {}", parent.hwnd);
+                                workspace.replace_window(parent.hwnd, window)?;
+                                workspace.set_layer(WorkspaceLayer::Tiling);
+                                parent.hide();
+                                self.swallowed_windows.insert(window.hwnd, parent);
+                                self.update_focused_workspace(true, false)?;
+                            } else if let Some(owner) = dialog_owner {
+                                tracing::info!("stacking dialog on its owner {}", owner.hwnd);
+                                let container_idx = workspace
+                                    .container_idx_for_window(owner.hwnd)
+                                    .ok_or_else(|| anyhow!("there is no container"))?;
+                                workspace.containers_mut()[container_idx].add_window(window);
+                                workspace.focus_container(container_idx);
+                                workspace.set_layer(WorkspaceLayer::Tiling);
+                                self.update_focused_workspace(true, false)?;
+                                stackbar_manager::send_notification();
+                            } else if workspace.fill_reserved_tile(window) {
+                                tracing::info!("placing window in its reserved tile");
+                                workspace.set_layer(WorkspaceLayer::Tiling);
+                                self.update_focused_workspace(false, false)?;
                             } else {
                                 match behaviour.current_behaviour {
                                     WindowContainerBehaviour::Create => {
@@ -488,6 +683,15 @@ impl WindowManager {
                                 }
                             }
 
+                            if let Some(previous) = previously_focused.filter(|_| is_new_window) {
+                                if matches!(
+                                    window.new_window_focus_behaviour(),
+                                    NewWindowFocusBehaviour::Never
+                                ) {
+                                    self.refuse_new_window_focus(window, previous)?;
+                                }
+                            }
+
                             if (self.focused_workspace()?.containers().len() == 1
                                 && self.focused_workspace()?.floating_windows().is_empty())
                                 || (self.focused_workspace()?.containers().is_empty()
@@ -533,6 +737,7 @@ impl WindowManager {
                     .focused_workspace_idx();
 
                 WindowsApi::bring_window_to_top(window.hwnd)?;
+                mouse_hook::start_move_resize(window.hwnd);
 
                 let pending_move_op = Arc::make_mut(&mut self.pending_move_op);
                 *pending_move_op = Option::from((monitor_idx, workspace_idx, window.hwnd));
@@ -557,6 +762,8 @@ impl WindowManager {
                     }
                 }
 
+                mouse_hook::snap_after_move_resize(self, window.hwnd)?;
+
                 let target_monitor_idx = self
                     .monitor_idx_from_current_pos()
                     .ok_or_else(|| anyhow!("cannot get monitor idx from current position"))?;
@@ -778,6 +985,58 @@ impl WindowManager {
                     }
                 }
             }
+            WindowManagerEvent::MaximizeChange(_, window) => {
+                // The window isn't necessarily on the focused workspace, it may be on a
+                // workspace of another monitor
+                if let Some((monitor_idx, workspace_idx)) =
+                    self.known_hwnds.get(&window.hwnd).copied()
+                {
+                    let monitor = self
+                        .monitors_mut()
+                        .get_mut(monitor_idx)
+                        .ok_or_else(|| anyhow!("there is no monitor with this idx"))?;
+                    let is_visible = monitor.focused_workspace_idx() == workspace_idx;
+                    let workspace = monitor
+                        .workspaces_mut()
+                        .get_mut(workspace_idx)
+                        .ok_or_else(|| anyhow!("there is no workspace with this idx"))?;
+
+                    let in_monocle = workspace
+                        .monocle_container()
+                        .as_ref()
+                        .is_some_and(|container| container.contains_window(window.hwnd));
+
+                    let mut changed = false;
+
+                    if window.is_maximized() {
+                        if !in_monocle && workspace.container_idx_for_window(window.hwnd).is_some()
+                        {
+                            if workspace.monocle_container().is_some() {
+                                Self::monocle_off_for_workspace(workspace)?;
+                            }
+
+                            if let Some(idx) = workspace.container_idx_for_window(window.hwnd) {
+                                tracing::info!(
+                                    "maximized window {} is going into monocle",
+                                    window.hwnd
+                                );
+                                workspace.focus_container(idx);
+                                Self::monocle_on_for_workspace(workspace)?;
+                                MAXIMIZED_MONOCLE_HWNDS.lock().insert(window.hwnd);
+                                changed = true;
+                            }
+                        }
+                    } else if MAXIMIZED_MONOCLE_HWNDS.lock().remove(&window.hwnd) && in_monocle {
+                        tracing::info!("restored window {} is leaving monocle", window.hwnd);
+                        Self::monocle_off_for_workspace(workspace)?;
+                        changed = true;
+                    }
+
+                    if changed && is_visible {
+                        self.update_focused_workspace_by_monitor_idx(monitor_idx)?;
+                    }
+                }
+            }
             WindowManagerEvent::MouseCapture(..)
             | WindowManagerEvent::Cloak(..)
             | WindowManagerEvent::TitleUpdate(..) => {}
@@ -791,6 +1050,8 @@ impl WindowManager {
         // Update list of known_hwnds and their monitor/workspace index pair
         self.update_known_hwnds();
 
+        resize_adjustments::persist(self);
+
         notify_subscribers(
             Notification {
                 event: NotificationEvent::WindowManager(event),
@@ -1,44 +1,159 @@
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::sync::OnceLock;
+use std::thread::Thread;
+use std::time::Duration;
+use std::time::Instant;
 
 use parking_lot::Mutex;
-use winput::message_loop;
-use winput::message_loop::Event;
-use winput::Action;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_LBUTTON;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_MBUTTON;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_RBUTTON;
 
 use crate::core::FocusFollowsMouseImplementation;
-
+use crate::should_act;
+use crate::window::Window;
 use crate::window_manager::WindowManager;
+use crate::windows_api::WindowsApi;
+use crate::FOCUS_FOLLOWS_MOUSE_IGNORE_RULES;
+use crate::REGEX_IDENTIFIERS;
+
+/// How often the window under the cursor is checked
+const POLL_INTERVAL: Duration = Duration::from_millis(25);
+
+/// Window classes of menus, tooltips and popups, which would be closed if the window beneath them
+/// were raised while they are hovered
+const IGNORED_CLASSES: [&str; 6] = [
+    "#32768",
+    "tooltips_class32",
+    "Xaml_WindowedPopupClass",
+    "SysShadow",
+    "NotifyIconOverflowWindow",
+    "TopLevelWindowForOverflowXamlIsland",
+];
+
+/// How long the cursor has to rest over a window before it is raised, in milliseconds
+pub static FOCUS_FOLLOWS_MOUSE_DELAY: AtomicU64 = AtomicU64::new(0);
+
+/// Whether the komorebi implementation of focus follows mouse is enabled, mirrored from the window
+/// manager so that the movement thread doesn't have to lock it to find out
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The movement thread, which is parked while focus follows mouse is disabled
+static MOVEMENT_THREAD: OnceLock<Thread> = OnceLock::new();
+
+/// Mirrors the focus follows mouse implementation of the window manager, waking up the movement
+/// thread if the komorebi implementation has been enabled
+pub fn set_focus_follows_mouse(implementation: Option<FocusFollowsMouseImplementation>) {
+    let enabled = matches!(
+        implementation,
+        Some(FocusFollowsMouseImplementation::Komorebi)
+    );
+
+    if !ENABLED.swap(enabled, Ordering::SeqCst) && enabled {
+        if let Some(thread) = MOVEMENT_THREAD.get() {
+            thread.unpark();
+        }
+    }
+}
 
 #[tracing::instrument]
 pub fn listen_for_movements(wm: Arc<Mutex<WindowManager>>) {
+    set_focus_follows_mouse(wm.lock().focus_follows_mouse);
+
     std::thread::spawn(move || {
-        let mut ignore_movement = false;
+        let _ = MOVEMENT_THREAD.set(std::thread::current());
 
-        let receiver = message_loop::start().expect("could not start winput message loop");
+        let mut last_cursor_pos = None;
+        let mut hovered = None;
+        let mut hovered_since = Instant::now();
+        // Only raise after the cursor has moved, so that focus given with the keyboard isn't
+        // taken away again by a cursor which is resting over another window
+        let mut pending = false;
 
         loop {
-            let focus_follows_mouse = wm.lock().focus_follows_mouse;
-            if matches!(
-                focus_follows_mouse,
-                Some(FocusFollowsMouseImplementation::Komorebi)
-            ) {
-                match receiver.next_event() {
-                    // Don't want to send any raise events while we are dragging or resizing
-                    Event::MouseButton { action, .. } => match action {
-                        Action::Press => ignore_movement = true,
-                        Action::Release => ignore_movement = false,
-                    },
-                    Event::MouseMoveRelative { .. } => {
-                        if !ignore_movement {
-                            match wm.lock().raise_window_at_cursor_pos() {
-                                Ok(()) => {}
-                                Err(error) => tracing::error!("{}", error),
-                            }
-                        }
-                    }
-                    _ => {}
-                }
+            if !ENABLED.load(Ordering::SeqCst) {
+                pending = false;
+                std::thread::park();
+                continue;
+            }
+
+            std::thread::sleep(POLL_INTERVAL);
+
+            let Ok(cursor_pos) = WindowsApi::cursor_pos() else {
+                continue;
+            };
+
+            let cursor_pos = (cursor_pos.x, cursor_pos.y);
+            if last_cursor_pos.replace(cursor_pos) != Some(cursor_pos) {
+                pending = true;
+            }
+
+            let Ok(hwnd) = WindowsApi::window_at_cursor_pos() else {
+                continue;
+            };
+
+            let hwnd = WindowsApi::root_window(hwnd).unwrap_or(hwnd);
+            if hovered.replace(hwnd) != Some(hwnd) {
+                hovered_since = Instant::now();
+            }
+
+            let delay = Duration::from_millis(FOCUS_FOLLOWS_MOUSE_DELAY.load(Ordering::SeqCst));
+
+            // Don't want to send any raise events while we are dragging or resizing
+            if !pending
+                || hovered_since.elapsed() < delay
+                || [VK_LBUTTON, VK_RBUTTON, VK_MBUTTON]
+                    .into_iter()
+                    .any(WindowsApi::key_is_pressed)
+            {
+                continue;
+            }
+
+            pending = false;
+
+            if is_ignored(Window::from(hwnd)) {
+                continue;
+            }
+
+            if let Err(error) = wm.lock().raise_window_at_cursor_pos() {
+                tracing::error!("{}", error);
             }
         }
     });
 }
+
+/// Whether hovering over `window` should never raise the window beneath the cursor
+fn is_ignored(window: Window) -> bool {
+    let Ok(class) = window.class() else {
+        return true;
+    };
+
+    if IGNORED_CLASSES.contains(&class.as_str()) {
+        return true;
+    }
+
+    let ignore_rules = FOCUS_FOLLOWS_MOUSE_IGNORE_RULES.lock();
+    if ignore_rules.is_empty() {
+        return false;
+    }
+
+    let regex_identifiers = REGEX_IDENTIFIERS.lock();
+    let (title, exe, path) = (
+        window.title().unwrap_or_default(),
+        window.exe().unwrap_or_default(),
+        window.path().unwrap_or_default(),
+    );
+
+    should_act(
+        &title,
+        &exe,
+        &class,
+        &path,
+        &ignore_rules,
+        &regex_identifiers,
+    )
+    .is_some()
+}
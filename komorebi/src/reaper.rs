@@ -9,7 +9,6 @@ use crate::Window;
 use crate::WindowManager;
 use crate::WindowManagerEvent;
 use crate::DATA_DIR;
-use crate::HIDING_BEHAVIOUR;
 
 use crossbeam_channel::Receiver;
 use crossbeam_channel::Sender;
@@ -171,8 +170,6 @@ fn find_orphans() -> color_eyre::Result<()> {
 
     loop {
         std::thread::sleep(Duration::from_millis(20));
-        let hiding_behaviour = *HIDING_BEHAVIOUR.lock();
-
         let mut cache = HWNDS_CACHE.lock();
         let mut orphan_hwnds = HashMap::new();
 
@@ -190,7 +187,7 @@ fn find_orphans() -> color_eyre::Result<()> {
                     !window.is_visible()
                     // We cannot execute this lovely hack if the user is using HidingBehaviour::Hide because
                     // it will result in legitimate hidden, non-visible windows being yeeted from the state
-                    && !matches!(hiding_behaviour, HidingBehaviour::Hide)
+                    && !matches!(window.hiding_behaviour(), HidingBehaviour::Hide)
                 )
             {
                 orphan_hwnds.insert(window.hwnd, (*m_idx, *w_idx));
@@ -0,0 +1,372 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::OnceLock;
+
+use color_eyre::eyre::anyhow;
+use color_eyre::Result;
+use crossbeam_channel::Receiver;
+use crossbeam_channel::Sender;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::core::Layout;
+use crate::core::Rect;
+use crate::window_manager::WindowManager;
+use crate::workspace::Workspace;
+use crate::DATA_DIR;
+
+lazy_static! {
+    static ref RESIZE_ADJUSTMENTS: Arc<Mutex<ResizeAdjustments>> =
+        Arc::new(Mutex::new(ResizeAdjustments::default()));
+    // The layout and container count of each workspace when its adjustments were last recorded,
+    // keyed by monitor and workspace index
+    static ref LAST_RECORDED: Arc<Mutex<HashMap<(usize, usize), (String, usize)>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+}
+
+/// The manual resize adjustments of all workspaces, used to restore tile sizes across komorebi
+/// restarts
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ResizeAdjustments {
+    pub adjustments: Vec<ResizeAdjustment>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ResizeAdjustment {
+    pub monitor_idx: usize,
+    pub workspace_idx: usize,
+    /// Name of the layout the adjustments were made in
+    pub layout: String,
+    /// Number of containers the adjustments were made with
+    pub container_count: usize,
+    pub resize_dimensions: Vec<Option<Rect>>,
+}
+
+/// The name adjustments made in `layout` are persisted under
+pub fn layout_name(layout: &Layout) -> String {
+    match layout {
+        Layout::Default(layout) => layout.to_string(),
+        Layout::Custom(custom) => custom.name.clone().unwrap_or_else(|| "Custom".to_string()),
+    }
+}
+
+fn key(workspace: &Workspace) -> Option<(String, usize)> {
+    let container_count = workspace.containers().len();
+
+    // the resize dimensions are not truncated while a container is in monocle, so they can't be
+    // matched up with the tiled containers until it is reintegrated
+    if workspace.monocle_container().is_some()
+        || workspace.resize_dimensions().len() != container_count
+    {
+        return None;
+    }
+
+    Option::from((layout_name(workspace.layout()), container_count))
+}
+
+impl ResizeAdjustments {
+    /// The file which is kept up to date with the latest resize adjustments and restored from on
+    /// startup
+    pub fn default_path() -> PathBuf {
+        DATA_DIR.join("komorebi.resize_adjustments.json")
+    }
+
+    /// Writes to a temporary file which is then renamed over `path`, so that a crash part of the
+    /// way through never leaves a truncated file behind
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let tmp = path.with_extension("json.tmp");
+
+        let file = File::create(&tmp)?;
+        serde_json::to_writer_pretty(&file, self)?;
+        file.sync_all()?;
+
+        std::fs::rename(&tmp, path)?;
+
+        Ok(())
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path)
+            .map_err(|_| anyhow!("no resize adjustments found at {}", path.display()))?;
+
+        Ok(serde_json::from_reader(file)?)
+    }
+
+    fn position(
+        &self,
+        monitor_idx: usize,
+        workspace_idx: usize,
+        key: &(String, usize),
+    ) -> Option<usize> {
+        self.adjustments.iter().position(|adjustment| {
+            adjustment.monitor_idx == monitor_idx
+                && adjustment.workspace_idx == workspace_idx
+                && adjustment.layout == key.0
+                && adjustment.container_count == key.1
+        })
+    }
+
+    /// Records the current resize dimensions of every workspace in `wm`, returning true if
+    /// anything has changed. Adjustments saved for other layouts and container counts are kept,
+    /// and are only removed when the workspace they belong to is reset while still matching them.
+    fn record(
+        &mut self,
+        wm: &WindowManager,
+        last_recorded: &mut HashMap<(usize, usize), (String, usize)>,
+    ) -> bool {
+        let mut changed = false;
+
+        for (monitor_idx, monitor) in wm.monitors().iter().enumerate() {
+            for (workspace_idx, workspace) in monitor.workspaces().iter().enumerate() {
+                let Some(key) = key(workspace) else {
+                    continue;
+                };
+
+                let position = self.position(monitor_idx, workspace_idx, &key);
+                let resize_dimensions = workspace.resize_dimensions();
+
+                if resize_dimensions.iter().any(Option::is_some) {
+                    match position {
+                        Some(idx) => {
+                            let adjustment = &mut self.adjustments[idx];
+                            if adjustment.resize_dimensions != *resize_dimensions {
+                                adjustment.resize_dimensions.clone_from(resize_dimensions);
+                                changed = true;
+                            }
+                        }
+                        None => {
+                            self.adjustments.push(ResizeAdjustment {
+                                monitor_idx,
+                                workspace_idx,
+                                layout: key.0.clone(),
+                                container_count: key.1,
+                                resize_dimensions: resize_dimensions.clone(),
+                            });
+                            changed = true;
+                        }
+                    }
+                } else if let Some(idx) = position {
+                    if last_recorded.get(&(monitor_idx, workspace_idx)) == Some(&key) {
+                        self.adjustments.remove(idx);
+                        changed = true;
+                    }
+                }
+
+                last_recorded.insert((monitor_idx, workspace_idx), key);
+            }
+        }
+
+        changed
+    }
+}
+
+static CHANNEL: OnceLock<(Sender<ResizeAdjustments>, Receiver<ResizeAdjustments>)> =
+    OnceLock::new();
+
+fn channel() -> &'static (Sender<ResizeAdjustments>, Receiver<ResizeAdjustments>) {
+    CHANNEL.get_or_init(|| crossbeam_channel::bounded(20))
+}
+
+fn event_tx() -> Sender<ResizeAdjustments> {
+    channel().0.clone()
+}
+
+fn event_rx() -> Receiver<ResizeAdjustments> {
+    channel().1.clone()
+}
+
+/// Records the resize adjustments of every workspace, handing a snapshot of them to be written to
+/// disk if they have changed
+pub fn persist(wm: &WindowManager) {
+    let snapshot = {
+        let mut adjustments = RESIZE_ADJUSTMENTS.lock();
+        let mut last_recorded = LAST_RECORDED.lock();
+        adjustments
+            .record(wm, &mut last_recorded)
+            .then(|| adjustments.clone())
+    };
+
+    if let Some(snapshot) = snapshot {
+        if event_tx().send(snapshot).is_err() {
+            tracing::error!("could not send resize adjustments to be saved");
+        }
+    }
+}
+
+pub fn listen_for_changes() {
+    std::thread::spawn(move || loop {
+        match handle_changes() {
+            Ok(()) => {
+                tracing::warn!("restarting finished thread");
+            }
+            Err(error) => {
+                tracing::warn!("restarting failed thread: {}", error);
+            }
+        }
+    });
+}
+
+/// Saves snapshots of the resize adjustments away from the window manager and adjustment locks,
+/// only writing the latest when several have queued up behind a slow write
+pub fn handle_changes() -> Result<()> {
+    tracing::info!("listening");
+
+    let receiver = event_rx();
+
+    while let Ok(adjustments) = receiver.recv() {
+        let latest = receiver.try_iter().last().unwrap_or(adjustments);
+        if let Err(error) = latest.save(ResizeAdjustments::default_path()) {
+            tracing::error!("could not save resize adjustments: {error}");
+        }
+    }
+
+    Ok(())
+}
+
+impl WindowManager {
+    /// Applies the resize adjustments in `adjustments` to every workspace which has the same
+    /// layout and number of containers they were made with
+    #[tracing::instrument(skip(self, adjustments))]
+    pub fn restore_resize_adjustments(&mut self, adjustments: ResizeAdjustments) -> Result<()> {
+        tracing::info!("restoring resize adjustments");
+
+        // locked in the same order as when persisting
+        let mut resize_adjustments = RESIZE_ADJUSTMENTS.lock();
+        let mut last_recorded = LAST_RECORDED.lock();
+
+        for (monitor_idx, monitor) in self.monitors_mut().iter_mut().enumerate() {
+            for (workspace_idx, workspace) in monitor.workspaces_mut().iter_mut().enumerate() {
+                let Some(key) = key(workspace) else {
+                    continue;
+                };
+
+                if let Some(idx) = adjustments.position(monitor_idx, workspace_idx, &key) {
+                    workspace.set_resize_dimensions(
+                        adjustments.adjustments[idx].resize_dimensions.clone(),
+                    );
+                }
+
+                last_recorded.insert((monitor_idx, workspace_idx), key);
+            }
+        }
+
+        *resize_adjustments = adjustments;
+        drop(last_recorded);
+        drop(resize_adjustments);
+
+        self.retile_all(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::container::Container;
+    use crate::monitor;
+    use crate::DefaultLayout;
+    use crate::WindowManagerEvent;
+    use crossbeam_channel::bounded;
+    use crossbeam_channel::Receiver;
+    use crossbeam_channel::Sender;
+    use uuid::Uuid;
+
+    struct TestContext {
+        socket_path: Option<PathBuf>,
+    }
+
+    impl Drop for TestContext {
+        fn drop(&mut self) {
+            if let Some(socket_path) = &self.socket_path {
+                // Clean up the socket file
+                std::fs::remove_file(socket_path).unwrap();
+            }
+        }
+    }
+
+    fn setup_window_manager() -> (WindowManager, TestContext) {
+        let (_sender, receiver): (Sender<WindowManagerEvent>, Receiver<WindowManagerEvent>) =
+            bounded(1);
+
+        // Temporary socket path for testing
+        let socket_name = format!("komorebi-test-{}.sock", Uuid::new_v4());
+        let socket_path = PathBuf::from(socket_name);
+
+        let wm = WindowManager::new(receiver, Some(socket_path.clone())).unwrap();
+
+        (
+            wm,
+            TestContext {
+                socket_path: Some(socket_path),
+            },
+        )
+    }
+
+    #[test]
+    fn test_record_keeps_adjustments_for_other_container_counts() {
+        let (mut wm, _context) = setup_window_manager();
+
+        let mut m = monitor::new(
+            0,
+            Rect::default(),
+            Rect::default(),
+            "TestMonitor".to_string(),
+            "TestDevice".to_string(),
+            "TestDeviceID".to_string(),
+            Some("TestMonitorID".to_string()),
+        );
+
+        let workspace = m.focused_workspace_mut().unwrap();
+        workspace.set_layout(Layout::Default(DefaultLayout::BSP));
+        for _ in 0..2 {
+            workspace.add_container_to_back(Container::default());
+        }
+
+        let adjusted = vec![
+            Some(Rect {
+                left: 0,
+                top: 0,
+                right: 100,
+                bottom: 0,
+            }),
+            None,
+        ];
+        workspace.set_resize_dimensions(adjusted.clone());
+        wm.monitors_mut().push_back(m);
+
+        let mut adjustments = ResizeAdjustments::default();
+        let mut last_recorded = HashMap::new();
+
+        assert!(adjustments.record(&wm, &mut last_recorded));
+        assert_eq!(adjustments.adjustments.len(), 1);
+        assert_eq!(adjustments.adjustments[0].container_count, 2);
+        assert_eq!(adjustments.adjustments[0].resize_dimensions, adjusted);
+
+        // a third container without any adjustments leaves the two container adjustments alone
+        let workspace = wm.focused_workspace_mut().unwrap();
+        workspace.add_container_to_back(Container::default());
+        workspace.set_resize_dimensions(vec![None, None, None]);
+
+        assert!(!adjustments.record(&wm, &mut last_recorded));
+        assert_eq!(adjustments.adjustments.len(), 1);
+
+        // resetting the adjustments while still matching them removes them
+        let workspace = wm.focused_workspace_mut().unwrap();
+        workspace.remove_container_by_idx(2);
+        workspace.set_resize_dimensions(adjusted);
+        assert!(!adjustments.record(&wm, &mut last_recorded));
+
+        let workspace = wm.focused_workspace_mut().unwrap();
+        workspace.set_resize_dimensions(vec![None, None]);
+        assert!(adjustments.record(&wm, &mut last_recorded));
+        assert!(adjustments.adjustments.is_empty());
+    }
+}
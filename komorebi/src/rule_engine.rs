@@ -0,0 +1,146 @@
+use crate::core::config_generation::IdWithIdentifier;
+use crate::core::config_generation::MatchingRule;
+use crate::core::config_generation::RuleCombinator;
+use crate::window::should_act_individual;
+use regex::Regex;
+use std::collections::HashMap;
+
+/// Returns true if a window with the given properties is matched by `rule`, evaluating any
+/// nested rule combinators
+pub fn matches(
+    rule: &MatchingRule,
+    title: &str,
+    exe_name: &str,
+    class: &str,
+    path: &str,
+    regex_identifiers: &HashMap<String, Regex>,
+) -> bool {
+    let matches_identifier = |identifier: &IdWithIdentifier| {
+        should_act_individual(title, exe_name, class, path, identifier, regex_identifiers)
+    };
+
+    let matches_rule =
+        |rule: &MatchingRule| matches(rule, title, exe_name, class, path, regex_identifiers);
+
+    match rule {
+        MatchingRule::Simple(identifier) => matches_identifier(identifier),
+        MatchingRule::Composite(identifiers) => identifiers.iter().all(matches_identifier),
+        MatchingRule::Combinator(combinator) => match combinator {
+            RuleCombinator::All(rules) => rules.iter().all(matches_rule),
+            RuleCombinator::Any(rules) => rules.iter().any(matches_rule),
+            RuleCombinator::Not(rule) => !matches_rule(rule.as_ref()),
+        },
+    }
+}
+
+/// Returns all of the identifiers contained in `rule`, including those nested in combinators
+pub fn identifiers_mut(rule: &mut MatchingRule) -> Vec<&mut IdWithIdentifier> {
+    match rule {
+        MatchingRule::Simple(identifier) => vec![identifier],
+        MatchingRule::Composite(identifiers) => identifiers.iter_mut().collect(),
+        MatchingRule::Combinator(combinator) => match combinator {
+            RuleCombinator::All(rules) | RuleCombinator::Any(rules) => {
+                rules.iter_mut().flat_map(identifiers_mut).collect()
+            }
+            RuleCombinator::Not(rule) => identifiers_mut(rule),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config_generation::MatchingStrategy;
+    use crate::core::ApplicationIdentifier;
+
+    fn matches_firefox(rule: &MatchingRule, title: &str) -> bool {
+        matches(
+            rule,
+            title,
+            "firefox.exe",
+            "MozillaWindowClass",
+            "C:\\Program Files\\Mozilla Firefox\\firefox.exe",
+            &HashMap::new(),
+        )
+    }
+
+    #[test]
+    fn test_deserialize_combinators() {
+        let rule: MatchingRule = serde_json::from_str(
+            r#"{
+                "all": [
+                    { "kind": "Exe", "id": "firefox.exe", "matching_strategy": "Equals" },
+                    {
+                        "not": {
+                            "any": [
+                                { "kind": "Title", "id": "Picture-in-Picture", "matching_strategy": "Equals" },
+                                [{ "kind": "Class", "id": "MozillaDialogClass", "matching_strategy": "Equals" }]
+                            ]
+                        }
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let MatchingRule::Combinator(RuleCombinator::All(rules)) = &rule else {
+            panic!("expected an 'all' combinator, got {rule:?}");
+        };
+
+        assert!(matches!(rules[0], MatchingRule::Simple(_)));
+        assert!(matches!(
+            rules[1],
+            MatchingRule::Combinator(RuleCombinator::Not(_))
+        ));
+    }
+
+    #[test]
+    fn test_evaluate_combinators() {
+        let exe = MatchingRule::Simple(IdWithIdentifier {
+            kind: ApplicationIdentifier::Exe,
+            id: String::from("firefox.exe"),
+            matching_strategy: Some(MatchingStrategy::Equals),
+        });
+
+        let pip = MatchingRule::Simple(IdWithIdentifier {
+            kind: ApplicationIdentifier::Title,
+            id: String::from("Picture-in-Picture"),
+            matching_strategy: Some(MatchingStrategy::Equals),
+        });
+
+        let rule = MatchingRule::Combinator(RuleCombinator::All(vec![
+            exe.clone(),
+            MatchingRule::Combinator(RuleCombinator::Not(Box::new(pip.clone()))),
+        ]));
+
+        assert!(matches_firefox(&rule, "Mozilla Firefox"));
+        assert!(!matches_firefox(&rule, "Picture-in-Picture"));
+
+        let rule = MatchingRule::Combinator(RuleCombinator::Any(vec![pip, exe]));
+        assert!(matches_firefox(&rule, "Mozilla Firefox"));
+
+        let rule = MatchingRule::Combinator(RuleCombinator::Any(vec![]));
+        assert!(!matches_firefox(&rule, "Mozilla Firefox"));
+    }
+
+    #[test]
+    fn test_identifiers_mut() {
+        let mut rule: MatchingRule = serde_json::from_str(
+            r#"{
+                "any": [
+                    { "kind": "Exe", "id": "a.exe" },
+                    { "not": { "kind": "Title", "id": "b" } },
+                    [{ "kind": "Class", "id": "c" }, { "kind": "Path", "id": "d" }]
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let ids = identifiers_mut(&mut rule)
+            .into_iter()
+            .map(|identifier| identifier.id.clone())
+            .collect::<Vec<_>>();
+
+        assert_eq!(ids, vec!["a.exe", "b", "c", "d"]);
+    }
+}
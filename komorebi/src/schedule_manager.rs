@@ -0,0 +1,305 @@
+#![deny(clippy::unwrap_used, clippy::expect_used)]
+
+use crate::core::config_generation::MatchingRule;
+use crate::core::config_generation::WorkspaceMatchingRule;
+use crate::notify_subscribers;
+use crate::Notification;
+use crate::NotificationEvent;
+use crate::WindowManager;
+
+use chrono::Datelike;
+use chrono::Local;
+use chrono::NaiveDateTime;
+use chrono::NaiveTime;
+use chrono::Weekday;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use serde::Deserialize;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How often the schedules are re-evaluated
+const SCHEDULE_INTERVAL: Duration = Duration::from_secs(30);
+
+lazy_static! {
+    pub static ref WORKSPACE_SCHEDULES: Arc<Mutex<Vec<WorkspaceSchedule>>> =
+        Arc::new(Mutex::new(Vec::new()));
+    pub static ref SCHEDULED_WORKSPACE_MATCHING_RULES: Arc<Mutex<Vec<WorkspaceMatchingRule>>> =
+        Arc::new(Mutex::new(Vec::new()));
+    pub static ref ACTIVE_WORKSPACE_SCHEDULES: Arc<Mutex<Vec<String>>> =
+        Arc::new(Mutex::new(Vec::new()));
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum ScheduleDay {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl From<Weekday> for ScheduleDay {
+    fn from(value: Weekday) -> Self {
+        match value {
+            Weekday::Mon => Self::Monday,
+            Weekday::Tue => Self::Tuesday,
+            Weekday::Wed => Self::Wednesday,
+            Weekday::Thu => Self::Thursday,
+            Weekday::Fri => Self::Friday,
+            Weekday::Sat => Self::Saturday,
+            Weekday::Sun => Self::Sunday,
+        }
+    }
+}
+
+/// A workspace schedule which has started or ended, identified by its name
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum WorkspaceScheduleNotification {
+    Activated(String),
+    Deactivated(String),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WorkspaceSchedule {
+    /// Name of the schedule, shown in the window manager state while it is active
+    pub name: String,
+    /// Days on which the schedule starts (default: every day)
+    pub days: Option<Vec<ScheduleDay>>,
+    /// Time of day at which the schedule starts in 24-hour "HH:MM" format
+    pub start: String,
+    /// Time of day at which the schedule ends in 24-hour "HH:MM" format, schedules ending before
+    /// they start run past midnight
+    pub end: String,
+    /// Name of the workspace that matching windows are sent to while the schedule is active
+    pub workspace: String,
+    /// Rules identifying the windows to send to the workspace, these take precedence over any
+    /// workspace rules while the schedule is active
+    pub rules: Vec<MatchingRule>,
+}
+
+impl WorkspaceSchedule {
+    fn applies_on(&self, day: Weekday) -> bool {
+        self.days
+            .as_ref()
+            .is_none_or(|days| days.contains(&ScheduleDay::from(day)))
+    }
+
+    /// Returns true if the schedule is active at `now`
+    pub fn is_active(&self, now: NaiveDateTime) -> bool {
+        let (Ok(start), Ok(end)) = (
+            NaiveTime::parse_from_str(&self.start, "%H:%M"),
+            NaiveTime::parse_from_str(&self.end, "%H:%M"),
+        ) else {
+            tracing::warn!(
+                "invalid start or end time for workspace schedule {}",
+                self.name
+            );
+            return false;
+        };
+
+        let time = now.time();
+        let day = now.weekday();
+
+        if start <= end {
+            self.applies_on(day) && time >= start && time < end
+        } else {
+            // the schedule runs past midnight, so the early hours belong to the previous day
+            (self.applies_on(day) && time >= start) || (self.applies_on(day.pred()) && time < end)
+        }
+    }
+}
+
+/// Updates the scheduled workspace rules to match the schedules active at the current time,
+/// returning true if they have changed
+pub fn update_active_schedules(wm: &mut WindowManager) -> bool {
+    let now = Local::now().naive_local();
+    let schedules = WORKSPACE_SCHEDULES.lock().clone();
+
+    let mut active = vec![];
+    let mut rules = vec![];
+
+    for schedule in schedules.iter().filter(|schedule| schedule.is_active(now)) {
+        let Some((monitor_idx, workspace_idx)) =
+            wm.monitor_workspace_index_by_name(&schedule.workspace)
+        else {
+            tracing::warn!(
+                "workspace schedule {} targets unknown workspace {}",
+                schedule.name,
+                schedule.workspace
+            );
+
+            continue;
+        };
+
+        active.push(schedule.name.clone());
+
+        for rule in &schedule.rules {
+            rules.push(WorkspaceMatchingRule {
+                monitor_index: monitor_idx,
+                workspace_index: workspace_idx,
+                matching_rule: rule.clone(),
+                initial_only: false,
+                follow: false,
+            });
+        }
+    }
+
+    *ACTIVE_WORKSPACE_SCHEDULES.lock() = active;
+
+    let mut scheduled_rules = SCHEDULED_WORKSPACE_MATCHING_RULES.lock();
+    if *scheduled_rules == rules {
+        return false;
+    }
+
+    *scheduled_rules = rules;
+    true
+}
+
+/// The schedules which were activated or deactivated going from the `previous` to the `current`
+/// active schedules
+fn schedule_changes(previous: &[String], current: &[String]) -> Vec<WorkspaceScheduleNotification> {
+    let deactivated = previous
+        .iter()
+        .filter(|name| !current.contains(name))
+        .map(|name| WorkspaceScheduleNotification::Deactivated(name.clone()));
+
+    let activated = current
+        .iter()
+        .filter(|name| !previous.contains(name))
+        .map(|name| WorkspaceScheduleNotification::Activated(name.clone()));
+
+    deactivated.chain(activated).collect()
+}
+
+pub fn listen_for_schedules(wm: Arc<Mutex<WindowManager>>) {
+    std::thread::spawn(move || loop {
+        match handle_schedules(wm.clone()) {
+            Ok(()) => {
+                tracing::warn!("restarting finished thread");
+            }
+            Err(error) => {
+                tracing::warn!("restarting failed thread: {}", error);
+            }
+        }
+    });
+}
+
+pub fn handle_schedules(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result<()> {
+    tracing::info!("listening");
+
+    loop {
+        std::thread::sleep(SCHEDULE_INTERVAL);
+
+        if WORKSPACE_SCHEDULES.lock().is_empty() {
+            continue;
+        }
+
+        let mut wm = wm.lock();
+        let previously_active = ACTIVE_WORKSPACE_SCHEDULES.lock().clone();
+
+        if update_active_schedules(&mut wm) {
+            tracing::info!("active workspace schedules changed, enforcing workspace rules");
+            wm.enforce_workspace_rules()?;
+        }
+
+        let active = ACTIVE_WORKSPACE_SCHEDULES.lock().clone();
+        for notification in schedule_changes(&previously_active, &active) {
+            tracing::info!("workspace schedule changed: {notification:?}");
+
+            notify_subscribers(
+                Notification {
+                    event: NotificationEvent::WorkspaceSchedule(notification),
+                    state: wm.as_ref().into(),
+                },
+                true,
+            )?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn schedule(days: Option<Vec<ScheduleDay>>, start: &str, end: &str) -> WorkspaceSchedule {
+        WorkspaceSchedule {
+            name: String::from("test"),
+            days,
+            start: start.to_string(),
+            end: end.to_string(),
+            workspace: String::from("work"),
+            rules: vec![],
+        }
+    }
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        // 2025-01-06 is a Monday
+        NaiveDate::from_ymd_opt(2025, 1, day)
+            .and_then(|date| date.and_hms_opt(hour, minute, 0))
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_schedule_is_active() {
+        let work = schedule(
+            Some(vec![
+                ScheduleDay::Monday,
+                ScheduleDay::Tuesday,
+                ScheduleDay::Wednesday,
+                ScheduleDay::Thursday,
+                ScheduleDay::Friday,
+            ]),
+            "09:00",
+            "17:00",
+        );
+
+        assert!(work.is_active(at(6, 9, 0)));
+        assert!(work.is_active(at(10, 16, 59)));
+        assert!(!work.is_active(at(6, 17, 0)));
+        assert!(!work.is_active(at(6, 8, 59)));
+        // Saturday
+        assert!(!work.is_active(at(11, 12, 0)));
+    }
+
+    #[test]
+    fn test_schedule_past_midnight() {
+        let evening = schedule(Some(vec![ScheduleDay::Friday]), "17:00", "02:00");
+
+        // Friday evening
+        assert!(evening.is_active(at(10, 23, 0)));
+        // early Saturday morning still belongs to Friday
+        assert!(evening.is_active(at(11, 1, 30)));
+        assert!(!evening.is_active(at(11, 2, 0)));
+        // early Friday morning belongs to Thursday
+        assert!(!evening.is_active(at(10, 1, 30)));
+    }
+
+    #[test]
+    fn test_schedule_invalid_time() {
+        assert!(!schedule(None, "9am", "17:00").is_active(at(6, 10, 0)));
+    }
+
+    #[test]
+    fn test_schedule_changes() {
+        let previous = vec![String::from("work"), String::from("focus")];
+        let current = vec![String::from("focus"), String::from("evening")];
+
+        assert_eq!(
+            schedule_changes(&previous, &current),
+            vec![
+                WorkspaceScheduleNotification::Deactivated(String::from("work")),
+                WorkspaceScheduleNotification::Activated(String::from("evening")),
+            ]
+        );
+
+        assert!(schedule_changes(&current, &current).is_empty());
+    }
+}
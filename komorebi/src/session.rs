@@ -0,0 +1,320 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::path::Path;
+use std::path::PathBuf;
+
+use color_eyre::eyre::anyhow;
+use color_eyre::Result;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::container::Container;
+use crate::window::Window;
+use crate::window_manager::WindowManager;
+use crate::DATA_DIR;
+
+/// The workspace assignments of all managed windows, used to restore window placement across
+/// komorebi restarts
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Session {
+    pub windows: Vec<SessionWindow>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct SessionWindow {
+    pub hwnd: isize,
+    pub exe: String,
+    pub title: String,
+    pub monitor_idx: usize,
+    pub workspace_idx: usize,
+    /// Index of the container holding the window, or `None` if the window is floating
+    pub container_idx: Option<usize>,
+    /// Index of the window within its container, or within the floating windows
+    pub window_idx: usize,
+}
+
+impl SessionWindow {
+    fn new(
+        window: Window,
+        monitor_idx: usize,
+        workspace_idx: usize,
+        container_idx: Option<usize>,
+        window_idx: usize,
+    ) -> Option<Self> {
+        Option::from(Self {
+            hwnd: window.hwnd,
+            exe: window.exe().ok()?,
+            title: window.title().unwrap_or_default(),
+            monitor_idx,
+            workspace_idx,
+            container_idx,
+            window_idx,
+        })
+    }
+}
+
+impl From<&WindowManager> for Session {
+    fn from(wm: &WindowManager) -> Self {
+        let mut windows = vec![];
+
+        for (monitor_idx, monitor) in wm.monitors().iter().enumerate() {
+            for (workspace_idx, workspace) in monitor.workspaces().iter().enumerate() {
+                for (container_idx, container) in workspace.containers().iter().enumerate() {
+                    for (window_idx, window) in container.windows().iter().enumerate() {
+                        windows.extend(SessionWindow::new(
+                            *window,
+                            monitor_idx,
+                            workspace_idx,
+                            Option::from(container_idx),
+                            window_idx,
+                        ));
+                    }
+                }
+
+                // Monocle and maximized windows are restored as regular containers at the
+                // index they would have been reintegrated at
+                if let Some(container) = workspace.monocle_container() {
+                    let container_idx = workspace
+                        .monocle_container_restore_idx()
+                        .unwrap_or(workspace.containers().len());
+
+                    for (window_idx, window) in container.windows().iter().enumerate() {
+                        windows.extend(SessionWindow::new(
+                            *window,
+                            monitor_idx,
+                            workspace_idx,
+                            Option::from(container_idx),
+                            window_idx,
+                        ));
+                    }
+                }
+
+                if let Some(window) = workspace.maximized_window() {
+                    let container_idx = workspace
+                        .maximized_window_restore_idx()
+                        .unwrap_or(workspace.containers().len());
+
+                    windows.extend(SessionWindow::new(
+                        *window,
+                        monitor_idx,
+                        workspace_idx,
+                        Option::from(container_idx),
+                        0,
+                    ));
+                }
+
+                for (window_idx, window) in workspace.floating_windows().iter().enumerate() {
+                    windows.extend(SessionWindow::new(
+                        *window,
+                        monitor_idx,
+                        workspace_idx,
+                        None,
+                        window_idx,
+                    ));
+                }
+            }
+        }
+
+        Self { windows }
+    }
+}
+
+impl Session {
+    /// The session file written when komorebi is stopped and restored from on startup
+    pub fn default_path() -> PathBuf {
+        DATA_DIR.join("komorebi.session.json")
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let file = OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .create(true)
+            .open(path)?;
+
+        serde_json::to_writer_pretty(&file, self)?;
+
+        Ok(())
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let file =
+            File::open(path).map_err(|_| anyhow!("no session found at {}", path.display()))?;
+
+        Ok(serde_json::from_reader(file)?)
+    }
+}
+
+impl WindowManager {
+    /// Moves the currently managed tiled and floating windows to the monitors, workspaces and
+    /// containers recorded in `session`. Windows are matched by hwnd first, then by exe and
+    /// title, and finally by exe alone.
+    #[tracing::instrument(skip(self, session))]
+    pub fn restore_session(&mut self, session: &Session) -> Result<()> {
+        tracing::info!("restoring session");
+
+        let mut candidates = vec![];
+        for monitor in self.monitors() {
+            for workspace in monitor.workspaces() {
+                for container in workspace.containers() {
+                    for window in container.windows() {
+                        if let Ok(exe) = window.exe() {
+                            candidates.push((*window, exe, window.title().unwrap_or_default()));
+                        }
+                    }
+                }
+
+                for window in workspace.floating_windows() {
+                    if let Ok(exe) = window.exe() {
+                        candidates.push((*window, exe, window.title().unwrap_or_default()));
+                    }
+                }
+            }
+        }
+
+        let mut entries = session.windows.clone();
+        entries.sort_by_key(|entry| {
+            (
+                entry.monitor_idx,
+                entry.workspace_idx,
+                entry.container_idx.is_none(),
+                entry.container_idx,
+                entry.window_idx,
+            )
+        });
+
+        let monitor_count = self.monitors().len();
+        entries.retain(|entry| entry.monitor_idx < monitor_count);
+
+        // Each matching strategy is applied to every entry before falling back to the next, so
+        // that a loose match for one entry can't claim a window which exactly matches another
+        let mut matches: Vec<Option<Window>> = vec![None; entries.len()];
+        for pass in 0..3 {
+            for (entry, matched) in entries.iter().zip(matches.iter_mut()) {
+                if matched.is_some() {
+                    continue;
+                }
+
+                let position = candidates.iter().position(|(window, exe, title)| {
+                    *exe == entry.exe
+                        && match pass {
+                            0 => window.hwnd == entry.hwnd,
+                            1 => *title == entry.title,
+                            _ => true,
+                        }
+                });
+
+                if let Some(position) = position {
+                    let (window, _, _) = candidates.remove(position);
+                    *matched = Option::from(window);
+                }
+            }
+        }
+
+        let assignments = matches
+            .into_iter()
+            .zip(entries)
+            .filter_map(|(window, entry)| window.map(|window| (window, entry)))
+            .collect::<Vec<_>>();
+
+        if assignments.is_empty() {
+            return Ok(());
+        }
+
+        let hwnds = assignments
+            .iter()
+            .map(|(window, _)| window.hwnd)
+            .collect::<HashSet<_>>();
+
+        for monitor in self.monitors_mut() {
+            for workspace in monitor.workspaces_mut() {
+                workspace
+                    .floating_windows_mut()
+                    .retain(|window| !hwnds.contains(&window.hwnd));
+
+                for idx in (0..workspace.containers().len()).rev() {
+                    let container = &mut workspace.containers_mut()[idx];
+                    container
+                        .windows_mut()
+                        .retain(|window| !hwnds.contains(&window.hwnd));
+
+                    if container.windows().is_empty() {
+                        workspace.remove_container_by_idx(idx);
+                    } else if container.focused_window_idx() >= container.windows().len() {
+                        container.focus_window(0);
+                    }
+                }
+
+                let container_count = workspace.containers().len();
+                if workspace.focused_container_idx() >= container_count {
+                    workspace.focus_container(container_count.saturating_sub(1));
+                }
+            }
+        }
+
+        let mut previous = None;
+        let mut previous_insertion_idx = 0;
+
+        for (window, entry) in assignments {
+            let monitor = self
+                .monitors_mut()
+                .get_mut(entry.monitor_idx)
+                .ok_or_else(|| anyhow!("there is no monitor"))?;
+
+            monitor.ensure_workspace_count(entry.workspace_idx + 1);
+
+            let workspace = monitor
+                .workspaces_mut()
+                .get_mut(entry.workspace_idx)
+                .ok_or_else(|| anyhow!("there is no workspace"))?;
+
+            match entry.container_idx {
+                None => {
+                    workspace.floating_windows_mut().push_back(window);
+                }
+                Some(container_idx) => {
+                    let key = (entry.monitor_idx, entry.workspace_idx, container_idx);
+
+                    let stack_with_previous = previous == Some(key)
+                        && previous_insertion_idx < workspace.containers().len();
+
+                    if stack_with_previous {
+                        workspace.containers_mut()[previous_insertion_idx].add_window(window);
+                    } else {
+                        let mut container = Container::default();
+                        container.add_window(window);
+
+                        let idx = container_idx.min(workspace.containers().len());
+                        previous_insertion_idx = workspace.insert_container_at_idx(idx, container);
+                    }
+
+                    previous = Some(key);
+                }
+            }
+        }
+
+        for monitor in self.monitors_mut() {
+            let focused_workspace_idx = monitor.focused_workspace_idx();
+
+            for (idx, workspace) in monitor.workspaces_mut().iter_mut().enumerate() {
+                if idx == focused_workspace_idx {
+                    for container in workspace.containers_mut() {
+                        container.load_focused_window();
+                    }
+
+                    for window in workspace.floating_windows() {
+                        window.restore();
+                    }
+                } else {
+                    workspace.hide(None);
+                }
+            }
+        }
+
+        self.retile_all(false)
+    }
+}
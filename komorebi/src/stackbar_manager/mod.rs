@@ -4,6 +4,7 @@ use crate::container::Container;
 use crate::core::StackbarLabel;
 use crate::core::StackbarMode;
 use crate::stackbar_manager::stackbar::Stackbar;
+use crate::SocketMessage;
 use crate::WindowManager;
 use crate::WindowsApi;
 use crate::DEFAULT_CONTAINER_PADDING;
@@ -36,11 +37,18 @@ pub static STACKBAR_TEMPORARILY_DISABLED: AtomicBool = AtomicBool::new(false);
 lazy_static! {
     pub static ref STACKBAR_STATE: Mutex<HashMap<String, Stackbar>> = Mutex::new(HashMap::new());
     pub static ref STACKBAR_FONT_FAMILY: Mutex<Option<String>> = Mutex::new(None);
+    pub static ref STACKBAR_CLOSE_GLYPH: Mutex<Option<String>> = Mutex::new(None);
     static ref STACKBARS_MONITORS: Mutex<HashMap<String, usize>> = Mutex::new(HashMap::new());
     static ref STACKBARS_CONTAINERS: Mutex<HashMap<isize, Container>> = Mutex::new(HashMap::new());
+    // The stackbar hwnd and index of the tab which is being dragged
+    static ref STACKBAR_DRAGGED_TAB: Mutex<Option<(isize, usize)>> = Mutex::new(None);
 }
 
-pub struct Notification;
+pub enum Notification {
+    Update,
+    /// A tab has been dragged to move the window with this hwnd to a new index in its stack
+    MoveWindow(isize, usize),
+}
 
 static CHANNEL: OnceLock<(Sender<Notification>, Receiver<Notification>)> = OnceLock::new();
 
@@ -57,7 +65,16 @@ fn event_rx() -> Receiver<Notification> {
 }
 
 pub fn send_notification() {
-    if event_tx().try_send(Notification).is_err() {
+    if event_tx().try_send(Notification::Update).is_err() {
+        tracing::warn!("channel is full; dropping notification")
+    }
+}
+
+fn send_move_window(hwnd: isize, idx: usize) {
+    if event_tx()
+        .try_send(Notification::MoveWindow(hwnd, idx))
+        .is_err()
+    {
         tracing::warn!("channel is full; dropping notification")
     }
 }
@@ -88,7 +105,20 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
 
     let receiver = event_rx();
 
-    'receiver: for _ in receiver {
+    'receiver: for notification in receiver {
+        if let Notification::MoveWindow(hwnd, idx) = notification {
+            // This goes through the same path as a command so that subscribers are notified of
+            // the new order of the stack, and the stackbars are updated once it has been applied
+            if let Err(error) = wm
+                .lock()
+                .process_command(SocketMessage::MoveStackWindow(hwnd, idx), std::io::sink())
+            {
+                tracing::error!("could not move stack window: {error}");
+            }
+
+            continue 'receiver;
+        }
+
         let mut stackbars = STACKBAR_STATE.lock();
         let mut stackbars_monitors = STACKBARS_MONITORS.lock();
 
@@ -5,7 +5,10 @@ use crate::container::Container;
 use crate::core::BorderStyle;
 use crate::core::Rect;
 use crate::core::StackbarLabel;
+use crate::stackbar_manager::send_move_window;
 use crate::stackbar_manager::STACKBARS_CONTAINERS;
+use crate::stackbar_manager::STACKBAR_CLOSE_GLYPH;
+use crate::stackbar_manager::STACKBAR_DRAGGED_TAB;
 use crate::stackbar_manager::STACKBAR_FOCUSED_TEXT_COLOUR;
 use crate::stackbar_manager::STACKBAR_FONT_FAMILY;
 use crate::stackbar_manager::STACKBAR_FONT_SIZE;
@@ -15,8 +18,10 @@ use crate::stackbar_manager::STACKBAR_TAB_HEIGHT;
 use crate::stackbar_manager::STACKBAR_TAB_WIDTH;
 use crate::stackbar_manager::STACKBAR_UNFOCUSED_TEXT_COLOUR;
 use crate::windows_api;
+use crate::Window;
 use crate::WindowsApi;
 use crate::DEFAULT_CONTAINER_PADDING;
+use crate::PRIVACY_MODE;
 use crate::WINDOWS_11;
 use crossbeam_utils::atomic::AtomicConsume;
 use std::os::windows::ffi::OsStrExt;
@@ -54,6 +59,8 @@ use windows::Win32::Graphics::Gdi::LOGPIXELSY;
 use windows::Win32::Graphics::Gdi::PROOF_QUALITY;
 use windows::Win32::Graphics::Gdi::PS_SOLID;
 use windows::Win32::System::WindowsProgramming::MulDiv;
+use windows::Win32::UI::Input::KeyboardAndMouse::ReleaseCapture;
+use windows::Win32::UI::Input::KeyboardAndMouse::SetCapture;
 use windows::Win32::UI::WindowsAndMessaging::CreateWindowExW;
 use windows::Win32::UI::WindowsAndMessaging::DefWindowProcW;
 use windows::Win32::UI::WindowsAndMessaging::DispatchMessageW;
@@ -70,6 +77,8 @@ use windows::Win32::UI::WindowsAndMessaging::LWA_COLORKEY;
 use windows::Win32::UI::WindowsAndMessaging::MSG;
 use windows::Win32::UI::WindowsAndMessaging::WM_DESTROY;
 use windows::Win32::UI::WindowsAndMessaging::WM_LBUTTONDOWN;
+use windows::Win32::UI::WindowsAndMessaging::WM_LBUTTONUP;
+use windows::Win32::UI::WindowsAndMessaging::WM_MBUTTONDOWN;
 use windows::Win32::UI::WindowsAndMessaging::WM_SETCURSOR;
 use windows::Win32::UI::WindowsAndMessaging::WNDCLASSW;
 use windows::Win32::UI::WindowsAndMessaging::WS_EX_LAYERED;
@@ -167,12 +176,10 @@ impl Stackbar {
         container: &mut Container,
         layout: &Rect,
     ) -> color_eyre::Result<()> {
-        let width = STACKBAR_TAB_WIDTH.load_consume();
-        let height = STACKBAR_TAB_HEIGHT.load_consume();
-        let gap = DEFAULT_CONTAINER_PADDING.load_consume();
         let background = STACKBAR_TAB_BACKGROUND_COLOUR.load_consume();
         let focused_text_colour = STACKBAR_FOCUSED_TEXT_COLOUR.load_consume();
         let unfocused_text_colour = STACKBAR_UNFOCUSED_TEXT_COLOUR.load_consume();
+        let close_glyph = STACKBAR_CLOSE_GLYPH.lock().clone();
 
         let mut stackbars_containers = STACKBARS_CONTAINERS.lock();
         stackbars_containers.insert(self.hwnd, container.clone());
@@ -231,13 +238,7 @@ impl Stackbar {
                     SetTextColor(hdc, COLORREF(unfocused_text_colour));
                 }
 
-                let left = gap + (i as i32 * (width + gap));
-                let mut rect = Rect {
-                    top: 0,
-                    left,
-                    right: left + width,
-                    bottom: height,
-                };
+                let mut rect = tab_rect(i);
 
                 match STYLE.load() {
                     BorderStyle::System => {
@@ -268,14 +269,32 @@ impl Stackbar {
                     }
                 }
 
+                // Process names are shown instead of titles while privacy mode is on
                 let label = match STACKBAR_LABEL.load() {
-                    StackbarLabel::Process => {
+                    StackbarLabel::Title if !PRIVACY_MODE.load(Ordering::SeqCst) => {
+                        window.title()?
+                    }
+                    StackbarLabel::Process | StackbarLabel::Title => {
                         let exe = window.exe()?;
                         exe.trim_end_matches(".exe").to_string()
                     }
-                    StackbarLabel::Title => window.title()?,
                 };
 
+                if let Some(close_glyph) = &close_glyph {
+                    let close_rect = close_button_rect(&rect);
+                    let mut glyph: Vec<u16> = close_glyph.encode_utf16().collect();
+
+                    DrawTextW(
+                        hdc,
+                        &mut glyph,
+                        &mut close_rect.into(),
+                        DT_SINGLELINE | DT_CENTER | DT_VCENTER,
+                    );
+
+                    // the title is truncated before the close glyph rather than drawn under it
+                    rect.right = close_rect.left;
+                }
+
                 let mut tab_title: Vec<u16> = label.encode_utf16().collect();
 
                 rect.left_padding(10);
@@ -332,9 +351,19 @@ impl Stackbar {
                         let x = l_param.0 as i32 & 0xFFFF;
                         let y = (l_param.0 as i32 >> 16) & 0xFFFF;
 
-                        let width = STACKBAR_TAB_WIDTH.load_consume();
-                        let height = STACKBAR_TAB_HEIGHT.load_consume();
-                        let gap = DEFAULT_CONTAINER_PADDING.load_consume();
+                        // Clicking the close glyph of a tab closes its window without changing
+                        // which window is focused in the stack
+                        if STACKBAR_CLOSE_GLYPH.lock().is_some() {
+                            if let Some(window) = container.windows().iter().enumerate().find_map(
+                                |(index, window)| {
+                                    contains(&close_button_rect(&tab_rect(index)), x, y)
+                                        .then_some(window)
+                                },
+                            ) {
+                                close_tab(window);
+                                return LRESULT(0);
+                            }
+                        }
 
                         let focused_window_idx = container.focused_window_idx();
                         let focused_window_rect = WindowsApi::window_rect(
@@ -343,12 +372,12 @@ impl Stackbar {
                         .unwrap_or_default();
 
                         for (index, window) in container.windows().iter().enumerate() {
-                            let left = gap + (index as i32 * (width + gap));
-                            let right = left + width;
-                            let top = 0;
-                            let bottom = height;
+                            if contains(&tab_rect(index), x, y) {
+                                // The tab may be dragged and dropped onto another tab before the
+                                // mouse button is released
+                                *STACKBAR_DRAGGED_TAB.lock() = Some((hwnd.0 as isize, index));
+                                SetCapture(hwnd);
 
-                            if x >= left && x <= right && y >= top && y <= bottom {
                                 // If we are focusing a window that isn't currently focused in the
                                 // stackbar, make sure we update its location so that it doesn't render
                                 // on top of other tiles before eventually ending up in the correct
@@ -384,6 +413,57 @@ impl Stackbar {
 
                     LRESULT(0)
                 }
+                WM_LBUTTONUP => {
+                    // TODO: error handling
+                    let _ = ReleaseCapture();
+
+                    let dragged_tab = STACKBAR_DRAGGED_TAB
+                        .lock()
+                        .take()
+                        .filter(|(stackbar, _)| *stackbar == hwnd.0 as isize);
+
+                    let stackbars_containers = STACKBARS_CONTAINERS.lock();
+                    if let (Some((_, from)), Some(container)) =
+                        (dragged_tab, stackbars_containers.get(&(hwnd.0 as isize)))
+                    {
+                        let x = l_param.0 as i32 & 0xFFFF;
+                        let y = (l_param.0 as i32 >> 16) & 0xFFFF;
+
+                        // Dropping a tab onto another tab moves its window to the position of that
+                        // tab in the stack
+                        let to = (0..container.windows().len())
+                            .find(|index| contains(&tab_rect(*index), x, y))
+                            .filter(|to| *to != from);
+
+                        if let (Some(to), Some(window)) = (to, container.windows().get(from)) {
+                            send_move_window(window.hwnd, to);
+                        }
+                    }
+
+                    LRESULT(0)
+                }
+                WM_MBUTTONDOWN => {
+                    let stackbars_containers = STACKBARS_CONTAINERS.lock();
+                    if let Some(container) = stackbars_containers.get(&(hwnd.0 as isize)) {
+                        let x = l_param.0 as i32 & 0xFFFF;
+                        let y = (l_param.0 as i32 >> 16) & 0xFFFF;
+
+                        // Middle-clicking anywhere on a tab closes its window, as in a browser
+                        if let Some(window) =
+                            container
+                                .windows()
+                                .iter()
+                                .enumerate()
+                                .find_map(|(index, window)| {
+                                    contains(&tab_rect(index), x, y).then_some(window)
+                                })
+                        {
+                            close_tab(window);
+                        }
+                    }
+
+                    LRESULT(0)
+                }
                 WM_DESTROY => {
                     PostQuitMessage(0);
                     LRESULT(0)
@@ -394,6 +474,38 @@ impl Stackbar {
     }
 }
 
+/// The area of the tab at `index`, relative to the stackbar
+fn tab_rect(index: usize) -> Rect {
+    let width = STACKBAR_TAB_WIDTH.load_consume();
+    let gap = DEFAULT_CONTAINER_PADDING.load_consume();
+    let left = gap + (index as i32 * (width + gap));
+
+    Rect {
+        top: 0,
+        left,
+        right: left + width,
+        bottom: STACKBAR_TAB_HEIGHT.load_consume(),
+    }
+}
+
+/// The square at the end of `tab` in which the close glyph is drawn
+fn close_button_rect(tab: &Rect) -> Rect {
+    Rect {
+        left: tab.right - (tab.bottom - tab.top),
+        ..*tab
+    }
+}
+
+fn contains(rect: &Rect, x: i32, y: i32) -> bool {
+    x >= rect.left && x <= rect.right && y >= rect.top && y <= rect.bottom
+}
+
+fn close_tab(window: &Window) {
+    if let Err(err) = window.close() {
+        tracing::error!("stackbar close error: hwnd {} ({})", *window, err);
+    }
+}
+
 fn wide_string(s: &str) -> Vec<u16> {
     std::ffi::OsStr::new(s)
         .encode_wide()
@@ -0,0 +1,280 @@
+use crate::NotificationEvent;
+use crate::State;
+use crate::DATA_DIR;
+use crate::SUBSCRIPTION_DIFF_SOCKETS;
+
+use color_eyre::Result;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use std::io::Write;
+use std::path::PathBuf;
+use uds_windows::UnixStream;
+
+/// Number of patches sent to a diff subscriber before a full snapshot is sent again so that
+/// subscribers which have drifted out of sync can recover
+pub const SNAPSHOT_INTERVAL: u64 = 100;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type")]
+pub enum DiffNotification<'a> {
+    /// The full window manager state, sent on subscription and every `SNAPSHOT_INTERVAL` patches
+    Snapshot {
+        sequence: u64,
+        event: &'a NotificationEvent,
+        state: &'a Value,
+    },
+    /// JSON-patch style operations which transform the previously sent state into the current one
+    Patch {
+        sequence: u64,
+        event: &'a NotificationEvent,
+        patch: Vec<PatchOperation>,
+    },
+}
+
+#[derive(Debug)]
+pub struct DiffSubscriber {
+    path: PathBuf,
+    last_state: Option<Value>,
+    sequence: u64,
+}
+
+impl DiffSubscriber {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            last_state: None,
+            sequence: 0,
+        }
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// Returns the next notification for this subscriber, or `None` if the state is unchanged
+    /// since the last notification that was sent
+    fn next_notification<'a>(
+        &self,
+        event: &'a NotificationEvent,
+        state: &'a Value,
+    ) -> Option<DiffNotification<'a>> {
+        let sequence = self.sequence + 1;
+
+        match &self.last_state {
+            Some(last_state) if sequence % SNAPSHOT_INTERVAL != 0 => {
+                let patch = diff(last_state, state);
+                if patch.is_empty() {
+                    return None;
+                }
+
+                Some(DiffNotification::Patch {
+                    sequence,
+                    event,
+                    patch,
+                })
+            }
+            _ => Some(DiffNotification::Snapshot {
+                sequence,
+                event,
+                state,
+            }),
+        }
+    }
+}
+
+pub fn push_diff_notifications(event: &NotificationEvent, state: &State) -> Result<()> {
+    let mut sockets = SUBSCRIPTION_DIFF_SOCKETS.lock();
+    if sockets.is_empty() {
+        return Ok(());
+    }
+
+    let state = serde_json::to_value(state)?;
+    let mut stale_sockets = vec![];
+
+    for (socket, subscriber) in &mut *sockets {
+        let Some(notification) = subscriber.next_notification(event, &state) else {
+            continue;
+        };
+
+        match UnixStream::connect(&subscriber.path) {
+            Ok(mut stream) => {
+                match stream.write_all(serde_json::to_string(&notification)?.as_bytes()) {
+                    Ok(()) => {
+                        tracing::debug!("pushed diff notification to subscriber: {socket}");
+
+                        // The state is only considered acknowledged once it has been delivered
+                        subscriber.last_state = Some(state.clone());
+                        subscriber.sequence += 1;
+                    }
+                    Err(error) => {
+                        tracing::error!(
+                            "could not push diff notification to subscriber {socket}: {error}"
+                        );
+                        stale_sockets.push(socket.clone());
+                    }
+                }
+            }
+            Err(_) => {
+                stale_sockets.push(socket.clone());
+            }
+        }
+    }
+
+    for socket in stale_sockets {
+        tracing::warn!("removing stale diff subscription: {socket}");
+        sockets.remove(&socket);
+        let socket_path = DATA_DIR.join(socket);
+        if let Err(error) = std::fs::remove_file(&socket_path) {
+            tracing::error!(
+                "could not remove stale subscriber socket file at {}: {error}",
+                socket_path.display()
+            )
+        }
+    }
+
+    Ok(())
+}
+
+/// Computes the JSON-patch style operations which transform `old` into `new`
+pub fn diff(old: &Value, new: &Value) -> Vec<PatchOperation> {
+    let mut patch = vec![];
+    diff_at(old, new, String::new(), &mut patch);
+    patch
+}
+
+fn diff_at(old: &Value, new: &Value, path: String, patch: &mut Vec<PatchOperation>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for (key, old_value) in old {
+                let key_path = format!("{path}/{}", escape(key));
+                match new.get(key) {
+                    Some(new_value) => diff_at(old_value, new_value, key_path, patch),
+                    None => patch.push(PatchOperation::Remove { path: key_path }),
+                }
+            }
+
+            for (key, new_value) in new {
+                if !old.contains_key(key) {
+                    patch.push(PatchOperation::Add {
+                        path: format!("{path}/{}", escape(key)),
+                        value: new_value.clone(),
+                    });
+                }
+            }
+        }
+        (Value::Array(old), Value::Array(new)) => {
+            let common = old.len().min(new.len());
+            for (i, (old_value, new_value)) in old.iter().zip(new.iter()).enumerate() {
+                diff_at(old_value, new_value, format!("{path}/{i}"), patch);
+            }
+
+            // Remove from the back so that earlier indices remain valid
+            for i in (common..old.len()).rev() {
+                patch.push(PatchOperation::Remove {
+                    path: format!("{path}/{i}"),
+                });
+            }
+
+            for (i, value) in new.iter().enumerate().skip(common) {
+                patch.push(PatchOperation::Add {
+                    path: format!("{path}/{i}"),
+                    value: value.clone(),
+                });
+            }
+        }
+        (old, new) if old != new => patch.push(PatchOperation::Replace {
+            path,
+            value: new.clone(),
+        }),
+        _ => {}
+    }
+}
+
+/// Escapes a key for use as a JSON pointer reference token
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_unchanged() {
+        let state = json!({ "monitors": [{ "name": "DISPLAY1" }], "is_paused": false });
+        assert!(diff(&state, &state).is_empty());
+    }
+
+    #[test]
+    fn test_diff_objects() {
+        let old = json!({ "a": 1, "b": { "c": true }, "d/e": "x" });
+        let new = json!({ "a": 2, "b": { "c": true, "f": null } });
+
+        assert_eq!(
+            diff(&old, &new),
+            vec![
+                PatchOperation::Replace {
+                    path: String::from("/a"),
+                    value: json!(2)
+                },
+                PatchOperation::Add {
+                    path: String::from("/b/f"),
+                    value: Value::Null
+                },
+                PatchOperation::Remove {
+                    path: String::from("/d~1e")
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_arrays() {
+        let old = json!({ "elements": [1, 2, 3, 4] });
+        let new = json!({ "elements": [1, 5] });
+
+        assert_eq!(
+            diff(&old, &new),
+            vec![
+                PatchOperation::Replace {
+                    path: String::from("/elements/1"),
+                    value: json!(5)
+                },
+                PatchOperation::Remove {
+                    path: String::from("/elements/3")
+                },
+                PatchOperation::Remove {
+                    path: String::from("/elements/2")
+                },
+            ]
+        );
+
+        assert_eq!(
+            diff(&new, &old),
+            vec![
+                PatchOperation::Replace {
+                    path: String::from("/elements/1"),
+                    value: json!(2)
+                },
+                PatchOperation::Add {
+                    path: String::from("/elements/2"),
+                    value: json!(3)
+                },
+                PatchOperation::Add {
+                    path: String::from("/elements/3"),
+                    value: json!(4)
+                },
+            ]
+        );
+    }
+}
@@ -4,9 +4,11 @@ use crate::animation::ANIMATION_DURATION_PER_ANIMATION;
 use crate::animation::ANIMATION_ENABLED_GLOBAL;
 use crate::animation::ANIMATION_ENABLED_PER_ANIMATION;
 use crate::animation::ANIMATION_FPS;
+use crate::animation::ANIMATION_MAX_CONCURRENT;
 use crate::animation::ANIMATION_STYLE_GLOBAL;
 use crate::animation::ANIMATION_STYLE_PER_ANIMATION;
 use crate::animation::DEFAULT_ANIMATION_FPS;
+use crate::animation::WORKSPACE_SWITCH_ANIMATION;
 use crate::asc::ApplicationSpecificConfiguration;
 use crate::asc::AscApplicationRulesOrSchema;
 use crate::border_manager;
@@ -17,14 +19,21 @@ use crate::config_generation::WorkspaceMatchingRule;
 use crate::core::config_generation::ApplicationConfiguration;
 use crate::core::config_generation::ApplicationConfigurationGenerator;
 use crate::core::config_generation::ApplicationOptions;
+use crate::core::config_generation::FloatingGeometryRule;
+use crate::core::config_generation::HidingBehaviourRule;
 use crate::core::config_generation::MatchingRule;
 use crate::core::config_generation::MatchingStrategy;
+use crate::core::config_generation::MinimumTileSizeRule;
+use crate::core::config_generation::NewWindowFocusRule;
+use crate::core::config_generation::WorkspaceRule;
 use crate::core::AnimationStyle;
+use crate::core::BorderAnimation;
 use crate::core::BorderImplementation;
 use crate::core::BorderStyle;
 use crate::core::DefaultLayout;
 use crate::core::FocusFollowsMouseImplementation;
 use crate::core::HidingBehaviour;
+use crate::core::KeyModifier;
 use crate::core::Layout;
 use crate::core::MoveBehaviour;
 use crate::core::OperationBehaviour;
@@ -34,13 +43,27 @@ use crate::core::StackbarLabel;
 use crate::core::StackbarMode;
 use crate::core::WindowContainerBehaviour;
 use crate::core::WindowManagementBehaviour;
+use crate::core::WorkspaceSwitchAnimation;
 use crate::current_virtual_desktop;
 use crate::default_layout::LayoutOptions;
+use crate::eviction_manager::EvictionBehaviour;
+use crate::eviction_manager::EVICTION_BEHAVIOUR;
+use crate::eviction_manager::EVICTION_THRESHOLD;
+use crate::eviction_manager::EVICTION_WINDOW_SECONDS;
 use crate::monitor;
 use crate::monitor::Monitor;
 use crate::monitor_reconciliator;
+use crate::monitor_reconciliator::taskbar::TASKBAR_AUTO_HIDE_OFFSET;
+use crate::mouse_hook;
+use crate::mouse_hook::MOUSE_DRAG_MODIFIERS;
+use crate::process_movement;
 use crate::resolve_option_hashmap_usize_path;
 use crate::ring::Ring;
+use crate::rule_engine;
+use crate::schedule_manager;
+use crate::schedule_manager::WorkspaceSchedule;
+use crate::schedule_manager::WORKSPACE_SCHEDULES;
+use crate::stackbar_manager::STACKBAR_CLOSE_GLYPH;
 use crate::stackbar_manager::STACKBAR_FOCUSED_TEXT_COLOUR;
 use crate::stackbar_manager::STACKBAR_FONT_FAMILY;
 use crate::stackbar_manager::STACKBAR_FONT_SIZE;
@@ -60,28 +83,51 @@ use crate::workspace::Workspace;
 use crate::AspectRatio;
 use crate::Axis;
 use crate::CrossBoundaryBehaviour;
+use crate::CrossMonitorFocusBehaviour;
 use crate::FloatingLayerBehaviour;
+use crate::MinimumTileSizeBehaviour;
+use crate::NewWindowFocusBehaviour;
 use crate::Placement;
 use crate::PredefinedAspectRatio;
 use crate::ResolvedPathBuf;
 use crate::WindowHandlingBehaviour;
+use crate::ANIMATION_BLACKLIST;
+use crate::CONFIGURATION_LOADED_AT;
+use crate::CONFIGURATION_PATH;
 use crate::DATA_DIR;
 use crate::DEFAULT_CONTAINER_PADDING;
 use crate::DEFAULT_WORKSPACE_PADDING;
 use crate::DISPLAY_INDEX_PREFERENCES;
+use crate::DYNAMIC_WORKSPACES;
 use crate::FLOATING_APPLICATIONS;
+use crate::FLOATING_GEOMETRY_RULES;
 use crate::FLOATING_WINDOW_TOGGLE_ASPECT_RATIO;
+use crate::FOCUS_FOLLOWS_MOUSE_IGNORE_RULES;
 use crate::HIDING_BEHAVIOUR;
+use crate::HIDING_BEHAVIOURS;
+use crate::HIDING_BEHAVIOUR_RULES;
 use crate::IGNORE_IDENTIFIERS;
 use crate::LAYERED_WHITELIST;
 use crate::MANAGE_IDENTIFIERS;
+use crate::MAXIMIZE_MONOCLE_MATCHES;
+use crate::MAXIMIZE_MONOCLE_RULES;
+use crate::MINIMIZE_TO_TRAY_RULES;
+use crate::MINIMUM_TILE_SIZES;
+use crate::MINIMUM_TILE_SIZE_BEHAVIOUR;
+use crate::MINIMUM_TILE_SIZE_RULES;
 use crate::MONITOR_INDEX_PREFERENCES;
+use crate::NEW_WINDOW_FOCUS_BEHAVIOUR;
+use crate::NEW_WINDOW_FOCUS_RULES;
+use crate::NOTIFICATION_COALESCING_WINDOW;
 use crate::NO_TITLEBAR;
 use crate::OBJECT_NAME_CHANGE_ON_LAUNCH;
 use crate::OBJECT_NAME_CHANGE_TITLE_IGNORE_LIST;
+use crate::PRIVACY_SENSITIVE_APPLICATIONS;
 use crate::REGEX_IDENTIFIERS;
 use crate::SLOW_APPLICATION_COMPENSATION_TIME;
 use crate::SLOW_APPLICATION_IDENTIFIERS;
+use crate::SWALLOWING_RULES;
+use crate::TRANSIENT_DIALOG_RULES;
 use crate::TRANSPARENCY_BLACKLIST;
 use crate::TRAY_AND_MULTI_WINDOW_IDENTIFIERS;
 use crate::WINDOWS_11;
@@ -103,6 +149,8 @@ use std::io::Write;
 use std::path::PathBuf;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
 use uds_windows::UnixListener;
 use uds_windows::UnixStream;
 
@@ -184,7 +232,7 @@ pub struct Wallpaper {
 
 // serde_as must be before derive
 #[serde_with::serde_as]
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct WorkspaceConfig {
     /// Name
@@ -214,10 +262,18 @@ pub struct WorkspaceConfig {
     pub workspace_padding: Option<i32>,
     /// Initial workspace application rules
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub initial_workspace_rules: Option<Vec<MatchingRule>>,
+    pub initial_workspace_rules: Option<Vec<WorkspaceRule>>,
     /// Permanent workspace application rules
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub workspace_rules: Option<Vec<MatchingRule>>,
+    pub workspace_rules: Option<Vec<WorkspaceRule>>,
+    /// Applications which must never open on this workspace, matching windows are moved to the
+    /// workspace designated by their workspace rules or to the next workspace on the monitor
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocked_applications: Option<Vec<MatchingRule>>,
+    /// Keep directional focus commands on this workspace's monitor instead of crossing over to
+    /// neighbouring monitors (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contain_focus: Option<bool>,
     /// Apply this monitor's window-based work area offset (default: true)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub apply_window_based_work_area_offset: Option<bool>,
@@ -230,6 +286,9 @@ pub struct WorkspaceConfig {
     /// Enable or disable float override, which makes it so every new window opens in floating mode (default: false)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub float_override: Option<bool>,
+    /// Remove container and workspace padding when only a single container is tiled (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smart_gaps: Option<bool>,
     /// Specify an axis on which to flip the selected layout (default: None)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub layout_flip: Option<Axis>,
@@ -310,10 +369,19 @@ impl From<&Workspace> for WorkspaceConfig {
                 .workspace_config()
                 .as_ref()
                 .and_then(|c| c.workspace_rules.clone()),
+            blocked_applications: value
+                .workspace_config()
+                .as_ref()
+                .and_then(|c| c.blocked_applications.clone()),
+            contain_focus: value
+                .workspace_config()
+                .as_ref()
+                .and_then(|c| c.contain_focus),
             apply_window_based_work_area_offset: Some(value.apply_window_based_work_area_offset()),
             window_container_behaviour: *value.window_container_behaviour(),
             window_container_behaviour_rules: Option::from(window_container_behaviour_rules),
             float_override: *value.float_override(),
+            smart_gaps: Some(value.smart_gaps()),
             layout_flip: value.layout_flip(),
             floating_layer_behaviour: value.floating_layer_behaviour(),
             wallpaper: None,
@@ -325,7 +393,16 @@ impl From<&Workspace> for WorkspaceConfig {
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct MonitorConfig {
     /// Workspace configurations
+    #[serde(default)]
     pub workspaces: Vec<WorkspaceConfig>,
+    /// Number of workspaces to create on this monitor. Workspaces which are not configured in
+    /// `workspaces` are created with the default options (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_count: Option<usize>,
+    /// Names of the workspaces to create on this monitor, in order. Workspaces which are not
+    /// configured in `workspaces` are created with these names (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_names: Option<Vec<String>>,
     /// Monitor-specific work area offset (default: None)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub work_area_offset: Option<Rect>,
@@ -349,6 +426,30 @@ pub struct MonitorConfig {
     pub floating_layer_behaviour: Option<FloatingLayerBehaviour>,
 }
 
+impl MonitorConfig {
+    /// Generates the workspaces declared inline with `workspace_count` and `workspace_names`
+    /// which have not been configured in `workspaces`. Generated workspaces without a name in
+    /// `workspace_names` are named after their position on the monitor.
+    pub fn generate_workspaces(&mut self) {
+        let names = self.workspace_names.clone().unwrap_or_default();
+        let count = self
+            .workspace_count
+            .unwrap_or_default()
+            .max(names.len())
+            .max(self.workspaces.len());
+
+        for idx in self.workspaces.len()..count {
+            self.workspaces.push(WorkspaceConfig {
+                name: names
+                    .get(idx)
+                    .cloned()
+                    .unwrap_or_else(|| (idx + 1).to_string()),
+                ..Default::default()
+            });
+        }
+    }
+}
+
 impl From<&Monitor> for MonitorConfig {
     fn from(value: &Monitor) -> Self {
         let mut workspaces = vec![];
@@ -377,6 +478,8 @@ impl From<&Monitor> for MonitorConfig {
 
         Self {
             workspaces,
+            workspace_count: None,
+            workspace_names: None,
             work_area_offset: value.work_area_offset(),
             window_based_work_area_offset: value.window_based_work_area_offset(),
             window_based_work_area_offset_limit: Some(value.window_based_work_area_offset_limit()),
@@ -448,15 +551,36 @@ pub struct StaticConfig {
     /// Determine what happens when an action is called on a window at a monitor boundary (default: Monitor)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cross_boundary_behaviour: Option<CrossBoundaryBehaviour>,
+    /// Determine which window is focused when focus moves across a monitor boundary (default: Layout)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cross_monitor_focus_behaviour: Option<CrossMonitorFocusBehaviour>,
     /// Determine what happens when commands are sent while an unmanaged window is in the foreground (default: Op)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub unmanaged_window_operation_behaviour: Option<OperationBehaviour>,
-    /// END OF LIFE FEATURE: Use https://github.com/LGUG2Z/masir instead
+    /// Enable or disable focus follows mouse for the operating system (default: None)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub focus_follows_mouse: Option<FocusFollowsMouseImplementation>,
-    /// Enable or disable mouse follows focus (default: true)
+    /// How long the cursor has to rest over a window before it is focused by the komorebi focus
+    /// follows mouse implementation, in milliseconds (default: 0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focus_follows_mouse_delay: Option<u64>,
+    /// Identify menus, tooltips and other popups which should never cause the window beneath them
+    /// to be focused by the komorebi focus follows mouse implementation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focus_follows_mouse_ignore_rules: Option<Vec<MatchingRule>>,
+    /// Enable or disable mouse follows focus, which moves the mouse cursor to the center of the
+    /// window focused by a komorebi command (default: true)
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(alias = "cursor_follows_focus")]
     pub mouse_follows_focus: Option<bool>,
+    /// Modifier keys which, while held, allow any window to be moved with a left-click drag and
+    /// resized from its nearest corner with a right-click drag (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mouse_drag_modifiers: Option<Vec<KeyModifier>>,
+    /// Distance in pixels within which floating windows moved or resized with the mouse snap to
+    /// monitor edges and other floating windows, 0 disables snapping (default: 10)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub floating_window_snap_distance: Option<i32>,
     /// Path to applications.json from komorebi-application-specific-configurations (default: None)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub app_specific_configuration_path: Option<AppSpecificConfigurationPath>,
@@ -480,6 +604,9 @@ pub struct StaticConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(alias = "active_window_border_style")]
     pub border_style: Option<BorderStyle>,
+    /// Animate the border of the focused window, paused while battery saver is on (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub border_animation: Option<BorderAnimation>,
     /// DEPRECATED from v0.1.31: no longer required
     #[serde(skip_serializing_if = "Option::is_none")]
     pub border_z_order: Option<ZOrder>,
@@ -495,6 +622,13 @@ pub struct StaticConfig {
     /// Individual window transparency ignore rules
     #[serde(skip_serializing_if = "Option::is_none")]
     pub transparency_ignore_rules: Option<Vec<MatchingRule>>,
+    /// Individual window animation ignore rules, matching windows are always moved instantly
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub animation_ignore_rules: Option<Vec<MatchingRule>>,
+    /// Identify applications which are hidden in a per-workspace list instead of being minimized
+    /// or closed with komorebic, to be brought back with `komorebic restore-hidden`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimize_to_tray_rules: Option<Vec<MatchingRule>>,
     /// Global default workspace padding (default: 10)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default_workspace_padding: Option<i32>,
@@ -504,12 +638,23 @@ pub struct StaticConfig {
     /// Monitor and workspace configurations
     #[serde(skip_serializing_if = "Option::is_none")]
     pub monitors: Option<Vec<MonitorConfig>>,
+    /// Workspace rules which only apply at certain times of the day, sending matching windows to
+    /// a named workspace while active
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_schedules: Option<Vec<WorkspaceSchedule>>,
     /// Which Windows signal to use when hiding windows (default: Cloak)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub window_hiding_behaviour: Option<HidingBehaviour>,
+    /// Window hiding behaviours for matching applications, overriding `window_hiding_behaviour`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hiding_behaviour_rules: Option<Vec<HidingBehaviourRule>>,
     /// Global work area (space used for tiling) offset (default: None)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub global_work_area_offset: Option<Rect>,
+    /// Reserve space for an auto-hiding taskbar in the work area of its monitor while the taskbar
+    /// is shown (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub taskbar_auto_hide_offset: Option<bool>,
     /// Individual window floating rules
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(alias = "float_rules")]
@@ -520,6 +665,25 @@ pub struct StaticConfig {
     /// Identify applications which should be managed as floating windows
     #[serde(skip_serializing_if = "Option::is_none")]
     pub floating_applications: Option<Vec<MatchingRule>>,
+    /// Pin the position and size of matching applications whenever they spawn as or are toggled
+    /// to floating windows, in pixels or percentages of the work area
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub floating_geometry_rules: Option<Vec<FloatingGeometryRule>>,
+    /// Identify terminal applications whose tiles should be swallowed by the GUI applications they launch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub swallowing_rules: Option<Vec<MatchingRule>>,
+    /// Identify applications which force themselves maximized, so that maximizing them puts them in
+    /// monocle on their workspace and restoring them takes them out of it again
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximize_monocle_rules: Option<Vec<MatchingRule>>,
+    /// Identify applications whose dialog windows should be stacked in the container of the window
+    /// that owns them instead of being given a new tile
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transient_dialog_rules: Option<Vec<MatchingRule>>,
+    /// Identify applications which should be left out of the bar's workspace icons, previews and
+    /// taskbar while privacy mode is on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub privacy_sensitive_applications: Option<Vec<MatchingRule>>,
     /// Identify border overflow applications
     #[serde(skip_serializing_if = "Option::is_none")]
     pub border_overflow_applications: Option<Vec<MatchingRule>>,
@@ -547,6 +711,10 @@ pub struct StaticConfig {
     /// Animations configuration options
     #[serde(skip_serializing_if = "Option::is_none")]
     pub animation: Option<AnimationsConfig>,
+    /// Eviction of tiled windows which keep moving themselves back out of the position they are
+    /// placed in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub window_eviction: Option<WindowEvictionConfig>,
     /// Theme configuration options
     #[serde(skip_serializing_if = "Option::is_none")]
     pub theme: Option<KomorebiTheme>,
@@ -570,6 +738,25 @@ pub struct StaticConfig {
     /// Which Windows API behaviour to use when manipulating windows (default: Sync)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub window_handling_behaviour: Option<WindowHandlingBehaviour>,
+    /// Remove empty, unnamed workspaces created on demand when they are no longer focused (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dynamic_workspaces: Option<bool>,
+    /// Suppress identical consecutive notifications and coalesce cloak/uncloak chatter sent to
+    /// subscribers within this window, in milliseconds (default: 0, disabled)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notification_coalescing_window: Option<u64>,
+    /// Minimum tile sizes for matching applications
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum_tile_size_rules: Option<Vec<MinimumTileSizeRule>>,
+    /// What to do when a tile would be smaller than the minimum tile size of its window (default: Reflow)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum_tile_size_behaviour: Option<MinimumTileSizeBehaviour>,
+    /// Whether newly created windows receive focus (default: Always)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_window_focus_behaviour: Option<NewWindowFocusBehaviour>,
+    /// New window focus behaviours for matching applications, overriding `new_window_focus_behaviour`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_window_focus_rules: Option<Vec<NewWindowFocusRule>>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -586,6 +773,29 @@ pub struct AnimationsConfig {
     /// Set the animation FPS (default: 60)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fps: Option<u64>,
+    /// Set the maximum number of animations which can run at the same time, any further
+    /// animations jump straight to their final frame (default: unlimited)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_animations: Option<usize>,
+    /// Set the animation used when switching workspaces, which is only enabled when `enabled` is
+    /// set for `workspace_switch` specifically (default: Slide)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_switch: Option<WorkspaceSwitchAnimation>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WindowEvictionConfig {
+    /// Number of rejected placements after which a window is evicted, 0 disables eviction
+    /// (default: 0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub threshold: Option<usize>,
+    /// Number of seconds within which the rejected placements have to happen (default: 10)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seconds: Option<u64>,
+    /// What to do with an evicted window (default: Float)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub behaviour: Option<EvictionBehaviour>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -701,11 +911,7 @@ pub enum KomorebiTheme {
 
 impl StaticConfig {
     pub fn end_of_life(raw: &str) {
-        let features = vec![
-            "focus_follows_mouse",
-            "custom_layout",
-            "custom_layout_rules",
-        ];
+        let features = vec!["custom_layout", "custom_layout_rules"];
 
         let mut display = false;
 
@@ -801,6 +1007,10 @@ pub struct TabsConfig {
     /// Font size
     #[serde(skip_serializing_if = "Option::is_none")]
     pub font_size: Option<i32>,
+    /// Glyph drawn at the end of each tab which closes the tab's window when clicked, e.g. "✕"
+    /// (default: none)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub close_glyph: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -871,12 +1081,17 @@ impl From<&WindowManager> for StaticConfig {
             ),
             cross_monitor_move_behaviour: Option::from(value.cross_monitor_move_behaviour),
             cross_boundary_behaviour: Option::from(value.cross_boundary_behaviour),
+            cross_monitor_focus_behaviour: Option::from(value.cross_monitor_focus_behaviour),
             unmanaged_window_operation_behaviour: Option::from(
                 value.unmanaged_window_operation_behaviour,
             ),
             minimum_window_height: Some(window::MINIMUM_HEIGHT.load(Ordering::SeqCst)),
             minimum_window_width: Some(window::MINIMUM_WIDTH.load(Ordering::SeqCst)),
             focus_follows_mouse: value.focus_follows_mouse,
+            focus_follows_mouse_delay: Option::from(
+                process_movement::FOCUS_FOLLOWS_MOUSE_DELAY.load(Ordering::SeqCst),
+            ),
+            focus_follows_mouse_ignore_rules: None,
             mouse_follows_focus: Option::from(value.mouse_follows_focus),
             app_specific_configuration_path: None,
             border_width: Option::from(border_manager::BORDER_WIDTH.load(Ordering::SeqCst)),
@@ -890,7 +1105,10 @@ impl From<&WindowManager> for StaticConfig {
                 transparency_manager::TRANSPARENCY_ALPHA.load(Ordering::SeqCst),
             ),
             transparency_ignore_rules: None,
+            animation_ignore_rules: None,
+            minimize_to_tray_rules: None,
             border_style: Option::from(STYLE.load()),
+            border_animation: border_manager::ANIMATION.load(),
             border_z_order: None,
             border_implementation: Option::from(IMPLEMENTATION.load()),
             default_workspace_padding: Option::from(
@@ -900,10 +1118,23 @@ impl From<&WindowManager> for StaticConfig {
                 DEFAULT_CONTAINER_PADDING.load(Ordering::SeqCst),
             ),
             monitors: Option::from(monitors),
+            workspace_schedules: None,
             window_hiding_behaviour: Option::from(*HIDING_BEHAVIOUR.lock()),
+            hiding_behaviour_rules: Option::from(HIDING_BEHAVIOUR_RULES.lock().clone()),
+            mouse_drag_modifiers: Some(MOUSE_DRAG_MODIFIERS.lock().clone())
+                .filter(|modifiers| !modifiers.is_empty()),
+            floating_window_snap_distance: Option::from(
+                mouse_hook::SNAP_DISTANCE.load(Ordering::SeqCst),
+            ),
             global_work_area_offset: value.work_area_offset,
+            taskbar_auto_hide_offset: Option::from(TASKBAR_AUTO_HIDE_OFFSET.load(Ordering::SeqCst)),
             ignore_rules: None,
             floating_applications: None,
+            floating_geometry_rules: Option::from(FLOATING_GEOMETRY_RULES.lock().clone()),
+            swallowing_rules: None,
+            maximize_monocle_rules: None,
+            transient_dialog_rules: None,
+            privacy_sensitive_applications: None,
             manage_rules: None,
             border_overflow_applications: None,
             tray_and_multi_window_applications: None,
@@ -923,6 +1154,11 @@ impl From<&WindowManager> for StaticConfig {
             display_index_preferences: Option::from(DISPLAY_INDEX_PREFERENCES.read().clone()),
             stackbar: None,
             animation: None,
+            window_eviction: Option::from(WindowEvictionConfig {
+                threshold: Option::from(EVICTION_THRESHOLD.load(Ordering::SeqCst)),
+                seconds: Option::from(EVICTION_WINDOW_SECONDS.load(Ordering::SeqCst)),
+                behaviour: Option::from(EVICTION_BEHAVIOUR.load()),
+            }),
             theme: None,
             slow_application_compensation_time: Option::from(
                 SLOW_APPLICATION_COMPENSATION_TIME.load(Ordering::SeqCst),
@@ -932,6 +1168,14 @@ impl From<&WindowManager> for StaticConfig {
             remove_titlebar_applications: Option::from(NO_TITLEBAR.lock().clone()),
             floating_window_aspect_ratio: Option::from(*FLOATING_WINDOW_TOGGLE_ASPECT_RATIO.lock()),
             window_handling_behaviour: Option::from(WINDOW_HANDLING_BEHAVIOUR.load()),
+            dynamic_workspaces: Option::from(DYNAMIC_WORKSPACES.load(Ordering::SeqCst)),
+            notification_coalescing_window: Option::from(
+                NOTIFICATION_COALESCING_WINDOW.load(Ordering::SeqCst),
+            ),
+            minimum_tile_size_rules: Option::from(MINIMUM_TILE_SIZE_RULES.lock().clone()),
+            minimum_tile_size_behaviour: Option::from(MINIMUM_TILE_SIZE_BEHAVIOUR.load()),
+            new_window_focus_behaviour: Option::from(NEW_WINDOW_FOCUS_BEHAVIOUR.load()),
+            new_window_focus_rules: Option::from(NEW_WINDOW_FOCUS_RULES.lock().clone()),
         }
     }
 }
@@ -953,11 +1197,30 @@ impl StaticConfig {
             preferences.clone_from(display_index_preferences);
         }
 
+        if let Err(error) = monitor_reconciliator::load_persisted_display_index_preferences() {
+            tracing::warn!("could not load persisted display index preferences: {error}");
+        }
+
         if let Some(behaviour) = self.window_hiding_behaviour {
             let mut window_hiding_behaviour = HIDING_BEHAVIOUR.lock();
             *window_hiding_behaviour = behaviour;
         }
 
+        let mouse_drag_modifiers = self.mouse_drag_modifiers.clone().unwrap_or_default();
+        if !mouse_drag_modifiers.is_empty() {
+            mouse_hook::start();
+        }
+        mouse_hook::set_mouse_drag_modifiers(mouse_drag_modifiers);
+
+        if let Some(distance) = self.floating_window_snap_distance {
+            mouse_hook::SNAP_DISTANCE.store(distance, Ordering::SeqCst);
+        }
+
+        process_movement::FOCUS_FOLLOWS_MOUSE_DELAY.store(
+            self.focus_follows_mouse_delay.unwrap_or_default(),
+            Ordering::SeqCst,
+        );
+
         if let Some(height) = self.minimum_window_height {
             window::MINIMUM_HEIGHT.store(height, Ordering::SeqCst);
         }
@@ -966,6 +1229,12 @@ impl StaticConfig {
             window::MINIMUM_WIDTH.store(width, Ordering::SeqCst);
         }
 
+        if let Some(eviction) = &self.window_eviction {
+            EVICTION_THRESHOLD.store(eviction.threshold.unwrap_or(0), Ordering::SeqCst);
+            EVICTION_WINDOW_SECONDS.store(eviction.seconds.unwrap_or(10), Ordering::SeqCst);
+            EVICTION_BEHAVIOUR.store(eviction.behaviour.unwrap_or_default());
+        }
+
         if let Some(animations) = &self.animation {
             match &animations.enabled {
                 PerAnimationPrefixConfig::Prefix(enabled) => {
@@ -1004,6 +1273,13 @@ impl StaticConfig {
                 animations.fps.unwrap_or(DEFAULT_ANIMATION_FPS),
                 Ordering::SeqCst,
             );
+
+            ANIMATION_MAX_CONCURRENT.store(
+                animations.max_concurrent_animations.unwrap_or(usize::MAX),
+                Ordering::SeqCst,
+            );
+
+            WORKSPACE_SWITCH_ANIMATION.store(animations.workspace_switch.unwrap_or_default());
         }
 
         if let Some(container) = self.default_container_padding {
@@ -1046,6 +1322,7 @@ impl StaticConfig {
         }
 
         STYLE.store(self.border_style.unwrap_or_default());
+        border_manager::ANIMATION.store(self.border_animation);
 
         if !*WINDOWS_11
             && matches!(
@@ -1083,8 +1360,14 @@ impl StaticConfig {
         let mut object_name_change_title_ignore_list = OBJECT_NAME_CHANGE_TITLE_IGNORE_LIST.lock();
         let mut layered_identifiers = LAYERED_WHITELIST.lock();
         let mut transparency_blacklist = TRANSPARENCY_BLACKLIST.lock();
+        let mut animation_blacklist = ANIMATION_BLACKLIST.lock();
+        let mut minimize_to_tray_rules = MINIMIZE_TO_TRAY_RULES.lock();
         let mut slow_application_identifiers = SLOW_APPLICATION_IDENTIFIERS.lock();
         let mut floating_applications = FLOATING_APPLICATIONS.lock();
+        let mut swallowing_rules = SWALLOWING_RULES.lock();
+        let mut maximize_monocle_rules = MAXIMIZE_MONOCLE_RULES.lock();
+        let mut transient_dialog_rules = TRANSIENT_DIALOG_RULES.lock();
+        let mut privacy_sensitive_applications = PRIVACY_SENSITIVE_APPLICATIONS.lock();
         let mut no_titlebar_applications = NO_TITLEBAR.lock();
 
         if let Some(rules) = &mut self.ignore_rules {
@@ -1095,6 +1378,27 @@ impl StaticConfig {
             populate_rules(rules, &mut floating_applications, &mut regex_identifiers)?;
         }
 
+        if let Some(rules) = &mut self.swallowing_rules {
+            populate_rules(rules, &mut swallowing_rules, &mut regex_identifiers)?;
+        }
+
+        if let Some(rules) = &mut self.maximize_monocle_rules {
+            populate_rules(rules, &mut maximize_monocle_rules, &mut regex_identifiers)?;
+            MAXIMIZE_MONOCLE_MATCHES.lock().clear();
+        }
+
+        if let Some(rules) = &mut self.transient_dialog_rules {
+            populate_rules(rules, &mut transient_dialog_rules, &mut regex_identifiers)?;
+        }
+
+        if let Some(rules) = &mut self.privacy_sensitive_applications {
+            populate_rules(
+                rules,
+                &mut privacy_sensitive_applications,
+                &mut regex_identifiers,
+            )?;
+        }
+
         if let Some(rules) = &mut self.manage_rules {
             populate_rules(rules, &mut manage_identifiers, &mut regex_identifiers)?;
         }
@@ -1134,6 +1438,29 @@ impl StaticConfig {
             populate_rules(rules, &mut transparency_blacklist, &mut regex_identifiers)?;
         }
 
+        if let Some(rules) = &mut self.animation_ignore_rules {
+            populate_rules(rules, &mut animation_blacklist, &mut regex_identifiers)?;
+        }
+
+        if let Some(rules) = &mut self.minimize_to_tray_rules {
+            populate_rules(rules, &mut minimize_to_tray_rules, &mut regex_identifiers)?;
+        }
+
+        let mut workspace_schedules = self.workspace_schedules.clone().unwrap_or_default();
+        for schedule in &mut workspace_schedules {
+            populate_rules(&mut schedule.rules, &mut vec![], &mut regex_identifiers)?;
+        }
+
+        *WORKSPACE_SCHEDULES.lock() = workspace_schedules;
+
+        for monitor in self.monitors.iter_mut().flatten() {
+            for workspace in &mut monitor.workspaces {
+                if let Some(rules) = &mut workspace.blocked_applications {
+                    populate_rules(rules, &mut vec![], &mut regex_identifiers)?;
+                }
+            }
+        }
+
         if let Some(rules) = &mut self.slow_application_identifiers {
             populate_rules(
                 rules,
@@ -1146,6 +1473,82 @@ impl StaticConfig {
             populate_rules(rules, &mut no_titlebar_applications, &mut regex_identifiers)?;
         }
 
+        if let Some(rules) = &mut self.minimum_tile_size_rules {
+            let mut matching_rules = rules
+                .iter()
+                .map(|rule| rule.matching_rule.clone())
+                .collect::<Vec<_>>();
+
+            populate_rules(&mut matching_rules, &mut vec![], &mut regex_identifiers)?;
+
+            for (rule, matching_rule) in rules.iter_mut().zip(matching_rules) {
+                rule.matching_rule = matching_rule;
+            }
+
+            let mut minimum_tile_size_rules = MINIMUM_TILE_SIZE_RULES.lock();
+            minimum_tile_size_rules.clone_from(rules);
+            MINIMUM_TILE_SIZES.lock().clear();
+        }
+
+        if let Some(rules) = &mut self.new_window_focus_rules {
+            let mut matching_rules = rules
+                .iter()
+                .map(|rule| rule.matching_rule.clone())
+                .collect::<Vec<_>>();
+
+            populate_rules(&mut matching_rules, &mut vec![], &mut regex_identifiers)?;
+
+            for (rule, matching_rule) in rules.iter_mut().zip(matching_rules) {
+                rule.matching_rule = matching_rule;
+            }
+
+            let mut new_window_focus_rules = NEW_WINDOW_FOCUS_RULES.lock();
+            new_window_focus_rules.clone_from(rules);
+        }
+
+        if let Some(rules) = &mut self.hiding_behaviour_rules {
+            let mut matching_rules = rules
+                .iter()
+                .map(|rule| rule.matching_rule.clone())
+                .collect::<Vec<_>>();
+
+            populate_rules(&mut matching_rules, &mut vec![], &mut regex_identifiers)?;
+
+            for (rule, matching_rule) in rules.iter_mut().zip(matching_rules) {
+                rule.matching_rule = matching_rule;
+            }
+
+            let mut hiding_behaviour_rules = HIDING_BEHAVIOUR_RULES.lock();
+            hiding_behaviour_rules.clone_from(rules);
+            HIDING_BEHAVIOURS.lock().clear();
+        }
+
+        if let Some(rules) = &mut self.floating_geometry_rules {
+            let mut matching_rules = rules
+                .iter()
+                .map(|rule| rule.matching_rule.clone())
+                .collect::<Vec<_>>();
+
+            populate_rules(&mut matching_rules, &mut vec![], &mut regex_identifiers)?;
+
+            for (rule, matching_rule) in rules.iter_mut().zip(matching_rules) {
+                rule.matching_rule = matching_rule;
+            }
+
+            let mut floating_geometry_rules = FLOATING_GEOMETRY_RULES.lock();
+            floating_geometry_rules.clone_from(rules);
+        }
+
+        if let Some(rules) = &mut self.focus_follows_mouse_ignore_rules {
+            let mut focus_follows_mouse_ignore_rules = FOCUS_FOLLOWS_MOUSE_IGNORE_RULES.lock();
+            focus_follows_mouse_ignore_rules.clear();
+            populate_rules(
+                rules,
+                &mut focus_follows_mouse_ignore_rules,
+                &mut regex_identifiers,
+            )?;
+        }
+
         if let Some(stackbar) = &self.stackbar {
             if let Some(height) = &stackbar.height {
                 STACKBAR_TAB_HEIGHT.store(*height, Ordering::SeqCst);
@@ -1179,6 +1582,7 @@ impl StaticConfig {
 
                 STACKBAR_FONT_SIZE.store(tabs.font_size.unwrap_or(0), Ordering::SeqCst);
                 *STACKBAR_FONT_FAMILY.lock() = tabs.font_family.clone();
+                *STACKBAR_CLOSE_GLYPH.lock() = tabs.close_glyph.clone();
             }
         }
 
@@ -1223,16 +1627,44 @@ impl StaticConfig {
             WINDOW_HANDLING_BEHAVIOUR.store(behaviour);
         }
 
+        MINIMUM_TILE_SIZE_BEHAVIOUR.store(self.minimum_tile_size_behaviour.unwrap_or_default());
+        NEW_WINDOW_FOCUS_BEHAVIOUR.store(self.new_window_focus_behaviour.unwrap_or_default());
+
+        DYNAMIC_WORKSPACES.store(
+            self.dynamic_workspaces.unwrap_or_default(),
+            Ordering::SeqCst,
+        );
+
+        TASKBAR_AUTO_HIDE_OFFSET.store(
+            self.taskbar_auto_hide_offset.unwrap_or_default(),
+            Ordering::SeqCst,
+        );
+        monitor_reconciliator::taskbar::send_notification();
+
+        NOTIFICATION_COALESCING_WINDOW.store(
+            self.notification_coalescing_window.unwrap_or_default(),
+            Ordering::SeqCst,
+        );
+
         Ok(())
     }
 
     pub fn read_raw(raw: &str) -> Result<Self> {
-        Ok(serde_json::from_str(raw)?)
+        let mut value: Self = serde_json::from_str(raw)?;
+        value.generate_monitor_workspaces();
+
+        Ok(value)
     }
 
     pub fn read(path: &PathBuf) -> Result<Self> {
         let content = std::fs::read_to_string(path)?;
-        serde_json::from_str(&content).map_err(Into::into)
+        Self::read_raw(&content)
+    }
+
+    fn generate_monitor_workspaces(&mut self) {
+        for monitor in self.monitors.iter_mut().flatten() {
+            monitor.generate_workspaces();
+        }
     }
 
     #[allow(clippy::too_many_lines)]
@@ -1243,6 +1675,7 @@ impl StaticConfig {
     ) -> Result<WindowManager> {
         let mut value = Self::read(path)?;
         value.apply_globals()?;
+        set_active_configuration(path);
 
         let listener = match unix_listener {
             Some(listener) => listener,
@@ -1294,6 +1727,9 @@ impl StaticConfig {
             cross_boundary_behaviour: value
                 .cross_boundary_behaviour
                 .unwrap_or(CrossBoundaryBehaviour::Monitor),
+            cross_monitor_focus_behaviour: value
+                .cross_monitor_focus_behaviour
+                .unwrap_or(CrossMonitorFocusBehaviour::Layout),
             unmanaged_window_operation_behaviour: value
                 .unmanaged_window_operation_behaviour
                 .unwrap_or(OperationBehaviour::Op),
@@ -1306,6 +1742,9 @@ impl StaticConfig {
             already_moved_window_handles: Arc::new(Mutex::new(HashSet::new())),
             uncloack_to_ignore: 0,
             known_hwnds: HashMap::new(),
+            swallowed_windows: HashMap::new(),
+            window_owners: HashMap::new(),
+            marks: HashMap::new(),
         };
 
         match value.focus_follows_mouse {
@@ -1431,8 +1870,9 @@ impl StaticConfig {
                             workspace_matching_rules.push(WorkspaceMatchingRule {
                                 monitor_index: i,
                                 workspace_index: j,
-                                matching_rule: r.clone(),
+                                matching_rule: r.matching_rule().clone(),
                                 initial_only: false,
+                                follow: r.follow(),
                             });
                         }
                     }
@@ -1442,8 +1882,9 @@ impl StaticConfig {
                             workspace_matching_rules.push(WorkspaceMatchingRule {
                                 monitor_index: i,
                                 workspace_index: j,
-                                matching_rule: r.clone(),
+                                matching_rule: r.matching_rule().clone(),
                                 initial_only: true,
+                                follow: r.follow(),
                             });
                         }
                     }
@@ -1506,6 +1947,7 @@ impl StaticConfig {
             }
         }
 
+        schedule_manager::update_active_schedules(&mut wm);
         wm.enforce_workspace_rules()?;
 
         if value.border == Some(true) {
@@ -1519,6 +1961,7 @@ impl StaticConfig {
         let mut value = Self::read(path)?;
 
         value.apply_globals()?;
+        set_active_configuration(path);
 
         let configs_with_preference: Vec<_> =
             DISPLAY_INDEX_PREFERENCES.read().keys().copied().collect();
@@ -1606,8 +2049,9 @@ impl StaticConfig {
                             workspace_matching_rules.push(WorkspaceMatchingRule {
                                 monitor_index: i,
                                 workspace_index: j,
-                                matching_rule: r.clone(),
+                                matching_rule: r.matching_rule().clone(),
                                 initial_only: false,
+                                follow: r.follow(),
                             });
                         }
                     }
@@ -1617,8 +2061,9 @@ impl StaticConfig {
                             workspace_matching_rules.push(WorkspaceMatchingRule {
                                 monitor_index: i,
                                 workspace_index: j,
-                                matching_rule: r.clone(),
+                                matching_rule: r.matching_rule().clone(),
                                 initial_only: true,
+                                follow: r.follow(),
                             });
                         }
                     }
@@ -1681,6 +2126,7 @@ impl StaticConfig {
             }
         }
 
+        schedule_manager::update_active_schedules(wm);
         wm.enforce_workspace_rules()?;
 
         border_manager::BORDER_ENABLED.store(value.border.unwrap_or(true), Ordering::SeqCst);
@@ -1700,6 +2146,7 @@ impl StaticConfig {
             value.float_rule_placement.unwrap_or(Placement::None);
         wm.cross_monitor_move_behaviour = value.cross_monitor_move_behaviour.unwrap_or_default();
         wm.cross_boundary_behaviour = value.cross_boundary_behaviour.unwrap_or_default();
+        wm.cross_monitor_focus_behaviour = value.cross_monitor_focus_behaviour.unwrap_or_default();
         wm.unmanaged_window_operation_behaviour = value
             .unmanaged_window_operation_behaviour
             .unwrap_or_default();
@@ -1728,6 +2175,15 @@ impl StaticConfig {
     }
 }
 
+/// Records `path` as the static configuration which is live, to be reported in the State
+fn set_active_configuration(path: &PathBuf) {
+    *CONFIGURATION_PATH.lock() = Option::from(path.clone());
+    *CONFIGURATION_LOADED_AT.lock() = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|elapsed| elapsed.as_secs());
+}
+
 fn populate_option(
     entry: &mut ApplicationConfiguration,
     identifiers: &mut Vec<MatchingRule>,
@@ -1761,28 +2217,14 @@ fn populate_rules(
 ) -> Result<()> {
     for matching_rule in matching_rules {
         if !identifiers.contains(matching_rule) {
-            match matching_rule {
-                MatchingRule::Simple(simple) => {
-                    if simple.matching_strategy.is_none() {
-                        simple.matching_strategy = Option::from(MatchingStrategy::Legacy);
-                    }
-
-                    if matches!(simple.matching_strategy, Some(MatchingStrategy::Regex)) {
-                        let re = Regex::new(&simple.id)?;
-                        regex_identifiers.insert(simple.id.clone(), re);
-                    }
+            for rule in rule_engine::identifiers_mut(matching_rule) {
+                if rule.matching_strategy.is_none() {
+                    rule.matching_strategy = Option::from(MatchingStrategy::Legacy);
                 }
-                MatchingRule::Composite(composite) => {
-                    for rule in composite {
-                        if rule.matching_strategy.is_none() {
-                            rule.matching_strategy = Option::from(MatchingStrategy::Legacy);
-                        }
 
-                        if matches!(rule.matching_strategy, Some(MatchingStrategy::Regex)) {
-                            let re = Regex::new(&rule.id)?;
-                            regex_identifiers.insert(rule.id.clone(), re);
-                        }
-                    }
+                if matches!(rule.matching_strategy, Some(MatchingStrategy::Regex)) {
+                    let re = Regex::new(&rule.id)?;
+                    regex_identifiers.insert(rule.id.clone(), re);
                 }
             }
             identifiers.push(matching_rule.clone());
@@ -1990,4 +2432,45 @@ mod tests {
         let config = serde_json::from_str::<WorkspaceConfig>(config).unwrap();
         assert_eq!(config.custom_layout_rules, None);
     }
+
+    #[test]
+    fn generate_monitor_workspaces() {
+        let config = r#"
+        {
+            "monitors": [
+                {
+                    "workspace_count": 4,
+                    "workspace_names": ["I", "II", "III"],
+                    "workspaces": [
+                        {
+                            "name": "main",
+                            "layout": "Grid"
+                        }
+                    ]
+                },
+                {
+                    "workspace_names": ["web", "chat"]
+                }
+            ]
+        }
+        "#;
+        let config = StaticConfig::read_raw(config).unwrap();
+        let monitors = config.monitors.unwrap();
+
+        let names = monitors[0]
+            .workspaces
+            .iter()
+            .map(|w| w.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["main", "II", "III", "4"]);
+        assert!(monitors[0].workspaces[0].layout.is_some());
+        assert_eq!(monitors[0].workspaces[1].layout, None);
+
+        let names = monitors[1]
+            .workspaces
+            .iter()
+            .map(|w| w.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["web", "chat"]);
+    }
 }
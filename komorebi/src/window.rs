@@ -10,15 +10,20 @@ use crate::animation::ANIMATION_ENABLED_PER_ANIMATION;
 use crate::animation::ANIMATION_MANAGER;
 use crate::animation::ANIMATION_STYLE_GLOBAL;
 use crate::animation::ANIMATION_STYLE_PER_ANIMATION;
+use crate::animation::WORKSPACE_SWITCH_ANIMATION;
 use crate::border_manager;
 use crate::com::SetCloak;
+use crate::core::config_generation::FloatingGeometryRule;
 use crate::core::config_generation::IdWithIdentifier;
 use crate::core::config_generation::MatchingRule;
 use crate::core::config_generation::MatchingStrategy;
 use crate::core::ApplicationIdentifier;
 use crate::core::HidingBehaviour;
+use crate::core::NewWindowFocusBehaviour;
 use crate::core::Rect;
+use crate::core::WorkspaceSwitchAnimation;
 use crate::focus_manager;
+use crate::rule_engine;
 use crate::stackbar_manager;
 use crate::styles::ExtendedWindowStyle;
 use crate::styles::WindowStyle;
@@ -27,15 +32,29 @@ use crate::window_manager_event::WindowManagerEvent;
 use crate::windows_api;
 use crate::windows_api::WindowsApi;
 use crate::AnimationStyle;
+use crate::ANIMATION_BLACKLIST;
 use crate::FLOATING_APPLICATIONS;
+use crate::FLOATING_GEOMETRY_RULES;
 use crate::FLOATING_WINDOW_TOGGLE_ASPECT_RATIO;
 use crate::HIDDEN_HWNDS;
 use crate::HIDING_BEHAVIOUR;
+use crate::HIDING_BEHAVIOURS;
+use crate::HIDING_BEHAVIOUR_RULES;
 use crate::IGNORE_IDENTIFIERS;
 use crate::LAYERED_WHITELIST;
 use crate::MANAGE_IDENTIFIERS;
+use crate::MAXIMIZE_MONOCLE_MATCHES;
+use crate::MAXIMIZE_MONOCLE_RULES;
+use crate::MINIMIZE_TO_TRAY_RULES;
+use crate::MINIMUM_TILE_SIZES;
+use crate::MINIMUM_TILE_SIZE_RULES;
+use crate::NEW_WINDOW_FOCUS_BEHAVIOUR;
+use crate::NEW_WINDOW_FOCUS_RULES;
 use crate::NO_TITLEBAR;
+use crate::OFF_SCREEN_RECTS;
 use crate::PERMAIGNORE_CLASSES;
+use crate::PRIVACY_MODE;
+use crate::PRIVACY_SENSITIVE_APPLICATIONS;
 use crate::REGEX_IDENTIFIERS;
 use crate::SLOW_APPLICATION_COMPENSATION_TIME;
 use crate::SLOW_APPLICATION_IDENTIFIERS;
@@ -134,12 +153,17 @@ impl Serialize for Window {
     {
         let mut state = serializer.serialize_struct("Window", 5)?;
         state.serialize_field("hwnd", &self.hwnd)?;
-        state.serialize_field(
-            "title",
-            &self
-                .title()
-                .unwrap_or_else(|_| String::from("could not get window title")),
-        )?;
+        // Titles are blanked while privacy mode is on so that they don't leak to subscribers
+        if PRIVACY_MODE.load(Ordering::SeqCst) {
+            state.serialize_field("title", "")?;
+        } else {
+            state.serialize_field(
+                "title",
+                &self
+                    .title()
+                    .unwrap_or_else(|_| String::from("could not get window title")),
+            )?;
+        }
         state.serialize_field(
             "exe",
             &self
@@ -200,6 +224,12 @@ impl RenderDispatcher for MovementRenderDispatcher {
         Ok(())
     }
 
+    fn retarget(&mut self) -> Result<()> {
+        self.start_rect = WindowsApi::window_rect(self.hwnd)?;
+
+        Ok(())
+    }
+
     fn render(&self, progress: f64) -> Result<()> {
         let new_rect = self.start_rect.lerp(self.target_rect, progress, self.style);
 
@@ -300,6 +330,117 @@ impl RenderDispatcher for TransparencyRenderDispatcher {
     }
 }
 
+struct WorkspaceSwitchRenderDispatcher {
+    hwnd: isize,
+    animation: WorkspaceSwitchAnimation,
+    start_rect: Rect,
+    target_rect: Rect,
+    is_layered: bool,
+    style: AnimationStyle,
+}
+
+impl WorkspaceSwitchRenderDispatcher {
+    const PREFIX: AnimationPrefix = AnimationPrefix::WorkspaceSwitch;
+
+    pub fn new(
+        hwnd: isize,
+        animation: WorkspaceSwitchAnimation,
+        offset: i32,
+        target_rect: Rect,
+        is_layered: bool,
+        style: AnimationStyle,
+    ) -> Self {
+        Self {
+            hwnd,
+            animation,
+            start_rect: Rect {
+                left: target_rect.left + offset,
+                ..target_rect
+            },
+            target_rect,
+            is_layered,
+            style,
+        }
+    }
+}
+
+impl RenderDispatcher for WorkspaceSwitchRenderDispatcher {
+    fn get_animation_key(&self) -> String {
+        new_animation_key(
+            WorkspaceSwitchRenderDispatcher::PREFIX,
+            self.hwnd.to_string(),
+        )
+    }
+
+    fn pre_render(&self) -> Result<()> {
+        stackbar_manager::STACKBAR_TEMPORARILY_DISABLED.store(true, Ordering::SeqCst);
+        stackbar_manager::send_notification();
+
+        match self.animation {
+            WorkspaceSwitchAnimation::Slide => {
+                WindowsApi::move_window(self.hwnd, &self.start_rect, false)
+            }
+            WorkspaceSwitchAnimation::Fade => {
+                if !self.is_layered {
+                    let window = Window::from(self.hwnd);
+                    let mut ex_style = window.ex_style()?;
+                    ex_style.insert(ExtendedWindowStyle::LAYERED);
+                    window.update_ex_style(&ex_style)?;
+                }
+
+                WindowsApi::set_transparent(self.hwnd, 0)
+            }
+        }
+    }
+
+    fn render(&self, progress: f64) -> Result<()> {
+        match self.animation {
+            WorkspaceSwitchAnimation::Slide => {
+                let new_rect = self.start_rect.lerp(self.target_rect, progress, self.style);
+                WindowsApi::move_window(self.hwnd, &new_rect, false)?;
+                WindowsApi::invalidate_rect(self.hwnd, None, false);
+
+                Ok(())
+            }
+            WorkspaceSwitchAnimation::Fade => {
+                WindowsApi::set_transparent(self.hwnd, 0u8.lerp(u8::MAX, progress, self.style))
+            }
+        }
+    }
+
+    fn post_render(&self) -> Result<()> {
+        match self.animation {
+            WorkspaceSwitchAnimation::Slide => {
+                WindowsApi::position_window(self.hwnd, &self.target_rect, false, false)?;
+            }
+            WorkspaceSwitchAnimation::Fade => {
+                WindowsApi::set_transparent(self.hwnd, u8::MAX)?;
+
+                if !self.is_layered {
+                    let window = Window::from(self.hwnd);
+                    let mut ex_style = window.ex_style()?;
+                    ex_style.remove(ExtendedWindowStyle::LAYERED);
+                    window.update_ex_style(&ex_style)?;
+                }
+            }
+        }
+
+        if ANIMATION_MANAGER
+            .lock()
+            .count_in_progress(WorkspaceSwitchRenderDispatcher::PREFIX)
+            == 0
+        {
+            stackbar_manager::STACKBAR_TEMPORARILY_DISABLED.store(false, Ordering::SeqCst);
+
+            stackbar_manager::send_notification();
+            border_manager::send_notification(None);
+            transparency_manager::send_notification();
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Copy, Clone, Debug, Display, EnumString, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
@@ -380,7 +521,9 @@ impl Window {
                 .is_some_and(|v| *v);
             drop(animation_enabled);
 
-            if move_enabled || ANIMATION_ENABLED_GLOBAL.load(Ordering::SeqCst) {
+            if (move_enabled || ANIMATION_ENABLED_GLOBAL.load(Ordering::SeqCst))
+                && !self.is_animation_ignored()
+            {
                 let anim_count = ANIMATION_MANAGER
                     .lock()
                     .count_in_progress(MovementRenderDispatcher::PREFIX);
@@ -450,8 +593,9 @@ impl Window {
         let animation_enabled = ANIMATION_ENABLED_PER_ANIMATION.lock();
         let move_enabled = animation_enabled.get(&MovementRenderDispatcher::PREFIX);
 
-        if move_enabled.is_some_and(|enabled| *enabled)
-            || ANIMATION_ENABLED_GLOBAL.load(Ordering::SeqCst)
+        if (move_enabled.is_some_and(|enabled| *enabled)
+            || ANIMATION_ENABLED_GLOBAL.load(Ordering::SeqCst))
+            && !self.is_animation_ignored()
         {
             let duration = Duration::from_millis(
                 *ANIMATION_DURATION_PER_ANIMATION
@@ -473,6 +617,35 @@ impl Window {
         }
     }
 
+    /// Animates this window into its current position after a workspace switch, offsetting the
+    /// start of a slide by `offset` pixels horizontally
+    pub fn animate_workspace_switch(self, offset: i32) -> Result<()> {
+        let target_rect = WindowsApi::window_rect(self.hwnd)?;
+        let is_layered = self.ex_style()?.contains(ExtendedWindowStyle::LAYERED);
+
+        let duration = Duration::from_millis(
+            *ANIMATION_DURATION_PER_ANIMATION
+                .lock()
+                .get(&WorkspaceSwitchRenderDispatcher::PREFIX)
+                .unwrap_or(&ANIMATION_DURATION_GLOBAL.load(Ordering::SeqCst)),
+        );
+        let style = *ANIMATION_STYLE_PER_ANIMATION
+            .lock()
+            .get(&WorkspaceSwitchRenderDispatcher::PREFIX)
+            .unwrap_or(&ANIMATION_STYLE_GLOBAL.lock());
+
+        let render_dispatcher = WorkspaceSwitchRenderDispatcher::new(
+            self.hwnd,
+            WORKSPACE_SWITCH_ANIMATION.load(),
+            offset,
+            target_rect,
+            is_layered,
+            style,
+        );
+
+        AnimationEngine::animate(render_dispatcher, duration)
+    }
+
     pub fn is_maximized(self) -> bool {
         WindowsApi::is_zoomed(self.hwnd)
     }
@@ -485,17 +658,325 @@ impl Window {
         WindowsApi::is_window_visible(self.hwnd)
     }
 
+    /// Whether this window should receive focus when it is first opened, taking the last matching
+    /// new window focus rule over the global new window focus behaviour
+    pub fn new_window_focus_behaviour(self) -> NewWindowFocusBehaviour {
+        let new_window_focus_rules = NEW_WINDOW_FOCUS_RULES.lock();
+        if !new_window_focus_rules.is_empty() {
+            let regex_identifiers = REGEX_IDENTIFIERS.lock();
+            let (title, exe, class, path) = (
+                self.title().unwrap_or_default(),
+                self.exe().unwrap_or_default(),
+                self.class().unwrap_or_default(),
+                self.path().unwrap_or_default(),
+            );
+
+            if let Some(rule) = new_window_focus_rules.iter().rev().find(|rule| {
+                should_act(
+                    &title,
+                    &exe,
+                    &class,
+                    &path,
+                    std::slice::from_ref(&rule.matching_rule),
+                    &regex_identifiers,
+                )
+                .is_some()
+            }) {
+                return rule.behaviour;
+            }
+        }
+
+        NEW_WINDOW_FOCUS_BEHAVIOUR.load()
+    }
+
+    /// The minimum tile width and height of the last minimum tile size rule which matches this
+    /// window, if any. Rules are only matched the first time this is called for a window
+    pub fn minimum_tile_size(self) -> Option<(Option<i32>, Option<i32>)> {
+        if let Some(minimum_tile_size) = MINIMUM_TILE_SIZES.lock().get(&self.hwnd) {
+            return *minimum_tile_size;
+        }
+
+        let minimum_tile_size = {
+            let minimum_tile_size_rules = MINIMUM_TILE_SIZE_RULES.lock();
+            if minimum_tile_size_rules.is_empty() {
+                return None;
+            }
+
+            let regex_identifiers = REGEX_IDENTIFIERS.lock();
+            let (title, exe, class, path) = (
+                self.title().unwrap_or_default(),
+                self.exe().unwrap_or_default(),
+                self.class().unwrap_or_default(),
+                self.path().unwrap_or_default(),
+            );
+
+            minimum_tile_size_rules
+                .iter()
+                .rev()
+                .find(|rule| {
+                    should_act(
+                        &title,
+                        &exe,
+                        &class,
+                        &path,
+                        std::slice::from_ref(&rule.matching_rule),
+                        &regex_identifiers,
+                    )
+                    .is_some()
+                })
+                .map(|rule| (rule.width, rule.height))
+        };
+
+        MINIMUM_TILE_SIZES
+            .lock()
+            .insert(self.hwnd, minimum_tile_size);
+
+        minimum_tile_size
+    }
+
+    /// Which Windows signal to use when hiding this window, taking the last matching hiding
+    /// behaviour rule over the global window hiding behaviour. Rules are only matched the first
+    /// time this is called for a window
+    pub fn hiding_behaviour(self) -> HidingBehaviour {
+        let cached = HIDING_BEHAVIOURS.lock().get(&self.hwnd).copied();
+        let rule_behaviour = cached.unwrap_or_else(|| {
+            let rule_behaviour = self.hiding_behaviour_rule();
+            HIDING_BEHAVIOURS.lock().insert(self.hwnd, rule_behaviour);
+            rule_behaviour
+        });
+
+        rule_behaviour.unwrap_or_else(|| *HIDING_BEHAVIOUR.lock())
+    }
+
+    /// The behaviour of the last hiding behaviour rule which matches this window, if any
+    fn hiding_behaviour_rule(self) -> Option<HidingBehaviour> {
+        let hiding_behaviour_rules = HIDING_BEHAVIOUR_RULES.lock();
+        if hiding_behaviour_rules.is_empty() {
+            return None;
+        }
+
+        let regex_identifiers = REGEX_IDENTIFIERS.lock();
+        let (title, exe, class, path) = (
+            self.title().unwrap_or_default(),
+            self.exe().unwrap_or_default(),
+            self.class().unwrap_or_default(),
+            self.path().unwrap_or_default(),
+        );
+
+        hiding_behaviour_rules
+            .iter()
+            .rev()
+            .find(|rule| {
+                should_act(
+                    &title,
+                    &exe,
+                    &class,
+                    &path,
+                    std::slice::from_ref(&rule.matching_rule),
+                    &regex_identifiers,
+                )
+                .is_some()
+            })
+            .map(|rule| rule.behaviour)
+    }
+
+    /// The last floating geometry rule which matches this window, if any
+    pub fn floating_geometry_rule(self) -> Option<FloatingGeometryRule> {
+        let floating_geometry_rules = FLOATING_GEOMETRY_RULES.lock();
+        if floating_geometry_rules.is_empty() {
+            return None;
+        }
+
+        let regex_identifiers = REGEX_IDENTIFIERS.lock();
+        let (title, exe, class, path) = (
+            self.title().unwrap_or_default(),
+            self.exe().unwrap_or_default(),
+            self.class().unwrap_or_default(),
+            self.path().unwrap_or_default(),
+        );
+
+        floating_geometry_rules
+            .iter()
+            .rev()
+            .find(|rule| {
+                should_act(
+                    &title,
+                    &exe,
+                    &class,
+                    &path,
+                    std::slice::from_ref(&rule.matching_rule),
+                    &regex_identifiers,
+                )
+                .is_some()
+            })
+            .cloned()
+    }
+
+    /// Moves and resizes this window within `work_area` according to its floating geometry rule,
+    /// returning false if it doesn't match one
+    pub fn apply_floating_geometry(&mut self, work_area: &Rect) -> Result<bool> {
+        let Some(rule) = self.floating_geometry_rule() else {
+            return Ok(false);
+        };
+
+        let current_rect = WindowsApi::window_rect(self.hwnd)?;
+        self.set_position(&rule.rect(work_area, &current_rect), true)?;
+
+        Ok(true)
+    }
+
+    /// Whether this window matches an animation ignore rule, in which case it is always moved
+    /// instantly
+    pub fn is_animation_ignored(self) -> bool {
+        let animation_blacklist = ANIMATION_BLACKLIST.lock();
+        if animation_blacklist.is_empty() {
+            return false;
+        }
+
+        let regex_identifiers = REGEX_IDENTIFIERS.lock();
+        let (title, exe, class, path) = (
+            self.title().unwrap_or_default(),
+            self.exe().unwrap_or_default(),
+            self.class().unwrap_or_default(),
+            self.path().unwrap_or_default(),
+        );
+
+        should_act(
+            &title,
+            &exe,
+            &class,
+            &path,
+            &animation_blacklist,
+            &regex_identifiers,
+        )
+        .is_some()
+    }
+
+    /// Whether this window matches a maximize monocle rule, in which case maximizing it puts it in
+    /// monocle on its workspace instead of fighting the tiling layout. Rules are only matched the
+    /// first time this is called for a window
+    pub fn is_maximize_monocle(self) -> bool {
+        if let Some(is_maximize_monocle) = MAXIMIZE_MONOCLE_MATCHES.lock().get(&self.hwnd) {
+            return *is_maximize_monocle;
+        }
+
+        let is_maximize_monocle = self.matches_maximize_monocle_rule();
+        MAXIMIZE_MONOCLE_MATCHES
+            .lock()
+            .insert(self.hwnd, is_maximize_monocle);
+
+        is_maximize_monocle
+    }
+
+    fn matches_maximize_monocle_rule(self) -> bool {
+        let maximize_monocle_rules = MAXIMIZE_MONOCLE_RULES.lock();
+        if maximize_monocle_rules.is_empty() {
+            return false;
+        }
+
+        let regex_identifiers = REGEX_IDENTIFIERS.lock();
+        let (title, exe, class, path) = (
+            self.title().unwrap_or_default(),
+            self.exe().unwrap_or_default(),
+            self.class().unwrap_or_default(),
+            self.path().unwrap_or_default(),
+        );
+
+        should_act(
+            &title,
+            &exe,
+            &class,
+            &path,
+            &maximize_monocle_rules,
+            &regex_identifiers,
+        )
+        .is_some()
+    }
+
+    /// Whether this window matches a privacy sensitive application rule, in which case it is left
+    /// out of the previews shown by the bar while privacy mode is on
+    pub fn is_privacy_sensitive(self) -> bool {
+        let privacy_sensitive_applications = PRIVACY_SENSITIVE_APPLICATIONS.lock();
+        if privacy_sensitive_applications.is_empty() {
+            return false;
+        }
+
+        let regex_identifiers = REGEX_IDENTIFIERS.lock();
+        let (title, exe, class, path) = (
+            self.title().unwrap_or_default(),
+            self.exe().unwrap_or_default(),
+            self.class().unwrap_or_default(),
+            self.path().unwrap_or_default(),
+        );
+
+        should_act(
+            &title,
+            &exe,
+            &class,
+            &path,
+            &privacy_sensitive_applications,
+            &regex_identifiers,
+        )
+        .is_some()
+    }
+
+    /// Whether this window matches a minimize to tray rule, in which case it is hidden in its
+    /// workspace's hidden windows instead of being minimized or closed
+    pub fn is_minimize_to_tray(self) -> bool {
+        let minimize_to_tray_rules = MINIMIZE_TO_TRAY_RULES.lock();
+        if minimize_to_tray_rules.is_empty() {
+            return false;
+        }
+
+        let regex_identifiers = REGEX_IDENTIFIERS.lock();
+        let (title, exe, class, path) = (
+            self.title().unwrap_or_default(),
+            self.exe().unwrap_or_default(),
+            self.class().unwrap_or_default(),
+            self.path().unwrap_or_default(),
+        );
+
+        should_act(
+            &title,
+            &exe,
+            &class,
+            &path,
+            &minimize_to_tray_rules,
+            &regex_identifiers,
+        )
+        .is_some()
+    }
+
     pub fn hide_with_border(self, hide_border: bool) {
         let mut programmatically_hidden_hwnds = HIDDEN_HWNDS.lock();
         if !programmatically_hidden_hwnds.contains(&self.hwnd) {
             programmatically_hidden_hwnds.push(self.hwnd);
         }
 
-        let hiding_behaviour = HIDING_BEHAVIOUR.lock();
-        match *hiding_behaviour {
+        match self.hiding_behaviour() {
             HidingBehaviour::Hide => WindowsApi::hide_window(self.hwnd),
             HidingBehaviour::Minimize => WindowsApi::minimize_window(self.hwnd),
             HidingBehaviour::Cloak => SetCloak(self.hwnd(), 1, 2),
+            HidingBehaviour::OffScreen => {
+                let mut off_screen_rects = OFF_SCREEN_RECTS.lock();
+                if !off_screen_rects.contains_key(&self.hwnd) {
+                    if let Ok(rect) = WindowsApi::window_rect(self.hwnd) {
+                        // The same coordinates Windows uses for minimized windows, which are
+                        // guaranteed to be outside of every monitor
+                        let off_screen = Rect {
+                            left: -32000,
+                            top: -32000,
+                            right: rect.right,
+                            bottom: rect.bottom,
+                        };
+
+                        if WindowsApi::position_window(self.hwnd, &off_screen, false, true).is_ok()
+                        {
+                            off_screen_rects.insert(self.hwnd, rect);
+                        }
+                    }
+                }
+            }
         }
         if hide_border {
             border_manager::hide_border(self.hwnd);
@@ -515,12 +996,23 @@ impl Window {
             programmatically_hidden_hwnds.remove(idx);
         }
 
-        let hiding_behaviour = HIDING_BEHAVIOUR.lock();
-        match *hiding_behaviour {
+        // A window moved off-screen is always moved back, even if the hiding behaviour which
+        // applies to it has changed since it was hidden
+        if let Some(rect) = OFF_SCREEN_RECTS.lock().remove(&self.hwnd) {
+            if let Err(error) = WindowsApi::position_window(self.hwnd, &rect, false, true) {
+                tracing::warn!(
+                    "failed to move window {} back on-screen: {error}",
+                    self.hwnd
+                );
+            }
+        }
+
+        match self.hiding_behaviour() {
             HidingBehaviour::Hide | HidingBehaviour::Minimize => {
                 WindowsApi::restore_window(self.hwnd);
             }
             HidingBehaviour::Cloak => SetCloak(self.hwnd(), 1, 0),
+            HidingBehaviour::OffScreen => {}
         }
         if restore_border {
             border_manager::show_border(self.hwnd);
@@ -1055,36 +1547,8 @@ pub fn should_act(
 ) -> Option<MatchingRule> {
     let mut matching_rule = None;
     for rule in identifiers {
-        match rule {
-            MatchingRule::Simple(identifier) => {
-                if should_act_individual(
-                    title,
-                    exe_name,
-                    class,
-                    path,
-                    identifier,
-                    regex_identifiers,
-                ) {
-                    matching_rule = Some(rule.clone());
-                };
-            }
-            MatchingRule::Composite(identifiers) => {
-                let mut composite_results = vec![];
-                for identifier in identifiers {
-                    composite_results.push(should_act_individual(
-                        title,
-                        exe_name,
-                        class,
-                        path,
-                        identifier,
-                        regex_identifiers,
-                    ));
-                }
-
-                if composite_results.iter().all(|&x| x) {
-                    matching_rule = Some(rule.clone());
-                }
-            }
+        if rule_engine::matches(rule, title, exe_name, class, path, regex_identifiers) {
+            matching_rule = Some(rule.clone());
         }
     }
 
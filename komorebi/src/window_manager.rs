@@ -24,13 +24,18 @@ use serde::Serialize;
 use uds_windows::UnixListener;
 use uds_windows::UnixStream;
 
+use crate::animation::prefix::AnimationPrefix;
 use crate::animation::AnimationEngine;
 use crate::animation::ANIMATION_ENABLED_GLOBAL;
 use crate::animation::ANIMATION_ENABLED_PER_ANIMATION;
+use crate::animation::ANIMATION_MANAGER;
+use crate::animation::WORKSPACE_SWITCH_ANIMATION_WINDOW_LIMIT;
 use crate::core::config_generation::MatchingRule;
+use crate::core::config_generation::WorkspaceMatchingRule;
 use crate::core::custom_layout::CustomLayout;
 use crate::core::Arrangement;
 use crate::core::Axis;
+use crate::core::BorderAnimation;
 use crate::core::BorderImplementation;
 use crate::core::BorderStyle;
 use crate::core::CycleDirection;
@@ -57,9 +62,13 @@ use crate::core::StackbarMode;
 use crate::current_virtual_desktop;
 use crate::load_configuration;
 use crate::monitor::Monitor;
+use crate::monitor_reconciliator;
 use crate::ring::Ring;
+use crate::rule_engine;
+use crate::schedule_manager::ACTIVE_WORKSPACE_SCHEDULES;
+use crate::schedule_manager::SCHEDULED_WORKSPACE_MATCHING_RULES;
+use crate::session::Session;
 use crate::should_act;
-use crate::should_act_individual;
 use crate::stackbar_manager::STACKBAR_FOCUSED_TEXT_COLOUR;
 use crate::stackbar_manager::STACKBAR_LABEL;
 use crate::stackbar_manager::STACKBAR_MODE;
@@ -80,22 +89,35 @@ use crate::workspace::WorkspaceLayer;
 use crate::BorderColours;
 use crate::Colour;
 use crate::CrossBoundaryBehaviour;
+use crate::CrossMonitorFocusBehaviour;
+use crate::NewWindowFocusBehaviour;
 use crate::Rgb;
+use crate::BARS_HIDDEN;
+use crate::CONFIGURATION_LOADED_AT;
+use crate::CONFIGURATION_PATH;
 use crate::CUSTOM_FFM;
 use crate::DATA_DIR;
 use crate::DISPLAY_INDEX_PREFERENCES;
 use crate::DUPLICATE_MONITOR_SERIAL_IDS;
+use crate::DYNAMIC_WORKSPACES;
+use crate::HIDDEN_HWNDS;
 use crate::HIDING_BEHAVIOUR;
 use crate::HOME_DIR;
 use crate::IGNORE_IDENTIFIERS;
 use crate::LAYERED_WHITELIST;
 use crate::MANAGE_IDENTIFIERS;
+use crate::MAXIMIZED_MONOCLE_HWNDS;
 use crate::MONITOR_INDEX_PREFERENCES;
 use crate::NO_TITLEBAR;
 use crate::OBJECT_NAME_CHANGE_ON_LAUNCH;
+use crate::PRIVACY_MODE;
 use crate::REGEX_IDENTIFIERS;
 use crate::REMOVE_TITLEBARS;
+use crate::STARTED_AT;
+use crate::SUBSCRIPTION_DIFF_SOCKETS;
 use crate::SUBSCRIPTION_SOCKETS;
+use crate::SWALLOWING_RULES;
+use crate::TRANSIENT_DIALOG_RULES;
 use crate::TRANSPARENCY_BLACKLIST;
 use crate::TRAY_AND_MULTI_WINDOW_IDENTIFIERS;
 use crate::WORKSPACE_MATCHING_RULES;
@@ -112,6 +134,7 @@ pub struct WindowManager {
     pub window_management_behaviour: WindowManagementBehaviour,
     pub cross_monitor_move_behaviour: MoveBehaviour,
     pub cross_boundary_behaviour: CrossBoundaryBehaviour,
+    pub cross_monitor_focus_behaviour: CrossMonitorFocusBehaviour,
     pub unmanaged_window_operation_behaviour: OperationBehaviour,
     pub focus_follows_mouse: Option<FocusFollowsMouseImplementation>,
     pub mouse_follows_focus: bool,
@@ -123,6 +146,12 @@ pub struct WindowManager {
     pub uncloack_to_ignore: usize,
     /// Maps each known window hwnd to the (monitor, workspace) index pair managing it
     pub known_hwnds: HashMap<isize, (usize, usize)>,
+    /// Maps each swallowing window hwnd to the window whose tile it has taken over
+    pub swallowed_windows: HashMap<isize, Window>,
+    /// Maps each managed owned window hwnd to the hwnd of its owner window
+    pub window_owners: HashMap<isize, isize>,
+    /// Maps each user-defined mark to the hwnd of the window it has been assigned to
+    pub marks: HashMap<String, isize>,
 }
 
 #[allow(clippy::struct_excessive_bools)]
@@ -141,6 +170,27 @@ pub struct State {
     pub focus_follows_mouse: Option<FocusFollowsMouseImplementation>,
     pub mouse_follows_focus: bool,
     pub has_pending_raise_op: bool,
+    /// Maps each managed owned window hwnd to the hwnd of its owner window
+    #[serde(default)]
+    pub window_owners: HashMap<isize, isize>,
+    /// Names of the workspace schedules which are currently active
+    #[serde(default)]
+    pub active_workspace_schedules: Vec<String>,
+    /// Path of the static configuration file which was most recently loaded
+    #[serde(default)]
+    pub configuration_path: Option<PathBuf>,
+    /// Unix timestamp in seconds of when the static configuration was last loaded or reloaded
+    #[serde(default)]
+    pub configuration_loaded_at: Option<u64>,
+    /// Whether the bars have been hidden with `komorebic toggle-bar`
+    #[serde(default)]
+    pub bars_hidden: bool,
+    /// Whether privacy mode has been turned on with `komorebic privacy-mode`
+    #[serde(default)]
+    pub privacy_mode: bool,
+    /// Managed windows matching the `privacy_sensitive_applications` rules while privacy mode is on
+    #[serde(default)]
+    pub privacy_sensitive_windows: Vec<isize>,
 }
 
 impl State {
@@ -187,6 +237,28 @@ impl State {
             return true;
         }
 
+        if self.window_owners != new.window_owners {
+            return true;
+        }
+
+        if self.configuration_path != new.configuration_path {
+            return true;
+        }
+
+        if self.configuration_loaded_at != new.configuration_loaded_at {
+            return true;
+        }
+
+        if self.bars_hidden != new.bars_hidden {
+            return true;
+        }
+
+        if self.privacy_mode != new.privacy_mode
+            || self.privacy_sensitive_windows != new.privacy_sensitive_windows
+        {
+            return true;
+        }
+
         false
     }
 }
@@ -198,6 +270,7 @@ pub struct GlobalState {
     pub border_enabled: bool,
     pub border_colours: BorderColours,
     pub border_style: BorderStyle,
+    pub border_animation: Option<BorderAnimation>,
     pub border_offset: i32,
     pub border_width: i32,
     pub stackbar_mode: StackbarMode,
@@ -211,6 +284,7 @@ pub struct GlobalState {
     pub transparency_alpha: u8,
     pub transparency_blacklist: Vec<MatchingRule>,
     pub remove_titlebars: bool,
+    pub dynamic_workspaces: bool,
     #[serde(alias = "float_identifiers")]
     pub ignore_identifiers: Vec<MatchingRule>,
     pub manage_identifiers: Vec<MatchingRule>,
@@ -252,6 +326,7 @@ impl Default for GlobalState {
                 ))),
             },
             border_style: STYLE.load(),
+            border_animation: border_manager::ANIMATION.load(),
             border_offset: border_manager::BORDER_OFFSET.load(Ordering::SeqCst),
             border_width: border_manager::BORDER_WIDTH.load(Ordering::SeqCst),
             stackbar_mode: STACKBAR_MODE.load(),
@@ -271,6 +346,7 @@ impl Default for GlobalState {
             transparency_alpha: TRANSPARENCY_ALPHA.load(Ordering::SeqCst),
             transparency_blacklist: TRANSPARENCY_BLACKLIST.lock().clone(),
             remove_titlebars: REMOVE_TITLEBARS.load(Ordering::SeqCst),
+            dynamic_workspaces: DYNAMIC_WORKSPACES.load(Ordering::SeqCst),
             ignore_identifiers: IGNORE_IDENTIFIERS.lock().clone(),
             manage_identifiers: MANAGE_IDENTIFIERS.lock().clone(),
             layered_whitelist: LAYERED_WHITELIST.lock().clone(),
@@ -288,6 +364,28 @@ impl Default for GlobalState {
     }
 }
 
+/// The reply to a Ping, for clients to check that komorebi is running and compatible with them
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Pong {
+    /// Version of the running komorebi
+    pub version: String,
+    /// Number of seconds since komorebi was started
+    pub uptime: u64,
+    /// Process id of the running komorebi
+    pub pid: u32,
+}
+
+impl Pong {
+    pub fn now() -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            uptime: STARTED_AT.elapsed().as_secs(),
+            pid: std::process::id(),
+        }
+    }
+}
+
 impl AsRef<Self> for WindowManager {
     fn as_ref(&self) -> &Self {
         self
@@ -315,6 +413,7 @@ impl From<&WindowManager> for State {
                 work_area_offset: monitor.work_area_offset,
                 window_based_work_area_offset: monitor.window_based_work_area_offset,
                 window_based_work_area_offset_limit: monitor.window_based_work_area_offset_limit,
+                taskbar_work_area_offset: monitor.taskbar_work_area_offset,
                 workspaces: {
                     let mut ws = Ring::default();
                     *ws.elements_mut() = monitor
@@ -326,9 +425,14 @@ impl From<&WindowManager> for State {
                             monocle_container: workspace.monocle_container.clone(),
                             monocle_container_restore_idx: workspace.monocle_container_restore_idx,
                             maximized_window: workspace.maximized_window,
+                            zoomed_window: workspace.zoomed_window,
                             maximized_window_restore_idx: workspace.maximized_window_restore_idx,
                             floating_windows: workspace.floating_windows.clone(),
                             layout: workspace.layout.clone(),
+                            custom_layout_name: match &workspace.layout {
+                                Layout::Custom(custom) => custom.name.clone(),
+                                Layout::Default(_) => None,
+                            },
                             layout_options: workspace.layout_options,
                             layout_rules: workspace.layout_rules.clone(),
                             layout_flip: workspace.layout_flip,
@@ -336,9 +440,13 @@ impl From<&WindowManager> for State {
                             container_padding: workspace.container_padding,
                             latest_layout: workspace.latest_layout.clone(),
                             resize_dimensions: workspace.resize_dimensions.clone(),
+                            split_ratios: workspace.split_ratios.clone(),
+                            manual_layout: workspace.manual_layout.clone(),
+                            tabbed_groups: workspace.tabbed_groups.clone(),
                             tile: workspace.tile,
                             apply_window_based_work_area_offset: workspace
                                 .apply_window_based_work_area_offset,
+                            smart_gaps: workspace.smart_gaps,
                             window_container_behaviour: workspace.window_container_behaviour,
                             window_container_behaviour_rules: workspace
                                 .window_container_behaviour_rules
@@ -349,6 +457,9 @@ impl From<&WindowManager> for State {
                             globals: workspace.globals,
                             wallpaper: workspace.wallpaper.clone(),
                             workspace_config: None,
+                            reserved_tiles: workspace.reserved_tiles.clone(),
+                            focus_history: workspace.focus_history.clone(),
+                            hidden_windows: workspace.hidden_windows.clone(),
                         })
                         .collect::<VecDeque<_>>();
                     ws.focus(monitor.workspaces.focused_idx());
@@ -360,10 +471,24 @@ impl From<&WindowManager> for State {
                 workspace_padding: monitor.workspace_padding,
                 wallpaper: monitor.wallpaper.clone(),
                 floating_layer_behaviour: monitor.floating_layer_behaviour,
+                always_on_top: monitor.always_on_top.clone(),
+                always_on_top_focused: monitor.always_on_top_focused,
             })
             .collect::<VecDeque<_>>();
         stripped_monitors.focus(wm.monitors.focused_idx());
 
+        let privacy_mode = PRIVACY_MODE.load(Ordering::SeqCst);
+        let mut privacy_sensitive_windows = if privacy_mode {
+            wm.known_hwnds
+                .keys()
+                .copied()
+                .filter(|hwnd| Window::from(*hwnd).is_privacy_sensitive())
+                .collect::<Vec<_>>()
+        } else {
+            vec![]
+        };
+        privacy_sensitive_windows.sort_unstable();
+
         Self {
             monitors: stripped_monitors,
             monitor_usr_idx_map: wm.monitor_usr_idx_map.clone(),
@@ -376,7 +501,14 @@ impl From<&WindowManager> for State {
             focus_follows_mouse: wm.focus_follows_mouse,
             mouse_follows_focus: wm.mouse_follows_focus,
             has_pending_raise_op: wm.has_pending_raise_op,
+            window_owners: wm.window_owners.clone(),
             unmanaged_window_operation_behaviour: wm.unmanaged_window_operation_behaviour,
+            active_workspace_schedules: ACTIVE_WORKSPACE_SCHEDULES.lock().clone(),
+            configuration_path: CONFIGURATION_PATH.lock().clone(),
+            configuration_loaded_at: *CONFIGURATION_LOADED_AT.lock(),
+            bars_hidden: BARS_HIDDEN.load(Ordering::SeqCst),
+            privacy_mode,
+            privacy_sensitive_windows,
         }
     }
 }
@@ -391,6 +523,7 @@ struct EnforceWorkspaceRuleOp {
     target_monitor_idx: usize,
     target_workspace_idx: usize,
     floating: bool,
+    follow: bool,
 }
 impl EnforceWorkspaceRuleOp {
     const fn is_origin(&self, monitor_idx: usize, workspace_idx: usize) -> bool {
@@ -439,6 +572,7 @@ impl WindowManager {
             window_management_behaviour: WindowManagementBehaviour::default(),
             cross_monitor_move_behaviour: MoveBehaviour::Swap,
             cross_boundary_behaviour: CrossBoundaryBehaviour::Monitor,
+            cross_monitor_focus_behaviour: CrossMonitorFocusBehaviour::Layout,
             unmanaged_window_operation_behaviour: OperationBehaviour::Op,
             resize_delta: 50,
             focus_follows_mouse: None,
@@ -449,12 +583,20 @@ impl WindowManager {
             already_moved_window_handles: Arc::new(Mutex::new(HashSet::new())),
             uncloack_to_ignore: 0,
             known_hwnds: HashMap::new(),
+            swallowed_windows: HashMap::new(),
+            window_owners: HashMap::new(),
+            marks: HashMap::new(),
         })
     }
 
     #[tracing::instrument(skip(self))]
     pub fn init(&mut self) -> Result<()> {
         tracing::info!("initialising");
+
+        if let Err(error) = monitor_reconciliator::load_persisted_display_index_preferences() {
+            tracing::warn!("could not load persisted display index preferences: {error}");
+        }
+
         WindowsApi::load_monitor_information(self)?;
         WindowsApi::load_workspace_information(&mut self.monitors)
     }
@@ -780,6 +922,85 @@ impl WindowManager {
         None
     }
 
+    /// Returns the monitor which should be focused when focus crosses a monitor boundary in the
+    /// given direction, along with the container which should be focused on it if it was chosen
+    /// based on the on-screen geometry rather than the layout. Focus never crosses a monitor
+    /// boundary from a workspace which contains focus
+    pub fn monitor_and_container_idx_in_direction(
+        &self,
+        direction: OperationDirection,
+    ) -> Option<(usize, Option<usize>)> {
+        if self
+            .focused_workspace()
+            .is_ok_and(|workspace| workspace.contains_focus())
+        {
+            return None;
+        }
+
+        match self.cross_monitor_focus_behaviour {
+            CrossMonitorFocusBehaviour::Layout => self
+                .monitor_idx_in_direction(direction)
+                .map(|monitor_idx| (monitor_idx, None)),
+            CrossMonitorFocusBehaviour::Geometry => {
+                self.nearest_container_across_monitors(direction)
+            }
+        }
+    }
+
+    /// Finds the monitor and container closest on screen to the focused window in the given
+    /// direction, considering the focused workspace of every other monitor
+    fn nearest_container_across_monitors(
+        &self,
+        direction: OperationDirection,
+    ) -> Option<(usize, Option<usize>)> {
+        let focused_monitor_idx = self.focused_monitor_idx();
+        let origin = self
+            .focused_window()
+            .ok()
+            .and_then(|window| WindowsApi::window_rect(window.hwnd).ok())
+            .or_else(|| self.focused_monitor_size().ok())?;
+
+        let mut nearest: Option<((i32, i32, i32), usize, Option<usize>)> = None;
+
+        for (monitor_idx, monitor) in self.monitors().iter().enumerate() {
+            if monitor_idx == focused_monitor_idx {
+                continue;
+            }
+
+            let Some(workspace) = monitor.focused_workspace() else {
+                continue;
+            };
+
+            // Individual containers can only be targeted when they are visibly tiled
+            let candidates = if workspace.containers().is_empty()
+                || workspace.monocle_container().is_some()
+                || workspace.maximized_window().is_some()
+                || workspace.layer() != &WorkspaceLayer::Tiling
+            {
+                vec![(None, *monitor.size())]
+            } else {
+                workspace
+                    .latest_layout()
+                    .iter()
+                    .take(workspace.containers().len())
+                    .enumerate()
+                    .map(|(idx, rect)| (Some(idx), *rect))
+                    .collect()
+            };
+
+            for (container_idx, rect) in candidates {
+                if let Some(distance) = origin.distance_in_direction(&rect, direction) {
+                    if !matches!(nearest, Some((nearest_distance, _, _)) if nearest_distance <= distance)
+                    {
+                        nearest = Some((distance, monitor_idx, container_idx));
+                    }
+                }
+            }
+        }
+
+        nearest.map(|(_, monitor_idx, container_idx)| (monitor_idx, container_idx))
+    }
+
     /// Calculates the direction of a move across monitors given a specific monitor index
     pub fn direction_from_monitor_idx(
         &self,
@@ -820,6 +1041,7 @@ impl WindowManager {
         target_monitor_idx: usize,
         target_workspace_idx: usize,
         floating: bool,
+        follow: bool,
         to_move: &mut Vec<EnforceWorkspaceRuleOp>,
     ) {
         tracing::trace!(
@@ -837,6 +1059,7 @@ impl WindowManager {
             target_monitor_idx,
             target_workspace_idx,
             floating,
+            follow,
         });
     }
 
@@ -855,6 +1078,7 @@ impl WindowManager {
         // at the end of this function
         {
             let workspace_matching_rules = WORKSPACE_MATCHING_RULES.lock();
+            let scheduled_workspace_matching_rules = SCHEDULED_WORKSPACE_MATCHING_RULES.lock();
             let regex_identifiers = REGEX_IDENTIFIERS.lock();
             // Go through all the monitors and workspaces
             for (i, monitor) in self.monitors().iter().enumerate() {
@@ -867,36 +1091,30 @@ impl WindowManager {
                         if let (Ok(exe_name), Ok(title), Ok(class), Ok(path)) =
                             (window.exe(), window.title(), window.class(), window.path())
                         {
-                            for rule in &*workspace_matching_rules {
-                                let matched = match &rule.matching_rule {
-                                    MatchingRule::Simple(r) => should_act_individual(
-                                        &title,
-                                        &exe_name,
-                                        &class,
-                                        &path,
-                                        r,
-                                        &regex_identifiers,
-                                    ),
-                                    MatchingRule::Composite(r) => {
-                                        let mut composite_results = vec![];
-                                        for identifier in r {
-                                            composite_results.push(should_act_individual(
-                                                &title,
-                                                &exe_name,
-                                                &class,
-                                                &path,
-                                                identifier,
-                                                &regex_identifiers,
-                                            ));
-                                        }
+                            let matches_rule = |rule: &WorkspaceMatchingRule| {
+                                rule_engine::matches(
+                                    &rule.matching_rule,
+                                    &title,
+                                    &exe_name,
+                                    &class,
+                                    &path,
+                                    &regex_identifiers,
+                                )
+                            };
 
-                                        composite_results.iter().all(|&x| x)
-                                    }
+                            // rules from active schedules take precedence over workspace rules
+                            let rules =
+                                if scheduled_workspace_matching_rules.iter().any(matches_rule) {
+                                    &*scheduled_workspace_matching_rules
+                                } else {
+                                    &*workspace_matching_rules
                                 };
 
-                                if matched {
-                                    let floating = workspace.floating_windows().contains(window);
+                            let floating = workspace.floating_windows().contains(window);
+                            let rule_ops = to_move.len();
 
+                            for rule in rules {
+                                if matches_rule(rule) {
                                     if rule.initial_only {
                                         if !already_moved_window_handles.contains(&window.hwnd) {
                                             already_moved_window_handles.insert(window.hwnd);
@@ -909,6 +1127,7 @@ impl WindowManager {
                                                 rule.monitor_index,
                                                 rule.workspace_index,
                                                 floating,
+                                                rule.follow,
                                                 &mut to_move,
                                             );
                                         }
@@ -921,11 +1140,55 @@ impl WindowManager {
                                             rule.monitor_index,
                                             rule.workspace_index,
                                             floating,
+                                            rule.follow,
                                             &mut to_move,
                                         );
                                     }
                                 }
                             }
+
+                            // windows of blocked applications which no rule moves elsewhere are
+                            // redirected to the next workspace which doesn't block them
+                            let redirected = to_move[rule_ops..].iter().any(|op| !op.is_enforced());
+
+                            if !redirected
+                                && workspace.blocks_window(
+                                    &title,
+                                    &exe_name,
+                                    &class,
+                                    &path,
+                                    &regex_identifiers,
+                                )
+                            {
+                                let workspace_count = monitor.workspaces().len();
+                                let target_workspace_idx = (1..workspace_count)
+                                    .map(|offset| (j + offset) % workspace_count)
+                                    .find(|idx| {
+                                        monitor.workspaces().get(*idx).is_some_and(|target| {
+                                            !target.blocks_window(
+                                                &title,
+                                                &exe_name,
+                                                &class,
+                                                &path,
+                                                &regex_identifiers,
+                                            )
+                                        })
+                                    });
+
+                                if let Some(target_workspace_idx) = target_workspace_idx {
+                                    self.add_window_handle_to_move_based_on_workspace_rule(
+                                        &title,
+                                        window.hwnd,
+                                        i,
+                                        j,
+                                        i,
+                                        target_workspace_idx,
+                                        floating,
+                                        false,
+                                        &mut to_move,
+                                    );
+                                }
+                            }
                         }
                     }
                 }
@@ -1015,51 +1278,139 @@ impl WindowManager {
             }
         }
 
-        // Only re-tile the focused workspace if we need to
-        if should_update_focused_workspace {
+        // If a window was moved away from the focused workspace by a rule which should be
+        // followed, focus the window on its new workspace instead of re-tiling the old one
+        let to_follow = to_move
+            .iter()
+            .rev()
+            .find(|op| op.follow && op.is_origin(focused_monitor_idx, focused_workspace_idx));
+
+        if let Some(op) = to_follow {
+            tracing::info!(
+                "following window to monitor {}, workspace {}",
+                op.target_monitor_idx,
+                op.target_workspace_idx
+            );
+
+            if self.focused_monitor_idx() != op.target_monitor_idx {
+                self.focus_monitor(op.target_monitor_idx)?;
+            }
+
+            self.focus_workspace(op.target_workspace_idx)?;
+
+            let window = Window::from(op.hwnd);
+            let workspace = self.focused_workspace_mut()?;
+            if let Some(container_idx) = workspace.container_idx_for_window(op.hwnd) {
+                workspace.focus_container(container_idx);
+            }
+
+            window.focus(self.mouse_follows_focus)?;
+        } else if should_update_focused_workspace {
+            // Only re-tile the focused workspace if we need to
             self.update_focused_workspace(false, false)?;
+
+            // Windows which were sent to another workspace shouldn't keep focus if their new
+            // window focus behaviour only allows focus on the focused workspace
+            let refuse_focus = to_move.iter().any(|op| {
+                op.is_origin(focused_monitor_idx, focused_workspace_idx)
+                    && !matches!(
+                        Window::from(op.hwnd).new_window_focus_behaviour(),
+                        NewWindowFocusBehaviour::Always
+                    )
+            });
+
+            if refuse_focus {
+                if let Ok(window) = self.focused_window() {
+                    window.focus(self.mouse_follows_focus)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Gives focus back to the previously focused window of the focused workspace after a new
+    /// window which shouldn't receive focus has been opened on it, marking the new window as
+    /// urgent instead
+    pub fn refuse_new_window_focus(&mut self, window: Window, previous: Window) -> Result<()> {
+        let workspace = self.focused_workspace_mut()?;
+        if workspace.container_idx_for_window(previous.hwnd).is_some() {
+            workspace.focus_container_by_window(previous.hwnd)?;
+            workspace.set_layer(WorkspaceLayer::Tiling);
+        } else if workspace
+            .floating_windows()
+            .iter()
+            .any(|w| w.hwnd == previous.hwnd)
+        {
+            workspace.set_layer(WorkspaceLayer::Floating);
         }
 
+        self.update_focused_workspace(false, false)?;
+        previous.focus(self.mouse_follows_focus)?;
+        WindowsApi::flash_window(window.hwnd);
+
         Ok(())
     }
 
     #[tracing::instrument(skip(self))]
     pub fn retile_all(&mut self, preserve_resize_dimensions: bool) -> Result<()> {
+        for monitor_idx in 0..self.monitors().len() {
+            let workspace_idx = self.monitors()[monitor_idx].focused_workspace_idx();
+            self.retile_workspace(monitor_idx, workspace_idx, preserve_resize_dimensions)?;
+        }
+
+        Ok(())
+    }
+
+    /// Forces the retiling of the workspace at `workspace_idx` on the monitor at `monitor_idx`,
+    /// leaving every other workspace untouched. Workspaces which are not visible only have their
+    /// resize adjustments reset, and are laid out again when they are next focused.
+    #[tracing::instrument(skip(self))]
+    pub fn retile_workspace(
+        &mut self,
+        monitor_idx: usize,
+        workspace_idx: usize,
+        preserve_resize_dimensions: bool,
+    ) -> Result<()> {
         let offset = self.work_area_offset;
 
-        for monitor in self.monitors_mut() {
-            let offset = if monitor.work_area_offset().is_some() {
-                monitor.work_area_offset()
-            } else {
-                offset
-            };
+        let monitor = self
+            .monitors_mut()
+            .get_mut(monitor_idx)
+            .ok_or_else(|| anyhow!("there is no monitor at index {monitor_idx}"))?;
 
-            let focused_workspace_idx = monitor.focused_workspace_idx();
-            monitor.update_workspace_globals(focused_workspace_idx, offset);
+        let is_focused_workspace = monitor.focused_workspace_idx() == workspace_idx;
+        if is_focused_workspace {
+            monitor.update_workspace_globals(workspace_idx, offset);
+        }
 
-            let hmonitor = monitor.id();
-            let monitor_wp = monitor.wallpaper.clone();
-            let workspace = monitor
-                .focused_workspace_mut()
-                .ok_or_else(|| anyhow!("there is no workspace"))?;
+        let hmonitor = monitor.id();
+        let monitor_wp = monitor.wallpaper.clone();
+        let workspace = monitor
+            .workspaces_mut()
+            .get_mut(workspace_idx)
+            .ok_or_else(|| anyhow!("there is no workspace at index {workspace_idx}"))?;
 
-            // Reset any resize adjustments if we want to force a retile
-            if !preserve_resize_dimensions {
-                for resize in workspace.resize_dimensions_mut() {
-                    *resize = None;
-                }
+        // Reset any resize adjustments if we want to force a retile
+        if !preserve_resize_dimensions {
+            for resize in workspace.resize_dimensions_mut() {
+                *resize = None;
             }
 
-            if workspace.wallpaper().is_some() || monitor_wp.is_some() {
-                if let Err(error) = workspace.apply_wallpaper(hmonitor, &monitor_wp) {
-                    tracing::error!("failed to apply wallpaper: {}", error);
-                }
-            }
+            workspace.split_ratios_mut().clear();
+        }
 
-            workspace.update()?;
+        if !is_focused_workspace {
+            return Ok(());
         }
 
-        Ok(())
+        if workspace.wallpaper().is_some() || monitor_wp.is_some() {
+            if let Err(error) = workspace.apply_wallpaper(hmonitor, &monitor_wp) {
+                tracing::error!("failed to apply wallpaper: {}", error);
+            }
+        }
+
+        workspace.update()
     }
 
     #[tracing::instrument(skip(self))]
@@ -1552,6 +1903,9 @@ impl WindowManager {
             }
             WorkspaceLayer::Tiling => {
                 match workspace.layout() {
+                    Layout::Default(DefaultLayout::Manual) => {
+                        bail!("containers cannot be resized when using the Manual layout");
+                    }
                     Layout::Default(layout) => {
                         tracing::info!("resizing window");
                         let len = NonZeroUsize::new(workspace.containers().len())
@@ -1570,6 +1924,7 @@ impl WindowManager {
                                 workspace.layout_flip(),
                                 focused_idx,
                                 len,
+                                workspace.layout_options(),
                             )
                             .is_some()
                         {
@@ -1579,6 +1934,7 @@ impl WindowManager {
                                 workspace.container_padding(),
                                 workspace.layout_flip(),
                                 &[],
+                                workspace.split_ratios(),
                                 workspace.focused_container_idx(),
                                 workspace.layout_options(),
                                 workspace.latest_layout(),
@@ -1639,6 +1995,84 @@ impl WindowManager {
         Ok(())
     }
 
+    /// Resizes the focused window along `axis` by `horizontal_delta` and `vertical_delta` pixels
+    /// on each edge, or the primary column of a custom layout by `primary_width_delta` percent
+    #[tracing::instrument(skip(self))]
+    pub fn resize_window_axis(
+        &mut self,
+        axis: Axis,
+        sizing: Sizing,
+        horizontal_delta: i32,
+        vertical_delta: i32,
+        primary_width_delta: f32,
+    ) -> Result<()> {
+        // If the user has a custom layout, allow for the resizing of the primary column
+        // with this signal
+        let workspace = self.focused_workspace_mut()?;
+        let container_len = workspace.containers().len();
+        let no_layout_rules = workspace.layout_rules().is_empty();
+
+        if let Layout::Custom(ref mut custom) = workspace.layout_mut() {
+            if matches!(axis, Axis::Horizontal) {
+                #[allow(clippy::cast_precision_loss)]
+                let percentage = custom
+                    .primary_width_percentage()
+                    .unwrap_or(100.0 / (custom.len() as f32));
+
+                if no_layout_rules {
+                    match sizing {
+                        Sizing::Increase => {
+                            custom.set_primary_width_percentage(percentage + primary_width_delta);
+                        }
+                        Sizing::Decrease => {
+                            custom.set_primary_width_percentage(percentage - primary_width_delta);
+                        }
+                    }
+                } else {
+                    for rule in workspace.layout_rules_mut() {
+                        if container_len >= rule.0 {
+                            if let Layout::Custom(ref mut custom) = rule.1 {
+                                match sizing {
+                                    Sizing::Increase => {
+                                        custom.set_primary_width_percentage(
+                                            percentage + primary_width_delta,
+                                        );
+                                    }
+                                    Sizing::Decrease => {
+                                        custom.set_primary_width_percentage(
+                                            percentage - primary_width_delta,
+                                        );
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            // Otherwise proceed with the resizing logic for individual window containers in the
+            // assumed BSP layout
+        } else {
+            match axis {
+                Axis::Horizontal => {
+                    self.resize_window(OperationDirection::Left, sizing, horizontal_delta, false)?;
+                    self.resize_window(OperationDirection::Right, sizing, horizontal_delta, false)?;
+                }
+                Axis::Vertical => {
+                    self.resize_window(OperationDirection::Up, sizing, vertical_delta, false)?;
+                    self.resize_window(OperationDirection::Down, sizing, vertical_delta, false)?;
+                }
+                Axis::HorizontalAndVertical => {
+                    self.resize_window(OperationDirection::Left, sizing, horizontal_delta, false)?;
+                    self.resize_window(OperationDirection::Right, sizing, horizontal_delta, false)?;
+                    self.resize_window(OperationDirection::Up, sizing, vertical_delta, false)?;
+                    self.resize_window(OperationDirection::Down, sizing, vertical_delta, false)?;
+                }
+            }
+        }
+
+        self.update_focused_workspace(false, false)
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn stop(&mut self, ignore_restore: bool) -> Result<()> {
         tracing::info!(
@@ -1651,6 +2085,10 @@ impl WindowManager {
             serde_json::to_string_pretty(&state)?,
         )?;
 
+        if let Err(error) = Session::from(&*self).save(Session::default_path()) {
+            tracing::error!("could not save session: {error}");
+        }
+
         ANIMATION_ENABLED_PER_ANIMATION.lock().clear();
         ANIMATION_ENABLED_GLOBAL.store(false, Ordering::SeqCst);
         self.restore_all_windows(ignore_restore)?;
@@ -1667,6 +2105,13 @@ impl WindowManager {
             }
         }
 
+        let diff_sockets = SUBSCRIPTION_DIFF_SOCKETS.lock();
+        for subscriber in (*diff_sockets).values() {
+            if let Ok(stream) = UnixStream::connect(subscriber.path()) {
+                stream.shutdown(Shutdown::Both)?;
+            }
+        }
+
         let socket = DATA_DIR.join("komorebi.sock");
         let _ = std::fs::remove_file(socket);
 
@@ -1683,6 +2128,10 @@ impl WindowManager {
         let border_implementation = border_manager::IMPLEMENTATION.load();
 
         for monitor in self.monitors_mut() {
+            for window in monitor.always_on_top() {
+                WindowsApi::set_topmost(window.hwnd, false)?;
+            }
+
             for workspace in monitor.workspaces_mut() {
                 if let Some(monocle) = workspace.monocle_container() {
                     for window in monocle.windows() {
@@ -1721,12 +2170,121 @@ impl WindowManager {
                         }
                     }
                 }
+
+                // these have no taskbar entry to bring them back with once komorebi is stopped
+                for window in workspace.hidden_windows() {
+                    WindowsApi::restore_window(window.hwnd);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes `window` from its workspace and hides it in the workspace's hidden windows until
+    /// they are brought back with `komorebic restore-hidden`
+    #[tracing::instrument(skip(self))]
+    pub fn hide_to_tray(&mut self, window: Window) -> Result<()> {
+        tracing::info!("hiding window {} to tray", window.hwnd);
+
+        let focused_monitor_idx = self.focused_monitor_idx();
+        let (monitor_idx, workspace_idx) = match self.known_hwnds.get(&window.hwnd) {
+            Some(location) => *location,
+            None => (
+                focused_monitor_idx,
+                self.focused_workspace_idx_for_monitor_idx(focused_monitor_idx)?,
+            ),
+        };
+
+        let monitor = self
+            .monitors_mut()
+            .get_mut(monitor_idx)
+            .ok_or_else(|| anyhow!("there is no monitor"))?;
+        let is_focused_workspace = monitor.focused_workspace_idx() == workspace_idx;
+        let workspace = monitor
+            .workspaces_mut()
+            .get_mut(workspace_idx)
+            .ok_or_else(|| anyhow!("there is no workspace"))?;
+
+        if workspace.contains_window(window.hwnd) {
+            workspace.remove_window(window.hwnd)?;
+        }
+
+        if !workspace
+            .hidden_windows()
+            .iter()
+            .any(|hidden| hidden.hwnd == window.hwnd)
+        {
+            workspace.hidden_windows_mut().push(window);
+        }
+
+        // the hide event this sends is ours, not the application's
+        {
+            let mut programmatically_hidden_hwnds = HIDDEN_HWNDS.lock();
+            if !programmatically_hidden_hwnds.contains(&window.hwnd) {
+                programmatically_hidden_hwnds.push(window.hwnd);
             }
         }
 
+        WindowsApi::hide_window(window.hwnd);
+
+        if is_focused_workspace {
+            self.update_focused_workspace_by_monitor_idx(monitor_idx)?;
+        }
+
         Ok(())
     }
 
+    /// Forgets that `hwnd` was hidden to the tray, for when it has been shown again by its
+    /// application, eg. from its tray icon, returning whether it was hidden
+    pub fn forget_hidden_window(&mut self, hwnd: isize) -> bool {
+        let mut was_hidden = false;
+
+        for monitor in self.monitors_mut() {
+            for workspace in monitor.workspaces_mut() {
+                let hidden_windows = workspace.hidden_windows_mut();
+                let count = hidden_windows.len();
+                hidden_windows.retain(|window| window.hwnd != hwnd);
+                was_hidden |= hidden_windows.len() != count;
+            }
+        }
+
+        if was_hidden {
+            tracing::info!("window {hwnd} is no longer hidden to tray");
+            HIDDEN_HWNDS.lock().retain(|hidden| *hidden != hwnd);
+        }
+
+        was_hidden
+    }
+
+    /// Shows the hidden windows of the focused workspace again, tiling each of them in a new
+    /// container
+    #[tracing::instrument(skip(self))]
+    pub fn restore_hidden_windows(&mut self) -> Result<()> {
+        tracing::info!("restoring hidden windows");
+
+        let hidden_windows = std::mem::take(self.focused_workspace_mut()?.hidden_windows_mut());
+
+        HIDDEN_HWNDS
+            .lock()
+            .retain(|hwnd| !hidden_windows.iter().any(|window| window.hwnd == *hwnd));
+
+        // the application may have been quit from its tray icon in the meantime, or shown again
+        // and managed somewhere else
+        let hidden_windows = hidden_windows
+            .into_iter()
+            .filter(|window| window.is_window() && !self.known_hwnds.contains_key(&window.hwnd))
+            .collect::<Vec<_>>();
+
+        let workspace = self.focused_workspace_mut()?;
+        for window in hidden_windows {
+            workspace.new_container_for_window(window);
+            WindowsApi::restore_window(window.hwnd);
+        }
+
+        self.update_focused_workspace(false, false)
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn remove_all_accents(&mut self) -> Result<()> {
         tracing::info!("removing all window accents");
@@ -1866,6 +2424,35 @@ impl WindowManager {
         self.update_focused_workspace_by_monitor_idx(first_idx)
     }
 
+    /// Swaps the workspaces at `first` and `second` on the monitor at `monitor_idx`, updating the
+    /// workspace rules which refer to either of them
+    #[tracing::instrument(skip(self))]
+    pub fn swap_workspaces(
+        &mut self,
+        monitor_idx: usize,
+        first: usize,
+        second: usize,
+    ) -> Result<()> {
+        tracing::info!("swapping workspaces");
+
+        self.monitors_mut()
+            .get_mut(monitor_idx)
+            .ok_or_else(|| anyhow!("there is no monitor at this index"))?
+            .swap_workspaces(first, second)?;
+
+        for rule in WORKSPACE_MATCHING_RULES.lock().iter_mut() {
+            if rule.monitor_index == monitor_idx {
+                if rule.workspace_index == first {
+                    rule.workspace_index = second;
+                } else if rule.workspace_index == second {
+                    rule.workspace_index = first;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn swap_focused_monitor(&mut self, idx: usize) -> Result<()> {
         tracing::info!("swapping focused monitor");
@@ -2051,6 +2638,29 @@ impl WindowManager {
         workspace
     }
 
+    #[tracing::instrument(skip(self))]
+    pub fn clone_workspace_to_monitor(&mut self, idx: usize) -> Result<()> {
+        tracing::info!("cloning workspace");
+        let mouse_follows_focus = self.mouse_follows_focus;
+        let offset = self.work_area_offset;
+        let workspace = self.focused_workspace()?.clone_without_windows();
+
+        {
+            let target_monitor: &mut Monitor = self
+                .monitors_mut()
+                .get_mut(idx)
+                .ok_or_else(|| anyhow!("there is no monitor"))?;
+
+            target_monitor.workspaces_mut().push_back(workspace);
+            target_monitor.update_workspaces_globals(offset);
+            target_monitor.focus_workspace(target_monitor.workspaces().len().saturating_sub(1))?;
+            target_monitor.load_focused_workspace(mouse_follows_focus)?;
+        }
+
+        self.focus_monitor(idx)?;
+        self.update_focused_workspace(mouse_follows_focus, true)
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn move_workspace_to_monitor(&mut self, idx: usize) -> Result<()> {
         tracing::info!("moving workspace");
@@ -2255,54 +2865,58 @@ impl WindowManager {
                     };
                 }
             }
-
-            return Ok(());
-        }
-
-        // if there is no floating_window in that direction for this workspace
-        let monitor_idx = self
-            .monitor_idx_in_direction(direction)
-            .ok_or_else(|| anyhow!("there is no container or monitor in this direction"))?;
-
-        self.focus_monitor(monitor_idx)?;
-        let mouse_follows_focus = self.mouse_follows_focus;
-
-        if let Ok(focused_workspace) = self.focused_workspace_mut() {
-            if let Some(window) = focused_workspace.maximized_window() {
-                window.focus(mouse_follows_focus)?;
-                cross_monitor_monocle_or_max = true;
-            } else if let Some(monocle) = focused_workspace.monocle_container() {
-                if let Some(window) = monocle.focused_window() {
-                    window.focus(mouse_follows_focus)?;
-                    cross_monitor_monocle_or_max = true;
-                }
-            } else if focused_workspace.layer() == &WorkspaceLayer::Tiling {
-                match direction {
-                    OperationDirection::Left => match focused_workspace.layout() {
-                        Layout::Default(layout) => {
-                            let target_index =
-                                layout.rightmost_index(focused_workspace.containers().len());
-                            focused_workspace.focus_container(target_index);
-                        }
-                        Layout::Custom(_) => {
-                            focused_workspace.focus_container(
-                                focused_workspace.containers().len().saturating_sub(1),
-                            );
-                        }
-                    },
-                    OperationDirection::Right => match focused_workspace.layout() {
-                        Layout::Default(layout) => {
-                            let target_index =
-                                layout.leftmost_index(focused_workspace.containers().len());
-                            focused_workspace.focus_container(target_index);
-                        }
-                        Layout::Custom(_) => {
-                            focused_workspace.focus_container(0);
-                        }
-                    },
-                    _ => {}
-                };
-            }
+
+            return Ok(());
+        }
+
+        // if there is no floating_window in that direction for this workspace
+        let (monitor_idx, nearest_container_idx) = self
+            .monitor_and_container_idx_in_direction(direction)
+            .ok_or_else(|| anyhow!("there is no container or monitor in this direction"))?;
+
+        self.focus_monitor(monitor_idx)?;
+        let mouse_follows_focus = self.mouse_follows_focus;
+
+        if let Ok(focused_workspace) = self.focused_workspace_mut() {
+            if let Some(window) = focused_workspace.maximized_window() {
+                window.focus(mouse_follows_focus)?;
+                cross_monitor_monocle_or_max = true;
+            } else if let Some(monocle) = focused_workspace.monocle_container() {
+                if let Some(window) = monocle.focused_window() {
+                    window.focus(mouse_follows_focus)?;
+                    cross_monitor_monocle_or_max = true;
+                }
+            } else if focused_workspace.layer() == &WorkspaceLayer::Tiling {
+                if let Some(idx) = nearest_container_idx {
+                    focused_workspace.focus_container(idx);
+                } else {
+                    match direction {
+                        OperationDirection::Left => match focused_workspace.layout() {
+                            Layout::Default(layout) => {
+                                let target_index =
+                                    layout.rightmost_index(focused_workspace.containers().len());
+                                focused_workspace.focus_container(target_index);
+                            }
+                            Layout::Custom(_) => {
+                                focused_workspace.focus_container(
+                                    focused_workspace.containers().len().saturating_sub(1),
+                                );
+                            }
+                        },
+                        OperationDirection::Right => match focused_workspace.layout() {
+                            Layout::Default(layout) => {
+                                let target_index =
+                                    layout.leftmost_index(focused_workspace.containers().len());
+                                focused_workspace.focus_container(target_index);
+                            }
+                            Layout::Custom(_) => {
+                                focused_workspace.focus_container(0);
+                            }
+                        },
+                        _ => {}
+                    };
+                }
+            }
         }
 
         if !cross_monitor_monocle_or_max {
@@ -2417,8 +3031,8 @@ impl WindowManager {
         // if there is no container in that direction for this workspace
         match new_idx {
             None => {
-                let monitor_idx = self
-                    .monitor_idx_in_direction(direction)
+                let (monitor_idx, nearest_container_idx) = self
+                    .monitor_and_container_idx_in_direction(direction)
                     .ok_or_else(|| anyhow!("there is no container or monitor in this direction"))?;
 
                 self.focus_monitor(monitor_idx)?;
@@ -2434,31 +3048,35 @@ impl WindowManager {
                             cross_monitor_monocle_or_max = true;
                         }
                     } else if focused_workspace.layer() == &WorkspaceLayer::Tiling {
-                        match direction {
-                            OperationDirection::Left => match focused_workspace.layout() {
-                                Layout::Default(layout) => {
-                                    let target_index = layout
-                                        .rightmost_index(focused_workspace.containers().len());
-                                    focused_workspace.focus_container(target_index);
-                                }
-                                Layout::Custom(_) => {
-                                    focused_workspace.focus_container(
-                                        focused_workspace.containers().len().saturating_sub(1),
-                                    );
-                                }
-                            },
-                            OperationDirection::Right => match focused_workspace.layout() {
-                                Layout::Default(layout) => {
-                                    let target_index =
-                                        layout.leftmost_index(focused_workspace.containers().len());
-                                    focused_workspace.focus_container(target_index);
-                                }
-                                Layout::Custom(_) => {
-                                    focused_workspace.focus_container(0);
-                                }
-                            },
-                            _ => {}
-                        };
+                        if let Some(idx) = nearest_container_idx {
+                            focused_workspace.focus_container(idx);
+                        } else {
+                            match direction {
+                                OperationDirection::Left => match focused_workspace.layout() {
+                                    Layout::Default(layout) => {
+                                        let target_index = layout
+                                            .rightmost_index(focused_workspace.containers().len());
+                                        focused_workspace.focus_container(target_index);
+                                    }
+                                    Layout::Custom(_) => {
+                                        focused_workspace.focus_container(
+                                            focused_workspace.containers().len().saturating_sub(1),
+                                        );
+                                    }
+                                },
+                                OperationDirection::Right => match focused_workspace.layout() {
+                                    Layout::Default(layout) => {
+                                        let target_index = layout
+                                            .leftmost_index(focused_workspace.containers().len());
+                                        focused_workspace.focus_container(target_index);
+                                    }
+                                    Layout::Custom(_) => {
+                                        focused_workspace.focus_container(0);
+                                    }
+                                },
+                                _ => {}
+                            };
+                        }
                     }
                 }
             }
@@ -2957,6 +3575,45 @@ impl WindowManager {
         self.update_focused_workspace(self.mouse_follows_focus, true)
     }
 
+    #[tracing::instrument(skip(self))]
+    pub fn move_stack_window(&mut self, hwnd: isize, idx: usize) -> Result<()> {
+        tracing::info!("moving stack window to index {idx}");
+
+        let (monitor_idx, workspace_idx) = *self
+            .known_hwnds
+            .get(&hwnd)
+            .ok_or_else(|| anyhow!("there is no managed window with hwnd {hwnd}"))?;
+
+        let workspace = self
+            .monitors_mut()
+            .get_mut(monitor_idx)
+            .and_then(|monitor| monitor.workspaces_mut().get_mut(workspace_idx))
+            .ok_or_else(|| anyhow!("there is no workspace at index {workspace_idx}"))?;
+
+        let in_monocle = workspace
+            .monocle_container()
+            .as_ref()
+            .is_some_and(|container| container.contains_window(hwnd));
+
+        let container = if in_monocle {
+            workspace.monocle_container_mut().as_mut()
+        } else {
+            let container_idx = workspace
+                .container_idx_for_window(hwnd)
+                .ok_or_else(|| anyhow!("there is no container with hwnd {hwnd}"))?;
+            workspace.containers_mut().get_mut(container_idx)
+        }
+        .ok_or_else(|| anyhow!("there is no container with hwnd {hwnd}"))?;
+
+        let from = container
+            .idx_for_window(hwnd)
+            .ok_or_else(|| anyhow!("there is no window with hwnd {hwnd}"))?;
+
+        container.move_window(from, idx);
+
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn stack_all(&mut self) -> Result<()> {
         self.unstack_all(false)?;
@@ -3031,18 +3688,13 @@ impl WindowManager {
         tracing::info!("adding window to container");
 
         let workspace = self.focused_workspace_mut()?;
-        let len = NonZeroUsize::new(workspace.containers_mut().len())
-            .ok_or_else(|| anyhow!("there must be at least one container"))?;
+        if workspace.containers().is_empty() {
+            return Err(anyhow!("there must be at least one container"));
+        }
+
         let current_container_idx = workspace.focused_container_idx();
 
-        let is_valid = direction
-            .destination(
-                workspace.layout().as_boxed_direction().as_ref(),
-                workspace.layout_flip(),
-                workspace.focused_container_idx(),
-                len,
-            )
-            .is_some();
+        let is_valid = workspace.new_idx_for_direction(direction).is_some();
 
         if is_valid {
             let new_idx = workspace.new_idx_for_direction(direction).ok_or_else(|| {
@@ -3188,6 +3840,61 @@ impl WindowManager {
         self.update_focused_workspace(is_floating_window, true)
     }
 
+    /// Pins the focused window above the tiling layer of every workspace on the focused monitor,
+    /// or returns it to the focused workspace as a new container if it is already pinned
+    #[tracing::instrument(skip(self))]
+    pub fn toggle_always_on_top(&mut self) -> Result<()> {
+        let hwnd = WindowsApi::foreground_window()?;
+        let mouse_follows_focus = self.mouse_follows_focus;
+
+        let monitor = self
+            .focused_monitor_mut()
+            .ok_or_else(|| anyhow!("there is no monitor"))?;
+
+        if let Some(idx) = monitor.always_on_top().iter().position(|w| w.hwnd == hwnd) {
+            tracing::info!("unpinning window from the top");
+
+            let window = monitor
+                .always_on_top_mut()
+                .remove(idx)
+                .ok_or_else(|| anyhow!("there is no window"))?;
+            monitor.set_always_on_top_focused(false);
+            WindowsApi::set_topmost(window.hwnd, false)?;
+
+            let workspace = monitor
+                .focused_workspace_mut()
+                .ok_or_else(|| anyhow!("there is no workspace"))?;
+            workspace.new_container_for_window(window);
+            workspace.set_layer(WorkspaceLayer::Tiling);
+        } else {
+            let workspace = monitor
+                .focused_workspace_mut()
+                .ok_or_else(|| anyhow!("there is no workspace"))?;
+
+            if !workspace.contains_managed_window(hwnd) {
+                tracing::warn!("ignoring toggle-always-on-top command for an unmanaged window");
+                return Ok(());
+            }
+
+            tracing::info!("pinning window to the top");
+
+            workspace.remove_window(hwnd)?;
+            WindowsApi::set_topmost(hwnd, true)?;
+            monitor.always_on_top_mut().push_back(Window::from(hwnd));
+            monitor.set_always_on_top_focused(true);
+        }
+
+        self.update_focused_workspace(false, false)?;
+        Window::from(hwnd).focus(mouse_follows_focus)
+    }
+
+    /// Whether the window with `hwnd` is pinned above the tiling layer of any monitor
+    pub fn is_always_on_top(&self, hwnd: isize) -> bool {
+        self.monitors()
+            .iter()
+            .any(|monitor| monitor.always_on_top().iter().any(|w| w.hwnd == hwnd))
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn toggle_lock(&mut self) -> Result<()> {
         let workspace = self.focused_workspace_mut()?;
@@ -3214,7 +3921,7 @@ impl WindowManager {
             .back_mut()
             .ok_or_else(|| anyhow!("there is no floating window"))?;
 
-        if toggle_float_placement.should_center() {
+        if !window.apply_floating_geometry(&work_area)? && toggle_float_placement.should_center() {
             window.center(&work_area, toggle_float_placement.should_resize())?;
         }
         window.focus(self.mouse_follows_focus)?;
@@ -3249,7 +3956,11 @@ impl WindowManager {
     pub fn monocle_on(&mut self) -> Result<()> {
         tracing::info!("enabling monocle");
 
-        let workspace = self.focused_workspace_mut()?;
+        Self::monocle_on_for_workspace(self.focused_workspace_mut()?)
+    }
+
+    /// Puts the focused container of `workspace` in monocle, hiding everything else on it
+    pub fn monocle_on_for_workspace(workspace: &mut Workspace) -> Result<()> {
         workspace.new_monocle_container()?;
 
         for container in workspace.containers_mut() {
@@ -3267,7 +3978,21 @@ impl WindowManager {
     pub fn monocle_off(&mut self) -> Result<()> {
         tracing::info!("disabling monocle");
 
-        let workspace = self.focused_workspace_mut()?;
+        Self::monocle_off_for_workspace(self.focused_workspace_mut()?)
+    }
+
+    /// Takes the monocle container of `workspace` out of monocle, showing everything else on it
+    pub fn monocle_off_for_workspace(workspace: &mut Workspace) -> Result<()> {
+        if let Some(container) = workspace.monocle_container() {
+            let mut maximized_monocle_hwnds = MAXIMIZED_MONOCLE_HWNDS.lock();
+            for window in container.windows() {
+                // Leaving monocle without restoring a window which was maximized into it has to
+                // restore it too, otherwise it would go straight back into monocle
+                if maximized_monocle_hwnds.remove(&window.hwnd) {
+                    WindowsApi::unmaximize_window(window.hwnd);
+                }
+            }
+        }
 
         for container in workspace.containers_mut() {
             container.restore();
@@ -3920,10 +4645,67 @@ impl WindowManager {
             .focused_monitor_mut()
             .ok_or_else(|| anyhow!("there is no workspace"))?;
 
+        let previous_idx = monitor.focused_workspace_idx();
+
         monitor.focus_workspace(idx)?;
         monitor.load_focused_workspace(mouse_follows_focus)?;
 
-        self.update_focused_workspace(false, true)
+        self.update_focused_workspace(false, true)?;
+
+        if idx != previous_idx {
+            self.animate_workspace_switch(idx > previous_idx)?;
+        }
+
+        Ok(())
+    }
+
+    /// Animates the windows of the newly focused workspace into place if workspace switch
+    /// animations are enabled, sliding them in from the right when `forwards` and from the left
+    /// otherwise
+    fn animate_workspace_switch(&self, forwards: bool) -> Result<()> {
+        // workspace switch animations are opt-in, they are not enabled by the global toggle
+        let enabled = ANIMATION_ENABLED_PER_ANIMATION
+            .lock()
+            .get(&AnimationPrefix::WorkspaceSwitch)
+            .is_some_and(|enabled| *enabled);
+
+        if !enabled {
+            return Ok(());
+        }
+
+        let monitor = self
+            .focused_monitor()
+            .ok_or_else(|| anyhow!("there is no monitor"))?;
+
+        let windows = monitor
+            .focused_workspace()
+            .ok_or_else(|| anyhow!("there is no workspace"))?
+            .visible_windows()
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|window| !window.is_animation_ignored())
+            .collect::<Vec<_>>();
+
+        // under load, i.e. with lots of windows or when switching again before the previous
+        // switch has finished animating, the switch is instant rather than choppy
+        if windows.len() > WORKSPACE_SWITCH_ANIMATION_WINDOW_LIMIT
+            || ANIMATION_MANAGER
+                .lock()
+                .count_in_progress(AnimationPrefix::WorkspaceSwitch)
+                > 0
+        {
+            return Ok(());
+        }
+
+        let width = monitor.work_area_size().right;
+        let offset = if forwards { width } else { -width };
+
+        for window in windows {
+            window.animate_workspace_switch(offset)?;
+        }
+
+        Ok(())
     }
 
     #[tracing::instrument(skip(self))]
@@ -3986,6 +4768,231 @@ impl WindowManager {
             .ok_or_else(|| anyhow!("there is no window"))
     }
 
+    /// Looks for a tiled window on the focused workspace matching the `swallowing_rules` which
+    /// belongs to an ancestor process of `window`. If one is found, `window` should take over its
+    /// tile until it is closed.
+    pub fn swallowing_parent(&self, window: Window) -> Result<Option<Window>> {
+        // Terminals usually launch a shell which then launches the GUI application, so we need to
+        // check more than just the direct parent process
+        const MAX_ANCESTORS: usize = 4;
+
+        let swallowing_rules = SWALLOWING_RULES.lock();
+        if swallowing_rules.is_empty() {
+            return Ok(None);
+        }
+
+        let workspace = self.focused_workspace()?;
+        let candidates = workspace
+            .containers()
+            .iter()
+            .chain(workspace.monocle_container())
+            .flat_map(|c| c.windows())
+            .filter(|w| w.hwnd != window.hwnd)
+            .collect::<Vec<_>>();
+
+        if candidates.is_empty() {
+            return Ok(None);
+        }
+
+        let regex_identifiers = REGEX_IDENTIFIERS.lock();
+        let mut process_id = window.process_id();
+
+        for _ in 0..MAX_ANCESTORS {
+            let parent_process_id = match WindowsApi::parent_process_id(process_id)? {
+                Some(parent_process_id) if parent_process_id != 0 => parent_process_id,
+                _ => break,
+            };
+
+            for candidate in &candidates {
+                if candidate.process_id() != parent_process_id {
+                    continue;
+                }
+
+                if let (Ok(title), Ok(exe_name), Ok(class), Ok(path)) = (
+                    candidate.title(),
+                    candidate.exe(),
+                    candidate.class(),
+                    candidate.path(),
+                ) {
+                    if should_act(
+                        &title,
+                        &exe_name,
+                        &class,
+                        &path,
+                        &swallowing_rules,
+                        &regex_identifiers,
+                    )
+                    .is_some()
+                    {
+                        return Ok(Option::from(**candidate));
+                    }
+                }
+            }
+
+            process_id = parent_process_id;
+        }
+
+        Ok(None)
+    }
+
+    /// Looks for the tiled window on the focused workspace which owns `window`, if that window
+    /// matches the `transient_dialog_rules`. If one is found, `window` should be stacked in the
+    /// container of its owner instead of being given a new tile.
+    pub fn transient_dialog_owner(&self, window: Window) -> Result<Option<Window>> {
+        let transient_dialog_rules = TRANSIENT_DIALOG_RULES.lock();
+        if transient_dialog_rules.is_empty() {
+            return Ok(None);
+        }
+
+        let Ok(owner_hwnd) = WindowsApi::owner_window(window.hwnd) else {
+            return Ok(None);
+        };
+
+        let workspace = self.focused_workspace()?;
+        let Some(owner) = workspace
+            .containers()
+            .iter()
+            .flat_map(|c| c.windows())
+            .find(|w| w.hwnd == owner_hwnd)
+        else {
+            return Ok(None);
+        };
+
+        let regex_identifiers = REGEX_IDENTIFIERS.lock();
+
+        if let (Ok(title), Ok(exe_name), Ok(class), Ok(path)) =
+            (owner.title(), owner.exe(), owner.class(), owner.path())
+        {
+            if should_act(
+                &title,
+                &exe_name,
+                &class,
+                &path,
+                &transient_dialog_rules,
+                &regex_identifiers,
+            )
+            .is_some()
+            {
+                return Ok(Option::from(*owner));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Records the owner of `window` if it is a window managed on the focused workspace, so that
+    /// focus can be returned to it when `window` is closed
+    pub fn track_window_owner(&mut self, window: Window) -> Result<()> {
+        let Ok(owner_hwnd) = WindowsApi::owner_window(window.hwnd) else {
+            return Ok(());
+        };
+
+        if owner_hwnd != window.hwnd && self.focused_workspace()?.contains_window(owner_hwnd) {
+            self.window_owners.insert(window.hwnd, owner_hwnd);
+        }
+
+        Ok(())
+    }
+
+    /// Stops tracking the closed window `hwnd`, handing any windows it owned over to its own
+    /// owner so that ownership chains stay intact, and focuses the closest ancestor of `hwnd`
+    /// which is still managed on the focused workspace, whichever layer it is on
+    pub fn return_focus_to_owner(&mut self, hwnd: isize) -> Result<()> {
+        let owner_hwnd = self.window_owners.remove(&hwnd);
+
+        for owner in self.window_owners.values_mut() {
+            if *owner == hwnd {
+                match owner_hwnd {
+                    Some(owner_hwnd) => *owner = owner_hwnd,
+                    None => *owner = 0,
+                }
+            }
+        }
+
+        self.window_owners.retain(|_, owner| *owner != 0);
+
+        let workspace = self.focused_workspace()?;
+        let mut candidate = owner_hwnd;
+        // The length of the map bounds the walk in case of an ownership cycle
+        let mut remaining = self.window_owners.len();
+
+        let target = loop {
+            match candidate {
+                Some(owner) if workspace.contains_window(owner) && WindowsApi::is_window(owner) => {
+                    break Some(owner);
+                }
+                Some(owner) if remaining > 0 => {
+                    remaining -= 1;
+                    candidate = self.window_owners.get(&owner).copied();
+                }
+                _ => break None,
+            }
+        };
+
+        let Some(target) = target else {
+            return Ok(());
+        };
+
+        tracing::info!("returning focus to owner window {target}");
+
+        let mouse_follows_focus = self.mouse_follows_focus;
+        let workspace = self.focused_workspace_mut()?;
+
+        if let Some(idx) = workspace
+            .floating_windows()
+            .iter()
+            .position(|w| w.hwnd == target)
+        {
+            workspace.floating_windows.focus(idx);
+            workspace.set_layer(WorkspaceLayer::Floating);
+            Window::from(target).focus(mouse_follows_focus)?;
+        } else if workspace.container_idx_for_window(target).is_some() {
+            workspace.focus_container_by_window(target)?;
+            workspace.set_layer(WorkspaceLayer::Tiling);
+            self.update_focused_workspace(mouse_follows_focus, true)?;
+        } else {
+            // The owner is the monocle container or the maximized window
+            Window::from(target).focus(mouse_follows_focus)?;
+        }
+
+        Ok(())
+    }
+
+    /// Focuses the managed window with the given hwnd, switching to the monitor and workspace
+    /// which it is on if necessary
+    pub fn focus_hwnd(&mut self, hwnd: isize) -> Result<()> {
+        let mut location = None;
+        for (monitor_idx, monitor) in self.monitors().iter().enumerate() {
+            for (workspace_idx, workspace) in monitor.workspaces().iter().enumerate() {
+                if workspace.contains_window(hwnd) {
+                    location = Option::from((monitor_idx, workspace_idx));
+                }
+            }
+        }
+
+        let (monitor_idx, workspace_idx) =
+            location.ok_or_else(|| anyhow!("there is no managed window with this hwnd"))?;
+
+        self.focus_monitor(monitor_idx)?;
+
+        let focused_workspace_idx = self
+            .focused_monitor()
+            .ok_or_else(|| anyhow!("there is no monitor"))?
+            .focused_workspace_idx();
+
+        if focused_workspace_idx != workspace_idx {
+            self.focus_workspace(workspace_idx)?;
+        }
+
+        let workspace = self.focused_workspace_mut()?;
+        if workspace.container_idx_for_window(hwnd).is_some() {
+            workspace.focus_container_by_window(hwnd)?;
+        }
+
+        self.update_focused_workspace(self.mouse_follows_focus, false)?;
+        Window::from(hwnd).focus(self.mouse_follows_focus)
+    }
+
     /// Updates the list of `known_hwnds` and their monitor/workspace index pair
     ///
     /// [`known_hwnds`]: `Self.known_hwnds`
@@ -4053,6 +5060,7 @@ impl WindowManager {
 mod tests {
     use super::*;
     use crate::monitor;
+    use crate::static_config::WorkspaceConfig;
     use crossbeam_channel::bounded;
     use crossbeam_channel::Sender;
     use std::path::PathBuf;
@@ -4399,6 +5407,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_contain_focus() {
+        let (mut wm, _test_context) = setup_window_manager();
+
+        // Create two monitors side by side
+        for (idx, left) in [0, 1920].into_iter().enumerate() {
+            let size = Rect {
+                left,
+                top: 0,
+                right: 1920,
+                bottom: 1080,
+            };
+
+            let m = monitor::new(
+                idx as isize,
+                size,
+                size,
+                format!("TestMonitor{idx}"),
+                format!("TestDevice{idx}"),
+                format!("TestDeviceID{idx}"),
+                Some(format!("TestMonitorID{idx}")),
+            );
+
+            wm.monitors_mut().push_back(m);
+        }
+
+        wm.focus_monitor(0).unwrap();
+
+        // Focus can cross over to the monitor on the right
+        assert_eq!(
+            wm.monitor_and_container_idx_in_direction(OperationDirection::Right),
+            Some((1, None))
+        );
+
+        wm.focused_workspace_mut()
+            .unwrap()
+            .set_workspace_config(Some(WorkspaceConfig {
+                contain_focus: Some(true),
+                ..Default::default()
+            }));
+
+        // Focus stays on the first monitor once its workspace contains focus
+        assert_eq!(
+            wm.monitor_and_container_idx_in_direction(OperationDirection::Right),
+            None
+        );
+    }
+
     #[test]
     fn test_focus_container_in_cycle_direction() {
         let (mut wm, _test_context) = setup_window_manager();
@@ -6,7 +6,10 @@ use serde::Serialize;
 
 use crate::window::should_act;
 use crate::window::Window;
+use crate::windows_api::WindowsApi;
 use crate::winevent::WinEvent;
+use crate::MAXIMIZED_MONOCLE_HWNDS;
+use crate::MAXIMIZE_MONOCLE_RULES;
 use crate::OBJECT_NAME_CHANGE_ON_LAUNCH;
 use crate::OBJECT_NAME_CHANGE_TITLE_IGNORE_LIST;
 use crate::REGEX_IDENTIFIERS;
@@ -24,6 +27,7 @@ pub enum WindowManagerEvent {
     Uncloak(WinEvent, Window),
     MoveResizeStart(WinEvent, Window),
     MoveResizeEnd(WinEvent, Window),
+    MaximizeChange(WinEvent, Window),
     MouseCapture(WinEvent, Window),
     Manage(Window),
     Unmanage(Window),
@@ -70,6 +74,9 @@ impl Display for WindowManagerEvent {
             Self::MoveResizeEnd(winevent, window) => {
                 write!(f, "MoveResizeEnd (WinEvent: {winevent}, Window: {window})",)
             }
+            Self::MaximizeChange(winevent, window) => {
+                write!(f, "MaximizeChange (WinEvent: {winevent}, Window: {window})",)
+            }
             Self::MouseCapture(winevent, window) => {
                 write!(f, "MouseCapture (WinEvent: {winevent}, Window: {window})",)
             }
@@ -95,6 +102,7 @@ impl WindowManagerEvent {
             | Self::Uncloak(_, window)
             | Self::MoveResizeStart(_, window)
             | Self::MoveResizeEnd(_, window)
+            | Self::MaximizeChange(_, window)
             | Self::MouseCapture(_, window)
             | Self::Raise(window)
             | Self::Manage(window)
@@ -118,6 +126,7 @@ impl WindowManagerEvent {
             WindowManagerEvent::Uncloak(_, _) => "Uncloak",
             WindowManagerEvent::MoveResizeStart(_, _) => "MoveResizeStart",
             WindowManagerEvent::MoveResizeEnd(_, _) => "MoveResizeEnd",
+            WindowManagerEvent::MaximizeChange(_, _) => "MaximizeChange",
             WindowManagerEvent::MouseCapture(_, _) => "MouseCapture",
             WindowManagerEvent::Manage(_) => "Manage",
             WindowManagerEvent::Unmanage(_) => "Unmanage",
@@ -137,6 +146,7 @@ impl WindowManagerEvent {
             | WindowManagerEvent::Uncloak(event, _)
             | WindowManagerEvent::MoveResizeStart(event, _)
             | WindowManagerEvent::MoveResizeEnd(event, _)
+            | WindowManagerEvent::MaximizeChange(event, _)
             | WindowManagerEvent::MouseCapture(event, _)
             | WindowManagerEvent::TitleUpdate(event, _) => Some(event.to_string()),
             WindowManagerEvent::Manage(_)
@@ -165,6 +175,22 @@ impl WindowManagerEvent {
             }
             WinEvent::SystemMoveSizeStart => Option::from(Self::MoveResizeStart(winevent, window)),
             WinEvent::SystemMoveSizeEnd => Option::from(Self::MoveResizeEnd(winevent, window)),
+            WinEvent::ObjectLocationChange => {
+                // This is sent for every movement of every window, so only windows which have
+                // been maximized into monocle, or have just been maximized, are looked at closely
+                if MAXIMIZE_MONOCLE_RULES.lock().is_empty() {
+                    return None;
+                }
+
+                let is_maximized = WindowsApi::is_zoomed(window.hwnd);
+                let in_monocle = MAXIMIZED_MONOCLE_HWNDS.lock().contains(&window.hwnd);
+
+                if is_maximized != in_monocle && (in_monocle || window.is_maximize_monocle()) {
+                    Option::from(Self::MaximizeChange(winevent, window))
+                } else {
+                    None
+                }
+            }
             WinEvent::SystemCaptureStart | WinEvent::SystemCaptureEnd => {
                 Option::from(Self::MouseCapture(winevent, window))
             }
@@ -49,9 +49,16 @@ use windows::Win32::Graphics::Gdi::MONITORINFOEXW;
 use windows::Win32::Graphics::Gdi::MONITOR_DEFAULTTONEAREST;
 use windows::Win32::System::Com::CoCreateInstance;
 use windows::Win32::System::Com::CLSCTX_ALL;
+use windows::Win32::System::Diagnostics::ToolHelp::CreateToolhelp32Snapshot;
+use windows::Win32::System::Diagnostics::ToolHelp::Process32FirstW;
+use windows::Win32::System::Diagnostics::ToolHelp::Process32NextW;
+use windows::Win32::System::Diagnostics::ToolHelp::PROCESSENTRY32W;
+use windows::Win32::System::Diagnostics::ToolHelp::TH32CS_SNAPPROCESS;
 use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::System::Power::GetSystemPowerStatus;
 use windows::Win32::System::Power::RegisterPowerSettingNotification;
 use windows::Win32::System::Power::HPOWERNOTIFY;
+use windows::Win32::System::Power::SYSTEM_POWER_STATUS;
 use windows::Win32::System::RemoteDesktop::ProcessIdToSessionId;
 use windows::Win32::System::RemoteDesktop::WTSRegisterSessionNotification;
 use windows::Win32::System::Threading::GetCurrentProcessId;
@@ -64,23 +71,36 @@ use windows::Win32::UI::HiDpi::GetDpiForMonitor;
 use windows::Win32::UI::HiDpi::SetProcessDpiAwarenessContext;
 use windows::Win32::UI::HiDpi::DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2;
 use windows::Win32::UI::HiDpi::MDT_EFFECTIVE_DPI;
+use windows::Win32::UI::Input::KeyboardAndMouse::GetAsyncKeyState;
 use windows::Win32::UI::Input::KeyboardAndMouse::GetKeyState;
 use windows::Win32::UI::Input::KeyboardAndMouse::SendInput;
 use windows::Win32::UI::Input::KeyboardAndMouse::INPUT;
 use windows::Win32::UI::Input::KeyboardAndMouse::INPUT_0;
+use windows::Win32::UI::Input::KeyboardAndMouse::INPUT_KEYBOARD;
 use windows::Win32::UI::Input::KeyboardAndMouse::INPUT_MOUSE;
+use windows::Win32::UI::Input::KeyboardAndMouse::KEYBDINPUT;
+use windows::Win32::UI::Input::KeyboardAndMouse::KEYBD_EVENT_FLAGS;
+use windows::Win32::UI::Input::KeyboardAndMouse::KEYEVENTF_KEYUP;
 use windows::Win32::UI::Input::KeyboardAndMouse::MOUSEEVENTF_LEFTDOWN;
 use windows::Win32::UI::Input::KeyboardAndMouse::MOUSEEVENTF_LEFTUP;
 use windows::Win32::UI::Input::KeyboardAndMouse::MOUSEINPUT;
+use windows::Win32::UI::Input::KeyboardAndMouse::MOUSE_EVENT_FLAGS;
+use windows::Win32::UI::Input::KeyboardAndMouse::VIRTUAL_KEY;
 use windows::Win32::UI::Input::KeyboardAndMouse::VK_LBUTTON;
 use windows::Win32::UI::Input::KeyboardAndMouse::VK_MENU;
 use windows::Win32::UI::Shell::DesktopWallpaper;
 use windows::Win32::UI::Shell::IDesktopWallpaper;
+use windows::Win32::UI::Shell::SHAppBarMessage;
+use windows::Win32::UI::Shell::ABM_GETSTATE;
+use windows::Win32::UI::Shell::ABS_AUTOHIDE;
+use windows::Win32::UI::Shell::APPBARDATA;
 use windows::Win32::UI::Shell::DWPOS_FILL;
 use windows::Win32::UI::WindowsAndMessaging::AllowSetForegroundWindow;
 use windows::Win32::UI::WindowsAndMessaging::BringWindowToTop;
 use windows::Win32::UI::WindowsAndMessaging::CreateWindowExW;
 use windows::Win32::UI::WindowsAndMessaging::EnumWindows;
+use windows::Win32::UI::WindowsAndMessaging::FlashWindowEx;
+use windows::Win32::UI::WindowsAndMessaging::GetAncestor;
 use windows::Win32::UI::WindowsAndMessaging::GetCursorPos;
 use windows::Win32::UI::WindowsAndMessaging::GetDesktopWindow;
 use windows::Win32::UI::WindowsAndMessaging::GetForegroundWindow;
@@ -105,18 +125,28 @@ use windows::Win32::UI::WindowsAndMessaging::SetForegroundWindow;
 use windows::Win32::UI::WindowsAndMessaging::SetLayeredWindowAttributes;
 use windows::Win32::UI::WindowsAndMessaging::SetWindowLongPtrW;
 use windows::Win32::UI::WindowsAndMessaging::SetWindowPos;
+use windows::Win32::UI::WindowsAndMessaging::SetWindowsHookExW;
 use windows::Win32::UI::WindowsAndMessaging::ShowWindow;
 use windows::Win32::UI::WindowsAndMessaging::ShowWindowAsync;
 use windows::Win32::UI::WindowsAndMessaging::SystemParametersInfoW;
 use windows::Win32::UI::WindowsAndMessaging::WindowFromPoint;
 use windows::Win32::UI::WindowsAndMessaging::CW_USEDEFAULT;
 use windows::Win32::UI::WindowsAndMessaging::DEV_BROADCAST_DEVICEINTERFACE_W;
+use windows::Win32::UI::WindowsAndMessaging::FLASHWINFO;
+use windows::Win32::UI::WindowsAndMessaging::FLASHW_TIMERNOFG;
+use windows::Win32::UI::WindowsAndMessaging::FLASHW_TRAY;
+use windows::Win32::UI::WindowsAndMessaging::GA_ROOT;
 use windows::Win32::UI::WindowsAndMessaging::GWL_EXSTYLE;
 use windows::Win32::UI::WindowsAndMessaging::GWL_STYLE;
 use windows::Win32::UI::WindowsAndMessaging::GW_HWNDNEXT;
+use windows::Win32::UI::WindowsAndMessaging::GW_OWNER;
 use windows::Win32::UI::WindowsAndMessaging::HDEVNOTIFY;
+use windows::Win32::UI::WindowsAndMessaging::HHOOK;
+use windows::Win32::UI::WindowsAndMessaging::HOOKPROC;
 use windows::Win32::UI::WindowsAndMessaging::HWND_BOTTOM;
+use windows::Win32::UI::WindowsAndMessaging::HWND_NOTOPMOST;
 use windows::Win32::UI::WindowsAndMessaging::HWND_TOP;
+use windows::Win32::UI::WindowsAndMessaging::HWND_TOPMOST;
 use windows::Win32::UI::WindowsAndMessaging::LWA_ALPHA;
 use windows::Win32::UI::WindowsAndMessaging::REGISTER_NOTIFICATION_FLAGS;
 use windows::Win32::UI::WindowsAndMessaging::SET_WINDOW_POS_FLAGS;
@@ -137,6 +167,7 @@ use windows::Win32::UI::WindowsAndMessaging::SW_NORMAL;
 use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNOACTIVATE;
 use windows::Win32::UI::WindowsAndMessaging::SYSTEM_PARAMETERS_INFO_ACTION;
 use windows::Win32::UI::WindowsAndMessaging::SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS;
+use windows::Win32::UI::WindowsAndMessaging::WH_MOUSE_LL;
 use windows::Win32::UI::WindowsAndMessaging::WINDOW_LONG_PTR_INDEX;
 use windows::Win32::UI::WindowsAndMessaging::WM_CLOSE;
 use windows::Win32::UI::WindowsAndMessaging::WNDCLASSW;
@@ -537,6 +568,20 @@ impl WindowsApi {
         unsafe { BringWindowToTop(HWND(as_ptr!(hwnd))) }.process()
     }
 
+    /// Flash the taskbar button of the window until it is activated, marking it as urgent
+    pub fn flash_window(hwnd: isize) {
+        let info = FLASHWINFO {
+            cbSize: size_of::<FLASHWINFO>() as u32,
+            hwnd: HWND(as_ptr!(hwnd)),
+            dwFlags: FLASHW_TRAY | FLASHW_TIMERNOFG,
+            uCount: 0,
+            dwTimeout: 0,
+        };
+
+        // the return value is the previous flash state of the window rather than an error
+        let _ = unsafe { FlashWindowEx(&info) };
+    }
+
     /// Raise the window to the top of the Z order, but do not activate or focus
     /// it. Use raise_and_focus_window to activate and focus a window.
     pub fn raise_window(hwnd: isize) -> Result<()> {
@@ -561,6 +606,26 @@ impl WindowsApi {
         )
     }
 
+    /// Pin the window above all windows which are not topmost, or release it again, without
+    /// moving, resizing or activating it
+    pub fn set_topmost(hwnd: isize, topmost: bool) -> Result<()> {
+        let flags = SetWindowPosition::NO_MOVE
+            | SetWindowPosition::NO_SIZE
+            | SetWindowPosition::NO_ACTIVATE;
+
+        let position = if topmost {
+            HWND_TOPMOST
+        } else {
+            HWND_NOTOPMOST
+        };
+        Self::set_window_pos(
+            HWND(as_ptr!(hwnd)),
+            &Rect::default(),
+            position,
+            flags.bits(),
+        )
+    }
+
     /// Lower the window to the bottom of the Z order, but do not activate or focus
     /// it.
     pub fn lower_window(hwnd: isize) -> Result<()> {
@@ -730,6 +795,10 @@ impl WindowsApi {
         unsafe { GetWindow(HWND(as_ptr!(hwnd)), GW_HWNDNEXT)? }.process()
     }
 
+    pub fn owner_window(hwnd: isize) -> Result<isize> {
+        unsafe { GetWindow(HWND(as_ptr!(hwnd)), GW_OWNER)? }.process()
+    }
+
     pub fn alt_tab_windows() -> Result<Vec<Window>> {
         let mut hwnds = vec![];
         Self::enum_windows(
@@ -740,6 +809,28 @@ impl WindowsApi {
         Ok(hwnds)
     }
 
+    pub fn taskbars() -> Result<Vec<isize>> {
+        let mut hwnds = vec![];
+        Self::enum_windows(
+            Some(windows_callbacks::taskbars),
+            &mut hwnds as *mut Vec<isize> as isize,
+        )?;
+
+        Ok(hwnds)
+    }
+
+    /// Whether the taskbar is set to automatically hide
+    pub fn taskbar_auto_hide() -> bool {
+        let mut data = APPBARDATA {
+            cbSize: size_of::<APPBARDATA>() as u32,
+            ..Default::default()
+        };
+
+        #[allow(clippy::cast_possible_truncation)]
+        let state = unsafe { SHAppBarMessage(ABM_GETSTATE, &mut data) } as u32;
+        state & ABS_AUTOHIDE != 0
+    }
+
     #[allow(dead_code)]
     pub fn top_visible_window() -> Result<isize> {
         let hwnd = Self::top_window()?;
@@ -829,6 +920,10 @@ impl WindowsApi {
         Self::window_from_point(Self::cursor_pos()?)
     }
 
+    pub fn root_window(hwnd: isize) -> Result<isize> {
+        unsafe { GetAncestor(HWND(as_ptr!(hwnd)), GA_ROOT) }.process()
+    }
+
     pub fn center_cursor_in_rect(rect: &Rect) -> Result<()> {
         Self::set_cursor_pos(rect.left + (rect.right / 2), rect.top + (rect.bottom / 2))
     }
@@ -848,6 +943,31 @@ impl WindowsApi {
         (process_id, thread_id)
     }
 
+    pub fn parent_process_id(process_id: u32) -> Result<Option<u32>> {
+        let snapshot = unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) }.process()?;
+
+        let mut entry = PROCESSENTRY32W {
+            dwSize: u32::try_from(size_of::<PROCESSENTRY32W>())?,
+            ..Default::default()
+        };
+
+        let mut parent_process_id = None;
+        let mut found = unsafe { Process32FirstW(snapshot, &mut entry) }.is_ok();
+
+        while found {
+            if entry.th32ProcessID == process_id {
+                parent_process_id = Option::from(entry.th32ParentProcessID);
+                break;
+            }
+
+            found = unsafe { Process32NextW(snapshot, &mut entry) }.is_ok();
+        }
+
+        Self::close_process(snapshot)?;
+
+        Ok(parent_process_id)
+    }
+
     pub fn current_process_id() -> u32 {
         unsafe { GetCurrentProcessId() }
     }
@@ -1059,6 +1179,11 @@ impl WindowsApi {
         Ok(ex_info)
     }
 
+    pub fn monitor_rect(hmonitor: isize) -> Result<Rect> {
+        let ex_info = Self::monitor_info_w(HMONITOR(as_ptr!(hmonitor)))?;
+        Ok(Rect::from(ex_info.monitorInfo.rcMonitor))
+    }
+
     pub fn monitor_device_path(hmonitor: isize) -> Option<String> {
         for display in win32_display_data::connected_displays_all().flatten() {
             if display.hmonitor == hmonitor {
@@ -1343,6 +1468,13 @@ impl WindowsApi {
         unsafe { RegisterPowerSettingNotification(HANDLE::from(HWND(as_ptr!(hwnd))), guid, flags) }
     }
 
+    /// Whether battery saver is turned on, in which case non-essential visual effects should be
+    /// skipped
+    pub fn battery_saver_enabled() -> bool {
+        let mut status = SYSTEM_POWER_STATUS::default();
+        unsafe { GetSystemPowerStatus(&mut status) }.is_ok() && status.SystemStatusFlag == 1
+    }
+
     pub fn register_device_notification(
         hwnd: isize,
         mut filter: DEV_BROADCAST_DEVICEINTERFACE_W,
@@ -1373,6 +1505,19 @@ impl WindowsApi {
         actual != 0
     }
 
+    /// Checks the physical state of the key, which unlike `GetKeyState` is also up to date when
+    /// called from a low-level hook
+    pub fn key_is_pressed(key: VIRTUAL_KEY) -> bool {
+        let state = unsafe { GetAsyncKeyState(i32::from(key.0)) };
+        #[allow(clippy::cast_sign_loss)]
+        let actual = (state as u16) & 0x8000;
+        actual != 0
+    }
+
+    pub fn set_low_level_mouse_hook(hook: HOOKPROC) -> Result<HHOOK> {
+        unsafe { SetWindowsHookExW(WH_MOUSE_LL, hook, None, 0) }.process()
+    }
+
     pub fn left_click() -> u32 {
         let inputs = [
             INPUT {
@@ -1409,6 +1554,51 @@ impl WindowsApi {
         }
     }
 
+    /// Presses and releases `key`
+    pub fn send_key(key: VIRTUAL_KEY) -> u32 {
+        let input = |flags| INPUT {
+            r#type: INPUT_KEYBOARD,
+            Anonymous: INPUT_0 {
+                ki: KEYBDINPUT {
+                    wVk: key,
+                    wScan: 0,
+                    dwFlags: flags,
+                    time: 0,
+                    dwExtraInfo: 0,
+                },
+            },
+        };
+
+        let inputs = [input(KEYBD_EVENT_FLAGS(0)), input(KEYEVENTF_KEYUP)];
+
+        #[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation)]
+        unsafe {
+            SendInput(&inputs, std::mem::size_of::<INPUT>() as i32)
+        }
+    }
+
+    /// Presses or releases a mouse button at the current cursor position
+    pub fn send_mouse_button(flags: MOUSE_EVENT_FLAGS) -> u32 {
+        let inputs = [INPUT {
+            r#type: INPUT_MOUSE,
+            Anonymous: INPUT_0 {
+                mi: MOUSEINPUT {
+                    dx: 0,
+                    dy: 0,
+                    mouseData: 0,
+                    dwFlags: flags,
+                    time: 0,
+                    dwExtraInfo: 0,
+                },
+            },
+        }];
+
+        #[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation)]
+        unsafe {
+            SendInput(&inputs, std::mem::size_of::<INPUT>() as i32)
+        }
+    }
+
     pub fn wts_register_session_notification(hwnd: isize) -> Result<()> {
         unsafe { WTSRegisterSessionNotification(HWND(as_ptr!(hwnd)), 1) }.process()
     }
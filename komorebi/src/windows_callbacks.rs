@@ -2,6 +2,8 @@ use std::collections::VecDeque;
 
 use crate::border_manager;
 use crate::container::Container;
+use crate::monitor_reconciliator::taskbar;
+use crate::mouse_hook;
 use crate::window::RuleDebug;
 use crate::window::Window;
 use crate::window_manager_event::WindowManagerEvent;
@@ -10,12 +12,16 @@ use crate::winevent::WinEvent;
 use crate::winevent_listener;
 use windows::Win32::Foundation::HWND;
 use windows::Win32::Foundation::LPARAM;
+use windows::Win32::Foundation::LRESULT;
 use windows::Win32::Foundation::WPARAM;
 use windows::Win32::UI::Accessibility::HWINEVENTHOOK;
+use windows::Win32::UI::WindowsAndMessaging::CallNextHookEx;
 use windows::Win32::UI::WindowsAndMessaging::GetWindowLongW;
 use windows::Win32::UI::WindowsAndMessaging::SendNotifyMessageW;
 use windows::Win32::UI::WindowsAndMessaging::GWL_EXSTYLE;
 use windows::Win32::UI::WindowsAndMessaging::GWL_STYLE;
+use windows::Win32::UI::WindowsAndMessaging::LLMHF_INJECTED;
+use windows::Win32::UI::WindowsAndMessaging::MSLLHOOKSTRUCT;
 use windows::Win32::UI::WindowsAndMessaging::OBJID_WINDOW;
 use windows::Win32::UI::WindowsAndMessaging::WS_CHILD;
 use windows::Win32::UI::WindowsAndMessaging::WS_EX_NOACTIVATE;
@@ -69,6 +75,18 @@ pub extern "system" fn alt_tab_windows(hwnd: HWND, lparam: LPARAM) -> BOOL {
     true.into()
 }
 
+pub extern "system" fn taskbars(hwnd: HWND, lparam: LPARAM) -> BOOL {
+    let taskbars = unsafe { &mut *(lparam.0 as *mut Vec<isize>) };
+
+    if let Ok(class) = WindowsApi::real_window_class_w(hwnd.0 as isize) {
+        if class == "Shell_TrayWnd" || class == "Shell_SecondaryTrayWnd" {
+            taskbars.push(hwnd.0 as isize);
+        }
+    }
+
+    true.into()
+}
+
 fn has_filtered_style(hwnd: HWND) -> bool {
     let style = unsafe { GetWindowLongW(hwnd, GWL_STYLE) as u32 };
     let ex_style = unsafe { GetWindowLongW(hwnd, GWL_EXSTYLE) as u32 };
@@ -129,6 +147,8 @@ pub extern "system" fn win_event_hook(
         border_manager::send_notification(Some(hwnd.0 as isize));
     }
 
+    taskbar::handle_win_event(winevent, hwnd.0 as isize);
+
     let event_type = match WindowManagerEvent::from_win_event(winevent, window) {
         None => {
             tracing::trace!(
@@ -148,3 +168,20 @@ pub extern "system" fn win_event_hook(
         .send(event_type)
         .expect("could not send message on winevent_listener::event_tx");
 }
+
+pub extern "system" fn low_level_mouse_hook(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    if code >= 0 {
+        let info = unsafe { &*(lparam.0 as *const MSLLHOOKSTRUCT) };
+        #[allow(clippy::cast_possible_truncation)]
+        let message = wparam.0 as u32;
+
+        let injected = info.flags & LLMHF_INJECTED != 0;
+
+        if mouse_hook::handle_mouse_event(message, info.pt, injected) {
+            // a non-zero return value stops the event from reaching the window under the cursor
+            return LRESULT(1);
+        }
+    }
+
+    unsafe { CallNextHookEx(None, code, wparam, lparam) }
+}
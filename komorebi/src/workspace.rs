@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::ffi::OsStr;
 use std::fmt::Display;
@@ -8,16 +9,20 @@ use std::sync::atomic::Ordering;
 
 use crate::border_manager;
 use crate::container::Container;
+use crate::core::manual_layout;
 use crate::core::Axis;
 use crate::core::CustomLayout;
 use crate::core::CycleDirection;
 use crate::core::DefaultLayout;
 use crate::core::Layout;
+use crate::core::ManualLayout;
 use crate::core::OperationDirection;
 use crate::core::Rect;
 use crate::default_layout::LayoutOptions;
+use crate::eviction_manager;
 use crate::lockable_sequence::LockableSequence;
 use crate::ring::Ring;
+use crate::rule_engine;
 use crate::should_act;
 use crate::stackbar_manager;
 use crate::stackbar_manager::STACKBAR_TAB_HEIGHT;
@@ -27,6 +32,7 @@ use crate::window::WindowDetails;
 use crate::windows_api::WindowsApi;
 use crate::FloatingLayerBehaviour;
 use crate::KomorebiTheme;
+use crate::MinimumTileSizeBehaviour;
 use crate::SocketMessage;
 use crate::Wallpaper;
 use crate::WindowContainerBehaviour;
@@ -34,6 +40,9 @@ use crate::DATA_DIR;
 use crate::DEFAULT_CONTAINER_PADDING;
 use crate::DEFAULT_WORKSPACE_PADDING;
 use crate::INITIAL_CONFIGURATION_LOADED;
+use crate::MAXIMIZED_MONOCLE_HWNDS;
+use crate::MINIMUM_TILE_SIZE_BEHAVIOUR;
+use crate::MINIMUM_TILE_SIZE_RULES;
 use crate::NO_TITLEBAR;
 use crate::REGEX_IDENTIFIERS;
 use crate::REMOVE_TITLEBARS;
@@ -44,10 +53,19 @@ use getset::Getters;
 use getset::MutGetters;
 use getset::Setters;
 use komorebi_themes::Base16ColourPalette;
+use nanoid::nanoid;
+use regex::Regex;
 use serde::Deserialize;
 use serde::Serialize;
 use uds_windows::UnixStream;
 
+/// Maximum number of windows remembered in the focus history of a workspace
+const FOCUS_HISTORY_LIMIT: usize = 16;
+
+/// Bounds of the share of the area a BSP split can give to the container it was made for
+const MIN_SPLIT_RATIO: f32 = 0.1;
+const MAX_SPLIT_RATIO: f32 = 0.9;
+
 #[allow(clippy::struct_field_names)]
 #[derive(
     Debug, Clone, Serialize, Deserialize, Getters, CopyGetters, MutGetters, Setters, PartialEq,
@@ -66,10 +84,17 @@ pub struct Workspace {
     pub maximized_window: Option<Window>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[getset(get_copy = "pub", set = "pub")]
+    pub zoomed_window: Option<isize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[getset(get_copy = "pub", set = "pub")]
     pub maximized_window_restore_idx: Option<usize>,
     pub floating_windows: Ring<Window>,
     #[getset(get = "pub", get_mut = "pub", set = "pub")]
     pub layout: Layout,
+    /// Name of the active custom layout, only populated in the window manager state
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[getset(get = "pub")]
+    pub custom_layout_name: Option<String>,
     #[getset(get_copy = "pub", set = "pub")]
     pub layout_options: Option<LayoutOptions>,
     #[getset(get = "pub", get_mut = "pub", set = "pub")]
@@ -84,10 +109,25 @@ pub struct Workspace {
     pub latest_layout: Vec<Rect>,
     #[getset(get = "pub", get_mut = "pub", set = "pub")]
     pub resize_dimensions: Vec<Option<Rect>>,
+    /// The share of the area given to the container at each BSP split, indexed by split
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[getset(get = "pub", get_mut = "pub", set = "pub")]
+    pub split_ratios: Vec<Option<f32>>,
+    /// The tree of splits containers are placed in when using the Manual layout
+    #[serde(default)]
+    #[getset(get = "pub", get_mut = "pub")]
+    pub manual_layout: ManualLayout,
+    /// The hwnds of the windows of each container from before they were stacked as tabs by the
+    /// Tabbed layout, to split them back up when switching to another layout
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[getset(get = "pub")]
+    pub tabbed_groups: Option<Vec<Vec<isize>>>,
     #[getset(get = "pub", set = "pub")]
     pub tile: bool,
     #[getset(get_copy = "pub", set = "pub")]
     pub apply_window_based_work_area_offset: bool,
+    #[getset(get_copy = "pub", set = "pub")]
+    pub smart_gaps: bool,
     #[getset(get = "pub", get_mut = "pub", set = "pub")]
     pub window_container_behaviour: Option<WindowContainerBehaviour>,
     #[getset(get = "pub", get_mut = "pub", set = "pub")]
@@ -106,6 +146,29 @@ pub struct Workspace {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[getset(get = "pub", set = "pub")]
     pub workspace_config: Option<WorkspaceConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[getset(get = "pub", get_mut = "pub")]
+    pub reserved_tiles: Vec<ReservedTile>,
+    #[serde(skip)]
+    #[getset(get = "pub")]
+    pub focus_history: VecDeque<isize>,
+    /// Windows matching a minimize to tray rule which have been hidden from this workspace
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[getset(get = "pub", get_mut = "pub")]
+    pub hidden_windows: Vec<Window>,
+}
+
+/// A tile position held in the layout for an application which hasn't opened its window yet
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ReservedTile {
+    pub id: String,
+    /// The executable of the application the tile is reserved for
+    pub exe: String,
+    /// Position of the tile in the layout, counting both containers and reserved tiles
+    pub idx: usize,
+    /// Area of the placeholder drawn for the tile
+    pub rect: Rect,
 }
 
 #[derive(Debug, Default, Copy, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -135,10 +198,12 @@ impl Default for Workspace {
             containers: Ring::default(),
             monocle_container: None,
             maximized_window: None,
+            zoomed_window: None,
             maximized_window_restore_idx: None,
             monocle_container_restore_idx: None,
             floating_windows: Ring::default(),
             layout: Layout::Default(DefaultLayout::BSP),
+            custom_layout_name: None,
             layout_options: None,
             layout_rules: vec![],
             layout_flip: None,
@@ -146,8 +211,12 @@ impl Default for Workspace {
             container_padding: Option::from(DEFAULT_CONTAINER_PADDING.load(Ordering::SeqCst)),
             latest_layout: vec![],
             resize_dimensions: vec![],
+            split_ratios: vec![],
+            manual_layout: ManualLayout::default(),
+            tabbed_groups: None,
             tile: true,
             apply_window_based_work_area_offset: true,
+            smart_gaps: false,
             window_container_behaviour: None,
             window_container_behaviour_rules: None,
             float_override: None,
@@ -156,6 +225,9 @@ impl Default for Workspace {
             globals: Default::default(),
             workspace_config: None,
             wallpaper: None,
+            reserved_tiles: vec![],
+            focus_history: VecDeque::new(),
+            hidden_windows: vec![],
         }
     }
 }
@@ -264,6 +336,7 @@ impl Workspace {
         }
 
         self.set_float_override(config.float_override);
+        self.set_smart_gaps(config.smart_gaps.unwrap_or_default());
         self.set_layout_flip(config.layout_flip);
         self.set_floating_layer_behaviour(config.floating_layer_behaviour);
         self.set_wallpaper(config.wallpaper.clone());
@@ -435,6 +508,7 @@ impl Workspace {
         }
 
         let idx = self.focused_container_idx();
+        let zoomed_container_idx = self.zoomed_container_idx();
         let mut to_focus = None;
 
         for (i, container) in self.containers_mut().iter_mut().enumerate() {
@@ -444,7 +518,10 @@ impl Workspace {
                 }
             }
 
-            container.restore();
+            // containers underneath a zoomed window stay hidden until it is unzoomed
+            if zoomed_container_idx.is_none_or(|zoomed| zoomed == i) {
+                container.restore();
+            }
         }
 
         if let Some(container) = self.focused_container_mut() {
@@ -485,14 +562,22 @@ impl Workspace {
         // make sure we are never holding on to empty containers
         self.containers_mut().retain(|c| !c.windows().is_empty());
 
-        let container_padding = self
+        let mut container_padding = self
             .container_padding()
             .or(self.globals().container_padding)
             .unwrap_or_default();
-        let workspace_padding = self
+        let mut workspace_padding = self
             .workspace_padding()
             .or(self.globals().workspace_padding)
             .unwrap_or_default();
+
+        if self.smart_gaps
+            && (self.monocle_container().is_some()
+                || self.containers().len() + self.reserved_tiles().len() == 1)
+        {
+            container_padding = 0;
+            workspace_padding = 0;
+        }
         let border_width = self.globals().border_width;
         let border_offset = self.globals().border_offset;
         let work_area = self.globals().work_area;
@@ -561,44 +646,165 @@ impl Workspace {
             self.set_window_container_behaviour(updated_behaviour);
         }
 
+        self.enforce_tabbed_layout();
+
         let managed_maximized_window = self.maximized_window().is_some();
 
         if *self.tile() {
             if let Some(container) = self.monocle_container_mut() {
                 if let Some(window) = container.focused_window_mut() {
-                    adjusted_work_area.add_padding(container_padding);
-                    adjusted_work_area.add_padding(border_offset);
-                    adjusted_work_area.add_padding(border_width);
-                    window.set_position(&adjusted_work_area, true)?;
+                    // A window which was maximized into monocle is left maximized rather than
+                    // fighting it for its position
+                    if !MAXIMIZED_MONOCLE_HWNDS.lock().contains(&window.hwnd) {
+                        adjusted_work_area.add_padding(container_padding);
+                        adjusted_work_area.add_padding(border_offset);
+                        adjusted_work_area.add_padding(border_width);
+                        window.set_position(&adjusted_work_area, true)?;
+                    }
                 };
             } else if let Some(window) = self.maximized_window_mut() {
                 window.maximize();
-            } else if !self.containers().is_empty() {
-                let mut layouts = self.layout().as_boxed_arrangement().calculate(
-                    &adjusted_work_area,
-                    NonZeroUsize::new(self.containers().len()).ok_or_else(|| {
-                        anyhow!(
-                            "there must be at least one container to calculate a workspace layout"
-                        )
-                    })?,
-                    Some(container_padding),
-                    self.layout_flip(),
-                    self.resize_dimensions(),
-                    self.focused_container_idx(),
-                    self.layout_options(),
-                    self.latest_layout(),
-                );
+            } else if !self.containers().is_empty() || !self.reserved_tiles().is_empty() {
+                let reserved_positions = self.reserved_tile_positions();
+
+                // Reserved tiles take part in the layout calculation like any other container
+                let mut resize_dimensions = self.resize_dimensions().clone();
+                for position in &reserved_positions {
+                    resize_dimensions.insert((*position).min(resize_dimensions.len()), None);
+                }
+
+                let mut layouts = if matches!(self.layout(), Layout::Default(DefaultLayout::Manual))
+                {
+                    let mut ids = self
+                        .containers()
+                        .iter()
+                        .map(|container| container.id().clone())
+                        .collect::<Vec<_>>();
+                    for (tile, position) in self.reserved_tiles().iter().zip(&reserved_positions) {
+                        ids.insert((*position).min(ids.len()), tile.id.clone());
+                    }
+
+                    self.manual_layout.sync(&ids);
+                    self.manual_layout
+                        .calculate(&adjusted_work_area, &ids, container_padding)
+                } else {
+                    self.layout().as_boxed_arrangement().calculate(
+                        &adjusted_work_area,
+                        NonZeroUsize::new(self.containers().len() + reserved_positions.len())
+                            .ok_or_else(|| {
+                                anyhow!(
+                                    "there must be at least one container to calculate a workspace layout"
+                                )
+                            })?,
+                        Some(container_padding),
+                        self.layout_flip(),
+                        &resize_dimensions,
+                        self.split_ratios(),
+                        self.layout_idx_for_container_idx(self.focused_container_idx()),
+                        self.layout_options(),
+                        self.latest_layout(),
+                    )
+                };
+
+                for (tile, position) in self.reserved_tiles.iter_mut().zip(reserved_positions).rev()
+                {
+                    if position >= layouts.len() {
+                        continue;
+                    }
+
+                    let mut rect = layouts.remove(position);
+                    rect.add_padding(border_offset);
+                    rect.add_padding(border_width);
+
+                    tile.idx = position;
+                    tile.rect = rect;
+                }
+
+                let minimum_tile_sizes = self.minimum_tile_sizes();
+                if minimum_tile_sizes.iter().any(Option::is_some) {
+                    match MINIMUM_TILE_SIZE_BEHAVIOUR.load() {
+                        MinimumTileSizeBehaviour::Reflow => {
+                            for (idx, minimum) in minimum_tile_sizes.iter().enumerate() {
+                                if let Some((width, height)) = minimum {
+                                    reflow_for_minimum_tile_size(
+                                        &mut layouts,
+                                        idx,
+                                        *width,
+                                        *height,
+                                    );
+                                }
+                            }
+                        }
+                        MinimumTileSizeBehaviour::Float => {
+                            let too_small = minimum_tile_sizes
+                                .iter()
+                                .zip(&layouts)
+                                .enumerate()
+                                .filter_map(|(idx, (minimum, layout))| {
+                                    minimum.and_then(|(width, height)| {
+                                        (layout.right < width.unwrap_or_default()
+                                            || layout.bottom < height.unwrap_or_default())
+                                        .then_some(idx)
+                                    })
+                                })
+                                .collect::<Vec<_>>();
+
+                            if !too_small.is_empty() {
+                                for idx in too_small.into_iter().rev() {
+                                    if let Some(container) = self.remove_container_by_idx(idx) {
+                                        for window in container.windows() {
+                                            let mut window = *window;
+                                            window.center(&adjusted_work_area, true)?;
+                                            self.floating_windows_mut().push_back(window);
+                                        }
+                                    }
+                                }
+
+                                let container_count = self.containers().len();
+                                if self.focused_container_idx() >= container_count {
+                                    self.focus_container(container_count.saturating_sub(1));
+                                }
+
+                                // The remaining containers need to be laid out again now that
+                                // there are fewer of them
+                                return self.update();
+                            }
+                        }
+                    }
+                }
 
                 let should_remove_titlebars = REMOVE_TITLEBARS.load(Ordering::SeqCst);
                 let no_titlebar = NO_TITLEBAR.lock().clone();
                 let regex_identifiers = REGEX_IDENTIFIERS.lock().clone();
 
+                // the zoom ends by itself once the zoomed window is no longer tiled here
+                let zoomed_container_idx = self.zoomed_container_idx();
+                if zoomed_container_idx.is_none() {
+                    self.unzoom();
+                }
+
+                let mut zoomed_area = adjusted_work_area;
+                zoomed_area.add_padding(container_padding);
+
                 let containers = self.containers_mut();
 
                 for (i, container) in containers.iter_mut().enumerate() {
+                    if zoomed_container_idx.is_some_and(|zoomed| zoomed != i) {
+                        container.hide(None);
+                        continue;
+                    }
+
                     let window_count = container.windows().len();
 
                     if let Some(layout) = layouts.get_mut(i) {
+                        // a zoomed container covers the work area while the calculated layout
+                        // underneath it is kept for when it is unzoomed
+                        let layout = if zoomed_container_idx == Some(i) {
+                            &mut zoomed_area
+                        } else {
+                            layout
+                        };
+
                         layout.add_padding(border_offset);
                         layout.add_padding(border_width);
 
@@ -637,7 +843,12 @@ impl Workspace {
                                     WindowsApi::restore_window(window.hwnd);
                                 }
                             }
+                            if let Ok(rect) = WindowsApi::window_rect(window.hwnd) {
+                                eviction_manager::check_placement(window.hwnd, &rect);
+                            }
+
                             window.set_position(layout, false)?;
+                            eviction_manager::record_placement(window.hwnd);
                         }
                     }
                 }
@@ -664,6 +875,124 @@ impl Workspace {
         Ok(())
     }
 
+    /// The minimum tile width and height required by the focused window of each container, if
+    /// any of the configured minimum tile size rules match it
+    #[allow(clippy::type_complexity)]
+    fn minimum_tile_sizes(&self) -> Vec<Option<(Option<i32>, Option<i32>)>> {
+        if MINIMUM_TILE_SIZE_RULES.lock().is_empty() {
+            return vec![];
+        }
+
+        self.containers()
+            .iter()
+            .map(|container| container.focused_window()?.minimum_tile_size())
+            .collect()
+    }
+
+    /// The positions of the reserved tiles in the layout, adjusted so that they are unique and
+    /// don't go past the number of tiles in the layout
+    fn reserved_tile_positions(&self) -> Vec<usize> {
+        let reserved_count = self.reserved_tiles().len();
+        let tile_count = self.containers().len() + reserved_count;
+
+        let mut positions = vec![];
+        let mut next = 0;
+
+        for (i, tile) in self.reserved_tiles().iter().enumerate() {
+            let position = tile.idx.max(next).min(tile_count - (reserved_count - i));
+            positions.push(position);
+            next = position + 1;
+        }
+
+        positions
+    }
+
+    fn layout_idx_for_container_idx(&self, container_idx: usize) -> usize {
+        let mut idx = container_idx;
+        for position in self.reserved_tile_positions() {
+            if position <= idx {
+                idx += 1;
+            }
+        }
+
+        idx
+    }
+
+    /// The index of the container at `layout_idx`, or `None` if a reserved tile is at that
+    /// position in the layout
+    fn container_idx_for_layout_idx(&self, layout_idx: usize) -> Option<usize> {
+        let reserved_positions = self.reserved_tile_positions();
+        if reserved_positions.contains(&layout_idx) {
+            return None;
+        }
+
+        Option::from(
+            layout_idx
+                - reserved_positions
+                    .iter()
+                    .filter(|position| **position < layout_idx)
+                    .count(),
+        )
+    }
+
+    /// Reserves a tile for windows of `exe`, either at the position of the focused container or
+    /// at the end of the layout
+    pub fn reserve_tile(&mut self, exe: &str, here: bool) {
+        let idx = if here && !self.containers().is_empty() {
+            self.layout_idx_for_container_idx(self.focused_container_idx())
+        } else {
+            self.containers().len() + self.reserved_tiles().len()
+        };
+
+        for tile in self.reserved_tiles_mut() {
+            if tile.idx >= idx {
+                tile.idx += 1;
+            }
+        }
+
+        let position = self.reserved_tiles().partition_point(|tile| tile.idx < idx);
+        self.reserved_tiles_mut().insert(
+            position,
+            ReservedTile {
+                id: nanoid!(),
+                exe: exe.to_string(),
+                idx,
+                rect: Rect::default(),
+            },
+        );
+    }
+
+    /// Places `window` in a new container at the position of the first tile reserved for its
+    /// application, returning `true` if there was such a tile
+    pub fn fill_reserved_tile(&mut self, window: Window) -> bool {
+        let Ok(exe) = window.exe() else {
+            return false;
+        };
+
+        let Some(position) = self
+            .reserved_tiles()
+            .iter()
+            .position(|tile| tile.exe.eq_ignore_ascii_case(&exe))
+        else {
+            return false;
+        };
+
+        let tile = self.reserved_tiles_mut().remove(position);
+
+        // Every reserved tile before this one occupies a layout position without a container
+        let container_idx = tile
+            .idx
+            .saturating_sub(position)
+            .min(self.containers().len());
+
+        let mut container = Container::default();
+        container.add_window(window);
+
+        self.insert_container_at_idx(container_idx, container);
+
+        true
+    }
+
     pub fn container_for_window(&self, hwnd: isize) -> Option<&Container> {
         self.containers().get(self.container_idx_for_window(hwnd)?)
     }
@@ -824,6 +1153,32 @@ impl Workspace {
         Ok(false)
     }
 
+    /// Returns an empty workspace with all of the configuration of this workspace, but none of
+    /// its windows or the state derived from them. Reserved tiles and the static configuration
+    /// identify the original workspace, so they are not carried over either
+    pub fn clone_without_windows(&self) -> Self {
+        Self {
+            name: None,
+            workspace_config: None,
+            reserved_tiles: vec![],
+            containers: Ring::default(),
+            monocle_container: None,
+            monocle_container_restore_idx: None,
+            maximized_window: None,
+            zoomed_window: None,
+            maximized_window_restore_idx: None,
+            floating_windows: Ring::default(),
+            latest_layout: vec![],
+            resize_dimensions: vec![],
+            manual_layout: ManualLayout::default(),
+            tabbed_groups: None,
+            layer: Default::default(),
+            focus_history: VecDeque::new(),
+            hidden_windows: vec![],
+            ..self.clone()
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         self.containers().is_empty()
             && self.maximized_window().is_none()
@@ -831,6 +1186,55 @@ impl Workspace {
             && self.floating_windows().is_empty()
     }
 
+    /// The index of the container holding the zoomed window, if there is one
+    pub fn zoomed_container_idx(&self) -> Option<usize> {
+        self.zoomed_window()
+            .and_then(|hwnd| self.container_idx_for_window(hwnd))
+    }
+
+    /// Zooms the focused window to cover the work area of the workspace, or unzooms the currently
+    /// zoomed window, restoring the layout underneath it
+    pub fn toggle_zoom(&mut self) -> Result<()> {
+        if self.zoomed_window().is_some() {
+            self.unzoom();
+        } else {
+            let hwnd = self
+                .focused_container()
+                .and_then(Container::focused_window)
+                .map(|window| window.hwnd)
+                .ok_or_else(|| anyhow!("there is no tiled window to zoom"))?;
+
+            self.set_zoomed_window(Option::from(hwnd));
+        }
+
+        Ok(())
+    }
+
+    /// Clears the zoomed window, if there is one, and restores the focused window of every
+    /// container which was hidden underneath it
+    pub fn unzoom(&mut self) {
+        if self.zoomed_window.take().is_some() {
+            for container in self.containers() {
+                container.restore();
+            }
+        }
+    }
+
+    /// Records a window as the most recently focused window on this workspace
+    pub fn record_focus(&mut self, hwnd: isize) {
+        self.focus_history.retain(|h| *h != hwnd);
+        self.focus_history.push_front(hwnd);
+        self.focus_history.truncate(FOCUS_HISTORY_LIMIT);
+    }
+
+    /// Returns the most recently focused window other than `current` which is still on this workspace
+    pub fn last_focused_window(&self, current: isize) -> Option<isize> {
+        self.focus_history
+            .iter()
+            .copied()
+            .find(|hwnd| *hwnd != current && self.contains_window(*hwnd))
+    }
+
     pub fn contains_window(&self, hwnd: isize) -> bool {
         for container in self.containers() {
             if container.contains_window(hwnd) {
@@ -1004,6 +1408,28 @@ impl Workspace {
         Ok(())
     }
 
+    /// Replaces the tiled window with `hwnd` with `window`, keeping the same position in its
+    /// container, and returns the window that was replaced.
+    pub fn replace_window(&mut self, hwnd: isize, window: Window) -> Result<Window> {
+        border_manager::delete_border(hwnd);
+
+        if let Some(container) = self.monocle_container_mut() {
+            if let Some(replaced) = container.replace_window(hwnd, window) {
+                return Ok(replaced);
+            }
+        }
+
+        let container_idx = self
+            .container_idx_for_window(hwnd)
+            .ok_or_else(|| anyhow!("there is no window"))?;
+
+        self.containers_mut()
+            .get_mut(container_idx)
+            .ok_or_else(|| anyhow!("there is no container"))?
+            .replace_window(hwnd, window)
+            .ok_or_else(|| anyhow!("there is no window"))
+    }
+
     pub fn remove_focused_container(&mut self) -> Option<Container> {
         let focused_idx = self.focused_container_idx();
         let container = self.remove_container_by_idx(focused_idx);
@@ -1022,11 +1448,29 @@ impl Workspace {
     pub fn new_idx_for_direction(&self, direction: OperationDirection) -> Option<usize> {
         let len = NonZeroUsize::new(self.containers().len())?;
 
+        if matches!(self.layout(), Layout::Default(DefaultLayout::Manual)) {
+            // Reserved tiles take up space in the layout, so neighbours are looked up among the
+            // positions of both containers and reserved tiles
+            let mut layouts = self.latest_layout().clone();
+            for tile in self.reserved_tiles() {
+                layouts.insert(tile.idx.min(layouts.len()), tile.rect);
+            }
+
+            let idx = manual_layout::neighbour_in_direction(
+                &layouts,
+                self.layout_idx_for_container_idx(self.focused_container_idx()),
+                direction,
+            )?;
+
+            return self.container_idx_for_layout_idx(idx);
+        }
+
         direction.destination(
             self.layout().as_boxed_direction().as_ref(),
             self.layout_flip(),
             self.focused_container_idx(),
             len,
+            self.layout_options(),
         )
     }
 
@@ -1201,6 +1645,142 @@ impl Workspace {
         }
     }
 
+    /// Stacks every window into a single container while the Tabbed layout is in use, recording
+    /// how they were grouped beforehand so that switching to another layout can restore them
+    fn enforce_tabbed_layout(&mut self) {
+        if !matches!(self.layout(), Layout::Default(DefaultLayout::Tabbed)) {
+            if let Some(groups) = self.tabbed_groups.take() {
+                self.restore_tabbed_groups(&groups);
+            }
+
+            return;
+        }
+
+        if self.containers().len() < 2 || self.monocle_container().is_some() {
+            return;
+        }
+
+        if self.tabbed_groups.is_none() {
+            self.tabbed_groups = Option::from(
+                self.containers()
+                    .iter()
+                    .map(|container| container.windows().iter().map(|w| w.hwnd).collect())
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        let focused_hwnd = self
+            .focused_container()
+            .and_then(Container::focused_window)
+            .map(|window| window.hwnd);
+
+        let mut windows = vec![];
+        while self.containers().len() > 1 {
+            match self.remove_container_by_idx(1) {
+                Some(container) => windows.extend(container.windows().iter().copied()),
+                None => break,
+            }
+        }
+
+        self.focus_container(0);
+        if let Some(tabs) = self.focused_container_mut() {
+            for window in windows {
+                tabs.add_window(window);
+            }
+
+            if let Some(idx) = focused_hwnd.and_then(|hwnd| tabs.idx_for_window(hwnd)) {
+                tabs.focus_window(idx);
+            }
+
+            tabs.load_focused_window();
+        }
+    }
+
+    /// Splits the tabs of the Tabbed layout back up into the containers in `groups`, with any
+    /// windows which were added since then getting a container of their own
+    fn restore_tabbed_groups(&mut self, groups: &[Vec<isize>]) {
+        // the tabs have been rearranged by hand since, so there is nothing left to restore
+        if self.containers().len() != 1 {
+            return;
+        }
+
+        let Some(tabs) = self.remove_container_by_idx(0) else {
+            return;
+        };
+
+        let focused_hwnd = tabs.focused_window().map(|window| window.hwnd);
+        let mut remaining = tabs.windows().iter().copied().collect::<Vec<_>>();
+
+        let mut containers = vec![];
+        for group in groups {
+            let mut container = Container::default();
+            for hwnd in group {
+                if let Some(idx) = remaining.iter().position(|window| window.hwnd == *hwnd) {
+                    container.add_window(remaining.remove(idx));
+                }
+            }
+
+            if !container.windows().is_empty() {
+                containers.push(container);
+            }
+        }
+
+        for window in remaining {
+            let mut container = Container::default();
+            container.add_window(window);
+            containers.push(container);
+        }
+
+        for mut container in containers {
+            if let Some(idx) = focused_hwnd.and_then(|hwnd| container.idx_for_window(hwnd)) {
+                container.focus_window(idx);
+            }
+
+            container.load_focused_window();
+            self.insert_container_at_idx(self.containers().len(), container);
+        }
+
+        match focused_hwnd.and_then(|hwnd| self.container_idx_for_window(hwnd)) {
+            Some(idx) => self.focus_container(idx),
+            None => self.focus_container(0),
+        }
+    }
+
+    /// Moves the edge of the focused container in `direction` by `delta` percent of the area of
+    /// the BSP split which that edge belongs to, leaving every other split untouched
+    pub fn resize_split(&mut self, direction: OperationDirection, delta: i32) -> Result<()> {
+        if !matches!(self.layout(), Layout::Default(DefaultLayout::BSP)) {
+            return Err(anyhow!(
+                "split ratios can only be adjusted in the BSP layout"
+            ));
+        }
+
+        let len = self.containers().len() + self.reserved_tiles().len();
+        let focused_idx = self.layout_idx_for_container_idx(self.focused_container_idx());
+
+        let split_idx = bsp_split_in_direction(focused_idx, len, self.layout_flip(), direction)
+            .ok_or_else(|| anyhow!("there is no split {direction} of the focused container"))?;
+
+        // growing the share of a split shrinks the remaining area, which is where the focused
+        // container is unless the split was made for it
+        let delta = delta as f32 / 100.0;
+        let delta = if split_idx == focused_idx {
+            delta
+        } else {
+            -delta
+        };
+
+        let split_ratios = self.split_ratios_mut();
+        if split_ratios.len() <= split_idx {
+            split_ratios.resize(split_idx + 1, None);
+        }
+
+        let ratio = split_ratios[split_idx].unwrap_or(0.5) + delta;
+        split_ratios[split_idx] = Option::from(ratio.clamp(MIN_SPLIT_RATIO, MAX_SPLIT_RATIO));
+
+        Ok(())
+    }
+
     fn enforce_resize_constraints_for_bsp(&mut self) {
         for (i, rect) in self.resize_dimensions_mut().iter_mut().enumerate() {
             if let Some(rect) = rect {
@@ -1626,7 +2206,21 @@ impl Workspace {
     }
 
     pub fn swap_containers(&mut self, i: usize, j: usize) {
+        let ids = (
+            self.containers().get(i).map(|c| c.id().clone()),
+            self.containers().get(j).map(|c| c.id().clone()),
+        );
+
         self.containers.elements_mut().swap_respecting_locks(i, j);
+
+        // containers keep their place in the tree of the Manual layout wherever they are in the
+        // list, so they have to be swapped there too
+        if let (Some(a), Some(b)) = ids {
+            if self.containers().get(i).is_some_and(|c| *c.id() == b) {
+                self.manual_layout.swap(&a, &b);
+            }
+        }
+
         self.focus_container(j);
     }
 
@@ -1652,6 +2246,34 @@ impl Workspace {
         }
     }
 
+    /// Returns true if directional focus should never leave this workspace's monitor
+    pub fn contains_focus(&self) -> bool {
+        self.workspace_config
+            .as_ref()
+            .and_then(|config| config.contain_focus)
+            .unwrap_or_default()
+    }
+
+    /// Returns true if a window with the given properties is matched by the applications which
+    /// are blocked from opening on this workspace
+    pub fn blocks_window(
+        &self,
+        title: &str,
+        exe_name: &str,
+        class: &str,
+        path: &str,
+        regex_identifiers: &HashMap<String, Regex>,
+    ) -> bool {
+        self.workspace_config
+            .as_ref()
+            .and_then(|config| config.blocked_applications.as_ref())
+            .is_some_and(|rules| {
+                rules.iter().any(|rule| {
+                    rule_engine::matches(rule, title, exe_name, class, path, regex_identifiers)
+                })
+            })
+    }
+
     pub fn visible_windows(&self) -> Vec<Option<&Window>> {
         let mut vec = vec![];
 
@@ -1720,6 +2342,136 @@ impl Workspace {
     }
 }
 
+/// Grows the tile at `idx` to the given minimum width and height by taking space from the tiles
+/// directly next to it, if they can spare it without being shrunk to less than half their size
+fn reflow_for_minimum_tile_size(
+    layouts: &mut [Rect],
+    idx: usize,
+    minimum_width: Option<i32>,
+    minimum_height: Option<i32>,
+) {
+    if let Some(minimum_width) = minimum_width {
+        reflow_horizontally(layouts, idx, minimum_width);
+    }
+
+    if let Some(minimum_height) = minimum_height {
+        // Reflowing vertically is the same as reflowing horizontally with the axes swapped
+        let transpose = |rect: &mut Rect| {
+            std::mem::swap(&mut rect.left, &mut rect.top);
+            std::mem::swap(&mut rect.right, &mut rect.bottom);
+        };
+
+        layouts.iter_mut().for_each(transpose);
+        reflow_horizontally(layouts, idx, minimum_height);
+        layouts.iter_mut().for_each(transpose);
+    }
+}
+
+fn reflow_horizontally(layouts: &mut [Rect], idx: usize, minimum_width: i32) {
+    let Some(target) = layouts.get(idx).copied() else {
+        return;
+    };
+
+    let deficit = minimum_width - target.right;
+    if deficit <= 0 {
+        return;
+    }
+
+    let overlaps_vertically =
+        |rect: &Rect| rect.top < target.top + target.bottom && target.top < rect.top + rect.bottom;
+
+    let right_side = layouts
+        .iter()
+        .enumerate()
+        .filter(|(i, rect)| {
+            *i != idx && overlaps_vertically(rect) && rect.left >= target.left + target.right
+        })
+        .map(|(i, rect)| (i, rect.left))
+        .collect::<Vec<_>>();
+
+    let left_side = layouts
+        .iter()
+        .enumerate()
+        .filter(|(i, rect)| {
+            *i != idx && overlaps_vertically(rect) && rect.left + rect.right <= target.left
+        })
+        .map(|(i, rect)| (i, rect.left + rect.right))
+        .collect::<Vec<_>>();
+
+    // Only the tiles directly adjacent to the target give up space
+    let nearest = |side: &[(usize, i32)], closest: Option<i32>| {
+        side.iter()
+            .filter(|(_, edge)| Some(*edge) == closest)
+            .map(|(i, _)| *i)
+            .collect::<Vec<_>>()
+    };
+
+    let right_neighbours = nearest(&right_side, right_side.iter().map(|(_, e)| *e).min());
+    let left_neighbours = nearest(&left_side, left_side.iter().map(|(_, e)| *e).max());
+
+    let can_shrink = |neighbours: &[usize]| {
+        !neighbours.is_empty()
+            && neighbours
+                .iter()
+                .all(|i| layouts[*i].right - deficit >= layouts[*i].right / 2)
+    };
+
+    if can_shrink(&right_neighbours) {
+        layouts[idx].right += deficit;
+        for i in right_neighbours {
+            layouts[i].left += deficit;
+            layouts[i].right -= deficit;
+        }
+    } else if can_shrink(&left_neighbours) {
+        layouts[idx].left -= deficit;
+        layouts[idx].right += deficit;
+        for i in left_neighbours {
+            layouts[i].right -= deficit;
+        }
+    }
+}
+
+/// Returns the index of the innermost BSP split which borders the container at `idx` in
+/// `direction`, if any
+fn bsp_split_in_direction(
+    idx: usize,
+    len: usize,
+    layout_flip: Option<Axis>,
+    direction: OperationDirection,
+) -> Option<usize> {
+    // the last container is never split
+    let last_split = len.checked_sub(2)?;
+
+    (0..=idx.min(last_split)).rev().find(|split_idx| {
+        // the edge of the container a split was made for which borders the remaining area
+        let main_edge = if split_idx % 2 == 0 {
+            match layout_flip {
+                Some(Axis::Horizontal | Axis::HorizontalAndVertical) => OperationDirection::Left,
+                _ => OperationDirection::Right,
+            }
+        } else {
+            match layout_flip {
+                Some(Axis::Vertical | Axis::HorizontalAndVertical) => OperationDirection::Up,
+                _ => OperationDirection::Down,
+            }
+        };
+
+        let edge = if *split_idx == idx {
+            main_edge
+        } else {
+            main_edge.opposite()
+        };
+
+        matches!(
+            (edge, direction),
+            (OperationDirection::Left, OperationDirection::Left)
+                | (OperationDirection::Right, OperationDirection::Right)
+                | (OperationDirection::Up, OperationDirection::Up)
+                | (OperationDirection::Down, OperationDirection::Down)
+        )
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1771,6 +2523,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_resize_split_only_adjusts_split_of_focused_container() {
+        let mut ws = Workspace::default();
+
+        for _ in 0..3 {
+            ws.add_container_to_back(Container::default());
+        }
+
+        // the second container is bordered by its own split below and the first split to its left
+        ws.focus_container(1);
+
+        ws.resize_split(OperationDirection::Down, 10).unwrap();
+        assert_eq!(ws.split_ratios(), &vec![None, Some(0.6)]);
+
+        ws.resize_split(OperationDirection::Left, 25).unwrap();
+        assert_eq!(ws.split_ratios(), &vec![Some(0.25), Some(0.6)]);
+
+        // nothing borders the second container to the right
+        assert!(ws.resize_split(OperationDirection::Right, 10).is_err());
+
+        // ratios are clamped so that no container can be resized away entirely
+        ws.resize_split(OperationDirection::Down, 100).unwrap();
+        assert_eq!(ws.split_ratios()[1], Some(MAX_SPLIT_RATIO));
+    }
+
     #[test]
     fn test_locked_containers_remove_window() {
         let mut ws = Workspace::default();
@@ -1875,6 +2652,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_clone_without_windows() {
+        let mut workspace = Workspace {
+            name: Some(String::from("tuned")),
+            layout: Layout::Default(DefaultLayout::VerticalStack),
+            layout_rules: vec![(3, Layout::Default(DefaultLayout::Grid))],
+            workspace_padding: Some(20),
+            container_padding: Some(5),
+            smart_gaps: true,
+            reserved_tiles: vec![ReservedTile {
+                id: String::from("tile"),
+                exe: String::from("notepad.exe"),
+                idx: 0,
+                rect: Rect::default(),
+            }],
+            ..Default::default()
+        };
+
+        let mut container = Container::default();
+        container.windows_mut().push_back(Window::from(1));
+        workspace.add_container_to_back(container);
+        workspace.floating_windows_mut().push_back(Window::from(2));
+
+        let clone = workspace.clone_without_windows();
+
+        // Layout, padding, gaps and rules are copied
+        assert_eq!(clone.layout, workspace.layout);
+        assert_eq!(clone.layout_rules, workspace.layout_rules);
+        assert_eq!(clone.workspace_padding, Some(20));
+        assert_eq!(clone.container_padding, Some(5));
+        assert!(clone.smart_gaps);
+
+        // Name, windows and reserved tiles are not
+        assert!(clone.name.is_none());
+        assert!(clone.is_empty());
+        assert!(clone.reserved_tiles.is_empty());
+    }
+
     #[test]
     fn test_contains_window() {
         // Create default workspace
@@ -1894,6 +2709,33 @@ mod tests {
         assert!(!workspace.is_empty())
     }
 
+    #[test]
+    fn test_last_focused_window() {
+        let mut workspace = Workspace::default();
+
+        for i in 0..3 {
+            let mut container = Container::default();
+            container.windows_mut().push_back(Window::from(i));
+            workspace.add_container_to_back(container);
+        }
+
+        // Nothing to jump back to without a history
+        assert_eq!(workspace.last_focused_window(0), None);
+
+        workspace.record_focus(0);
+        workspace.record_focus(1);
+        workspace.record_focus(2);
+
+        // Should jump back and forth between the two most recently focused windows
+        assert_eq!(workspace.last_focused_window(2), Some(1));
+        workspace.record_focus(1);
+        assert_eq!(workspace.last_focused_window(1), Some(2));
+
+        // Windows which are no longer on the workspace are skipped
+        workspace.remove_container_by_idx(2);
+        assert_eq!(workspace.last_focused_window(1), Some(0));
+    }
+
     #[test]
     fn test_add_container_to_back() {
         let mut workspace = Workspace::default();
@@ -2518,4 +3360,89 @@ mod tests {
             assert_eq!(visible_windows[2].unwrap().hwnd, 300);
         }
     }
+
+    #[test]
+    fn test_reserve_tile() {
+        let mut workspace = Workspace::default();
+
+        // Add 3 containers with 1 window each
+        for i in 0..3 {
+            let mut container = Container::default();
+            container.windows_mut().push_back(Window::from(i));
+            workspace.add_container_to_back(container);
+        }
+
+        // Reserving without --here places the tile at the end of the layout
+        workspace.reserve_tile("spotify.exe", false);
+        assert_eq!(workspace.reserved_tiles()[0].idx, 3);
+
+        // Reserving with --here places the tile at the position of the focused container
+        workspace.focus_container(1);
+        workspace.reserve_tile("discord.exe", true);
+
+        let reserved = workspace
+            .reserved_tiles()
+            .iter()
+            .map(|tile| (tile.exe.as_str(), tile.idx))
+            .collect::<Vec<_>>();
+
+        assert_eq!(reserved, vec![("discord.exe", 1), ("spotify.exe", 4)]);
+        assert_eq!(workspace.reserved_tile_positions(), vec![1, 4]);
+
+        // The focused container moves over by one in the layout to make room for the tile
+        assert_eq!(workspace.layout_idx_for_container_idx(0), 0);
+        assert_eq!(workspace.layout_idx_for_container_idx(1), 2);
+        assert_eq!(workspace.layout_idx_for_container_idx(2), 3);
+        assert_eq!(workspace.container_idx_for_layout_idx(1), None);
+        assert_eq!(workspace.container_idx_for_layout_idx(2), Some(1));
+        assert_eq!(workspace.container_idx_for_layout_idx(3), Some(2));
+
+        // Positions never go past the end of the layout
+        workspace.remove_container_by_idx(2);
+        assert_eq!(workspace.reserved_tile_positions(), vec![1, 3]);
+    }
+
+    #[test]
+    fn test_reflow_for_minimum_tile_size() {
+        let rect = |left, top, right, bottom| Rect {
+            left,
+            top,
+            right,
+            bottom,
+        };
+
+        // Three columns, the middle one of which is too narrow
+        let mut layouts = vec![
+            rect(0, 0, 800, 1000),
+            rect(800, 0, 400, 1000),
+            rect(1200, 0, 400, 1000),
+        ];
+
+        reflow_for_minimum_tile_size(&mut layouts, 1, Some(600), None);
+
+        // Only the directly adjacent column on the right gives up space
+        assert_eq!(layouts[0], rect(0, 0, 800, 1000));
+        assert_eq!(layouts[1], rect(800, 0, 600, 1000));
+        assert_eq!(layouts[2], rect(1400, 0, 200, 1000));
+
+        // The right column can't give up any more space, so the left column does instead
+        reflow_for_minimum_tile_size(&mut layouts, 2, Some(300), None);
+
+        assert_eq!(layouts[1], rect(800, 0, 500, 1000));
+        assert_eq!(layouts[2], rect(1300, 0, 300, 1000));
+
+        // Two rows, the bottom one of which is too short
+        let mut layouts = vec![rect(0, 0, 1000, 800), rect(0, 800, 1000, 200)];
+
+        reflow_for_minimum_tile_size(&mut layouts, 1, None, Some(400));
+
+        assert_eq!(layouts[0], rect(0, 0, 1000, 600));
+        assert_eq!(layouts[1], rect(0, 600, 1000, 400));
+
+        // Neighbours are never shrunk to less than half of their size
+        reflow_for_minimum_tile_size(&mut layouts, 1, None, Some(800));
+
+        assert_eq!(layouts[0], rect(0, 0, 1000, 600));
+        assert_eq!(layouts[1], rect(0, 600, 1000, 400));
+    }
 }
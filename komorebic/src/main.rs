@@ -1,6 +1,8 @@
 #![warn(clippy::all)]
 #![allow(clippy::missing_errors_doc, clippy::doc_markdown)]
 
+use chrono::DateTime;
+use chrono::Local;
 use chrono::Utc;
 use komorebi_client::replace_env_in_path;
 use komorebi_client::PathExt;
@@ -24,7 +26,6 @@ use color_eyre::eyre::bail;
 use color_eyre::Result;
 use dirs::data_local_dir;
 use fs_tail::TailedFile;
-use komorebi_client::send_message;
 use komorebi_client::send_query;
 use komorebi_client::AppSpecificConfigurationPath;
 use komorebi_client::ApplicationSpecificConfiguration;
@@ -55,12 +56,17 @@ use komorebi_client::OperationDirection;
 use komorebi_client::Rect;
 use komorebi_client::Sizing;
 use komorebi_client::SocketMessage;
+use komorebi_client::SplitDirection;
+use komorebi_client::State;
 use komorebi_client::StateQuery;
 use komorebi_client::StaticConfig;
+use komorebi_client::SubscribeOptions;
+use komorebi_client::SubscriptionEvent;
 use komorebi_client::WindowKind;
 
 lazy_static! {
     static ref HAS_CUSTOM_CONFIG_HOME: AtomicBool = AtomicBool::new(false);
+    static ref NO_MOUSE_FOLLOWS_FOCUS: AtomicBool = AtomicBool::new(false);
     static ref HOME_DIR: PathBuf = {
         std::env::var("KOMOREBI_CONFIG_HOME").map_or_else(
             |_| dirs::home_dir().expect("there is no home directory"),
@@ -104,6 +110,8 @@ lazy_static! {
 
 shadow_rs::shadow!(build);
 
+mod tui;
+
 #[derive(thiserror::Error, Debug, miette::Diagnostic)]
 #[error("{message}")]
 #[diagnostic(code(komorebi::configuration), help("try fixing this syntax error"))]
@@ -117,7 +125,9 @@ struct ConfigurationError {
 
 #[derive(Copy, Clone, ValueEnum)]
 enum BooleanState {
+    #[value(alias = "on")]
     Enable,
+    #[value(alias = "off")]
     Disable,
 }
 
@@ -166,6 +176,7 @@ gen_enum_subcommand_args! {
     CycleLayout: CycleDirection,
     WatchConfiguration: BooleanState,
     MouseFollowsFocus: BooleanState,
+    PrivacyMode: BooleanState,
     Query: StateQuery,
     WindowHidingBehaviour: HidingBehaviour,
     CrossMonitorMoveBehaviour: MoveBehaviour,
@@ -199,6 +210,13 @@ gen_target_subcommand_args! {
     FocusStackWindow,
 }
 
+#[derive(Parser)]
+struct CloneWorkspace {
+    /// Target monitor index (zero-indexed)
+    #[clap(long)]
+    to: usize,
+}
+
 macro_rules! gen_named_target_subcommand_args {
     // SubCommand Pattern
     ( $( $name:ident ),+ $(,)? ) => {
@@ -385,6 +403,30 @@ struct ResizeAxis {
     sizing: Sizing,
 }
 
+#[derive(Parser)]
+struct ResizeAxisPercent {
+    #[clap(value_enum)]
+    axis: Axis,
+    /// Percentage of the work area to resize by, negative values decrease the size
+    #[clap(allow_negative_numbers = true)]
+    percent: i32,
+}
+
+#[derive(Parser)]
+struct Split {
+    #[clap(value_enum)]
+    direction: SplitDirection,
+}
+
+#[derive(Parser)]
+struct ResizeSplit {
+    #[clap(value_enum)]
+    direction: OperationDirection,
+    /// Percentage of the split to move the edge by, negative values move it back
+    #[clap(allow_negative_numbers = true)]
+    delta: i32,
+}
+
 #[derive(Parser)]
 struct ResizeDelta {
     /// The delta of pixels by which to increase or decrease window dimensions when resizing
@@ -467,6 +509,19 @@ struct EnsureNamedWorkspaces {
     names: Vec<String>,
 }
 
+#[derive(Parser)]
+struct Retile {
+    /// Only retile the focused workspace of this monitor (zero-indexed)
+    #[clap(long)]
+    monitor: Option<usize>,
+    /// Only retile this workspace (zero-indexed) on the given monitor
+    #[clap(long, requires = "monitor")]
+    workspace: Option<usize>,
+    /// Only retile the workspace with this name
+    #[clap(long, conflicts_with_all = ["monitor", "workspace"])]
+    named_workspace: Option<String>,
+}
+
 #[derive(Parser)]
 struct FocusMonitorWorkspace {
     /// Target monitor index (zero-indexed)
@@ -475,6 +530,16 @@ struct FocusMonitorWorkspace {
     target_workspace: usize,
 }
 
+#[derive(Parser)]
+struct SwapWorkspaces {
+    /// Monitor index (zero-indexed)
+    monitor: usize,
+    /// First workspace index on the monitor (zero-indexed)
+    first: usize,
+    /// Second workspace index on the monitor (zero-indexed)
+    second: usize,
+}
+
 #[derive(Parser)]
 pub struct SendToMonitorWorkspace {
     /// Target monitor index (zero-indexed)
@@ -755,6 +820,12 @@ struct AnimationFps {
     fps: u64,
 }
 
+#[derive(Parser)]
+struct AnimationMaxConcurrent {
+    /// Maximum number of animations which can run at the same time
+    max: usize,
+}
+
 #[derive(Parser)]
 struct AnimationStyle {
     /// Desired ease function for animation
@@ -834,6 +905,23 @@ struct Kill {
     masir: bool,
 }
 
+#[derive(Parser)]
+struct MonitorInformation {
+    /// Pin the current monitor indices to the connected displays so that they stay stable across reboots and cable swaps
+    #[clap(long)]
+    persist: bool,
+}
+
+#[derive(Parser)]
+struct Reserve {
+    /// Executable of the application to reserve a tile for (e.g. spotify.exe)
+    #[clap(long)]
+    exe: String,
+    /// Reserve the tile at the position of the focused container instead of at the end of the layout
+    #[clap(long)]
+    here: bool,
+}
+
 #[derive(Parser)]
 struct SaveResize {
     /// File to which the resize layout dimensions should be saved
@@ -848,6 +936,20 @@ struct LoadResize {
     path: PathBuf,
 }
 
+#[derive(Parser)]
+struct SaveSession {
+    /// File to which the workspace assignments of all managed windows should be saved
+    #[clap(value_parser = replace_env_in_path)]
+    path: PathBuf,
+}
+
+#[derive(Parser)]
+struct LoadSession {
+    /// File from which the workspace assignments of managed windows should be restored
+    #[clap(value_parser = replace_env_in_path)]
+    path: PathBuf,
+}
+
 #[derive(Parser)]
 struct LoadCustomLayout {
     /// JSON or YAML file from which the custom layout definition should be loaded
@@ -859,6 +961,15 @@ struct LoadCustomLayout {
 struct SubscribeSocket {
     /// Name of the socket to send event notifications to
     socket: String,
+    /// Only send notifications for these kinds of events (comma-separated)
+    #[clap(long, value_enum, value_delimiter = ',')]
+    events: Vec<SubscriptionEvent>,
+}
+
+#[derive(Parser)]
+struct SubscribeSocketDiff {
+    /// Name of the socket to send state diff notifications to
+    socket: String,
 }
 
 #[derive(Parser)]
@@ -871,6 +982,9 @@ struct UnsubscribeSocket {
 struct SubscribePipe {
     /// Name of the pipe to send event notifications to (without "\\.\pipe\" prepended)
     named_pipe: String,
+    /// Only send notifications for these kinds of events (comma-separated)
+    #[clap(long, value_enum, value_delimiter = ',')]
+    events: Vec<SubscriptionEvent>,
 }
 
 #[derive(Parser)]
@@ -919,6 +1033,27 @@ struct AltFocusHack {
     boolean_state: BooleanState,
 }
 
+#[derive(Parser)]
+struct ReportBug {
+    /// Number of state snapshots to capture, one second apart
+    #[clap(long, default_value = "3")]
+    snapshots: usize,
+    /// Number of lines to include from the end of today's log
+    #[clap(long, default_value = "500")]
+    log_lines: usize,
+    /// Replace window titles in the state snapshots and the log with a placeholder
+    #[clap(long)]
+    redact_titles: bool,
+    /// Directory to write the zip file to (default: the current directory)
+    #[clap(long)]
+    output: Option<PathBuf>,
+    /// Path to the static configuration JSON file to include (default: the file komorebi was
+    /// started with)
+    #[clap(long)]
+    #[clap(value_parser = replace_env_in_path)]
+    config: Option<PathBuf>,
+}
+
 #[derive(Parser)]
 struct EnableAutostart {
     /// Path to a static configuration JSON file
@@ -964,6 +1099,24 @@ struct EagerFocus {
     exe: String,
 }
 
+#[derive(Parser)]
+struct Mark {
+    /// Name of the mark to assign to the focused window
+    mark: String,
+}
+
+#[derive(Parser)]
+struct JumpToMark {
+    /// Name of the mark to jump to
+    mark: String,
+}
+
+#[derive(Parser)]
+struct Unmark {
+    /// Name of the mark to clear
+    mark: String,
+}
+
 #[derive(Parser)]
 struct ScrollingLayoutColumns {
     /// Desired number of visible columns
@@ -973,6 +1126,9 @@ struct ScrollingLayoutColumns {
 #[derive(Parser)]
 #[clap(author, about, version = build::CLAP_LONG_VERSION)]
 struct Opts {
+    /// Don't move the mouse cursor to the window focused by this command
+    #[clap(long, global = true)]
+    no_mouse_follows_focus: bool,
     #[clap(subcommand)]
     subcmd: SubCommand,
 }
@@ -994,6 +1150,9 @@ enum SubCommand {
     /// Show the path to komorebi.json
     #[clap(alias = "config")]
     Configuration,
+    /// Show the path to the configuration the running komorebi process was loaded with and when
+    #[clap(alias = "active-config")]
+    ActiveConfiguration,
     /// Show the path to komorebi.bar.json
     #[clap(alias = "bar-config")]
     #[clap(alias = "bconfig")]
@@ -1008,6 +1167,8 @@ enum SubCommand {
     State,
     /// Show a JSON representation of the current global state
     GlobalState,
+    /// Check that komorebi is running, showing its version, uptime and process id
+    Ping,
     /// Launch the komorebi-gui debugging tool
     Gui,
     /// Toggle the komorebi-shortcuts helper
@@ -1016,13 +1177,17 @@ enum SubCommand {
     VisibleWindows,
     /// Show information about connected monitors
     #[clap(alias = "monitor-info")]
-    MonitorInformation,
+    #[clap(alias = "monitors")]
+    MonitorInformation(MonitorInformation),
     /// Query the current window manager state
     #[clap(arg_required_else_help = true)]
     Query(Query),
     /// Subscribe to komorebi events using a Unix Domain Socket
     #[clap(arg_required_else_help = true)]
     SubscribeSocket(SubscribeSocket),
+    /// Subscribe to diffs of the komorebi state using a Unix Domain Socket
+    #[clap(arg_required_else_help = true)]
+    SubscribeSocketDiff(SubscribeSocketDiff),
     /// Unsubscribe from komorebi events
     #[clap(arg_required_else_help = true)]
     UnsubscribeSocket(UnsubscribeSocket),
@@ -1036,6 +1201,11 @@ enum SubCommand {
     UnsubscribePipe(UnsubscribePipe),
     /// Tail komorebi.exe's process logs (cancel with Ctrl-C)
     Log,
+    /// Gather version information, configuration files, monitor information, recent logs and
+    /// state snapshots into a zip file to attach to bug reports
+    ReportBug(ReportBug),
+    /// Interactive terminal UI showing the live monitors, workspaces and windows with an event feed
+    Tui,
     /// Quicksave the current resize layout dimensions
     #[clap(alias = "quick-save")]
     QuickSaveResize,
@@ -1050,6 +1220,12 @@ enum SubCommand {
     #[clap(arg_required_else_help = true)]
     #[clap(alias = "load")]
     LoadResize(LoadResize),
+    /// Save the workspace assignments of all managed windows to a session file
+    #[clap(arg_required_else_help = true)]
+    SaveSession(SaveSession),
+    /// Restore managed windows to the workspaces recorded in a session file
+    #[clap(arg_required_else_help = true)]
+    LoadSession(LoadSession),
     /// Change focus to the window in the specified direction
     #[clap(arg_required_else_help = true)]
     Focus(Focus),
@@ -1060,8 +1236,12 @@ enum SubCommand {
     Minimize,
     /// Close the focused window
     Close,
+    /// Show the windows hidden from the focused workspace by minimize to tray rules
+    RestoreHidden,
     /// Forcibly focus the window at the cursor with a left mouse click
     ForceFocus,
+    /// Focus the previously focused window on the focused workspace
+    FocusLastWindow,
     /// Change focus to the window in the specified cycle direction
     #[clap(arg_required_else_help = true)]
     CycleFocus(CycleFocus),
@@ -1071,6 +1251,15 @@ enum SubCommand {
     /// Focus the first managed window matching the given exe
     #[clap(arg_required_else_help = true)]
     EagerFocus(EagerFocus),
+    /// Assign a mark to the focused window
+    #[clap(arg_required_else_help = true)]
+    Mark(Mark),
+    /// Focus the window with the given mark, switching monitor and workspace if necessary
+    #[clap(arg_required_else_help = true)]
+    JumpToMark(JumpToMark),
+    /// Clear the given mark
+    #[clap(arg_required_else_help = true)]
+    Unmark(Unmark),
     /// Stack the focused window in the specified direction
     #[clap(arg_required_else_help = true)]
     Stack(Stack),
@@ -1096,6 +1285,15 @@ enum SubCommand {
     /// Resize the focused window or primary column along the specified axis
     #[clap(arg_required_else_help = true)]
     ResizeAxis(ResizeAxis),
+    /// Resize the focused window or primary column along the specified axis by a percentage of the work area
+    #[clap(arg_required_else_help = true)]
+    ResizeAxisPercent(ResizeAxisPercent),
+    /// Move the edge of the focused window in the specified direction by adjusting only the BSP split it belongs to
+    #[clap(arg_required_else_help = true)]
+    ResizeSplit(ResizeSplit),
+    /// Choose the direction the focused window is split in when the next window is added (Manual layout)
+    #[clap(arg_required_else_help = true)]
+    Split(Split),
     /// Move the focused window to the specified monitor
     #[clap(arg_required_else_help = true)]
     MoveToMonitor(MoveToMonitor),
@@ -1175,8 +1373,19 @@ enum SubCommand {
     /// Swap focused monitor workspaces with specified monitor
     #[clap(arg_required_else_help = true)]
     SwapWorkspacesWithMonitor(SwapWorkspacesWithMonitor),
+    /// Create a new workspace on the specified monitor with the layout, padding and rules of the focused workspace
+    #[clap(arg_required_else_help = true)]
+    CloneWorkspace(CloneWorkspace),
+    /// Swap the positions of two workspaces on the specified monitor
+    #[clap(arg_required_else_help = true)]
+    SwapWorkspaces(SwapWorkspaces),
     /// Create and append a new workspace on the focused monitor
     NewWorkspace,
+    /// Reserve a tile on the focused workspace for an application which hasn't opened a window yet
+    #[clap(arg_required_else_help = true)]
+    Reserve(Reserve),
+    /// Remove all reserved tiles from the focused workspace
+    ClearReservedTiles,
     /// Set the resize delta (used by resize-edge and resize-axis)
     #[clap(arg_required_else_help = true)]
     ResizeDelta(ResizeDelta),
@@ -1191,6 +1400,11 @@ enum SubCommand {
     MonitorWorkAreaOffset(MonitorWorkAreaOffset),
     /// Toggle application of the window-based work area offset for the focused workspace
     ToggleWindowBasedWorkAreaOffset,
+    /// Toggle hiding all komorebi-bar instances and releasing the work area offsets they have set
+    ToggleBar,
+    /// Turn privacy mode on or off to hide window titles and privacy sensitive applications while screen sharing
+    #[clap(arg_required_else_help = true)]
+    PrivacyMode(PrivacyMode),
     /// Set container padding on the focused workspace
     #[clap(arg_required_else_help = true)]
     FocusedWorkspaceContainerPadding(FocusedWorkspaceContainerPadding),
@@ -1225,8 +1439,8 @@ enum SubCommand {
     PromoteFocus,
     /// Promote the window in the specified direction
     PromoteWindow(PromoteWindow),
-    /// Force the retiling of all managed windows
-    Retile,
+    /// Force the retiling of all managed windows, or only those on the given monitor or workspace
+    Retile(Retile),
     /// Set the monitor index preference for a monitor identified using its size
     #[clap(arg_required_else_help = true)]
     MonitorIndexPreference(MonitorIndexPreference),
@@ -1306,6 +1520,9 @@ enum SubCommand {
     /// mode, for the currently focused workspace. If there was no override value set for the
     /// workspace previously it takes the opposite of the global value.
     ToggleWorkspaceFloatOverride,
+    /// Toggle smart gaps, which remove container and workspace padding when only a single
+    /// container is tiled, for the currently focused workspace
+    ToggleSmartGaps,
     /// Toggle between the Tiling and Floating layers on the focused workspace
     ToggleWorkspaceLayer,
     /// Toggle window tiling on the focused workspace
@@ -1314,10 +1531,15 @@ enum SubCommand {
     ToggleTiling,
     /// Toggle floating mode for the focused window
     ToggleFloat,
+    /// Toggle pinning the focused window above the tiled windows of every workspace on the focused monitor
+    ToggleAlwaysOnTop,
     /// Toggle monocle mode for the focused container
     ToggleMonocle,
     /// Toggle native maximization for the focused window
     ToggleMaximize,
+    /// Toggle a temporary zoom of the focused window over the whole workspace, keeping the layout
+    /// of the other windows underneath it
+    ToggleZoom,
     /// Toggle a lock for the focused container, ensuring it will not be displaced by any new windows
     ToggleLock,
     /// Restore all hidden windows (debugging command)
@@ -1446,6 +1668,9 @@ enum SubCommand {
     /// Set the frames per second for movement animations
     #[clap(arg_required_else_help = true)]
     AnimationFps(AnimationFps),
+    /// Set the maximum number of animations which can run at the same time
+    #[clap(arg_required_else_help = true)]
+    AnimationMaxConcurrent(AnimationMaxConcurrent),
     /// Set the ease function for movement animations
     #[clap(arg_required_else_help = true)]
     AnimationStyle(AnimationStyle),
@@ -1491,6 +1716,8 @@ enum SubCommand {
     SocketSchema,
     /// Generate a JSON Schema of the static configuration file
     StaticConfigSchema,
+    /// Generate a JSON Schema of the komorebi-bar configuration file
+    BarSchema,
     /// Generates a static configuration JSON file based on the current window manager state
     GenerateStaticConfig,
     /// Generates the komorebi.lnk shortcut in shell:startup to autostart komorebi
@@ -1499,6 +1726,16 @@ enum SubCommand {
     DisableAutostart,
 }
 
+fn send_message(message: &SocketMessage) -> komorebi_client::Result<()> {
+    if NO_MOUSE_FOLLOWS_FOCUS.load(Ordering::SeqCst) {
+        komorebi_client::send_message(&SocketMessage::NoMouseFollowsFocus(Box::new(
+            message.clone(),
+        )))
+    } else {
+        komorebi_client::send_message(message)
+    }
+}
+
 // print_query is a helper that queries komorebi and prints the response.
 // panics on error.
 fn print_query(message: &SocketMessage) {
@@ -1508,6 +1745,188 @@ fn print_query(message: &SocketMessage) {
     }
 }
 
+const REDACTED_TITLE: &str = "<redacted title>";
+
+/// Replaces every `title` string in `value` with a placeholder, collecting the original titles
+fn redact_titles(value: &mut serde_json::Value, titles: &mut Vec<String>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                match value {
+                    serde_json::Value::String(title) if key == "title" => {
+                        if !title.is_empty() && !titles.contains(title) {
+                            titles.push(title.clone());
+                        }
+
+                        *title = String::from(REDACTED_TITLE);
+                    }
+                    _ => redact_titles(value, titles),
+                }
+            }
+        }
+        serde_json::Value::Array(values) => {
+            for value in values {
+                redact_titles(value, titles);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Replaces the user's home directory and user name in `contents` with placeholders
+fn sanitize(contents: &str) -> String {
+    let mut sanitized = contents.to_string();
+
+    if let Some(home) = dirs::home_dir() {
+        for home in [
+            home.display().to_string(),
+            home.display().to_string().replace('\\', "\\\\"),
+            home.display().to_string().replace('\\', "/"),
+        ] {
+            sanitized = sanitized.replace(&home, "$Env:USERPROFILE");
+        }
+    }
+
+    if let Ok(user) = std::env::var("USERNAME") {
+        if !user.is_empty() {
+            sanitized = sanitize_user(&sanitized, &user);
+        }
+    }
+
+    sanitized
+}
+
+/// Replaces `user` wherever it is the name of a directory under a `Users` directory in a path,
+/// leaving any other occurrence of the same word alone
+fn sanitize_user(contents: &str, user: &str) -> String {
+    let mut sanitized = contents.to_string();
+
+    for separator in ["\\\\", "\\", "/"] {
+        let needle = format!("Users{separator}{user}");
+        let mut replaced = String::with_capacity(sanitized.len());
+        let mut rest = sanitized.as_str();
+
+        while let Some(idx) = rest.find(&needle) {
+            let end = idx + needle.len();
+            let is_component = !rest[end..]
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'));
+
+            replaced.push_str(&rest[..idx]);
+            if is_component {
+                replaced.push_str(&format!("Users{separator}<user>"));
+            } else {
+                replaced.push_str(&needle);
+            }
+
+            rest = &rest[end..];
+        }
+
+        replaced.push_str(rest);
+        sanitized = replaced;
+    }
+
+    sanitized
+}
+
+fn report_bug(args: &ReportBug) -> Result<PathBuf> {
+    let timestamp = Local::now().format("%Y-%m-%d-%H%M%S").to_string();
+    let name = format!("komorebi-bug-report-{timestamp}");
+    let staging = std::env::temp_dir().join(&name);
+    std::fs::create_dir_all(&staging)?;
+
+    let mut version = format!("{}\n", build::CLAP_LONG_VERSION);
+    if let Some(os) = sysinfo::System::long_os_version() {
+        version.push_str(&format!("os: {os}\n"));
+    }
+    std::fs::write(staging.join("version.txt"), version)?;
+
+    // komorebi may have been started with a configuration file other than komorebi.json
+    let configuration_path = args
+        .config
+        .clone()
+        .or_else(|| {
+            let state = send_query(&SocketMessage::State).ok()?;
+            let state = serde_json::from_str::<serde_json::Value>(&state).ok()?;
+            state.get("configuration_path")?.as_str().map(PathBuf::from)
+        })
+        .unwrap_or_else(|| HOME_DIR.join("komorebi.json"));
+
+    for config in [
+        configuration_path,
+        HOME_DIR.join("komorebi.bar.json"),
+        WHKD_CONFIG_DIR.join("whkdrc"),
+    ] {
+        if let (Some(file_name), Ok(contents)) =
+            (config.file_name(), std::fs::read_to_string(&config))
+        {
+            std::fs::write(staging.join(file_name), sanitize(&contents))?;
+        }
+    }
+
+    // komorebi may not be running, in which case the reason is recorded instead
+    let monitors = send_query(&SocketMessage::MonitorInformation)
+        .unwrap_or_else(|error| format!("could not query monitor information: {error}"));
+    std::fs::write(staging.join("monitors.json"), sanitize(&monitors))?;
+
+    let mut titles = vec![];
+    for i in 0..args.snapshots {
+        if i > 0 {
+            std::thread::sleep(Duration::from_secs(1));
+        }
+
+        let mut state = send_query(&SocketMessage::State)
+            .unwrap_or_else(|error| format!("could not query state: {error}"));
+
+        if args.redact_titles {
+            if let Ok(mut value) = serde_json::from_str::<serde_json::Value>(&state) {
+                redact_titles(&mut value, &mut titles);
+                state = serde_json::to_string_pretty(&value)?;
+            }
+        }
+
+        std::fs::write(staging.join(format!("state-{i}.json")), sanitize(&state))?;
+    }
+
+    // longer titles first so that titles which contain other titles are fully redacted
+    titles.sort_by_key(|title| std::cmp::Reverse(title.len()));
+
+    let log = std::env::temp_dir().join(format!("komorebi.log.{}", Utc::now().format("%Y-%m-%d")));
+
+    if let Ok(contents) = std::fs::read_to_string(log) {
+        let lines = contents.lines().collect::<Vec<_>>();
+        let mut excerpt = lines[lines.len().saturating_sub(args.log_lines)..].join("\n");
+
+        for title in &titles {
+            excerpt = excerpt.replace(title, REDACTED_TITLE);
+        }
+
+        std::fs::write(staging.join("komorebi.log"), sanitize(&excerpt))?;
+    }
+
+    let output = match &args.output {
+        Some(output) => output.clone(),
+        None => std::env::current_dir()?,
+    };
+    let zip = output.join(format!("{name}.zip"));
+
+    let script = format!(
+        "Compress-Archive -Path '{}\\*' -DestinationPath '{}' -Force",
+        staging.display(),
+        zip.display()
+    );
+
+    let result = powershell_script::run(&script);
+    std::fs::remove_dir_all(&staging)?;
+
+    if let Err(error) = result {
+        bail!("could not create {}: {error}", zip.display());
+    }
+
+    Ok(zip)
+}
+
 fn startup_dir() -> Result<PathBuf> {
     let startup = dirs::home_dir()
         .expect("unable to obtain user's home folder")
@@ -1529,6 +1948,7 @@ fn startup_dir() -> Result<PathBuf> {
 #[allow(clippy::too_many_lines, clippy::cognitive_complexity)]
 fn main() -> Result<()> {
     let opts: Opts = Opts::parse();
+    NO_MOUSE_FOLLOWS_FOCUS.store(opts.no_mouse_follows_focus, Ordering::SeqCst);
 
     match opts.subcmd {
         SubCommand::Docgen => {
@@ -1776,6 +2196,26 @@ fn main() -> Result<()> {
                 println!("{}", static_config.display());
             }
         }
+        SubCommand::ActiveConfiguration => {
+            let state: State = serde_json::from_str(&send_query(&SocketMessage::State)?)?;
+
+            match state.configuration_path {
+                Some(path) => {
+                    println!("{}", path.display());
+
+                    if let Some(loaded_at) = state
+                        .configuration_loaded_at
+                        .and_then(|secs| DateTime::from_timestamp(secs as i64, 0))
+                    {
+                        println!(
+                            "loaded at {}",
+                            loaded_at.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S")
+                        );
+                    }
+                }
+                None => println!("komorebi is not running with a static configuration file"),
+            }
+        }
         SubCommand::BarConfiguration => {
             let static_config = HOME_DIR.join("komorebi.bar.json");
 
@@ -1806,18 +2246,36 @@ fn main() -> Result<()> {
                 println!("{line}");
             }
         }
+        SubCommand::Tui => {
+            tui::run()?;
+        }
+        SubCommand::ReportBug(args) => {
+            let zip = report_bug(&args)?;
+
+            println!("Bug report written to {}", zip.display());
+            println!("Please check its contents before attaching it to an issue");
+            if !args.redact_titles {
+                println!("Window titles were not redacted, use --redact-titles to redact them");
+            }
+        }
         SubCommand::Focus(arg) => {
             send_message(&SocketMessage::FocusWindow(arg.operation_direction))?;
         }
         SubCommand::ForceFocus => {
             send_message(&SocketMessage::ForceFocus)?;
         }
+        SubCommand::FocusLastWindow => {
+            send_message(&SocketMessage::FocusLastWindow)?;
+        }
         SubCommand::Close => {
             send_message(&SocketMessage::Close)?;
         }
         SubCommand::Minimize => {
             send_message(&SocketMessage::Minimize)?;
         }
+        SubCommand::RestoreHidden => {
+            send_message(&SocketMessage::RestoreHidden)?;
+        }
         SubCommand::Promote => {
             send_message(&SocketMessage::Promote)?;
         }
@@ -1830,8 +2288,17 @@ fn main() -> Result<()> {
         SubCommand::TogglePause => {
             send_message(&SocketMessage::TogglePause)?;
         }
-        SubCommand::Retile => {
-            send_message(&SocketMessage::Retile)?;
+        SubCommand::Retile(arg) => {
+            let message = match (arg.monitor, arg.workspace, arg.named_workspace) {
+                (_, _, Some(workspace)) => SocketMessage::RetileNamedWorkspace(workspace),
+                (Some(monitor), Some(workspace), None) => {
+                    SocketMessage::RetileWorkspace(monitor, workspace)
+                }
+                (Some(monitor), None, None) => SocketMessage::RetileMonitor(monitor),
+                (None, _, None) => SocketMessage::Retile,
+            };
+
+            send_message(&message)?;
         }
         SubCommand::Move(arg) => {
             send_message(&SocketMessage::MoveWindow(arg.operation_direction))?;
@@ -1845,6 +2312,15 @@ fn main() -> Result<()> {
         SubCommand::EagerFocus(arg) => {
             send_message(&SocketMessage::EagerFocus(arg.exe))?;
         }
+        SubCommand::Mark(arg) => {
+            send_message(&SocketMessage::Mark(arg.mark))?;
+        }
+        SubCommand::JumpToMark(arg) => {
+            send_message(&SocketMessage::JumpToMark(arg.mark))?;
+        }
+        SubCommand::Unmark(arg) => {
+            send_message(&SocketMessage::Unmark(arg.mark))?;
+        }
         SubCommand::MoveToMonitor(arg) => {
             send_message(&SocketMessage::MoveContainerToMonitorNumber(arg.target))?;
         }
@@ -1912,6 +2388,16 @@ fn main() -> Result<()> {
         SubCommand::SwapWorkspacesWithMonitor(arg) => {
             send_message(&SocketMessage::SwapWorkspacesToMonitorNumber(arg.target))?;
         }
+        SubCommand::CloneWorkspace(arg) => {
+            send_message(&SocketMessage::CloneWorkspaceToMonitorNumber(arg.to))?;
+        }
+        SubCommand::SwapWorkspaces(arg) => {
+            send_message(&SocketMessage::SwapWorkspaces(
+                arg.monitor,
+                arg.first,
+                arg.second,
+            ))?;
+        }
         SubCommand::InvisibleBorders(arg) => {
             send_message(&SocketMessage::InvisibleBorders(Rect {
                 left: arg.left,
@@ -1942,6 +2428,12 @@ fn main() -> Result<()> {
         SubCommand::ToggleWindowBasedWorkAreaOffset => {
             send_message(&SocketMessage::ToggleWindowBasedWorkAreaOffset)?;
         }
+        SubCommand::ToggleBar => {
+            send_message(&SocketMessage::ToggleBar)?;
+        }
+        SubCommand::PrivacyMode(arg) => {
+            send_message(&SocketMessage::PrivacyMode(arg.boolean_state.into()))?;
+        }
         SubCommand::ContainerPadding(arg) => {
             send_message(&SocketMessage::ContainerPadding(
                 arg.monitor,
@@ -1995,12 +2487,18 @@ fn main() -> Result<()> {
         SubCommand::ToggleFloat => {
             send_message(&SocketMessage::ToggleFloat)?;
         }
+        SubCommand::ToggleAlwaysOnTop => {
+            send_message(&SocketMessage::ToggleAlwaysOnTop)?;
+        }
         SubCommand::ToggleMonocle => {
             send_message(&SocketMessage::ToggleMonocle)?;
         }
         SubCommand::ToggleMaximize => {
             send_message(&SocketMessage::ToggleMaximize)?;
         }
+        SubCommand::ToggleZoom => {
+            send_message(&SocketMessage::ToggleZoom)?;
+        }
         SubCommand::ToggleLock => {
             send_message(&SocketMessage::ToggleLock)?;
         }
@@ -2685,6 +3183,12 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
         SubCommand::NewWorkspace => {
             send_message(&SocketMessage::NewWorkspace)?;
         }
+        SubCommand::Reserve(arg) => {
+            send_message(&SocketMessage::ReserveTile(arg.exe, arg.here))?;
+        }
+        SubCommand::ClearReservedTiles => {
+            send_message(&SocketMessage::ClearReservedTiles)?;
+        }
         SubCommand::WorkspaceName(name) => {
             send_message(&SocketMessage::WorkspaceName(
                 name.monitor,
@@ -2725,6 +3229,9 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
         SubCommand::GlobalState => {
             print_query(&SocketMessage::GlobalState);
         }
+        SubCommand::Ping => {
+            print_query(&SocketMessage::Ping);
+        }
         SubCommand::Gui => {
             Command::new("komorebi-gui").spawn()?;
         }
@@ -2740,7 +3247,11 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
         SubCommand::VisibleWindows => {
             print_query(&SocketMessage::VisibleWindows);
         }
-        SubCommand::MonitorInformation => {
+        SubCommand::MonitorInformation(arg) => {
+            if arg.persist {
+                send_message(&SocketMessage::PersistMonitorIndices)?;
+            }
+
             print_query(&SocketMessage::MonitorInformation);
         }
         SubCommand::Query(arg) => {
@@ -2763,6 +3274,15 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
         SubCommand::ResizeAxis(arg) => {
             send_message(&SocketMessage::ResizeWindowAxis(arg.axis, arg.sizing))?;
         }
+        SubCommand::ResizeAxisPercent(arg) => {
+            send_message(&SocketMessage::ResizeWindowPercent(arg.axis, arg.percent))?;
+        }
+        SubCommand::ResizeSplit(arg) => {
+            send_message(&SocketMessage::ResizeSplit(arg.direction, arg.delta))?;
+        }
+        SubCommand::Split(arg) => {
+            send_message(&SocketMessage::Split(arg.direction))?;
+        }
         SubCommand::FocusFollowsMouse(arg) => {
             send_message(&SocketMessage::FocusFollowsMouse(
                 arg.implementation,
@@ -2830,14 +3350,43 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
         SubCommand::LoadResize(arg) => {
             send_message(&SocketMessage::Load(arg.path))?;
         }
+        SubCommand::SaveSession(arg) => {
+            send_message(&SocketMessage::SaveSession(arg.path))?;
+        }
+        SubCommand::LoadSession(arg) => {
+            send_message(&SocketMessage::LoadSession(arg.path))?;
+        }
         SubCommand::SubscribeSocket(arg) => {
-            send_message(&SocketMessage::AddSubscriberSocket(arg.socket))?;
+            if arg.events.is_empty() {
+                send_message(&SocketMessage::AddSubscriberSocket(arg.socket))?;
+            } else {
+                send_message(&SocketMessage::AddSubscriberSocketWithOptions(
+                    arg.socket,
+                    SubscribeOptions {
+                        filter_state_changes: false,
+                        events: Some(arg.events),
+                    },
+                ))?;
+            }
+        }
+        SubCommand::SubscribeSocketDiff(arg) => {
+            send_message(&SocketMessage::SubscribeSocketDiff(arg.socket))?;
         }
         SubCommand::UnsubscribeSocket(arg) => {
             send_message(&SocketMessage::RemoveSubscriberSocket(arg.socket))?;
         }
         SubCommand::SubscribePipe(arg) => {
-            send_message(&SocketMessage::AddSubscriberPipe(arg.named_pipe))?;
+            if arg.events.is_empty() {
+                send_message(&SocketMessage::AddSubscriberPipe(arg.named_pipe))?;
+            } else {
+                send_message(&SocketMessage::AddSubscriberPipeWithOptions(
+                    arg.named_pipe,
+                    SubscribeOptions {
+                        filter_state_changes: false,
+                        events: Some(arg.events),
+                    },
+                ))?;
+            }
         }
         SubCommand::UnsubscribePipe(arg) => {
             send_message(&SocketMessage::RemoveSubscriberPipe(arg.named_pipe))?;
@@ -2898,6 +3447,9 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
         SubCommand::AnimationFps(arg) => {
             send_message(&SocketMessage::AnimationFps(arg.fps))?;
         }
+        SubCommand::AnimationMaxConcurrent(arg) => {
+            send_message(&SocketMessage::AnimationMaxConcurrent(arg.max))?;
+        }
         SubCommand::AnimationStyle(arg) => {
             send_message(&SocketMessage::AnimationStyle(
                 arg.style,
@@ -2920,6 +3472,9 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
         SubCommand::ToggleWorkspaceFloatOverride => {
             send_message(&SocketMessage::ToggleWorkspaceFloatOverride)?;
         }
+        SubCommand::ToggleSmartGaps => {
+            send_message(&SocketMessage::ToggleSmartGaps)?;
+        }
         SubCommand::ToggleWorkspaceLayer => {
             send_message(&SocketMessage::ToggleWorkspaceLayer)?;
         }
@@ -3073,6 +3628,19 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
                 println!("{schema}");
             }
         }
+        SubCommand::BarSchema => {
+            // the bar configuration types live in the komorebi-bar binary
+            let output = Command::new("komorebi-bar.exe").arg("--schema").output()?;
+            if !output.status.success() {
+                bail!(
+                    "komorebi-bar.exe --schema failed: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
+            }
+
+            let stdout = String::from_utf8(output.stdout)?;
+            print!("{stdout}");
+        }
         SubCommand::GenerateStaticConfig => {
             print_query(&SocketMessage::GenerateStaticConfig);
         }
@@ -0,0 +1,350 @@
+use std::collections::VecDeque;
+use std::io::BufReader;
+use std::io::Read;
+use std::sync::mpsc;
+use std::sync::mpsc::Receiver;
+use std::time::Duration;
+
+use color_eyre::Result;
+use komorebi_client::send_batch;
+use komorebi_client::send_message;
+use komorebi_client::send_query;
+use komorebi_client::Container;
+use komorebi_client::Layout;
+use komorebi_client::Notification;
+use komorebi_client::NotificationEvent;
+use komorebi_client::OperationDirection;
+use komorebi_client::SocketMessage;
+use komorebi_client::State;
+use komorebi_client::Window;
+use ratatui::crossterm::event;
+use ratatui::crossterm::event::Event;
+use ratatui::crossterm::event::KeyCode;
+use ratatui::crossterm::event::KeyEvent;
+use ratatui::crossterm::event::KeyEventKind;
+use ratatui::crossterm::event::KeyModifiers;
+use ratatui::layout::Constraint;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::widgets::Block;
+use ratatui::widgets::List;
+use ratatui::widgets::ListItem;
+use ratatui::widgets::ListState;
+use ratatui::widgets::Paragraph;
+use ratatui::DefaultTerminal;
+use ratatui::Frame;
+
+const SUBSCRIBER_NAME: &str = "komorebic-tui.sock";
+const EVENT_FEED_LIMIT: usize = 100;
+const HELP: &str =
+    "↑/↓ select · enter focus · shift+←↑↓→ move window · t toggle float · r retile · q quit";
+
+/// What an action on a row of the tree applies to
+#[derive(Debug, Clone, Copy)]
+enum Target {
+    Monitor(usize),
+    Workspace(usize, usize),
+    Window(isize),
+}
+
+struct Row {
+    depth: usize,
+    label: String,
+    focused: bool,
+    target: Target,
+}
+
+#[derive(Default)]
+struct App {
+    rows: Vec<Row>,
+    list_state: ListState,
+    events: VecDeque<String>,
+    status: Option<String>,
+}
+
+fn window_label(window: &Window) -> String {
+    let title = window.title().unwrap_or_default();
+    let exe = window.exe().unwrap_or_default();
+
+    format!("{title} ({exe}) [{}]", window.hwnd)
+}
+
+fn event_label(event: &NotificationEvent) -> String {
+    match event {
+        NotificationEvent::WindowManager(event) => event.to_string(),
+        NotificationEvent::Socket(message) => {
+            serde_json::to_string(message).unwrap_or_else(|_| format!("{message:?}"))
+        }
+        NotificationEvent::Monitor(notification) => format!("{notification:?}"),
+        NotificationEvent::VirtualDesktop(notification) => format!("{notification:?}"),
+        NotificationEvent::WorkspaceSchedule(notification) => format!("{notification:?}"),
+        NotificationEvent::WindowEviction(eviction) => {
+            format!(
+                "WindowEviction ({}, {:?})",
+                eviction.exe, eviction.behaviour
+            )
+        }
+    }
+}
+
+impl App {
+    fn update_state(&mut self, state: &State) {
+        let mut rows = vec![];
+        let focused_monitor_idx = state.monitors.focused_idx();
+
+        for (monitor_idx, monitor) in state.monitors.elements().iter().enumerate() {
+            rows.push(Row {
+                depth: 0,
+                label: format!("Monitor {}: {}", monitor_idx + 1, monitor.name()),
+                focused: monitor_idx == focused_monitor_idx,
+                target: Target::Monitor(monitor_idx),
+            });
+
+            for (workspace_idx, workspace) in monitor.workspaces().iter().enumerate() {
+                let layout = if !*workspace.tile() {
+                    String::from("Floating")
+                } else {
+                    match workspace.layout() {
+                        Layout::Default(layout) => layout.to_string(),
+                        Layout::Custom(_) => String::from("Custom"),
+                    }
+                };
+
+                rows.push(Row {
+                    depth: 1,
+                    label: format!(
+                        "Workspace {} [{layout}]",
+                        workspace
+                            .name()
+                            .clone()
+                            .unwrap_or_else(|| format!("{}", workspace_idx + 1))
+                    ),
+                    focused: workspace_idx == monitor.focused_workspace_idx(),
+                    target: Target::Workspace(monitor_idx, workspace_idx),
+                });
+
+                let mut push_container = |container: &Container, prefix: &str, focused: bool| {
+                    for (window_idx, window) in container.windows().iter().enumerate() {
+                        rows.push(Row {
+                            depth: 2,
+                            label: format!("{prefix}{}", window_label(window)),
+                            focused: focused && window_idx == container.focused_window_idx(),
+                            target: Target::Window(window.hwnd),
+                        });
+                    }
+                };
+
+                if let Some(container) = workspace.monocle_container() {
+                    push_container(container, "[monocle] ", true);
+                }
+
+                for (container_idx, container) in workspace.containers().iter().enumerate() {
+                    let prefix = if container.windows().len() > 1 {
+                        format!("[stack {}] ", container_idx + 1)
+                    } else {
+                        String::new()
+                    };
+
+                    push_container(
+                        container,
+                        &prefix,
+                        workspace.monocle_container().is_none()
+                            && container_idx == workspace.focused_container_idx(),
+                    );
+                }
+
+                for window in workspace.floating_windows().iter() {
+                    rows.push(Row {
+                        depth: 2,
+                        label: format!("[floating] {}", window_label(window)),
+                        focused: window.is_focused(),
+                        target: Target::Window(window.hwnd),
+                    });
+                }
+            }
+        }
+
+        self.rows = rows;
+
+        match self.list_state.selected() {
+            Some(selected) if selected < self.rows.len() => {}
+            _ => self.list_state.select((!self.rows.is_empty()).then_some(0)),
+        }
+    }
+
+    fn push_event(&mut self, event: &NotificationEvent) {
+        self.events.push_front(event_label(event));
+        self.events.truncate(EVENT_FEED_LIMIT);
+    }
+
+    fn selected_target(&self) -> Option<Target> {
+        self.list_state
+            .selected()
+            .and_then(|selected| self.rows.get(selected))
+            .map(|row| row.target)
+    }
+
+    fn send(&mut self, messages: Vec<SocketMessage>) {
+        self.status = send_batch(messages)
+            .err()
+            .map(|error| format!("could not send command to komorebi: {error}"));
+    }
+
+    /// Runs `message` on the selected window after focusing it
+    fn on_selected_window(&mut self, message: SocketMessage) {
+        match self.selected_target() {
+            Some(Target::Window(hwnd)) => self.send(vec![SocketMessage::FocusHwnd(hwnd), message]),
+            _ => self.status = Some(String::from("select a window first")),
+        }
+    }
+
+    /// Handles a key press, returning true when the TUI should exit
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return true,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return true,
+            KeyCode::Left if shift => {
+                self.on_selected_window(SocketMessage::MoveWindow(OperationDirection::Left))
+            }
+            KeyCode::Right if shift => {
+                self.on_selected_window(SocketMessage::MoveWindow(OperationDirection::Right))
+            }
+            KeyCode::Up if shift => {
+                self.on_selected_window(SocketMessage::MoveWindow(OperationDirection::Up))
+            }
+            KeyCode::Down if shift => {
+                self.on_selected_window(SocketMessage::MoveWindow(OperationDirection::Down))
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.list_state.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => self.list_state.select_next(),
+            KeyCode::Home => self.list_state.select_first(),
+            KeyCode::End => self.list_state.select_last(),
+            KeyCode::Char('t') => self.on_selected_window(SocketMessage::ToggleFloat),
+            KeyCode::Char('r') => self.send(vec![SocketMessage::Retile]),
+            KeyCode::Enter | KeyCode::Char('f') => match self.selected_target() {
+                Some(Target::Monitor(monitor_idx)) => {
+                    self.send(vec![SocketMessage::FocusMonitorNumber(monitor_idx)])
+                }
+                Some(Target::Workspace(monitor_idx, workspace_idx)) => {
+                    self.send(vec![SocketMessage::FocusMonitorWorkspaceNumber(
+                        monitor_idx,
+                        workspace_idx,
+                    )])
+                }
+                Some(Target::Window(hwnd)) => self.send(vec![SocketMessage::FocusHwnd(hwnd)]),
+                None => {}
+            },
+            _ => {}
+        }
+
+        false
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [tree_area, events_area, help_area] = ratatui::layout::Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(12),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let items = self
+            .rows
+            .iter()
+            .map(|row| {
+                let item = ListItem::new(format!("{}{}", "  ".repeat(row.depth), row.label));
+                if row.focused {
+                    item.add_modifier(Modifier::BOLD).green()
+                } else {
+                    item
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let tree = List::new(items)
+            .block(Block::bordered().title(" komorebi "))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+
+        frame.render_stateful_widget(tree, tree_area, &mut self.list_state);
+
+        let events = self
+            .events
+            .iter()
+            .map(|event| ListItem::new(event.as_str()))
+            .collect::<Vec<_>>();
+
+        frame.render_widget(
+            List::new(events).block(Block::bordered().title(" events ")),
+            events_area,
+        );
+
+        let help = match &self.status {
+            Some(status) => Paragraph::new(status.as_str()).red(),
+            None => Paragraph::new(HELP).dim(),
+        };
+
+        frame.render_widget(help, help_area);
+    }
+
+    fn run(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        notifications: &Receiver<Notification>,
+    ) -> Result<()> {
+        loop {
+            while let Ok(notification) = notifications.try_recv() {
+                self.push_event(&notification.event);
+                self.update_state(&notification.state);
+            }
+
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if event::poll(Duration::from_millis(100))? {
+                if let Event::Key(key) = event::read()? {
+                    if key.kind == KeyEventKind::Press && self.handle_key(key) {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Runs an interactive terminal UI showing the live window manager state and event feed
+pub fn run() -> Result<()> {
+    let mut app = App::default();
+
+    let state = serde_json::from_str::<State>(&send_query(&SocketMessage::State)?)?;
+    app.update_state(&state);
+
+    let listener = komorebi_client::subscribe(SUBSCRIBER_NAME)?;
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        for client in listener.incoming().flatten() {
+            let mut buffer = Vec::new();
+            let mut reader = BufReader::new(client);
+
+            if reader.read_to_end(&mut buffer).is_ok() {
+                if let Ok(notification) = serde_json::from_slice::<Notification>(&buffer) {
+                    if tx.send(notification).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+
+    let mut terminal = ratatui::init();
+    let result = app.run(&mut terminal, &rx);
+    ratatui::restore();
+
+    send_message(&SocketMessage::RemoveSubscriberSocket(String::from(
+        SUBSCRIBER_NAME,
+    )))?;
+
+    result
+}